@@ -0,0 +1,344 @@
+//! Agent manifests for `am agents apply`.
+//!
+//! A manifest lists the agents a project should have. Applying it registers
+//! the missing ones, updates the ones whose program, model, task, or
+//! attachments policy drifted, and (with `--prune`) retires agents the
+//! manifest no longer lists. This module parses and validates manifests and
+//! decides what each entry needs; `handle_agents_apply` performs the writes.
+//!
+//! ```toml
+//! [[agents]]
+//! name = "BlueLake"
+//! program = "codex-cli"
+//! model = "gpt-5"
+//! task = "API migrations"
+//!
+//! [[agents.reservations]]
+//! path = "migrations/**"
+//! ttl_seconds = 7200
+//! ```
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use mcp_agent_mail_db::AgentRow;
+use serde::{Deserialize, Serialize};
+
+use crate::CliError;
+
+/// Attachment policies `register_agent` accepts.
+const ATTACHMENTS_POLICIES: &[&str] = &["auto", "inline", "file", "none"];
+
+const DEFAULT_ATTACHMENTS_POLICY: &str = "auto";
+
+/// TTL for reservation seeds that do not set one.
+const DEFAULT_SEED_TTL_SECONDS: i64 = 3600;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentManifest {
+    #[serde(default)]
+    pub agents: Vec<ManifestAgent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestAgent {
+    pub name: String,
+    pub program: String,
+    pub model: String,
+    #[serde(default)]
+    pub task: Option<String>,
+    #[serde(default)]
+    pub attachments_policy: Option<String>,
+    /// File reservations to hold for the agent; re-created when lapsed.
+    #[serde(default)]
+    pub reservations: Vec<ReservationSeed>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReservationSeed {
+    pub path: String,
+    #[serde(default = "default_seed_ttl_seconds")]
+    pub ttl_seconds: i64,
+    #[serde(default = "default_seed_exclusive")]
+    pub exclusive: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+const fn default_seed_ttl_seconds() -> i64 {
+    DEFAULT_SEED_TTL_SECONDS
+}
+
+const fn default_seed_exclusive() -> bool {
+    true
+}
+
+impl ManifestAgent {
+    /// Task as `register_agent` stores it: omitted means empty.
+    #[must_use]
+    pub fn task(&self) -> &str {
+        self.task.as_deref().map_or("", str::trim)
+    }
+
+    #[must_use]
+    pub fn attachments_policy(&self) -> &str {
+        self.attachments_policy
+            .as_deref()
+            .map_or(DEFAULT_ATTACHMENTS_POLICY, str::trim)
+    }
+}
+
+/// Parse a manifest, choosing JSON or TOML by file extension.
+pub fn parse_manifest(path: &Path, source: &str) -> Result<AgentManifest, CliError> {
+    let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let parsed = match ext.as_deref() {
+        Some("json") => serde_json::from_str(source).map_err(|err| err.to_string()),
+        Some("toml") => toml::from_str(source).map_err(|err| err.to_string()),
+        Some("yaml" | "yml") => {
+            return Err(CliError::InvalidArgument(format!(
+                "{}: YAML manifests are not supported; write the manifest as .toml or .json",
+                path.display()
+            )));
+        }
+        _ => serde_json::from_str(source)
+            .or_else(|_| toml::from_str(source))
+            .map_err(|err| err.to_string()),
+    };
+    parsed.map_err(|err| {
+        CliError::InvalidArgument(format!("invalid agent manifest {}: {err}", path.display()))
+    })
+}
+
+/// A manifest entry that failed validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestIssue {
+    /// Position in the manifest, from 1.
+    pub entry: usize,
+    pub name: String,
+    pub error: String,
+}
+
+impl std::fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "entry {} ({}): {}", self.entry, self.name, self.error)
+    }
+}
+
+/// Check every entry before anything is written. Returns the valid entries
+/// and one issue per invalid entry; an entry whose name repeats an earlier
+/// one (case-insensitively) is invalid.
+#[must_use]
+pub fn validate_manifest(manifest: AgentManifest) -> (Vec<ManifestAgent>, Vec<ManifestIssue>) {
+    let mut valid = Vec::with_capacity(manifest.agents.len());
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, mut agent) in manifest.agents.into_iter().enumerate() {
+        agent.name = agent.name.trim().to_string();
+        agent.program = agent.program.trim().to_string();
+        agent.model = agent.model.trim().to_string();
+        let first_seen = seen.insert(agent.name.to_ascii_lowercase());
+        match validate_agent(&agent, first_seen) {
+            Ok(()) => valid.push(agent),
+            Err(error) => issues.push(ManifestIssue {
+                entry: index + 1,
+                name: agent.name,
+                error,
+            }),
+        }
+    }
+    (valid, issues)
+}
+
+fn validate_agent(agent: &ManifestAgent, first_seen: bool) -> Result<(), String> {
+    if !mcp_agent_mail_core::models::is_valid_agent_name(&agent.name) {
+        return Err(format!(
+            "invalid agent name '{}': must be adjective+noun, e.g. BlueLake",
+            agent.name
+        ));
+    }
+    if let Some(pattern) = mcp_agent_mail_core::reserved_agent_name_pattern(&agent.name) {
+        return Err(format!(
+            "agent name '{}' is reserved (matches '{pattern}')",
+            agent.name
+        ));
+    }
+    if !first_seen {
+        return Err(format!("duplicate agent name '{}'", agent.name));
+    }
+    if agent.program.is_empty() {
+        return Err("program cannot be empty".to_string());
+    }
+    if agent.model.is_empty() {
+        return Err("model cannot be empty".to_string());
+    }
+    if !ATTACHMENTS_POLICIES.contains(&agent.attachments_policy()) {
+        return Err(format!(
+            "attachments_policy must be one of {}",
+            ATTACHMENTS_POLICIES.join(", ")
+        ));
+    }
+    for seed in &agent.reservations {
+        if seed.path.trim().is_empty() {
+            return Err("reservation path cannot be empty".to_string());
+        }
+        if seed.ttl_seconds <= 0 {
+            return Err(format!(
+                "reservation {}: ttl_seconds must be positive",
+                seed.path
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// What `agents apply` did (or, under `--dry-run`, would do) to one agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplyAction {
+    Created,
+    Updated,
+    Unchanged,
+    Retired,
+    /// Skipped under `--partial` because the entry failed validation.
+    Invalid,
+}
+
+impl ApplyAction {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Updated => "updated",
+            Self::Unchanged => "unchanged",
+            Self::Retired => "retired",
+            Self::Invalid => "invalid",
+        }
+    }
+}
+
+/// Fields of `existing` that differ from the manifest entry, plus
+/// `reinstated` when a retired agent is listed again. Empty when the
+/// registration is already up to date.
+#[must_use]
+pub fn registration_changes(
+    agent: &ManifestAgent,
+    existing: &AgentRow,
+    retired: bool,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    if existing.program != agent.program {
+        changes.push("program".to_string());
+    }
+    if existing.model != agent.model {
+        changes.push("model".to_string());
+    }
+    if existing.task_description != agent.task() {
+        changes.push("task".to_string());
+    }
+    if existing.attachments_policy != agent.attachments_policy() {
+        changes.push("attachments_policy".to_string());
+    }
+    if retired {
+        changes.push("reinstated".to_string());
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str) -> ManifestAgent {
+        ManifestAgent {
+            name: name.to_string(),
+            program: "codex-cli".to_string(),
+            model: "gpt-5".to_string(),
+            task: None,
+            attachments_policy: None,
+            reservations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_toml_and_json_manifests() {
+        let toml = r#"
+            [[agents]]
+            name = "BlueLake"
+            program = "codex-cli"
+            model = "gpt-5"
+            task = "API migrations"
+
+            [[agents.reservations]]
+            path = "migrations/**"
+            ttl_seconds = 7200
+        "#;
+        let manifest = parse_manifest(Path::new("agents.toml"), toml).unwrap();
+        assert_eq!(manifest.agents.len(), 1);
+        let seed = &manifest.agents[0].reservations[0];
+        assert_eq!(seed.path, "migrations/**");
+        assert_eq!(seed.ttl_seconds, 7200);
+        assert!(seed.exclusive);
+
+        let json = r#"{"agents": [{"name": "RedFox", "program": "claude-code", "model": "opus"}]}"#;
+        let manifest = parse_manifest(Path::new("agents.json"), json).unwrap();
+        assert_eq!(manifest.agents[0].name, "RedFox");
+        assert_eq!(manifest.agents[0].attachments_policy(), "auto");
+
+        assert!(parse_manifest(Path::new("agents.yaml"), "agents: []").is_err());
+        assert!(
+            parse_manifest(
+                Path::new("agents.json"),
+                r#"{"agents": [{"name": "RedFox", "program": "p", "model": "m", "colour": "red"}]}"#
+            )
+            .is_err(),
+            "unknown fields are rejected"
+        );
+    }
+
+    #[test]
+    fn validation_reports_every_bad_entry() {
+        let mut bad_policy = entry("GreenCastle");
+        bad_policy.attachments_policy = Some("always".to_string());
+        let manifest = AgentManifest {
+            agents: vec![
+                entry("BlueLake"),
+                entry("not a name"),
+                entry("bluelake"),
+                bad_policy,
+                entry("RedFox"),
+            ],
+        };
+        let (valid, issues) = validate_manifest(manifest);
+        let names: Vec<&str> = valid.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["BlueLake", "RedFox"]);
+        let entries: Vec<usize> = issues.iter().map(|i| i.entry).collect();
+        assert_eq!(entries, [2, 3, 4]);
+        assert!(issues[1].error.contains("duplicate"), "{:?}", issues[1]);
+    }
+
+    #[test]
+    fn registration_changes_lists_drifted_fields() {
+        let mut agent = entry("BlueLake");
+        let existing = AgentRow {
+            name: "BlueLake".to_string(),
+            program: "codex-cli".to_string(),
+            model: "gpt-5".to_string(),
+            attachments_policy: "auto".to_string(),
+            ..AgentRow::default()
+        };
+        assert!(registration_changes(&agent, &existing, false).is_empty());
+
+        agent.model = "gpt-5.1".to_string();
+        agent.task = Some("review".to_string());
+        assert_eq!(
+            registration_changes(&agent, &existing, true),
+            ["model", "task", "reinstated"]
+        );
+    }
+}
@@ -5,7 +5,9 @@
 
 #![forbid(unsafe_code)]
 
-use std::collections::BTreeMap;
+pub mod scenarios;
+
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
 use std::process::{Command, Stdio};
@@ -90,6 +92,8 @@ pub enum BenchCategory {
     Analysis,
     StubEncoder,
     Operational,
+    /// In-process handler runs against a seeded [`scenarios::BenchScale`] dataset.
+    Scenario,
 }
 
 /// Runtime condition required for a benchmark to be runnable.
@@ -742,6 +746,20 @@ pub fn run_timed(
     if command.is_empty() {
         return Err(BenchTimingError::EmptyCommand);
     }
+    time_iterations(warmup, runs, || run_once(command, env, working_dir))
+}
+
+/// Warmup+measurement loop shared by process and in-process benchmarks.
+///
+/// `invoke` returns `(exit_code, error, elapsed_us, success)` for one run.
+fn time_iterations<F>(
+    warmup: u32,
+    runs: u32,
+    mut invoke: F,
+) -> Result<TimingResult, BenchTimingError>
+where
+    F: FnMut() -> (Option<i32>, Option<String>, i64, bool),
+{
     if warmup == 0 {
         return Err(BenchTimingError::ZeroWarmup);
     }
@@ -756,7 +774,7 @@ pub fn run_timed(
     let mut measurement_failures = 0_u32;
 
     for iteration in 1..=warmup {
-        let (exit_code, error, elapsed_us, success) = run_once(command, env, working_dir);
+        let (exit_code, error, elapsed_us, success) = invoke();
         if !success {
             warmup_failures += 1;
             failures.push(TimingFailure {
@@ -770,7 +788,7 @@ pub fn run_timed(
     }
 
     for iteration in 1..=runs {
-        let (exit_code, error, elapsed_us, success) = run_once(command, env, working_dir);
+        let (exit_code, error, elapsed_us, success) = invoke();
         if success {
             samples_seconds.push(elapsed_us as f64 / 1_000_000.0);
             continue;
@@ -1252,6 +1270,98 @@ pub fn apply_baseline_comparison(
     }
 }
 
+/// How one benchmark moved between two saved baselines.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BaselineDiffStatus {
+    /// Present in both files and within the regression threshold.
+    Ok,
+    /// Present in both files and slower than the threshold allows.
+    Regressed,
+    /// Only present in the candidate file.
+    Added,
+    /// Only present in the base file.
+    Removed,
+}
+
+/// Per-benchmark row of a baseline-to-baseline comparison.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BaselineDiffEntry {
+    pub name: String,
+    pub status: BaselineDiffStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_p95_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidate_p95_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_p95_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_pct: Option<f64>,
+}
+
+/// Comparison of two saved baselines, aligned by benchmark name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BaselineDiff {
+    /// Regression threshold in percent (10.0 = 10%).
+    pub threshold_pct: f64,
+    pub regressions: usize,
+    pub added: usize,
+    pub removed: usize,
+    pub benchmarks: Vec<BaselineDiffEntry>,
+}
+
+/// Compare a `candidate` baseline against a `base` baseline without running
+/// anything. Benchmarks found in only one file are reported as added/removed.
+///
+/// `threshold_pct` is expressed as a ratio (0.10 = 10%).
+#[must_use]
+pub fn compare_baselines(
+    base: &BaselineData,
+    candidate: &BaselineData,
+    threshold_pct: f64,
+) -> BaselineDiff {
+    let names: BTreeSet<&String> = base.keys().chain(candidate.keys()).collect();
+    let benchmarks: Vec<BaselineDiffEntry> = names
+        .into_iter()
+        .map(|name| {
+            let base_p95_ms = base.get(name).copied();
+            let candidate_p95_ms = candidate.get(name).copied();
+            let (status, delta_p95_ms, delta_pct) = match (base_p95_ms, candidate_p95_ms) {
+                (Some(before), Some(after)) => {
+                    let delta = round_to(after - before, 2);
+                    let pct =
+                        (before > f64::EPSILON).then(|| round_to((delta / before) * 100.0, 2));
+                    let status = if baseline_regression(delta, before, threshold_pct) {
+                        BaselineDiffStatus::Regressed
+                    } else {
+                        BaselineDiffStatus::Ok
+                    };
+                    (status, Some(delta), pct)
+                }
+                (None, _) => (BaselineDiffStatus::Added, None, None),
+                (_, None) => (BaselineDiffStatus::Removed, None, None),
+            };
+            BaselineDiffEntry {
+                name: name.clone(),
+                status,
+                base_p95_ms,
+                candidate_p95_ms,
+                delta_p95_ms,
+                delta_pct,
+            }
+        })
+        .collect();
+    let count =
+        |status: BaselineDiffStatus| benchmarks.iter().filter(|b| b.status == status).count();
+    BaselineDiff {
+        threshold_pct: round_to(threshold_pct * 100.0, 2),
+        regressions: count(BaselineDiffStatus::Regressed),
+        added: count(BaselineDiffStatus::Added),
+        removed: count(BaselineDiffStatus::Removed),
+        benchmarks,
+    }
+}
+
 /// Benchmark host identity captured in summaries.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HardwareInfo {
@@ -1671,6 +1781,38 @@ mod tests {
         assert!(over.regression, "11% delta must exceed 10% threshold");
     }
 
+    #[test]
+    fn compare_baselines_aligns_by_name_and_reports_added_removed() {
+        let base = BaselineData::from([
+            ("help".to_string(), 10.0),
+            ("lint".to_string(), 20.0),
+            ("retired".to_string(), 5.0),
+        ]);
+        let candidate = BaselineData::from([
+            ("help".to_string(), 10.9),
+            ("lint".to_string(), 22.2),
+            ("fresh".to_string(), 1.0),
+        ]);
+
+        let diff = compare_baselines(&base, &candidate, 0.10);
+        assert_eq!(diff.threshold_pct, 10.0);
+        assert_eq!((diff.regressions, diff.added, diff.removed), (1, 1, 1));
+        let status = |name: &str| {
+            diff.benchmarks
+                .iter()
+                .find(|entry| entry.name == name)
+                .map(|entry| entry.status)
+        };
+        assert_eq!(status("help"), Some(BaselineDiffStatus::Ok));
+        assert_eq!(status("lint"), Some(BaselineDiffStatus::Regressed));
+        assert_eq!(status("fresh"), Some(BaselineDiffStatus::Added));
+        assert_eq!(status("retired"), Some(BaselineDiffStatus::Removed));
+        let lint = &diff.benchmarks[2];
+        assert_eq!(lint.name, "lint");
+        assert_eq!(lint.delta_p95_ms, Some(2.2));
+        assert_eq!(lint.delta_pct, Some(11.0));
+    }
+
     #[test]
     fn apply_baseline_comparison_updates_result_metadata() {
         let mut results = BTreeMap::new();
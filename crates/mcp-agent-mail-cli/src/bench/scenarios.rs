@@ -0,0 +1,615 @@
+//! DB-backed scenario benchmarks for `am bench`.
+//!
+//! The process benchmarks in [`super::DEFAULT_BENCHMARKS`] mostly measure CLI
+//! start-up against a 60-message fixture. Scenarios instead seed a dedicated
+//! database to a [`BenchScale`] (deterministic RNG, [`SCENARIO_SEED`]) and run
+//! the real command handlers in-process through [`CliRunner`], so the timings
+//! reflect query cost on realistic mailbox sizes:
+//!
+//! - `scenario_inbox_fetch_<scale>`: `mail inbox` over the seeded messages.
+//! - `scenario_conflict_check_<scale>`: `file_reservations conflicts` against
+//!   the active reservations.
+//! - `scenario_project_list_<scale>`: `list-projects` over the seeded projects.
+//!
+//! The scale is part of each benchmark name so baselines recorded at one
+//! scale are never compared against another.
+
+#![forbid(unsafe_code)]
+
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use clap::{Parser, ValueEnum};
+use mcp_agent_mail_core::test_harness::Rng64;
+use mcp_agent_mail_db::DbConn;
+use mcp_agent_mail_db::sqlmodel::Value;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    BenchCategory, BenchCondition, BenchConfig, BenchProfile, BenchSeedError, BenchTimingError,
+    BenchmarkDef, DEFAULT_RUNS, TimingResult, db_error, elapsed_us, time_iterations,
+};
+use crate::{CliError, CliRunner};
+
+/// Human key of the project that owns the scenario messages and reservations.
+pub const SCENARIO_PROJECT_HUMAN_KEY: &str = "/tmp/bench-scenario";
+/// Slug of the scenario project.
+pub const SCENARIO_PROJECT_SLUG: &str = "tmp-bench-scenario";
+/// Agent whose inbox receives every seeded message.
+pub const SCENARIO_RECIPIENT: &str = "BlueLake";
+/// RNG seed for scenario datasets; the same scale always seeds the same rows.
+pub const SCENARIO_SEED: u64 = 0x5eed_a6e7_3a11_0001;
+
+const SCENARIO_SENDERS: &[&str] = &[
+    "RedFox",
+    "GreenCastle",
+    "GoldHawk",
+    "SilverPond",
+    "AmberRidge",
+    "CopperCreek",
+    "JadeMeadow",
+];
+const SCENARIO_IMPORTANCE: &[&str] = &["low", "normal", "normal", "normal", "high", "urgent"];
+/// Crate directories the seeded reservation patterns are spread over.
+const SCENARIO_RESERVATION_CRATES: u32 = 64;
+/// Rows per multi-row `INSERT` while seeding.
+const SEED_BATCH_ROWS: usize = 100;
+const MICROS_PER_SECOND: i64 = 1_000_000;
+const RESERVATION_TTL_US: i64 = 30 * 24 * 60 * 60 * MICROS_PER_SECOND;
+
+/// Dataset size for scenario benchmarks (`am bench --scale`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchScale {
+    /// 1k messages, 100 reservations, 20 projects.
+    #[default]
+    Small,
+    /// 10k messages, 1k reservations, 100 projects.
+    Medium,
+    /// 100k messages, 5k reservations, 500 projects.
+    Large,
+}
+
+impl BenchScale {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Small => "small",
+            Self::Medium => "medium",
+            Self::Large => "large",
+        }
+    }
+
+    #[must_use]
+    pub const fn dataset(self) -> ScenarioDataset {
+        match self {
+            Self::Small => ScenarioDataset {
+                messages: 1_000,
+                reservations: 100,
+                projects: 20,
+            },
+            Self::Medium => ScenarioDataset {
+                messages: 10_000,
+                reservations: 1_000,
+                projects: 100,
+            },
+            Self::Large => ScenarioDataset {
+                messages: 100_000,
+                reservations: 5_000,
+                projects: 500,
+            },
+        }
+    }
+}
+
+/// Row counts seeded for one [`BenchScale`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScenarioDataset {
+    /// Messages addressed to [`SCENARIO_RECIPIENT`].
+    pub messages: u32,
+    /// Active file reservations in the scenario project.
+    pub reservations: u32,
+    /// Projects in total, including the scenario project.
+    pub projects: u32,
+}
+
+/// Structured diagnostics for scenario dataset seeding.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScenarioSeedReport {
+    pub scale: BenchScale,
+    pub seed: u64,
+    pub dataset: ScenarioDataset,
+    /// The database already held this dataset and was reused as-is.
+    pub skipped: bool,
+    pub elapsed_us: i64,
+}
+
+const CMD_INBOX_FETCH: &[&str] = &[
+    "mail",
+    "inbox",
+    "--project",
+    SCENARIO_PROJECT_HUMAN_KEY,
+    "--agent",
+    SCENARIO_RECIPIENT,
+    "--limit",
+    "50",
+    "--include-bodies",
+    "--json",
+];
+// Probe paths sit outside every seeded pattern, so the check scans all
+// active reservations and reports no conflicts.
+const CMD_CONFLICT_CHECK: &[&str] = &[
+    "file_reservations",
+    "conflicts",
+    SCENARIO_PROJECT_HUMAN_KEY,
+    "docs/bench/probe.md",
+    "tools/bench/probe.rs",
+];
+const CMD_PROJECT_LIST: &[&str] = &["list-projects", "--json"];
+
+/// Scenario catalog; names gain a `_<scale>` suffix in [`scenario_configs`].
+pub const SCENARIO_BENCHMARKS: &[BenchmarkDef] = &[
+    BenchmarkDef {
+        name: "scenario_inbox_fetch",
+        command: CMD_INBOX_FETCH,
+        category: BenchCategory::Scenario,
+        default_runs: DEFAULT_RUNS,
+        requires_seeded_db: false,
+        conditional: false,
+        condition: BenchCondition::Always,
+        env: &[],
+    },
+    BenchmarkDef {
+        name: "scenario_conflict_check",
+        command: CMD_CONFLICT_CHECK,
+        category: BenchCategory::Scenario,
+        default_runs: DEFAULT_RUNS,
+        requires_seeded_db: false,
+        conditional: false,
+        condition: BenchCondition::Always,
+        env: &[],
+    },
+    BenchmarkDef {
+        name: "scenario_project_list",
+        command: CMD_PROJECT_LIST,
+        category: BenchCategory::Scenario,
+        default_runs: DEFAULT_RUNS,
+        requires_seeded_db: false,
+        conditional: false,
+        condition: BenchCondition::Always,
+        env: &[],
+    },
+];
+
+/// Scenario benchmark configs for `profile`, named for `scale`.
+#[must_use]
+pub fn scenario_configs(profile: BenchProfile, scale: BenchScale) -> Vec<BenchConfig> {
+    SCENARIO_BENCHMARKS
+        .iter()
+        .map(|definition| {
+            let mut cfg = definition.to_config(profile);
+            cfg.name = format!("{}_{}", definition.name, scale.as_str());
+            cfg
+        })
+        .collect()
+}
+
+fn count_rows(conn: &DbConn, sql: &str) -> Result<i64, BenchSeedError> {
+    let rows = conn
+        .query_sync(sql, &[])
+        .map_err(|e| db_error("counting scenario rows", e))?;
+    Ok(rows
+        .first()
+        .and_then(|row| row.get_named("count").ok())
+        .unwrap_or(0))
+}
+
+/// Whether the database already holds exactly `dataset`.
+fn dataset_present(conn: &DbConn, dataset: ScenarioDataset) -> Result<bool, BenchSeedError> {
+    let project = conn
+        .query_sync(
+            "SELECT id FROM projects WHERE human_key = ? LIMIT 1",
+            &[Value::Text(SCENARIO_PROJECT_HUMAN_KEY.to_string())],
+        )
+        .map_err(|e| db_error("selecting scenario project", e))?;
+    if project.is_empty() {
+        return Ok(false);
+    }
+    Ok(
+        count_rows(conn, "SELECT COUNT(*) AS count FROM projects")? == i64::from(dataset.projects)
+            && count_rows(conn, "SELECT COUNT(*) AS count FROM messages")?
+                == i64::from(dataset.messages)
+            && count_rows(conn, "SELECT COUNT(*) AS count FROM file_reservations")?
+                == i64::from(dataset.reservations),
+    )
+}
+
+/// Insert `rows` into `table` (`columns` in order) with multi-row `INSERT`s.
+fn insert_batched(
+    conn: &DbConn,
+    table: &str,
+    columns: &[&str],
+    rows: &[Vec<Value>],
+    context: &'static str,
+) -> Result<(), BenchSeedError> {
+    let row_placeholders = format!("({})", vec!["?"; columns.len()].join(", "));
+    for chunk in rows.chunks(SEED_BATCH_ROWS) {
+        let values = vec![row_placeholders.as_str(); chunk.len()].join(", ");
+        let sql = format!(
+            "INSERT INTO {table} ({}) VALUES {values}",
+            columns.join(", ")
+        );
+        let params: Vec<Value> = chunk.iter().flatten().cloned().collect();
+        conn.execute_sync(&sql, &params)
+            .map_err(|e| db_error(context, e))?;
+    }
+    Ok(())
+}
+
+fn purge_scenario_rows(conn: &DbConn) -> Result<(), BenchSeedError> {
+    for table in [
+        "message_recipients",
+        "messages",
+        "file_reservation_releases",
+        "file_reservations",
+        "agents",
+        "projects",
+    ] {
+        conn.execute_sync(&format!("DELETE FROM {table}"), &[])
+            .map_err(|e| db_error("clearing scenario database", e))?;
+    }
+    Ok(())
+}
+
+fn insert_scenario_rows(
+    conn: &DbConn,
+    dataset: ScenarioDataset,
+    now_us: i64,
+) -> Result<(), BenchSeedError> {
+    let mut rng = Rng64::new(SCENARIO_SEED);
+
+    let mut projects = vec![vec![
+        Value::BigInt(1),
+        Value::Text(SCENARIO_PROJECT_SLUG.to_string()),
+        Value::Text(SCENARIO_PROJECT_HUMAN_KEY.to_string()),
+        Value::BigInt(now_us),
+    ]];
+    for idx in 2..=i64::from(dataset.projects) {
+        projects.push(vec![
+            Value::BigInt(idx),
+            Value::Text(format!("{SCENARIO_PROJECT_SLUG}-p{idx:04}")),
+            Value::Text(format!("{SCENARIO_PROJECT_HUMAN_KEY}-p{idx:04}")),
+            Value::BigInt(now_us - idx),
+        ]);
+    }
+    insert_batched(
+        conn,
+        "projects",
+        &["id", "slug", "human_key", "created_at"],
+        &projects,
+        "inserting scenario projects",
+    )?;
+
+    // Agent 1 is the recipient; senders follow in catalog order.
+    let agents: Vec<Vec<Value>> = std::iter::once(SCENARIO_RECIPIENT)
+        .chain(SCENARIO_SENDERS.iter().copied())
+        .zip(1_i64..)
+        .map(|(name, id)| {
+            vec![
+                Value::BigInt(id),
+                Value::BigInt(1),
+                Value::Text(name.to_string()),
+                Value::Text("bench".to_string()),
+                Value::Text("bench".to_string()),
+                Value::Text("scenario benchmark fixture".to_string()),
+                Value::BigInt(now_us),
+                Value::BigInt(now_us),
+            ]
+        })
+        .collect();
+    insert_batched(
+        conn,
+        "agents",
+        &[
+            "id",
+            "project_id",
+            "name",
+            "program",
+            "model",
+            "task_description",
+            "inception_ts",
+            "last_active_ts",
+        ],
+        &agents,
+        "inserting scenario agents",
+    )?;
+    let sender_ids: Vec<i64> = (2..=i64::try_from(agents.len()).unwrap_or(i64::MAX)).collect();
+
+    let message_count = i64::from(dataset.messages);
+    let threads = u64::from(dataset.messages / 8).max(1);
+    let first_ts = now_us - message_count * MICROS_PER_SECOND;
+    let capacity = usize::try_from(dataset.messages).unwrap_or(0);
+    let mut messages = Vec::with_capacity(capacity);
+    let mut recipients = Vec::with_capacity(capacity);
+    for id in 1..=message_count {
+        let created_ts = first_ts + id * MICROS_PER_SECOND;
+        let thread = rng.next_bounded(threads);
+        let ack_required = rng.next_bounded(10) == 0;
+        messages.push(vec![
+            Value::BigInt(id),
+            Value::BigInt(1),
+            Value::BigInt(*rng.choose(&sender_ids)),
+            Value::Text(format!("scn-{thread}")),
+            Value::Text(format!("scenario message {id}")),
+            Value::Text(format!(
+                "Scenario body {id} in thread {thread}; seeded for inbox benchmarks."
+            )),
+            Value::Text((*rng.choose(SCENARIO_IMPORTANCE)).to_string()),
+            Value::BigInt(i64::from(ack_required)),
+            Value::BigInt(created_ts),
+        ]);
+        let read = rng.next_bounded(10) < 6;
+        let acked = read && ack_required && rng.next_bounded(2) == 0;
+        recipients.push(vec![
+            Value::BigInt(id),
+            Value::BigInt(1),
+            Value::Text("to".to_string()),
+            if read {
+                Value::BigInt(created_ts + MICROS_PER_SECOND)
+            } else {
+                Value::Null
+            },
+            if acked {
+                Value::BigInt(created_ts + 2 * MICROS_PER_SECOND)
+            } else {
+                Value::Null
+            },
+        ]);
+    }
+    insert_batched(
+        conn,
+        "messages",
+        &[
+            "id",
+            "project_id",
+            "sender_id",
+            "thread_id",
+            "subject",
+            "body_md",
+            "importance",
+            "ack_required",
+            "created_ts",
+        ],
+        &messages,
+        "inserting scenario messages",
+    )?;
+    insert_batched(
+        conn,
+        "message_recipients",
+        &["message_id", "agent_id", "kind", "read_ts", "ack_ts"],
+        &recipients,
+        "inserting scenario message recipients",
+    )?;
+
+    let reservations: Vec<Vec<Value>> = (1..=dataset.reservations)
+        .map(|idx| {
+            let crate_dir = idx % SCENARIO_RESERVATION_CRATES;
+            let pattern = if rng.next_bounded(5) == 0 {
+                format!("crates/c{crate_dir}/tests/**")
+            } else {
+                format!("crates/c{crate_dir}/src/m{idx}.rs")
+            };
+            let jitter = i64::try_from(rng.next_bounded(3_600)).unwrap_or(0) * MICROS_PER_SECOND;
+            vec![
+                Value::BigInt(i64::from(idx)),
+                Value::BigInt(1),
+                Value::BigInt(*rng.choose(&sender_ids)),
+                Value::Text(pattern),
+                Value::BigInt(i64::from(rng.next_bounded(10) != 0)),
+                Value::Text("scenario".to_string()),
+                Value::BigInt(now_us),
+                Value::BigInt(now_us + RESERVATION_TTL_US + jitter),
+            ]
+        })
+        .collect();
+    insert_batched(
+        conn,
+        "file_reservations",
+        &[
+            "id",
+            "project_id",
+            "agent_id",
+            "path_pattern",
+            "\"exclusive\"",
+            "reason",
+            "created_ts",
+            "expires_ts",
+        ],
+        &reservations,
+        "inserting scenario file reservations",
+    )
+}
+
+/// Seed a dedicated scenario database to `scale`.
+///
+/// A database that already holds the dataset is reused, so an
+/// `AM_BENCH_WORKSPACE` seeded by an earlier run skips straight to timing.
+/// Anything else in the database is cleared first.
+pub fn seed_scenario_database(
+    conn: &DbConn,
+    scale: BenchScale,
+) -> Result<ScenarioSeedReport, BenchSeedError> {
+    let started = Instant::now();
+    let dataset = scale.dataset();
+    conn.execute_raw(&mcp_agent_mail_db::schema::init_schema_sql_base())
+        .map_err(|e| db_error("initializing schema for scenario seed", e))?;
+    if dataset_present(conn, dataset)? {
+        return Ok(ScenarioSeedReport {
+            scale,
+            seed: SCENARIO_SEED,
+            dataset,
+            skipped: true,
+            elapsed_us: elapsed_us(started.elapsed()),
+        });
+    }
+
+    conn.execute_raw("BEGIN IMMEDIATE")
+        .map_err(|e| db_error("starting scenario seed transaction", e))?;
+    let seeded = purge_scenario_rows(conn).and_then(|()| {
+        insert_scenario_rows(conn, dataset, mcp_agent_mail_db::timestamps::now_micros())
+    });
+    match seeded {
+        Ok(()) => {
+            conn.execute_raw("COMMIT")
+                .map_err(|e| db_error("committing scenario seed transaction", e))?;
+            Ok(ScenarioSeedReport {
+                scale,
+                seed: SCENARIO_SEED,
+                dataset,
+                skipped: false,
+                elapsed_us: elapsed_us(started.elapsed()),
+            })
+        }
+        Err(err) => {
+            let _ = conn.execute_raw("ROLLBACK");
+            Err(err)
+        }
+    }
+}
+
+fn run_in_process_once(
+    runner: &CliRunner,
+    command: &[String],
+) -> (Option<i32>, Option<String>, i64, bool) {
+    let started = Instant::now();
+    let parsed =
+        crate::Cli::try_parse_from(std::iter::once("am").chain(command.iter().map(String::as_str)));
+    let command = match parsed.map(|cli| cli.command) {
+        Ok(Some(command)) => command,
+        Ok(None) => {
+            return (
+                None,
+                Some("scenario command has no subcommand".to_string()),
+                elapsed_us(started.elapsed()),
+                false,
+            );
+        }
+        Err(err) => {
+            return (
+                None,
+                Some(err.to_string()),
+                elapsed_us(started.elapsed()),
+                false,
+            );
+        }
+    };
+    match runner.run(command) {
+        Ok(_) => (Some(0), None, elapsed_us(started.elapsed()), true),
+        Err(CliError::ExitCode(code)) => (Some(code), None, elapsed_us(started.elapsed()), false),
+        Err(err) => (
+            None,
+            Some(err.to_string()),
+            elapsed_us(started.elapsed()),
+            false,
+        ),
+    }
+}
+
+/// Run a scenario command in-process with warmup+measurement loops.
+///
+/// `command` is the `am` argv without the program name; `env` is layered
+/// over the process environment for every run (see [`CliRunner::run`]).
+pub fn run_in_process_timed(
+    command: &[String],
+    warmup: u32,
+    runs: u32,
+    env: &BTreeMap<String, String>,
+) -> Result<TimingResult, BenchTimingError> {
+    if command.is_empty() {
+        return Err(BenchTimingError::EmptyCommand);
+    }
+    let runner = env.iter().fold(CliRunner::new(), |runner, (key, value)| {
+        runner.env(key, value)
+    });
+    time_iterations(warmup, runs, || run_in_process_once(&runner, command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(conn: &DbConn, sql: &str) -> i64 {
+        count_rows(conn, sql).expect("count")
+    }
+
+    #[test]
+    fn scenario_configs_are_named_for_their_scale() {
+        let names: Vec<String> = scenario_configs(BenchProfile::Quick, BenchScale::Large)
+            .into_iter()
+            .map(|cfg| {
+                assert_eq!(cfg.category, BenchCategory::Scenario);
+                assert_eq!(cfg.runs, crate::bench::QUICK_RUNS);
+                cfg.name
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "scenario_inbox_fetch_large",
+                "scenario_conflict_check_large",
+                "scenario_project_list_large",
+            ]
+        );
+        assert_eq!(
+            BenchScale::Large.dataset(),
+            ScenarioDataset {
+                messages: 100_000,
+                reservations: 5_000,
+                projects: 500,
+            }
+        );
+    }
+
+    #[test]
+    fn seed_scenario_database_is_deterministic_and_reused() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let dataset = BenchScale::Small.dataset();
+        let mut fingerprints = Vec::new();
+        for name in ["a.sqlite3", "b.sqlite3"] {
+            let conn =
+                DbConn::open_file(dir.path().join(name).display().to_string()).expect("open db");
+            let report = seed_scenario_database(&conn, BenchScale::Small).expect("seed");
+            assert!(!report.skipped);
+            assert_eq!(report.dataset, dataset);
+            assert_eq!(
+                count(&conn, "SELECT COUNT(*) AS count FROM projects"),
+                i64::from(dataset.projects)
+            );
+            assert_eq!(
+                count(
+                    &conn,
+                    "SELECT COUNT(*) AS count FROM message_recipients WHERE agent_id = 1"
+                ),
+                i64::from(dataset.messages)
+            );
+            assert_eq!(
+                count(&conn, "SELECT COUNT(*) AS count FROM file_reservations"),
+                i64::from(dataset.reservations)
+            );
+            fingerprints.push((
+                count(
+                    &conn,
+                    "SELECT SUM(sender_id * id + ack_required) AS count FROM messages",
+                ),
+                count(
+                    &conn,
+                    "SELECT COUNT(*) AS count FROM message_recipients WHERE read_ts IS NULL",
+                ),
+            ));
+
+            let again = seed_scenario_database(&conn, BenchScale::Small).expect("reseed");
+            assert!(again.skipped);
+        }
+        assert_eq!(fingerprints[0], fingerprints[1]);
+    }
+}
@@ -135,6 +135,34 @@ impl std::fmt::Display for RunMode {
     }
 }
 
+/// Where a gate definition came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GateProvenance {
+    /// Shipped with `am ci` (see [`default_gates`]).
+    #[default]
+    Builtin,
+    /// Defined or overridden by the workspace `am-ci.toml`.
+    Custom,
+}
+
+impl GateProvenance {
+    /// Returns the string representation for JSON output.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Builtin => "builtin",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+impl std::fmt::Display for GateProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // Gate Configuration
 // ──────────────────────────────────────────────────────────────────────────────
@@ -156,6 +184,18 @@ pub struct GateConfig {
     /// Expected artifact paths/globs produced by this gate.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub expected_artifacts: Vec<String>,
+    /// Whether the gate is builtin or comes from the workspace gate file.
+    #[serde(default)]
+    pub provenance: GateProvenance,
+    /// Per-gate timeout override in seconds (runner default when absent).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timeout_secs: Option<u64>,
+    /// If true, a failure is reported but does not flip the decision to no-go.
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub allow_failure: bool,
+    /// Names of gates that must pass before this gate runs.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub depends_on: Vec<String>,
 }
 
 impl GateConfig {
@@ -173,6 +213,10 @@ impl GateConfig {
             skip_in_quick: false,
             parallel_group: None,
             expected_artifacts: Vec::new(),
+            provenance: GateProvenance::Builtin,
+            timeout_secs: None,
+            allow_failure: false,
+            depends_on: Vec::new(),
         }
     }
 
@@ -208,6 +252,34 @@ impl GateConfig {
         self
     }
 
+    /// Builder: mark this gate as defined by the workspace gate file.
+    #[must_use]
+    pub fn custom(mut self) -> Self {
+        self.provenance = GateProvenance::Custom;
+        self
+    }
+
+    /// Builder: override the runner timeout for this gate.
+    #[must_use]
+    pub fn timeout_secs(mut self, secs: u64) -> Self {
+        self.timeout_secs = Some(secs);
+        self
+    }
+
+    /// Builder: let this gate fail without blocking the release decision.
+    #[must_use]
+    pub fn allow_failure(mut self) -> Self {
+        self.allow_failure = true;
+        self
+    }
+
+    /// Builder: require another gate (by name) to pass before this one runs.
+    #[must_use]
+    pub fn depends_on(mut self, gate: impl Into<String>) -> Self {
+        self.depends_on.push(gate.into());
+        self
+    }
+
     /// Returns the command as a display string.
     #[must_use]
     pub fn command_display(&self) -> String {
@@ -849,6 +921,12 @@ pub struct GateExecutionLogEntry {
     /// Artifact/log bundle paths associated with this gate.
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub artifact_links: Vec<String>,
+    /// Whether the gate is builtin or defined by the workspace gate file.
+    #[serde(default)]
+    pub provenance: GateProvenance,
+    /// True when a failure of this gate does not block the decision.
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub allow_failure: bool,
 }
 
 /// The full gate report (schema: am_ci_gate_report.v1).
@@ -955,8 +1033,20 @@ impl GateReport {
             thresholds.insert(category, ThresholdInfo::from_results(&results, category));
         }
 
+        // Gates marked allow_failure still show up as failures in the
+        // summary, but never block the decision on their own.
+        let tolerated: BTreeSet<&str> = gate_configs
+            .iter()
+            .filter(|gate| gate.allow_failure)
+            .map(|gate| gate.name.as_str())
+            .collect();
+        let blocking_failures = results
+            .iter()
+            .filter(|r| r.status == GateStatus::Fail && !tolerated.contains(r.name.as_str()))
+            .count();
+
         // Determine decision
-        let (decision, decision_reason, release_eligible) = if summary.fail > 0 {
+        let (decision, decision_reason, release_eligible) = if blocking_failures > 0 {
             (
                 Decision::NoGo,
                 "one or more gates failed".to_string(),
@@ -998,6 +1088,12 @@ impl GateReport {
                     .get(&result.name)
                     .cloned()
                     .unwrap_or_default(),
+                provenance: gate_configs
+                    .iter()
+                    .find(|gate| gate.name == result.name)
+                    .map(|gate| gate.provenance)
+                    .unwrap_or_default(),
+                allow_failure: tolerated.contains(result.name.as_str()),
             })
             .collect();
 
@@ -1151,6 +1247,7 @@ const E2E_RELEASE_HEALTH_GATES: &[&str] = &[
 const PERF_RELEASE_HEALTH_GATES: &[&str] = &[
     "Perf + security regressions",
     "Perf migration guardrails",
+    "Hot query plan guard",
     "Archive perf gate",
     "ATC perf gate",
 ];
@@ -2024,6 +2121,20 @@ pub fn default_gates() -> Vec<GateConfig> {
                 "--nocapture",
             ],
         ),
+        GateConfig::new(
+            "Hot query plan guard",
+            GateCategory::Performance,
+            [
+                "cargo",
+                "test",
+                "-p",
+                "mcp-agent-mail-db",
+                "--test",
+                "query_plan_guard",
+                "--",
+                "--nocapture",
+            ],
+        ),
         GateConfig::new(
             "Archive perf gate",
             GateCategory::Performance,
@@ -2065,6 +2176,284 @@ pub fn default_gates() -> Vec<GateConfig> {
     ]
 }
 
+// ──────────────────────────────────────────────────────────────────────────────
+// Workspace Gate File (am-ci.toml)
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// File name of the optional workspace gate definitions, read from the
+/// workspace root.
+pub const WORKSPACE_GATES_FILE: &str = "am-ci.toml";
+
+/// One `[[gate]]` table from `am-ci.toml`.
+///
+/// An entry whose `name` matches a builtin gate overrides only the fields it
+/// sets (or removes the builtin when `enabled = false`); any other entry
+/// defines a new custom gate and must provide `command`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkspaceGateEntry {
+    name: toml::Spanned<String>,
+    command: Option<String>,
+    args: Option<Vec<String>>,
+    category: Option<GateCategory>,
+    #[serde(alias = "timeout")]
+    timeout_secs: Option<u64>,
+    /// Include the gate in `--quick` runs (defaults to true for new gates).
+    quick: Option<bool>,
+    allow_failure: Option<bool>,
+    depends_on: Option<Vec<toml::Spanned<String>>>,
+    enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkspaceGateFile {
+    #[serde(default)]
+    gate: Vec<WorkspaceGateEntry>,
+}
+
+/// A malformed or inconsistent `am-ci.toml`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceGateError {
+    /// Path of the offending file.
+    pub path: PathBuf,
+    /// 1-based line of the problem, when it can be located.
+    pub line: Option<usize>,
+    /// 1-based column of the problem, when it can be located.
+    pub column: Option<usize>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl WorkspaceGateError {
+    fn at(path: &Path, source: &str, offset: Option<usize>, message: impl Into<String>) -> Self {
+        let (line, column) = offset.map_or((None, None), |offset| {
+            let (line, column) = line_column(source, offset);
+            (Some(line), Some(column))
+        });
+        Self {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for WorkspaceGateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(
+                f,
+                "{}:{line}:{column}: {}",
+                self.path.display(),
+                self.message
+            ),
+            _ => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceGateError {}
+
+/// Converts a byte offset into a 1-based (line, column) pair.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let prefix = source.get(..offset).unwrap_or(source);
+    let line_start = prefix.rfind('\n').map_or(0, |nl| nl + 1);
+    (
+        prefix.matches('\n').count() + 1,
+        prefix[line_start..].chars().count() + 1,
+    )
+}
+
+/// Loads the gate list for `workspace_root`: the builtins merged with
+/// `am-ci.toml` when that file exists.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, is not valid TOML, or
+/// describes an inconsistent gate set. Nothing is executed in that case.
+pub fn load_workspace_gates(workspace_root: &Path) -> Result<Vec<GateConfig>, WorkspaceGateError> {
+    let path = workspace_root.join(WORKSPACE_GATES_FILE);
+    let source = match std::fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(default_gates()),
+        Err(err) => {
+            return Err(WorkspaceGateError::at(
+                &path,
+                "",
+                None,
+                format!("failed to read: {err}"),
+            ));
+        }
+    };
+    merge_workspace_gates(&path, &source, default_gates())
+}
+
+/// Merges the gate definitions in `source` (the contents of `path`) into
+/// `builtins`.
+///
+/// Overridden and new gates are marked [`GateProvenance::Custom`]. The result
+/// is ordered so every gate follows the gates it depends on; otherwise the
+/// builtin order is kept and new gates are appended in file order.
+///
+/// # Errors
+/// Returns an error with line/column information for TOML syntax errors,
+/// unknown keys, duplicate entries, custom gates without a command,
+/// dependencies on missing gates, and dependency cycles.
+pub fn merge_workspace_gates(
+    path: &Path,
+    source: &str,
+    builtins: Vec<GateConfig>,
+) -> Result<Vec<GateConfig>, WorkspaceGateError> {
+    let file: WorkspaceGateFile = toml::from_str(source).map_err(|err| {
+        WorkspaceGateError::at(
+            path,
+            source,
+            err.span().map(|span| span.start),
+            err.message().trim(),
+        )
+    })?;
+
+    let mut gates = builtins;
+    let mut seen = BTreeSet::new();
+    let mut name_offsets = HashMap::new();
+    let mut dependency_refs = Vec::new();
+
+    for entry in file.gate {
+        let name_offset = entry.name.span().start;
+        let name = entry.name.into_inner();
+        if !seen.insert(name.clone()) {
+            return Err(WorkspaceGateError::at(
+                path,
+                source,
+                Some(name_offset),
+                format!("gate '{name}' is defined more than once"),
+            ));
+        }
+        name_offsets.insert(name.clone(), name_offset);
+
+        let existing = gates.iter().position(|gate| gate.name == name);
+        if entry.enabled == Some(false) {
+            match existing {
+                Some(idx) => {
+                    gates.remove(idx);
+                    continue;
+                }
+                None => {
+                    return Err(WorkspaceGateError::at(
+                        path,
+                        source,
+                        Some(name_offset),
+                        format!("cannot disable '{name}': no builtin gate has that name"),
+                    ));
+                }
+            }
+        }
+
+        let idx = if let Some(idx) = existing {
+            idx
+        } else {
+            let Some(command) = entry.command.as_deref() else {
+                return Err(WorkspaceGateError::at(
+                    path,
+                    source,
+                    Some(name_offset),
+                    format!("custom gate '{name}' needs a command"),
+                ));
+            };
+            gates.push(GateConfig::new(
+                name.clone(),
+                GateCategory::Quality,
+                [command],
+            ));
+            gates.len() - 1
+        };
+        let gate = &mut gates[idx];
+
+        match (entry.command, entry.args) {
+            (Some(command), args) => {
+                gate.command = std::iter::once(command)
+                    .chain(args.unwrap_or_default())
+                    .collect();
+            }
+            (None, Some(args)) => {
+                gate.command.truncate(1);
+                gate.command.extend(args);
+            }
+            (None, None) => {}
+        }
+        if let Some(category) = entry.category {
+            gate.category = category;
+        }
+        if let Some(secs) = entry.timeout_secs {
+            gate.timeout_secs = Some(secs);
+        }
+        if let Some(quick) = entry.quick {
+            gate.skip_in_quick = !quick;
+        }
+        if let Some(allow_failure) = entry.allow_failure {
+            gate.allow_failure = allow_failure;
+        }
+        if let Some(depends_on) = entry.depends_on {
+            gate.depends_on = depends_on.iter().map(|dep| dep.get_ref().clone()).collect();
+            dependency_refs.extend(depends_on.into_iter().map(|dep| (name.clone(), dep)));
+        }
+        gate.provenance = GateProvenance::Custom;
+    }
+
+    for (owner, dep) in &dependency_refs {
+        let target = dep.get_ref();
+        if target == owner {
+            return Err(WorkspaceGateError::at(
+                path,
+                source,
+                Some(dep.span().start),
+                format!("gate '{owner}' cannot depend on itself"),
+            ));
+        }
+        if !gates.iter().any(|gate| &gate.name == target) {
+            return Err(WorkspaceGateError::at(
+                path,
+                source,
+                Some(dep.span().start),
+                format!("gate '{owner}' depends on unknown or disabled gate '{target}'"),
+            ));
+        }
+    }
+
+    order_by_dependencies(gates).map_err(|name| {
+        WorkspaceGateError::at(
+            path,
+            source,
+            name_offsets.get(&name).copied(),
+            format!("dependency cycle involving gate '{name}'"),
+        )
+    })
+}
+
+/// Stable topological sort: repeatedly takes the first gate whose
+/// dependencies have all been placed. Returns the name of a gate stuck in a
+/// cycle on failure.
+fn order_by_dependencies(gates: Vec<GateConfig>) -> Result<Vec<GateConfig>, String> {
+    let known: BTreeSet<String> = gates.iter().map(|gate| gate.name.clone()).collect();
+    let mut pending = gates;
+    let mut placed = BTreeSet::new();
+    let mut ordered = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let Some(idx) = pending.iter().position(|gate| {
+            gate.depends_on
+                .iter()
+                .all(|dep| placed.contains(dep) || !known.contains(dep))
+        }) else {
+            return Err(pending[0].name.clone());
+        };
+        let gate = pending.remove(idx);
+        placed.insert(gate.name.clone());
+        ordered.push(gate);
+    }
+    Ok(ordered)
+}
+
 /// Environment variables to set on child gate processes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GateEnvironment {
@@ -2290,8 +2679,8 @@ pub fn run_gate(config: &GateConfig, runner_config: &GateRunnerConfig) -> GateRe
         })
     });
 
-    // Wait for completion with timeout
-    let timeout = Duration::from_secs(runner_config.timeout_secs);
+    // Wait for completion with timeout (per-gate override wins)
+    let timeout = Duration::from_secs(config.timeout_secs.unwrap_or(runner_config.timeout_secs));
     let status = loop {
         match child.try_wait() {
             Ok(Some(status)) => break Ok(status),
@@ -2358,8 +2747,31 @@ pub fn run_gate(config: &GateConfig, runner_config: &GateRunnerConfig) -> GateRe
     }
 }
 
+/// Returns a skip result when one of `config`'s dependencies did not pass.
+///
+/// Gates that quick mode skips anyway keep their "--quick mode" reason.
+fn dependency_skip(
+    config: &GateConfig,
+    runner_config: &GateRunnerConfig,
+    finished: &HashMap<String, GateStatus>,
+) -> Option<GateResult> {
+    if runner_config.mode == RunMode::Quick && config.skip_in_quick {
+        return None;
+    }
+    config.depends_on.iter().find_map(|dep| {
+        let reason = match finished.get(dep) {
+            Some(GateStatus::Pass) => return None,
+            Some(status) => format!("dependency '{dep}' did not pass ({status})"),
+            None => format!("dependency '{dep}' did not run"),
+        };
+        Some(GateResult::skip(config, reason))
+    })
+}
+
 /// Runs all gates sequentially and returns a report.
 ///
+/// A gate whose `depends_on` gates did not pass is skipped.
+///
 /// # Arguments
 /// * `gates` - List of gate configurations to run.
 /// * `runner_config` - Runner configuration.
@@ -2369,6 +2781,7 @@ pub fn run_gate(config: &GateConfig, runner_config: &GateRunnerConfig) -> GateRe
 pub fn run_gates(gates: &[GateConfig], runner_config: &GateRunnerConfig) -> GateReport {
     let total = gates.len();
     let mut results = Vec::with_capacity(total);
+    let mut finished = HashMap::new();
 
     for (idx, gate) in gates.iter().enumerate() {
         // Progress callback
@@ -2377,13 +2790,15 @@ pub fn run_gates(gates: &[GateConfig], runner_config: &GateRunnerConfig) -> Gate
         }
 
         // Run the gate
-        let result = run_gate(gate, runner_config);
+        let result = dependency_skip(gate, runner_config, &finished)
+            .unwrap_or_else(|| run_gate(gate, runner_config));
 
         // Result callback
         if let Some(callback) = runner_config.on_gate_complete {
             callback(&result);
         }
 
+        finished.insert(gate.name.clone(), result.status);
         results.push(result);
     }
 
@@ -2404,6 +2819,108 @@ pub fn run_default_gates(runner_config: &GateRunnerConfig) -> GateReport {
     run_gates(&gates, runner_config)
 }
 
+/// Runs one phase of [`run_gates_parallel`] in dependency waves.
+///
+/// Each wave starts, concurrently, every pending gate whose `depends_on`
+/// gates have finished (or are not part of this phase), then waits for all of
+/// them. Gates whose dependencies did not pass are skipped without spawning.
+fn run_gate_waves(
+    phase: &[(usize, &GateConfig)],
+    runner_config: &GateRunnerConfig,
+    total: usize,
+    finished: &mut HashMap<String, GateStatus>,
+) -> Vec<(usize, GateResult)> {
+    use std::thread;
+
+    let mut pending = phase.to_vec();
+    let mut indexed_results = Vec::with_capacity(pending.len());
+
+    while !pending.is_empty() {
+        let pending_names: BTreeSet<&str> = pending
+            .iter()
+            .map(|&(_, gate)| gate.name.as_str())
+            .collect();
+        let (ready, blocked): (Vec<_>, Vec<_>) = pending.iter().copied().partition(|(_, gate)| {
+            gate.depends_on
+                .iter()
+                .all(|dep| finished.contains_key(dep) || !pending_names.contains(dep.as_str()))
+        });
+
+        // Validated configs are acyclic; never hang if one slips through.
+        if ready.is_empty() {
+            for (idx, gate) in blocked {
+                if let Some(callback) = runner_config.on_gate_start {
+                    callback(&gate.name, idx, total);
+                }
+                let result = GateResult::skip(gate, "dependency cycle");
+                if let Some(callback) = runner_config.on_gate_complete {
+                    callback(&result);
+                }
+                finished.insert(gate.name.clone(), result.status);
+                indexed_results.push((idx, result));
+            }
+            break;
+        }
+
+        let mut handles: Vec<(usize, GateConfig, thread::JoinHandle<GateResult>)> = Vec::new();
+        for (idx, gate) in ready {
+            if let Some(callback) = runner_config.on_gate_start {
+                callback(&gate.name, idx, total);
+            }
+            if let Some(result) = dependency_skip(gate, runner_config, finished) {
+                if let Some(callback) = runner_config.on_gate_complete {
+                    callback(&result);
+                }
+                finished.insert(gate.name.clone(), result.status);
+                indexed_results.push((idx, result));
+                continue;
+            }
+            let gate = gate.clone();
+            let gate_for_thread = gate.clone();
+            let config = runner_config.clone();
+            let handle = thread::spawn(move || {
+                eprintln!("  [{}] Starting: {}", idx + 1, gate_for_thread.name);
+                let result = run_gate(&gate_for_thread, &config);
+                if let Some(callback) = config.on_gate_complete {
+                    callback(&result);
+                }
+                eprintln!(
+                    "  [{}] Finished: {} - {}",
+                    idx + 1,
+                    gate_for_thread.name,
+                    result.status.as_str()
+                );
+                result
+            });
+            handles.push((idx, gate, handle));
+        }
+
+        // Wait for the whole wave before releasing its dependents
+        for (idx, gate, handle) in handles {
+            let result = match handle.join() {
+                Ok(result) => result,
+                Err(_) => {
+                    let failure = GateResult::fail_simple(
+                        &gate,
+                        Duration::from_secs(0),
+                        "gate worker thread panicked",
+                    );
+                    if let Some(callback) = runner_config.on_gate_complete {
+                        callback(&failure);
+                    }
+                    failure
+                }
+            };
+            finished.insert(gate.name.clone(), result.status);
+            indexed_results.push((idx, result));
+        }
+
+        pending = blocked;
+    }
+
+    indexed_results
+}
+
 /// Runs all gates in parallel where possible and returns a report.
 ///
 /// Gates are run concurrently using a thread pool. Each gate runs in its own
@@ -2412,6 +2929,9 @@ pub fn run_default_gates(runner_config: &GateRunnerConfig) -> GateReport {
 ///
 /// The compile group (fmt, clippy, build) must complete before the test group
 /// runs to ensure build artifacts exist. Other groups can run in parallel.
+/// Within each phase, a gate starts only after its `depends_on` gates finish,
+/// and is skipped if any of them did not pass. A compile gate cannot wait on a
+/// gate from the later phase; such a dependency counts as not run.
 ///
 /// # Arguments
 /// * `gates` - List of gate configurations to run.
@@ -2421,7 +2941,6 @@ pub fn run_default_gates(runner_config: &GateRunnerConfig) -> GateReport {
 /// A `GateReport` with all results and summary.
 pub fn run_gates_parallel(gates: &[GateConfig], runner_config: &GateRunnerConfig) -> GateReport {
     use std::collections::HashSet;
-    use std::thread;
 
     let total = gates.len();
 
@@ -2445,62 +2964,21 @@ pub fn run_gates_parallel(gates: &[GateConfig], runner_config: &GateRunnerConfig
         .collect();
 
     let mut indexed_results: Vec<(usize, GateResult)> = Vec::with_capacity(total);
+    let mut finished = HashMap::new();
 
     // Phase 1: Run compile gates in parallel
     eprintln!("Phase 1: Running compile gates in parallel...");
-    {
-        let handles: Vec<(usize, GateConfig, thread::JoinHandle<GateResult>)> = compile_gates
-            .iter()
-            .map(|(idx, gate)| {
-                if let Some(callback) = runner_config.on_gate_start {
-                    callback(&gate.name, *idx, total);
-                }
-                let gate = (*gate).clone();
-                let gate_for_thread = gate.clone();
-                let config = runner_config.clone();
-                let idx = *idx;
-                let handle = thread::spawn(move || {
-                    eprintln!("  [{}] Starting: {}", idx + 1, gate_for_thread.name);
-                    let result = run_gate(&gate_for_thread, &config);
-                    if let Some(callback) = config.on_gate_complete {
-                        callback(&result);
-                    }
-                    eprintln!(
-                        "  [{}] Finished: {} - {}",
-                        idx + 1,
-                        gate_for_thread.name,
-                        result.status.as_str()
-                    );
-                    result
-                });
-                (idx, gate, handle)
-            })
-            .collect();
-
-        // Wait for all compile gates to complete
-        for (idx, gate, handle) in handles {
-            let result = match handle.join() {
-                Ok(result) => result,
-                Err(_) => {
-                    let failure = GateResult::fail_simple(
-                        &gate,
-                        Duration::from_secs(0),
-                        "gate worker thread panicked",
-                    );
-                    if let Some(callback) = runner_config.on_gate_complete {
-                        callback(&failure);
-                    }
-                    failure
-                }
-            };
-            indexed_results.push((idx, result));
-        }
-    }
+    indexed_results.extend(run_gate_waves(
+        &compile_gates,
+        runner_config,
+        total,
+        &mut finished,
+    ));
 
     // Check if compile phase failed - if so, skip remaining gates
     let compile_failed = indexed_results
         .iter()
-        .any(|(_, r)| r.status == GateStatus::Fail);
+        .any(|(idx, r)| r.status == GateStatus::Fail && !gates[*idx].allow_failure);
 
     // Phase 2: Run remaining gates in parallel (if compile passed)
     if compile_failed {
@@ -2521,52 +2999,12 @@ pub fn run_gates_parallel(gates: &[GateConfig], runner_config: &GateRunnerConfig
             "Phase 2: Running remaining {} gates in parallel...",
             other_gates.len()
         );
-        let handles: Vec<(usize, GateConfig, thread::JoinHandle<GateResult>)> = other_gates
-            .iter()
-            .map(|(idx, gate)| {
-                if let Some(callback) = runner_config.on_gate_start {
-                    callback(&gate.name, *idx, total);
-                }
-                let gate = (*gate).clone();
-                let gate_for_thread = gate.clone();
-                let config = runner_config.clone();
-                let idx = *idx;
-                let handle = thread::spawn(move || {
-                    eprintln!("  [{}] Starting: {}", idx + 1, gate_for_thread.name);
-                    let result = run_gate(&gate_for_thread, &config);
-                    if let Some(callback) = config.on_gate_complete {
-                        callback(&result);
-                    }
-                    eprintln!(
-                        "  [{}] Finished: {} - {}",
-                        idx + 1,
-                        gate_for_thread.name,
-                        result.status.as_str()
-                    );
-                    result
-                });
-                (idx, gate, handle)
-            })
-            .collect();
-
-        // Wait for all gates to complete
-        for (idx, gate, handle) in handles {
-            let result = match handle.join() {
-                Ok(result) => result,
-                Err(_) => {
-                    let failure = GateResult::fail_simple(
-                        &gate,
-                        Duration::from_secs(0),
-                        "gate worker thread panicked",
-                    );
-                    if let Some(callback) = runner_config.on_gate_complete {
-                        callback(&failure);
-                    }
-                    failure
-                }
-            };
-            indexed_results.push((idx, result));
-        }
+        indexed_results.extend(run_gate_waves(
+            &other_gates,
+            runner_config,
+            total,
+            &mut finished,
+        ));
     }
 
     // Ensure we always return one result per gate (never panic on internal runner faults).
@@ -2613,9 +3051,20 @@ pub fn print_gate_summary(report: &GateReport) {
             GateStatus::Fail => "FAIL",
             GateStatus::Skip => "SKIP",
         };
+        let log_entry = report
+            .execution_log
+            .iter()
+            .find(|entry| entry.gate == result.name);
+        let mut tags = String::new();
+        if log_entry.is_some_and(|entry| entry.provenance == GateProvenance::Custom) {
+            tags.push_str(" (custom)");
+        }
+        if result.status == GateStatus::Fail && log_entry.is_some_and(|entry| entry.allow_failure) {
+            tags.push_str(" (failure allowed)");
+        }
         println!(
-            "  {} {} [{}s] {}",
-            status_icon, status_label, result.elapsed_seconds, result.name
+            "  {} {} [{}s] {}{}",
+            status_icon, status_label, result.elapsed_seconds, result.name, tags
         );
         if let Some(ref tail) = result.stderr_tail {
             // Print first 3 lines of stderr for quick diagnostics
@@ -2785,7 +3234,7 @@ mod tests {
     #[test]
     fn test_default_gates_count() {
         let gates = default_gates();
-        assert_eq!(gates.len(), 20, "Expected 20 default gates");
+        assert_eq!(gates.len(), 21, "Expected 21 default gates");
     }
 
     #[test]
@@ -4174,4 +4623,161 @@ error[E0425]: another error
             gates.len()
         );
     }
+
+    const WORKSPACE_GATES_FIXTURE: &str = include_str!("../tests/fixtures/am_ci/am-ci.toml");
+
+    #[test]
+    fn workspace_gates_fixture_adds_custom_gate_and_disables_builtin() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            temp.path().join(WORKSPACE_GATES_FILE),
+            WORKSPACE_GATES_FIXTURE,
+        )
+        .expect("write fixture");
+
+        let gates = load_workspace_gates(temp.path()).expect("fixture parses");
+
+        assert!(!gates.iter().any(|gate| gate.name == "E2E full matrix"));
+        assert_eq!(gates.len(), default_gates().len() + 1);
+        let sanity = gates
+            .iter()
+            .find(|gate| gate.name == "Workspace sanity")
+            .expect("custom gate present");
+        assert_eq!(sanity.provenance, GateProvenance::Custom);
+        assert_eq!(sanity.category, GateCategory::Docs);
+        assert_eq!(sanity.command, vec!["true".to_string()]);
+        assert_eq!(sanity.timeout_secs, Some(30));
+        assert!(!sanity.skip_in_quick);
+        let follow_up = gates
+            .iter()
+            .find(|gate| gate.name == "Workspace follow-up")
+            .expect("dependent gate present");
+        assert_eq!(follow_up.command, vec!["bash", "-c", "exit 0"]);
+        assert!(follow_up.skip_in_quick);
+        assert!(follow_up.allow_failure);
+        assert_eq!(follow_up.depends_on, vec!["Workspace sanity".to_string()]);
+        assert!(
+            gates
+                .iter()
+                .filter(|gate| !gate.name.starts_with("Workspace"))
+                .all(|gate| gate.provenance == GateProvenance::Builtin)
+        );
+    }
+
+    #[test]
+    fn workspace_gates_missing_file_yields_builtins() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let gates = load_workspace_gates(temp.path()).expect("no file is fine");
+        assert_eq!(gates.len(), default_gates().len());
+    }
+
+    #[test]
+    fn workspace_gates_override_builtin_by_name() {
+        let source = "[[gate]]\nname = \"Clippy\"\nargs = [\"clippy\", \"--workspace\"]\nallow_failure = true\n";
+        let gates = merge_workspace_gates(Path::new("am-ci.toml"), source, default_gates())
+            .expect("override parses");
+        let clippy = gates.iter().find(|gate| gate.name == "Clippy").unwrap();
+        assert_eq!(clippy.command, vec!["cargo", "clippy", "--workspace"]);
+        assert_eq!(clippy.provenance, GateProvenance::Custom);
+        assert!(clippy.allow_failure);
+    }
+
+    #[test]
+    fn workspace_gates_malformed_toml_reports_line_and_column() {
+        let source = "[[gate]]\nname = \"Docs build\"\ncommand = \"true\" extra\n";
+        let err = merge_workspace_gates(Path::new("am-ci.toml"), source, default_gates())
+            .expect_err("malformed TOML must fail");
+        assert_eq!(err.line, Some(3));
+        assert!(err.column.is_some());
+        assert!(err.to_string().starts_with("am-ci.toml:3:"), "{err}");
+    }
+
+    #[test]
+    fn workspace_gates_reject_unknown_keys_and_dependencies() {
+        let unknown_key = "[[gate]]\nname = \"Docs\"\ncommand = \"true\"\nretries = 2\n";
+        let err = merge_workspace_gates(Path::new("am-ci.toml"), unknown_key, Vec::new())
+            .expect_err("unknown key must fail");
+        assert_eq!(err.line, Some(4));
+
+        let missing_command = "[[gate]]\nname = \"Docs\"\n";
+        let err = merge_workspace_gates(Path::new("am-ci.toml"), missing_command, Vec::new())
+            .expect_err("custom gate needs a command");
+        assert_eq!((err.line, err.column), (Some(2), Some(8)));
+
+        let unknown_dep =
+            "[[gate]]\nname = \"Docs\"\ncommand = \"true\"\ndepends_on = [\"Nope\"]\n";
+        let err = merge_workspace_gates(Path::new("am-ci.toml"), unknown_dep, Vec::new())
+            .expect_err("unknown dependency must fail");
+        assert_eq!((err.line, err.column), (Some(4), Some(15)));
+        assert!(err.message.contains("'Nope'"));
+
+        let cycle = "[[gate]]\nname = \"A\"\ncommand = \"true\"\ndepends_on = [\"B\"]\n\n\
+                     [[gate]]\nname = \"B\"\ncommand = \"true\"\ndepends_on = [\"A\"]\n";
+        let err = merge_workspace_gates(Path::new("am-ci.toml"), cycle, Vec::new())
+            .expect_err("cycle must fail");
+        assert!(err.message.contains("dependency cycle"), "{err}");
+        assert_eq!(err.line, Some(2));
+    }
+
+    #[test]
+    fn workspace_gates_are_ordered_after_their_dependencies() {
+        let source = "[[gate]]\nname = \"Late\"\ncommand = \"true\"\ndepends_on = [\"Early\"]\n\n\
+                      [[gate]]\nname = \"Early\"\ncommand = \"true\"\n";
+        let gates =
+            merge_workspace_gates(Path::new("am-ci.toml"), source, Vec::new()).expect("parses");
+        let names: Vec<_> = gates.iter().map(|gate| gate.name.as_str()).collect();
+        assert_eq!(names, vec!["Early", "Late"]);
+    }
+
+    #[test]
+    fn test_parallel_respects_depends_on() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let gates = vec![
+            GateConfig::new(
+                "Producer",
+                GateCategory::Quality,
+                ["bash", "-c", "sleep 0.3 && touch produced.marker"],
+            ),
+            GateConfig::new(
+                "Consumer",
+                GateCategory::Quality,
+                ["test", "-f", "produced.marker"],
+            )
+            .depends_on("Producer"),
+            GateConfig::new("Blocked", GateCategory::Quality, ["true"]).depends_on("Broken"),
+            GateConfig::new("Broken", GateCategory::Quality, ["false"]),
+        ];
+        let config = GateRunnerConfig::new(temp.path()).mode(RunMode::Full);
+
+        let report = run_gates_parallel(&gates, &config);
+
+        assert_eq!(report.gates[0].status, GateStatus::Pass);
+        assert_eq!(report.gates[1].status, GateStatus::Pass);
+        assert_eq!(report.gates[2].status, GateStatus::Skip);
+        assert!(report.gates[2].command.contains("dependency 'Broken'"));
+        assert_eq!(report.gates[3].status, GateStatus::Fail);
+    }
+
+    #[test]
+    fn test_allowed_failure_does_not_block_decision_and_reports_provenance() {
+        let gates = vec![
+            GateConfig::new("Builtin pass", GateCategory::Quality, ["true"]),
+            GateConfig::new("Flaky custom", GateCategory::Docs, ["false"])
+                .custom()
+                .allow_failure(),
+        ];
+        let config = GateRunnerConfig::default().mode(RunMode::Full);
+
+        let report = run_gates(&gates, &config);
+
+        assert_eq!(report.summary.fail, 1);
+        assert_eq!(report.decision, Decision::Go);
+        let json: serde_json::Value =
+            serde_json::from_str(&report.to_json().expect("json")).expect("parse");
+        let log = json["execution_log"].as_array().expect("execution_log");
+        assert_eq!(log[0]["provenance"], "builtin");
+        assert!(log[0].get("allow_failure").is_none());
+        assert_eq!(log[1]["provenance"], "custom");
+        assert_eq!(log[1]["allow_failure"], true);
+    }
 }
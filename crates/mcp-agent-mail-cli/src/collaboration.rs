@@ -0,0 +1,567 @@
+//! Pairwise collaboration statistics for `am tooling collaboration`.
+//!
+//! Leads re-sharding file ownership want to know which agent pairs talk the
+//! most and collide over files most often. Everything here is computed with a
+//! handful of grouped queries over a time window, restricted to the most active
+//! agents so the report stays cheap on large installs:
+//!
+//! - messages exchanged, per direction;
+//! - average reply latency: for a message from A to B in a thread, the time
+//!   until B's first later message in that thread;
+//! - reservation conflicts: overlapping reservations of the same path pattern,
+//!   at least one exclusive, held by the two agents at the same time;
+//! - shared files: distinct path patterns both agents reserved in the window.
+//!
+//! Each query's row count and wall time are recorded so the report can say
+//! exactly what it cost to produce.
+
+#![forbid(unsafe_code)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use mcp_agent_mail_db::DbConn;
+use serde::Serialize;
+use sqlmodel_core::Value;
+
+use crate::{CliError, CliResult};
+
+/// Default cap on the number of agents the matrix covers.
+pub const DEFAULT_MAX_AGENTS: usize = 50;
+
+/// Activity counters used to rank agents for the matrix.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AgentActivity {
+    pub name: String,
+    pub sent: i64,
+    pub received: i64,
+    pub reservations: i64,
+}
+
+impl AgentActivity {
+    #[must_use]
+    pub const fn total(&self) -> i64 {
+        self.sent + self.received + self.reservations
+    }
+}
+
+/// Metrics for one unordered agent pair; `a` sorts before `b` by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PairStats {
+    pub a: String,
+    pub b: String,
+    pub a_to_b: i64,
+    pub b_to_a: i64,
+    /// Replies observed in either direction.
+    pub replies: i64,
+    /// Mean reply latency across both directions, in seconds.
+    pub avg_reply_seconds: Option<f64>,
+    pub reservation_conflicts: i64,
+    pub shared_files: i64,
+}
+
+impl PairStats {
+    #[must_use]
+    pub const fn messages(&self) -> i64 {
+        self.a_to_b + self.b_to_a
+    }
+}
+
+/// One executed query and what it cost.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryCost {
+    pub name: &'static str,
+    pub rows: usize,
+    pub elapsed_us: u64,
+}
+
+/// Full report: ranked agents, sorted pairs, and the per-query cost.
+#[derive(Debug, Clone)]
+pub struct CollaborationReport {
+    pub since_us: i64,
+    pub max_agents: usize,
+    /// Agents with any activity in the window, before the cap.
+    pub agents_total: usize,
+    /// The capped agent set, most active first.
+    pub agents: Vec<AgentActivity>,
+    /// Pairs with any interaction, busiest first.
+    pub pairs: Vec<PairStats>,
+    /// Directional `(from, to)` message counts keyed by agent index.
+    messages: HashMap<(usize, usize), i64>,
+    /// `(asker, replier)` reply latency sums (µs) and counts.
+    replies: HashMap<(usize, usize), (i64, i64)>,
+    pub queries: Vec<QueryCost>,
+}
+
+struct Meter<'a> {
+    conn: &'a DbConn,
+    queries: Vec<QueryCost>,
+}
+
+impl Meter<'_> {
+    fn run(
+        &mut self,
+        name: &'static str,
+        sql: &str,
+        params: &[Value],
+    ) -> CliResult<Vec<sqlmodel_core::Row>> {
+        let started = Instant::now();
+        let rows = self
+            .conn
+            .query_sync(sql, params)
+            .map_err(|e| CliError::Other(format!("collaboration {name} query failed: {e}")))?;
+        self.queries.push(QueryCost {
+            name,
+            rows: rows.len(),
+            elapsed_us: u64::try_from(started.elapsed().as_micros()).unwrap_or(u64::MAX),
+        });
+        Ok(rows)
+    }
+}
+
+fn id_placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Build the report for `project_id` over messages and reservations created
+/// at or after `since_us`, keeping the `max_agents` most active agents.
+pub fn collaboration_report(
+    conn: &DbConn,
+    project_id: i64,
+    since_us: i64,
+    max_agents: usize,
+) -> CliResult<CollaborationReport> {
+    let mut meter = Meter {
+        conn,
+        queries: Vec::new(),
+    };
+    let window = [Value::BigInt(project_id), Value::BigInt(since_us)];
+
+    let names: HashMap<i64, String> = meter
+        .run(
+            "agents",
+            "SELECT id, name FROM agents WHERE project_id = ?",
+            &window[..1],
+        )?
+        .iter()
+        .filter_map(|r| Some((r.get_named::<i64>("id").ok()?, r.get_named("name").ok()?)))
+        .collect();
+
+    let mut activity: HashMap<i64, AgentActivity> = HashMap::new();
+    let activity_queries: [(&'static str, &str); 3] = [
+        (
+            "sent",
+            "SELECT sender_id AS agent_id, COUNT(*) AS n FROM messages \
+             WHERE project_id = ? AND created_ts >= ? GROUP BY sender_id",
+        ),
+        (
+            "received",
+            "SELECT r.agent_id AS agent_id, COUNT(*) AS n FROM message_recipients r \
+             JOIN messages m ON m.id = r.message_id \
+             WHERE m.project_id = ? AND m.created_ts >= ? GROUP BY r.agent_id",
+        ),
+        (
+            "reservations",
+            "SELECT agent_id, COUNT(*) AS n FROM file_reservations \
+             WHERE project_id = ? AND created_ts >= ? GROUP BY agent_id",
+        ),
+    ];
+    for (name, sql) in activity_queries {
+        for row in meter.run(name, sql, &window)? {
+            let (Ok(agent_id), Ok(n)) = (row.get_named::<i64>("agent_id"), row.get_named("n"))
+            else {
+                continue;
+            };
+            let Some(agent_name) = names.get(&agent_id) else {
+                continue;
+            };
+            let entry = activity.entry(agent_id).or_insert_with(|| AgentActivity {
+                name: agent_name.clone(),
+                ..AgentActivity::default()
+            });
+            match name {
+                "sent" => entry.sent = n,
+                "received" => entry.received = n,
+                _ => entry.reservations = n,
+            }
+        }
+    }
+
+    let agents_total = activity.len();
+    let mut ranked: Vec<(i64, AgentActivity)> = activity.into_iter().collect();
+    ranked.sort_by(|(_, l), (_, r)| r.total().cmp(&l.total()).then_with(|| l.name.cmp(&r.name)));
+    ranked.truncate(max_agents);
+    let index: HashMap<i64, usize> = ranked
+        .iter()
+        .enumerate()
+        .map(|(idx, (id, _))| (*id, idx))
+        .collect();
+    let agents: Vec<AgentActivity> = ranked.into_iter().map(|(_, a)| a).collect();
+
+    let mut report = CollaborationReport {
+        since_us,
+        max_agents,
+        agents_total,
+        agents,
+        pairs: Vec::new(),
+        messages: HashMap::new(),
+        replies: HashMap::new(),
+        queries: Vec::new(),
+    };
+    if index.len() < 2 {
+        report.queries = meter.queries;
+        return Ok(report);
+    }
+
+    let ids: Vec<i64> = index.keys().copied().collect();
+    let ph = id_placeholders(ids.len());
+    let mut params = window.to_vec();
+    params.extend(ids.iter().copied().map(Value::BigInt));
+    params.extend(ids.iter().copied().map(Value::BigInt));
+
+    for row in meter.run(
+        "messages",
+        &format!(
+            "SELECT m.sender_id AS from_id, r.agent_id AS to_id, COUNT(*) AS n \
+             FROM messages m JOIN message_recipients r ON r.message_id = m.id \
+             WHERE m.project_id = ? AND m.created_ts >= ? AND r.agent_id <> m.sender_id \
+               AND m.sender_id IN ({ph}) AND r.agent_id IN ({ph}) \
+             GROUP BY m.sender_id, r.agent_id"
+        ),
+        &params,
+    )? {
+        if let (Some(from), Some(to), Ok(n)) = (
+            pair_index(&index, &row, "from_id"),
+            pair_index(&index, &row, "to_id"),
+            row.get_named::<i64>("n"),
+        ) {
+            report.messages.insert((from, to), n);
+        }
+    }
+
+    for row in meter.run(
+        "replies",
+        &format!(
+            "SELECT m.sender_id AS from_id, r.agent_id AS to_id, m.created_ts AS sent_ts, \
+                    MIN(x.created_ts) AS reply_ts \
+             FROM messages m \
+             JOIN message_recipients r ON r.message_id = m.id \
+             JOIN messages x ON x.project_id = m.project_id AND x.thread_id = m.thread_id \
+               AND x.sender_id = r.agent_id AND x.created_ts > m.created_ts \
+             WHERE m.project_id = ? AND m.created_ts >= ? AND m.thread_id IS NOT NULL \
+               AND r.agent_id <> m.sender_id \
+               AND m.sender_id IN ({ph}) AND r.agent_id IN ({ph}) \
+             GROUP BY m.id, r.agent_id"
+        ),
+        &params,
+    )? {
+        if let (Some(from), Some(to), Ok(sent), Ok(reply)) = (
+            pair_index(&index, &row, "from_id"),
+            pair_index(&index, &row, "to_id"),
+            row.get_named::<i64>("sent_ts"),
+            row.get_named::<i64>("reply_ts"),
+        ) {
+            let slot = report.replies.entry((from, to)).or_insert((0, 0));
+            slot.0 = slot.0.saturating_add(reply.saturating_sub(sent));
+            slot.1 += 1;
+        }
+    }
+
+    let mut pairs: BTreeMap<(usize, usize), PairStats> = BTreeMap::new();
+    for row in meter.run(
+        "reservation_pairs",
+        &format!(
+            "SELECT a.agent_id AS a_id, b.agent_id AS b_id, \
+                    COUNT(DISTINCT a.path_pattern) AS shared_files, \
+                    SUM(CASE WHEN (a.\"exclusive\" = 1 OR b.\"exclusive\" = 1) \
+                          AND a.created_ts < COALESCE(rb.released_ts, b.released_ts, b.expires_ts) \
+                          AND b.created_ts < COALESCE(ra.released_ts, a.released_ts, a.expires_ts) \
+                        THEN 1 ELSE 0 END) AS conflicts \
+             FROM file_reservations a \
+             JOIN file_reservations b ON b.project_id = a.project_id \
+               AND b.path_pattern = a.path_pattern AND b.agent_id > a.agent_id \
+             LEFT JOIN file_reservation_releases ra ON ra.reservation_id = a.id \
+             LEFT JOIN file_reservation_releases rb ON rb.reservation_id = b.id \
+             WHERE a.project_id = ? AND a.created_ts >= ? AND b.created_ts >= ? \
+               AND a.agent_id IN ({ph}) AND b.agent_id IN ({ph}) \
+             GROUP BY a.agent_id, b.agent_id"
+        ),
+        &{
+            let mut p = params.clone();
+            p.insert(2, Value::BigInt(since_us));
+            p
+        },
+    )? {
+        if let (Some(a), Some(b)) = (
+            pair_index(&index, &row, "a_id"),
+            pair_index(&index, &row, "b_id"),
+        ) {
+            let pair = report.pair_entry(&mut pairs, a, b);
+            pair.shared_files = row.get_named("shared_files").unwrap_or(0);
+            pair.reservation_conflicts = row.get_named("conflicts").unwrap_or(0);
+        }
+    }
+
+    let message_keys: Vec<(usize, usize)> = report
+        .messages
+        .keys()
+        .chain(report.replies.keys())
+        .copied()
+        .collect();
+    for (from, to) in message_keys {
+        report.pair_entry(&mut pairs, from, to);
+    }
+    for (&(i, j), pair) in &mut pairs {
+        let (from_a, from_b) = if report.agents[i].name == pair.a {
+            ((i, j), (j, i))
+        } else {
+            ((j, i), (i, j))
+        };
+        pair.a_to_b = report.messages.get(&from_a).copied().unwrap_or(0);
+        pair.b_to_a = report.messages.get(&from_b).copied().unwrap_or(0);
+        let (sum, count) = [from_a, from_b]
+            .iter()
+            .filter_map(|key| report.replies.get(key))
+            .fold((0_i64, 0_i64), |(s, c), (ds, dc)| (s + ds, c + dc));
+        pair.replies = count;
+        pair.avg_reply_seconds = average_seconds(sum, count);
+    }
+
+    report.pairs = pairs.into_values().collect();
+    report.pairs.sort_by(|l, r| {
+        r.messages()
+            .cmp(&l.messages())
+            .then_with(|| r.reservation_conflicts.cmp(&l.reservation_conflicts))
+            .then_with(|| r.shared_files.cmp(&l.shared_files))
+            .then_with(|| (&l.a, &l.b).cmp(&(&r.a, &r.b)))
+    });
+    report.queries = meter.queries;
+    Ok(report)
+}
+
+fn pair_index(
+    index: &HashMap<i64, usize>,
+    row: &sqlmodel_core::Row,
+    column: &str,
+) -> Option<usize> {
+    row.get_named::<i64>(column)
+        .ok()
+        .and_then(|id| index.get(&id).copied())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn average_seconds(sum_us: i64, count: i64) -> Option<f64> {
+    (count > 0).then(|| (sum_us as f64 / count as f64 / 1_000_000.0 * 10.0).round() / 10.0)
+}
+
+impl CollaborationReport {
+    fn pair_entry<'p>(
+        &self,
+        pairs: &'p mut BTreeMap<(usize, usize), PairStats>,
+        x: usize,
+        y: usize,
+    ) -> &'p mut PairStats {
+        let key = (x.min(y), x.max(y));
+        pairs.entry(key).or_insert_with(|| {
+            let (a, b) = (&self.agents[key.0].name, &self.agents[key.1].name);
+            let (a, b) = if a <= b { (a, b) } else { (b, a) };
+            PairStats {
+                a: a.clone(),
+                b: b.clone(),
+                ..PairStats::default()
+            }
+        })
+    }
+
+    /// Total wall time spent in queries, in milliseconds.
+    #[must_use]
+    pub fn query_elapsed_ms(&self) -> f64 {
+        let total: u64 = self.queries.iter().map(|q| q.elapsed_us).sum();
+        #[allow(clippy::cast_precision_loss)]
+        let ms = total as f64 / 1_000.0;
+        (ms * 100.0).round() / 100.0
+    }
+
+    /// Square matrices indexed like `agents`: `messages[i][j]` counts mail
+    /// from agent i to agent j, `avg_reply_seconds[i][j]` is how long agent j
+    /// took on average to answer agent i, and the reservation matrices are
+    /// symmetric.
+    #[must_use]
+    pub fn matrix_json(&self) -> serde_json::Value {
+        let n = self.agents.len();
+        let mut messages = vec![vec![0_i64; n]; n];
+        let mut replies = vec![vec![None::<f64>; n]; n];
+        let mut conflicts = vec![vec![0_i64; n]; n];
+        let mut shared = vec![vec![0_i64; n]; n];
+        for (&(i, j), &count) in &self.messages {
+            messages[i][j] = count;
+        }
+        for (&(i, j), &(sum, count)) in &self.replies {
+            replies[i][j] = average_seconds(sum, count);
+        }
+        let position: HashMap<&str, usize> = self
+            .agents
+            .iter()
+            .enumerate()
+            .map(|(idx, a)| (a.name.as_str(), idx))
+            .collect();
+        for pair in &self.pairs {
+            let (i, j) = (position[pair.a.as_str()], position[pair.b.as_str()]);
+            conflicts[i][j] = pair.reservation_conflicts;
+            conflicts[j][i] = pair.reservation_conflicts;
+            shared[i][j] = pair.shared_files;
+            shared[j][i] = pair.shared_files;
+        }
+        serde_json::json!({
+            "agents": self.agents.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(),
+            "messages": messages,
+            "avg_reply_seconds": replies,
+            "reservation_conflicts": conflicts,
+            "shared_files": shared,
+        })
+    }
+
+    #[must_use]
+    pub fn to_json(&self, project: &str, window: &str) -> serde_json::Value {
+        serde_json::json!({
+            "project": project,
+            "window": window,
+            "since": mcp_agent_mail_db::micros_to_iso(self.since_us),
+            "max_agents": self.max_agents,
+            "agents_total": self.agents_total,
+            "agents_truncated": self.agents_total > self.agents.len(),
+            "agents": self.agents.iter().map(|a| serde_json::json!({
+                "name": a.name,
+                "sent": a.sent,
+                "received": a.received,
+                "reservations": a.reservations,
+                "activity": a.total(),
+            })).collect::<Vec<_>>(),
+            "pairs": self.pairs.iter().map(|p| serde_json::json!({
+                "a": p.a,
+                "b": p.b,
+                "a_to_b": p.a_to_b,
+                "b_to_a": p.b_to_a,
+                "messages": p.messages(),
+                "replies": p.replies,
+                "avg_reply_seconds": p.avg_reply_seconds,
+                "reservation_conflicts": p.reservation_conflicts,
+                "shared_files": p.shared_files,
+            })).collect::<Vec<_>>(),
+            "matrix": self.matrix_json(),
+            "query_cost": {
+                "queries": self.queries.len(),
+                "rows": self.queries.iter().map(|q| q.rows).sum::<usize>(),
+                "elapsed_ms": self.query_elapsed_ms(),
+                "detail": self.queries,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000_000_000;
+    const MINUTE: i64 = 60_000_000;
+
+    fn seeded_conn() -> DbConn {
+        let conn = DbConn::open_memory().expect("open memory db");
+        conn.execute_raw(&mcp_agent_mail_db::schema::init_schema_sql_base())
+            .expect("init schema");
+        conn.execute_raw("PRAGMA foreign_keys = OFF")
+            .expect("disable foreign keys for fixture");
+        conn.execute_raw(&format!(
+            "INSERT INTO projects (id, slug, human_key, created_at) VALUES (1, 'p', '/tmp/p', 0);
+             INSERT INTO agents
+               (id, project_id, name, program, model, task_description, inception_ts,
+                last_active_ts, attachments_policy, contact_policy)
+             VALUES (1, 1, 'BlueLake', 'codex-cli', 'gpt-5', '', 0, 0, 'auto', 'auto'),
+                    (2, 1, 'RedFox', 'codex-cli', 'gpt-5', '', 0, 0, 'auto', 'auto'),
+                    (3, 1, 'GreenCastle', 'codex-cli', 'gpt-5', '', 0, 0, 'auto', 'auto');
+             INSERT INTO messages (id, project_id, sender_id, thread_id, subject, body_md, created_ts)
+             VALUES (1, 1, 1, 't1', 'plan', 'b', {t0}),
+                    (2, 1, 2, 't1', 'Re: plan', 'b', {t2}),
+                    (3, 1, 1, 't1', 'Re: plan', 'b', {t6}),
+                    (4, 1, 3, NULL, 'fyi', 'b', {t0}),
+                    (5, 1, 2, 'old', 'stale', 'b', 0);
+             INSERT INTO message_recipients (message_id, agent_id, kind)
+             VALUES (1, 2, 'to'), (2, 1, 'to'), (3, 2, 'to'), (4, 1, 'to'), (5, 3, 'to');
+             INSERT INTO file_reservations
+               (id, project_id, agent_id, path_pattern, \"exclusive\", created_ts, expires_ts)
+             VALUES (1, 1, 1, 'src/db/**', 1, {t0}, {t10}),
+                    (2, 1, 2, 'src/db/**', 1, {t2}, {t10}),
+                    (3, 1, 1, 'docs/**', 0, {t0}, {t2}),
+                    (4, 1, 3, 'docs/**', 0, {t6}, {t10});",
+            t0 = NOW,
+            t2 = NOW + 2 * MINUTE,
+            t6 = NOW + 6 * MINUTE,
+            t10 = NOW + 10 * MINUTE,
+        ))
+        .expect("seed fixture");
+        conn
+    }
+
+    #[test]
+    fn report_counts_messages_replies_and_reservation_overlap_per_pair() {
+        let conn = seeded_conn();
+        let report = collaboration_report(&conn, 1, NOW, DEFAULT_MAX_AGENTS).expect("report");
+
+        assert_eq!(report.agents_total, 3);
+        assert_eq!(report.agents[0].name, "BlueLake");
+        assert_eq!(report.pairs.len(), 2);
+
+        let blue_red = &report.pairs[0];
+        assert_eq!(
+            (blue_red.a.as_str(), blue_red.b.as_str()),
+            ("BlueLake", "RedFox")
+        );
+        assert_eq!((blue_red.a_to_b, blue_red.b_to_a), (2, 1));
+        // RedFox answered in 2 minutes, BlueLake answered back in 4.
+        assert_eq!(blue_red.replies, 2);
+        assert_eq!(blue_red.avg_reply_seconds, Some(180.0));
+        assert_eq!(blue_red.reservation_conflicts, 1);
+        assert_eq!(blue_red.shared_files, 1);
+
+        let blue_green = &report.pairs[1];
+        assert_eq!(
+            (blue_green.a.as_str(), blue_green.b.as_str()),
+            ("BlueLake", "GreenCastle")
+        );
+        assert_eq!((blue_green.a_to_b, blue_green.b_to_a), (0, 1));
+        assert_eq!(blue_green.avg_reply_seconds, None);
+        // Shared, non-exclusive, and not overlapping in time.
+        assert_eq!(blue_green.shared_files, 1);
+        assert_eq!(blue_green.reservation_conflicts, 0);
+
+        let json = report.to_json("p", "30d");
+        let matrix = &json["matrix"];
+        let blue = 0;
+        let red = matrix["agents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .position(|n| n == "RedFox")
+            .unwrap();
+        assert_eq!(matrix["messages"][blue][red], 2);
+        assert_eq!(matrix["messages"][red][blue], 1);
+        assert_eq!(matrix["avg_reply_seconds"][blue][red], 120.0);
+        assert_eq!(matrix["avg_reply_seconds"][red][blue], 240.0);
+        assert_eq!(matrix["reservation_conflicts"][red][blue], 1);
+        assert_eq!(json["query_cost"]["queries"], 7);
+        assert_eq!(json["query_cost"]["detail"][0]["name"], "agents");
+    }
+
+    #[test]
+    fn agent_cap_keeps_the_most_active_agents() {
+        let conn = seeded_conn();
+        let report = collaboration_report(&conn, 1, NOW, 2).expect("report");
+
+        assert_eq!(report.agents_total, 3);
+        let names: Vec<&str> = report.agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["BlueLake", "RedFox"]);
+        assert_eq!(report.pairs.len(), 1);
+        assert_eq!(report.to_json("p", "30d")["agents_truncated"], true);
+    }
+}
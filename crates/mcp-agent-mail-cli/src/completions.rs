@@ -0,0 +1,292 @@
+//! Shell completion scripts for `am completions <shell>`.
+//!
+//! The static part of every script comes straight from `clap_complete`, so
+//! subcommands and flags never drift from the real command tree. On top of
+//! that each script gets a small shell-specific hook that completes *values*
+//! the parser cannot know about — project slugs, agent names, and benchmark
+//! names — by calling the hidden `am __complete <kind> <prefix>` helper.
+//!
+//! The helper is on the interactive path of every TAB press, so it runs under
+//! a hard [`COMPLETE_BUDGET`]: a missing database, a slow disk, or any error
+//! yields an empty candidate list instead of a blocked prompt.
+
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+/// Wall-clock budget for one `am __complete` lookup.
+pub const COMPLETE_BUDGET: Duration = Duration::from_millis(200);
+
+/// Upper bound on candidates printed by one `am __complete` call.
+const MAX_CANDIDATES: usize = 200;
+
+/// Shells `am completions` can emit a script for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+}
+
+impl CompletionShell {
+    const fn generator(self) -> clap_complete::Shell {
+        match self {
+            Self::Bash => clap_complete::Shell::Bash,
+            Self::Zsh => clap_complete::Shell::Zsh,
+            Self::Fish => clap_complete::Shell::Fish,
+            Self::Powershell => clap_complete::Shell::PowerShell,
+        }
+    }
+}
+
+/// Value families completed dynamically by `am __complete`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionKind {
+    /// Project slugs (`--project`, `--to-project`), read from the mailbox DB.
+    Projects,
+    /// Agent names (`--agent`, `--from`, `--to`), read from the mailbox DB.
+    Agents,
+    /// Benchmark names (`bench --filter`), from the built-in catalog.
+    Benchmarks,
+}
+
+/// Render the completion script for `shell`, including the dynamic hooks.
+#[must_use]
+pub fn render_script(shell: CompletionShell, cmd: &mut clap::Command, bin_name: &str) -> String {
+    let mut buf = Vec::new();
+    clap_complete::generate(shell.generator(), cmd, bin_name, &mut buf);
+    let script = String::from_utf8_lossy(&buf).into_owned();
+    match shell {
+        CompletionShell::Bash => with_bash_hook(script, bin_name),
+        CompletionShell::Zsh => with_zsh_hook(script, bin_name),
+        CompletionShell::Fish => with_fish_hook(script, bin_name),
+        CompletionShell::Powershell => with_powershell_hook(script, bin_name),
+    }
+}
+
+/// Bash: wrap the generated `_<bin>` function and re-register the wrapper.
+fn with_bash_hook(mut script: String, bin: &str) -> String {
+    script.push_str(&format!(
+        r#"
+_{bin}_dynamic() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}" kind=""
+    case "${{prev}}" in
+        --project|--to-project) kind=projects ;;
+        --agent|--from|--to) kind=agents ;;
+        --filter) [[ " ${{COMP_WORDS[*]}} " == *" bench "* ]] && kind=benchmarks ;;
+    esac
+    if [[ -n "${{kind}}" ]]; then
+        COMPREPLY=( $(compgen -W "$({bin} __complete "${{kind}}" "${{cur}}" 2>/dev/null)" -- "${{cur}}") )
+        return 0
+    fi
+    _{bin} "$@"
+}}
+complete -F _{bin}_dynamic -o bashdefault -o default {bin}
+"#
+    ));
+    script
+}
+
+/// Zsh: replace the generated dispatch trailer so both the `fpath` autoload
+/// and the `source` install paths bind the dynamic wrapper (the autoloaded
+/// `_<bin>` redefines itself on first use, so the wrapper must be re-bound).
+fn with_zsh_hook(mut script: String, bin: &str) -> String {
+    let trailer = format!("if [ \"$funcstack[1]\" = \"_{bin}\" ]; then");
+    if let Some(idx) = script.rfind(&trailer) {
+        script.truncate(idx);
+    }
+    script.push_str(&format!(
+        r#"_{bin}_dynamic() {{
+    local kind=""
+    case "${{words[CURRENT-1]}}" in
+        --project|--to-project) kind=projects ;;
+        --agent|--from|--to) kind=agents ;;
+        --filter) (( ${{words[(I)bench]}} )) && kind=benchmarks ;;
+    esac
+    if [[ -n "$kind" ]]; then
+        local -a values
+        values=(${{(f)"$({bin} __complete "$kind" "$PREFIX" 2>/dev/null)"}})
+        compadd -a values
+        return
+    fi
+    _{bin} "$@"
+}}
+
+compdef _{bin}_dynamic {bin}
+if [ "$funcstack[1]" = "_{bin}" ]; then
+    _{bin}_dynamic "$@"
+fi
+"#
+    ));
+    script
+}
+
+/// Fish: option-value completions merge with the generated ones.
+fn with_fish_hook(mut script: String, bin: &str) -> String {
+    script.push_str(&format!(
+        r#"complete -c {bin} -l project -l to-project -r -f -a '({bin} __complete projects (commandline -ct) 2>/dev/null)'
+complete -c {bin} -l agent -l from -l to -r -f -a '({bin} __complete agents (commandline -ct) 2>/dev/null)'
+complete -c {bin} -n '__fish_seen_subcommand_from bench' -l filter -r -f -a '({bin} __complete benchmarks (commandline -ct) 2>/dev/null)'
+"#
+    ));
+    script
+}
+
+/// PowerShell: keep the generated completer as a script block and register a
+/// wrapper that answers value positions first.
+fn with_powershell_hook(script: String, bin: &str) -> String {
+    let register =
+        format!("Register-ArgumentCompleter -Native -CommandName '{bin}' -ScriptBlock {{");
+    let static_var = format!("${bin}StaticCompleter");
+    let mut script = script.replacen(&register, &format!("{static_var} = {{"), 1);
+    script.push_str(&format!(
+        r#"
+Register-ArgumentCompleter -Native -CommandName '{bin}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $tokens = @($commandAst.CommandElements | ForEach-Object {{ $_.ToString() }})
+    $prev = if ($wordToComplete) {{ $tokens[-2] }} else {{ $tokens[-1] }}
+    $kind = switch ($prev) {{
+        {{ $_ -in '--project', '--to-project' }} {{ 'projects' }}
+        {{ $_ -in '--agent', '--from', '--to' }} {{ 'agents' }}
+        '--filter' {{ if ($tokens -contains 'bench') {{ 'benchmarks' }} }}
+    }}
+    if ($kind) {{
+        {bin} __complete $kind $wordToComplete 2>$null | ForEach-Object {{
+            [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+        }}
+        return
+    }}
+    & {static_var} $wordToComplete $commandAst $cursorPosition
+}}
+"#
+    ));
+    script
+}
+
+/// Candidates for `kind` starting with `prefix`, or nothing once
+/// [`COMPLETE_BUDGET`] elapses.
+///
+/// The lookup runs on a detached thread; a lookup that misses the budget is
+/// abandoned and dies with the process.
+#[must_use]
+pub fn candidates(kind: CompletionKind, prefix: &str) -> Vec<String> {
+    if kind == CompletionKind::Benchmarks {
+        return filter_prefix(
+            crate::bench::DEFAULT_BENCHMARKS
+                .iter()
+                .map(|b| b.name.to_string()),
+            prefix,
+        );
+    }
+    let (tx, rx) = mpsc::channel();
+    let prefix_owned = prefix.to_string();
+    let spawned = std::thread::Builder::new()
+        .name("am-complete".to_string())
+        .spawn(move || {
+            let _ = tx.send(db_candidates(kind, &prefix_owned));
+        });
+    if spawned.is_err() {
+        return Vec::new();
+    }
+    rx.recv_timeout(COMPLETE_BUDGET).unwrap_or_default()
+}
+
+fn filter_prefix(names: impl Iterator<Item = String>, prefix: &str) -> Vec<String> {
+    let mut out: Vec<String> = names.filter(|name| name.starts_with(prefix)).collect();
+    out.sort();
+    out.dedup();
+    out.truncate(MAX_CANDIDATES);
+    out
+}
+
+/// Read-only DB lookup; every failure (including a DB that does not exist
+/// yet) is an empty list.
+fn db_candidates(kind: CompletionKind, prefix: &str) -> Vec<String> {
+    let cfg = mcp_agent_mail_db::DbPoolConfig::from_env();
+    let Ok(path) = cfg.sqlite_path() else {
+        return Vec::new();
+    };
+    let path = crate::resolve_sqlite_path_with_absolute_candidate(&path);
+    if path == ":memory:" || !Path::new(&path).exists() {
+        return Vec::new();
+    }
+    let Ok(conn) = crate::open_db_sync_read_only_with_database_url(&cfg.database_url) else {
+        return Vec::new();
+    };
+    let sql = match kind {
+        CompletionKind::Projects => "SELECT DISTINCT slug AS name FROM projects",
+        CompletionKind::Agents => "SELECT DISTINCT name FROM agents",
+        CompletionKind::Benchmarks => return Vec::new(),
+    };
+    let Ok(rows) = conn.query_sync(sql, &[]) else {
+        return Vec::new();
+    };
+    filter_prefix(
+        rows.iter()
+            .filter_map(|row| row.get_named::<String>("name").ok()),
+        prefix,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn script_for(shell: CompletionShell) -> String {
+        let mut cmd = crate::Cli::command();
+        render_script(shell, &mut cmd, "am")
+    }
+
+    #[test]
+    fn every_shell_script_lists_top_level_commands() {
+        let top_level: Vec<String> = crate::Cli::command()
+            .get_subcommands()
+            .filter(|sub| !sub.is_hide_set())
+            .map(|sub| sub.get_name().to_string())
+            .collect();
+        assert!(top_level.iter().any(|name| name == "completions"));
+        for shell in CompletionShell::value_variants() {
+            let script = script_for(*shell);
+            for name in &top_level {
+                assert!(
+                    script.contains(name.as_str()),
+                    "{shell:?} completion script is missing top-level command `{name}`"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_shell_script_wires_the_dynamic_helper() {
+        for shell in CompletionShell::value_variants() {
+            let script = script_for(*shell);
+            for kind in ["projects", "agents", "benchmarks"] {
+                assert!(
+                    script.contains(&format!("__complete {kind}"))
+                        || script.contains(&format!("kind={kind}"))
+                        || script.contains(&format!("'{kind}'")),
+                    "{shell:?} script does not complete {kind}"
+                );
+            }
+        }
+        assert!(script_for(CompletionShell::Bash).contains("complete -F _am_dynamic"));
+        assert!(script_for(CompletionShell::Zsh).contains("compdef _am_dynamic am"));
+        assert!(script_for(CompletionShell::Powershell).contains("$amStaticCompleter = {"));
+    }
+
+    #[test]
+    fn benchmark_candidates_filter_by_prefix() {
+        let all = candidates(CompletionKind::Benchmarks, "");
+        assert!(all.iter().any(|name| name == "help"));
+        let help = candidates(CompletionKind::Benchmarks, "hel");
+        assert!(!help.is_empty());
+        assert!(help.iter().all(|name| name.starts_with("hel")));
+        assert!(candidates(CompletionKind::Benchmarks, "zz-no-such-bench").is_empty());
+    }
+}
@@ -164,6 +164,29 @@ fn query_project_by_slug(
         .transpose()
 }
 
+fn query_projects_by_slug_all(
+    conn: &mcp_agent_mail_db::DbConn,
+    slug: &str,
+) -> CliResult<Vec<ResolvedProject>> {
+    let rows = conn
+        .query_sync(
+            "SELECT id, slug, human_key FROM projects WHERE slug = ? COLLATE NOCASE ORDER BY id",
+            &[sqlmodel_core::Value::Text(slug.to_string())],
+        )
+        .map_err(|e| CliError::Other(format!("project query failed: {e}")))?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(ResolvedProject {
+                id: require_i64_column(row, "id", "projects")?,
+                slug: require_text_column(row, "slug", "projects")?,
+                human_key: require_text_column(row, "human_key", "projects")?,
+                created_at: 0,
+            })
+        })
+        .collect()
+}
+
 fn query_project_by_human_key(
     conn: &mcp_agent_mail_db::DbConn,
     human_key: &str,
@@ -300,41 +323,59 @@ fn project_matches_absolute_lookup(
     stored.human_key == requested.human_key || stored.canonical_path == requested.canonical_path
 }
 
-/// Look up a project by slug or `human_key`.
+/// Look up a project by slug, `human_key`, or filesystem path.
 ///
 /// For absolute filesystem paths, prefer exact/canonical `human_key` matching
 /// before accepting a slug hit so slug collisions cannot resolve to the wrong
-/// project.
+/// project. Relative keys try slug and `human_key` first, then fall back to
+/// the path they name (relative to the working directory) when it exists.
 pub fn resolve_project(conn: &mcp_agent_mail_db::DbConn, key: &str) -> CliResult<ResolvedProject> {
+    let cwd = std::env::current_dir().ok();
+    resolve_project_from(conn, key, cwd.as_deref())
+}
+
+/// Like [`resolve_project`], returning only the project id.
+pub fn resolve_project_id(conn: &mcp_agent_mail_db::DbConn, key: &str) -> CliResult<i64> {
+    resolve_project(conn, key).map(|project| project.id)
+}
+
+/// Absolute form of a relative, path-looking project key that exists on disk
+/// under `cwd`. Bare words (`backend`) are not treated as paths.
+pub fn absolutize_relative_project_path(key: &str, cwd: Option<&Path>) -> Option<String> {
+    let path = Path::new(key);
+    if path.is_absolute() || !(key.starts_with('.') || key.contains(std::path::MAIN_SEPARATOR)) {
+        return None;
+    }
+    let absolute = cwd?.join(path);
+    absolute
+        .exists()
+        .then(|| absolute.canonicalize().unwrap_or(absolute))
+        .map(|p| p.display().to_string())
+}
+
+fn resolve_project_from(
+    conn: &mcp_agent_mail_db::DbConn,
+    key: &str,
+    cwd: Option<&Path>,
+) -> CliResult<ResolvedProject> {
     let key = key.trim();
     if Path::new(key).is_absolute() {
-        let requested = resolve_project_identity(key);
-        if let Some(project) = query_project_by_human_key(conn, &requested.human_key)? {
-            return Ok(project);
-        }
-        if requested.human_key != key
-            && let Some(project) = query_project_by_human_key(conn, key)?
-        {
-            return Ok(project);
-        }
-        if let Some(project) = query_project_by_slug(conn, &requested.slug)?
-            && project_matches_absolute_lookup(&project, &requested)
-        {
-            return Ok(project);
-        }
-    } else {
-        if let Some(project) = query_project_by_slug(conn, key)? {
-            return Ok(project);
-        }
-        if let Some(project) = query_project_by_human_key(conn, key)? {
-            return Ok(project);
-        }
-        if let Some(project) = list_project_inventory(conn)?
-            .into_iter()
-            .find(|project| project.slug.eq_ignore_ascii_case(key) || project.human_key == key)
-        {
-            return Ok(project);
-        }
+        return resolve_project_by_absolute_path(conn, key);
+    }
+    if let Some(project) = query_project_by_slug(conn, key)? {
+        return Ok(project);
+    }
+    if let Some(project) = query_project_by_human_key(conn, key)? {
+        return Ok(project);
+    }
+    if let Some(project) = list_project_inventory(conn)?
+        .into_iter()
+        .find(|project| project.slug.eq_ignore_ascii_case(key) || project.human_key == key)
+    {
+        return Ok(project);
+    }
+    if let Some(absolute) = absolutize_relative_project_path(key, cwd) {
+        return resolve_project_by_absolute_path(conn, &absolute);
     }
 
     Err(CliError::InvalidArgument(format!(
@@ -342,6 +383,45 @@ pub fn resolve_project(conn: &mcp_agent_mail_db::DbConn, key: &str) -> CliResult
     )))
 }
 
+fn resolve_project_by_absolute_path(
+    conn: &mcp_agent_mail_db::DbConn,
+    key: &str,
+) -> CliResult<ResolvedProject> {
+    let requested = resolve_project_identity(key);
+    if let Some(project) = query_project_by_human_key(conn, &requested.human_key)? {
+        return Ok(project);
+    }
+    if requested.human_key != key
+        && let Some(project) = query_project_by_human_key(conn, key)?
+    {
+        return Ok(project);
+    }
+    let slug_matches = query_projects_by_slug_all(conn, &requested.slug)?;
+    if let Some(project) = slug_matches
+        .iter()
+        .find(|project| project_matches_absolute_lookup(project, &requested))
+    {
+        return Ok(project.clone());
+    }
+    if slug_matches.is_empty() {
+        return Err(CliError::InvalidArgument(format!(
+            "project not found: {key}"
+        )));
+    }
+
+    // The path's slug is taken by other project(s): say so rather than
+    // silently reporting nothing, and never guess between them.
+    let candidates = slug_matches
+        .iter()
+        .map(|project| format!("{} ({})", project.human_key, project.slug))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(CliError::InvalidArgument(format!(
+        "project not found: {key}; its slug '{}' belongs to a different project: {candidates}",
+        requested.slug
+    )))
+}
+
 // ── Agent resolution ────────────────────────────────────────────────────
 
 /// A resolved agent identity.
@@ -832,6 +912,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_project_slug_collision_lists_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let url = format!("sqlite:///{}", db_path.display());
+        let storage_root_str = dir.path().display().to_string();
+        mcp_agent_mail_core::config::with_process_env_overrides_for_test(
+            &[("STORAGE_ROOT", &storage_root_str)],
+            || {
+                let ctx = CliContext::open_with_url(&url).unwrap();
+                let project_a = dir.path().join("repo").join("a-b");
+                let project_b = dir.path().join("repo").join("a").join("b");
+                std::fs::create_dir_all(&project_a).unwrap();
+                std::fs::create_dir_all(&project_b).unwrap();
+                let project_a_key = project_a.canonicalize().unwrap().display().to_string();
+                let project_b_key = project_b.canonicalize().unwrap().display().to_string();
+                let slug = resolve_project_identity(&project_a_key).slug;
+
+                ctx.conn
+                    .execute_raw(&format!(
+                        "INSERT INTO projects (slug, human_key, created_at) VALUES ('{slug}', '{project_a_key}', 1000000)"
+                    ))
+                    .unwrap();
+
+                let err = ctx.resolve_project(&project_b_key).unwrap_err().to_string();
+                assert!(err.contains(&format!("slug '{slug}'")), "unexpected: {err}");
+                assert!(err.contains(&project_a_key), "candidate missing: {err}");
+            },
+        );
+    }
+
+    #[test]
+    fn resolve_project_by_relative_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let url = format!("sqlite:///{}", db_path.display());
+        let storage_root_str = dir.path().display().to_string();
+        mcp_agent_mail_core::config::with_process_env_overrides_for_test(
+            &[("STORAGE_ROOT", &storage_root_str)],
+            || {
+                let ctx = CliContext::open_with_url(&url).unwrap();
+                let project = dir.path().join("projects").join("backend");
+                std::fs::create_dir_all(&project).unwrap();
+                let identity = resolve_project_identity(
+                    &project.canonicalize().unwrap().display().to_string(),
+                );
+                ctx.conn
+                    .execute_raw(&format!(
+                        "INSERT INTO projects (slug, human_key, created_at) VALUES ('{}', '{}', 1000000)",
+                        identity.slug, identity.human_key
+                    ))
+                    .unwrap();
+
+                let cwd = dir.path().join("projects");
+                let proj = resolve_project_from(&ctx.conn, "./backend", Some(&cwd)).unwrap();
+                assert_eq!(proj.human_key, identity.human_key);
+                let proj =
+                    resolve_project_from(&ctx.conn, "projects/backend", Some(dir.path())).unwrap();
+                assert_eq!(proj.slug, identity.slug);
+                assert_eq!(
+                    resolve_project_id(&ctx.conn, &identity.human_key).unwrap(),
+                    proj.id
+                );
+
+                // Bare words and missing paths are never treated as paths.
+                assert!(resolve_project_from(&ctx.conn, "backend", Some(&cwd)).is_err());
+                assert!(resolve_project_from(&ctx.conn, "./missing", Some(&cwd)).is_err());
+            },
+        );
+    }
+
     #[test]
     fn resolve_agent_not_found() {
         let dir = tempfile::tempdir().unwrap();
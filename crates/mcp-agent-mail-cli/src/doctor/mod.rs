@@ -46,7 +46,11 @@ pub fn handle_capabilities(format: Option<CliOutputFormat>) -> CliResult<()> {
 
     let fmt = format.unwrap_or(CliOutputFormat::Json);
     match fmt {
-        CliOutputFormat::Json | CliOutputFormat::Toon | CliOutputFormat::Table => {
+        CliOutputFormat::Json
+        | CliOutputFormat::Toon
+        | CliOutputFormat::Table
+        | CliOutputFormat::Csv
+        | CliOutputFormat::Markdown => {
             // Capabilities is a contract — always JSON regardless of format
             // request. (TOON would erase types; table is lossy.)
             let json = serde_json::to_string_pretty(&report)
@@ -332,7 +336,11 @@ fn emit_explain_envelope(
     format: Option<CliOutputFormat>,
 ) -> CliResult<()> {
     match format.unwrap_or(CliOutputFormat::Json) {
-        CliOutputFormat::Json | CliOutputFormat::Toon | CliOutputFormat::Table => {
+        CliOutputFormat::Json
+        | CliOutputFormat::Toon
+        | CliOutputFormat::Table
+        | CliOutputFormat::Csv
+        | CliOutputFormat::Markdown => {
             let pretty = serde_json::to_string_pretty(envelope)
                 .map_err(|e| CliError::Other(format!("serializing explain: {e}")))?;
             println!("{pretty}");
@@ -384,7 +392,13 @@ pub fn handle_fixers(format: Option<CliOutputFormat>) -> CliResult<()> {
         }
     });
     match fmt {
-        CliOutputFormat::Json | CliOutputFormat::Toon => {
+        CliOutputFormat::Json
+        | CliOutputFormat::Toon
+        | CliOutputFormat::Csv
+        | CliOutputFormat::Markdown => {
+            if fmt.is_tabular() {
+                crate::output::warn_tabular_fallback(fmt);
+            }
             let envelope = serde_json::json!({
                 "schema_version": "1.0",
                 "doctor_contract_version": runs::DOCTOR_CONTRACT_VERSION,
@@ -1370,7 +1384,11 @@ pub fn handle_selftest(format: Option<CliOutputFormat>) -> CliResult<()> {
     });
 
     match format.unwrap_or(CliOutputFormat::Json) {
-        CliOutputFormat::Json | CliOutputFormat::Toon | CliOutputFormat::Table => {
+        CliOutputFormat::Json
+        | CliOutputFormat::Toon
+        | CliOutputFormat::Table
+        | CliOutputFormat::Csv
+        | CliOutputFormat::Markdown => {
             let s = serde_json::to_string_pretty(&envelope)
                 .map_err(|e| CliError::Other(format!("serializing selftest: {e}")))?;
             println!("{s}");
@@ -1442,7 +1460,10 @@ pub fn handle_support_bundle(
         format.unwrap_or(CliOutputFormat::Table)
     };
     match format {
-        CliOutputFormat::Json => {
+        CliOutputFormat::Json | CliOutputFormat::Csv | CliOutputFormat::Markdown => {
+            if format.is_tabular() {
+                crate::output::warn_tabular_fallback(format);
+            }
             let body = serde_json::to_string_pretty(&result)
                 .map_err(|err| CliError::Other(format!("serializing support bundle: {err}")))?;
             println!("{body}");
@@ -2556,7 +2577,10 @@ pub fn handle_ls(target: &std::path::Path, format: Option<CliOutputFormat>) -> C
         }
     });
     match fmt {
-        CliOutputFormat::Json => {
+        CliOutputFormat::Json | CliOutputFormat::Csv | CliOutputFormat::Markdown => {
+            if fmt.is_tabular() {
+                crate::output::warn_tabular_fallback(fmt);
+            }
             let json = serde_json::to_string_pretty(&serde_json::json!({
                 "schema_version": "1.0",
                 "runs": runs,
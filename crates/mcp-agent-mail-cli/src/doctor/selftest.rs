@@ -616,6 +616,10 @@ fn run_selftest_sequence_in_process(project_key: &str) -> WriteSelftestReport {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         {
@@ -665,6 +669,8 @@ fn run_selftest_sequence_in_process(project_key: &str) -> WriteSelftestReport {
             Some(60),
             Some(true),
             Some("doctor write-selftest".to_string()),
+            None,
+            None,
         )
         .await
         {
@@ -682,6 +688,7 @@ fn run_selftest_sequence_in_process(project_key: &str) -> WriteSelftestReport {
             SENDER.to_string(),
             Some(vec!["src/**".to_string()]),
             None,
+            None,
         )
         .await
         {
@@ -695,7 +702,7 @@ fn run_selftest_sequence_in_process(project_key: &str) -> WriteSelftestReport {
         // 8: list_agents (read-after-write). limit/active_within_days are the
         // pagination/filter params added to the tool; the selftest smoke check
         // wants the full roster, so pass None/None.
-        match identity::list_agents(&ctx, project_key.clone(), None, None).await {
+        match identity::list_agents(&ctx, project_key.clone(), None, None, None).await {
             Ok(_) => steps.push(StepResult::passed("list_agents")),
             Err(e) => {
                 steps.push(StepResult::failed("list_agents", e.message));
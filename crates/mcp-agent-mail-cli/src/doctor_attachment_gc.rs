@@ -0,0 +1,621 @@
+//! Content-addressed attachment upkeep for `am doctor gc-attachments` and
+//! `am doctor repair --migrate-attachments` / `--revert-attachments`.
+//!
+//! File attachments are stored once per distinct content at
+//! `projects/<slug>/attachments/<sha256[..2]>/<sha256[2..]>/blob`, and each
+//! message references its blobs by `path` and `sha256` in the `attachments`
+//! JSON column. There is no refcount column: a blob is live exactly while some
+//! message row references it, so deleting messages (retention, prune, reset)
+//! just drops references and the GC pass reclaims the bytes afterwards.
+//!
+//! The migration copies older per-message copies under `attachments/files/`
+//! into blobs, repoints the message references, and writes a mapping file with
+//! every rewritten reference. Legacy files are left in place, so
+//! `--revert-attachments <mapping>` restores the previous references exactly.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use mcp_agent_mail_core::config::Config;
+use mcp_agent_mail_db::sqlmodel::Value as SqlValue;
+use sha2::{Digest as _, Sha256};
+
+use crate::{CliError, CliResult};
+
+/// Blobs younger than this are never collected: a concurrent send writes the
+/// blob before it records the message reference.
+pub const GC_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Version tag written into migration mapping files.
+const MAPPING_VERSION: u32 = 1;
+
+/// One message's parsed `attachments` array.
+#[derive(Debug, Clone)]
+pub struct MessageAttachments {
+    pub message_id: i64,
+    pub project_slug: String,
+    pub attachments: Vec<serde_json::Value>,
+}
+
+/// Every attachment reference in the mailbox.
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentRows {
+    pub messages: Vec<MessageAttachments>,
+    /// `(message_id, raw)` for rows whose JSON did not parse. GC treats any
+    /// sha256 appearing in the raw text as referenced.
+    pub unparsed: Vec<(i64, String)>,
+}
+
+pub fn load_attachment_rows(conn: &mcp_agent_mail_db::DbConn) -> CliResult<AttachmentRows> {
+    let mut rows_out = AttachmentRows::default();
+    let mut last_id = 0i64;
+    loop {
+        let rows = conn
+            .query_sync(
+                "SELECT m.id AS id, p.slug AS slug, m.attachments AS attachments \
+                 FROM messages m JOIN projects p ON p.id = m.project_id \
+                 WHERE m.attachments != '[]' AND m.attachments != '' AND m.id > ? \
+                 ORDER BY m.id ASC LIMIT 500",
+                &[SqlValue::BigInt(last_id)],
+            )
+            .map_err(|e| CliError::Other(format!("attachment reference scan failed: {e}")))?;
+        if rows.is_empty() {
+            break;
+        }
+        for row in &rows {
+            let message_id: i64 = row.get_named("id").unwrap_or(0);
+            last_id = message_id;
+            let raw: String = row.get_named("attachments").unwrap_or_default();
+            match serde_json::from_str::<serde_json::Value>(&raw) {
+                Ok(serde_json::Value::Array(attachments)) => {
+                    rows_out.messages.push(MessageAttachments {
+                        message_id,
+                        project_slug: row.get_named("slug").unwrap_or_default(),
+                        attachments,
+                    });
+                }
+                _ => rows_out.unparsed.push((message_id, raw)),
+            }
+        }
+    }
+    Ok(rows_out)
+}
+
+/// Paths and hashes the mailbox still points at.
+#[derive(Debug, Clone, Default)]
+pub struct AttachmentReferences {
+    paths: BTreeSet<String>,
+    sha256: BTreeSet<String>,
+    unparsed: Vec<String>,
+}
+
+impl AttachmentReferences {
+    #[must_use]
+    pub fn collect(rows: &AttachmentRows) -> Self {
+        let mut refs = Self::default();
+        for message in &rows.messages {
+            for attachment in &message.attachments {
+                let field = |key: &str| attachment.get(key).and_then(serde_json::Value::as_str);
+                for key in ["path", "original_path"] {
+                    if let Some(path) = field(key) {
+                        refs.paths.insert(path.to_string());
+                    }
+                }
+                if field("type") != Some("inline")
+                    && let Some(sha) = field("sha256")
+                {
+                    refs.sha256.insert(sha.to_ascii_lowercase());
+                }
+            }
+        }
+        refs.unparsed = rows
+            .unparsed
+            .iter()
+            .map(|(_, raw)| raw.to_ascii_lowercase())
+            .collect();
+        refs
+    }
+
+    fn references(&self, rel_path: &str, sha256: &str) -> bool {
+        self.paths.contains(rel_path)
+            || self.sha256.contains(sha256)
+            || self.unparsed.iter().any(|raw| raw.contains(sha256))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GcCandidate {
+    pub project: String,
+    pub sha256: String,
+    /// Path relative to the storage root.
+    pub path: String,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct GcReport {
+    pub blobs_scanned: usize,
+    pub referenced: usize,
+    /// Unreferenced but younger than [`GC_GRACE_PERIOD`].
+    pub recent: usize,
+    pub unreferenced: Vec<GcCandidate>,
+    pub bytes_reclaimable: u64,
+}
+
+/// Find blobs no message references, across every project archive.
+pub fn plan_gc(
+    storage_root: &Path,
+    refs: &AttachmentReferences,
+    now: SystemTime,
+) -> CliResult<GcReport> {
+    let mut report = GcReport::default();
+    for slug in project_slugs(storage_root)? {
+        let project_root = storage_root.join("projects").join(&slug);
+        let blobs = mcp_agent_mail_storage::list_attachment_blobs(&project_root)
+            .map_err(|e| CliError::Other(format!("scan {}: {e}", project_root.display())))?;
+        for blob in blobs {
+            report.blobs_scanned += 1;
+            let rel = format!(
+                "projects/{slug}/{}",
+                mcp_agent_mail_storage::attachment_blob_rel_path(&blob.sha256)
+            );
+            if refs.references(&rel, &blob.sha256) {
+                report.referenced += 1;
+                continue;
+            }
+            let age = fs::metadata(&blob.path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            if age < GC_GRACE_PERIOD {
+                report.recent += 1;
+                continue;
+            }
+            report.bytes_reclaimable += blob.bytes;
+            report.unreferenced.push(GcCandidate {
+                project: slug.clone(),
+                sha256: blob.sha256,
+                path: rel,
+                bytes: blob.bytes,
+            });
+        }
+    }
+    Ok(report)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GcApplyOutcome {
+    pub removed: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
+/// Delete the planned blobs and prune the directories they leave empty.
+#[must_use]
+pub fn apply_gc(storage_root: &Path, report: &GcReport) -> GcApplyOutcome {
+    let mut outcome = GcApplyOutcome::default();
+    for candidate in &report.unreferenced {
+        let path = storage_root.join(&candidate.path);
+        match fs::remove_file(&path) {
+            Ok(()) => {
+                // `<sha[2..]>/` then `<sha[..2]>/`; non-empty dirs stay.
+                let mut dir = path.parent();
+                for _ in 0..2 {
+                    let Some(current) = dir else { break };
+                    if fs::remove_dir(current).is_err() {
+                        break;
+                    }
+                    dir = current.parent();
+                }
+                outcome.removed.push(candidate.path.clone());
+            }
+            Err(e) => outcome.failed.push((candidate.path.clone(), e.to_string())),
+        }
+    }
+    outcome
+}
+
+fn project_slugs(storage_root: &Path) -> CliResult<Vec<String>> {
+    let projects = storage_root.join("projects");
+    let entries = match fs::read_dir(&projects) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut slugs: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    slugs.sort();
+    Ok(slugs)
+}
+
+/// One rewritten attachment reference.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MigrationEntry {
+    pub message_id: i64,
+    /// Position in the message's `attachments` array.
+    pub index: usize,
+    pub from: String,
+    pub to: String,
+    pub sha256: String,
+}
+
+/// Mapping file written by `--migrate-attachments` and read by
+/// `--revert-attachments`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MigrationMapping {
+    pub version: u32,
+    pub created_at: String,
+    pub storage_root: PathBuf,
+    pub entries: Vec<MigrationEntry>,
+}
+
+impl MigrationMapping {
+    #[must_use]
+    pub fn new(storage_root: &Path, entries: Vec<MigrationEntry>) -> Self {
+        Self {
+            version: MAPPING_VERSION,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            storage_root: storage_root.to_path_buf(),
+            entries,
+        }
+    }
+
+    pub fn read(path: &Path) -> CliResult<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| CliError::Other(format!("Failed to read {}: {e}", path.display())))?;
+        let mapping: Self = serde_json::from_str(&text).map_err(|e| {
+            CliError::InvalidArgument(format!("{} is not a mapping file: {e}", path.display()))
+        })?;
+        if mapping.version != MAPPING_VERSION {
+            return Err(CliError::InvalidArgument(format!(
+                "{}: unsupported mapping version {}",
+                path.display(),
+                mapping.version
+            )));
+        }
+        Ok(mapping)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MigrationSkip {
+    pub message_id: i64,
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MigrationPlan {
+    pub entries: Vec<MigrationEntry>,
+    pub skipped: Vec<MigrationSkip>,
+}
+
+/// Attachments whose `path` is a legacy `<project>/attachments/files/...`
+/// copy, with the blob each one would move to.
+#[must_use]
+pub fn plan_migration(storage_root: &Path, rows: &AttachmentRows) -> MigrationPlan {
+    let mut plan = MigrationPlan::default();
+    for message in &rows.messages {
+        for (index, attachment) in message.attachments.iter().enumerate() {
+            let Some(path) = attachment.get("path").and_then(serde_json::Value::as_str) else {
+                continue;
+            };
+            let Some(slug) = legacy_file_project(path) else {
+                continue;
+            };
+            let skip = |reason: String| MigrationSkip {
+                message_id: message.message_id,
+                path: path.to_string(),
+                reason,
+            };
+            let content = match fs::read(storage_root.join(path)) {
+                Ok(content) if !content.is_empty() => content,
+                Ok(_) => {
+                    plan.skipped.push(skip("file is empty".to_string()));
+                    continue;
+                }
+                Err(e) => {
+                    plan.skipped.push(skip(e.to_string()));
+                    continue;
+                }
+            };
+            let sha256 = hex::encode(Sha256::digest(&content));
+            plan.entries.push(MigrationEntry {
+                message_id: message.message_id,
+                index,
+                from: path.to_string(),
+                to: format!(
+                    "projects/{slug}/{}",
+                    mcp_agent_mail_storage::attachment_blob_rel_path(&sha256)
+                ),
+                sha256,
+            });
+        }
+    }
+    plan
+}
+
+/// Project slug of a `projects/<slug>/attachments/files/...` path, or `None`
+/// when the path is not a legacy per-message copy.
+fn legacy_file_project(path: &str) -> Option<&str> {
+    let mut parts = path.split('/');
+    if parts.next() != Some("projects") {
+        return None;
+    }
+    let slug = parts.next().filter(|s| !s.is_empty() && *s != "..")?;
+    if parts.next() != Some("attachments") || parts.next() != Some("files") {
+        return None;
+    }
+    let rest: Vec<&str> = parts.collect();
+    (!rest.is_empty()
+        && rest
+            .iter()
+            .all(|p| !p.is_empty() && *p != ".." && *p != "."))
+    .then_some(slug)
+}
+
+/// Write the blobs for `entries` and return the archive paths to commit.
+pub fn write_migration_blobs(
+    config: &Config,
+    entries: &[MigrationEntry],
+) -> CliResult<Vec<String>> {
+    let mut archives = BTreeMap::new();
+    let mut rel_paths = BTreeSet::new();
+    for entry in entries {
+        let Some(slug) = legacy_file_project(&entry.from) else {
+            continue;
+        };
+        if !archives.contains_key(slug) {
+            let archive = mcp_agent_mail_storage::ensure_archive(config, slug)
+                .map_err(|e| CliError::Other(format!("open project archive {slug}: {e}")))?;
+            archives.insert(slug.to_string(), archive);
+        }
+        let stored = mcp_agent_mail_storage::store_attachment_blob(
+            &archives[slug],
+            &config.storage_root.join(&entry.from),
+            usize::MAX,
+        )
+        .map_err(|e| CliError::Other(format!("store blob for {}: {e}", entry.from)))?;
+        rel_paths.extend(stored.rel_paths);
+    }
+    Ok(rel_paths.into_iter().collect())
+}
+
+/// Apply `entries` to the loaded rows, returning each changed message's new
+/// attachments array. With `revert`, references go from `to` back to `from`.
+/// Entries whose current reference no longer matches are skipped.
+pub fn rewrite_references(
+    rows: &AttachmentRows,
+    entries: &[MigrationEntry],
+    revert: bool,
+) -> (BTreeMap<i64, Vec<serde_json::Value>>, Vec<MigrationSkip>) {
+    let by_id: BTreeMap<i64, &MessageAttachments> =
+        rows.messages.iter().map(|m| (m.message_id, m)).collect();
+    let mut updates: BTreeMap<i64, Vec<serde_json::Value>> = BTreeMap::new();
+    let mut skipped = Vec::new();
+    for entry in entries {
+        let (expect, replace) = if revert {
+            (&entry.to, &entry.from)
+        } else {
+            (&entry.from, &entry.to)
+        };
+        let Some(message) = by_id.get(&entry.message_id) else {
+            skipped.push(MigrationSkip {
+                message_id: entry.message_id,
+                path: expect.clone(),
+                reason: "message no longer has attachments".to_string(),
+            });
+            continue;
+        };
+        let attachments = updates
+            .entry(entry.message_id)
+            .or_insert_with(|| message.attachments.clone());
+        let Some(object) = attachments
+            .get_mut(entry.index)
+            .and_then(serde_json::Value::as_object_mut)
+            .filter(|o| o.get("path").and_then(serde_json::Value::as_str) == Some(expect.as_str()))
+        else {
+            skipped.push(MigrationSkip {
+                message_id: entry.message_id,
+                path: expect.clone(),
+                reason: "reference changed since the mapping was written".to_string(),
+            });
+            continue;
+        };
+        object.insert("path".to_string(), serde_json::json!(replace));
+        if !revert {
+            object.insert("sha256".to_string(), serde_json::json!(entry.sha256));
+        }
+    }
+    updates.retain(|id, attachments| by_id[id].attachments != *attachments);
+    (updates, skipped)
+}
+
+/// Persist rewritten attachments arrays in one transaction.
+pub fn store_rewritten_references(
+    conn: &mcp_agent_mail_db::DbConn,
+    updates: &BTreeMap<i64, Vec<serde_json::Value>>,
+) -> CliResult<usize> {
+    conn.execute_raw("BEGIN IMMEDIATE")
+        .map_err(|e| CliError::Other(format!("begin attachment rewrite: {e}")))?;
+    for (message_id, attachments) in updates {
+        let json = serde_json::to_string(attachments)
+            .map_err(|e| CliError::Format(format!("serialize attachments: {e}")))?;
+        if let Err(e) = conn.execute_sync(
+            "UPDATE messages SET attachments = ? WHERE id = ?",
+            &[SqlValue::Text(json), SqlValue::BigInt(*message_id)],
+        ) {
+            let _ = conn.execute_raw("ROLLBACK");
+            return Err(CliError::Other(format!(
+                "rewrite attachments for message {message_id}: {e}"
+            )));
+        }
+    }
+    conn.execute_raw("COMMIT")
+        .map_err(|e| CliError::Other(format!("commit attachment rewrite: {e}")))?;
+    Ok(updates.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(messages: &[(i64, serde_json::Value)]) -> AttachmentRows {
+        AttachmentRows {
+            messages: messages
+                .iter()
+                .map(|(id, attachments)| MessageAttachments {
+                    message_id: *id,
+                    project_slug: "demo".to_string(),
+                    attachments: attachments.as_array().cloned().unwrap_or_default(),
+                })
+                .collect(),
+            unparsed: Vec::new(),
+        }
+    }
+
+    fn write_blob(storage_root: &Path, content: &[u8]) -> (String, String) {
+        let sha = hex::encode(Sha256::digest(content));
+        let rel = format!(
+            "projects/demo/{}",
+            mcp_agent_mail_storage::attachment_blob_rel_path(&sha)
+        );
+        let path = storage_root.join(&rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+        (sha, rel)
+    }
+
+    #[test]
+    fn gc_keeps_referenced_and_recent_blobs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (kept_sha, kept_rel) = write_blob(dir.path(), b"referenced log");
+        let (_, orphan_rel) = write_blob(dir.path(), b"orphaned screenshot");
+        let (unparsed_sha, _) = write_blob(dir.path(), b"referenced from bad json");
+        let mut rows = rows(&[(
+            1,
+            serde_json::json!([{"type": "file", "path": kept_rel, "sha256": kept_sha}]),
+        )]);
+        rows.unparsed
+            .push((2, format!("[{{\"sha256\":\"{unparsed_sha}\"")));
+        let refs = AttachmentReferences::collect(&rows);
+
+        let fresh = plan_gc(dir.path(), &refs, SystemTime::now()).unwrap();
+        assert_eq!(fresh.blobs_scanned, 3);
+        assert_eq!(fresh.referenced, 2);
+        assert_eq!(fresh.recent, 1);
+        assert!(fresh.unreferenced.is_empty());
+
+        let later = SystemTime::now() + GC_GRACE_PERIOD * 2;
+        let report = plan_gc(dir.path(), &refs, later).unwrap();
+        assert_eq!(report.unreferenced.len(), 1);
+        assert_eq!(report.unreferenced[0].path, orphan_rel);
+        assert_eq!(report.bytes_reclaimable, 19);
+
+        let outcome = apply_gc(dir.path(), &report);
+        assert_eq!(outcome.removed, vec![orphan_rel.clone()]);
+        assert!(outcome.failed.is_empty());
+        assert!(!dir.path().join(&orphan_rel).exists());
+        assert!(!dir.path().join(&orphan_rel).parent().unwrap().exists());
+        assert!(dir.path().join(&kept_rel).exists());
+    }
+
+    #[test]
+    fn migration_plan_maps_legacy_copies_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = "projects/demo/attachments/files/ab/ab12.log";
+        fs::create_dir_all(dir.path().join("projects/demo/attachments/files/ab")).unwrap();
+        fs::write(dir.path().join(legacy), b"build log").unwrap();
+        let rows = rows(&[
+            (
+                1,
+                serde_json::json!([
+                    {"type": "inline", "name": "a.md"},
+                    {"type": "file", "path": legacy},
+                    {"type": "file", "path": "projects/demo/attachments/files/cd/gone.txt"},
+                ]),
+            ),
+            (2, serde_json::json!([{"type": "file", "path": legacy}])),
+            (
+                3,
+                serde_json::json!([{"type": "file", "path": "../escape/attachments/files/x"}]),
+            ),
+        ]);
+
+        let plan = plan_migration(dir.path(), &rows);
+        assert_eq!(plan.entries.len(), 2);
+        assert_eq!(plan.skipped.len(), 1);
+        let sha = hex::encode(Sha256::digest(b"build log"));
+        assert_eq!(plan.entries[0].index, 1);
+        assert_eq!(plan.entries[0].sha256, sha);
+        assert_eq!(
+            plan.entries[0].to, plan.entries[1].to,
+            "same content, one blob"
+        );
+
+        let (updates, skipped) = rewrite_references(&rows, &plan.entries, false);
+        assert!(skipped.is_empty());
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[&1][1]["path"], plan.entries[0].to.as_str());
+        assert_eq!(updates[&1][1]["sha256"], sha.as_str());
+
+        let migrated = AttachmentRows {
+            messages: rows
+                .messages
+                .iter()
+                .map(|m| MessageAttachments {
+                    attachments: updates
+                        .get(&m.message_id)
+                        .cloned()
+                        .unwrap_or_else(|| m.attachments.clone()),
+                    ..m.clone()
+                })
+                .collect(),
+            unparsed: Vec::new(),
+        };
+        let (reverted, skipped) = rewrite_references(&migrated, &plan.entries, true);
+        assert!(skipped.is_empty());
+        assert_eq!(reverted[&2][0]["path"], legacy);
+        assert_eq!(reverted[&1][1]["path"], legacy);
+    }
+
+    #[test]
+    fn rewrite_skips_references_that_moved_on() {
+        let rows = rows(&[(
+            7,
+            serde_json::json!([{"type": "file", "path": "elsewhere"}]),
+        )]);
+        let entry = MigrationEntry {
+            message_id: 7,
+            index: 0,
+            from: "projects/demo/attachments/files/ab/x".to_string(),
+            to: "projects/demo/attachments/ab/cd/blob".to_string(),
+            sha256: "abcd".to_string(),
+        };
+        let (updates, skipped) = rewrite_references(&rows, &[entry], false);
+        assert!(updates.is_empty());
+        assert_eq!(skipped.len(), 1);
+    }
+
+    #[test]
+    fn legacy_file_project_accepts_only_files_layout() {
+        assert_eq!(
+            legacy_file_project("projects/demo/attachments/files/ab/x.txt"),
+            Some("demo")
+        );
+        assert_eq!(
+            legacy_file_project("projects/demo/attachments/ab/cd/blob"),
+            None
+        );
+        assert_eq!(
+            legacy_file_project("projects/demo/attachments/files/../x"),
+            None
+        );
+        assert_eq!(legacy_file_project("attachments/files/ab/x.txt"), None);
+    }
+}
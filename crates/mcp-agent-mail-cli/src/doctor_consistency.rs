@@ -0,0 +1,384 @@
+//! Referential and invariant checks for `am doctor check` and
+//! `am doctor repair --consistency`.
+//!
+//! Each issue class is one query over the mailbox tables. `doctor check`
+//! reports a count and a few example IDs per class under stable keys, so
+//! automation can gate on one class without parsing the detail string.
+//! Repair deletes orphaned rows and releases impossible reservations in a
+//! single transaction; the caller takes the pre-repair backup first.
+
+use std::collections::BTreeMap;
+
+use mcp_agent_mail_db::CanonicalDbConn;
+use mcp_agent_mail_db::sqlmodel::Value as SqlValue;
+
+use crate::{CliError, CliResult};
+
+/// Example IDs reported per issue class.
+pub const EXAMPLE_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IssueClass {
+    /// `file_reservations` rows whose `agent_id` has no `agents` row.
+    ReservationMissingAgent,
+    /// `message_recipients` rows whose `message_id` has no `messages` row.
+    RecipientMissingMessage,
+    /// `agent_links` rows whose two sides are the same agent.
+    SelfAgentLink,
+    /// Unreleased reservations whose `expires_ts` is before `created_ts`.
+    ReservationExpiresBeforeCreated,
+}
+
+impl IssueClass {
+    pub const ALL: [Self; 4] = [
+        Self::ReservationMissingAgent,
+        Self::RecipientMissingMessage,
+        Self::SelfAgentLink,
+        Self::ReservationExpiresBeforeCreated,
+    ];
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ReservationMissingAgent => "reservation_missing_agent",
+            Self::RecipientMissingMessage => "recipient_missing_message",
+            Self::SelfAgentLink => "self_agent_link",
+            Self::ReservationExpiresBeforeCreated => "reservation_expires_before_created",
+        }
+    }
+
+    /// What `am doctor repair --consistency` does with rows of this class.
+    #[must_use]
+    pub const fn repair_action(self) -> &'static str {
+        match self {
+            Self::ReservationExpiresBeforeCreated => "release",
+            _ => "delete",
+        }
+    }
+
+    /// `(id expression, FROM/WHERE clause)` selecting the offending rows.
+    /// Recipient rows have no id column and are labelled `message_id:agent_id`.
+    const fn query_parts(self) -> (&'static str, &'static str) {
+        match self {
+            Self::ReservationMissingAgent => (
+                "CAST(fr.id AS TEXT)",
+                "file_reservations fr \
+                 WHERE NOT EXISTS (SELECT 1 FROM agents a WHERE a.id = fr.agent_id)",
+            ),
+            Self::RecipientMissingMessage => (
+                "mr.message_id || ':' || mr.agent_id",
+                "message_recipients mr \
+                 WHERE NOT EXISTS (SELECT 1 FROM messages m WHERE m.id = mr.message_id)",
+            ),
+            Self::SelfAgentLink => (
+                "CAST(al.id AS TEXT)",
+                "agent_links al WHERE al.a_agent_id = al.b_agent_id",
+            ),
+            Self::ReservationExpiresBeforeCreated => (
+                "CAST(fr.id AS TEXT)",
+                "file_reservations fr \
+                 WHERE fr.expires_ts < fr.created_ts AND fr.released_ts IS NULL \
+                 AND NOT EXISTS (SELECT 1 FROM file_reservation_releases r \
+                                 WHERE r.reservation_id = fr.id)",
+            ),
+        }
+    }
+
+    const fn order_by(self) -> &'static str {
+        match self {
+            Self::RecipientMissingMessage => "mr.message_id ASC, mr.agent_id ASC",
+            Self::SelfAgentLink => "al.id ASC",
+            _ => "fr.id ASC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct IssueSummary {
+    pub count: usize,
+    pub example_ids: Vec<String>,
+    /// `delete` or `release`.
+    pub repair: &'static str,
+}
+
+/// Per-class results, keyed by [`IssueClass::as_str`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(transparent)]
+pub struct ConsistencyReport {
+    pub issues: BTreeMap<&'static str, IssueSummary>,
+}
+
+impl ConsistencyReport {
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.issues.values().map(|issue| issue.count).sum()
+    }
+
+    #[must_use]
+    pub fn count(&self, class: IssueClass) -> usize {
+        self.issues
+            .get(class.as_str())
+            .map_or(0, |issue| issue.count)
+    }
+
+    /// One-line summary of the classes that have rows, e.g.
+    /// `self_agent_link=2 [4, 9]`.
+    #[must_use]
+    pub fn detail(&self) -> String {
+        if self.total() == 0 {
+            return "No orphaned rows or impossible reservations".to_string();
+        }
+        self.issues
+            .iter()
+            .filter(|(_, issue)| issue.count > 0)
+            .map(|(key, issue)| format!("{key}={} [{}]", issue.count, issue.example_ids.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+fn count_rows(conn: &CanonicalDbConn, class: IssueClass) -> CliResult<usize> {
+    let (_, from_where) = class.query_parts();
+    let rows = conn
+        .query_sync(&format!("SELECT COUNT(*) AS cnt FROM {from_where}"), &[])
+        .map_err(|e| CliError::Other(format!("{} query failed: {e}", class.as_str())))?;
+    let count: i64 = rows
+        .first()
+        .and_then(|row| row.get_named("cnt").ok())
+        .unwrap_or(0);
+    Ok(usize::try_from(count).unwrap_or(0))
+}
+
+fn example_ids(conn: &CanonicalDbConn, class: IssueClass) -> CliResult<Vec<String>> {
+    let (id_expr, from_where) = class.query_parts();
+    let rows = conn
+        .query_sync(
+            &format!(
+                "SELECT {id_expr} AS id FROM {from_where} ORDER BY {} LIMIT ?",
+                class.order_by()
+            ),
+            &[SqlValue::BigInt(EXAMPLE_LIMIT as i64)],
+        )
+        .map_err(|e| CliError::Other(format!("{} query failed: {e}", class.as_str())))?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.get_named::<String>("id").ok())
+        .collect())
+}
+
+pub fn scan(conn: &CanonicalDbConn) -> CliResult<ConsistencyReport> {
+    let mut report = ConsistencyReport::default();
+    for class in IssueClass::ALL {
+        let count = count_rows(conn, class)?;
+        let example_ids = if count == 0 {
+            Vec::new()
+        } else {
+            example_ids(conn, class)?
+        };
+        report.issues.insert(
+            class.as_str(),
+            IssueSummary {
+                count,
+                example_ids,
+                repair: class.repair_action(),
+            },
+        );
+    }
+    Ok(report)
+}
+
+fn repair_class(conn: &CanonicalDbConn, class: IssueClass, now_us: i64) -> CliResult<()> {
+    let (_, from_where) = class.query_parts();
+    let statements: Vec<(String, Vec<SqlValue>)> = match class {
+        // The release ledger references reservations, so drop ledger rows first.
+        IssueClass::ReservationMissingAgent => vec![
+            (
+                format!(
+                    "DELETE FROM file_reservation_releases \
+                     WHERE reservation_id IN (SELECT fr.id FROM {from_where})"
+                ),
+                Vec::new(),
+            ),
+            (
+                format!(
+                    "DELETE FROM file_reservations WHERE id IN (SELECT fr.id FROM {from_where})"
+                ),
+                Vec::new(),
+            ),
+        ],
+        IssueClass::RecipientMissingMessage => vec![(
+            format!(
+                "DELETE FROM message_recipients WHERE rowid IN (SELECT mr.rowid FROM {from_where})"
+            ),
+            Vec::new(),
+        )],
+        IssueClass::SelfAgentLink => vec![(
+            format!("DELETE FROM agent_links WHERE id IN (SELECT al.id FROM {from_where})"),
+            Vec::new(),
+        )],
+        // Record the release in the ledger and the base row, matching
+        // `release_reservations`. The ledger insert runs first because the
+        // selection excludes rows whose `released_ts` is already set.
+        IssueClass::ReservationExpiresBeforeCreated => vec![
+            (
+                format!(
+                    "INSERT OR IGNORE INTO file_reservation_releases (reservation_id, released_ts) \
+                     SELECT fr.id, ? FROM {from_where}"
+                ),
+                vec![SqlValue::BigInt(now_us)],
+            ),
+            (
+                "UPDATE file_reservations SET released_ts = ? \
+                 WHERE released_ts IS NULL AND expires_ts < created_ts \
+                 AND id IN (SELECT reservation_id FROM file_reservation_releases \
+                            WHERE released_ts = ?)"
+                    .to_string(),
+                vec![SqlValue::BigInt(now_us), SqlValue::BigInt(now_us)],
+            ),
+        ],
+    };
+    for (sql, params) in statements {
+        conn.execute_sync(&sql, &params)
+            .map_err(|e| CliError::Other(format!("{} repair failed: {e}", class.as_str())))?;
+    }
+    Ok(())
+}
+
+/// Clean every class in `report` that has rows, in one transaction, and
+/// return the number of rows handled per class.
+pub fn repair(
+    conn: &CanonicalDbConn,
+    report: &ConsistencyReport,
+    now_us: i64,
+) -> CliResult<BTreeMap<&'static str, usize>> {
+    let pending: Vec<IssueClass> = IssueClass::ALL
+        .into_iter()
+        .filter(|class| report.count(*class) > 0)
+        .collect();
+    if pending.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    conn.execute_raw("BEGIN IMMEDIATE")
+        .map_err(|e| CliError::Other(format!("failed to begin consistency repair: {e}")))?;
+    let result = pending
+        .iter()
+        .try_for_each(|class| repair_class(conn, *class, now_us));
+    match result {
+        Ok(()) => conn
+            .execute_raw("COMMIT")
+            .map_err(|e| CliError::Other(format!("failed to commit consistency repair: {e}")))?,
+        Err(err) => {
+            let _ = conn.execute_raw("ROLLBACK");
+            return Err(err);
+        }
+    }
+
+    let after = scan(conn)?;
+    if after.total() > 0 {
+        return Err(CliError::Other(format!(
+            "consistency repair reported success but rows remain: {}",
+            after.detail()
+        )));
+    }
+    Ok(pending
+        .into_iter()
+        .map(|class| (class.as_str(), report.count(class)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_conn() -> CanonicalDbConn {
+        let conn = CanonicalDbConn::open_memory().expect("open memory db");
+        conn.execute_raw(&mcp_agent_mail_db::schema::init_schema_sql_base())
+            .expect("init schema");
+        conn.execute_raw("PRAGMA foreign_keys = OFF")
+            .expect("disable foreign keys for fixture");
+        conn.execute_raw(
+            "INSERT INTO projects (id, slug, human_key, created_at) VALUES (1, 'p', '/tmp/p', 0);
+             INSERT INTO agents
+               (id, project_id, name, program, model, task_description, inception_ts,
+                last_active_ts, attachments_policy, contact_policy)
+             VALUES (1, 1, 'BlueLake', 'codex-cli', 'gpt-5', '', 0, 0, 'auto', 'auto'),
+                    (2, 1, 'RedFox', 'codex-cli', 'gpt-5', '', 0, 0, 'auto', 'auto');
+             INSERT INTO messages (id, project_id, sender_id, subject, body_md, created_ts)
+             VALUES (10, 1, 1, 'hi', 'body', 0);
+             INSERT INTO message_recipients (message_id, agent_id, kind)
+             VALUES (10, 2, 'to'), (99, 2, 'to');
+             INSERT INTO file_reservations
+               (id, project_id, agent_id, path_pattern, created_ts, expires_ts)
+             VALUES (1, 1, 1, 'ok/**', 100, 200),
+                    (2, 1, 42, 'ghost/**', 100, 200),
+                    (3, 1, 1, 'backwards/**', 500, 100);
+             INSERT INTO file_reservation_releases (reservation_id, released_ts) VALUES (2, 150);
+             INSERT INTO agent_links
+               (id, a_project_id, a_agent_id, b_project_id, b_agent_id, status, created_ts, updated_ts)
+             VALUES (1, 1, 1, 1, 2, 'approved', 0, 0),
+                    (2, 1, 2, 1, 2, 'approved', 0, 0);",
+        )
+        .expect("seed fixture");
+        conn
+    }
+
+    #[test]
+    fn scan_reports_each_class_with_examples() {
+        let conn = seeded_conn();
+        let report = scan(&conn).expect("scan");
+        let issue = |class: IssueClass| report.issues[class.as_str()].clone();
+        assert_eq!(
+            issue(IssueClass::ReservationMissingAgent).example_ids,
+            ["2"]
+        );
+        assert_eq!(
+            issue(IssueClass::RecipientMissingMessage).example_ids,
+            ["99:2"]
+        );
+        assert_eq!(issue(IssueClass::SelfAgentLink).example_ids, ["2"]);
+        let impossible = issue(IssueClass::ReservationExpiresBeforeCreated);
+        assert_eq!(impossible.example_ids, ["3"]);
+        assert_eq!(impossible.repair, "release");
+        assert_eq!(report.total(), 4);
+
+        let json = serde_json::to_value(&report).expect("serialize");
+        assert_eq!(json["self_agent_link"]["count"], 1);
+    }
+
+    #[test]
+    fn repair_deletes_orphans_and_releases_impossible_reservations() {
+        let conn = seeded_conn();
+        let report = scan(&conn).expect("scan");
+        let handled = repair(&conn, &report, 777).expect("repair");
+        assert_eq!(handled.values().sum::<usize>(), 4);
+        assert_eq!(scan(&conn).expect("rescan").total(), 0);
+
+        let rows = conn
+            .query_sync(
+                "SELECT id, released_ts FROM file_reservations ORDER BY id",
+                &[],
+            )
+            .expect("reservations");
+        let ids: Vec<(i64, Option<i64>)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get_named("id").unwrap(),
+                    row.get_named("released_ts").ok(),
+                )
+            })
+            .collect();
+        assert_eq!(ids, [(1, None), (3, Some(777))]);
+        let ledger = conn
+            .query_sync(
+                "SELECT reservation_id FROM file_reservation_releases ORDER BY reservation_id",
+                &[],
+            )
+            .expect("ledger");
+        let ledger: Vec<i64> = ledger
+            .iter()
+            .map(|row| row.get_named("reservation_id").unwrap())
+            .collect();
+        assert_eq!(ledger, [3]);
+    }
+}
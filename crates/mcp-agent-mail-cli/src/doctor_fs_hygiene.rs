@@ -0,0 +1,672 @@
+//! Filesystem hygiene pass for `am doctor check` / `am doctor repair --fs`.
+//!
+//! Enumerates the storage root and the SQLite directory for leftovers that
+//! accumulate after crashes, interrupted cleanups, and manual surgery:
+//!
+//! - `-wal` / `-shm` / `-journal` sidecars whose main database is gone, and
+//!   `-shm` files without a `-wal` partner.
+//! - `*.corrupt-*`, `*.startup-quarantine-*`, and `*.cleanup-quarantine-*`
+//!   artifacts left by doctor and the startup/cleanup healers.
+//! - `*.lock.owner.json` metadata whose owning pid is no longer alive.
+//! - `.setup-self-heal/*.json` caches whose project directory disappeared.
+//! - `projects/<slug>` directories without a DB row, and DB rows without a
+//!   directory.
+//!
+//! Every finding is classified as `benign` (expected, self-managing),
+//! `suspicious` (worth a human look, never touched automatically), or
+//! `actionable` (safe to move aside). `am doctor repair --fs` only ever
+//! MOVES actionable paths into a timestamped backup directory — nothing is
+//! deleted.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Quarantine artifacts younger than this stay `benign`: the healer that
+/// produced them may still be mid-investigation.
+const QUARANTINE_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+const SQLITE_SIDECAR_SUFFIXES: &[&str] = &["-wal", "-shm", "-journal"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HygieneClass {
+    Benign,
+    Suspicious,
+    Actionable,
+}
+
+impl HygieneClass {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Benign => "benign",
+            Self::Suspicious => "suspicious",
+            Self::Actionable => "actionable",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct HygieneFinding {
+    pub kind: &'static str,
+    pub class: HygieneClass,
+    /// Absolute path of the primary artifact.
+    pub path: PathBuf,
+    /// Paths that must move together with `path` (e.g. the lock file paired
+    /// with its owner metadata).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub companions: Vec<PathBuf>,
+    pub explanation: String,
+}
+
+/// Findings grouped by class, as emitted under `fs_hygiene` in
+/// `am doctor check --json`.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct HygieneReport {
+    pub storage_root: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_dir: Option<PathBuf>,
+    /// False when the DB project list was unavailable, so the
+    /// directory-vs-row comparison was skipped.
+    pub db_rows_compared: bool,
+    pub benign: Vec<HygieneFinding>,
+    pub suspicious: Vec<HygieneFinding>,
+    pub actionable: Vec<HygieneFinding>,
+}
+
+impl HygieneReport {
+    fn push(&mut self, finding: HygieneFinding) {
+        match finding.class {
+            HygieneClass::Benign => self.benign.push(finding),
+            HygieneClass::Suspicious => self.suspicious.push(finding),
+            HygieneClass::Actionable => self.actionable.push(finding),
+        }
+    }
+
+    #[must_use]
+    pub fn needs_attention(&self) -> bool {
+        !self.actionable.is_empty() || !self.suspicious.is_empty()
+    }
+
+    #[must_use]
+    pub fn summary_line(&self) -> String {
+        format!(
+            "{} actionable, {} suspicious, {} benign",
+            self.actionable.len(),
+            self.suspicious.len(),
+            self.benign.len()
+        )
+    }
+
+    pub fn findings(&self) -> impl Iterator<Item = &HygieneFinding> {
+        self.actionable
+            .iter()
+            .chain(self.suspicious.iter())
+            .chain(self.benign.iter())
+    }
+}
+
+/// Inputs for a hygiene scan.
+#[derive(Debug, Clone)]
+pub struct HygieneScanInput<'a> {
+    pub storage_root: &'a Path,
+    /// Resolved path of the live SQLite database, if file-backed.
+    pub db_path: Option<&'a Path>,
+    /// Project slugs from the `projects` table; `None` skips the
+    /// directory-vs-row comparison.
+    pub db_project_slugs: Option<&'a BTreeSet<String>>,
+    pub now: SystemTime,
+}
+
+pub fn scan(input: &HygieneScanInput<'_>) -> HygieneReport {
+    let storage_root = absolute(input.storage_root);
+    let db_path = input.db_path.map(absolute);
+    let db_dir = db_path
+        .as_deref()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf);
+    let mut report = HygieneReport {
+        storage_root: storage_root.clone(),
+        db_dir: db_dir.clone(),
+        db_rows_compared: input.db_project_slugs.is_some(),
+        ..HygieneReport::default()
+    };
+
+    let mut flat_dirs = vec![storage_root.clone()];
+    if let Some(dir) = db_dir.as_ref()
+        && !flat_dirs.contains(dir)
+    {
+        flat_dirs.push(dir.clone());
+    }
+    for dir in &flat_dirs {
+        scan_flat_dir(dir, db_path.as_deref(), input.now, &mut report);
+    }
+
+    scan_self_heal_caches(&storage_root.join(".setup-self-heal"), &mut report);
+    scan_projects(&storage_root.join("projects"), input, &mut report);
+    report
+}
+
+/// Scan one directory (non-recursively) for SQLite sidecars, quarantine
+/// artifacts, and lock owner metadata.
+fn scan_flat_dir(dir: &Path, live_db: Option<&Path>, now: SystemTime, report: &mut HygieneReport) {
+    for path in list_files(dir) {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if let Some(finding) = classify_quarantine(&path, name, now) {
+            report.push(finding);
+        } else if let Some(finding) = classify_sidecar(&path, name, live_db) {
+            report.push(finding);
+        } else if let Some(finding) = classify_lock_owner(&path, name) {
+            report.push(finding);
+        }
+    }
+}
+
+fn classify_quarantine(path: &Path, name: &str, now: SystemTime) -> Option<HygieneFinding> {
+    if name.contains(".corrupt-") {
+        return Some(HygieneFinding {
+            kind: "corrupt_quarantine",
+            class: HygieneClass::Benign,
+            path: path.to_path_buf(),
+            companions: Vec::new(),
+            explanation: "Corrupt database snapshot kept for forensics; retention is managed by \
+                          `am doctor quarantine`."
+                .to_string(),
+        });
+    }
+    if !(name.contains(".startup-quarantine-") || name.contains(".cleanup-quarantine-")) {
+        return None;
+    }
+    let age = fs::symlink_metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|mtime| now.duration_since(mtime).ok());
+    let expired = age.is_some_and(|age| age >= QUARANTINE_RETENTION);
+    Some(HygieneFinding {
+        kind: "heal_quarantine",
+        class: if expired {
+            HygieneClass::Actionable
+        } else {
+            HygieneClass::Benign
+        },
+        path: path.to_path_buf(),
+        companions: Vec::new(),
+        explanation: if expired {
+            format!(
+                "Quarantined by a startup/cleanup healer more than {} days ago; safe to move aside.",
+                QUARANTINE_RETENTION.as_secs() / 86_400
+            )
+        } else {
+            "Recently quarantined by a startup/cleanup healer; kept while it may still be \
+             needed for diagnosis."
+                .to_string()
+        },
+    })
+}
+
+fn classify_sidecar(path: &Path, name: &str, live_db: Option<&Path>) -> Option<HygieneFinding> {
+    let (suffix, main_name) = SQLITE_SIDECAR_SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix).map(|main| (*suffix, main)))?;
+    if main_name.is_empty() {
+        return None;
+    }
+    let main_path = path.with_file_name(main_name);
+    let is_live = live_db.is_some_and(|db| db == main_path);
+    if !main_path.exists() {
+        return Some(HygieneFinding {
+            kind: "orphan_sqlite_sidecar",
+            class: if is_live {
+                HygieneClass::Suspicious
+            } else {
+                HygieneClass::Actionable
+            },
+            path: path.to_path_buf(),
+            companions: Vec::new(),
+            explanation: if is_live {
+                format!(
+                    "`{suffix}` sidecar present but the configured database {} is missing; \
+                     it may hold the only copy of recent writes — inspect before cleaning.",
+                    main_path.display()
+                )
+            } else {
+                format!(
+                    "`{suffix}` sidecar whose database {} no longer exists.",
+                    main_path.display()
+                )
+            },
+        });
+    }
+    if suffix == "-shm" && !path.with_file_name(format!("{main_name}-wal")).exists() {
+        return Some(HygieneFinding {
+            kind: "shm_without_wal",
+            class: if is_live {
+                HygieneClass::Suspicious
+            } else {
+                HygieneClass::Actionable
+            },
+            path: path.to_path_buf(),
+            companions: Vec::new(),
+            explanation: if is_live {
+                "Live database has `-shm` without `-wal`; use `am doctor fix` \
+                 (wal_shm_sidecar_drift) rather than moving it by hand."
+                    .to_string()
+            } else {
+                "`-shm` index without a `-wal` partner on an inactive database.".to_string()
+            },
+        });
+    }
+    None
+}
+
+#[derive(serde::Deserialize)]
+struct LockOwnerMetadata {
+    pid: u32,
+}
+
+fn classify_lock_owner(path: &Path, name: &str) -> Option<HygieneFinding> {
+    let lock_name = name.strip_suffix(".owner.json")?;
+    if !lock_name.ends_with(".lock") {
+        return None;
+    }
+    let lock_path = path.with_file_name(lock_name);
+    let companions = if lock_path.exists() {
+        vec![lock_path.clone()]
+    } else {
+        Vec::new()
+    };
+    let owner = fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<LockOwnerMetadata>(&raw).ok());
+    let Some(owner) = owner else {
+        return Some(HygieneFinding {
+            kind: "lock_owner_unreadable",
+            class: HygieneClass::Suspicious,
+            path: path.to_path_buf(),
+            companions: Vec::new(),
+            explanation: format!(
+                "Owner metadata for {} is not valid `{{pid, created_ts}}` JSON.",
+                lock_path.display()
+            ),
+        });
+    };
+    if crate::doctor::fixers::is_pid_alive(owner.pid) {
+        return Some(HygieneFinding {
+            kind: "lock_held",
+            class: HygieneClass::Benign,
+            path: path.to_path_buf(),
+            companions: Vec::new(),
+            explanation: format!(
+                "Lock {} is owned by live pid {}.",
+                lock_path.display(),
+                owner.pid
+            ),
+        });
+    }
+    Some(HygieneFinding {
+        kind: "stale_lock",
+        class: HygieneClass::Actionable,
+        path: path.to_path_buf(),
+        companions,
+        explanation: format!(
+            "Lock {} names pid {} which is no longer running.",
+            lock_path.display(),
+            owner.pid
+        ),
+    })
+}
+
+#[derive(serde::Deserialize)]
+struct SelfHealCacheProbe {
+    project_dir: PathBuf,
+}
+
+fn scan_self_heal_caches(dir: &Path, report: &mut HygieneReport) {
+    for path in list_files(dir) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let probe = fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<SelfHealCacheProbe>(&raw).ok());
+        match probe {
+            None => report.push(HygieneFinding {
+                kind: "self_heal_cache_unreadable",
+                class: HygieneClass::Suspicious,
+                path,
+                companions: Vec::new(),
+                explanation: "Setup self-heal cache could not be parsed; `am setup run` will \
+                              rewrite it on the next successful run."
+                    .to_string(),
+            }),
+            Some(probe) if !probe.project_dir.is_dir() => report.push(HygieneFinding {
+                kind: "orphaned_self_heal_cache",
+                class: HygieneClass::Actionable,
+                path,
+                companions: Vec::new(),
+                explanation: format!(
+                    "Setup self-heal cache for {} which no longer exists.",
+                    probe.project_dir.display()
+                ),
+            }),
+            Some(_) => {}
+        }
+    }
+}
+
+fn scan_projects(projects_root: &Path, input: &HygieneScanInput<'_>, report: &mut HygieneReport) {
+    let mut dir_slugs = BTreeSet::new();
+    let entries = fs::read_dir(projects_root)
+        .map(|entries| entries.flatten().collect::<Vec<_>>())
+        .unwrap_or_default();
+    for entry in entries {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let path = entry.path();
+        if file_type.is_file() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if let Some(finding) = classify_lock_owner(&path, &name) {
+                report.push(finding);
+            }
+            continue;
+        }
+        if !file_type.is_dir() {
+            continue;
+        }
+        let slug = entry.file_name().to_string_lossy().into_owned();
+        for file in list_files(&path) {
+            if let Some(name) = file.file_name().and_then(|n| n.to_str())
+                && let Some(finding) = classify_lock_owner(&file, name)
+            {
+                report.push(finding);
+            }
+        }
+        if let Some(db_slugs) = input.db_project_slugs
+            && !db_slugs.contains(&slug)
+        {
+            let empty = fs::read_dir(&path).is_ok_and(|mut it| it.next().is_none());
+            report.push(HygieneFinding {
+                kind: "project_dir_without_db_row",
+                class: if empty {
+                    HygieneClass::Actionable
+                } else {
+                    HygieneClass::Suspicious
+                },
+                path: path.clone(),
+                companions: Vec::new(),
+                explanation: if empty {
+                    format!("Empty archive directory for unknown project `{slug}`.")
+                } else {
+                    format!(
+                        "Archive directory for `{slug}` has content but no `projects` row; \
+                         consider `am doctor reconstruct --dry-run` before moving it."
+                    )
+                },
+            });
+        }
+        dir_slugs.insert(slug);
+    }
+
+    if let Some(db_slugs) = input.db_project_slugs {
+        for slug in db_slugs.difference(&dir_slugs) {
+            report.push(HygieneFinding {
+                kind: "db_row_without_project_dir",
+                class: HygieneClass::Suspicious,
+                path: absolute(&projects_root.join(slug)),
+                companions: Vec::new(),
+                explanation: format!(
+                    "Project `{slug}` exists in the database but has no archive directory; \
+                     the next write recreates it, but archived history is missing."
+                ),
+            });
+        }
+    }
+}
+
+fn list_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|ft| ft.is_file()))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Result of moving actionable findings aside.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct HygieneApplyOutcome {
+    pub backup_dir: PathBuf,
+    pub moved: Vec<(PathBuf, PathBuf)>,
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// Move every actionable path (and its companions) into `backup_dir`,
+/// preserving the layout relative to the storage root / DB directory.
+pub fn apply(report: &HygieneReport, backup_dir: &Path) -> HygieneApplyOutcome {
+    let mut outcome = HygieneApplyOutcome {
+        backup_dir: backup_dir.to_path_buf(),
+        ..HygieneApplyOutcome::default()
+    };
+    for finding in &report.actionable {
+        for source in std::iter::once(&finding.path).chain(finding.companions.iter()) {
+            let dest = backup_dir.join(backup_relative_path(report, source));
+            let moved = dest
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::rename(source, &dest));
+            match moved {
+                Ok(()) => outcome.moved.push((source.clone(), dest)),
+                Err(error) => outcome.failed.push((source.clone(), error.to_string())),
+            }
+        }
+    }
+    outcome
+}
+
+fn backup_relative_path(report: &HygieneReport, source: &Path) -> PathBuf {
+    if let Ok(rel) = source.strip_prefix(&report.storage_root) {
+        return PathBuf::from("storage_root").join(rel);
+    }
+    if let Some(db_dir) = report.db_dir.as_ref()
+        && let Ok(rel) = source.strip_prefix(db_dir)
+    {
+        return PathBuf::from("db_dir").join(rel);
+    }
+    PathBuf::from("other").join(source.file_name().unwrap_or(source.as_os_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_dir(root: &Path, slugs: Option<&BTreeSet<String>>) -> HygieneReport {
+        scan(&HygieneScanInput {
+            storage_root: root,
+            db_path: Some(&root.join("storage.sqlite3")),
+            db_project_slugs: slugs,
+            now: SystemTime::now(),
+        })
+    }
+
+    fn kinds(findings: &[HygieneFinding]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.kind).collect()
+    }
+
+    #[test]
+    fn orphan_sidecars_are_actionable_except_for_live_db() {
+        let td = tempfile::tempdir().unwrap();
+        fs::write(td.path().join("old.sqlite3-wal"), b"x").unwrap();
+        fs::write(td.path().join("storage.sqlite3-wal"), b"x").unwrap();
+        let report = scan_dir(td.path(), None);
+        assert_eq!(kinds(&report.actionable), vec!["orphan_sqlite_sidecar"]);
+        assert!(report.actionable[0].path.ends_with("old.sqlite3-wal"));
+        assert_eq!(kinds(&report.suspicious), vec!["orphan_sqlite_sidecar"]);
+        assert!(report.actionable[0].path.is_absolute());
+    }
+
+    #[test]
+    fn shm_without_wal_is_flagged() {
+        let td = tempfile::tempdir().unwrap();
+        fs::write(td.path().join("other.sqlite3"), b"").unwrap();
+        fs::write(td.path().join("other.sqlite3-shm"), b"").unwrap();
+        fs::write(td.path().join("storage.sqlite3"), b"").unwrap();
+        fs::write(td.path().join("storage.sqlite3-wal"), b"").unwrap();
+        fs::write(td.path().join("storage.sqlite3-shm"), b"").unwrap();
+        let report = scan_dir(td.path(), None);
+        assert_eq!(kinds(&report.actionable), vec!["shm_without_wal"]);
+        assert!(report.suspicious.is_empty());
+    }
+
+    #[test]
+    fn quarantine_artifacts_age_into_actionable() {
+        let td = tempfile::tempdir().unwrap();
+        fs::write(
+            td.path()
+                .join("storage.sqlite3.corrupt-20260101_000000_000"),
+            b"",
+        )
+        .unwrap();
+        fs::write(
+            td.path().join("storage.sqlite3-wal.cleanup-quarantine-1"),
+            b"",
+        )
+        .unwrap();
+        let fresh = scan_dir(td.path(), None);
+        let mut fresh_kinds = kinds(&fresh.benign);
+        fresh_kinds.sort_unstable();
+        assert_eq!(fresh_kinds, vec!["corrupt_quarantine", "heal_quarantine"]);
+        assert!(fresh.actionable.is_empty());
+
+        let later = scan(&HygieneScanInput {
+            storage_root: td.path(),
+            db_path: None,
+            db_project_slugs: None,
+            now: SystemTime::now() + QUARANTINE_RETENTION + Duration::from_secs(60),
+        });
+        assert_eq!(kinds(&later.actionable), vec!["heal_quarantine"]);
+        assert_eq!(kinds(&later.benign), vec!["corrupt_quarantine"]);
+    }
+
+    #[test]
+    fn lock_owner_classification_follows_pid_liveness() {
+        let td = tempfile::tempdir().unwrap();
+        let project = td.path().join("projects").join("alpha");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".archive.lock"), b"").unwrap();
+        fs::write(
+            project.join(".archive.lock.owner.json"),
+            serde_json::json!({"pid": 999_999_999u32, "created_ts": 0}).to_string(),
+        )
+        .unwrap();
+        fs::write(
+            project.join(".commit.lock.owner.json"),
+            serde_json::json!({"pid": std::process::id(), "created_ts": 0}).to_string(),
+        )
+        .unwrap();
+        fs::write(
+            td.path().join(".mailbox.activity.lock.owner.json"),
+            b"not json",
+        )
+        .unwrap();
+        let report = scan_dir(td.path(), None);
+        assert_eq!(kinds(&report.actionable), vec!["stale_lock"]);
+        assert_eq!(report.actionable[0].companions.len(), 1);
+        assert_eq!(kinds(&report.benign), vec!["lock_held"]);
+        assert_eq!(kinds(&report.suspicious), vec!["lock_owner_unreadable"]);
+    }
+
+    #[test]
+    fn self_heal_cache_for_missing_project_is_actionable() {
+        let td = tempfile::tempdir().unwrap();
+        let cache_dir = td.path().join(".setup-self-heal");
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(
+            cache_dir.join("a.json"),
+            serde_json::json!({"project_dir": td.path().join("gone")}).to_string(),
+        )
+        .unwrap();
+        fs::write(
+            cache_dir.join("b.json"),
+            serde_json::json!({"project_dir": td.path()}).to_string(),
+        )
+        .unwrap();
+        fs::write(cache_dir.join("c.json"), b"{").unwrap();
+        let report = scan_dir(td.path(), None);
+        assert_eq!(kinds(&report.actionable), vec!["orphaned_self_heal_cache"]);
+        assert_eq!(
+            kinds(&report.suspicious),
+            vec!["self_heal_cache_unreadable"]
+        );
+    }
+
+    #[test]
+    fn project_dirs_are_compared_with_db_rows_only_when_available() {
+        let td = tempfile::tempdir().unwrap();
+        let projects = td.path().join("projects");
+        fs::create_dir_all(projects.join("empty")).unwrap();
+        fs::create_dir_all(projects.join("full")).unwrap();
+        fs::write(projects.join("full").join("file"), b"x").unwrap();
+        fs::create_dir_all(projects.join("known")).unwrap();
+
+        assert!(scan_dir(td.path(), None).findings().next().is_none());
+
+        let slugs: BTreeSet<String> = ["known", "missing"].map(String::from).into();
+        let report = scan_dir(td.path(), Some(&slugs));
+        assert!(report.db_rows_compared);
+        assert_eq!(
+            kinds(&report.actionable),
+            vec!["project_dir_without_db_row"]
+        );
+        assert!(report.actionable[0].path.ends_with("empty"));
+        let mut suspicious = kinds(&report.suspicious);
+        suspicious.sort_unstable();
+        assert_eq!(
+            suspicious,
+            vec!["db_row_without_project_dir", "project_dir_without_db_row"]
+        );
+    }
+
+    #[test]
+    fn apply_moves_actionable_paths_with_companions() {
+        let td = tempfile::tempdir().unwrap();
+        fs::write(td.path().join("old.sqlite3-wal"), b"x").unwrap();
+        fs::write(td.path().join("x.lock"), b"").unwrap();
+        fs::write(
+            td.path().join("x.lock.owner.json"),
+            serde_json::json!({"pid": 999_999_999u32}).to_string(),
+        )
+        .unwrap();
+        let report = scan_dir(td.path(), None);
+        let backup = td.path().join("backups").join("fs-hygiene-test");
+        let outcome = apply(&report, &backup);
+        assert!(outcome.failed.is_empty(), "{:?}", outcome.failed);
+        assert_eq!(outcome.moved.len(), 3);
+        assert!(!td.path().join("old.sqlite3-wal").exists());
+        assert!(!td.path().join("x.lock").exists());
+        assert!(
+            backup
+                .join("storage_root")
+                .join("x.lock.owner.json")
+                .exists()
+        );
+        assert!(backup.join("storage_root").join("x.lock").exists());
+        assert!(scan_dir(td.path(), None).actionable.is_empty());
+    }
+}
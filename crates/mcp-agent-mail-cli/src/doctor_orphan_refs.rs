@@ -137,7 +137,10 @@ pub fn run(
     aggregate_summary(&mut report);
 
     match format {
-        CliOutputFormat::Json => {
+        CliOutputFormat::Json | CliOutputFormat::Csv | CliOutputFormat::Markdown => {
+            if format.is_tabular() {
+                crate::output::warn_tabular_fallback(format);
+            }
             println!(
                 "{}",
                 serde_json::to_string_pretty(&report)
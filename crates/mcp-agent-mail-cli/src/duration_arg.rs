@@ -0,0 +1,333 @@
+//! Duration-valued command-line flags.
+//!
+//! Every TTL, age, timeout, and retry-delay flag accepts a number with a unit
+//! suffix (`250ms`, `90s`, `15m`, `2h`, `1d`, `2w`) or a run of them (`1h30m`), and
+//! its clap value parser yields a [`Duration`]. A bare integer keeps the unit
+//! the flag has always used, so `--ttl 3600` still means one hour; the first
+//! such value in a process prints a note suggesting the suffixed form.
+
+#![forbid(unsafe_code)]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+const GRAMMAR_HINT: &str = "expected e.g. 90s, 15m, 2h, 1d, 2w, or 1h30m";
+
+static LEGACY_NOTE_PRINTED: AtomicBool = AtomicBool::new(false);
+
+/// Unit a flag applied to bare integers before suffixes were accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegacyUnit {
+    Millis,
+    Seconds,
+    Minutes,
+    Days,
+}
+
+impl LegacyUnit {
+    const fn millis(self) -> u64 {
+        match self {
+            Self::Millis => 1,
+            Self::Seconds => 1_000,
+            Self::Minutes => 60_000,
+            Self::Days => 86_400_000,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Millis => "milliseconds",
+            Self::Seconds => "seconds",
+            Self::Minutes => "minutes",
+            Self::Days => "days",
+        }
+    }
+}
+
+/// Parse `value`. Bare integers are read in `legacy` units and rejected when
+/// `legacy` is `None`. The second element reports whether the legacy form was
+/// used.
+pub fn parse_duration(value: &str, legacy: Option<LegacyUnit>) -> Result<(Duration, bool), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("empty duration: {GRAMMAR_HINT}"));
+    }
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        let Some(unit) = legacy else {
+            return Err(format!("duration '{value}' needs a unit: {GRAMMAR_HINT}"));
+        };
+        let count = value
+            .parse::<u64>()
+            .map_err(|_| format!("duration '{value}' is out of range"))?;
+        let millis = count
+            .checked_mul(unit.millis())
+            .ok_or_else(|| format!("duration '{value}' is out of range"))?;
+        return bounded(value, millis).map(|duration| (duration, true));
+    }
+
+    let mut total: u64 = 0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        let unit_len = rest[digits..]
+            .bytes()
+            .take_while(u8::is_ascii_alphabetic)
+            .count();
+        if digits == 0 || unit_len == 0 {
+            return Err(format!("invalid duration '{value}': {GRAMMAR_HINT}"));
+        }
+        let count = rest[..digits]
+            .parse::<u64>()
+            .map_err(|_| format!("duration '{value}' is out of range"))?;
+        let scale: u64 = match &rest[digits..digits + unit_len] {
+            "ms" => 1,
+            "s" => 1_000,
+            "m" => 60_000,
+            "h" => 3_600_000,
+            "d" => 86_400_000,
+            "w" => 604_800_000,
+            unit => {
+                return Err(format!(
+                    "unknown unit '{unit}' in duration '{value}': {GRAMMAR_HINT}"
+                ));
+            }
+        };
+        total = count
+            .checked_mul(scale)
+            .and_then(|millis| total.checked_add(millis))
+            .ok_or_else(|| format!("duration '{value}' is out of range"))?;
+        rest = &rest[digits + unit_len..];
+    }
+    bounded(value, total).map(|duration| (duration, false))
+}
+
+/// Keep every accepted duration representable as signed microseconds, which
+/// is how the database stores timestamps.
+fn bounded(value: &str, millis: u64) -> Result<Duration, String> {
+    const MAX_MILLIS: u64 = (u64::MAX >> 1) / 1_000;
+    if millis > MAX_MILLIS {
+        return Err(format!("duration '{value}' is out of range"));
+    }
+    Ok(Duration::from_millis(millis))
+}
+
+fn parse_with_legacy(value: &str, unit: LegacyUnit) -> Result<Duration, String> {
+    let (duration, legacy) = parse_duration(value, Some(unit))?;
+    if legacy && !duration.is_zero() && !LEGACY_NOTE_PRINTED.swap(true, Ordering::Relaxed) {
+        ftui_runtime::ftui_eprintln!(
+            "note: bare duration '{}' is read as {}; prefer the suffixed form '{}'",
+            value.trim(),
+            unit.name(),
+            format_duration(duration)
+        );
+    }
+    Ok(duration)
+}
+
+/// Value parser for flags whose bare integers mean seconds.
+pub fn seconds(value: &str) -> Result<Duration, String> {
+    parse_with_legacy(value, LegacyUnit::Seconds)
+}
+
+/// Value parser for flags whose bare integers mean minutes.
+pub fn minutes(value: &str) -> Result<Duration, String> {
+    parse_with_legacy(value, LegacyUnit::Minutes)
+}
+
+/// Value parser for flags whose bare integers mean days.
+pub fn days(value: &str) -> Result<Duration, String> {
+    parse_with_legacy(value, LegacyUnit::Days)
+}
+
+/// Value parser for flags whose bare integers mean milliseconds.
+pub fn millis(value: &str) -> Result<Duration, String> {
+    parse_with_legacy(value, LegacyUnit::Millis)
+}
+
+/// Value parser for flags that never accepted bare integers.
+pub fn suffixed(value: &str) -> Result<Duration, String> {
+    parse_duration(value, None).map(|(duration, _)| duration)
+}
+
+/// Render `duration` in the suffixed grammar, largest units first
+/// (`5400s` becomes `1h30m`).
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let mut millis = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    if millis == 0 {
+        return "0s".to_string();
+    }
+    let mut out = String::new();
+    for (suffix, scale) in [
+        ("d", 86_400_000),
+        ("h", 3_600_000),
+        ("m", 60_000),
+        ("s", 1_000),
+        ("ms", 1),
+    ] {
+        if millis >= scale {
+            out.push_str(&(millis / scale).to_string());
+            out.push_str(suffix);
+            millis %= scale;
+        }
+    }
+    out
+}
+
+/// Whole seconds, saturating at `i64::MAX`.
+#[must_use]
+pub fn whole_seconds(duration: Duration) -> i64 {
+    i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+}
+
+/// Whole seconds, rounding a partial second up and saturating at
+/// `u64::MAX`, for intervals that must not collapse to zero ("disabled").
+#[must_use]
+pub fn seconds_ceil(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis().div_ceil(1_000)).unwrap_or(u64::MAX)
+}
+
+/// Whole minutes, rounding a partial minute up and saturating at `u32::MAX`,
+/// for the robot views that still report windows in minutes.
+#[must_use]
+pub fn minutes_ceil(duration: Duration) -> u32 {
+    u32::try_from(duration.as_secs().div_ceil(60)).unwrap_or(u32::MAX)
+}
+
+/// Microseconds, saturating at `i64::MAX`.
+#[must_use]
+pub fn micros(duration: Duration) -> i64 {
+    i64::try_from(duration.as_micros()).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(value: &str, legacy: Option<LegacyUnit>) -> (Duration, bool) {
+        parse_duration(value, legacy).unwrap_or_else(|err| panic!("{value}: {err}"))
+    }
+
+    #[test]
+    fn suffixed_grammar() {
+        let cases = [
+            ("250ms", Duration::from_millis(250)),
+            ("90s", Duration::from_secs(90)),
+            ("15m", Duration::from_secs(900)),
+            ("2h", Duration::from_secs(7_200)),
+            ("1d", Duration::from_secs(86_400)),
+            ("2w", Duration::from_secs(1_209_600)),
+            ("1h30m", Duration::from_secs(5_400)),
+            ("1d2h3m4s5ms", Duration::from_millis(93_784_005)),
+            ("0s", Duration::ZERO),
+            ("  45m ", Duration::from_secs(2_700)),
+            ("2m2m", Duration::from_secs(240)),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(ok(value, None), (expected, false), "{value}");
+            assert_eq!(
+                ok(value, Some(LegacyUnit::Minutes)),
+                (expected, false),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        for value in [
+            "", " ", "s", "m5", "1.5h", "-5m", "5 m", "5y", "5sec", "1h-", "h1", "+5s", "5M",
+        ] {
+            assert!(
+                parse_duration(value, Some(LegacyUnit::Seconds)).is_err(),
+                "{value:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn bare_integers_need_a_legacy_unit() {
+        assert!(parse_duration("30", None).is_err());
+        assert!(suffixed("30").is_err());
+        assert_eq!(suffixed("30d"), Ok(Duration::from_secs(30 * 86_400)));
+    }
+
+    #[test]
+    fn overflow_is_an_error_not_a_wrap() {
+        for value in [
+            "18446744073709551616",
+            "18446744073709551615d",
+            "9223372036854776s",
+            "106751991167d1s",
+            "99999999999999999999ms",
+        ] {
+            let err = parse_duration(value, Some(LegacyUnit::Seconds)).expect_err(value);
+            assert!(err.contains("out of range"), "{value}: {err}");
+        }
+        assert!(parse_duration("18446744073709551615", Some(LegacyUnit::Minutes)).is_err());
+        let (max, _) = ok("9223372036854775ms", None);
+        assert_eq!(micros(max), 9_223_372_036_854_775_000);
+    }
+
+    /// Each flag family keeps the unit its bare integers always had.
+    #[test]
+    fn legacy_unit_compatibility_table() {
+        let table: [(fn(&str) -> Result<Duration, String>, &str, Duration); 13] = [
+            (seconds, "3600", Duration::from_secs(3_600)),
+            (seconds, "604800", Duration::from_secs(604_800)),
+            (seconds, "0", Duration::ZERO),
+            // `check-inbox --rate-limit` / `--urgent-rate-limit`
+            (seconds, "120", Duration::from_secs(120)),
+            // `mail send --expires-in`
+            (seconds, "90", Duration::from_secs(90)),
+            (seconds, "1", Duration::from_secs(1)),
+            // `doctor backups prune --keep-days`
+            (days, "14", Duration::from_secs(1_209_600)),
+            (days, "36h", Duration::from_secs(129_600)),
+            (days, "2w", Duration::from_secs(1_209_600)),
+            (minutes, "30", Duration::from_secs(1_800)),
+            (minutes, "0", Duration::ZERO),
+            (millis, "10000", Duration::from_secs(10)),
+            (millis, "1000", Duration::from_secs(1)),
+        ];
+        for (parser, value, expected) in table {
+            assert_eq!(parser(value), Ok(expected), "{value}");
+        }
+        assert_eq!(
+            ok("120", Some(LegacyUnit::Seconds)),
+            (Duration::from_secs(120), true)
+        );
+        assert_eq!(seconds("2m"), minutes("2m"));
+        assert_eq!(millis("2m"), Ok(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn format_round_trips_through_the_parser() {
+        let cases = [
+            (Duration::ZERO, "0s"),
+            (Duration::from_millis(250), "250ms"),
+            (Duration::from_secs(90), "1m30s"),
+            (Duration::from_secs(3_600), "1h"),
+            (Duration::from_secs(604_800), "7d"),
+            (Duration::from_millis(93_784_005), "1d2h3m4s5ms"),
+        ];
+        for (duration, text) in cases {
+            assert_eq!(format_duration(duration), text);
+            assert_eq!(suffixed(text), Ok(duration));
+        }
+    }
+
+    #[test]
+    fn conversions_saturate() {
+        assert_eq!(whole_seconds(Duration::from_millis(1_999)), 1);
+        assert_eq!(whole_seconds(Duration::MAX), i64::MAX);
+        assert_eq!(minutes_ceil(Duration::from_secs(60)), 1);
+        assert_eq!(minutes_ceil(Duration::from_secs(61)), 2);
+        assert_eq!(minutes_ceil(Duration::MAX), u32::MAX);
+        assert_eq!(seconds_ceil(Duration::from_millis(250)), 1);
+        assert_eq!(seconds_ceil(Duration::from_secs(120)), 120);
+        assert_eq!(seconds_ceil(Duration::ZERO), 0);
+        assert_eq!(micros(Duration::from_secs(2)), 2_000_000);
+        assert_eq!(micros(Duration::MAX), i64::MAX);
+    }
+}
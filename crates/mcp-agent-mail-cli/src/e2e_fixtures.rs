@@ -0,0 +1,896 @@
+//! HTTP record/replay fixtures for `am e2e run --record/--replay`.
+//!
+//! Recording puts a proxy between a script suite and the server it boots:
+//! the runner reserves the server's `HTTP_PORT`, listens on a second port,
+//! and `e2e_start_server_with_logs` points `E2E_SERVER_URL` at the proxy.
+//! Every request/response pair is written to `<dir>/<suite>.json` once the
+//! suite exits.
+//!
+//! Replaying serves those responses from an in-process stub instead, and
+//! `e2e_start_server_with_logs` does not build or boot a server at all. A
+//! request matches the next unused exchange with the same method, path, and
+//! body hash, so repeated identical requests replay in recorded order. Any
+//! request without a match gets a 500 and fails the suite with a diff
+//! against the closest recorded request.
+//!
+//! Suites must send deterministic bodies to be replayable (no temp paths
+//! or timestamps); `test_peer_addr.sh` is the reference suite.
+//!
+//! # Environment passed to suites
+//!
+//! - `AM_E2E_FIXTURE_MODE`: `record` or `replay`
+//! - `AM_E2E_FIXTURE_URL`: base URL of the proxy or stub (no path)
+//!
+//! Credentials never reach fixture files: `Authorization`,
+//! `Proxy-Authorization`, `Cookie`, and `Set-Cookie` values are replaced
+//! with [`REDACTED`].
+
+#![forbid(unsafe_code)]
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Replacement for sensitive header values in fixture files.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Fixture file format version.
+pub const FIXTURE_SCHEMA: &str = "e2e-http-fixture.v1";
+
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Headers that describe one connection rather than the exchange; they are
+/// neither recorded nor forwarded.
+const CONNECTION_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "content-length",
+    "transfer-encoding",
+    "keep-alive",
+    "expect",
+];
+
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+const IO_TIMEOUT: Duration = Duration::from_secs(60);
+/// Lines compared per side when diffing an unexpected request.
+const MAX_DIFF_LINES: usize = 400;
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Fixture Files
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Whether suites record fixtures or replay them, and where they live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+impl FixtureMode {
+    /// Value of `AM_E2E_FIXTURE_MODE`.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Record(_) => "record",
+            Self::Replay(_) => "replay",
+        }
+    }
+
+    /// Fixture directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        match self {
+            Self::Record(dir) | Self::Replay(dir) => dir,
+        }
+    }
+}
+
+/// One recorded request/response pair.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub method: String,
+    /// Request target, including any query string.
+    pub path: String,
+    /// SHA-256 of the raw request body; part of the replay match key.
+    pub body_sha256: String,
+    /// Request headers, sensitive values redacted. Informational only.
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: String,
+    pub status: u16,
+    pub response_headers: Vec<(String, String)>,
+    pub response_body: String,
+}
+
+/// Contents of `<dir>/<suite>.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureFile {
+    pub schema: String,
+    pub suite: String,
+    /// Recording time (RFC3339).
+    pub recorded_at: String,
+    pub exchanges: Vec<Exchange>,
+}
+
+impl FixtureFile {
+    /// Path of `suite`'s fixture file in `dir`.
+    #[must_use]
+    pub fn path(dir: &Path, suite: &str) -> PathBuf {
+        dir.join(format!("{suite}.json"))
+    }
+
+    /// Reads `suite`'s fixture file from `dir`.
+    pub fn load(dir: &Path, suite: &str) -> io::Result<Self> {
+        let path = Self::path(dir, suite);
+        let body = fs::read_to_string(&path).map_err(|error| {
+            io::Error::new(
+                error.kind(),
+                format!(
+                    "no replay fixture for suite '{suite}' at {}: {error}",
+                    path.display()
+                ),
+            )
+        })?;
+        serde_json::from_str(&body).map_err(|error| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid replay fixture {}: {error}", path.display()),
+            )
+        })
+    }
+
+    /// Writes the fixture to `dir`, replacing any earlier recording.
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let path = Self::path(dir, &self.suite);
+        let body = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(&path, body + "\n")?;
+        Ok(path)
+    }
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Fixture Server
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum Backend {
+    Record {
+        upstream: SocketAddr,
+        exchanges: Vec<Exchange>,
+    },
+    Replay {
+        exchanges: Vec<Exchange>,
+        used: Vec<bool>,
+        requests_seen: usize,
+        errors: Vec<String>,
+    },
+}
+
+/// Recording proxy or replay stub listening on a loopback port for the
+/// duration of one suite attempt.
+#[derive(Debug)]
+pub struct FixtureServer {
+    addr: SocketAddr,
+    backend: Arc<Mutex<Backend>>,
+    stop: Arc<AtomicBool>,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+/// What a [`FixtureServer`] saw once stopped.
+#[derive(Debug, Default)]
+pub struct FixtureOutcome {
+    /// Recorded exchanges, in completion order (record mode).
+    pub exchanges: Vec<Exchange>,
+    /// Unexpected requests, each with a diff (replay mode).
+    pub errors: Vec<String>,
+}
+
+impl FixtureServer {
+    /// Starts a proxy that forwards to `127.0.0.1:upstream_port` and records
+    /// every exchange.
+    pub fn record(upstream_port: u16) -> io::Result<Self> {
+        Self::start(Backend::Record {
+            upstream: SocketAddr::from(([127, 0, 0, 1], upstream_port)),
+            exchanges: Vec::new(),
+        })
+    }
+
+    /// Starts a stub that answers from `exchanges`.
+    pub fn replay(exchanges: Vec<Exchange>) -> io::Result<Self> {
+        let used = vec![false; exchanges.len()];
+        Self::start(Backend::Replay {
+            exchanges,
+            used,
+            requests_seen: 0,
+            errors: Vec::new(),
+        })
+    }
+
+    fn start(backend: Backend) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let addr = listener.local_addr()?;
+        let backend = Arc::new(Mutex::new(backend));
+        let stop = Arc::new(AtomicBool::new(false));
+        let accept_thread = {
+            let backend = Arc::clone(&backend);
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                let mut connections = Vec::new();
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Acquire) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let backend = Arc::clone(&backend);
+                    connections.push(std::thread::spawn(move || {
+                        let _ = handle_connection(stream, &backend);
+                    }));
+                }
+                for connection in connections {
+                    let _ = connection.join();
+                }
+            })
+        };
+        Ok(Self {
+            addr,
+            backend,
+            stop,
+            accept_thread: Some(accept_thread),
+        })
+    }
+
+    /// Base URL suites send requests to (`AM_E2E_FIXTURE_URL`).
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Stops accepting, waits for in-flight requests, and returns what the
+    /// server saw.
+    #[must_use]
+    pub fn finish(mut self) -> FixtureOutcome {
+        self.shutdown();
+        let backend = std::mem::replace(
+            &mut *self.backend.lock().unwrap_or_else(PoisonError::into_inner),
+            Backend::Record {
+                upstream: self.addr,
+                exchanges: Vec::new(),
+            },
+        );
+        match backend {
+            Backend::Record { exchanges, .. } => FixtureOutcome {
+                exchanges,
+                errors: Vec::new(),
+            },
+            Backend::Replay { errors, .. } => FixtureOutcome {
+                exchanges: Vec::new(),
+                errors,
+            },
+        }
+    }
+
+    fn shutdown(&mut self) {
+        let Some(accept_thread) = self.accept_thread.take() else {
+            return;
+        };
+        self.stop.store(true, Ordering::Release);
+        // Wake the blocking accept so the thread sees the stop flag.
+        let _ = TcpStream::connect(self.addr);
+        let _ = accept_thread.join();
+    }
+}
+
+impl Drop for FixtureServer {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// HTTP Handling
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+struct HttpRequest {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct HttpResponse {
+    status: u16,
+    reason: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+fn handle_connection(stream: TcpStream, backend: &Mutex<Backend>) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let mut reader = BufReader::new(stream);
+    let request = read_request(&mut reader, &mut writer)?;
+
+    let upstream = match &*backend.lock().unwrap_or_else(PoisonError::into_inner) {
+        Backend::Record { upstream, .. } => Some(*upstream),
+        Backend::Replay { .. } => None,
+    };
+    let response = match upstream {
+        Some(upstream) => {
+            let response = match forward(&request, upstream) {
+                Ok(response) => response,
+                Err(error) => {
+                    plain_response(502, &format!("am e2e record: upstream failed: {error}"))
+                }
+            };
+            if let Backend::Record { exchanges, .. } =
+                &mut *backend.lock().unwrap_or_else(PoisonError::into_inner)
+            {
+                exchanges.push(exchange_for(&request, &response));
+            }
+            response
+        }
+        None => replay_response(&request, backend),
+    };
+
+    write_response(&mut writer, &response)?;
+    writer.flush()?;
+    let _ = writer.shutdown(Shutdown::Both);
+    Ok(())
+}
+
+fn replay_response(request: &HttpRequest, backend: &Mutex<Backend>) -> HttpResponse {
+    let mut guard = backend.lock().unwrap_or_else(PoisonError::into_inner);
+    let Backend::Replay {
+        exchanges,
+        used,
+        requests_seen,
+        errors,
+    } = &mut *guard
+    else {
+        return plain_response(500, "am e2e replay: server is not replaying");
+    };
+    *requests_seen += 1;
+    let digest = body_sha256(&request.body);
+    let matched = exchanges
+        .iter()
+        .zip(used.iter())
+        .position(|(exchange, used)| {
+            !used
+                && exchange.method == request.method
+                && exchange.path == request.target
+                && exchange.body_sha256 == digest
+        });
+    if let Some(index) = matched {
+        used[index] = true;
+        let exchange = &exchanges[index];
+        return HttpResponse {
+            status: exchange.status,
+            reason: reason_phrase(exchange.status).to_string(),
+            headers: exchange.response_headers.clone(),
+            body: exchange.response_body.clone().into_bytes(),
+        };
+    }
+
+    let error = unexpected_request_report(*requests_seen, request, exchanges);
+    errors.push(error.clone());
+    plain_response(500, &error)
+}
+
+/// Describe a request no fixture matched, with a diff against the closest
+/// recorded request.
+fn unexpected_request_report(seq: usize, request: &HttpRequest, exchanges: &[Exchange]) -> String {
+    let body = String::from_utf8_lossy(&request.body);
+    let mut report = format!(
+        "am e2e replay: unexpected request #{seq}: {} {} (body sha256 {})",
+        request.method,
+        request.target,
+        body_sha256(&request.body)
+    );
+    let closest = exchanges
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, exchange)| {
+            (
+                exchange.method == request.method,
+                exchange.path == request.target,
+                shared_line_count(&exchange.request_body, &body),
+            )
+        });
+    match closest {
+        Some((index, exchange)) => {
+            report.push_str(&format!(
+                "\nclosest recorded request (exchange {}): {} {}\n",
+                index + 1,
+                exchange.method,
+                exchange.path
+            ));
+            if exchange.method != request.method || exchange.path != request.target {
+                report.push_str(&format!(
+                    "- {} {}\n+ {} {}\n",
+                    exchange.method, exchange.path, request.method, request.target
+                ));
+            }
+            report.push_str(&diff_lines(
+                &pretty_body(&exchange.request_body),
+                &pretty_body(&body),
+            ));
+        }
+        None => report.push_str("\nthe fixture has no recorded requests"),
+    }
+    report
+}
+
+fn forward(request: &HttpRequest, upstream: SocketAddr) -> io::Result<HttpResponse> {
+    let mut stream = TcpStream::connect_timeout(&upstream, IO_TIMEOUT)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut head = format!(
+        "{} {} HTTP/1.1\r\nHost: {upstream}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        request.method,
+        request.target,
+        request.body.len()
+    );
+    for (name, value) in &request.headers {
+        if !is_connection_header(name) {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&request.body)?;
+    stream.flush()?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    parse_response(&raw)
+}
+
+fn exchange_for(request: &HttpRequest, response: &HttpResponse) -> Exchange {
+    Exchange {
+        method: request.method.clone(),
+        path: request.target.clone(),
+        body_sha256: body_sha256(&request.body),
+        request_headers: recorded_headers(&request.headers),
+        request_body: String::from_utf8_lossy(&request.body).into_owned(),
+        status: response.status,
+        response_headers: recorded_headers(&response.headers),
+        response_body: String::from_utf8_lossy(&response.body).into_owned(),
+    }
+}
+
+fn recorded_headers(headers: &[(String, String)]) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| !is_connection_header(name))
+        .map(|(name, value)| {
+            let value = if is_sensitive_header(name) {
+                REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+fn is_sensitive_header(name: &str) -> bool {
+    SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.eq_ignore_ascii_case(sensitive))
+}
+
+fn is_connection_header(name: &str) -> bool {
+    CONNECTION_HEADERS
+        .iter()
+        .any(|header| name.eq_ignore_ascii_case(header))
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+fn read_head(reader: &mut impl BufRead) -> io::Result<Vec<String>> {
+    let mut lines = Vec::new();
+    let mut total = 0;
+    loop {
+        let mut line = String::new();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed before the end of the HTTP head",
+            ));
+        }
+        total += read;
+        if total > MAX_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "HTTP head is too large",
+            ));
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            return Ok(lines);
+        }
+        lines.push(line.to_string());
+    }
+}
+
+fn parse_headers(lines: &[String]) -> Vec<(String, String)> {
+    lines
+        .iter()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn read_request(reader: &mut impl BufRead, writer: &mut impl Write) -> io::Result<HttpRequest> {
+    let head = read_head(reader)?;
+    let Some((request_line, header_lines)) = head.split_first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "empty HTTP request",
+        ));
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("malformed request line: {request_line}"),
+        ));
+    };
+    let headers = parse_headers(header_lines);
+    if header(&headers, "expect").is_some_and(|value| value.eq_ignore_ascii_case("100-continue")) {
+        writer.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+        writer.flush()?;
+    }
+    let length = header(&headers, "content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(HttpRequest {
+        method: method.to_string(),
+        target: target.to_string(),
+        headers,
+        body,
+    })
+}
+
+fn parse_response(raw: &[u8]) -> io::Result<HttpResponse> {
+    let mut reader = BufReader::new(raw);
+    let mut head = read_head(&mut reader)?;
+    // Skip interim 1xx responses.
+    while head
+        .first()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .is_some_and(|code| code.starts_with('1'))
+    {
+        head = read_head(&mut reader)?;
+    }
+    let Some((status_line, header_lines)) = head.split_first() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "empty HTTP response",
+        ));
+    };
+    let mut parts = status_line.splitn(3, ' ');
+    let _version = parts.next();
+    let status = parts
+        .next()
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("malformed status line: {status_line}"),
+            )
+        })?;
+    let reason = parts.next().unwrap_or_default().to_string();
+    let headers = parse_headers(header_lines);
+    let mut body = Vec::new();
+    reader.read_to_end(&mut body)?;
+    if header(&headers, "transfer-encoding").is_some_and(|value| value.contains("chunked")) {
+        body = dechunk(&body)?;
+    } else if let Some(length) =
+        header(&headers, "content-length").and_then(|value| value.parse::<usize>().ok())
+    {
+        body.truncate(length);
+    }
+    Ok(HttpResponse {
+        status,
+        reason,
+        headers,
+        body,
+    })
+}
+
+fn dechunk(mut raw: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed chunked body");
+    let mut body = Vec::new();
+    loop {
+        let line_end = raw
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(invalid)?;
+        let size_line = std::str::from_utf8(&raw[..line_end]).map_err(|_| invalid())?;
+        let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_hex, 16).map_err(|_| invalid())?;
+        raw = &raw[line_end + 2..];
+        if size == 0 {
+            return Ok(body);
+        }
+        if raw.len() < size {
+            return Err(invalid());
+        }
+        body.extend_from_slice(&raw[..size]);
+        raw = raw.get(size + 2..).unwrap_or_default();
+    }
+}
+
+fn write_response(writer: &mut impl Write, response: &HttpResponse) -> io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        response.status,
+        response.reason,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        if !is_connection_header(name) {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+    }
+    head.push_str("\r\n");
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(&response.body)
+}
+
+fn plain_response(status: u16, message: &str) -> HttpResponse {
+    HttpResponse {
+        status,
+        reason: reason_phrase(status).to_string(),
+        headers: vec![(
+            "content-type".to_string(),
+            "text/plain; charset=utf-8".to_string(),
+        )],
+        body: format!("{message}\n").into_bytes(),
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Status",
+    }
+}
+
+/// Hex SHA-256 of a request body, as stored in [`Exchange::body_sha256`].
+#[must_use]
+pub fn body_sha256(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Request Diffs
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Pretty-print JSON bodies so the diff is line-oriented.
+fn pretty_body(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| serde_json::to_string_pretty(&value).ok())
+        .unwrap_or_else(|| body.to_string())
+}
+
+fn shared_line_count(recorded: &str, actual: &str) -> usize {
+    let recorded = pretty_body(recorded);
+    let actual = pretty_body(actual);
+    let actual_lines: std::collections::HashSet<&str> = actual.lines().collect();
+    recorded
+        .lines()
+        .filter(|line| actual_lines.contains(line))
+        .count()
+}
+
+/// Line diff of `expected` (recorded) against `actual`: `- ` lines are only
+/// in the recording, `+ ` lines only in the new request.
+fn diff_lines(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().take(MAX_DIFF_LINES).collect();
+    let new: Vec<&str> = actual.lines().take(MAX_DIFF_LINES).collect();
+    // Longest-common-subsequence table, filled from the end.
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        } else {
+            out.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        }
+    }
+    out
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Tests
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal upstream that echoes each request body back with a sequence
+    /// number, using chunked encoding like a streaming server would.
+    fn spawn_upstream() -> (u16, JoinHandle<()>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            for (seq, stream) in listener.incoming().take(2).enumerate() {
+                let stream = stream.unwrap();
+                let mut writer = stream.try_clone().unwrap();
+                let mut reader = BufReader::new(stream);
+                let request = read_request(&mut reader, &mut writer).unwrap();
+                assert_eq!(
+                    header(&request.headers, "authorization"),
+                    Some(if seq == 0 {
+                        "Bearer secret-token"
+                    } else {
+                        "Bearer other"
+                    })
+                );
+                let body = format!(
+                    "{{\"seq\":{seq},\"echo\":{}}}",
+                    String::from_utf8(request.body).unwrap()
+                );
+                write!(
+                    writer,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Set-Cookie: session=abc\r\nTransfer-Encoding: chunked\r\n\r\n\
+                     {:x}\r\n{body}\r\n0\r\n\r\n",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (port, handle)
+    }
+
+    fn post(url: &str, path: &str, body: &str, auth: &str) -> (u16, String) {
+        let addr = url.strip_prefix("http://").unwrap();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST {path} HTTP/1.1\r\nHost: {addr}\r\nAuthorization: {auth}\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).unwrap();
+        let response = parse_response(&raw).unwrap();
+        (response.status, String::from_utf8(response.body).unwrap())
+    }
+
+    #[test]
+    fn record_then_replay_round_trips_and_redacts_credentials() {
+        let (upstream_port, upstream) = spawn_upstream();
+        let proxy = FixtureServer::record(upstream_port).unwrap();
+        let first = post(&proxy.url(), "/mcp/", r#"{"id":1}"#, "Bearer secret-token");
+        let second = post(&proxy.url(), "/mcp/", r#"{"id":1}"#, "Bearer other");
+        assert_eq!(first, (200, r#"{"seq":0,"echo":{"id":1}}"#.to_string()));
+        assert_eq!(second.1, r#"{"seq":1,"echo":{"id":1}}"#);
+        let recorded = proxy.finish().exchanges;
+        upstream.join().unwrap();
+        assert_eq!(recorded.len(), 2);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let fixture = FixtureFile {
+            schema: FIXTURE_SCHEMA.to_string(),
+            suite: "demo".to_string(),
+            recorded_at: Utc::now().to_rfc3339(),
+            exchanges: recorded,
+        };
+        let path = fixture.save(tmp.path()).unwrap();
+        let on_disk = fs::read_to_string(&path).unwrap();
+        assert!(!on_disk.contains("Bearer"), "credentials leaked: {on_disk}");
+        assert!(!on_disk.contains("session=abc"), "cookie leaked: {on_disk}");
+        assert!(on_disk.contains(REDACTED));
+
+        // Identical requests replay in recorded order.
+        let loaded = FixtureFile::load(tmp.path(), "demo").unwrap();
+        let stub = FixtureServer::replay(loaded.exchanges).unwrap();
+        assert_eq!(post(&stub.url(), "/mcp/", r#"{"id":1}"#, "x"), first);
+        assert_eq!(post(&stub.url(), "/mcp/", r#"{"id":1}"#, "x"), second);
+        assert!(stub.finish().errors.is_empty());
+    }
+
+    #[test]
+    fn replay_fails_unexpected_requests_with_closest_diff() {
+        let exchange = Exchange {
+            method: "POST".to_string(),
+            path: "/api/".to_string(),
+            body_sha256: body_sha256(br#"{"name":"health_check","id":1}"#),
+            request_headers: Vec::new(),
+            request_body: r#"{"name":"health_check","id":1}"#.to_string(),
+            status: 200,
+            response_headers: Vec::new(),
+            response_body: "{}".to_string(),
+        };
+        let stub = FixtureServer::replay(vec![exchange]).unwrap();
+        let (status, body) = post(&stub.url(), "/api/", r#"{"name":"health_chek","id":1}"#, "");
+        assert_eq!(status, 500);
+        assert!(body.contains("unexpected request #1"), "{body}");
+
+        let errors = stub.finish().errors;
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("closest recorded request (exchange 1)"));
+        assert!(
+            errors[0].contains("-   \"name\": \"health_check\""),
+            "{}",
+            errors[0]
+        );
+        assert!(
+            errors[0].contains("+   \"name\": \"health_chek\""),
+            "{}",
+            errors[0]
+        );
+        assert!(errors[0].contains("    \"id\": 1"), "{}", errors[0]);
+    }
+
+    #[test]
+    fn missing_fixture_names_the_suite() {
+        let tmp = tempfile::tempdir().unwrap();
+        let err = FixtureFile::load(tmp.path(), "peer_addr").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains("suite 'peer_addr'"), "{err}");
+    }
+}
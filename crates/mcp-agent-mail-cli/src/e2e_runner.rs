@@ -24,20 +24,37 @@
 //! - stdout/stderr output
 //! - Execution timing
 //!
-//! Results are aggregated into JSON reports compatible with `e2e_artifacts`.
+//! `--jobs N` runs up to N suites at once. Script suites then each get a
+//! private `TMPDIR` and an `HTTP_PORT` reserved by binding port 0, so side by
+//! side suites cannot collide on temp paths or the server port. `--fail-fast`
+//! stops starting new suites after the first failure; suites already running
+//! finish and are reported.
+//!
+//! Results are aggregated into JSON reports compatible with `e2e_artifacts`,
+//! listed alphabetically whatever order the suites finished in. With
+//! `--artifacts` each suite's stdout/stderr is also written to its own log
+//! file.
+//!
+//! `--record <dir>` and `--replay <dir>` run script suites against an HTTP
+//! recording proxy or a replay stub; see [`crate::e2e_fixtures`].
 
 #![forbid(unsafe_code)]
 
 use std::collections::HashMap;
 use std::fs;
 use std::io::{BufRead, BufReader};
+use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
 use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+use crate::e2e_fixtures::{FIXTURE_SCHEMA, FixtureFile, FixtureMode, FixtureServer};
+
 // ──────────────────────────────────────────────────────────────────────────────
 // Suite Registry
 // ──────────────────────────────────────────────────────────────────────────────
@@ -352,12 +369,16 @@ pub struct RunConfig {
     pub retries: u32,
     /// Environment variables to pass.
     pub env: HashMap<String, String>,
-    /// Whether to run in parallel.
-    pub parallel: bool,
+    /// Maximum number of suites running at once (1 = sequential).
+    pub jobs: usize,
+    /// Stop starting new suites after the first failure.
+    pub fail_fast: bool,
     /// Keep temporary directories.
     pub keep_tmp: bool,
     /// Force rebuild before running.
     pub force_build: bool,
+    /// Record HTTP exchanges to, or replay them from, a fixture directory.
+    pub fixtures: Option<FixtureMode>,
 }
 
 impl Default for RunConfig {
@@ -369,9 +390,11 @@ impl Default for RunConfig {
             timeout: Some(Duration::from_secs(600)), // 10 minutes
             retries: 0,
             env: HashMap::new(),
-            parallel: false,
+            jobs: 1,
+            fail_fast: false,
             keep_tmp: false,
             force_build: false,
+            fixtures: None,
         }
     }
 }
@@ -389,6 +412,48 @@ pub struct Runner {
 struct SuiteExecution {
     output: std::process::Output,
     timed_out: bool,
+    /// Unexpected replay requests or recording failures; any fails the suite.
+    fixture_errors: Vec<String>,
+}
+
+/// Private temp directory and server port for one script-suite attempt when
+/// suites run concurrently.
+#[derive(Debug)]
+struct SuiteIsolation {
+    tmp_dir: tempfile::TempDir,
+    http_port: u16,
+}
+
+impl SuiteIsolation {
+    fn new(suite_name: &str) -> std::io::Result<Self> {
+        let tmp_dir = tempfile::Builder::new()
+            .prefix(&format!("am-e2e-{suite_name}-"))
+            .tempdir()?;
+        // The listener is dropped right away; the suite's server binds the
+        // port moments later. Another process could grab it in between, but
+        // only the kernel's ephemeral allocator hands it out.
+        let http_port = TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port();
+        Ok(Self { tmp_dir, http_port })
+    }
+
+    /// Build cache shared by every suite, where `e2e_lib.sh` would put it
+    /// without a private `TMPDIR` (so isolation does not force rebuilds).
+    fn shared_cargo_target_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+            return PathBuf::from(dir);
+        }
+        let tmp_root = std::env::var_os("TMPDIR")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                if Path::new("/data/tmp").is_dir() {
+                    PathBuf::from("/data/tmp")
+                } else {
+                    PathBuf::from("/tmp")
+                }
+            });
+        tmp_root.join("cargo-target")
+    }
 }
 
 impl Runner {
@@ -425,7 +490,7 @@ impl Runner {
         let start_instant = Instant::now();
 
         // Determine which suites to run
-        let suites: Vec<&Suite> = if suite_names.is_empty() {
+        let mut suites: Vec<&Suite> = if suite_names.is_empty() {
             self.registry.suites()
         } else {
             suite_names
@@ -433,34 +498,90 @@ impl Runner {
                 .filter_map(|name| self.registry.get(name))
                 .collect()
         };
+        suites.sort_by(|a, b| a.name.cmp(&b.name));
 
-        let mut results = Vec::with_capacity(suites.len());
-        let mut passed = 0;
-        let mut failed = 0;
-
-        for suite in &suites {
-            let result = self.run_suite(suite);
-            if result.passed {
-                passed += 1;
-            } else {
-                failed += 1;
+        let jobs = self.config.jobs.clamp(1, suites.len().max(1));
+        let next_suite = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+        let finished = Mutex::new(Vec::with_capacity(suites.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..jobs {
+                scope.spawn(|| {
+                    while !stop.load(Ordering::Acquire) {
+                        let Some(suite) = suites.get(next_suite.fetch_add(1, Ordering::AcqRel))
+                        else {
+                            break;
+                        };
+                        let result = self.run_suite(suite);
+                        if !result.passed && self.config.fail_fast {
+                            stop.store(true, Ordering::Release);
+                        }
+                        finished
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .push(result);
+                    }
+                });
             }
-            results.push(result);
-        }
+        });
+
+        // Suites are claimed in order, so everything past the claim counter
+        // was never started.
+        let started = next_suite.into_inner().min(suites.len());
+        let skipped_suites: Vec<String> = suites[started..]
+            .iter()
+            .map(|suite| suite.name.clone())
+            .collect();
+        let mut results = finished
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        let passed = results.iter().filter(|result| result.passed).count() as u32;
+        let failed = results.len() as u32 - passed;
 
         let run_ended = Utc::now();
         let elapsed = start_instant.elapsed();
+        let log_dir = self.config.artifact_dir.as_ref().and_then(|base| {
+            let dir = base
+                .join("runs")
+                .join(run_started.format("%Y%m%d_%H%M%S").to_string());
+            Self::write_suite_logs(&dir, &results)
+                .ok()
+                .map(|()| dir.display().to_string())
+        });
 
         RunReport {
             total: suites.len() as u32,
             passed,
             failed,
-            skipped: 0,
+            skipped: skipped_suites.len() as u32,
             duration_ms: elapsed.as_millis() as u64,
             started_at: run_started.to_rfc3339(),
             ended_at: run_ended.to_rfc3339(),
             results,
+            jobs: jobs as u32,
+            suite_duration_ms: 0,
+            skipped_suites,
+            log_dir,
+        }
+        .with_suite_duration()
+    }
+
+    /// Writes `<suite>.stdout.log` and `<suite>.stderr.log` for every result,
+    /// so concurrent suites never share a log.
+    fn write_suite_logs(dir: &Path, results: &[SuiteResult]) -> std::io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for result in results {
+            fs::write(
+                dir.join(format!("{}.stdout.log", result.name)),
+                &result.stdout,
+            )?;
+            fs::write(
+                dir.join(format!("{}.stderr.log", result.name)),
+                &result.stderr,
+            )?;
         }
+        Ok(())
     }
 
     /// Runs suites with include/exclude filtering.
@@ -477,6 +598,26 @@ impl Runner {
     /// Runs a single suite.
     fn run_suite(&self, suite: &Suite) -> SuiteResult {
         if Self::is_native_suite(&suite.name) {
+            if let Some(mode) = &self.config.fixtures {
+                let now = Utc::now().to_rfc3339();
+                return SuiteResult {
+                    name: suite.name.clone(),
+                    passed: false,
+                    exit_code: -1,
+                    duration_ms: 0,
+                    stdout: String::new(),
+                    stderr: format!(
+                        "--{} only supports script suites; '{}' is a native suite",
+                        mode.as_str(),
+                        suite.name
+                    ),
+                    assertions_passed: 0,
+                    assertions_failed: 0,
+                    assertions_skipped: 0,
+                    started_at: now.clone(),
+                    ended_at: now,
+                };
+            }
             return if suite.name == Self::NATIVE_HTTP_SUITE
                 || suite.name == Self::NATIVE_HTTP_STREAMABLE_SUITE
                 || suite.name == Self::NATIVE_MCP_API_PARITY_SUITE
@@ -533,7 +674,9 @@ impl Runner {
                     } else {
                         execution.output.status.code().unwrap_or(-1)
                     };
-                    let passed = !execution.timed_out && execution.output.status.success();
+                    let passed = !execution.timed_out
+                        && execution.output.status.success()
+                        && execution.fixture_errors.is_empty();
 
                     if execution.timed_out {
                         if !stderr.is_empty() {
@@ -545,6 +688,12 @@ impl Runner {
                             .map_or(0, |duration| duration.as_millis());
                         stderr.push_str(&format!("Suite timed out after {timeout_ms}ms"));
                     }
+                    for error in &execution.fixture_errors {
+                        if !stderr.is_empty() {
+                            stderr.push('\n');
+                        }
+                        stderr.push_str(error);
+                    }
 
                     last_stdout = stdout;
                     last_stderr = stderr;
@@ -623,6 +772,29 @@ impl Runner {
         for (key, value) in &self.config.env {
             cmd.env(key, value);
         }
+        let isolation = if self.config.jobs > 1 {
+            let isolation = SuiteIsolation::new(&suite.name)?;
+            cmd.env("TMPDIR", isolation.tmp_dir.path());
+            cmd.env("HTTP_PORT", isolation.http_port.to_string());
+            if !self.config.env.contains_key("CARGO_TARGET_DIR") {
+                cmd.env(
+                    "CARGO_TARGET_DIR",
+                    SuiteIsolation::shared_cargo_target_dir(),
+                );
+            }
+            Some(isolation)
+        } else {
+            None
+        };
+        let fixture_server = match &self.config.fixtures {
+            Some(mode) => Some(self.start_fixture_server(
+                &mut cmd,
+                mode,
+                &suite.name,
+                isolation.as_ref().map(|isolation| isolation.http_port),
+            )?),
+            None => None,
+        };
 
         // Capture output
         cmd.stdout(Stdio::piped());
@@ -679,8 +851,88 @@ impl Runner {
             stdout,
             stderr,
         };
+        if let Some(isolation) = isolation
+            && self.config.keep_tmp
+        {
+            let _ = isolation.tmp_dir.keep();
+        }
+        let passed = !timed_out && output.status.success();
+        let fixture_errors = match fixture_server {
+            Some(server) => self.finish_fixture_server(server, &suite.name, passed)?,
+            None => Vec::new(),
+        };
 
-        Ok(SuiteExecution { output, timed_out })
+        Ok(SuiteExecution {
+            output,
+            timed_out,
+            fixture_errors,
+        })
+    }
+
+    /// Start the recording proxy or replay stub for one attempt and point
+    /// the suite at it. Recording needs the server's port up front, so it
+    /// reuses the isolation port (or `HTTP_PORT` from `--env`) or reserves
+    /// one.
+    fn start_fixture_server(
+        &self,
+        cmd: &mut Command,
+        mode: &FixtureMode,
+        suite_name: &str,
+        isolation_port: Option<u16>,
+    ) -> std::io::Result<FixtureServer> {
+        let server = match mode {
+            FixtureMode::Record(_) => {
+                let configured_port = self
+                    .config
+                    .env
+                    .get("HTTP_PORT")
+                    .and_then(|port| port.parse::<u16>().ok());
+                let http_port = match isolation_port.or(configured_port) {
+                    Some(port) => port,
+                    None => TcpListener::bind(("127.0.0.1", 0))?.local_addr()?.port(),
+                };
+                cmd.env("HTTP_PORT", http_port.to_string());
+                FixtureServer::record(http_port)?
+            }
+            FixtureMode::Replay(dir) => {
+                FixtureServer::replay(FixtureFile::load(dir, suite_name)?.exchanges)?
+            }
+        };
+        cmd.env("AM_E2E_FIXTURE_MODE", mode.as_str());
+        cmd.env("AM_E2E_FIXTURE_URL", server.url());
+        Ok(server)
+    }
+
+    /// Stop the fixture server. A passing recorded attempt is saved (a
+    /// failing one would capture the failure, so the previous fixture is
+    /// kept); replay returns its unexpected-request reports.
+    fn finish_fixture_server(
+        &self,
+        server: FixtureServer,
+        suite_name: &str,
+        passed: bool,
+    ) -> std::io::Result<Vec<String>> {
+        let outcome = server.finish();
+        let Some(FixtureMode::Record(dir)) = &self.config.fixtures else {
+            return Ok(outcome.errors);
+        };
+        if !passed {
+            return Ok(Vec::new());
+        }
+        if outcome.exchanges.is_empty() {
+            return Ok(vec![format!(
+                "am e2e record: suite '{suite_name}' made no HTTP requests through the \
+                 recording proxy (does it start its server with e2e_start_server_with_logs?)"
+            )]);
+        }
+        FixtureFile {
+            schema: FIXTURE_SCHEMA.to_string(),
+            suite: suite_name.to_string(),
+            recorded_at: Utc::now().to_rfc3339(),
+            exchanges: outcome.exchanges,
+        }
+        .save(dir)?;
+        Ok(Vec::new())
     }
 
     fn is_native_suite(name: &str) -> bool {
@@ -1911,11 +2163,34 @@ pub struct RunReport {
     pub started_at: String,
     /// End timestamp (RFC3339).
     pub ended_at: String,
-    /// Individual suite results.
+    /// Individual suite results, sorted by suite name.
     pub results: Vec<SuiteResult>,
+    /// Maximum number of suites that ran at once.
+    #[serde(default = "default_report_jobs")]
+    pub jobs: u32,
+    /// Sum of the suite durations; `duration_ms` is the wall clock.
+    #[serde(default)]
+    pub suite_duration_ms: u64,
+    /// Suites never started because `--fail-fast` stopped the run.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_suites: Vec<String>,
+    /// Directory holding each suite's `.stdout.log`/`.stderr.log`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_dir: Option<String>,
+}
+
+const fn default_report_jobs() -> u32 {
+    1
 }
 
 impl RunReport {
+    /// Fills `suite_duration_ms` from the results.
+    #[must_use]
+    pub fn with_suite_duration(mut self) -> Self {
+        self.suite_duration_ms = self.results.iter().map(|result| result.duration_ms).sum();
+        self
+    }
+
     /// Returns true if all suites passed.
     #[must_use]
     pub fn success(&self) -> bool {
@@ -1941,6 +2216,10 @@ impl RunReport {
             "  Passed: {}  |  Failed: {}  |  Skipped: {}\n",
             self.passed, self.failed, self.skipped
         ));
+        s.push_str(&format!(
+            "  Wall clock: {}ms  |  Suite time: {}ms  |  Jobs: {}\n",
+            self.duration_ms, self.suite_duration_ms, self.jobs
+        ));
         s.push_str(&format!("{}\n", "═".repeat(60)));
 
         // List failures
@@ -1956,6 +2235,17 @@ impl RunReport {
             }
         }
 
+        if !self.skipped_suites.is_empty() {
+            s.push_str("\nNot started (--fail-fast):\n");
+            for name in &self.skipped_suites {
+                s.push_str(&format!("  - {name}\n"));
+            }
+        }
+
+        if let Some(log_dir) = &self.log_dir {
+            s.push_str(&format!("\nSuite logs: {log_dir}\n"));
+        }
+
         s
     }
 }
@@ -2028,6 +2318,10 @@ mod tests {
             started_at: "2026-02-12T00:00:00Z".to_string(),
             ended_at: "2026-02-12T00:00:01Z".to_string(),
             results: vec![],
+            jobs: 1,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
         };
         assert!(report.success());
         assert_eq!(report.exit_code(), 0);
@@ -2044,6 +2338,10 @@ mod tests {
             started_at: "2026-02-12T00:00:00Z".to_string(),
             ended_at: "2026-02-12T00:00:01Z".to_string(),
             results: vec![],
+            jobs: 1,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
         };
         assert!(!report.success());
         assert_eq!(report.exit_code(), 1);
@@ -2218,6 +2516,92 @@ exit 1
         assert!(result.stderr.contains("Attempts used: 2"));
     }
 
+    #[test]
+    fn test_runner_jobs_isolate_suites_and_report_alphabetically() {
+        let temp = TempDir::new().expect("tempdir");
+        let body = r#"#!/usr/bin/env bash
+sleep "${1:-0}"
+echo "tmp=${TMPDIR} port=${HTTP_PORT}"
+echo "Pass: 1  Fail: 0  Skip: 0"
+"#;
+        write_suite_script(temp.path(), "alpha", &body.replace("${1:-0}", "0.3"));
+        write_suite_script(temp.path(), "beta", body);
+        write_suite_script(temp.path(), "gamma", body);
+
+        let artifacts = temp.path().join("artifacts");
+        let config = RunConfig {
+            project_root: temp.path().to_path_buf(),
+            artifact_dir: Some(artifacts),
+            timeout: Some(Duration::from_secs(5)),
+            jobs: 3,
+            ..Default::default()
+        };
+        let runner = Runner::new(temp.path(), config).expect("runner");
+        let report = runner.run(&["gamma".to_string(), "alpha".to_string(), "beta".to_string()]);
+
+        assert!(report.success());
+        assert_eq!(report.jobs, 3);
+        let names: Vec<&str> = report.results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta", "gamma"]);
+        assert_eq!(
+            report.suite_duration_ms,
+            report.results.iter().map(|r| r.duration_ms).sum::<u64>()
+        );
+
+        let envs: std::collections::BTreeSet<&str> = report
+            .results
+            .iter()
+            .map(|r| r.stdout.lines().next().unwrap_or_default())
+            .collect();
+        assert_eq!(envs.len(), 3, "suites shared TMPDIR or HTTP_PORT: {envs:?}");
+        assert!(envs.iter().all(|line| !line.ends_with("port=")));
+
+        let log_dir = PathBuf::from(report.log_dir.as_deref().expect("log dir"));
+        let beta_log = fs::read_to_string(log_dir.join("beta.stdout.log")).expect("beta log");
+        assert!(beta_log.contains("Pass: 1"));
+        assert!(log_dir.join("gamma.stderr.log").is_file());
+    }
+
+    #[test]
+    fn test_runner_fail_fast_finishes_in_flight_and_skips_pending() {
+        let temp = TempDir::new().expect("tempdir");
+        write_suite_script(
+            temp.path(),
+            "a_fail",
+            "#!/usr/bin/env bash\nsleep 0.2\nexit 1\n",
+        );
+        write_suite_script(
+            temp.path(),
+            "b_slow",
+            "#!/usr/bin/env bash\nsleep 0.5\necho \"Pass: 1  Fail: 0  Skip: 0\"\n",
+        );
+        write_suite_script(temp.path(), "c_pending", "#!/usr/bin/env bash\nexit 0\n");
+
+        let config = RunConfig {
+            project_root: temp.path().to_path_buf(),
+            timeout: Some(Duration::from_secs(5)),
+            jobs: 2,
+            fail_fast: true,
+            ..Default::default()
+        };
+        let runner = Runner::new(temp.path(), config).expect("runner");
+        let report = runner.run(&[]);
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.passed, 1);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.skipped_suites, ["c_pending"]);
+        let names: Vec<&str> = report.results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["a_fail", "b_slow"]);
+        assert!(
+            report
+                .format_summary()
+                .contains("Not started (--fail-fast)")
+        );
+        assert_eq!(report.exit_code(), 1);
+    }
+
     #[test]
     fn test_run_report_summary_lists_failed_suite_names() {
         let report = RunReport {
@@ -2256,9 +2640,15 @@ exit 1
                     ended_at: "2026-02-12T00:00:01Z".to_string(),
                 },
             ],
-        };
+            jobs: 2,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
+        }
+        .with_suite_duration();
 
         let summary = report.format_summary();
+        assert!(summary.contains("Wall clock: 250ms  |  Suite time: 250ms  |  Jobs: 2"));
         assert!(summary.contains("E2E Run: FAIL"));
         assert!(summary.contains("Failed suites:"));
         assert!(summary.contains("beta (exit 7)"));
@@ -2597,9 +2987,84 @@ exit 1
         assert_eq!(cfg.timeout, Some(Duration::from_secs(600)));
         assert_eq!(cfg.retries, 0);
         assert!(cfg.env.is_empty());
-        assert!(!cfg.parallel);
+        assert_eq!(cfg.jobs, 1);
+        assert!(!cfg.fail_fast);
         assert!(!cfg.keep_tmp);
         assert!(!cfg.force_build);
+        assert!(cfg.fixtures.is_none());
+    }
+
+    #[test]
+    fn test_runner_replay_serves_fixtures_and_fails_unexpected_requests() {
+        let temp = TempDir::new().expect("tempdir");
+        let send = r#"#!/usr/bin/env bash
+set -euo pipefail
+addr="${AM_E2E_FIXTURE_URL#http://}"
+exec 3<>"/dev/tcp/${addr%:*}/${addr##*:}"
+printf 'POST /mcp/ HTTP/1.1\r\nHost: %s\r\nContent-Length: %s\r\n\r\n%s' \
+    "$addr" "${#BODY}" "$BODY" >&3
+cat <&3
+echo "Pass: 1  Fail: 0  Skip: 0"
+"#;
+        write_suite_script(
+            temp.path(),
+            "known",
+            &send.replace("set -euo", r#"BODY='{"id":1}'; set -euo"#),
+        );
+        write_suite_script(
+            temp.path(),
+            "unknown",
+            &send.replace("set -euo", r#"BODY='{"id":2}'; set -euo"#),
+        );
+        write_suite_script(temp.path(), "http", "#!/usr/bin/env bash\nexit 0\n");
+        let fixtures = temp.path().join("fixtures");
+        for suite in ["known", "unknown"] {
+            FixtureFile {
+                schema: FIXTURE_SCHEMA.to_string(),
+                suite: suite.to_string(),
+                recorded_at: Utc::now().to_rfc3339(),
+                exchanges: vec![crate::e2e_fixtures::Exchange {
+                    method: "POST".to_string(),
+                    path: "/mcp/".to_string(),
+                    body_sha256: crate::e2e_fixtures::body_sha256(br#"{"id":1}"#),
+                    request_headers: Vec::new(),
+                    request_body: r#"{"id":1}"#.to_string(),
+                    status: 200,
+                    response_headers: Vec::new(),
+                    response_body: "pong-from-fixture".to_string(),
+                }],
+            }
+            .save(&fixtures)
+            .expect("save fixture");
+        }
+
+        let config = RunConfig {
+            project_root: temp.path().to_path_buf(),
+            timeout: Some(Duration::from_secs(10)),
+            fixtures: Some(FixtureMode::Replay(fixtures)),
+            ..Default::default()
+        };
+        let runner = Runner::new(temp.path(), config).expect("runner");
+        let report = runner.run(&["known".to_string(), "unknown".to_string()]);
+
+        let known = &report.results[0];
+        assert!(known.passed, "{known:?}");
+        assert!(known.stdout.contains("pong-from-fixture"), "{known:?}");
+        let unknown = &report.results[1];
+        assert!(!unknown.passed);
+        assert!(
+            unknown.stderr.contains("unexpected request #1: POST /mcp/"),
+            "{unknown:?}"
+        );
+        assert!(unknown.stderr.contains(r#"+   "id": 2"#), "{unknown:?}");
+
+        let native = runner.run(&["http".to_string()]);
+        assert!(!native.results[0].passed);
+        assert!(
+            native.results[0]
+                .stderr
+                .contains("only supports script suites")
+        );
     }
 
     // ── SuiteResult serde ────────────────────────────────────────────────
@@ -2640,6 +3105,10 @@ exit 1
             started_at: "2026-02-12T00:00:00Z".to_string(),
             ended_at: "2026-02-12T00:00:01Z".to_string(),
             results: vec![],
+            jobs: 1,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
         };
         let json = serde_json::to_string(&report).unwrap();
         let back: RunReport = serde_json::from_str(&json).unwrap();
@@ -2659,6 +3128,10 @@ exit 1
             started_at: "2026-02-12T00:00:00Z".to_string(),
             ended_at: "2026-02-12T00:00:01Z".to_string(),
             results: vec![],
+            jobs: 1,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
         };
         let summary = report.format_summary();
         assert!(summary.contains("E2E Run: PASS"));
@@ -2870,6 +3343,10 @@ exit 1
             started_at: String::new(),
             ended_at: String::new(),
             results: vec![],
+            jobs: 1,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
         };
         assert_eq!(r.exit_code(), 0);
     }
@@ -2885,6 +3362,10 @@ exit 1
             started_at: String::new(),
             ended_at: String::new(),
             results: vec![],
+            jobs: 1,
+            suite_duration_ms: 0,
+            skipped_suites: Vec::new(),
+            log_dir: None,
         };
         assert_eq!(r.exit_code(), 1);
     }
@@ -0,0 +1,490 @@
+//! Evidence-ledger inspection for `am tooling evidence`.
+//!
+//! The ledger is the append-only JSONL file named by `AM_EVIDENCE_LEDGER_PATH`
+//! and can reach hundreds of megabytes, so every reader here streams it one
+//! line at a time and only ever holds the requested window in memory. A line
+//! that does not parse — usually a partial trailing write left by a crash
+//! mid-append — is skipped and counted instead of aborting the scan.
+//!
+//! `rotate` renames the active file to `<ledger>.1`, shifts older rotations up
+//! (`.1` → `.2`, …), and deletes whatever falls past `--keep`. A server that
+//! already has the ledger open keeps appending to the renamed file until it
+//! restarts.
+
+#![forbid(unsafe_code)]
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{CliError, CliResult};
+
+/// File formats accepted by `am tooling evidence export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// A single JSON array of ledger records.
+    Json,
+    /// One row per record; the evidence payload is a compact JSON column.
+    Csv,
+}
+
+/// Line counters reported by every ledger scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ScanStats {
+    pub scanned: u64,
+    pub matched: u64,
+    pub skipped_corrupt: u64,
+}
+
+/// Record filter for `am tooling evidence query`. Unset fields match anything.
+#[derive(Debug, Clone, Default)]
+pub struct EvidenceFilter {
+    /// Keep records at or after this timestamp (microseconds).
+    pub since_us: Option<i64>,
+    /// Exact record kind, or a dotted prefix (`search` matches `search.hybrid_budget`).
+    pub kind: Option<String>,
+    /// Agent named in the evidence payload (`agent`, `agent_name`, or `sender`).
+    pub agent: Option<String>,
+}
+
+impl EvidenceFilter {
+    #[must_use]
+    pub fn matches(&self, record: &Value) -> bool {
+        if let Some(since_us) = self.since_us
+            && record_ts_micros(record).is_none_or(|ts| ts < since_us)
+        {
+            return false;
+        }
+        if let Some(kind) = self.kind.as_deref() {
+            let actual = record_kind(record);
+            if actual != kind && !actual.starts_with(&format!("{kind}.")) {
+                return false;
+            }
+        }
+        if let Some(agent) = self.agent.as_deref() {
+            let evidence = record.get("evidence");
+            let named = ["agent", "agent_name", "sender"].iter().any(|key| {
+                evidence
+                    .and_then(|e| e.get(key))
+                    .and_then(Value::as_str)
+                    .is_some_and(|name| name.eq_ignore_ascii_case(agent))
+            });
+            if !named {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Resolve the ledger path: an explicit `--ledger` wins over the environment.
+pub fn ledger_path(explicit: Option<PathBuf>) -> CliResult<PathBuf> {
+    explicit
+        .or_else(|| {
+            mcp_agent_mail_core::config::process_env_value(
+                mcp_agent_mail_core::EVIDENCE_LEDGER_PATH_ENV,
+            )
+            .map(|raw| raw.trim().to_string())
+            .filter(|raw| !raw.is_empty())
+            .map(PathBuf::from)
+        })
+        .ok_or_else(|| {
+            CliError::InvalidArgument(format!(
+                "no evidence ledger configured; set {} or pass --ledger",
+                mcp_agent_mail_core::EVIDENCE_LEDGER_PATH_ENV
+            ))
+        })
+}
+
+/// Parse `--since`: an RFC3339 timestamp, a `YYYY-MM-DD` date (UTC midnight),
+/// or an age such as `24h` counted back from `now_us`.
+pub fn parse_since(value: &str, now_us: i64) -> CliResult<i64> {
+    let value = value.trim();
+    if let Some(micros) = mcp_agent_mail_core::timestamps::iso_to_micros(value) {
+        return Ok(micros);
+    }
+    if let Some(midnight) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    {
+        return Ok(mcp_agent_mail_core::timestamps::naive_to_micros(midnight));
+    }
+    crate::duration_arg::suffixed(value)
+        .map(|age| now_us.saturating_sub(crate::duration_arg::micros(age)))
+        .map_err(|_| {
+            CliError::InvalidArgument(format!(
+                "invalid --since value: {value} (expected RFC3339, YYYY-MM-DD, or an age like 24h)"
+            ))
+        })
+}
+
+/// `decision_point` for decision records, `type` for sideband records such
+/// as outcome backfills.
+#[must_use]
+pub fn record_kind(record: &Value) -> &str {
+    record
+        .get("decision_point")
+        .or_else(|| record.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or("unknown")
+}
+
+#[must_use]
+pub fn record_ts_micros(record: &Value) -> Option<i64> {
+    record.get("ts_micros").and_then(Value::as_i64)
+}
+
+/// Stream every parseable record in `path` through `visit`.
+pub fn scan(path: &Path, mut visit: impl FnMut(Value) -> io::Result<()>) -> CliResult<ScanStats> {
+    let file = File::open(path).map_err(|e| {
+        CliError::Other(format!(
+            "cannot open evidence ledger {}: {e}",
+            path.display()
+        ))
+    })?;
+    let mut reader = BufReader::new(file);
+    let mut stats = ScanStats::default();
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        stats.scanned += 1;
+        match serde_json::from_slice::<Value>(&line) {
+            Ok(record) if record.is_object() => visit(record)?,
+            _ => stats.skipped_corrupt += 1,
+        }
+    }
+    Ok(stats)
+}
+
+/// The newest `limit` records matching `filter`, oldest first.
+pub fn query(
+    path: &Path,
+    filter: &EvidenceFilter,
+    limit: usize,
+) -> CliResult<(Vec<Value>, ScanStats)> {
+    let mut window = VecDeque::with_capacity(limit.min(4096));
+    let mut matched = 0_u64;
+    let mut stats = scan(path, |record| {
+        if filter.matches(&record) {
+            matched += 1;
+            if window.len() == limit {
+                window.pop_front();
+            }
+            if limit > 0 {
+                window.push_back(record);
+            }
+        }
+        Ok(())
+    })?;
+    stats.matched = matched;
+    Ok((window.into(), stats))
+}
+
+const CSV_COLUMNS: [&str; 9] = [
+    "seq",
+    "ts",
+    "kind",
+    "decision_id",
+    "action",
+    "confidence",
+    "trace_id",
+    "model",
+    "evidence",
+];
+
+fn csv_row(record: &Value) -> String {
+    let text = |key: &str| match record.get(key) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    let ts = record_ts_micros(record)
+        .map(mcp_agent_mail_core::timestamps::micros_to_iso)
+        .unwrap_or_default();
+    let cells = [
+        text("seq"),
+        ts,
+        record_kind(record).to_string(),
+        text("decision_id"),
+        text("action"),
+        text("confidence"),
+        text("trace_id"),
+        text("model"),
+        record
+            .get("evidence")
+            .map(Value::to_string)
+            .unwrap_or_default(),
+    ];
+    cells
+        .iter()
+        .map(|cell| crate::output::csv_field(cell))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Stream records matching `filter` from `path` into `output`.
+///
+/// The export is written to a sibling temp file and renamed into place, so a
+/// failed export never leaves a truncated file at `output`.
+pub fn export(
+    path: &Path,
+    filter: &EvidenceFilter,
+    output: &Path,
+    format: ExportFormat,
+) -> CliResult<ScanStats> {
+    let tmp = output.with_extension("partial");
+    let mut writer = BufWriter::new(File::create(&tmp)?);
+    match format {
+        ExportFormat::Json => writer.write_all(b"[")?,
+        ExportFormat::Csv => writeln!(writer, "{}", CSV_COLUMNS.join(","))?,
+    }
+    let mut matched = 0_u64;
+    let result = scan(path, |record| {
+        if !filter.matches(&record) {
+            return Ok(());
+        }
+        match format {
+            ExportFormat::Json => {
+                writer.write_all(if matched == 0 { b"\n" } else { b",\n" })?;
+                serde_json::to_writer(&mut writer, &record).map_err(io::Error::other)?;
+            }
+            ExportFormat::Csv => writeln!(writer, "{}", csv_row(&record))?,
+        }
+        matched += 1;
+        Ok(())
+    });
+    let finished = result.and_then(|mut stats| {
+        if format == ExportFormat::Json {
+            writer.write_all(b"\n]\n")?;
+        }
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        std::fs::rename(&tmp, output)?;
+        stats.matched = matched;
+        Ok(stats)
+    });
+    if finished.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    finished
+}
+
+/// Outcome of `am tooling evidence rotate`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RotateReport {
+    pub ledger: PathBuf,
+    pub size_bytes: u64,
+    pub rotated: bool,
+    pub rotated_to: Option<PathBuf>,
+    pub pruned: Vec<PathBuf>,
+}
+
+fn rotation_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{index}"));
+    path.with_file_name(name)
+}
+
+/// Existing `<ledger>.<n>` rotations, by index.
+fn existing_rotations(path: &Path) -> Vec<(u32, PathBuf)> {
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{file_name}.");
+    let dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut rotations: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let index = name.to_str()?.strip_prefix(&prefix)?.parse::<u32>().ok()?;
+            (index > 0).then(|| (index, entry.path()))
+        })
+        .collect();
+    rotations.sort_by_key(|(index, _)| *index);
+    rotations
+}
+
+/// Rotate the ledger once it reaches `max_size_bytes`, keeping at most `keep`
+/// rotated files.
+pub fn rotate(path: &Path, max_size_bytes: u64, keep: u32) -> CliResult<RotateReport> {
+    let size_bytes = match std::fs::metadata(path) {
+        Ok(meta) => meta.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(e.into()),
+    };
+    let mut report = RotateReport {
+        ledger: path.to_path_buf(),
+        size_bytes,
+        ..RotateReport::default()
+    };
+    if size_bytes == 0 || size_bytes < max_size_bytes {
+        return Ok(report);
+    }
+
+    // Shift from the highest index down so no rename clobbers a live file;
+    // anything that would land past `keep` is deleted instead.
+    for (index, rotated) in existing_rotations(path).into_iter().rev() {
+        if index >= keep {
+            std::fs::remove_file(&rotated)?;
+            report.pruned.push(rotated);
+        } else {
+            std::fs::rename(&rotated, rotation_path(path, index + 1))?;
+        }
+    }
+    let target = rotation_path(path, 1);
+    std::fs::rename(path, &target)?;
+    report.rotated = true;
+    report.rotated_to = Some(target);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_ledger(dir: &Path, lines: &[&str]) -> PathBuf {
+        let path = dir.join("evidence.jsonl");
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    fn entry(ts: i64, point: &str, agent: &str) -> String {
+        serde_json::json!({
+            "seq": ts,
+            "ts_micros": ts,
+            "decision_id": format!("{point}-{ts}"),
+            "decision_point": point,
+            "action": "go",
+            "confidence": 0.9,
+            "evidence": {"agent": agent},
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn query_filters_and_skips_corrupt_trailing_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines = [
+            entry(10, "search.hybrid_budget", "BlueLake"),
+            r#"{"type":"outcome","seq":10,"actual":"ok","correct":true}"#.to_string(),
+            entry(20, "cache.evict", "BlueLake"),
+            entry(30, "search.hybrid_budget", "RedFox"),
+            r#"{"seq":40,"ts_micros":40,"decision_po"#.to_string(),
+        ];
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_ledger(dir.path(), &refs);
+
+        let filter = EvidenceFilter {
+            since_us: Some(15),
+            kind: Some("search".to_string()),
+            agent: None,
+        };
+        let (records, stats) = query(&path, &filter, 100).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(record_ts_micros(&records[0]), Some(30));
+        assert_eq!(stats.scanned, 5);
+        assert_eq!(stats.matched, 1);
+        assert_eq!(stats.skipped_corrupt, 1);
+
+        let by_agent = EvidenceFilter {
+            agent: Some("bluelake".to_string()),
+            ..EvidenceFilter::default()
+        };
+        let (records, _) = query(&path, &by_agent, 1).unwrap();
+        assert_eq!(records.len(), 1, "limit keeps only the newest match");
+        assert_eq!(record_kind(&records[0]), "cache.evict");
+
+        let outcomes = EvidenceFilter {
+            kind: Some("outcome".to_string()),
+            ..EvidenceFilter::default()
+        };
+        assert_eq!(query(&path, &outcomes, 10).unwrap().1.matched, 1);
+    }
+
+    #[test]
+    fn parse_since_accepts_timestamps_dates_and_ages() {
+        let now = 10 * 3_600_000_000;
+        assert_eq!(parse_since("2h", now).unwrap(), 8 * 3_600_000_000);
+        assert_eq!(parse_since("1970-01-01", now).unwrap(), 0);
+        assert_eq!(
+            parse_since("1970-01-01T00:00:01Z", now).unwrap(),
+            1_000_000
+        );
+        assert!(parse_since("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn export_writes_json_and_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines = [entry(1, "a.b", "X"), entry(2, "c.d", "Y")];
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let path = write_ledger(dir.path(), &refs);
+
+        let json_out = dir.path().join("out.json");
+        let stats = export(
+            &path,
+            &EvidenceFilter::default(),
+            &json_out,
+            ExportFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(stats.matched, 2);
+        let parsed: Vec<Value> =
+            serde_json::from_str(&std::fs::read_to_string(&json_out).unwrap()).unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let csv_out = dir.path().join("out.csv");
+        export(
+            &path,
+            &EvidenceFilter::default(),
+            &csv_out,
+            ExportFormat::Csv,
+        )
+        .unwrap();
+        let csv = std::fs::read_to_string(&csv_out).unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], CSV_COLUMNS.join(","));
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].contains("a.b") && rows[1].contains(r#""{""agent"":""X""}""#));
+    }
+
+    #[test]
+    fn rotate_shifts_and_prunes_old_rotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_ledger(dir.path(), &["{}"; 8]);
+        std::fs::write(rotation_path(&path, 1), b"one").unwrap();
+        std::fs::write(rotation_path(&path, 2), b"two").unwrap();
+
+        let skipped = rotate(&path, 1024 * 1024, 2).unwrap();
+        assert!(!skipped.rotated, "below the size threshold nothing moves");
+
+        let report = rotate(&path, 1, 2).unwrap();
+        assert!(report.rotated);
+        assert!(!path.exists());
+        assert_eq!(report.pruned, vec![rotation_path(&path, 2)]);
+        assert_eq!(std::fs::read(rotation_path(&path, 2)).unwrap(), b"one");
+        assert!(
+            std::fs::read_to_string(rotation_path(&path, 1))
+                .unwrap()
+                .starts_with("{}")
+        );
+        assert!(!rotation_path(&path, 3).exists());
+    }
+}
@@ -164,6 +164,10 @@ fn render_flag_list(snapshots: &[FlagSnapshot], fmt: output::CliOutputFormat) ->
             }
             Ok(())
         }
+        output::CliOutputFormat::Csv | output::CliOutputFormat::Markdown => {
+            output::emit_output(snapshots, fmt, || {});
+            Ok(())
+        }
         output::CliOutputFormat::Table => {
             if snapshots.is_empty() {
                 ftui_runtime::ftui_println!("No flags matched the requested filters.");
@@ -239,6 +243,10 @@ fn render_flag_status(snapshot: &FlagSnapshot, fmt: output::CliOutputFormat) ->
             );
             Ok(())
         }
+        output::CliOutputFormat::Csv | output::CliOutputFormat::Markdown => {
+            output::emit_output(snapshot, fmt, || {});
+            Ok(())
+        }
         output::CliOutputFormat::Table => {
             let mut table =
                 output::CliTable::new(vec!["NAME", "VALUE", "SOURCE", "DEFAULT", "DYNAMIC"]);
@@ -283,6 +291,10 @@ fn render_flag_explain(snapshot: &FlagSnapshot, fmt: output::CliOutputFormat) ->
             }
             Ok(())
         }
+        output::CliOutputFormat::Csv | output::CliOutputFormat::Markdown => {
+            output::emit_output(snapshot, fmt, || {});
+            Ok(())
+        }
         output::CliOutputFormat::Table => {
             let mut table = output::CliTable::new(vec!["FIELD", "VALUE"]);
             table.add_row(vec!["Name".to_string(), snapshot.name.clone()]);
@@ -340,6 +352,10 @@ fn render_flag_mutation(
             );
             Ok(())
         }
+        output::CliOutputFormat::Csv | output::CliOutputFormat::Markdown => {
+            output::emit_output(snapshot, fmt, || {});
+            Ok(())
+        }
         output::CliOutputFormat::Table => {
             ftui_runtime::ftui_println!(
                 "Updated {} ({}) in {}.",
@@ -277,13 +277,15 @@ pub fn sha256_hex(text: &str) -> String {
 }
 
 /// Parse `sha256sum`-style file: `<hex><space><space><filename>`.
+///
+/// Lines starting with `#` (approval audit comments) are skipped.
 pub fn read_checksums_file(path: &Path) -> Result<BTreeMap<String, String>, GoldenChecksumError> {
     let content = std::fs::read_to_string(path)?;
     let mut out = BTreeMap::new();
     for (idx, raw_line) in content.lines().enumerate() {
         let line_no = idx + 1;
         let line = raw_line.trim();
-        if line.is_empty() {
+        if line.is_empty() || line.starts_with('#') {
             continue;
         }
         let Some((hash_raw, filename_raw)) = line.split_once(char::is_whitespace) else {
@@ -327,6 +329,59 @@ pub fn write_checksums_file(
     Ok(())
 }
 
+/// Rewrite only the `updates` entries of an existing checksum file.
+///
+/// Every other line, including earlier approval comments, is kept verbatim;
+/// entries not yet present are appended. A `# approved_by=... at=...` comment
+/// naming the accepted files is added after the existing header comments.
+pub fn update_checksums_file(
+    path: &Path,
+    updates: &BTreeMap<String, String>,
+    approved_by: &str,
+    approved_at: &str,
+) -> Result<(), GoldenChecksumError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    let mut header = Vec::new();
+    let mut body = Vec::new();
+    let mut remaining = updates.clone();
+    for raw_line in content.lines() {
+        let trimmed = raw_line.trim();
+        if trimmed.starts_with('#') && body.is_empty() {
+            header.push(raw_line.to_string());
+            continue;
+        }
+        let filename = trimmed
+            .split_once(char::is_whitespace)
+            .map(|(_, name)| name.trim());
+        match filename.and_then(|name| remaining.remove_entry(name)) {
+            Some((name, hash)) => body.push(format!("{hash}  {name}")),
+            None => body.push(raw_line.to_string()),
+        }
+    }
+    body.extend(
+        remaining
+            .into_iter()
+            .map(|(name, hash)| format!("{hash}  {name}")),
+    );
+    let files = updates.keys().map(String::as_str).collect::<Vec<_>>();
+    header.push(format!(
+        "# approved_by={approved_by} at={approved_at} files={}",
+        files.join(",")
+    ));
+
+    let mut output = String::new();
+    for line in header.iter().chain(&body) {
+        output.push_str(line);
+        output.push('\n');
+    }
+    std::fs::write(path, output)?;
+    Ok(())
+}
+
 fn build_inline_diff(expected: &str, actual: &str, context_lines: usize) -> String {
     let expected_lines: Vec<&str> = expected.lines().collect();
     let actual_lines: Vec<&str> = actual.lines().collect();
@@ -495,6 +550,37 @@ mod tests {
         assert_eq!(loaded, checksums);
     }
 
+    #[test]
+    fn update_checksums_file_rewrites_only_selected_entries() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("checksums.sha256");
+        let keep = format!("{}  a.txt", sha256_hex("a"));
+        std::fs::write(&path, format!("{keep}\n{}  b.txt\n", sha256_hex("b"))).expect("write");
+
+        let mut updates = BTreeMap::new();
+        updates.insert("b.txt".to_string(), sha256_hex("b2"));
+        update_checksums_file(&path, &updates, "alice", "2026-02-12T07:30:59Z")
+            .expect("first update");
+        updates.insert("b.txt".to_string(), sha256_hex("b3"));
+        update_checksums_file(&path, &updates, "bob", "2026-02-13T07:30:59Z")
+            .expect("second update");
+
+        let content = std::fs::read_to_string(&path).expect("read");
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(
+            lines[0],
+            "# approved_by=alice at=2026-02-12T07:30:59Z files=b.txt"
+        );
+        assert_eq!(
+            lines[1],
+            "# approved_by=bob at=2026-02-13T07:30:59Z files=b.txt"
+        );
+        assert_eq!(lines[2], keep);
+        let loaded = read_checksums_file(&path).expect("read checksums");
+        assert_eq!(loaded["a.txt"], sha256_hex("a"));
+        assert_eq!(loaded["b.txt"], sha256_hex("b3"));
+    }
+
     #[test]
     fn read_checksums_file_rejects_invalid_hash() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -0,0 +1,657 @@
+//! `am init`: first-run wizard for a fresh install.
+//!
+//! Walks through the storage root (persisted to `config.env`), agent
+//! detection and MCP setup, the git guard, and a first agent identity. Each
+//! step delegates to the existing command handler through [`CliRunner`] so
+//! its payload can be folded into one summary; every step can be skipped and
+//! re-running the wizard only rewrites what changed.
+
+#![forbid(unsafe_code)]
+
+use crate::runner::CliRunner;
+use crate::{AgentsCommand, CliError, CliResult, Commands, GuardCommand, SetupCommand, output};
+use clap::Args;
+use mcp_agent_mail_core::Config;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Agent program suggested when registering the first agent.
+const DEFAULT_PROGRAM: &str = "claude-code";
+
+#[derive(Args, Debug, Clone)]
+pub struct InitArgs {
+    /// Accept every default without prompting (for CI provisioning).
+    #[arg(long, short = 'y', default_value_t = false)]
+    pub yes: bool,
+    /// Storage root to use (default: the configured STORAGE_ROOT).
+    #[arg(long)]
+    pub storage_root: Option<PathBuf>,
+    /// Project directory for project-local configs and the guard (default: cwd).
+    #[arg(long)]
+    pub project_dir: Option<PathBuf>,
+    /// Skip choosing the storage root and writing it to config.env.
+    #[arg(long, default_value_t = false)]
+    pub skip_storage: bool,
+    /// Skip agent detection and MCP config setup.
+    #[arg(long, default_value_t = false)]
+    pub skip_setup: bool,
+    /// Skip installing the git guard into the project repo.
+    #[arg(long, default_value_t = false)]
+    pub skip_guard: bool,
+    /// Skip registering a first agent (and the start-session snippet).
+    #[arg(long, default_value_t = false)]
+    pub skip_agent: bool,
+    /// Program of the first agent. With --model, registers it under --yes.
+    #[arg(long)]
+    pub program: Option<String>,
+    /// Model of the first agent.
+    #[arg(long)]
+    pub model: Option<String>,
+    /// Name of the first agent (default: AGENT_MAIL_AGENT, else generated).
+    /// Pass it to keep re-runs from registering a new identity each time.
+    #[arg(long)]
+    pub agent_name: Option<String>,
+    /// Output format: table, json, or toon (default: auto-detect).
+    #[arg(long, value_parser)]
+    pub format: Option<output::CliOutputFormat>,
+    /// Output JSON (shorthand for --format json).
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum InitStepStatus {
+    /// The step wrote something.
+    Done,
+    /// The step ran and everything was already in place.
+    Unchanged,
+    Skipped,
+    Failed,
+}
+
+impl InitStepStatus {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Done => "done",
+            Self::Unchanged => "unchanged",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct InitStep {
+    step: &'static str,
+    status: InitStepStatus,
+    detail: String,
+    /// Files and directories the step wrote (or confirmed in place).
+    paths: Vec<String>,
+}
+
+impl InitStep {
+    fn new(step: &'static str, status: InitStepStatus, detail: impl Into<String>) -> Self {
+        Self {
+            step,
+            status,
+            detail: detail.into(),
+            paths: Vec::new(),
+        }
+    }
+
+    fn with_paths(mut self, paths: Vec<String>) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    fn failed(step: &'static str, err: &CliError) -> Self {
+        Self::new(step, InitStepStatus::Failed, err.to_string())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct InitReport {
+    storage_root: String,
+    env_file: String,
+    project_dir: String,
+    steps: Vec<InitStep>,
+    /// `macro_start_session` call to paste into an agent when no agent was
+    /// registered here.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_session_snippet: Option<serde_json::Value>,
+}
+
+/// Answers prompts, or takes every default under `--yes`.
+struct Prompter {
+    yes: bool,
+}
+
+impl Prompter {
+    fn confirm(&self, label: &str, default: bool) -> CliResult<bool> {
+        if self.yes {
+            return Ok(default);
+        }
+        crate::prompt_bool(label, default)
+    }
+
+    /// Ask for a value; an empty answer keeps `default`.
+    fn ask(&self, label: &str, default: &str) -> CliResult<String> {
+        if self.yes {
+            return Ok(default.to_string());
+        }
+        let answer = crate::prompt_line(&format!("{label} [{default}]: "))?;
+        let answer = answer.trim();
+        Ok(if answer.is_empty() {
+            default.to_string()
+        } else {
+            answer.to_string()
+        })
+    }
+}
+
+pub fn handle_init(args: InitArgs) -> CliResult<()> {
+    let fmt = output::CliOutputFormat::resolve(args.format, args.json);
+    if !args.yes && !output::is_stdin_tty() {
+        return Err(CliError::Other(
+            "refusing to run the init wizard non-interactively without --yes".to_string(),
+        ));
+    }
+    let prompter = Prompter { yes: args.yes };
+    let config = Config::from_env();
+    let project_dir = args
+        .project_dir
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+    let env_file = crate::setup_config_env_file();
+
+    let mut steps = Vec::with_capacity(4);
+    let storage_root = if args.skip_storage {
+        steps.push(InitStep::new(
+            "storage",
+            InitStepStatus::Skipped,
+            "skipped by --skip-storage",
+        ));
+        config.storage_root.clone()
+    } else {
+        let default_root = args
+            .storage_root
+            .clone()
+            .unwrap_or_else(|| config.storage_root.clone());
+        let root =
+            PathBuf::from(prompter.ask("Storage root", &default_root.display().to_string())?);
+        steps.push(
+            init_storage_root(&root, &env_file)
+                .unwrap_or_else(|err| InitStep::failed("storage", &err)),
+        );
+        root
+    };
+
+    // Later steps run against the chosen root even before config.env is
+    // re-read by a new process.
+    let runner = CliRunner::new().storage_root(&storage_root);
+    steps.push(init_setup_step(
+        &prompter,
+        &runner,
+        &args,
+        &config,
+        &project_dir,
+    )?);
+    steps.push(init_guard_step(&prompter, &runner, &args, &project_dir)?);
+    let (agent_step, start_session_snippet) =
+        init_agent_step(&prompter, &runner, &args, &project_dir)?;
+    steps.push(agent_step);
+
+    let report = InitReport {
+        storage_root: storage_root.display().to_string(),
+        env_file: env_file.display().to_string(),
+        project_dir: project_dir.display().to_string(),
+        steps,
+        start_session_snippet,
+    };
+    output::emit_output(&report, fmt, || render_init_report(&report));
+
+    if report
+        .steps
+        .iter()
+        .any(|step| step.status == InitStepStatus::Failed)
+    {
+        return Err(CliError::ExitCode(1));
+    }
+    Ok(())
+}
+
+/// Create `root` and record it as `STORAGE_ROOT` in `env_file`.
+fn init_storage_root(root: &Path, env_file: &Path) -> CliResult<InitStep> {
+    let existed = root.is_dir();
+    std::fs::create_dir_all(root).map_err(|e| {
+        CliError::Other(format!(
+            "could not create storage root {}: {e}",
+            root.display()
+        ))
+    })?;
+    let written = mcp_agent_mail_core::setup::save_value_to_env_file(
+        env_file,
+        "STORAGE_ROOT",
+        &root.display().to_string(),
+    )
+    .map_err(|e| CliError::Other(format!("could not update {}: {e}", env_file.display())))?;
+
+    let status = if existed && !written {
+        InitStepStatus::Unchanged
+    } else {
+        InitStepStatus::Done
+    };
+    let detail = match (existed, written) {
+        (true, false) => "storage root already configured",
+        (true, true) => "recorded existing storage root in config.env",
+        (false, _) => "created storage root and recorded it in config.env",
+    };
+    Ok(InitStep::new("storage", status, detail).with_paths(vec![
+        root.display().to_string(),
+        env_file.display().to_string(),
+    ]))
+}
+
+fn init_setup_step(
+    prompter: &Prompter,
+    runner: &CliRunner,
+    args: &InitArgs,
+    config: &Config,
+    project_dir: &Path,
+) -> CliResult<InitStep> {
+    const STEP: &str = "setup";
+    if args.skip_setup {
+        return Ok(InitStep::new(
+            STEP,
+            InitStepStatus::Skipped,
+            "skipped by --skip-setup",
+        ));
+    }
+
+    let detected: Vec<&str> = crate::detect_installed_setup_agents()
+        .into_iter()
+        .map(|platform| platform.slug())
+        .collect();
+    let prompt = if detected.is_empty() {
+        "No coding agents detected; run setup anyway".to_string()
+    } else {
+        format!("Write MCP configs for {}", detected.join(", "))
+    };
+    if !prompter.confirm(&prompt, true)? {
+        return Ok(InitStep::new(STEP, InitStepStatus::Skipped, "declined"));
+    }
+
+    let command = Commands::Setup {
+        action: SetupCommand::Run {
+            agent: None,
+            dry_run: false,
+            yes: true,
+            token: None,
+            port: config.http_port,
+            host: config.http_host.clone(),
+            path: config.http_path.clone(),
+            project_dir: Some(project_dir.to_path_buf()),
+            format: Some(output::CliOutputFormat::Json),
+            json: false,
+            no_user_config: false,
+            no_hooks: false,
+            prune: false,
+            project_token: false,
+            uds: config.http_uds_path.clone(),
+            write_instructions: false,
+            remove_instructions: false,
+        },
+    };
+    let results = match runner.run(command) {
+        Ok(out) => out.payload().cloned().unwrap_or_default(),
+        Err(err) => return Ok(InitStep::failed(STEP, &err)),
+    };
+    Ok(summarize_setup_results(&results))
+}
+
+/// Fold a `setup run` payload into one wizard step.
+fn summarize_setup_results(results: &serde_json::Value) -> InitStep {
+    const STEP: &str = "setup";
+    let actions: Vec<&serde_json::Value> = results
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|result| result.get("actions").and_then(|a| a.as_array()))
+        .flatten()
+        .collect();
+    if actions.is_empty() {
+        return InitStep::new(
+            STEP,
+            InitStepStatus::Skipped,
+            "no coding agents detected; run `am setup run --agent <name>` later",
+        );
+    }
+
+    let outcome = |action: &serde_json::Value| {
+        let outcome = action.get("outcome");
+        outcome
+            .and_then(|o| o.as_str())
+            .or_else(|| outcome.and_then(|o| o.as_object()?.keys().next().map(String::as_str)))
+            .unwrap_or("")
+            .to_string()
+    };
+    let count = |wanted: &str| actions.iter().filter(|a| outcome(a) == wanted).count();
+    let (created, updated, failed) = (count("created"), count("updated"), count("failed"));
+    let paths = actions
+        .iter()
+        .filter(|a| matches!(outcome(a).as_str(), "created" | "updated" | "unchanged"))
+        .filter_map(|a| a.get("file_path").and_then(|p| p.as_str()))
+        .map(str::to_string)
+        .collect();
+    let status = if failed > 0 {
+        InitStepStatus::Failed
+    } else if created + updated > 0 {
+        InitStepStatus::Done
+    } else {
+        InitStepStatus::Unchanged
+    };
+    InitStep::new(
+        STEP,
+        status,
+        format!(
+            "{} config files: {created} created, {updated} updated, {failed} failed",
+            actions.len()
+        ),
+    )
+    .with_paths(paths)
+}
+
+fn init_guard_step(
+    prompter: &Prompter,
+    runner: &CliRunner,
+    args: &InitArgs,
+    project_dir: &Path,
+) -> CliResult<InitStep> {
+    const STEP: &str = "guard";
+    if args.skip_guard {
+        return Ok(InitStep::new(
+            STEP,
+            InitStepStatus::Skipped,
+            "skipped by --skip-guard",
+        ));
+    }
+    if !project_dir.join(".git").exists() {
+        return Ok(InitStep::new(
+            STEP,
+            InitStepStatus::Skipped,
+            format!("{} is not a git repository", project_dir.display()),
+        ));
+    }
+    if !prompter.confirm(
+        &format!(
+            "Install the file-reservation guard into {}",
+            project_dir.display()
+        ),
+        true,
+    )? {
+        return Ok(InitStep::new(STEP, InitStepStatus::Skipped, "declined"));
+    }
+
+    let already_installed = mcp_agent_mail_guard::guard_status(project_dir)
+        .is_ok_and(|status| status.pre_commit_present && status.pre_push_present);
+    let project = project_dir.display().to_string();
+    let command = Commands::Guard {
+        action: GuardCommand::Install {
+            project,
+            repo: project_dir.to_path_buf(),
+            prepush: true,
+            no_prepush: false,
+            commit_context: false,
+        },
+    };
+    if let Err(err) = runner.run(command) {
+        return Ok(InitStep::failed(STEP, &err));
+    }
+    let paths = mcp_agent_mail_guard::guard_status(project_dir)
+        .map(|status| {
+            let hooks_dir = PathBuf::from(status.hooks_dir);
+            vec![
+                hooks_dir.join("pre-commit").display().to_string(),
+                hooks_dir.join("pre-push").display().to_string(),
+            ]
+        })
+        .unwrap_or_default();
+    Ok(if already_installed {
+        InitStep::new(STEP, InitStepStatus::Unchanged, "guard hooks refreshed")
+    } else {
+        InitStep::new(
+            STEP,
+            InitStepStatus::Done,
+            "installed pre-commit and pre-push guard",
+        )
+    }
+    .with_paths(paths))
+}
+
+fn init_agent_step(
+    prompter: &Prompter,
+    runner: &CliRunner,
+    args: &InitArgs,
+    project_dir: &Path,
+) -> CliResult<(InitStep, Option<serde_json::Value>)> {
+    const STEP: &str = "agent";
+    if args.skip_agent {
+        return Ok((
+            InitStep::new(STEP, InitStepStatus::Skipped, "skipped by --skip-agent"),
+            None,
+        ));
+    }
+
+    let human_key = project_dir.display().to_string();
+    let have_identity = args.program.is_some() && args.model.is_some();
+    if !prompter.confirm("Register a first agent now", have_identity)? {
+        let snippet = start_session_snippet(&human_key, args.program.as_deref());
+        return Ok((
+            InitStep::new(
+                STEP,
+                InitStepStatus::Skipped,
+                "no agent registered; start one with the macro_start_session call below",
+            ),
+            Some(snippet),
+        ));
+    }
+
+    let program = prompter.ask(
+        "Agent program",
+        args.program.as_deref().unwrap_or(DEFAULT_PROGRAM),
+    )?;
+    let model = prompter.ask("Agent model", args.model.as_deref().unwrap_or("unknown"))?;
+    let default_name = args
+        .agent_name
+        .clone()
+        .or_else(|| std::env::var("AGENT_MAIL_AGENT").ok())
+        .unwrap_or_default();
+    let name = if default_name.is_empty() && !prompter.yes {
+        let answer = crate::prompt_line("Agent name (blank to generate): ")?;
+        Some(answer.trim().to_string()).filter(|n| !n.is_empty())
+    } else {
+        Some(default_name).filter(|n| !n.is_empty())
+    };
+
+    let command = Commands::Agents {
+        action: AgentsCommand::Register {
+            project_key: human_key,
+            program,
+            model,
+            name,
+            task: None,
+            attachments_policy: "auto".to_string(),
+            generate_secret: false,
+            format: Some(output::CliOutputFormat::Json),
+            json: false,
+        },
+    };
+    match runner.run(command) {
+        Ok(out) => {
+            let agent = out
+                .payload()
+                .and_then(|payload| payload.get("name"))
+                .and_then(|name| name.as_str())
+                .unwrap_or("?")
+                .to_string();
+            Ok((
+                InitStep::new(
+                    STEP,
+                    InitStepStatus::Done,
+                    format!("registered agent {agent}"),
+                ),
+                None,
+            ))
+        }
+        Err(err) => Ok((InitStep::failed(STEP, &err), None)),
+    }
+}
+
+/// The `macro_start_session` tool call an agent can paste to join the project.
+fn start_session_snippet(human_key: &str, program: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "tool": "macro_start_session",
+        "arguments": {
+            "human_key": human_key,
+            "program": program.unwrap_or(DEFAULT_PROGRAM),
+            "model": "<your-model>",
+            "task_description": "<what you are working on>",
+        },
+    })
+}
+
+fn render_init_report(report: &InitReport) {
+    let rows = report
+        .steps
+        .iter()
+        .map(|step| {
+            vec![
+                step.step.to_string(),
+                step.status.label().to_string(),
+                step.detail.clone(),
+            ]
+        })
+        .collect();
+    crate::print_table(Some("am init"), &["step", "status", "detail"], rows);
+
+    let paths: Vec<&String> = report.steps.iter().flat_map(|step| &step.paths).collect();
+    if !paths.is_empty() {
+        ftui_runtime::ftui_println!();
+        output::section("Paths:");
+        for path in paths {
+            ftui_runtime::ftui_println!("  {path}");
+        }
+    }
+    if let Some(snippet) = &report.start_session_snippet {
+        ftui_runtime::ftui_println!();
+        output::section("Start a session from your agent with:");
+        ftui_runtime::ftui_println!(
+            "{}",
+            serde_json::to_string_pretty(snippet).unwrap_or_default()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn clap_parses_init_flags() {
+        let cli = crate::Cli::try_parse_from([
+            "am",
+            "init",
+            "--yes",
+            "--skip-guard",
+            "--program",
+            "codex-cli",
+            "--model",
+            "gpt-5",
+        ])
+        .unwrap();
+        match cli.command.expect("expected command") {
+            Commands::Init(args) => {
+                assert!(args.yes);
+                assert!(args.skip_guard);
+                assert!(!args.skip_setup);
+                assert_eq!(args.program.as_deref(), Some("codex-cli"));
+                assert_eq!(args.model.as_deref(), Some("gpt-5"));
+            }
+            other => panic!("expected Init, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn storage_step_creates_root_and_is_idempotent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("mail-root");
+        let env_file = tmp.path().join("config").join("config.env");
+        std::fs::create_dir_all(env_file.parent().unwrap()).unwrap();
+        std::fs::write(&env_file, "HTTP_BEARER_TOKEN=secret\n").unwrap();
+
+        let first = init_storage_root(&root, &env_file).unwrap();
+        assert_eq!(first.status, InitStepStatus::Done);
+        assert!(root.is_dir());
+        let content = std::fs::read_to_string(&env_file).unwrap();
+        assert!(content.contains("HTTP_BEARER_TOKEN=secret"));
+        assert!(content.contains(&format!("STORAGE_ROOT={}", root.display())));
+
+        let second = init_storage_root(&root, &env_file).unwrap();
+        assert_eq!(second.status, InitStepStatus::Unchanged);
+        assert_eq!(std::fs::read_to_string(&env_file).unwrap(), content);
+    }
+
+    #[test]
+    fn setup_summary_counts_outcomes_and_lists_paths() {
+        let results = serde_json::json!([
+            {
+                "platform": "claude",
+                "actions": [
+                    {"file_path": "/p/.mcp.json", "description": "", "outcome": "created"},
+                    {"file_path": "/h/.claude.json", "description": "", "outcome": "unchanged"},
+                ],
+            },
+            {
+                "platform": "cursor",
+                "actions": [
+                    {"file_path": "/p/.cursor/mcp.json", "description": "", "outcome": {"failed": "denied"}},
+                ],
+            },
+        ]);
+        let step = summarize_setup_results(&results);
+        assert_eq!(step.status, InitStepStatus::Failed);
+        assert_eq!(
+            step.detail,
+            "3 config files: 1 created, 0 updated, 1 failed"
+        );
+        assert_eq!(step.paths, vec!["/p/.mcp.json", "/h/.claude.json"]);
+
+        let empty = summarize_setup_results(&serde_json::json!([]));
+        assert_eq!(empty.status, InitStepStatus::Skipped);
+    }
+
+    #[test]
+    fn declined_agent_step_yields_start_session_snippet() {
+        let args = crate::Cli::try_parse_from(["am", "init", "--yes"]).unwrap();
+        let Some(Commands::Init(args)) = args.command else {
+            panic!("expected Init");
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let (step, snippet) = init_agent_step(
+            &Prompter { yes: true },
+            &CliRunner::new(),
+            &args,
+            tmp.path(),
+        )
+        .unwrap();
+        assert_eq!(step.status, InitStepStatus::Skipped);
+        let snippet = snippet.expect("snippet");
+        assert_eq!(snippet["tool"], "macro_start_session");
+        assert_eq!(
+            snippet["arguments"]["human_key"].as_str(),
+            Some(tmp.path().display().to_string().as_str())
+        );
+    }
+}
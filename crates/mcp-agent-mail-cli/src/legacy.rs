@@ -5,10 +5,14 @@
 //! - `am legacy import`
 //! - `am legacy status`
 //! - `am upgrade`
+//!
+//! Python-era value encodings are normalized after migration; see
+//! [`crate::legacy_schema`] for the mapping table and verification report.
 
 #![forbid(unsafe_code)]
 
-use crate::{CliError, CliResult, SetupCommand, handle_setup, output};
+use crate::legacy_schema::{self, SchemaMappingReport, VerificationReport};
+use crate::{CliError, CliResult, SetupCommand, handle_setup, natural_keys, output};
 use chrono::Utc;
 use clap::{Args, Subcommand};
 use mcp_agent_mail_core::Config;
@@ -110,6 +114,10 @@ pub struct UpgradeArgs {
     /// Skip interactive confirmation prompt.
     #[arg(long, default_value_t = false)]
     pub yes: bool,
+    /// Re-run the post-import verification against the latest import without
+    /// importing again.
+    #[arg(long, default_value_t = false, conflicts_with = "dry_run")]
+    pub verify_only: bool,
     /// Output format: table, json, or toon.
     #[arg(long, value_parser)]
     pub format: Option<output::CliOutputFormat>,
@@ -310,6 +318,14 @@ struct LegacyImportReceipt {
     core_table_counts: BTreeMap<String, i64>,
     setup_refresh_ok: bool,
     warnings: Vec<String>,
+    /// Pre-import snapshot the verification compares against: the untouched
+    /// source in copy mode, the safety backup in in-place mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification_source_db: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_mapping: Option<SchemaMappingReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verification: Option<VerificationReport>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -389,6 +405,9 @@ pub fn handle_legacy(args: LegacyArgs) -> CliResult<()> {
 pub fn handle_upgrade(args: UpgradeArgs) -> CliResult<()> {
     let fmt = output::CliOutputFormat::resolve(args.format, args.json);
     let root = resolve_search_root(args.search_root);
+    if args.verify_only {
+        return handle_upgrade_verify_only(&root, fmt);
+    }
     let detect = build_detect_report(&root, None, None)?;
 
     let mut report = UpgradeReport {
@@ -469,11 +488,115 @@ pub fn handle_upgrade(args: UpgradeArgs) -> CliResult<()> {
                 "- Integrity: {}",
                 if r.integrity_check_ok { "ok" } else { "failed" }
             );
+            if let Some(verification) = &r.verification {
+                print_verification_summary(verification);
+            }
         }
     });
     Ok(())
 }
 
+/// `am upgrade --verify-only`: compare the latest import's pre-import
+/// snapshot with its destination. Exits 1 when they disagree.
+fn handle_upgrade_verify_only(root: &Path, fmt: output::CliOutputFormat) -> CliResult<()> {
+    let storage = resolve_storage_root(root, None)?.path;
+    let status = collect_status_report(&storage)?;
+    let Some(receipt) = status.latest_receipt else {
+        return Err(CliError::InvalidArgument(format!(
+            "no legacy import receipt under {}; run `am upgrade` to import first",
+            status.receipts_dir
+        )));
+    };
+    let source = receipt_verification_source(&receipt).ok_or_else(|| {
+        CliError::InvalidArgument(format!(
+            "receipt {} does not record a pre-import snapshot to verify against",
+            receipt.created_at
+        ))
+    })?;
+    if !source.is_file() {
+        return Err(CliError::InvalidArgument(format!(
+            "pre-import snapshot missing: {}",
+            source.display()
+        )));
+    }
+    let mut report = legacy_schema::verify_import(&source, Path::new(&receipt.target_db))?;
+    report.unmapped = receipt
+        .schema_mapping
+        .map(|mapping| mapping.unmapped)
+        .unwrap_or_default();
+    output::emit_output(&report, fmt, || {
+        ftui_runtime::ftui_println!("Legacy import verification");
+        ftui_runtime::ftui_println!("- Source (pre-import): {}", report.source_db);
+        ftui_runtime::ftui_println!("- Target: {}", report.target_db);
+        print_verification_summary(&report);
+    });
+    if report.ok {
+        Ok(())
+    } else {
+        Err(CliError::ExitCode(1))
+    }
+}
+
+/// Where the rows an import started from still live. Older receipts predate
+/// `verification_source_db`, so derive it from the mode and backup root.
+fn receipt_verification_source(receipt: &LegacyImportReceipt) -> Option<PathBuf> {
+    if let Some(path) = &receipt.verification_source_db {
+        return Some(PathBuf::from(path));
+    }
+    match receipt.mode {
+        ImportMode::Copy => Some(PathBuf::from(&receipt.source_db)),
+        ImportMode::InPlace => {
+            let name = Path::new(&receipt.source_db).file_name()?;
+            Some(
+                Path::new(receipt.backup_root.as_deref()?)
+                    .join("db")
+                    .join(name),
+            )
+        }
+    }
+}
+
+fn print_verification_summary(report: &VerificationReport) {
+    ftui_runtime::ftui_println!(
+        "- Verification: {}",
+        if report.ok { "ok" } else { "MISMATCH" }
+    );
+    let count = |rows: Option<i64>| rows.map_or_else(|| "-".to_string(), |n| n.to_string());
+    for table in &report.tables {
+        ftui_runtime::ftui_println!(
+            "  - {}: {} -> {} rows{}{}",
+            table.table,
+            count(table.source_rows),
+            count(table.target_rows),
+            if table.rows_match {
+                ""
+            } else {
+                " (count differs)"
+            },
+            if table.timestamps_match {
+                ""
+            } else {
+                " (timestamp range differs)"
+            }
+        );
+    }
+    ftui_runtime::ftui_println!(
+        "  - message checksums: {} sampled, {} mismatched",
+        report.message_checksums.len(),
+        report.checksum_mismatches
+    );
+    for value in &report.unmapped {
+        ftui_runtime::ftui_println!(
+            "  - unmapped {}.{} {:?} in {} row(s), imported as {:?}",
+            value.table,
+            value.column,
+            value.from.as_deref().unwrap_or("NULL"),
+            value.rows,
+            value.to
+        );
+    }
+}
+
 fn handle_legacy_detect(
     search_root: Option<PathBuf>,
     format: Option<output::CliOutputFormat>,
@@ -684,6 +807,9 @@ fn run_legacy_import(opts: ImportOptions, fmt: output::CliOutputFormat) -> CliRe
                 "failed"
             }
         );
+        if let Some(verification) = &receipt.verification {
+            print_verification_summary(verification);
+        }
         if !receipt.warnings.is_empty() {
             ftui_runtime::ftui_println!("- Warnings:");
             for warning in &receipt.warnings {
@@ -816,7 +942,9 @@ fn build_import_plan(opts: &ImportOptions) -> CliResult<ImportPlan> {
             operations.push("run schema::migrate_to_latest against target DB".to_string());
         }
     }
+    operations.push("normalize Python-era values (importance, timestamps, policies)".to_string());
     operations.push("run integrity_check and core-table sanity queries".to_string());
+    operations.push("verify row counts, timestamp ranges, and message checksums".to_string());
     operations.push("write JSON receipt under target storage root".to_string());
     operations.push("refresh agent MCP config via setup run".to_string());
 
@@ -839,6 +967,7 @@ fn execute_import(plan: ImportPlan, should_refresh_setup: bool) -> CliResult<Leg
     let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
     let mut warnings = Vec::new();
     let mut backup_root: Option<PathBuf> = None;
+    let mut verification_source = plan.source_db.clone();
 
     match plan.mode {
         ImportMode::InPlace => {
@@ -848,6 +977,9 @@ fn execute_import(plan: ImportPlan, should_refresh_setup: bool) -> CliResult<Leg
                 &plan.source_storage_root,
                 &backup_dir.join("storage_root_backup"),
             )?;
+            if let Some(name) = plan.source_db.file_name() {
+                verification_source = backup_dir.join("db").join(name);
+            }
             backup_root = Some(backup_dir);
         }
         ImportMode::Copy => {
@@ -873,7 +1005,36 @@ fn execute_import(plan: ImportPlan, should_refresh_setup: bool) -> CliResult<Leg
             plan.target_db.display()
         )));
     }
+    let schema_mapping = normalize_imported_db(&plan.target_db)?;
+    for value in &schema_mapping.unmapped {
+        warnings.push(format!(
+            "{} row(s) in {}.{} had unmapped value {:?}; imported as {:?}",
+            value.rows,
+            value.table,
+            value.column,
+            value.from.as_deref().unwrap_or("NULL"),
+            value.to
+        ));
+    }
     let core_counts = query_core_table_counts(&plan.target_db)?;
+    warnings.extend(natural_key_collision_warning(&plan.target_db)?);
+    let verification = match legacy_schema::verify_import(&verification_source, &plan.target_db) {
+        Ok(mut report) => {
+            if !report.ok {
+                warnings.push(
+                    "post-import verification found mismatches; run `am upgrade --verify-only` \
+                     for details"
+                        .to_string(),
+                );
+            }
+            report.unmapped.clone_from(&schema_mapping.unmapped);
+            Some(report)
+        }
+        Err(err) => {
+            warnings.push(format!("post-import verification failed to run: {err}"));
+            None
+        }
+    };
 
     let setup_ok = if should_refresh_setup {
         match run_setup_refresh_once(Some(plan.search_root.clone())) {
@@ -902,6 +1063,9 @@ fn execute_import(plan: ImportPlan, should_refresh_setup: bool) -> CliResult<Leg
         core_table_counts: core_counts,
         setup_refresh_ok: setup_ok,
         warnings,
+        verification_source_db: Some(verification_source.display().to_string()),
+        schema_mapping: Some(schema_mapping),
+        verification,
     };
     write_receipt(&plan.target_storage_root, &receipt, &timestamp)?;
     Ok(receipt)
@@ -923,6 +1087,11 @@ fn run_setup_refresh_once(project_dir: Option<PathBuf>) -> CliResult<()> {
         json: false,
         no_user_config: false,
         no_hooks: false,
+        prune: false,
+        project_token: false,
+        uds: config.http_uds_path,
+        write_instructions: false,
+        remove_instructions: false,
     })
 }
 
@@ -955,6 +1124,12 @@ fn migrate_sqlite_db(path: &Path) -> CliResult<Vec<String>> {
     }
 }
 
+fn normalize_imported_db(path: &Path) -> CliResult<SchemaMappingReport> {
+    let conn = DbConn::open_file(path.display().to_string())
+        .map_err(|e| CliError::Other(format!("cannot open sqlite DB {}: {e}", path.display())))?;
+    legacy_schema::normalize_python_schema(&conn)
+}
+
 fn integrity_check_ok(path: &Path) -> CliResult<bool> {
     let conn = DbConn::open_file(path.display().to_string())
         .map_err(|e| CliError::Other(format!("cannot open sqlite DB {}: {e}", path.display())))?;
@@ -1012,14 +1187,7 @@ fn query_core_table_counts(path: &Path) -> CliResult<BTreeMap<String, i64>> {
     let conn = DbConn::open_file(path.display().to_string())
         .map_err(|e| CliError::Other(format!("cannot open sqlite DB {}: {e}", path.display())))?;
     let mut out = BTreeMap::new();
-    for table in [
-        "projects",
-        "agents",
-        "messages",
-        "message_recipients",
-        "file_reservations",
-        "agent_links",
-    ] {
+    for table in legacy_schema::CORE_TABLES {
         let sql = format!("SELECT COUNT(*) AS c FROM {table}");
         let rows = conn
             .query_sync(&sql, &[])
@@ -1033,6 +1201,26 @@ fn query_core_table_counts(path: &Path) -> CliResult<BTreeMap<String, i64>> {
     Ok(out)
 }
 
+/// Messages sharing a natural key import fine, but `am archive restore
+/// --merge` matches by natural key and would treat them as one row.
+fn natural_key_collision_warning(path: &Path) -> CliResult<Option<String>> {
+    let conn = DbConn::open_file(path.display().to_string())
+        .map_err(|e| CliError::Other(format!("cannot open sqlite DB {}: {e}", path.display())))?;
+    let duplicates = natural_keys::duplicate_message_keys(&conn)?;
+    let Some((first, count)) = duplicates.first() else {
+        return Ok(None);
+    };
+    Ok(Some(format!(
+        "{} message natural key(s) are shared by more than one message \
+         (e.g. {count} messages from {} in {} at created_ts={}); \
+         archive merge restores will treat each group as a single message",
+        duplicates.len(),
+        first.sender,
+        first.project_slug,
+        first.created_ts
+    )))
+}
+
 fn write_receipt(
     target_storage_root: &Path,
     receipt: &LegacyImportReceipt,
@@ -1913,6 +2101,9 @@ mod tests {
             core_table_counts: counts,
             setup_refresh_ok: true,
             warnings: vec![],
+            verification_source_db: None,
+            schema_mapping: None,
+            verification: None,
         }
     }
 
@@ -2393,6 +2584,25 @@ mod tests {
         assert_eq!(parsed.source_db, "/tmp/storage.sqlite3");
     }
 
+    #[test]
+    fn receipt_verification_source_falls_back_for_older_receipts() {
+        let mut receipt = sample_receipt("2026-02-17T00:00:00Z", "/tmp/storage.sqlite3");
+        assert_eq!(
+            receipt_verification_source(&receipt),
+            Some(PathBuf::from("/tmp/backup/db/storage.sqlite3"))
+        );
+        receipt.mode = ImportMode::Copy;
+        assert_eq!(
+            receipt_verification_source(&receipt),
+            Some(PathBuf::from("/tmp/storage.sqlite3"))
+        );
+        receipt.verification_source_db = Some("/tmp/snapshot.sqlite3".to_string());
+        assert_eq!(
+            receipt_verification_source(&receipt),
+            Some(PathBuf::from("/tmp/snapshot.sqlite3"))
+        );
+    }
+
     #[test]
     fn write_receipt_avoids_timestamp_collision_overwrite() {
         let tmp = tempfile::tempdir().unwrap();
@@ -0,0 +1,679 @@
+//! Python-era schema normalization and post-import verification for
+//! `am legacy import` / `am upgrade`.
+//!
+//! `schema::migrate_to_latest` already rewrites the SQLAlchemy TEXT
+//! timestamps. What it leaves alone are values older Python builds wrote that
+//! still fit the Rust column types but mean something else. The importer
+//! rewrites them after migration:
+//!
+//! | Column                  | Python-era value                  | Imported as                          |
+//! |-------------------------|-----------------------------------|--------------------------------------|
+//! | `messages.importance`   | `0` / `1` / `2` / `3`             | `low` / `normal` / `high` / `urgent` |
+//! | `messages.importance`   | `High`, ` urgent `                | lowercased, trimmed                  |
+//! | `messages.importance`   | `NULL` / empty                    | `normal`                             |
+//! | `agents.contact_policy` | `Open`, ` auto `                  | lowercased, trimmed                  |
+//! | `agents.contact_policy` | `NULL` / empty                    | `auto`                               |
+//! | timestamp columns       | epoch seconds (INTEGER or REAL)   | epoch microseconds                   |
+//! | enum columns            | anything else                     | column default, reported as unmapped |
+//!
+//! Unmapped values never abort an import: the row keeps flowing with the
+//! column default and the original value is listed in the report.
+//!
+//! The verification report compares the pre-import database (the untouched
+//! source in copy mode, the safety backup in in-place mode) with the imported
+//! one: row counts per core table, min/max of each table's primary timestamp,
+//! and content checksums for a sample of messages.
+
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+
+use mcp_agent_mail_db::DbConn;
+use serde::{Deserialize, Serialize};
+use sqlmodel_core::Value;
+
+use crate::natural_keys::{content_fingerprint, table_columns};
+use crate::{CliError, CliResult};
+
+/// Tables compared row-for-row between source and destination.
+pub const CORE_TABLES: [&str; 6] = [
+    "projects",
+    "agents",
+    "messages",
+    "message_recipients",
+    "file_reservations",
+    "agent_links",
+];
+
+/// Integer timestamps below this are epoch seconds (this many seconds is
+/// year 5138; this many microseconds is 2 January 1970).
+pub const EPOCH_SECONDS_CEILING: i64 = 100_000_000_000;
+
+/// Messages checksummed by [`verify_import`].
+pub const MESSAGE_CHECKSUM_SAMPLE: usize = 50;
+
+/// An enum-like TEXT column and the Python-era spellings it accepts.
+struct EnumColumn {
+    table: &'static str,
+    column: &'static str,
+    allowed: &'static [&'static str],
+    /// Integer codes written by Python builds that stored enum ordinals.
+    codes: &'static [(&'static str, &'static str)],
+    default: &'static str,
+}
+
+const ENUM_COLUMNS: &[EnumColumn] = &[
+    EnumColumn {
+        table: "messages",
+        column: "importance",
+        allowed: &["low", "normal", "high", "urgent"],
+        codes: &[
+            ("0", "low"),
+            ("1", "normal"),
+            ("2", "high"),
+            ("3", "urgent"),
+        ],
+        default: "normal",
+    },
+    EnumColumn {
+        table: "agents",
+        column: "contact_policy",
+        allowed: &["open", "auto", "contacts_only", "block_all"],
+        codes: &[],
+        default: "auto",
+    },
+];
+
+const TIMESTAMP_COLUMNS: &[(&str, &str)] = &[
+    ("projects", "created_at"),
+    ("agents", "inception_ts"),
+    ("agents", "last_active_ts"),
+    ("messages", "created_ts"),
+    ("message_recipients", "read_ts"),
+    ("message_recipients", "ack_ts"),
+    ("file_reservations", "created_ts"),
+    ("file_reservations", "expires_ts"),
+    ("file_reservations", "released_ts"),
+    ("agent_links", "created_ts"),
+    ("agent_links", "updated_ts"),
+    ("agent_links", "expires_ts"),
+];
+
+/// Timestamp whose min/max is compared for each core table.
+fn primary_timestamp_column(table: &str) -> Option<&'static str> {
+    match table {
+        "projects" => Some("created_at"),
+        "agents" => Some("inception_ts"),
+        "messages" | "file_reservations" | "agent_links" => Some("created_ts"),
+        _ => None,
+    }
+}
+
+/// One rewrite applied (or one unmapped value defaulted) during import.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappedValue {
+    pub table: String,
+    pub column: String,
+    /// Original value; `None` for SQL `NULL`.
+    pub from: Option<String>,
+    pub to: String,
+    pub rows: i64,
+}
+
+/// What [`normalize_python_schema`] changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaMappingReport {
+    pub rewrites: Vec<MappedValue>,
+    /// Values outside the mapping table, imported as the column default.
+    pub unmapped: Vec<MappedValue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnumMapping {
+    Keep,
+    Rewrite(&'static str),
+    Unmapped(&'static str),
+}
+
+fn map_enum_value(spec: &EnumColumn, raw: Option<&str>) -> EnumMapping {
+    let Some(raw) = raw.filter(|value| !value.trim().is_empty()) else {
+        return EnumMapping::Rewrite(spec.default);
+    };
+    let normalized = raw.trim().to_ascii_lowercase();
+    if let Some(&allowed) = spec.allowed.iter().find(|&&value| value == normalized) {
+        return if raw == allowed {
+            EnumMapping::Keep
+        } else {
+            EnumMapping::Rewrite(allowed)
+        };
+    }
+    spec.codes
+        .iter()
+        .find(|(code, _)| *code == normalized)
+        .map_or(EnumMapping::Unmapped(spec.default), |&(_, value)| {
+            EnumMapping::Rewrite(value)
+        })
+}
+
+/// Rewrite known Python-era encodings in an already-migrated database.
+///
+/// Runs in one transaction; tables or columns the database lacks are skipped.
+pub fn normalize_python_schema(conn: &DbConn) -> CliResult<SchemaMappingReport> {
+    conn.execute_raw("BEGIN IMMEDIATE")
+        .map_err(|e| CliError::Other(format!("failed to start schema mapping: {e}")))?;
+    match normalize_python_schema_in_tx(conn) {
+        Ok(report) => {
+            conn.execute_raw("COMMIT")
+                .map_err(|e| CliError::Other(format!("failed to commit schema mapping: {e}")))?;
+            Ok(report)
+        }
+        Err(err) => {
+            let _ = conn.execute_raw("ROLLBACK");
+            Err(err)
+        }
+    }
+}
+
+fn normalize_python_schema_in_tx(conn: &DbConn) -> CliResult<SchemaMappingReport> {
+    let mut report = SchemaMappingReport::default();
+    for spec in ENUM_COLUMNS {
+        if !table_columns(conn, spec.table)?
+            .iter()
+            .any(|c| c == spec.column)
+        {
+            continue;
+        }
+        let (table, column) = (spec.table, spec.column);
+        let groups = conn
+            .query_sync(
+                &format!(
+                    "SELECT CAST({column} AS TEXT) AS value, COUNT(*) AS n \
+                     FROM {table} GROUP BY value"
+                ),
+                &[],
+            )
+            .map_err(|e| CliError::Other(format!("{table}.{column} scan failed: {e}")))?;
+        for group in &groups {
+            let raw = group.get_named::<String>("value").ok();
+            let rows: i64 = group.get_named("n").unwrap_or(0);
+            let (to, unmapped) = match map_enum_value(spec, raw.as_deref()) {
+                EnumMapping::Keep => continue,
+                EnumMapping::Rewrite(to) => (to, false),
+                EnumMapping::Unmapped(to) => (to, true),
+            };
+            let (filter, params) = raw.as_ref().map_or_else(
+                || {
+                    (
+                        format!("{column} IS NULL"),
+                        vec![Value::Text(to.to_string())],
+                    )
+                },
+                |value| {
+                    (
+                        format!("CAST({column} AS TEXT) = ?"),
+                        vec![Value::Text(to.to_string()), Value::Text(value.clone())],
+                    )
+                },
+            );
+            conn.execute_sync(
+                &format!("UPDATE {table} SET {column} = ? WHERE {filter}"),
+                &params,
+            )
+            .map_err(|e| CliError::Other(format!("{table}.{column} rewrite failed: {e}")))?;
+            let mapped = MappedValue {
+                table: table.to_string(),
+                column: column.to_string(),
+                from: raw,
+                to: to.to_string(),
+                rows,
+            };
+            if unmapped {
+                report.unmapped.push(mapped);
+            } else {
+                report.rewrites.push(mapped);
+            }
+        }
+    }
+
+    for &(table, column) in TIMESTAMP_COLUMNS {
+        if !table_columns(conn, table)?.iter().any(|c| c == column) {
+            continue;
+        }
+        for (kind, rewrite) in [
+            ("integer", format!("{column} * 1000000")),
+            ("real", format!("CAST({column} * 1000000 AS INTEGER)")),
+        ] {
+            let filter = format!(
+                "typeof({column}) = '{kind}' AND {column} > 0 AND {column} < {EPOCH_SECONDS_CEILING}"
+            );
+            let rows = count_rows(conn, table, &filter)?;
+            if rows == 0 {
+                continue;
+            }
+            conn.execute_raw(&format!(
+                "UPDATE {table} SET {column} = {rewrite} WHERE {filter}"
+            ))
+            .map_err(|e| CliError::Other(format!("{table}.{column} rewrite failed: {e}")))?;
+            report.rewrites.push(MappedValue {
+                table: table.to_string(),
+                column: column.to_string(),
+                from: Some(format!("epoch seconds ({kind})")),
+                to: "epoch microseconds".to_string(),
+                rows,
+            });
+        }
+    }
+    Ok(report)
+}
+
+fn count_rows(conn: &DbConn, table: &str, filter: &str) -> CliResult<i64> {
+    let rows = conn
+        .query_sync(
+            &format!("SELECT COUNT(*) AS n FROM {table} WHERE {filter}"),
+            &[],
+        )
+        .map_err(|e| CliError::Other(format!("count query failed for {table}: {e}")))?;
+    Ok(rows
+        .first()
+        .and_then(|row| row.get_named::<i64>("n").ok())
+        .unwrap_or(0))
+}
+
+/// Interpret a timestamp cell from either schema as epoch microseconds.
+///
+/// Accepts epoch seconds or microseconds (INTEGER, REAL, or numeric TEXT) and
+/// the ISO-8601 strings SQLAlchemy wrote.
+#[must_use]
+#[allow(
+    clippy::cast_possible_truncation,
+    reason = "REAL timestamps are range-checked against EPOCH_SECONDS_CEILING first"
+)]
+pub fn legacy_value_to_micros(value: &Value) -> Option<i64> {
+    let from_integer = |n: i64| {
+        if n > 0 && n < EPOCH_SECONDS_CEILING {
+            n.checked_mul(1_000_000)
+        } else {
+            Some(n)
+        }
+    };
+    match value {
+        Value::TinyInt(n) => from_integer(i64::from(*n)),
+        Value::SmallInt(n) => from_integer(i64::from(*n)),
+        Value::Int(n) => from_integer(i64::from(*n)),
+        Value::BigInt(n) => from_integer(*n),
+        Value::Double(f) if f.is_finite() && f.abs() < 9.0e18 => {
+            if *f > 0.0 && *f < 1.0e11 {
+                Some((f * 1_000_000.0) as i64)
+            } else {
+                Some(*f as i64)
+            }
+        }
+        Value::Text(text) => {
+            let text = text.trim();
+            if let Ok(n) = text.parse::<i64>() {
+                return from_integer(n);
+            }
+            if let Ok(f) = text.parse::<f64>() {
+                return legacy_value_to_micros(&Value::Double(f));
+            }
+            mcp_agent_mail_core::timestamps::iso_to_micros(&text.replacen(' ', "T", 1))
+        }
+        _ => None,
+    }
+}
+
+/// Row counts and timestamp range for one table on both sides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableVerification {
+    pub table: String,
+    /// `None` when the table does not exist on that side.
+    pub source_rows: Option<i64>,
+    pub target_rows: Option<i64>,
+    pub rows_match: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_column: Option<String>,
+    pub source_min_ts: Option<i64>,
+    pub source_max_ts: Option<i64>,
+    pub target_min_ts: Option<i64>,
+    pub target_max_ts: Option<i64>,
+    pub timestamps_match: bool,
+}
+
+/// Checksum of one sampled message on both sides.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageChecksum {
+    pub id: i64,
+    pub source: String,
+    /// `None` when the message is missing from the destination.
+    pub target: Option<String>,
+}
+
+impl MessageChecksum {
+    #[must_use]
+    pub fn matches(&self) -> bool {
+        self.target.as_deref() == Some(self.source.as_str())
+    }
+}
+
+/// Source-vs-destination comparison produced after an import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    pub source_db: String,
+    pub target_db: String,
+    pub ok: bool,
+    pub tables: Vec<TableVerification>,
+    pub message_checksums: Vec<MessageChecksum>,
+    pub checksum_mismatches: usize,
+    /// Values imported with a best-effort default (from the import receipt).
+    #[serde(default)]
+    pub unmapped: Vec<MappedValue>,
+}
+
+/// Compare the pre-import database at `source` with the imported `target`.
+///
+/// Both sides are read through the same mapping table, so an importance of
+/// `2` in the source and `high` in the destination checksum identically.
+pub fn verify_import(source: &Path, target: &Path) -> CliResult<VerificationReport> {
+    let open = |path: &Path| {
+        DbConn::open_file(path.display().to_string())
+            .map_err(|e| CliError::Other(format!("cannot open sqlite DB {}: {e}", path.display())))
+    };
+    let source_conn = open(source)?;
+    let target_conn = open(target)?;
+
+    let mut tables = Vec::with_capacity(CORE_TABLES.len());
+    for table in CORE_TABLES {
+        let source_columns = table_columns(&source_conn, table)?;
+        let target_columns = table_columns(&target_conn, table)?;
+        let source_rows = if source_columns.is_empty() {
+            None
+        } else {
+            Some(count_rows(&source_conn, table, "1 = 1")?)
+        };
+        let target_rows = if target_columns.is_empty() {
+            None
+        } else {
+            Some(count_rows(&target_conn, table, "1 = 1")?)
+        };
+        let timestamp_column = primary_timestamp_column(table);
+        let range = |conn: &DbConn, columns: &[String]| match timestamp_column {
+            Some(column) if columns.iter().any(|c| c == column) => {
+                timestamp_range(conn, table, column)
+            }
+            _ => Ok((None, None)),
+        };
+        let (source_min_ts, source_max_ts) = range(&source_conn, &source_columns)?;
+        let (target_min_ts, target_max_ts) = range(&target_conn, &target_columns)?;
+        tables.push(TableVerification {
+            table: table.to_string(),
+            source_rows,
+            target_rows,
+            rows_match: source_rows.unwrap_or(0) == target_rows.unwrap_or(0),
+            timestamp_column: timestamp_column.map(str::to_string),
+            source_min_ts,
+            source_max_ts,
+            target_min_ts,
+            target_max_ts,
+            timestamps_match: source_min_ts == target_min_ts && source_max_ts == target_max_ts,
+        });
+    }
+
+    let message_checksums = sample_message_checksums(&source_conn, &target_conn)?;
+    let checksum_mismatches = message_checksums
+        .iter()
+        .filter(|sample| !sample.matches())
+        .count();
+    let ok = checksum_mismatches == 0
+        && tables
+            .iter()
+            .all(|table| table.rows_match && table.timestamps_match);
+    Ok(VerificationReport {
+        source_db: source.display().to_string(),
+        target_db: target.display().to_string(),
+        ok,
+        tables,
+        message_checksums,
+        checksum_mismatches,
+        unmapped: Vec::new(),
+    })
+}
+
+fn timestamp_range(
+    conn: &DbConn,
+    table: &str,
+    column: &str,
+) -> CliResult<(Option<i64>, Option<i64>)> {
+    let rows = conn
+        .query_sync(&format!("SELECT {column} AS ts FROM {table}"), &[])
+        .map_err(|e| CliError::Other(format!("{table}.{column} scan failed: {e}")))?;
+    let mut min = None;
+    let mut max = None;
+    for micros in rows
+        .iter()
+        .filter_map(|row| row.get(0).and_then(legacy_value_to_micros))
+    {
+        min = Some(min.map_or(micros, |current: i64| current.min(micros)));
+        max = Some(max.map_or(micros, |current: i64| current.max(micros)));
+    }
+    Ok((min, max))
+}
+
+fn sample_message_checksums(source: &DbConn, target: &DbConn) -> CliResult<Vec<MessageChecksum>> {
+    if table_columns(source, "messages")?.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids: Vec<i64> = source
+        .query_sync("SELECT id FROM messages ORDER BY id", &[])
+        .map_err(|e| CliError::Other(format!("message id scan failed: {e}")))?
+        .iter()
+        .filter_map(|row| row.get_named::<i64>("id").ok())
+        .collect();
+    let stride = ids.len().div_ceil(MESSAGE_CHECKSUM_SAMPLE).max(1);
+    let target_has_messages = !table_columns(target, "messages")?.is_empty();
+    let mut out = Vec::new();
+    for &id in ids.iter().step_by(stride) {
+        let Some(source_sum) = message_checksum(source, id)? else {
+            continue;
+        };
+        let target_sum = if target_has_messages {
+            message_checksum(target, id)?
+        } else {
+            None
+        };
+        out.push(MessageChecksum {
+            id,
+            source: source_sum,
+            target: target_sum,
+        });
+    }
+    Ok(out)
+}
+
+/// Checksum over subject, body, thread id, and mapped importance.
+fn message_checksum(conn: &DbConn, id: i64) -> CliResult<Option<String>> {
+    let rows = conn
+        .query_sync(
+            "SELECT subject, body_md, thread_id, CAST(importance AS TEXT) AS importance \
+             FROM messages WHERE id = ?",
+            &[Value::BigInt(id)],
+        )
+        .map_err(|e| CliError::Other(format!("message {id} checksum query failed: {e}")))?;
+    let Some(row) = rows.first() else {
+        return Ok(None);
+    };
+    let subject: String = row.get_named("subject").unwrap_or_default();
+    let body: String = row.get_named("body_md").unwrap_or_default();
+    let thread_id: String = row.get_named("thread_id").unwrap_or_default();
+    let raw_importance = row.get_named::<String>("importance").ok();
+    let importance = match map_enum_value(&ENUM_COLUMNS[0], raw_importance.as_deref()) {
+        EnumMapping::Keep => raw_importance.unwrap_or_default(),
+        EnumMapping::Rewrite(value) | EnumMapping::Unmapped(value) => value.to_string(),
+    };
+    Ok(Some(format!(
+        "{:016x}",
+        content_fingerprint(&[subject.trim(), &body, &thread_id, &importance])
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn python_era_conn(path: &Path) -> DbConn {
+        let conn = DbConn::open_file(path.display().to_string()).expect("open db");
+        for sql in [
+            "CREATE TABLE projects (id INTEGER PRIMARY KEY, slug TEXT, human_key TEXT, \
+                created_at INTEGER)",
+            "CREATE TABLE agents (id INTEGER PRIMARY KEY, project_id INTEGER, name TEXT, \
+                inception_ts INTEGER, last_active_ts INTEGER, contact_policy TEXT)",
+            "CREATE TABLE messages (id INTEGER PRIMARY KEY, project_id INTEGER, \
+                sender_id INTEGER, thread_id TEXT, subject TEXT, body_md TEXT, \
+                importance TEXT, created_ts INTEGER)",
+            "INSERT INTO projects VALUES (1, 'proj', '/tmp/proj', 1700000000)",
+            "INSERT INTO agents VALUES (1, 1, 'BlueLake', 1700000000, 1700000100, NULL)",
+            "INSERT INTO agents VALUES (2, 1, 'RedFox', 1700000000, 1700000100, 'Open')",
+            "INSERT INTO agents VALUES (3, 1, 'GreenOwl', 1700000000, 1700000100, 'friends')",
+            "INSERT INTO messages VALUES (1, 1, 1, NULL, 'a', 'x', 2, 1700000200)",
+            "INSERT INTO messages VALUES (2, 1, 1, NULL, 'b', 'y', 'High', 1700000300)",
+            "INSERT INTO messages VALUES (3, 1, 2, 't', 'c', 'z', 'critical', 1700000400)",
+            "INSERT INTO messages VALUES (4, 1, 2, 't', 'd', 'w', 'normal', 1700000500)",
+        ] {
+            conn.execute_raw(sql).expect("seed python-era schema");
+        }
+        conn
+    }
+
+    fn text_column(conn: &DbConn, sql: &str) -> Vec<String> {
+        conn.query_sync(sql, &[])
+            .expect("query")
+            .iter()
+            .map(|row| row.get_named::<String>("v").unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn map_enum_value_follows_the_mapping_table() {
+        let importance = &ENUM_COLUMNS[0];
+        assert_eq!(map_enum_value(importance, Some("high")), EnumMapping::Keep);
+        assert_eq!(
+            map_enum_value(importance, Some(" Urgent ")),
+            EnumMapping::Rewrite("urgent")
+        );
+        assert_eq!(
+            map_enum_value(importance, Some("0")),
+            EnumMapping::Rewrite("low")
+        );
+        assert_eq!(
+            map_enum_value(importance, None),
+            EnumMapping::Rewrite("normal")
+        );
+        assert_eq!(
+            map_enum_value(importance, Some("9")),
+            EnumMapping::Unmapped("normal")
+        );
+    }
+
+    #[test]
+    fn legacy_value_to_micros_accepts_seconds_micros_and_iso() {
+        assert_eq!(
+            legacy_value_to_micros(&Value::BigInt(1_700_000_000)),
+            Some(1_700_000_000_000_000)
+        );
+        assert_eq!(
+            legacy_value_to_micros(&Value::BigInt(1_700_000_000_000_000)),
+            Some(1_700_000_000_000_000)
+        );
+        assert_eq!(
+            legacy_value_to_micros(&Value::Double(1_700_000_000.5)),
+            Some(1_700_000_000_500_000)
+        );
+        assert_eq!(
+            legacy_value_to_micros(&Value::Text("2023-11-14 22:13:20.000001".to_string())),
+            Some(1_700_000_000_000_001)
+        );
+        assert_eq!(
+            legacy_value_to_micros(&Value::Text("soon".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn normalize_python_schema_rewrites_known_values_and_reports_unmapped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let conn = python_era_conn(&tmp.path().join("db.sqlite3"));
+
+        let report = normalize_python_schema(&conn).expect("normalize");
+
+        assert_eq!(
+            text_column(&conn, "SELECT importance AS v FROM messages ORDER BY id"),
+            ["high", "high", "normal", "normal"]
+        );
+        assert_eq!(
+            text_column(&conn, "SELECT contact_policy AS v FROM agents ORDER BY id"),
+            ["auto", "open", "auto"]
+        );
+        let created: Vec<i64> = conn
+            .query_sync("SELECT created_ts FROM messages ORDER BY id", &[])
+            .unwrap()
+            .iter()
+            .map(|row| row.get_named("created_ts").unwrap())
+            .collect();
+        assert_eq!(created[0], 1_700_000_200_000_000);
+
+        let unmapped: Vec<_> = report
+            .unmapped
+            .iter()
+            .map(|v| (v.column.as_str(), v.from.as_deref(), v.to.as_str()))
+            .collect();
+        assert!(unmapped.contains(&("importance", Some("critical"), "normal")));
+        assert!(unmapped.contains(&("contact_policy", Some("friends"), "auto")));
+        assert!(
+            report
+                .rewrites
+                .iter()
+                .any(|v| v.column == "created_ts" && v.rows == 4)
+        );
+
+        // Idempotent: a second pass finds nothing left to rewrite.
+        let again = normalize_python_schema(&conn).expect("normalize again");
+        assert!(again.rewrites.is_empty() && again.unmapped.is_empty());
+    }
+
+    #[test]
+    fn verify_import_matches_normalized_copy_and_flags_drift() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = tmp.path().join("source.sqlite3");
+        let target = tmp.path().join("target.sqlite3");
+        drop(python_era_conn(&source));
+        std::fs::copy(&source, &target).unwrap();
+        let target_conn = DbConn::open_file(target.display().to_string()).unwrap();
+        normalize_python_schema(&target_conn).expect("normalize target");
+
+        let report = verify_import(&source, &target).expect("verify");
+        assert!(report.ok, "{report:#?}");
+        let messages = report
+            .tables
+            .iter()
+            .find(|t| t.table == "messages")
+            .unwrap();
+        assert_eq!(messages.source_rows, Some(4));
+        assert_eq!(messages.target_min_ts, Some(1_700_000_200_000_000));
+        assert_eq!(report.message_checksums.len(), 4);
+
+        target_conn
+            .execute_raw("UPDATE messages SET body_md = 'tampered' WHERE id = 3")
+            .unwrap();
+        target_conn
+            .execute_raw("DELETE FROM agents WHERE id = 3")
+            .unwrap();
+        let report = verify_import(&source, &target).expect("verify drift");
+        assert!(!report.ok);
+        assert_eq!(report.checksum_mismatches, 1);
+        assert!(
+            report
+                .tables
+                .iter()
+                .any(|t| t.table == "agents" && !t.rows_match)
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Attachment preparation for `am mail send --attach`.
+//!
+//! Every file is read and checked before anything is sent, so a refused
+//! attachment (sender policy `none`, over the size cap, unreadable) never
+//! leaves a half-delivered message behind. Placement follows the sender's
+//! `attachments_policy`: `inline` embeds the payload as base64 in the message
+//! record, `file` stores a content-addressed blob under the project archive,
+//! and `auto` inlines anything up to [`mcp_agent_mail_share::INLINE_ATTACHMENT_THRESHOLD`].
+
+use std::collections::BTreeSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest as _, Sha256};
+
+use crate::CliError;
+
+/// Magic-byte prefixes for the binary formats agents commonly attach.
+const BINARY_SIGNATURES: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"BZh", "application/x-bzip2"),
+    (b"\xfd7zXZ\x00", "application/x-xz"),
+    (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    (b"SQLite format 3\x00", "application/vnd.sqlite3"),
+    (b"\x7fELF", "application/x-elf"),
+    (b"\x00asm", "application/wasm"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentPlacement {
+    Inline,
+    File,
+}
+
+impl AttachmentPlacement {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::File => "file",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PreparedAttachment {
+    /// File name as given on the command line, without directories.
+    pub name: String,
+    pub source: PathBuf,
+    pub media_type: &'static str,
+    pub sha256: String,
+    pub content: Vec<u8>,
+    pub placement: AttachmentPlacement,
+}
+
+impl PreparedAttachment {
+    /// Metadata recorded in the message's `attachments` column.
+    /// `archive_path` is the archive-relative copy for `file` placements.
+    #[must_use]
+    pub fn metadata(&self, archive_path: Option<&str>) -> serde_json::Value {
+        use base64::Engine as _;
+
+        let mut value = serde_json::json!({
+            "type": self.placement.as_str(),
+            "name": self.name,
+            "media_type": self.media_type,
+            "bytes": self.content.len(),
+            "sha256": self.sha256,
+        });
+        let object = value.as_object_mut().expect("json object");
+        match self.placement {
+            AttachmentPlacement::Inline => {
+                object.insert(
+                    "data_base64".to_string(),
+                    serde_json::Value::String(
+                        base64::engine::general_purpose::STANDARD.encode(&self.content),
+                    ),
+                );
+            }
+            AttachmentPlacement::File => {
+                if let Some(path) = archive_path {
+                    object.insert(
+                        "path".to_string(),
+                        serde_json::Value::String(path.to_string()),
+                    );
+                }
+            }
+        }
+        value
+    }
+}
+
+/// Read and validate `paths` for `sender`, whose registered
+/// `attachments_policy` is `policy`.
+///
+/// Paths with identical content are attached once (first occurrence wins).
+/// `max_bytes == 0` disables the size cap.
+pub fn prepare_attachments(
+    paths: &[PathBuf],
+    sender: &str,
+    policy: &str,
+    max_bytes: usize,
+) -> Result<Vec<PreparedAttachment>, CliError> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let policy = policy.trim().to_ascii_lowercase();
+    if policy == "none" {
+        return Err(CliError::InvalidArgument(format!(
+            "{sender} is registered with attachments_policy 'none', so --attach is refused; \
+             re-register with --attachments-policy auto|inline|file to send files"
+        )));
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut prepared = Vec::with_capacity(paths.len());
+    for path in paths {
+        let metadata = fs::metadata(path).map_err(|e| {
+            CliError::InvalidArgument(format!("cannot read attachment {}: {e}", path.display()))
+        })?;
+        if !metadata.is_file() {
+            return Err(CliError::InvalidArgument(format!(
+                "attachment {} is not a regular file",
+                path.display()
+            )));
+        }
+        // Check before reading so an oversized file is never pulled into memory.
+        check_attachment_size(path, metadata.len(), max_bytes)?;
+        let content = fs::read(path).map_err(|e| {
+            CliError::InvalidArgument(format!("cannot read attachment {}: {e}", path.display()))
+        })?;
+        check_attachment_size(path, content.len() as u64, max_bytes)?;
+        if content.is_empty() {
+            return Err(CliError::InvalidArgument(format!(
+                "attachment {} is empty",
+                path.display()
+            )));
+        }
+
+        let sha256 = hex::encode(Sha256::digest(&content));
+        if !seen.insert(sha256.clone()) {
+            continue;
+        }
+        let placement = match policy.as_str() {
+            "inline" => AttachmentPlacement::Inline,
+            "file" => AttachmentPlacement::File,
+            _ if content.len() <= mcp_agent_mail_share::INLINE_ATTACHMENT_THRESHOLD => {
+                AttachmentPlacement::Inline
+            }
+            _ => AttachmentPlacement::File,
+        };
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        prepared.push(PreparedAttachment {
+            name,
+            source: path.clone(),
+            media_type: detect_media_type(path, &content),
+            sha256,
+            content,
+            placement,
+        });
+    }
+    Ok(prepared)
+}
+
+fn check_attachment_size(path: &Path, len: u64, max_bytes: usize) -> Result<(), CliError> {
+    if max_bytes > 0 && len > max_bytes as u64 {
+        return Err(CliError::InvalidArgument(format!(
+            "attachment {} is {len} bytes, over the {max_bytes}-byte cap \
+             (raise MAX_ATTACHMENT_BYTES to allow it)",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// MIME type for an attachment: magic bytes identify binary formats, text is
+/// typed by its extension, and anything else is `application/octet-stream`.
+#[must_use]
+pub fn detect_media_type(path: &Path, content: &[u8]) -> &'static str {
+    if let Some((_, media_type)) = BINARY_SIGNATURES
+        .iter()
+        .find(|(magic, _)| content.starts_with(magic))
+    {
+        return media_type;
+    }
+    if content.len() >= 12 && content.starts_with(b"RIFF") && &content[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+    if content.contains(&0) || std::str::from_utf8(content).is_err() {
+        return "application/octet-stream";
+    }
+    let extension = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("md" | "markdown") => "text/markdown",
+        Some("json") => "application/json",
+        Some("toml") => "application/toml",
+        Some("yaml" | "yml") => "application/yaml",
+        Some("csv") => "text/csv",
+        Some("html" | "htm") => "text/html",
+        Some("xml") => "application/xml",
+        Some("svg") => "image/svg+xml",
+        Some("js" | "mjs") => "text/javascript",
+        Some("css") => "text/css",
+        _ => "text/plain",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &[u8]) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, content).expect("write attachment");
+        path
+    }
+
+    #[test]
+    fn none_policy_refuses_attachments() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = write(dir.path(), "notes.txt", b"hello");
+        let err = prepare_attachments(&[path], "BlueLake", "none", 1024)
+            .expect_err("policy none must refuse");
+        assert!(
+            matches!(&err, CliError::InvalidArgument(msg) if msg.contains("attachments_policy 'none'")),
+            "unexpected error: {err:?}"
+        );
+        // No files means nothing to refuse.
+        assert!(
+            prepare_attachments(&[], "BlueLake", "none", 1024)
+                .expect("empty")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn placement_follows_policy_and_inline_threshold() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let small = write(dir.path(), "small.md", b"# hi\n");
+        let big = write(
+            dir.path(),
+            "big.log",
+            &vec![b'x'; mcp_agent_mail_share::INLINE_ATTACHMENT_THRESHOLD + 1],
+        );
+        let paths = [small, big];
+
+        let auto = prepare_attachments(&paths, "BlueLake", "auto", 0).expect("auto");
+        assert_eq!(auto[0].placement, AttachmentPlacement::Inline);
+        assert_eq!(auto[1].placement, AttachmentPlacement::File);
+
+        let inline = prepare_attachments(&paths, "BlueLake", "Inline", 0).expect("inline");
+        assert!(
+            inline
+                .iter()
+                .all(|a| a.placement == AttachmentPlacement::Inline)
+        );
+
+        let file = prepare_attachments(&paths, "BlueLake", "file", 0).expect("file");
+        assert!(
+            file.iter()
+                .all(|a| a.placement == AttachmentPlacement::File)
+        );
+    }
+
+    #[test]
+    fn duplicate_content_is_attached_once_and_cap_is_enforced() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let first = write(dir.path(), "a.txt", b"same bytes");
+        let copy = write(dir.path(), "b.txt", b"same bytes");
+        let prepared = prepare_attachments(
+            &[first.clone(), copy, first.clone()],
+            "BlueLake",
+            "auto",
+            1024,
+        )
+        .expect("dedupe");
+        assert_eq!(prepared.len(), 1);
+        assert_eq!(prepared[0].name, "a.txt");
+
+        let err = prepare_attachments(&[first], "BlueLake", "auto", 4).expect_err("over cap");
+        assert!(
+            matches!(&err, CliError::InvalidArgument(msg) if msg.contains("4-byte cap")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn media_type_detection_sniffs_binaries_and_keys_text_by_extension() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        assert_eq!(detect_media_type(Path::new("shot.bin"), png), "image/png");
+        assert_eq!(
+            detect_media_type(Path::new("report"), b"%PDF-1.7\n"),
+            "application/pdf"
+        );
+        assert_eq!(
+            detect_media_type(Path::new("blob.dat"), &[0, 159, 146, 150]),
+            "application/octet-stream"
+        );
+        assert_eq!(
+            detect_media_type(Path::new("plan.MD"), b"# Plan"),
+            "text/markdown"
+        );
+        assert_eq!(
+            detect_media_type(Path::new("notes"), b"plain words"),
+            "text/plain"
+        );
+    }
+
+    #[test]
+    fn metadata_embeds_inline_payload_and_references_file_copies() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = write(dir.path(), "notes.txt", b"hello");
+        let inline = prepare_attachments(std::slice::from_ref(&path), "BlueLake", "inline", 0)
+            .expect("inline");
+        let meta = inline[0].metadata(None);
+        assert_eq!(meta["type"], "inline");
+        assert_eq!(meta["data_base64"], "aGVsbG8=");
+        assert_eq!(meta["bytes"], 5);
+        assert!(meta.get("path").is_none());
+
+        let file = prepare_attachments(&[path], "BlueLake", "file", 0).expect("file");
+        let meta = file[0].metadata(Some("projects/demo/attachments/files/aa/x.txt"));
+        assert_eq!(meta["type"], "file");
+        assert_eq!(meta["path"], "projects/demo/attachments/files/aa/x.txt");
+        assert!(meta.get("data_base64").is_none());
+    }
+}
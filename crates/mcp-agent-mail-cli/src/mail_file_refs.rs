@@ -0,0 +1,386 @@
+//! File handoff references for `am mail send --ref-file` and
+//! `am mail verify-refs`.
+//!
+//! A reference does not carry the file. It records where the file sits in the
+//! project (relative to the project root) and what it looked like when the
+//! message was sent: size, mtime, and sha256. The recipient re-hashes the
+//! file in its own checkout later to learn whether the handoff still holds.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Read as _;
+use std::path::{Component, Path, PathBuf};
+
+use mcp_agent_mail_db::queries::MessageFileRef;
+use serde::Serialize;
+use sha2::{Digest as _, Sha256};
+
+use crate::CliError;
+
+/// How a referenced file compares with what the sender recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileRefStatus {
+    Match,
+    Modified,
+    Missing,
+}
+
+impl FileRefStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::Modified => "modified",
+            Self::Missing => "missing",
+        }
+    }
+}
+
+/// The file as it is on disk now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CurrentFile {
+    pub size_bytes: i64,
+    pub mtime: String,
+    pub sha256: String,
+}
+
+/// Outcome of checking one reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileRefCheck {
+    pub path: String,
+    pub status: FileRefStatus,
+    pub recorded: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<CurrentFile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Resolve and hash `paths`, which must be regular files under
+/// `project_root`.
+///
+/// A path given more than once is referenced once (first occurrence wins).
+/// `max_refs == 0` disables the per-message cap.
+pub fn prepare_file_refs(
+    project_root: &Path,
+    paths: &[PathBuf],
+    max_refs: usize,
+) -> Result<Vec<MessageFileRef>, CliError> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    let root = fs::canonicalize(project_root).map_err(|e| {
+        CliError::InvalidArgument(format!(
+            "--ref-file needs the project root {} on this machine: {e}",
+            project_root.display()
+        ))
+    })?;
+
+    let mut seen = BTreeSet::new();
+    let mut refs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let resolved = fs::canonicalize(path).map_err(|e| {
+            CliError::InvalidArgument(format!("cannot read ref file {}: {e}", path.display()))
+        })?;
+        let relative = resolved
+            .strip_prefix(&root)
+            .ok()
+            .and_then(project_relative_path)
+            .ok_or_else(|| {
+                CliError::InvalidArgument(format!(
+                    "ref file {} is outside the project root {}",
+                    path.display(),
+                    root.display()
+                ))
+            })?;
+        if !seen.insert(relative.clone()) {
+            continue;
+        }
+        let snapshot = snapshot_file(&resolved).map_err(|e| {
+            CliError::InvalidArgument(format!("cannot read ref file {}: {e}", path.display()))
+        })?;
+        refs.push(MessageFileRef {
+            path: relative,
+            size_bytes: snapshot.size_bytes,
+            mtime_ts: snapshot.mtime_ts,
+            sha256: snapshot.sha256,
+        });
+    }
+    if max_refs > 0 && refs.len() > max_refs {
+        return Err(CliError::InvalidArgument(format!(
+            "{} ref files given; a message may reference at most {max_refs} \
+             (raise MAX_MESSAGE_FILE_REFS to allow more)",
+            refs.len()
+        )));
+    }
+    Ok(refs)
+}
+
+/// Compare the file at `file_ref.path` under `project_root` with the
+/// recorded size and hash. The mtime is informational only: a file touched
+/// without changing its bytes still matches.
+#[must_use]
+pub fn verify_file_ref(project_root: &Path, file_ref: &MessageFileRef) -> FileRefCheck {
+    let mut check = FileRefCheck {
+        path: file_ref.path.clone(),
+        status: FileRefStatus::Missing,
+        recorded: file_ref_json(file_ref),
+        current: None,
+        error: None,
+    };
+    let Some(relative) = recorded_relative_path(&file_ref.path) else {
+        check.error = Some("recorded path escapes the project root".to_string());
+        return check;
+    };
+    let path = project_root.join(relative);
+    match fs::metadata(&path) {
+        Ok(metadata) if metadata.is_file() => {}
+        Ok(_) => {
+            check.error = Some("no longer a regular file".to_string());
+            return check;
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return check,
+        Err(e) => {
+            check.error = Some(e.to_string());
+            return check;
+        }
+    }
+    match snapshot_file(&path) {
+        Ok(snapshot) => {
+            check.status = if snapshot.size_bytes == file_ref.size_bytes
+                && snapshot.sha256 == file_ref.sha256
+            {
+                FileRefStatus::Match
+            } else {
+                FileRefStatus::Modified
+            };
+            check.current = Some(CurrentFile {
+                size_bytes: snapshot.size_bytes,
+                mtime: mcp_agent_mail_db::micros_to_iso(snapshot.mtime_ts),
+                sha256: snapshot.sha256,
+            });
+        }
+        Err(e) => check.error = Some(e.to_string()),
+    }
+    check
+}
+
+/// JSON shape of a reference in message listings.
+#[must_use]
+pub fn file_ref_json(file_ref: &MessageFileRef) -> serde_json::Value {
+    serde_json::json!({
+        "path": file_ref.path,
+        "size_bytes": file_ref.size_bytes,
+        "mtime": mcp_agent_mail_db::micros_to_iso(file_ref.mtime_ts),
+        "sha256": file_ref.sha256,
+    })
+}
+
+/// One-line `path (N bytes, sha256 abcdef012345)` list for human output.
+#[must_use]
+pub fn file_refs_summary(refs: &[serde_json::Value]) -> String {
+    refs.iter()
+        .map(|file_ref| {
+            let path = file_ref
+                .get("path")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("?");
+            let size = file_ref
+                .get("size_bytes")
+                .and_then(serde_json::Value::as_i64)
+                .unwrap_or(0);
+            let sha256 = file_ref
+                .get("sha256")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            format!("{path} ({size} bytes, sha256 {})", short_sha256(sha256))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// First 12 hex digits of a sha256, enough to tell versions apart in tables.
+#[must_use]
+pub fn short_sha256(sha256: &str) -> &str {
+    sha256.get(..12).unwrap_or(sha256)
+}
+
+struct FileSnapshot {
+    size_bytes: i64,
+    mtime_ts: i64,
+    sha256: String,
+}
+
+fn snapshot_file(path: &Path) -> std::io::Result<FileSnapshot> {
+    let mut file = fs::File::open(path)?;
+    let metadata = file.metadata()?;
+    if !metadata.is_file() {
+        return Err(std::io::Error::other("not a regular file"));
+    }
+    let mtime_ts = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, crate::duration_arg::micros);
+    let mut hasher = Sha256::new();
+    let mut size: u64 = 0;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        size += n as u64;
+    }
+    Ok(FileSnapshot {
+        size_bytes: i64::try_from(size).unwrap_or(i64::MAX),
+        mtime_ts,
+        sha256: hex::encode(hasher.finalize()),
+    })
+}
+
+/// `/`-joined form of a path below the project root, or `None` for the root
+/// itself.
+fn project_relative_path(relative: &Path) -> Option<String> {
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Turn a recorded `/`-joined path back into a relative path, refusing
+/// anything that could reach outside the project root.
+fn recorded_relative_path(recorded: &str) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for part in recorded.split('/') {
+        if part.is_empty() || part == "." || part == ".." || part.contains('\\') {
+            return None;
+        }
+        path.push(part);
+    }
+    (!path.as_os_str().is_empty() && path.is_relative()).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, rel: &str, content: &[u8]) -> PathBuf {
+        let path = dir.join(rel);
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, content).expect("write ref file");
+        path
+    }
+
+    #[test]
+    fn refs_are_project_relative_deduplicated_and_capped() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let migration = write(dir.path(), "migrations/0042.sql", b"CREATE INDEX x;");
+        let plan = write(dir.path(), "PLAN.md", b"");
+
+        let refs = prepare_file_refs(
+            dir.path(),
+            &[migration.clone(), plan.clone(), migration.clone()],
+            0,
+        )
+        .expect("prepare");
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].path, "migrations/0042.sql");
+        assert_eq!(refs[0].size_bytes, 15);
+        assert_eq!(
+            refs[0].sha256,
+            hex::encode(Sha256::digest(b"CREATE INDEX x;"))
+        );
+        assert!(refs[0].mtime_ts > 0);
+        assert_eq!(refs[1].path, "PLAN.md");
+
+        let err = prepare_file_refs(dir.path(), &[migration, plan], 1).expect_err("over cap");
+        assert!(
+            matches!(&err, CliError::InvalidArgument(msg) if msg.contains("at most 1")),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn refs_outside_the_project_root_are_refused() {
+        let root = tempfile::tempdir().expect("root");
+        let elsewhere = tempfile::tempdir().expect("elsewhere");
+        let outside = write(elsewhere.path(), "notes.txt", b"hi");
+        let err = prepare_file_refs(root.path(), &[outside], 0).expect_err("outside root");
+        assert!(
+            matches!(&err, CliError::InvalidArgument(msg) if msg.contains("outside the project root")),
+            "unexpected error: {err:?}"
+        );
+        let err = prepare_file_refs(root.path(), &[root.path().join("absent.txt")], 0)
+            .expect_err("missing file");
+        assert!(matches!(err, CliError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn verify_reports_match_modified_and_missing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let same = write(dir.path(), "a/same.txt", b"stable");
+        let changed = write(dir.path(), "a/changed.txt", b"before");
+        let gone = write(dir.path(), "gone.txt", b"soon deleted");
+        let refs = prepare_file_refs(dir.path(), &[same, changed.clone(), gone.clone()], 0)
+            .expect("prepare");
+
+        fs::write(&changed, b"after!").expect("modify");
+        fs::remove_file(&gone).expect("delete");
+
+        let statuses: Vec<FileRefStatus> = refs
+            .iter()
+            .map(|file_ref| verify_file_ref(dir.path(), file_ref).status)
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                FileRefStatus::Match,
+                FileRefStatus::Modified,
+                FileRefStatus::Missing
+            ]
+        );
+        let modified = verify_file_ref(dir.path(), &refs[1]);
+        let current = modified.current.expect("current file");
+        assert_eq!(current.size_bytes, 6);
+        assert_ne!(current.sha256, refs[1].sha256);
+    }
+
+    #[test]
+    fn recorded_paths_cannot_escape_the_root() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for path in ["../secret", "/etc/passwd", "a/../../b", "", "a//b"] {
+            let check = verify_file_ref(
+                dir.path(),
+                &MessageFileRef {
+                    path: path.to_string(),
+                    size_bytes: 0,
+                    mtime_ts: 0,
+                    sha256: String::new(),
+                },
+            );
+            assert_eq!(check.status, FileRefStatus::Missing, "{path}");
+            assert!(check.error.is_some(), "{path}");
+        }
+        assert_eq!(
+            recorded_relative_path("src/lib.rs"),
+            Some(PathBuf::from("src").join("lib.rs"))
+        );
+    }
+
+    #[test]
+    fn summary_shortens_hashes() {
+        let summary = file_refs_summary(&[serde_json::json!({
+            "path": "PLAN.md",
+            "size_bytes": 12,
+            "sha256": "0123456789abcdef0123",
+        })]);
+        assert_eq!(summary, "PLAN.md (12 bytes, sha256 0123456789ab)");
+    }
+}
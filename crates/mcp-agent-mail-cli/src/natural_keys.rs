@@ -0,0 +1,515 @@
+//! Natural-key matchers for mailbox rows.
+//!
+//! Row ids are local to one SQLite file, so anything that reconciles two
+//! mailboxes (`am archive restore --merge`, the legacy importer's duplicate
+//! scan) has to match rows by what they *are* instead of where they landed:
+//!
+//! - project: `slug`
+//! - agent: project slug + agent name (case-insensitive, like the server)
+//! - message: project slug + sender name + `created_ts` + subject hash
+//! - file reservation: project slug + holder name + path pattern + `created_ts`
+//!
+//! Each keyed row also carries a content fingerprint so callers can tell an
+//! identical row from a genuine conflict (same key, different content).
+
+#![forbid(unsafe_code)]
+
+use std::collections::BTreeMap;
+
+use mcp_agent_mail_db::DbConn;
+use sqlmodel_core::Value;
+
+use crate::{CliError, CliResult};
+
+/// Field separator folded into fingerprints so `("ab", "c")` and
+/// `("a", "bc")` hash differently.
+const FINGERPRINT_SEPARATOR: u8 = 0x1f;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProjectKey {
+    pub slug: String,
+}
+
+impl ProjectKey {
+    #[must_use]
+    pub fn new(slug: &str) -> Self {
+        Self {
+            slug: slug.trim().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AgentKey {
+    pub project_slug: String,
+    /// Lowercased; agent names are unique per project case-insensitively.
+    pub name: String,
+}
+
+impl AgentKey {
+    #[must_use]
+    pub fn new(project_slug: &str, name: &str) -> Self {
+        Self {
+            project_slug: project_slug.trim().to_string(),
+            name: name.trim().to_ascii_lowercase(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MessageKey {
+    pub project_slug: String,
+    /// Lowercased sender name.
+    pub sender: String,
+    pub created_ts: i64,
+    pub subject_hash: u64,
+}
+
+impl MessageKey {
+    #[must_use]
+    pub fn new(project_slug: &str, sender: &str, created_ts: i64, subject: &str) -> Self {
+        Self {
+            project_slug: project_slug.trim().to_string(),
+            sender: sender.trim().to_ascii_lowercase(),
+            created_ts,
+            subject_hash: subject_hash(subject),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReservationKey {
+    pub project_slug: String,
+    /// Lowercased holder name.
+    pub holder: String,
+    pub path_pattern: String,
+    pub created_ts: i64,
+}
+
+impl ReservationKey {
+    #[must_use]
+    pub fn new(project_slug: &str, holder: &str, path_pattern: &str, created_ts: i64) -> Self {
+        Self {
+            project_slug: project_slug.trim().to_string(),
+            holder: holder.trim().to_ascii_lowercase(),
+            path_pattern: path_pattern.to_string(),
+            created_ts,
+        }
+    }
+}
+
+/// A row located by natural key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyedRow {
+    /// Row id in the database it was loaded from.
+    pub id: i64,
+    /// Hash of the row's mutable content (see the loaders for the fields).
+    pub fingerprint: u64,
+    /// Timestamp used to decide which side is newer (agents only; messages
+    /// are immutable and report their `created_ts`).
+    pub recency_ts: i64,
+}
+
+/// How an incoming row relates to the rows already present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMatch {
+    /// No row with this key exists.
+    Missing,
+    /// A row with this key and the same content exists.
+    Identical,
+    /// A row with this key exists but its content differs.
+    Differs,
+}
+
+/// Classify `incoming` against the rows in `existing`.
+#[must_use]
+pub fn classify<K: Ord>(
+    existing: &BTreeMap<K, KeyedRow>,
+    key: &K,
+    incoming: &KeyedRow,
+) -> KeyMatch {
+    match existing.get(key) {
+        None => KeyMatch::Missing,
+        Some(row) if row.fingerprint == incoming.fingerprint => KeyMatch::Identical,
+        Some(_) => KeyMatch::Differs,
+    }
+}
+
+/// Stable hash of a message subject (whitespace-trimmed).
+#[must_use]
+pub fn subject_hash(subject: &str) -> u64 {
+    fnv1a64(subject.trim().as_bytes(), FNV_OFFSET)
+}
+
+/// Stable hash over an ordered list of fields.
+#[must_use]
+pub fn content_fingerprint(fields: &[&str]) -> u64 {
+    fields.iter().fold(FNV_OFFSET, |hash, field| {
+        let hash = fnv1a64(field.as_bytes(), hash);
+        fnv1a64(&[FINGERPRINT_SEPARATOR], hash)
+    })
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+fn fnv1a64(bytes: &[u8], seed: u64) -> u64 {
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = seed;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Load projects keyed by slug. Fingerprint: `human_key`.
+pub fn load_project_keys(conn: &DbConn) -> CliResult<BTreeMap<ProjectKey, KeyedRow>> {
+    let rows = conn
+        .query_sync("SELECT id, slug, human_key FROM projects", &[])
+        .map_err(|e| CliError::Other(format!("project key scan failed: {e}")))?;
+    let mut out = BTreeMap::new();
+    for row in &rows {
+        let id: i64 = row.get_named("id").unwrap_or(0);
+        let slug: String = row.get_named("slug").unwrap_or_default();
+        let human_key: String = row.get_named("human_key").unwrap_or_default();
+        out.insert(
+            ProjectKey::new(&slug),
+            KeyedRow {
+                id,
+                fingerprint: content_fingerprint(&[&human_key]),
+                recency_ts: 0,
+            },
+        );
+    }
+    Ok(out)
+}
+
+/// Load agents keyed by project slug + name. Fingerprint: program, model,
+/// task description. Recency: `last_active_ts`.
+pub fn load_agent_keys(conn: &DbConn) -> CliResult<BTreeMap<AgentKey, KeyedRow>> {
+    let rows = conn
+        .query_sync(
+            "SELECT a.id, p.slug, a.name, a.program, a.model, a.task_description, \
+                    a.last_active_ts \
+             FROM agents a JOIN projects p ON p.id = a.project_id",
+            &[],
+        )
+        .map_err(|e| CliError::Other(format!("agent key scan failed: {e}")))?;
+    let mut out = BTreeMap::new();
+    for row in &rows {
+        let id: i64 = row.get_named("id").unwrap_or(0);
+        let slug: String = row.get_named("slug").unwrap_or_default();
+        let name: String = row.get_named("name").unwrap_or_default();
+        let program: String = row.get_named("program").unwrap_or_default();
+        let model: String = row.get_named("model").unwrap_or_default();
+        let task: String = row.get_named("task_description").unwrap_or_default();
+        let last_active_ts: i64 = row.get_named("last_active_ts").unwrap_or(0);
+        out.insert(
+            AgentKey::new(&slug, &name),
+            KeyedRow {
+                id,
+                fingerprint: content_fingerprint(&[&program, &model, &task]),
+                recency_ts: last_active_ts,
+            },
+        );
+    }
+    Ok(out)
+}
+
+/// Load messages keyed by project slug + sender + `created_ts` + subject
+/// hash. Fingerprint: subject, body, thread id, importance, ack flag.
+///
+/// Messages whose sender row is missing cannot be keyed and are skipped.
+/// When several messages share one key, the lowest id wins; use
+/// [`duplicate_message_keys`] to surface such collisions.
+pub fn load_message_keys(conn: &DbConn) -> CliResult<BTreeMap<MessageKey, KeyedRow>> {
+    let mut out = BTreeMap::new();
+    for (key, row) in scan_message_keys(conn)? {
+        out.entry(key).or_insert(row);
+    }
+    Ok(out)
+}
+
+/// Natural keys that more than one message maps to, with their row count.
+pub fn duplicate_message_keys(conn: &DbConn) -> CliResult<Vec<(MessageKey, usize)>> {
+    let mut counts: BTreeMap<MessageKey, usize> = BTreeMap::new();
+    for (key, _) in scan_message_keys(conn)? {
+        *counts.entry(key).or_default() += 1;
+    }
+    Ok(counts.into_iter().filter(|(_, count)| *count > 1).collect())
+}
+
+fn scan_message_keys(conn: &DbConn) -> CliResult<Vec<(MessageKey, KeyedRow)>> {
+    let rows = conn
+        .query_sync(
+            "SELECT m.id, p.slug, a.name AS sender, m.created_ts, m.subject, m.body_md, \
+                    m.thread_id, m.importance, m.ack_required \
+             FROM messages m \
+             JOIN projects p ON p.id = m.project_id \
+             JOIN agents a ON a.id = m.sender_id \
+             ORDER BY m.id",
+            &[],
+        )
+        .map_err(|e| CliError::Other(format!("message key scan failed: {e}")))?;
+    let mut out = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let id: i64 = row.get_named("id").unwrap_or(0);
+        let slug: String = row.get_named("slug").unwrap_or_default();
+        let sender: String = row.get_named("sender").unwrap_or_default();
+        let created_ts: i64 = row.get_named("created_ts").unwrap_or(0);
+        let subject: String = row.get_named("subject").unwrap_or_default();
+        let body: String = row.get_named("body_md").unwrap_or_default();
+        let thread_id: String = row.get_named("thread_id").unwrap_or_default();
+        let importance: String = row.get_named("importance").unwrap_or_default();
+        let ack_required: i64 = row.get_named("ack_required").unwrap_or(0);
+        out.push((
+            MessageKey::new(&slug, &sender, created_ts, &subject),
+            KeyedRow {
+                id,
+                fingerprint: content_fingerprint(&[
+                    subject.trim(),
+                    &body,
+                    &thread_id,
+                    &importance,
+                    &ack_required.to_string(),
+                ]),
+                recency_ts: created_ts,
+            },
+        ));
+    }
+    Ok(out)
+}
+
+/// Load file reservations keyed by project slug + holder + path pattern +
+/// `created_ts`. Fingerprint: exclusivity and expiry. Recency: the release
+/// timestamp, from `released_ts` or the release ledger; 0 while active.
+pub fn load_reservation_keys(conn: &DbConn) -> CliResult<BTreeMap<ReservationKey, KeyedRow>> {
+    let released_sql = if table_columns(conn, "file_reservation_releases")?.is_empty() {
+        "r.released_ts"
+    } else {
+        "COALESCE(r.released_ts, (SELECT l.released_ts FROM file_reservation_releases l \
+                                  WHERE l.reservation_id = r.id))"
+    };
+    let rows = conn
+        .query_sync(
+            &format!(
+                "SELECT r.id, p.slug, a.name, r.path_pattern, r.\"exclusive\" AS exclusive, \
+                        r.created_ts, r.expires_ts, {released_sql} AS released_ts \
+                 FROM file_reservations r \
+                 JOIN projects p ON p.id = r.project_id \
+                 JOIN agents a ON a.id = r.agent_id \
+                 ORDER BY r.id"
+            ),
+            &[],
+        )
+        .map_err(|e| CliError::Other(format!("reservation key scan failed: {e}")))?;
+    let mut out = BTreeMap::new();
+    for row in &rows {
+        let id: i64 = row.get_named("id").unwrap_or(0);
+        let slug: String = row.get_named("slug").unwrap_or_default();
+        let holder: String = row.get_named("name").unwrap_or_default();
+        let pattern: String = row.get_named("path_pattern").unwrap_or_default();
+        let exclusive: i64 = row.get_named("exclusive").unwrap_or(0);
+        let created_ts: i64 = row.get_named("created_ts").unwrap_or(0);
+        let expires_ts: i64 = row.get_named("expires_ts").unwrap_or(0);
+        let released_ts: i64 = row.get_named("released_ts").unwrap_or(0);
+        out.entry(ReservationKey::new(&slug, &holder, &pattern, created_ts))
+            .or_insert(KeyedRow {
+                id,
+                fingerprint: content_fingerprint(&[
+                    &exclusive.to_string(),
+                    &expires_ts.to_string(),
+                ]),
+                recency_ts: released_ts,
+            });
+    }
+    Ok(out)
+}
+
+/// Column names of `table`, in declaration order.
+pub fn table_columns(conn: &DbConn, table: &str) -> CliResult<Vec<String>> {
+    let rows = conn
+        .query_sync(&format!("PRAGMA table_info({table})"), &[])
+        .map_err(|e| CliError::Other(format!("table_info({table}) failed: {e}")))?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.get_named::<String>("name").ok())
+        .collect())
+}
+
+/// Columns present in `table` on both sides, excluding `id`.
+///
+/// Archives may predate (or postdate) the live schema; copying only the
+/// shared columns lets the live defaults fill the rest.
+pub fn shared_columns(source: &DbConn, target: &DbConn, table: &str) -> CliResult<Vec<String>> {
+    let target_columns = table_columns(target, table)?;
+    Ok(table_columns(source, table)?
+        .into_iter()
+        .filter(|column| column != "id" && target_columns.contains(column))
+        .collect())
+}
+
+/// The first `len` values of `row`, for re-binding in a row copy.
+#[must_use]
+pub fn row_values(row: &sqlmodel_core::Row, len: usize) -> Vec<Value> {
+    (0..len)
+        .map(|idx| row.get(idx).cloned().unwrap_or(Value::Null))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_conn() -> DbConn {
+        let conn = DbConn::open_memory().expect("open memory db");
+        for sql in [
+            "CREATE TABLE projects (id INTEGER PRIMARY KEY, slug TEXT, human_key TEXT)",
+            "CREATE TABLE agents (id INTEGER PRIMARY KEY, project_id INTEGER, name TEXT, \
+                program TEXT, model TEXT, task_description TEXT, last_active_ts INTEGER)",
+            "CREATE TABLE messages (id INTEGER PRIMARY KEY, project_id INTEGER, \
+                sender_id INTEGER, thread_id TEXT, subject TEXT, body_md TEXT, \
+                importance TEXT, ack_required INTEGER, created_ts INTEGER)",
+            "INSERT INTO projects VALUES (1, 'proj', '/tmp/proj')",
+            "INSERT INTO agents VALUES (1, 1, 'BlueLake', 'cc', 'm', '', 10)",
+            "INSERT INTO agents VALUES (2, 1, 'RedFox', 'cc', 'm', '', 20)",
+        ] {
+            conn.execute_raw(sql).expect("seed schema");
+        }
+        conn
+    }
+
+    fn insert_message(conn: &DbConn, id: i64, sender_id: i64, subject: &str, body: &str, ts: i64) {
+        conn.execute_sync(
+            "INSERT INTO messages VALUES (?, 1, ?, NULL, ?, ?, 'normal', 0, ?)",
+            &[
+                Value::BigInt(id),
+                Value::BigInt(sender_id),
+                Value::Text(subject.to_string()),
+                Value::Text(body.to_string()),
+                Value::BigInt(ts),
+            ],
+        )
+        .expect("insert message");
+    }
+
+    #[test]
+    fn agent_key_is_case_insensitive() {
+        assert_eq!(
+            AgentKey::new("proj", "BlueLake"),
+            AgentKey::new("proj", " bluelake ")
+        );
+        assert_ne!(
+            AgentKey::new("proj", "BlueLake"),
+            AgentKey::new("other", "BlueLake")
+        );
+    }
+
+    #[test]
+    fn same_subject_and_timestamp_from_different_senders_do_not_collide() {
+        let conn = seeded_conn();
+        insert_message(&conn, 1, 1, "build green", "a", 1_000);
+        insert_message(&conn, 2, 2, "build green", "a", 1_000);
+
+        let keys = load_message_keys(&conn).expect("load keys");
+        assert_eq!(keys.len(), 2);
+        assert!(duplicate_message_keys(&conn).expect("dupes").is_empty());
+    }
+
+    #[test]
+    fn same_sender_subject_and_timestamp_collide() {
+        let conn = seeded_conn();
+        insert_message(&conn, 1, 1, "build green", "first", 1_000);
+        insert_message(&conn, 2, 1, "build green", "second", 1_000);
+
+        let keys = load_message_keys(&conn).expect("load keys");
+        assert_eq!(keys.len(), 1);
+        let key = MessageKey::new("proj", "bluelake", 1_000, "build green");
+        assert_eq!(keys[&key].id, 1, "lowest id wins a key collision");
+        assert_eq!(
+            duplicate_message_keys(&conn).expect("dupes"),
+            vec![(key, 2)]
+        );
+    }
+
+    #[test]
+    fn subject_whitespace_does_not_change_key() {
+        assert_eq!(
+            MessageKey::new("proj", "A", 5, "hello"),
+            MessageKey::new("proj", "a", 5, "  hello\n")
+        );
+        assert_ne!(
+            MessageKey::new("proj", "A", 5, "hello"),
+            MessageKey::new("proj", "A", 6, "hello")
+        );
+    }
+
+    #[test]
+    fn classify_distinguishes_identical_from_conflicting_content() {
+        let conn = seeded_conn();
+        insert_message(&conn, 1, 1, "status", "body", 1_000);
+        let existing = load_message_keys(&conn).expect("load keys");
+        let key = MessageKey::new("proj", "BlueLake", 1_000, "status");
+        let same = existing[&key];
+        let changed = KeyedRow {
+            fingerprint: same.fingerprint ^ 1,
+            ..same
+        };
+        let other_key = MessageKey::new("proj", "BlueLake", 2_000, "status");
+
+        assert_eq!(classify(&existing, &key, &same), KeyMatch::Identical);
+        assert_eq!(classify(&existing, &key, &changed), KeyMatch::Differs);
+        assert_eq!(classify(&existing, &other_key, &same), KeyMatch::Missing);
+    }
+
+    #[test]
+    fn reservation_release_comes_from_column_or_ledger() {
+        let conn = seeded_conn();
+        for sql in [
+            "CREATE TABLE file_reservations (id INTEGER PRIMARY KEY, project_id INTEGER, \
+                agent_id INTEGER, path_pattern TEXT, \"exclusive\" INTEGER, created_ts INTEGER, \
+                expires_ts INTEGER, released_ts INTEGER)",
+            "CREATE TABLE file_reservation_releases (reservation_id INTEGER PRIMARY KEY, \
+                released_ts INTEGER NOT NULL)",
+            "INSERT INTO file_reservations VALUES (1, 1, 1, 'src/**', 1, 100, 900, 500)",
+            "INSERT INTO file_reservations VALUES (2, 1, 2, 'docs/**', 0, 100, 900, NULL)",
+            "INSERT INTO file_reservations VALUES (3, 1, 2, 'tests/**', 0, 100, 900, NULL)",
+            "INSERT INTO file_reservation_releases VALUES (2, 700)",
+        ] {
+            conn.execute_raw(sql).expect("seed reservations");
+        }
+
+        let keys = load_reservation_keys(&conn).expect("load keys");
+        let recency = |holder: &str, pattern: &str| {
+            keys[&ReservationKey::new("proj", holder, pattern, 100)].recency_ts
+        };
+        assert_eq!(recency("BlueLake", "src/**"), 500);
+        assert_eq!(recency("redfox", "docs/**"), 700);
+        assert_eq!(recency("RedFox", "tests/**"), 0, "active reservation");
+    }
+
+    #[test]
+    fn fingerprint_respects_field_boundaries() {
+        assert_ne!(
+            content_fingerprint(&["ab", "c"]),
+            content_fingerprint(&["a", "bc"])
+        );
+    }
+
+    #[test]
+    fn shared_columns_skip_id_and_one_sided_columns() {
+        let source = DbConn::open_memory().expect("source");
+        source
+            .execute_raw("CREATE TABLE t (id INTEGER PRIMARY KEY, a TEXT, legacy TEXT)")
+            .expect("source table");
+        let target = DbConn::open_memory().expect("target");
+        target
+            .execute_raw("CREATE TABLE t (id INTEGER PRIMARY KEY, a TEXT, added TEXT)")
+            .expect("target table");
+        assert_eq!(
+            shared_columns(&source, &target, "t").expect("shared"),
+            vec!["a".to_string()]
+        );
+    }
+}
@@ -3,14 +3,19 @@
 //! Provides structured output that automatically adapts:
 //! - **JSON mode**: Machine-readable JSON via `--json` flag
 //! - **TOON mode**: Token-optimized output via `--format toon`
+//! - **CSV / Markdown mode**: List-like payloads as rows via `--format csv`
+//!   or `--format markdown`
 //! - **TTY mode**: Styled table output with headers and borders
 //! - **Pipe mode**: Clean plain-text tables (no color, no decoration)
 
 #![forbid(unsafe_code)]
 
 use serde::Serialize;
+use std::cell::RefCell;
 #[allow(unused_imports)]
 use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI32, Ordering};
 use unicode_width::UnicodeWidthStr;
 
 // ── Output format enum ────────────────────────────────────────────────────
@@ -28,6 +33,10 @@ pub enum CliOutputFormat {
     Json,
     /// Token-optimized TOON encoding.
     Toon,
+    /// Comma-separated values (RFC 4180 quoting) for list-like payloads.
+    Csv,
+    /// GitHub-flavored markdown table for list-like payloads.
+    Markdown,
 }
 
 impl std::fmt::Display for CliOutputFormat {
@@ -36,6 +45,8 @@ impl std::fmt::Display for CliOutputFormat {
             Self::Table => f.write_str("table"),
             Self::Json => f.write_str("json"),
             Self::Toon => f.write_str("toon"),
+            Self::Csv => f.write_str("csv"),
+            Self::Markdown => f.write_str("markdown"),
         }
     }
 }
@@ -48,8 +59,10 @@ impl std::str::FromStr for CliOutputFormat {
             "table" => Ok(Self::Table),
             "json" => Ok(Self::Json),
             "toon" => Ok(Self::Toon),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
             other => Err(format!(
-                "unknown output format: {other} (expected table, json, or toon)"
+                "unknown output format: {other} (expected table, json, toon, csv, or markdown)"
             )),
         }
     }
@@ -70,6 +83,13 @@ impl CliOutputFormat {
         // Preserve legacy default behavior: table output unless explicitly overridden.
         Self::Table
     }
+
+    /// Whether this is a row-oriented format (`csv`, `markdown`) that only
+    /// applies to list-like payloads.
+    #[must_use]
+    pub const fn is_tabular(self) -> bool {
+        matches!(self, Self::Csv | Self::Markdown)
+    }
 }
 
 /// Detect whether stdout is a TTY.
@@ -98,6 +118,19 @@ pub fn is_stdin_tty() -> bool {
     }
 }
 
+/// Detect whether stderr is a TTY.
+#[must_use]
+pub fn is_stderr_tty() -> bool {
+    #[cfg(test)]
+    {
+        false
+    }
+    #[cfg(not(test))]
+    {
+        std::io::stderr().is_terminal()
+    }
+}
+
 // ── Simple table renderer ────────────────────────────────────────────────
 
 /// A simple CLI table that auto-sizes columns and renders to text.
@@ -234,10 +267,13 @@ pub fn json_or_table<T: Serialize, F>(json_mode: bool, data: &T, render: F)
 where
     F: FnOnce(),
 {
+    if capture_payload(data) {
+        return;
+    }
     if json_mode {
-        ftui_runtime::ftui_println!("{}", encode_json_pretty_or_error(data));
+        emit_guarded(&encode_json_pretty_or_error(data), CliOutputFormat::Json);
     } else {
-        render();
+        emit_table_guarded(render);
     }
 }
 
@@ -246,36 +282,791 @@ where
 /// Emit data in the requested format.
 ///
 /// This is the recommended way to output data from CLI commands that support
-/// `--format`. It handles JSON, TOON, and table output uniformly.
+/// `--format`. It handles JSON, TOON, and table output uniformly. CSV and
+/// markdown render list-like payloads as rows and fall back to JSON (with a
+/// warning on stderr) for nested payloads.
 ///
 /// # Arguments
 /// - `data`: The data to output (must be Serialize for JSON/TOON)
 /// - `format`: The output format to use
 /// - `table_render`: Closure to render human-readable table output
 pub fn emit_output<T: Serialize, F>(data: &T, format: CliOutputFormat, table_render: F)
+where
+    F: FnOnce(),
+{
+    if capture_payload(data) {
+        return;
+    }
+    emit_output_with(
+        active_field_selection().as_ref(),
+        data,
+        format,
+        table_render,
+    );
+}
+
+fn emit_output_with<T: Serialize, F>(
+    fields: Option<&ActiveFields>,
+    data: &T,
+    format: CliOutputFormat,
+    table_render: F,
+) where
+    F: FnOnce(),
+{
+    let projection = fields.and_then(|fields| {
+        let value = serde_json::to_value(data).ok()?;
+        project_fields(&value, fields)
+    });
+    match (projection, fields) {
+        (Some(Ok(projected)), Some(fields)) => emit_projected(&projected, fields, format),
+        (Some(Err(unknown)), _) => emit_unknown_fields(&unknown, format),
+        _ => emit_formatted(data, format, table_render),
+    }
+}
+
+fn emit_formatted<T: Serialize, F>(data: &T, format: CliOutputFormat, table_render: F)
 where
     F: FnOnce(),
 {
     match format {
-        CliOutputFormat::Table => {
-            table_render();
-        }
+        CliOutputFormat::Table => emit_table_guarded(table_render),
         CliOutputFormat::Json => {
-            ftui_runtime::ftui_println!("{}", encode_json_pretty_or_error(data));
+            emit_guarded(&encode_json_pretty_or_error(data), format);
         }
         CliOutputFormat::Toon => {
             let json_str = encode_json_compact_or_error(data);
             match toon::json_to_toon(&json_str) {
-                Ok(toon_str) => ftui_runtime::ftui_println!("{}", toon_str),
+                Ok(toon_str) => emit_guarded(&toon_str, format),
                 Err(_) => {
                     // Fallback to JSON if TOON conversion fails
-                    ftui_runtime::ftui_println!("{}", encode_json_pretty_or_error(data));
+                    emit_guarded(&encode_json_pretty_or_error(data), format);
+                }
+            }
+        }
+        CliOutputFormat::Csv | CliOutputFormat::Markdown => {
+            let rows = serde_json::to_value(data)
+                .ok()
+                .as_ref()
+                .and_then(tabular_rows);
+            match rows {
+                Some((headers, rows)) => {
+                    let rendered = if format == CliOutputFormat::Csv {
+                        render_csv(&headers, &rows)
+                    } else {
+                        render_markdown(&headers, &rows)
+                    };
+                    if !rendered.is_empty() {
+                        emit_guarded(&rendered, format);
+                    }
                 }
+                None => {
+                    warn_tabular_fallback(format);
+                    emit_guarded(&encode_json_pretty_or_error(data), format);
+                }
+            }
+        }
+    }
+}
+
+/// Warn on stderr that `format` cannot represent this payload and JSON is
+/// being emitted instead.
+pub fn warn_tabular_fallback(format: CliOutputFormat) {
+    warn(&format!(
+        "--format {format} needs list-like output; falling back to JSON"
+    ));
+}
+
+// ── Output size guard ───────────────────────────────────────────────────
+
+/// Environment variable read when `--max-output-bytes` is not given.
+pub const MAX_OUTPUT_BYTES_ENV: &str = "AM_MAX_OUTPUT_BYTES";
+
+/// Cap applied to `am robot ...` when neither the flag nor the environment
+/// sets one. Human-facing commands stay unlimited by default.
+pub const ROBOT_DEFAULT_MAX_OUTPUT_BYTES: u64 = 1024 * 1024;
+
+/// Exit code for a command whose output was withheld for exceeding the cap.
+pub const OUTPUT_TOO_LARGE_EXIT_CODE: i32 = 65;
+
+/// Flags that shrink a command's output, in the order suggestions list them.
+pub const NARROWING_FLAGS: [&str; 3] = ["limit", "count-only", "fields"];
+
+/// The byte cap in force for this process and what the running command can
+/// do to stay under it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputLimit {
+    /// `None` means unlimited.
+    pub max_bytes: Option<u64>,
+    /// Command path without the binary name, e.g. `mail inbox`.
+    pub command: String,
+    /// Long names (without `--`) of the [`NARROWING_FLAGS`] the command accepts.
+    pub narrowing_flags: Vec<&'static str>,
+}
+
+/// Structured error printed in place of a payload that exceeded the cap.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct OutputTooLarge {
+    pub error: &'static str,
+    pub message: String,
+    pub command: String,
+    pub actual_bytes: u64,
+    pub max_bytes: u64,
+    pub exit_code: i32,
+    pub suggestions: Vec<String>,
+}
+
+static OUTPUT_LIMIT: Mutex<Option<OutputLimit>> = Mutex::new(None);
+static DEFERRED_EXIT_CODE: AtomicI32 = AtomicI32::new(0);
+
+/// Resolve the cap from the flag, then the environment, then the per-surface
+/// default. `0` from either source disables the cap.
+pub fn resolve_max_output_bytes(
+    flag: Option<u64>,
+    env: Option<&str>,
+    robot: bool,
+) -> Result<Option<u64>, String> {
+    let explicit = match (flag, env.map(str::trim).filter(|v| !v.is_empty())) {
+        (Some(bytes), _) => Some(bytes),
+        (None, Some(raw)) => Some(
+            raw.parse::<u64>()
+                .map_err(|_| format!("{MAX_OUTPUT_BYTES_ENV}={raw} is not a byte count"))?,
+        ),
+        (None, None) => None,
+    };
+    Ok(match explicit {
+        Some(0) => None,
+        Some(bytes) => Some(bytes),
+        None => robot.then_some(ROBOT_DEFAULT_MAX_OUTPUT_BYTES),
+    })
+}
+
+/// Install the cap for the rest of this process and clear any earlier
+/// withheld output.
+pub fn set_output_limit(limit: OutputLimit) {
+    *OUTPUT_LIMIT.lock().unwrap_or_else(|e| e.into_inner()) = Some(limit);
+    DEFERRED_EXIT_CODE.store(0, Ordering::Release);
+}
+
+/// Remove the cap (tests and embedders that reuse the process).
+pub fn clear_output_limit() {
+    *OUTPUT_LIMIT.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    DEFERRED_EXIT_CODE.store(0, Ordering::Release);
+}
+
+/// Exit code owed because a payload was withheld since the cap was
+/// installed: [`OUTPUT_TOO_LARGE_EXIT_CODE`] for an oversized payload,
+/// [`UNKNOWN_FIELDS_EXIT_CODE`] for an unknown `--fields` name. The CLI entry
+/// point returns it in place of 0.
+#[must_use]
+pub fn deferred_exit_code() -> Option<i32> {
+    match DEFERRED_EXIT_CODE.load(Ordering::Acquire) {
+        0 => None,
+        code => Some(code),
+    }
+}
+
+fn active_output_limit() -> Option<OutputLimit> {
+    OUTPUT_LIMIT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .filter(|limit| limit.max_bytes.is_some())
+}
+
+/// Check a rendered payload of `actual_bytes` against `limit`.
+#[must_use]
+pub fn check_output_size(limit: &OutputLimit, actual_bytes: u64) -> Option<OutputTooLarge> {
+    let max_bytes = limit.max_bytes?;
+    if actual_bytes <= max_bytes {
+        return None;
+    }
+    Some(OutputTooLarge {
+        error: "output_too_large",
+        message: format!(
+            "output of {actual_bytes} bytes exceeds the {max_bytes}-byte cap; nothing was printed"
+        ),
+        command: limit.command.clone(),
+        actual_bytes,
+        max_bytes,
+        exit_code: OUTPUT_TOO_LARGE_EXIT_CODE,
+        suggestions: narrowing_suggestions(limit, actual_bytes),
+    })
+}
+
+/// Concrete ways to get under the cap, specific to the flags `limit.command`
+/// accepts.
+#[must_use]
+pub fn narrowing_suggestions(limit: &OutputLimit, actual_bytes: u64) -> Vec<String> {
+    let command = if limit.command.is_empty() {
+        "am".to_string()
+    } else {
+        format!("am {}", limit.command)
+    };
+    let mut suggestions: Vec<String> = NARROWING_FLAGS
+        .iter()
+        .filter(|flag| limit.narrowing_flags.contains(flag))
+        .map(|flag| match *flag {
+            "limit" => format!("add --limit N to `{command}` to return fewer rows"),
+            "count-only" => {
+                format!("add --count-only to `{command}` to get totals instead of rows")
+            }
+            _ => format!("add --fields to `{command}` to keep only the columns you need"),
+        })
+        .collect();
+    if suggestions.is_empty() {
+        suggestions.push(format!(
+            "narrow `{command}` with its filters (see `{command} --help`)"
+        ));
+    }
+    suggestions.push(format!(
+        "or raise the cap: --max-output-bytes {actual_bytes} (0 disables; also {MAX_OUTPUT_BYTES_ENV})"
+    ));
+    suggestions
+}
+
+/// Print `rendered` unless it exceeds the active cap, in which case print the
+/// [`OutputTooLarge`] error in `format` instead. Machine formats get the error
+/// on stdout where the payload would have been; human formats get it on
+/// stderr.
+pub fn emit_guarded(rendered: &str, format: CliOutputFormat) {
+    emit_guarded_with(active_output_limit().as_ref(), rendered, format);
+}
+
+fn emit_guarded_with(
+    limit: Option<&OutputLimit>,
+    rendered: &str,
+    format: CliOutputFormat,
+) -> Option<OutputTooLarge> {
+    match limit.and_then(|limit| check_output_size(limit, rendered.len() as u64)) {
+        None => {
+            ftui_runtime::ftui_println!("{rendered}");
+            None
+        }
+        Some(overflow) => {
+            emit_output_too_large(&overflow, format);
+            Some(overflow)
+        }
+    }
+}
+
+fn emit_table_guarded<F: FnOnce()>(table_render: F) {
+    emit_table_guarded_with(active_output_limit().as_ref(), table_render);
+}
+
+/// Table output is printed by the caller's closure, so when a cap is active
+/// it is captured and measured before anything reaches stdout.
+fn emit_table_guarded_with<F: FnOnce()>(
+    limit: Option<&OutputLimit>,
+    table_render: F,
+) -> Option<OutputTooLarge> {
+    if limit.is_none() {
+        table_render();
+        return None;
+    }
+    // Another capture (a test harness) already owns stdio: print unguarded
+    // rather than lose the output.
+    let Ok(capture) = ftui_runtime::StdioCapture::install() else {
+        table_render();
+        return None;
+    };
+    table_render();
+    let rendered = capture.drain_to_string();
+    drop(capture);
+    if rendered.is_empty() {
+        return None;
+    }
+    emit_guarded_with(
+        limit,
+        rendered.strip_suffix('\n').unwrap_or(&rendered),
+        CliOutputFormat::Table,
+    )
+}
+
+fn emit_output_too_large(overflow: &OutputTooLarge, format: CliOutputFormat) {
+    DEFERRED_EXIT_CODE.store(OUTPUT_TOO_LARGE_EXIT_CODE, Ordering::Release);
+    emit_withheld(overflow, &overflow.message, &overflow.suggestions, format);
+}
+
+/// Print the structured error that replaces a withheld payload: on stdout in
+/// machine formats, as `message` plus one line per detail on stderr otherwise.
+fn emit_withheld<E: Serialize>(
+    payload: &E,
+    message: &str,
+    details: &[String],
+    format: CliOutputFormat,
+) {
+    match format {
+        CliOutputFormat::Json => {
+            ftui_runtime::ftui_println!("{}", encode_json_pretty_or_error(payload));
+        }
+        CliOutputFormat::Toon => {
+            let json_str = encode_json_compact_or_error(payload);
+            let rendered = toon::json_to_toon(&json_str)
+                .unwrap_or_else(|_| encode_json_pretty_or_error(payload));
+            ftui_runtime::ftui_println!("{rendered}");
+        }
+        CliOutputFormat::Table | CliOutputFormat::Csv | CliOutputFormat::Markdown => {
+            error(message);
+            for detail in details {
+                ftui_runtime::ftui_eprintln!("  - {detail}");
+            }
+        }
+    }
+}
+
+// ── Field selection ─────────────────────────────────────────────────────
+
+/// Exit code for a `--fields` list naming a field the output does not have.
+pub const UNKNOWN_FIELDS_EXIT_CODE: i32 = 2;
+
+const RESERVATION_RECORD_KEYS: &[&str] = &[
+    "my_reservations",
+    "all_active",
+    "conflicting_active",
+    "expiring_soon",
+];
+
+/// Commands that accept `--fields`, with the payload keys holding their
+/// records. A command whose payload is a bare array lists no keys.
+pub const FIELD_SELECTION_COMMANDS: [(&str, &[&str]); 10] = [
+    ("mail inbox", &["messages"]),
+    ("mail search", &[]),
+    ("agents list", &[]),
+    ("file_reservations list", &[]),
+    ("robot inbox", &["inbox"]),
+    ("robot search", &["results"]),
+    ("robot reservations", RESERVATION_RECORD_KEYS),
+    ("robot agents", &["agents"]),
+    ("inbox", &["inbox"]),
+    ("reservations", RESERVATION_RECORD_KEYS),
+];
+
+/// Parsed `--fields` value: comma-separated field names, dotted to reach
+/// into nested objects (`id,subject,sender.name`). Duplicates are dropped and
+/// the first-seen order is kept, which is also the output key order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSelection {
+    paths: Vec<String>,
+}
+
+impl FieldSelection {
+    /// Clap value parser for `--fields`.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut paths: Vec<String> = Vec::new();
+        for path in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            if path.split('.').any(str::is_empty) {
+                return Err(format!(
+                    "invalid field '{path}': expected a name or a dotted path like sender.name"
+                ));
+            }
+            if !paths.iter().any(|seen| seen == path) {
+                paths.push(path.to_string());
+            }
+        }
+        if paths.is_empty() {
+            return Err("expected at least one field name, e.g. --fields id,subject".to_string());
+        }
+        Ok(Self { paths })
+    }
+
+    #[must_use]
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+}
+
+/// `--fields` in force for the running command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveFields {
+    /// Command path without the binary name, e.g. `mail inbox`.
+    pub command: String,
+    pub selection: FieldSelection,
+    /// Payload keys holding the records; empty when the payload is an array.
+    pub record_keys: &'static [&'static str],
+}
+
+/// Structured error printed in place of a payload when `--fields` names a
+/// field its records do not have.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct UnknownFields {
+    pub error: &'static str,
+    pub message: String,
+    pub command: String,
+    pub unknown_fields: Vec<String>,
+    pub available_fields: Vec<String>,
+    pub exit_code: i32,
+}
+
+static FIELD_SELECTION: Mutex<Option<ActiveFields>> = Mutex::new(None);
+
+/// Whether `command` (a path like `mail inbox`) accepts `--fields`.
+#[must_use]
+pub fn supports_field_selection(command: &str) -> bool {
+    FIELD_SELECTION_COMMANDS
+        .iter()
+        .any(|(name, _)| *name == command)
+}
+
+/// Bind a parsed `--fields` value to the invoked command, rejecting commands
+/// that do not produce record lists.
+pub fn field_selection_for(
+    command: &str,
+    selection: Option<FieldSelection>,
+) -> Result<Option<ActiveFields>, String> {
+    let Some(selection) = selection else {
+        return Ok(None);
+    };
+    let Some((_, record_keys)) = FIELD_SELECTION_COMMANDS
+        .iter()
+        .find(|(name, _)| *name == command)
+    else {
+        let supported: Vec<String> = FIELD_SELECTION_COMMANDS
+            .iter()
+            .map(|(name, _)| format!("`am {name}`"))
+            .collect();
+        return Err(format!(
+            "--fields is not supported by `am {command}`; it applies to {}",
+            supported.join(", ")
+        ));
+    };
+    Ok(Some(ActiveFields {
+        command: command.to_string(),
+        selection,
+        record_keys,
+    }))
+}
+
+/// Install (or with `None`, remove) the field selection for the rest of this
+/// process.
+pub fn set_field_selection(fields: Option<ActiveFields>) {
+    *FIELD_SELECTION.lock().unwrap_or_else(|e| e.into_inner()) = fields;
+}
+
+/// The field selection in force, if any.
+#[must_use]
+pub fn active_field_selection() -> Option<ActiveFields> {
+    FIELD_SELECTION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+fn record_arrays<'a>(
+    value: &'a serde_json::Value,
+    record_keys: &[&str],
+) -> Vec<&'a Vec<serde_json::Value>> {
+    match value {
+        serde_json::Value::Array(items) => vec![items],
+        serde_json::Value::Object(map) => record_keys
+            .iter()
+            .filter_map(|key| map.get(*key).and_then(serde_json::Value::as_array))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn collect_field_paths(
+    map: &serde_json::Map<String, serde_json::Value>,
+    prefix: &str,
+    out: &mut std::collections::BTreeSet<String>,
+) {
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if let serde_json::Value::Object(nested) = value {
+            collect_field_paths(nested, &path, out);
+        }
+        out.insert(path);
+    }
+}
+
+/// Every selectable path in the records of `value`, sorted: each key, and
+/// for object-valued keys each dotted path beneath them. Arrays are leaves.
+/// The catalog comes from the serialized output itself, so it always matches
+/// what the command prints.
+#[must_use]
+pub fn available_fields(value: &serde_json::Value, record_keys: &[&str]) -> Vec<String> {
+    let mut paths = std::collections::BTreeSet::new();
+    for records in record_arrays(value, record_keys) {
+        for record in records.iter().filter_map(serde_json::Value::as_object) {
+            collect_field_paths(record, "", &mut paths);
+        }
+    }
+    paths.into_iter().collect()
+}
+
+fn lookup_path<'a>(record: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(record, |value, segment| value.get(segment))
+}
+
+fn insert_path(
+    map: &mut serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    value: serde_json::Value,
+) {
+    match path.split_once('.') {
+        None => {
+            map.insert(path.to_string(), value);
+        }
+        Some((head, rest)) => {
+            let child = map
+                .entry(head.to_string())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            if let serde_json::Value::Object(child) = child {
+                insert_path(child, rest, value);
+            }
+        }
+    }
+}
+
+fn project_record(record: &serde_json::Value, paths: &[String]) -> serde_json::Value {
+    if !record.is_object() {
+        return record.clone();
+    }
+    let mut projected = serde_json::Map::new();
+    for path in paths {
+        if let Some(value) = lookup_path(record, path) {
+            insert_path(&mut projected, path, value.clone());
+        }
+    }
+    serde_json::Value::Object(projected)
+}
+
+/// Keep only the selected paths in every record of `value`, leaving the rest
+/// of the payload (cursors, counts, envelope metadata) as it was. Returns
+/// `None` when there are no records to project, and the [`UnknownFields`]
+/// error when a path appears in none of them.
+#[must_use]
+pub fn project_fields(
+    value: &serde_json::Value,
+    fields: &ActiveFields,
+) -> Option<Result<serde_json::Value, UnknownFields>> {
+    if record_arrays(value, fields.record_keys)
+        .iter()
+        .all(|records| records.is_empty())
+    {
+        return None;
+    }
+    let available = available_fields(value, fields.record_keys);
+    let unknown: Vec<String> = fields
+        .selection
+        .paths()
+        .iter()
+        .filter(|path| available.binary_search(path).is_err())
+        .cloned()
+        .collect();
+    if !unknown.is_empty() {
+        return Some(Err(UnknownFields {
+            error: "unknown_fields",
+            message: format!(
+                "unknown field(s) for `am {}`: {}",
+                fields.command,
+                unknown.join(", ")
+            ),
+            command: fields.command.clone(),
+            unknown_fields: unknown,
+            available_fields: available,
+            exit_code: UNKNOWN_FIELDS_EXIT_CODE,
+        }));
+    }
+
+    let paths = fields.selection.paths();
+    let project = |records: &[serde_json::Value]| -> serde_json::Value {
+        serde_json::Value::Array(
+            records
+                .iter()
+                .map(|record| project_record(record, paths))
+                .collect(),
+        )
+    };
+    let projected = match value {
+        serde_json::Value::Array(records) => project(records),
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, entry)| {
+                    let entry = match entry {
+                        serde_json::Value::Array(records)
+                            if fields.record_keys.contains(&key.as_str()) =>
+                        {
+                            project(records)
+                        }
+                        _ => entry.clone(),
+                    };
+                    (key.clone(), entry)
+                })
+                .collect(),
+        ),
+        _ => value.clone(),
+    };
+    Some(Ok(projected))
+}
+
+/// Project the serialized payload for the active `--fields`. Unknown fields
+/// record [`UNKNOWN_FIELDS_EXIT_CODE`] as the deferred exit code and come back
+/// as the error to print instead.
+pub fn apply_field_selection(value: serde_json::Value) -> Result<serde_json::Value, UnknownFields> {
+    let Some(fields) = active_field_selection() else {
+        return Ok(value);
+    };
+    match project_fields(&value, &fields) {
+        None => Ok(value),
+        Some(Ok(projected)) => Ok(projected),
+        Some(Err(unknown)) => {
+            DEFERRED_EXIT_CODE.store(UNKNOWN_FIELDS_EXIT_CODE, Ordering::Release);
+            Err(unknown)
+        }
+    }
+}
+
+fn emit_unknown_fields(unknown: &UnknownFields, format: CliOutputFormat) {
+    DEFERRED_EXIT_CODE.store(UNKNOWN_FIELDS_EXIT_CODE, Ordering::Release);
+    let available = format!("available: {}", unknown.available_fields.join(", "));
+    emit_withheld(unknown, &unknown.message, &[available], format);
+}
+
+/// Tabular formats show one column per selected path, nested values
+/// included, in the order they were requested.
+fn emit_projected(projected: &serde_json::Value, fields: &ActiveFields, format: CliOutputFormat) {
+    if matches!(format, CliOutputFormat::Json | CliOutputFormat::Toon) {
+        emit_formatted(projected, format, || {});
+        return;
+    }
+    let headers: Vec<String> = fields.selection.paths().to_vec();
+    let rows: Vec<Vec<String>> = record_arrays(projected, fields.record_keys)
+        .into_iter()
+        .flatten()
+        .map(|record| {
+            headers
+                .iter()
+                .map(|path| {
+                    lookup_path(record, path)
+                        .map(tabular_cell)
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+    match format {
+        CliOutputFormat::Csv => emit_guarded(&render_csv(&headers, &rows), format),
+        CliOutputFormat::Markdown => emit_guarded(&render_markdown(&headers, &rows), format),
+        _ => emit_table_guarded(|| {
+            let upper: Vec<String> = headers.iter().map(|h| h.to_uppercase()).collect();
+            let mut table = CliTable::new(upper.iter().map(String::as_str).collect());
+            for row in rows {
+                table.add_row(row);
             }
+            table.render();
+        }),
+    }
+}
+
+// ── CSV / markdown rendering ────────────────────────────────────────────
+
+/// Flatten a payload into `(headers, rows)`.
+///
+/// Accepts an array of objects (columns in first-seen key order) or a single
+/// object whose values are all scalars (one row). Nested values inside a row
+/// are rendered as compact JSON. Returns `None` for anything else.
+fn tabular_rows(value: &serde_json::Value) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    let records: Vec<&serde_json::Map<String, serde_json::Value>> = match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(serde_json::Value::as_object)
+            .collect::<Option<_>>()?,
+        serde_json::Value::Object(map)
+            if map.values().all(|v| {
+                !matches!(
+                    v,
+                    serde_json::Value::Array(_) | serde_json::Value::Object(_)
+                )
+            }) =>
+        {
+            vec![map]
+        }
+        _ => return None,
+    };
+
+    let mut headers: Vec<String> = Vec::new();
+    for record in &records {
+        for key in record.keys() {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+    }
+    let rows = records
+        .iter()
+        .map(|record| {
+            headers
+                .iter()
+                .map(|key| record.get(key).map(tabular_cell).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    Some((headers, rows))
+}
+
+fn tabular_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Bool(_) | serde_json::Value::Number(_) => value.to_string(),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            serde_json::to_string(value).unwrap_or_default()
         }
     }
 }
 
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_csv(headers: &[String], rows: &[Vec<String>]) -> String {
+    if headers.is_empty() {
+        return String::new();
+    }
+    let line = |cells: &[String]| {
+        cells
+            .iter()
+            .map(|cell| csv_field(cell))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let mut lines = vec![line(headers)];
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+fn render_markdown(headers: &[String], rows: &[Vec<String>]) -> String {
+    if headers.is_empty() {
+        return String::new();
+    }
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = cells.iter().map(|cell| markdown_cell(cell)).collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![
+        line(headers),
+        format!("|{}", " --- |".repeat(headers.len())),
+    ];
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
 fn encode_json_pretty_or_error<T: Serialize>(data: &T) -> String {
     match serde_json::to_string_pretty(data) {
         Ok(json) => json,
@@ -304,7 +1095,22 @@ fn serialize_output_error_json(message: &str, pretty: bool) -> String {
 }
 
 /// Emit an empty result in the requested format.
+///
+/// List commands should prefer [`emit_empty_rows`] so CSV still carries a
+/// header row.
 pub fn emit_empty(format: CliOutputFormat, message: &str) {
+    emit_empty_rows(format, message, &[]);
+}
+
+/// Emit an empty list whose rows would have `columns`.
+///
+/// CSV prints just the header row, taken from the active `--fields`
+/// selection when there is one, so consumers see the same columns whether
+/// or not anything matched. Other formats behave like [`emit_empty`].
+pub fn emit_empty_rows(format: CliOutputFormat, message: &str, columns: &[&str]) {
+    if capture_payload(&serde_json::Value::Array(Vec::new())) {
+        return;
+    }
     match format {
         CliOutputFormat::Table => {
             ftui_runtime::ftui_println!("{message}");
@@ -315,11 +1121,27 @@ pub fn emit_empty(format: CliOutputFormat, message: &str) {
         CliOutputFormat::Toon => {
             ftui_runtime::ftui_println!("[]");
         }
+        CliOutputFormat::Csv => {
+            let headers: Vec<String> = active_field_selection().map_or_else(
+                || columns.iter().map(|column| (*column).to_string()).collect(),
+                |fields| fields.selection.paths().to_vec(),
+            );
+            let header = render_csv(&headers, &[]);
+            if !header.is_empty() {
+                ftui_runtime::ftui_println!("{header}");
+            }
+        }
+        CliOutputFormat::Markdown => {
+            ftui_runtime::ftui_println!("{message}");
+        }
     }
 }
 
 /// Output an "empty" message or empty JSON array.
 pub fn empty_result(json_mode: bool, message: &str) {
+    if capture_payload(&serde_json::Value::Array(Vec::new())) {
+        return;
+    }
     if json_mode {
         ftui_runtime::ftui_println!("[]");
     } else {
@@ -331,6 +1153,9 @@ pub fn empty_result(json_mode: bool, message: &str) {
 
 /// Print a success message with optional checkmark on TTY.
 pub fn success(msg: &str) {
+    if capture_message(msg) {
+        return;
+    }
     if is_tty() {
         ftui_runtime::ftui_println!("\x1b[32m✓\x1b[0m {msg}");
     } else {
@@ -340,6 +1165,9 @@ pub fn success(msg: &str) {
 
 /// Print an informational message to stderr.
 pub fn info(msg: &str) {
+    if capture_message(msg) {
+        return;
+    }
     if is_tty() {
         ftui_runtime::ftui_eprintln!("\x1b[34mi\x1b[0m {msg}");
     } else {
@@ -349,6 +1177,9 @@ pub fn info(msg: &str) {
 
 /// Print a warning message.
 pub fn warn(msg: &str) {
+    if capture_message(msg) {
+        return;
+    }
     if is_tty() {
         ftui_runtime::ftui_eprintln!("\x1b[33m!\x1b[0m {msg}");
     } else {
@@ -356,6 +1187,17 @@ pub fn warn(msg: &str) {
     }
 }
 
+/// Print a success message, or record `data` as the command's payload when
+/// output is being captured. For commands whose human output is a single
+/// status line but whose embedders need the structured result.
+pub fn success_with_payload<T: Serialize>(data: &T, msg: &str) {
+    if capture_payload(data) {
+        capture_message(msg);
+        return;
+    }
+    success(msg);
+}
+
 /// Print an error message.
 pub fn error(msg: &str) {
     if is_tty() {
@@ -374,6 +1216,60 @@ pub fn section(title: &str) {
     }
 }
 
+// ── Output capture ───────────────────────────────────────────────────────
+
+/// Payloads and status lines recorded instead of printed while a
+/// [`crate::CliRunner`] is running a command.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CapturedOutput {
+    /// Serialized payloads, in emission order (what `--json` would print).
+    pub payloads: Vec<serde_json::Value>,
+    /// Success, info, and warning lines.
+    pub messages: Vec<String>,
+}
+
+thread_local! {
+    static CAPTURE: RefCell<Option<CapturedOutput>> = const { RefCell::new(None) };
+}
+
+/// Start recording output on this thread, returning any capture already in
+/// progress so [`finish_capture`] can restore it.
+pub(crate) fn begin_capture() -> Option<CapturedOutput> {
+    CAPTURE.with(|cell| cell.replace(Some(CapturedOutput::default())))
+}
+
+/// Stop recording and return what was captured since [`begin_capture`].
+pub(crate) fn finish_capture(previous: Option<CapturedOutput>) -> CapturedOutput {
+    CAPTURE
+        .with(|cell| cell.replace(previous))
+        .unwrap_or_default()
+}
+
+fn capture_payload<T: Serialize>(data: &T) -> bool {
+    CAPTURE.with(|cell| {
+        let mut capture = cell.borrow_mut();
+        let Some(capture) = capture.as_mut() else {
+            return false;
+        };
+        capture.payloads.push(
+            serde_json::to_value(data)
+                .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() })),
+        );
+        true
+    })
+}
+
+fn capture_message(msg: &str) -> bool {
+    CAPTURE.with(|cell| {
+        let mut capture = cell.borrow_mut();
+        let Some(capture) = capture.as_mut() else {
+            return false;
+        };
+        capture.messages.push(msg.to_string());
+        true
+    })
+}
+
 // ── Key/value output ─────────────────────────────────────────────────────
 
 /// Print a key-value pair with aligned values.
@@ -1163,6 +2059,18 @@ mod tests {
             "TOON".parse::<CliOutputFormat>().unwrap(),
             CliOutputFormat::Toon
         );
+        assert_eq!(
+            "csv".parse::<CliOutputFormat>().unwrap(),
+            CliOutputFormat::Csv
+        );
+        assert_eq!(
+            "markdown".parse::<CliOutputFormat>().unwrap(),
+            CliOutputFormat::Markdown
+        );
+        assert_eq!(
+            "md".parse::<CliOutputFormat>().unwrap(),
+            CliOutputFormat::Markdown
+        );
     }
 
     #[test]
@@ -1177,6 +2085,8 @@ mod tests {
         assert_eq!(CliOutputFormat::Table.to_string(), "table");
         assert_eq!(CliOutputFormat::Json.to_string(), "json");
         assert_eq!(CliOutputFormat::Toon.to_string(), "toon");
+        assert_eq!(CliOutputFormat::Csv.to_string(), "csv");
+        assert_eq!(CliOutputFormat::Markdown.to_string(), "markdown");
     }
 
     #[test]
@@ -1242,6 +2152,56 @@ mod tests {
         // Just verify it produces output without crashing
     }
 
+    #[test]
+    fn emit_output_csv_quotes_commas_quotes_and_newlines() {
+        let data = serde_json::json!([
+            {"id": 1, "subject": "plain", "urgent": true},
+            {"id": 2, "subject": "a, b \"quoted\"\nsecond line", "extra": null},
+        ]);
+        let output = with_capture(|| {
+            emit_output(&data, CliOutputFormat::Csv, || {
+                panic!("table render should not be called");
+            });
+        });
+        assert_eq!(
+            output.trim_end(),
+            "id,subject,urgent,extra\n1,plain,true,\n2,\"a, b \"\"quoted\"\"\nsecond line\",,"
+        );
+    }
+
+    #[test]
+    fn emit_output_markdown_renders_gfm_table() {
+        let data = serde_json::json!([
+            {"name": "BlueLake", "task": "fix a|b\nthen c", "tags": ["x"]},
+        ]);
+        let output = with_capture(|| {
+            emit_output(&data, CliOutputFormat::Markdown, || {
+                panic!("table render should not be called");
+            });
+        });
+        assert_eq!(
+            output.trim_end(),
+            "| name | task | tags |\n| --- | --- | --- |\n| BlueLake | fix a\\|b<br>then c | [\"x\"] |"
+        );
+    }
+
+    #[test]
+    fn emit_output_tabular_formats_fall_back_to_json_for_nested_payloads() {
+        let data = serde_json::json!({"checks": [{"name": "db", "ok": true}], "ok": true});
+        for format in [CliOutputFormat::Csv, CliOutputFormat::Markdown] {
+            let output = with_capture(|| {
+                emit_output(&data, format, || {
+                    panic!("table render should not be called");
+                });
+            });
+            // The fallback warning goes to stderr; stdout carries the JSON.
+            let json_start = output.find('{').expect("json body");
+            let parsed: serde_json::Value =
+                serde_json::from_str(output[json_start..].trim()).expect("fallback json");
+            assert_eq!(parsed, data);
+        }
+    }
+
     #[test]
     fn emit_output_json_format_surfaces_serialization_errors() {
         struct FailingSerialize;
@@ -1304,6 +2264,27 @@ mod tests {
         assert_eq!(output.trim(), "[]");
     }
 
+    #[test]
+    fn emit_empty_csv_prints_nothing() {
+        let output = with_capture(|| {
+            emit_empty(CliOutputFormat::Csv, "No results");
+        });
+        assert!(output.is_empty(), "got: {output:?}");
+    }
+
+    #[test]
+    fn emit_empty_rows_csv_prints_the_header() {
+        let output = with_capture(|| {
+            emit_empty_rows(CliOutputFormat::Csv, "No results", &["id", "subject"]);
+        });
+        assert_eq!(output.trim_end(), "id,subject");
+
+        let output = with_capture(|| {
+            emit_empty_rows(CliOutputFormat::Table, "No results", &["id", "subject"]);
+        });
+        assert_eq!(output.trim(), "No results");
+    }
+
     #[test]
     fn emit_empty_table() {
         let output = with_capture(|| {
@@ -1311,4 +2292,348 @@ mod tests {
         });
         assert_eq!(output.trim(), "No results found.");
     }
+
+    // ── Output size guard ───────────────────────────────────────────────
+
+    fn capped(max_bytes: u64, command: &str, flags: &[&'static str]) -> OutputLimit {
+        OutputLimit {
+            max_bytes: Some(max_bytes),
+            command: command.to_string(),
+            narrowing_flags: flags.to_vec(),
+        }
+    }
+
+    #[test]
+    fn output_cap_allows_exactly_the_cap_and_rejects_one_more_byte() {
+        let limit = capped(100, "mail inbox", &["limit"]);
+        assert_eq!(check_output_size(&limit, 99), None);
+        assert_eq!(check_output_size(&limit, 100), None);
+        let overflow = check_output_size(&limit, 101).expect("over the cap");
+        assert_eq!(overflow.error, "output_too_large");
+        assert_eq!(overflow.actual_bytes, 101);
+        assert_eq!(overflow.max_bytes, 100);
+        assert_eq!(overflow.exit_code, OUTPUT_TOO_LARGE_EXIT_CODE);
+        assert_eq!(check_output_size(&OutputLimit::default(), u64::MAX), None);
+    }
+
+    #[test]
+    fn guarded_json_just_under_cap_prints_payload() {
+        let payload = encode_json_pretty_or_error(&vec!["alpha", "beta"]);
+        let limit = capped(payload.len() as u64, "mail inbox", &["limit"]);
+        let mut withheld = None;
+        let output = with_capture(|| {
+            withheld = emit_guarded_with(Some(&limit), &payload, CliOutputFormat::Json);
+        });
+        assert_eq!(withheld, None);
+        assert_eq!(output.trim(), payload);
+    }
+
+    #[test]
+    fn guarded_json_just_over_cap_prints_structured_error_instead() {
+        let payload = encode_json_pretty_or_error(&vec!["alpha", "beta"]);
+        let limit = capped(payload.len() as u64 - 1, "mail inbox", &["limit"]);
+        let mut withheld = None;
+        let output = with_capture(|| {
+            withheld = emit_guarded_with(Some(&limit), &payload, CliOutputFormat::Json);
+        });
+        assert!(withheld.is_some());
+        assert!(
+            !output.contains("alpha"),
+            "payload must be withheld: {output}"
+        );
+        let parsed: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(parsed["error"], "output_too_large");
+        assert_eq!(parsed["actual_bytes"], payload.len() as u64);
+        assert_eq!(parsed["max_bytes"], payload.len() as u64 - 1);
+        assert_eq!(parsed["command"], "mail inbox");
+        assert_eq!(parsed["exit_code"], OUTPUT_TOO_LARGE_EXIT_CODE);
+    }
+
+    #[test]
+    fn guarded_toon_over_cap_prints_error_as_toon() {
+        let limit = capped(8, "robot inbox", &["limit"]);
+        let output = with_capture(|| {
+            emit_guarded_with(Some(&limit), "messages[3]: a,b,c", CliOutputFormat::Toon);
+        });
+        assert!(output.contains("output_too_large"), "{output}");
+        assert!(!output.contains("messages[3]"), "{output}");
+        assert!(serde_json::from_str::<serde_json::Value>(output.trim()).is_err());
+    }
+
+    #[test]
+    fn guarded_table_is_measured_before_printing() {
+        let render = || {
+            let mut table = CliTable::new(vec!["ID", "SUBJECT"]);
+            for id in 0..50 {
+                table.add_row(vec![id.to_string(), format!("subject line {id}")]);
+            }
+            table.render();
+        };
+        let _g = CAPTURE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let withheld = emit_table_guarded_with(Some(&capped(64, "mail inbox", &[])), render)
+            .expect("a 50-row table exceeds 64 bytes");
+        assert!(withheld.actual_bytes > 64);
+        assert_eq!(withheld.max_bytes, 64);
+        assert_eq!(
+            emit_table_guarded_with(Some(&capped(1 << 20, "mail inbox", &[])), render),
+            None
+        );
+    }
+
+    #[test]
+    fn suggestions_follow_the_command_family() {
+        let listing = narrowing_suggestions(
+            &capped(10, "file_reservations active", &["limit", "count-only"]),
+            4096,
+        );
+        assert_eq!(
+            listing,
+            vec![
+                "add --limit N to `am file_reservations active` to return fewer rows",
+                "add --count-only to `am file_reservations active` to get totals instead of rows",
+                "or raise the cap: --max-output-bytes 4096 (0 disables; also AM_MAX_OUTPUT_BYTES)",
+            ]
+        );
+
+        let robot = narrowing_suggestions(&capped(10, "robot thread", &["limit"]), 20);
+        assert_eq!(robot.len(), 2);
+        assert!(robot[0].contains("--limit N to `am robot thread`"));
+        assert!(!robot.iter().any(|s| s.contains("--count-only")));
+
+        let projection =
+            narrowing_suggestions(&capped(10, "agents list", &["count-only", "fields"]), 20);
+        assert!(projection[0].contains("--count-only"));
+        assert!(projection[1].contains("add --fields to `am agents list`"));
+
+        let unfiltered = narrowing_suggestions(&capped(10, "doctor check", &[]), 20);
+        assert_eq!(
+            unfiltered[0],
+            "narrow `am doctor check` with its filters (see `am doctor check --help`)"
+        );
+    }
+
+    #[test]
+    fn max_output_bytes_resolution_precedence() {
+        assert_eq!(resolve_max_output_bytes(None, None, false), Ok(None));
+        assert_eq!(
+            resolve_max_output_bytes(None, None, true),
+            Ok(Some(ROBOT_DEFAULT_MAX_OUTPUT_BYTES))
+        );
+        assert_eq!(
+            resolve_max_output_bytes(None, Some(" 2048 "), false),
+            Ok(Some(2048))
+        );
+        assert_eq!(
+            resolve_max_output_bytes(Some(512), Some("2048"), true),
+            Ok(Some(512))
+        );
+        assert_eq!(resolve_max_output_bytes(Some(0), None, true), Ok(None));
+        assert_eq!(resolve_max_output_bytes(None, Some("0"), true), Ok(None));
+        assert_eq!(
+            resolve_max_output_bytes(None, Some(""), true),
+            Ok(Some(ROBOT_DEFAULT_MAX_OUTPUT_BYTES))
+        );
+        let err = resolve_max_output_bytes(None, Some("1MB"), false).unwrap_err();
+        assert!(err.contains("AM_MAX_OUTPUT_BYTES=1MB"), "{err}");
+    }
+
+    // ── Field selection ─────────────────────────────────────────────────
+
+    fn selecting(command: &str, raw: &str) -> ActiveFields {
+        field_selection_for(command, Some(FieldSelection::parse(raw).unwrap()))
+            .unwrap()
+            .expect("command accepts --fields")
+    }
+
+    fn message_records() -> serde_json::Value {
+        serde_json::json!([
+            {
+                "id": 7,
+                "subject": "Deploy",
+                "sender": {"name": "BlueLake", "program": "codex-cli"},
+                "importance": "high",
+            },
+            {
+                "id": 8,
+                "subject": "Review",
+                "sender": {"name": "RedFox", "program": "claude-code"},
+                "importance": "normal",
+            },
+        ])
+    }
+
+    fn projected(value: &serde_json::Value, fields: &ActiveFields) -> String {
+        let projected = project_fields(value, fields)
+            .expect("records to project")
+            .expect("known fields");
+        serde_json::to_string(&projected).unwrap()
+    }
+
+    #[test]
+    fn field_selection_parses_comma_separated_dotted_paths() {
+        let selection = FieldSelection::parse(" subject ,sender.name,,subject,id").unwrap();
+        assert_eq!(selection.paths(), ["subject", "sender.name", "id"]);
+        for bad in ["", ",", "sender.", ".id", "a..b"] {
+            assert!(FieldSelection::parse(bad).is_err(), "{bad:?} should fail");
+        }
+    }
+
+    #[test]
+    fn fields_keep_requested_paths_in_requested_order() {
+        let fields = selecting("mail search", "subject,sender.name,id");
+        assert_eq!(
+            projected(&message_records(), &fields),
+            r#"[{"subject":"Deploy","sender":{"name":"BlueLake"},"id":7},{"subject":"Review","sender":{"name":"RedFox"},"id":8}]"#
+        );
+
+        let fields = selecting("mail search", "sender,sender.name");
+        assert_eq!(
+            projected(&message_records(), &fields),
+            r#"[{"sender":{"name":"BlueLake","program":"codex-cli"}},{"sender":{"name":"RedFox","program":"claude-code"}}]"#
+        );
+    }
+
+    #[test]
+    fn fields_leave_cursor_and_envelope_keys_alone() {
+        let page = serde_json::json!({"cursor": 8, "messages": message_records()});
+        assert_eq!(
+            projected(&page, &selecting("mail inbox", "id")),
+            r#"{"cursor":8,"messages":[{"id":7},{"id":8}]}"#
+        );
+
+        let envelope = serde_json::json!({
+            "_meta": {"command": "robot inbox", "format": "json"},
+            "_alerts": [{"severity": "warn", "summary": "1 urgent"}],
+            "count": 2,
+            "inbox": message_records(),
+            "watermark": 8,
+        });
+        assert_eq!(
+            projected(&envelope, &selecting("robot inbox", "id,importance")),
+            r#"{"_meta":{"command":"robot inbox","format":"json"},"_alerts":[{"severity":"warn","summary":"1 urgent"}],"count":2,"inbox":[{"id":7,"importance":"high"},{"id":8,"importance":"normal"}],"watermark":8}"#
+        );
+    }
+
+    #[test]
+    fn unknown_fields_list_every_available_path() {
+        let fields = selecting("mail search", "id,title,sender.email");
+        let unknown = project_fields(&message_records(), &fields)
+            .expect("records to project")
+            .unwrap_err();
+        assert_eq!(unknown.error, "unknown_fields");
+        assert_eq!(
+            unknown.message,
+            "unknown field(s) for `am mail search`: title, sender.email"
+        );
+        assert_eq!(unknown.unknown_fields, ["title", "sender.email"]);
+        assert_eq!(
+            unknown.available_fields,
+            [
+                "id",
+                "importance",
+                "sender",
+                "sender.name",
+                "sender.program",
+                "subject"
+            ]
+        );
+        assert_eq!(unknown.exit_code, UNKNOWN_FIELDS_EXIT_CODE);
+    }
+
+    #[test]
+    fn fields_skip_payloads_without_records() {
+        let fields = selecting("mail inbox", "nope");
+        assert_eq!(project_fields(&serde_json::json!([]), &fields), None);
+        assert_eq!(
+            project_fields(&serde_json::json!({"total": 3, "unread": 1}), &fields),
+            None
+        );
+        assert_eq!(
+            project_fields(&serde_json::json!({"cursor": 4, "messages": []}), &fields),
+            None
+        );
+    }
+
+    #[test]
+    fn fields_render_selected_columns_in_every_format() {
+        let fields = selecting("mail search", "id,sender.name");
+        let records = message_records();
+
+        let output = with_capture(|| {
+            emit_output_with(Some(&fields), &records, CliOutputFormat::Json, || {
+                panic!("table render should not run");
+            });
+        });
+        assert_eq!(
+            output.trim(),
+            "[\n  {\n    \"id\": 7,\n    \"sender\": {\n      \"name\": \"BlueLake\"\n    }\n  },\n  {\n    \"id\": 8,\n    \"sender\": {\n      \"name\": \"RedFox\"\n    }\n  }\n]"
+        );
+
+        let output = with_capture(|| {
+            emit_output_with(Some(&fields), &records, CliOutputFormat::Csv, || {});
+        });
+        assert_eq!(output.trim(), "id,sender.name\n7,BlueLake\n8,RedFox");
+
+        let output = with_capture(|| {
+            emit_output_with(Some(&fields), &records, CliOutputFormat::Markdown, || {});
+        });
+        assert_eq!(
+            output.trim(),
+            "| id | sender.name |\n| --- | --- |\n| 7 | BlueLake |\n| 8 | RedFox |"
+        );
+
+        let output = with_capture(|| {
+            emit_output_with(Some(&fields), &records, CliOutputFormat::Table, || {
+                panic!("projected tables replace the command's own table");
+            });
+        });
+        assert!(output.contains("SENDER.NAME"), "{output}");
+        assert!(output.contains("BlueLake"), "{output}");
+        assert!(!output.contains("Deploy"), "{output}");
+    }
+
+    #[test]
+    fn unknown_fields_replace_json_payload_with_structured_error() {
+        let fields = selecting("agents list", "name,colour");
+        let records = serde_json::json!([{"name": "BlueLake", "program": "codex-cli"}]);
+        let output = with_capture(|| {
+            emit_output_with(Some(&fields), &records, CliOutputFormat::Json, || {});
+        });
+        let parsed: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({
+                "error": "unknown_fields",
+                "message": "unknown field(s) for `am agents list`: colour",
+                "command": "agents list",
+                "unknown_fields": ["colour"],
+                "available_fields": ["name", "program"],
+                "exit_code": 2,
+            })
+        );
+    }
+
+    #[test]
+    fn capture_records_payloads_and_messages_instead_of_printing() {
+        let previous = begin_capture();
+        emit_output(
+            &serde_json::json!({ "id": 7 }),
+            CliOutputFormat::Table,
+            || panic!("table renderer must not run while capturing"),
+        );
+        emit_empty(CliOutputFormat::Json, "No messages.");
+        success_with_payload(&serde_json::json!({ "released": 1 }), "Released 1.");
+        warn("heads up");
+        let captured = finish_capture(previous);
+        assert_eq!(
+            captured.payloads,
+            vec![
+                serde_json::json!({ "id": 7 }),
+                serde_json::json!([]),
+                serde_json::json!({ "released": 1 }),
+            ]
+        );
+        assert_eq!(captured.messages, vec!["Released 1.", "heads up"]);
+        assert!(!capture_message("not captured"));
+    }
 }
@@ -0,0 +1,515 @@
+//! Named connection profiles for `am --profile <name>` and `am profile ...`.
+//!
+//! A profile is a small TOML file under `~/.config/agent-mail/profiles/`
+//! holding the settings people otherwise flip by hand when moving between
+//! servers: HTTP host/port/path, a bearer-token *reference*, and the
+//! `storage_root`/`database_url` used by direct (serverless) commands.
+//!
+//! Selection order: `--profile` > `AM_PROFILE` > the default marker written by
+//! `am profile use`. The selected profile is installed as a config layer that
+//! beats env vars and env files, while command flags still beat the profile.
+//! Tokens are never stored in the profile itself: `token_command` runs a
+//! command (e.g. a keychain lookup) whose stdout is the token, and
+//! `token_env` names an environment variable to read it from. The command
+//! only runs the first time a command actually talks to a server (see
+//! [`profile_bearer_token`]), so offline commands and shell completion never
+//! pay for a keychain prompt.
+
+#![forbid(unsafe_code)]
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::{CliError, CliResult};
+
+/// Environment variable that selects a profile when `--profile` is absent.
+pub const PROFILE_ENV_VAR: &str = "AM_PROFILE";
+
+/// File inside the profiles directory naming the default profile.
+const DEFAULT_MARKER_FILE: &str = ".default";
+
+static ACTIVE_PROFILE: Mutex<Option<ActiveProfile>> = Mutex::new(None);
+
+/// `token_command` of the active profile, run on first use.
+static ACTIVE_TOKEN_COMMAND: Mutex<Option<String>> = Mutex::new(None);
+
+/// Result of running [`ACTIVE_TOKEN_COMMAND`], cached for the process.
+static RESOLVED_PROFILE_TOKEN: OnceLock<Result<String, String>> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionProfile {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub path: Option<String>,
+    /// Command whose trimmed stdout is the bearer token.
+    pub token_command: Option<String>,
+    /// Environment variable holding the bearer token.
+    pub token_env: Option<String>,
+    pub storage_root: Option<PathBuf>,
+    pub database_url: Option<String>,
+}
+
+/// How the active profile was chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileSelection {
+    Flag,
+    Env,
+    Default,
+}
+
+impl ProfileSelection {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Flag => "--profile",
+            Self::Env => PROFILE_ENV_VAR,
+            Self::Default => "default",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveProfile {
+    pub name: String,
+    pub selection: ProfileSelection,
+}
+
+impl ConnectionProfile {
+    /// Parse a profile file. Unknown keys are rejected so a typo does not
+    /// silently fall back to env/default settings.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let doc = text
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| format!("invalid TOML: {e}"))?;
+        let mut profile = Self::default();
+        for (key, item) in doc.iter() {
+            let text_value = || {
+                item.as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| format!("`{key}` must be a string"))
+            };
+            match key {
+                "host" => profile.host = Some(text_value()?),
+                "port" => {
+                    let port = item
+                        .as_integer()
+                        .and_then(|port| u16::try_from(port).ok())
+                        .ok_or_else(|| "`port` must be an integer in 0..=65535".to_string())?;
+                    profile.port = Some(port);
+                }
+                "path" => profile.path = Some(text_value()?),
+                "token_command" => profile.token_command = Some(text_value()?),
+                "token_env" => profile.token_env = Some(text_value()?),
+                "storage_root" => profile.storage_root = Some(PathBuf::from(text_value()?)),
+                "database_url" => profile.database_url = Some(text_value()?),
+                "token" | "bearer_token" => {
+                    return Err(format!(
+                        "`{key}` is not supported; reference the secret with token_command \
+                         or token_env instead of storing it in plaintext"
+                    ));
+                }
+                other => return Err(format!("unknown key `{other}`")),
+            }
+        }
+        if profile.token_command.is_some() && profile.token_env.is_some() {
+            return Err("set only one of token_command and token_env".to_string());
+        }
+        Ok(profile)
+    }
+
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let mut doc = toml_edit::DocumentMut::new();
+        if let Some(host) = &self.host {
+            doc["host"] = toml_edit::value(host.as_str());
+        }
+        if let Some(port) = self.port {
+            doc["port"] = toml_edit::value(i64::from(port));
+        }
+        if let Some(path) = &self.path {
+            doc["path"] = toml_edit::value(path.as_str());
+        }
+        if let Some(command) = &self.token_command {
+            doc["token_command"] = toml_edit::value(command.as_str());
+        }
+        if let Some(var) = &self.token_env {
+            doc["token_env"] = toml_edit::value(var.as_str());
+        }
+        if let Some(root) = &self.storage_root {
+            doc["storage_root"] = toml_edit::value(root.display().to_string());
+        }
+        if let Some(url) = &self.database_url {
+            doc["database_url"] = toml_edit::value(url.as_str());
+        }
+        doc.to_string()
+    }
+
+    /// Env-style settings this profile overrides. A `token_env` reference is
+    /// resolved here; `token_command` is left to [`profile_bearer_token`].
+    pub fn env_values(&self) -> CliResult<HashMap<String, String>> {
+        let mut values = HashMap::new();
+        if let Some(host) = &self.host {
+            values.insert("HTTP_HOST".to_string(), host.clone());
+        }
+        if let Some(port) = self.port {
+            values.insert("HTTP_PORT".to_string(), port.to_string());
+        }
+        if let Some(path) = &self.path {
+            values.insert("HTTP_PATH".to_string(), path.clone());
+        }
+        if let Some(token) = self.resolve_token_env()? {
+            values.insert("HTTP_BEARER_TOKEN".to_string(), token);
+        }
+        if let Some(root) = &self.storage_root {
+            values.insert("STORAGE_ROOT".to_string(), root.display().to_string());
+        }
+        if let Some(url) = &self.database_url {
+            values.insert("DATABASE_URL".to_string(), url.clone());
+        }
+        Ok(values)
+    }
+
+    fn resolve_token_env(&self) -> CliResult<Option<String>> {
+        let Some(var) = &self.token_env else {
+            return Ok(None);
+        };
+        std::env::var(var).map(Some).map_err(|_| {
+            CliError::InvalidArgument(format!(
+                "profile token_env `{var}` is not set in the environment"
+            ))
+        })
+    }
+}
+
+fn run_token_command(command: &str) -> Result<String, String> {
+    let output = shell_command(command)
+        .output()
+        .map_err(|e| format!("failed to run profile token_command: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "profile token_command exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        return Err("profile token_command printed an empty token".to_string());
+    }
+    Ok(token)
+}
+
+fn shell_command(command: &str) -> std::process::Command {
+    if cfg!(windows) {
+        let mut cmd = std::process::Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+/// `~/.config/agent-mail/profiles`.
+pub fn profiles_dir() -> CliResult<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(".config").join("agent-mail").join("profiles"))
+        .ok_or_else(|| CliError::Other("cannot locate home directory for profiles".to_string()))
+}
+
+pub fn validate_profile_name(name: &str) -> CliResult<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(CliError::InvalidArgument(format!(
+            "invalid profile name '{name}': use 1-64 letters, digits, '-' or '_'"
+        )))
+    }
+}
+
+fn profile_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.toml"))
+}
+
+/// Profile names in `dir`, sorted.
+pub fn list_profiles(dir: &Path) -> CliResult<Vec<String>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+                return None;
+            }
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|stem| validate_profile_name(stem).is_ok())
+                .map(str::to_string)
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+pub fn load_profile(dir: &Path, name: &str) -> CliResult<ConnectionProfile> {
+    validate_profile_name(name)?;
+    let path = profile_path(dir, name);
+    let text = match std::fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let available = list_profiles(dir)?;
+            let hint = if available.is_empty() {
+                "no profiles exist yet; create one with `am profile add <name>`".to_string()
+            } else {
+                format!("available: {}", available.join(", "))
+            };
+            return Err(CliError::InvalidArgument(format!(
+                "profile '{name}' not found in {} ({hint})",
+                dir.display()
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    ConnectionProfile::parse(&text)
+        .map_err(|e| CliError::InvalidArgument(format!("{}: {e}", path.display())))
+}
+
+pub fn save_profile(
+    dir: &Path,
+    name: &str,
+    profile: &ConnectionProfile,
+    overwrite: bool,
+) -> CliResult<PathBuf> {
+    validate_profile_name(name)?;
+    let path = profile_path(dir, name);
+    if !overwrite && path.exists() {
+        return Err(CliError::InvalidArgument(format!(
+            "profile '{name}' already exists; pass --force to replace it"
+        )));
+    }
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, profile.to_toml())?;
+    Ok(path)
+}
+
+/// Delete a profile; clears the default marker when it named this profile.
+pub fn remove_profile(dir: &Path, name: &str) -> CliResult<()> {
+    load_profile(dir, name)?;
+    std::fs::remove_file(profile_path(dir, name))?;
+    if default_profile(dir).as_deref() == Some(name) {
+        set_default_profile(dir, None)?;
+    }
+    Ok(())
+}
+
+#[must_use]
+pub fn default_profile(dir: &Path) -> Option<String> {
+    std::fs::read_to_string(dir.join(DEFAULT_MARKER_FILE))
+        .ok()
+        .map(|text| text.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+pub fn set_default_profile(dir: &Path, name: Option<&str>) -> CliResult<()> {
+    let marker = dir.join(DEFAULT_MARKER_FILE);
+    match name {
+        Some(name) => {
+            load_profile(dir, name)?;
+            std::fs::write(marker, format!("{name}\n"))?;
+        }
+        None => match std::fs::remove_file(marker) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
+
+/// Pick the profile name: `--profile` > `AM_PROFILE` > default marker.
+#[must_use]
+pub fn select_profile(dir: &Path, flag: Option<&str>, env: Option<&str>) -> Option<ActiveProfile> {
+    let non_empty = |value: Option<&str>| value.map(str::trim).filter(|v| !v.is_empty());
+    if let Some(name) = non_empty(flag) {
+        return Some(ActiveProfile {
+            name: name.to_string(),
+            selection: ProfileSelection::Flag,
+        });
+    }
+    if let Some(name) = non_empty(env) {
+        return Some(ActiveProfile {
+            name: name.to_string(),
+            selection: ProfileSelection::Env,
+        });
+    }
+    default_profile(dir).map(|name| ActiveProfile {
+        name,
+        selection: ProfileSelection::Default,
+    })
+}
+
+/// Select, load, and install the profile for this invocation.
+pub fn activate(flag: Option<&str>) -> CliResult<Option<ActiveProfile>> {
+    let env = std::env::var(PROFILE_ENV_VAR).ok();
+    if flag.is_none() && env.is_none() && dirs::home_dir().is_none() {
+        return Ok(None);
+    }
+    let dir = profiles_dir()?;
+    let Some(active) = select_profile(&dir, flag, env.as_deref()) else {
+        return Ok(None);
+    };
+    let profile = load_profile(&dir, &active.name)?;
+    mcp_agent_mail_core::config::set_profile_env_values(profile.env_values()?);
+    *ACTIVE_TOKEN_COMMAND
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = profile.token_command;
+    *ACTIVE_PROFILE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(active.clone());
+    Ok(Some(active))
+}
+
+/// The profile installed by [`activate`] for this process, if any.
+#[must_use]
+pub fn active_profile() -> Option<ActiveProfile> {
+    ACTIVE_PROFILE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+}
+
+/// Bearer token from the active profile's `token_command`.
+///
+/// Called where an HTTP client picks its credentials. The command runs at most
+/// once per process; later calls (including a failure) reuse the first result.
+pub fn profile_bearer_token() -> CliResult<Option<String>> {
+    let Some(command) = ACTIVE_TOKEN_COMMAND
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+    else {
+        return Ok(None);
+    };
+    RESOLVED_PROFILE_TOKEN
+        .get_or_init(|| run_token_command(&command))
+        .clone()
+        .map(Some)
+        .map_err(CliError::Other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work_profile() -> ConnectionProfile {
+        ConnectionProfile {
+            host: Some("10.0.0.5".to_string()),
+            port: Some(9900),
+            path: Some("/mcp/".to_string()),
+            token_command: Some("printf 'tok-123\\n'".to_string()),
+            token_env: None,
+            storage_root: Some(PathBuf::from("/srv/agent-mail")),
+            database_url: None,
+        }
+    }
+
+    #[test]
+    fn profile_round_trips_through_toml() {
+        let profile = work_profile();
+        let parsed = ConnectionProfile::parse(&profile.to_toml()).expect("parse");
+        assert_eq!(parsed, profile);
+    }
+
+    #[test]
+    fn parse_rejects_plaintext_tokens_and_unknown_keys() {
+        let err = ConnectionProfile::parse("token = \"abc\"").expect_err("plaintext token");
+        assert!(err.contains("token_command"), "{err}");
+        let err = ConnectionProfile::parse("hots = \"x\"").expect_err("typo");
+        assert!(err.contains("unknown key `hots`"), "{err}");
+        let err = ConnectionProfile::parse("port = 70000").expect_err("port range");
+        assert!(err.contains("port"), "{err}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn env_values_leave_token_command_for_first_use() {
+        let values = work_profile().env_values().expect("env values");
+        assert_eq!(values["HTTP_HOST"], "10.0.0.5");
+        assert_eq!(values["HTTP_PORT"], "9900");
+        assert!(!values.contains_key("HTTP_BEARER_TOKEN"));
+        assert_eq!(values["STORAGE_ROOT"], "/srv/agent-mail");
+        assert!(!values.contains_key("DATABASE_URL"));
+        assert_eq!(
+            run_token_command("printf 'tok-123\\n'").as_deref(),
+            Ok("tok-123")
+        );
+        let err = run_token_command("exit 3").expect_err("failing command");
+        assert!(err.contains("exited with"), "{err}");
+    }
+
+    #[test]
+    fn missing_profile_error_lists_available_names() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let err = load_profile(dir.path(), "work").expect_err("no profiles");
+        assert!(err.to_string().contains("am profile add"), "{err}");
+
+        save_profile(dir.path(), "team", &ConnectionProfile::default(), false).expect("save");
+        save_profile(dir.path(), "laptop", &ConnectionProfile::default(), false).expect("save");
+        let err = load_profile(dir.path(), "work").expect_err("missing");
+        assert!(
+            err.to_string().contains("available: laptop, team"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn selection_prefers_flag_then_env_then_default_marker() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        save_profile(dir.path(), "team", &ConnectionProfile::default(), false).expect("save");
+        assert_eq!(select_profile(dir.path(), None, None), None);
+
+        set_default_profile(dir.path(), Some("team")).expect("use");
+        let selected = select_profile(dir.path(), None, None).expect("default");
+        assert_eq!(selected.name, "team");
+        assert_eq!(selected.selection, ProfileSelection::Default);
+
+        let selected = select_profile(dir.path(), None, Some("laptop")).expect("env");
+        assert_eq!(selected.name, "laptop");
+        assert_eq!(selected.selection, ProfileSelection::Env);
+
+        let selected = select_profile(dir.path(), Some("work"), Some("laptop")).expect("flag");
+        assert_eq!(selected.name, "work");
+        assert_eq!(selected.selection, ProfileSelection::Flag);
+    }
+
+    #[test]
+    fn removing_the_default_profile_clears_the_marker() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        save_profile(dir.path(), "team", &ConnectionProfile::default(), false).expect("save");
+        set_default_profile(dir.path(), Some("team")).expect("use");
+        remove_profile(dir.path(), "team").expect("remove");
+        assert_eq!(default_profile(dir.path()), None);
+        assert!(list_profiles(dir.path()).expect("list").is_empty());
+    }
+
+    #[test]
+    fn save_refuses_to_overwrite_without_force_and_validates_names() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        save_profile(dir.path(), "team", &ConnectionProfile::default(), false).expect("save");
+        assert!(save_profile(dir.path(), "team", &ConnectionProfile::default(), false).is_err());
+        assert!(save_profile(dir.path(), "team", &ConnectionProfile::default(), true).is_ok());
+        assert!(validate_profile_name("../etc").is_err());
+        assert!(validate_profile_name("").is_err());
+    }
+}
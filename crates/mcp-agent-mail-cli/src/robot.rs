@@ -12,7 +12,7 @@ use fastmcp::McpErrorCode;
 use fastmcp::prelude::{McpContext, McpError, McpResult};
 use mcp_agent_mail_core::{
     AgentHealthGrade, AgentHealthInputs, AgentHealthMetric, AgentHealthScorecard,
-    AtcCanaryReportSummary, TailLatencyPhaseLedger, TailLatencyPhaseRecorder,
+    AtcCanaryReportSummary, HealthLevel, TailLatencyPhaseLedger, TailLatencyPhaseRecorder,
     append_tail_latency_evidence_if_configured, compute_agent_health,
     load_latest_atc_canary_report,
 };
@@ -879,10 +879,21 @@ fn serialize_envelope_with_format<T: Serialize>(
         _remediation: envelope._remediation.as_ref(),
         data: &envelope.data,
     };
+    if crate::output::active_field_selection().is_some() {
+        let value = serde_json::to_value(&view).map_err(|e| CliError::Format(e.to_string()))?;
+        return match crate::output::apply_field_selection(value) {
+            Ok(projected) => encode_envelope_json(&projected, pretty),
+            Err(unknown) => encode_envelope_json(&unknown, pretty),
+        };
+    }
+    encode_envelope_json(&view, pretty)
+}
+
+fn encode_envelope_json<T: Serialize>(value: &T, pretty: bool) -> Result<String, CliError> {
     if pretty {
-        serde_json::to_string_pretty(&view).map_err(|e| CliError::Format(e.to_string()))
+        serde_json::to_string_pretty(value).map_err(|e| CliError::Format(e.to_string()))
     } else {
-        serde_json::to_string(&view).map_err(|e| CliError::Format(e.to_string()))
+        serde_json::to_string(value).map_err(|e| CliError::Format(e.to_string()))
     }
 }
 
@@ -912,7 +923,8 @@ pub fn format_output_md<T: Serialize + MarkdownRenderable>(
     envelope: &RobotEnvelope<T>,
     format: OutputFormat,
 ) -> Result<String, CliError> {
-    if format == OutputFormat::Markdown {
+    // Markdown renders whole records, so `--fields` takes the TOON fallback.
+    if format == OutputFormat::Markdown && crate::output::active_field_selection().is_none() {
         return Ok(envelope.data.to_markdown(
             &envelope._meta,
             &envelope._alerts,
@@ -972,6 +984,24 @@ pub struct StatusData {
     pub reservations_expiring_soon: usize,
     pub active_agents: usize,
     pub recent_messages: usize,
+    /// Backpressure level from `compute_health_level`, independent of the
+    /// mailbox-level `health` above.
+    pub health_level: HealthLevel,
+    /// Whether Red-level load shedding is configured.
+    pub shedding_enabled: bool,
+    /// A safe-mode (read-only emergency) server is serving this storage root.
+    pub safe_mode: bool,
+    /// Newest unread messages for the agent; null when no agent is resolved.
+    pub top_unread: Option<Vec<StatusUnreadMessage>>,
+    /// IDs of messages still awaiting the agent's ack, oldest first; null when
+    /// no agent is resolved.
+    pub pending_ack_ids: Option<Vec<i64>>,
+    /// Active reservations held by the agent; null when no agent is resolved.
+    pub held_reservations: Option<Vec<HeldReservation>>,
+    /// What each agent says it is working on and how long ago it said so,
+    /// most recently active first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub agent_tasks: Vec<StatusAgentTask>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub my_reservations: Vec<ReservationEntry>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -994,6 +1024,36 @@ pub struct StatusData {
     pub forensic_timeline: Option<crate::ForensicTimelineReport>,
 }
 
+/// Unread message summary in `robot status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusUnreadMessage {
+    pub id: i64,
+    pub from: String,
+    pub subject: String,
+    pub importance: String,
+    pub created_at: String,
+}
+
+/// An agent's current task in `robot status`, with its freshness.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusAgentTask {
+    pub agent: String,
+    pub task: String,
+    /// How long ago the task was set, e.g. "3h ago".
+    pub task_set: String,
+    pub task_age_seconds: i64,
+}
+
+/// Reservation held by the status agent, with its absolute expiry.
+#[derive(Debug, Clone, Serialize)]
+pub struct HeldReservation {
+    pub id: i64,
+    pub path: String,
+    pub exclusive: bool,
+    pub expires_at: String,
+    pub remaining_seconds: i64,
+}
+
 /// Recovery state surfaced in `robot status` when the mailbox is degraded or recovering.
 #[derive(Debug, Clone, Serialize)]
 pub struct RecoveryStatus {
@@ -1784,6 +1844,11 @@ pub struct AgentRow {
     pub last_active: String,
     pub msg_count: usize,
     pub status: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub task: String,
+    /// How long ago the task was set, so stale tasks read as stale.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_set: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub health: Option<AgentHealthView>,
 }
@@ -2554,6 +2619,10 @@ pub enum RobotSubcommand {
         /// Include message bodies in output.
         #[arg(long)]
         include_bodies: bool,
+        /// Only messages whose id is greater than this watermark. Feed back
+        /// `watermark` from the previous response to poll without gaps.
+        #[arg(long, conflicts_with = "ack_overdue")]
+        after_watermark: Option<i64>,
     },
 
     /// Events since last check with temporal filters.
@@ -2624,9 +2693,9 @@ pub enum RobotSubcommand {
         /// Show only conflicting reservations.
         #[arg(long)]
         conflicts: bool,
-        /// Warn about reservations expiring within N minutes.
-        #[arg(long)]
-        expiring: Option<u32>,
+        /// Warn about reservations expiring within this window (e.g. 15m).
+        #[arg(long, value_parser = crate::duration_arg::minutes)]
+        expiring: Option<std::time::Duration>,
     },
 
     /// Tool performance summary (calls, errors, error%, latency percentiles).
@@ -2688,17 +2757,22 @@ pub enum RobotSubcommand {
         limit: Option<usize>,
     },
 
+    /// Prioritized TODO for the agent: overdue acks, urgent unread mail,
+    /// expiring reservations, unread mail, then pending contact requests, each
+    /// with a ready-to-call tool invocation.
+    NextActions,
+
     /// Stale bead ownership handoff dashboard with dry-run reopen recommendations.
     Handoff {
-        /// Consider in-progress beads stale after this many minutes without updates.
-        #[arg(long, default_value_t = 12 * 60)]
-        stale_minutes: u32,
-        /// Consider registered agents active when seen within this many minutes.
-        #[arg(long, default_value_t = 30)]
-        active_minutes: u32,
-        /// Treat comments within this many minutes as recent work.
-        #[arg(long, default_value_t = 24 * 60)]
-        fresh_comment_minutes: u32,
+        /// Consider in-progress beads stale after this long without updates.
+        #[arg(long, default_value = "12h", value_parser = crate::duration_arg::minutes)]
+        stale_minutes: std::time::Duration,
+        /// Consider registered agents active when seen within this window.
+        #[arg(long, default_value = "30m", value_parser = crate::duration_arg::minutes)]
+        active_minutes: std::time::Duration,
+        /// Treat comments within this window as recent work.
+        #[arg(long, default_value = "24h", value_parser = crate::duration_arg::minutes)]
+        fresh_comment_minutes: std::time::Duration,
         /// Include non-stale/keep records instead of only actionable handoffs.
         #[arg(long)]
         include_fresh: bool,
@@ -2746,6 +2820,7 @@ impl RobotSubcommand {
             Self::Projects => "robot projects",
             Self::Attachments => "robot attachments",
             Self::Atc { .. } => "robot atc",
+            Self::NextActions => "robot next-actions",
             Self::Handoff { .. } => "robot handoff",
         }
     }
@@ -3503,7 +3578,7 @@ fn resolve_robot_attachments_project_scope(
 }
 
 /// Format seconds into human-readable relative time.
-fn format_age(seconds: i64) -> String {
+pub(crate) fn format_age(seconds: i64) -> String {
     if seconds < 0 {
         return "just now".to_string();
     }
@@ -3532,6 +3607,10 @@ const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
 const ACK_OVERDUE_THRESHOLD_US: i64 = 30 * MICROS_PER_MINUTE;
 const ACK_SLA_VIOLATION_THRESHOLD_US: i64 = MICROS_PER_HOUR;
 
+const STATUS_TOP_UNREAD_LIMIT: i64 = 5;
+const STATUS_PENDING_ACK_ID_LIMIT: i64 = 50;
+const STATUS_AGENT_TASK_LIMIT: i64 = 10;
+
 /// Freshness budget for coalesced robot status/overview snapshots.
 ///
 /// The generation stamp below invalidates on mailbox writes that affect the
@@ -4747,6 +4826,59 @@ fn build_status_with_phase(
     };
     mark_tail_latency_phase(&mut phase, "sqlite_inbox_counts");
 
+    let (top_unread, pending_ack_ids) = if let Some((agent_id, _)) = &agent {
+        let top_unread = conn
+            .query_sync(
+                "SELECT m.id, m.subject, m.importance, m.created_ts, a.name AS sender
+                 FROM message_recipients mr
+                 JOIN messages m ON m.id = mr.message_id
+                 JOIN agents a ON a.id = m.sender_id
+                 WHERE mr.agent_id = ? AND m.project_id = ? AND mr.read_ts IS NULL
+                 ORDER BY m.created_ts DESC, m.id DESC
+                 LIMIT ?",
+                &[
+                    Value::BigInt(*agent_id),
+                    Value::BigInt(project_id),
+                    Value::BigInt(STATUS_TOP_UNREAD_LIMIT),
+                ],
+            )
+            .map_err(|e| CliError::Other(format!("top unread query failed: {e}")))?
+            .iter()
+            .map(|r| StatusUnreadMessage {
+                id: r.get_named("id").unwrap_or(0),
+                from: r.get_named("sender").unwrap_or_default(),
+                subject: r.get_named("subject").unwrap_or_default(),
+                importance: r.get_named("importance").unwrap_or_default(),
+                created_at: mcp_agent_mail_db::micros_to_iso(
+                    r.get_named("created_ts").unwrap_or(0),
+                ),
+            })
+            .collect();
+        let pending_ack_ids = conn
+            .query_sync(
+                "SELECT m.id
+                 FROM message_recipients mr
+                 JOIN messages m ON m.id = mr.message_id
+                 WHERE mr.agent_id = ? AND m.project_id = ?
+                   AND m.ack_required = 1 AND mr.ack_ts IS NULL
+                 ORDER BY m.created_ts ASC, m.id ASC
+                 LIMIT ?",
+                &[
+                    Value::BigInt(*agent_id),
+                    Value::BigInt(project_id),
+                    Value::BigInt(STATUS_PENDING_ACK_ID_LIMIT),
+                ],
+            )
+            .map_err(|e| CliError::Other(format!("pending ack query failed: {e}")))?
+            .iter()
+            .filter_map(|r| r.get_named::<i64>("id").ok())
+            .collect();
+        (Some(top_unread), Some(pending_ack_ids))
+    } else {
+        (None, None)
+    };
+    mark_tail_latency_phase(&mut phase, "sqlite_agent_inbox_detail");
+
     // 2. Active agents (active in last 15 minutes)
     let active_threshold = micros_ago(now_us, 15 * MICROS_PER_MINUTE);
     let active_agents = conn
@@ -4760,6 +4892,10 @@ fn build_status_with_phase(
         .unwrap_or(0) as usize;
     mark_tail_latency_phase(&mut phase, "sqlite_active_agents");
 
+    // Agent tasks with freshness, so stale tasks read as stale.
+    let agent_tasks = fetch_status_agent_tasks(conn, project_id, now_us);
+    mark_tail_latency_phase(&mut phase, "sqlite_agent_tasks");
+
     // 3. Recent messages (last hour)
     let hour_ago = micros_ago(now_us, MICROS_PER_HOUR);
     let recent_messages = conn
@@ -4812,38 +4948,51 @@ fn build_status_with_phase(
     mark_tail_latency_phase(&mut phase, "sqlite_expiring_reservations");
 
     // 5. My reservations (agent-specific)
-    let my_reservations = if let Some((agent_id, _)) = &agent {
-        conn.query_sync(
-            &format!(
-                "SELECT fr.path_pattern, fr.\"exclusive\", fr.expires_ts
-                 FROM file_reservations fr{active_reservation_join}
-                 WHERE fr.project_id = ? AND fr.agent_id = ? AND ({active_reservation_predicate})
-                   AND fr.expires_ts > ?
-                 ORDER BY fr.expires_ts ASC"
-            ),
-            &[
-                Value::BigInt(project_id),
-                Value::BigInt(*agent_id),
-                Value::BigInt(now_us),
-            ],
-        )
-        .map_err(|e| CliError::Other(format!("my reservations query failed: {e}")))?
-        .iter()
-        .map(|r| {
-            let expires: i64 = r.get_named("expires_ts").unwrap_or(0);
-            ReservationEntry {
-                agent: None,
-                path: r.get_named("path_pattern").unwrap_or_default(),
-                exclusive: r.get_named::<i64>("exclusive").unwrap_or(1) != 0,
-                remaining_seconds: remaining_seconds_from_micros(now_us, expires),
-                remaining: None,
-                granted_at: None,
-            }
-        })
-        .collect()
+    let held_reservations: Option<Vec<HeldReservation>> = if let Some((agent_id, _)) = &agent {
+        let held = conn
+            .query_sync(
+                &format!(
+                    "SELECT fr.id, fr.path_pattern, fr.\"exclusive\", fr.expires_ts
+                     FROM file_reservations fr{active_reservation_join}
+                     WHERE fr.project_id = ? AND fr.agent_id = ? AND ({active_reservation_predicate})
+                       AND fr.expires_ts > ?
+                     ORDER BY fr.expires_ts ASC, fr.id ASC"
+                ),
+                &[
+                    Value::BigInt(project_id),
+                    Value::BigInt(*agent_id),
+                    Value::BigInt(now_us),
+                ],
+            )
+            .map_err(|e| CliError::Other(format!("my reservations query failed: {e}")))?
+            .iter()
+            .map(|r| {
+                let expires: i64 = r.get_named("expires_ts").unwrap_or(0);
+                HeldReservation {
+                    id: r.get_named("id").unwrap_or(0),
+                    path: r.get_named("path_pattern").unwrap_or_default(),
+                    exclusive: r.get_named::<i64>("exclusive").unwrap_or(1) != 0,
+                    expires_at: mcp_agent_mail_db::micros_to_iso(expires),
+                    remaining_seconds: remaining_seconds_from_micros(now_us, expires),
+                }
+            })
+            .collect();
+        Some(held)
     } else {
-        vec![]
+        None
     };
+    let my_reservations = held_reservations
+        .iter()
+        .flatten()
+        .map(|held| ReservationEntry {
+            agent: None,
+            path: held.path.clone(),
+            exclusive: held.exclusive,
+            remaining_seconds: held.remaining_seconds,
+            remaining: None,
+            granted_at: None,
+        })
+        .collect();
     mark_tail_latency_phase(&mut phase, "sqlite_my_reservations");
 
     let reservation_forecast = build_reservations(
@@ -5038,6 +5187,13 @@ fn build_status_with_phase(
         reservations_expiring_soon,
         active_agents,
         recent_messages,
+        health_level: mcp_agent_mail_core::compute_health_level(),
+        shedding_enabled: config.backpressure_shedding_enabled,
+        safe_mode: safe_mode_server_running(&config.storage_root),
+        top_unread,
+        pending_ack_ids,
+        held_reservations,
+        agent_tasks,
         my_reservations,
         top_threads,
         anomalies,
@@ -5110,6 +5266,13 @@ fn build_status_with_snapshot_cache(
     Ok((data, actions))
 }
 
+/// Whether a live safe-mode server owns `storage_root`, per the marker it
+/// writes at startup. A marker left by a dead process is ignored.
+fn safe_mode_server_running(storage_root: &Path) -> bool {
+    mcp_agent_mail_core::safe_mode::read_marker(storage_root)
+        .is_some_and(|marker| crate::doctor::fixers::is_pid_alive(marker.pid))
+}
+
 fn status_health_from_recovery(
     recovery: Option<&RecoveryStatus>,
     anomalies: &[AnomalyCard],
@@ -5218,6 +5381,13 @@ fn build_recovery_only_status(
         reservations_expiring_soon: 0,
         active_agents: 0,
         recent_messages: 0,
+        health_level: mcp_agent_mail_core::compute_health_level(),
+        shedding_enabled: config.backpressure_shedding_enabled,
+        safe_mode: safe_mode_server_running(&config.storage_root),
+        top_unread: None,
+        pending_ack_ids: None,
+        held_reservations: None,
+        agent_tasks: vec![],
         my_reservations: vec![],
         top_threads: vec![],
         anomalies,
@@ -5244,10 +5414,17 @@ fn render_status_output(
     let mut env = RobotEnvelope::new(cmd_name, format, data);
     env._meta.project = project_slug;
     env._meta.agent = agent_name.clone();
+    if env.data.safe_mode {
+        env = env.with_alert(
+            "warn",
+            "Server is in SAFE MODE: read-only, writes return SAFE_MODE",
+            Some("Restart without --safe-mode to resume: am serve-http".to_string()),
+        );
+    }
     if agent_name.is_none() {
         env = env.with_alert(
             "info",
-            "Agent not detected — inbox/reservation sections omitted. Use --agent to specify.",
+            "Agent not detected — inbox/reservation fields are null. Use --agent to specify.",
             Some("am robot status --agent <NAME>".to_string()),
         );
     }
@@ -5271,12 +5448,16 @@ struct InboxResult {
     entries: Vec<InboxEntry>,
     alerts: Vec<(String, String, Option<String>)>,
     actions: Vec<String>,
+    /// Next polling cursor, set only for `--after-watermark` requests.
+    watermark: Option<i64>,
 }
 
 #[derive(Serialize)]
 struct RobotInboxData {
     count: usize,
     inbox: Vec<InboxEntry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    watermark: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -5289,6 +5470,7 @@ struct RobotInboxServerRequest {
     all: bool,
     limit: usize,
     include_bodies: bool,
+    after_watermark: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -5314,6 +5496,7 @@ fn render_inbox_result(
         RobotInboxData {
             count,
             inbox: result.entries,
+            watermark: result.watermark,
         },
     );
     env._meta.project = Some(project);
@@ -5349,6 +5532,7 @@ fn build_robot_inbox_server_request(
     all: bool,
     limit: Option<usize>,
     include_bodies: bool,
+    after_watermark: Option<i64>,
 ) -> Result<Option<RobotInboxServerRequest>, CliError> {
     let Some(agent_name) = resolved_agent_flag_or_env(agent_flag) else {
         return Ok(None);
@@ -5362,6 +5546,7 @@ fn build_robot_inbox_server_request(
         all,
         limit: limit.unwrap_or(20),
         include_bodies,
+        after_watermark,
     }))
 }
 
@@ -5386,7 +5571,7 @@ fn robot_inbox_server_arguments(request: &RobotInboxServerRequest) -> serde_json
     } else {
         request.unread
     };
-    serde_json::json!({
+    let mut arguments = serde_json::json!({
         "project_key": request.project_key,
         "agent_name": request.agent_name,
         "urgent_only": request.urgent,
@@ -5394,7 +5579,13 @@ fn robot_inbox_server_arguments(request: &RobotInboxServerRequest) -> serde_json
         "include_bodies": request.include_bodies,
         "unread_only": unread_only,
         "ack_overdue_only": ack_overdue_only,
-    })
+    });
+    if let Some(watermark) = request.after_watermark
+        && let Some(object) = arguments.as_object_mut()
+    {
+        object.insert("after_watermark".to_string(), serde_json::json!(watermark));
+    }
+    arguments
 }
 
 fn server_inbox_rows(payload: &serde_json::Value) -> Option<&Vec<serde_json::Value>> {
@@ -5515,6 +5706,13 @@ fn build_inbox_from_server_payload(
         .ok_or_else(|| CliError::Other("unexpected fetch_inbox response shape".to_string()))?;
     let now_us = mcp_agent_mail_db::now_micros();
     let ack_threshold = micros_ago(now_us, ACK_OVERDUE_THRESHOLD_US);
+    // The server returns a contiguous id window for watermark requests, so the
+    // cursor covers every returned row, including ones filtered out below.
+    let watermark = request.after_watermark.map(|previous| {
+        rows.iter()
+            .filter_map(|row| row.get("id").and_then(serde_json::Value::as_i64))
+            .fold(previous, i64::max)
+    });
     let mut projected = rows
         .iter()
         .map(|row| server_inbox_row_to_entry(row, now_us, ack_threshold, request.include_bodies))
@@ -5591,6 +5789,7 @@ fn build_inbox_from_server_payload(
         entries,
         alerts,
         actions,
+        watermark,
     })
 }
 
@@ -5602,7 +5801,7 @@ fn try_build_inbox_via_server(
     if urls.is_empty() {
         return Ok(None);
     }
-    let bearer = crate::local_server_bearer_token(config);
+    let bearer = crate::local_server_bearer_token(config)?;
     let mut last_unavailable: Option<(String, String)> = None;
 
     for server_url in urls {
@@ -5663,6 +5862,7 @@ fn render_local_inbox_result(
     all: bool,
     limit: Option<usize>,
     include_bodies: bool,
+    after_watermark: Option<i64>,
     mut phase: TailLatencyPhaseRecorder,
 ) -> Result<String, CliError> {
     let scope = resolve_robot_scope(project_flag, agent_flag)?;
@@ -5673,20 +5873,40 @@ fn render_local_inbox_result(
         )
     })?;
 
-    let result = build_inbox_with_snapshot_cache(
-        scope.conn(),
-        scope.project_id,
-        &scope.project_slug,
-        agent_id,
-        &agent_name_str,
-        urgent,
-        ack_overdue,
-        unread || (!urgent && !ack_overdue && !all),
-        all,
-        limit.unwrap_or(20),
-        include_bodies,
-        Some(&mut phase),
-    )?;
+    let unread_only = unread || (!urgent && !ack_overdue && !all);
+    let result = if let Some(watermark) = after_watermark {
+        // Watermark polls are cursor-dependent, so they bypass the snapshot cache.
+        build_inbox_window(
+            scope.conn(),
+            scope.project_id,
+            &scope.project_slug,
+            agent_id,
+            &agent_name_str,
+            urgent,
+            ack_overdue,
+            unread_only,
+            all,
+            limit.unwrap_or(20),
+            include_bodies,
+            Some(watermark),
+            Some(&mut phase),
+        )?
+    } else {
+        build_inbox_with_snapshot_cache(
+            scope.conn(),
+            scope.project_id,
+            &scope.project_slug,
+            agent_id,
+            &agent_name_str,
+            urgent,
+            ack_overdue,
+            unread_only,
+            all,
+            limit.unwrap_or(20),
+            include_bodies,
+            Some(&mut phase),
+        )?
+    };
     render_inbox_result(
         cmd_name,
         format,
@@ -5741,6 +5961,42 @@ fn build_inbox_with_phase(
     show_all: bool,
     limit: usize,
     include_bodies: bool,
+    phase: Option<&mut TailLatencyPhaseRecorder>,
+) -> Result<InboxResult, CliError> {
+    build_inbox_window(
+        conn,
+        project_id,
+        project_slug,
+        agent_id,
+        agent_name,
+        urgent_only,
+        ack_overdue_only,
+        unread_only,
+        show_all,
+        limit,
+        include_bodies,
+        None,
+        phase,
+    )
+}
+
+/// Build the robot inbox. With `after_watermark`, the `limit` window is taken
+/// in message-id order after the watermark (then re-sorted by priority), so
+/// consecutive polls never skip a message that fell outside the page.
+#[allow(clippy::too_many_arguments)]
+fn build_inbox_window(
+    conn: &DbConn,
+    project_id: i64,
+    project_slug: &str,
+    agent_id: i64,
+    agent_name: &str,
+    urgent_only: bool,
+    ack_overdue_only: bool,
+    unread_only: bool,
+    show_all: bool,
+    limit: usize,
+    include_bodies: bool,
+    after_watermark: Option<i64>,
     mut phase: Option<&mut TailLatencyPhaseRecorder>,
 ) -> Result<InboxResult, CliError> {
     let now_us = mcp_agent_mail_db::now_micros();
@@ -5765,6 +6021,11 @@ fn build_inbox_with_phase(
     } else {
         "'' AS body_md"
     };
+    let (watermark_filter, window_order) = if after_watermark.is_some() {
+        ("AND m.id > ?", "sub.id ASC")
+    } else {
+        ("", "sub.priority_bucket ASC, sub.created_ts DESC")
+    };
     let sql = format!(
         "SELECT sub.id, sub.subject, sub.thread_id, sub.importance, sub.ack_required,
                 sub.created_ts, sub.sender_id, sub.read_ts, sub.ack_ts, sub.body_md,
@@ -5783,24 +6044,33 @@ fn build_inbox_with_phase(
                     END AS priority_bucket
              FROM message_recipients mr
              JOIN messages m ON m.id = mr.message_id
-             WHERE mr.agent_id = ? AND m.project_id = ?
+             WHERE mr.agent_id = ? AND m.project_id = ? {watermark_filter}
          ) sub
          LEFT JOIN agents a_sender ON a_sender.id = sub.sender_id
          WHERE 1=1 {bucket_filter}
-         ORDER BY sub.priority_bucket ASC, sub.created_ts DESC
+         ORDER BY {window_order}
          LIMIT ?"
     );
+    let sql = if after_watermark.is_some() {
+        format!(
+            "SELECT * FROM ({sql}) windowed \
+             ORDER BY windowed.priority_bucket ASC, windowed.created_ts DESC"
+        )
+    } else {
+        sql
+    };
 
+    let mut params = vec![
+        Value::BigInt(ack_threshold),
+        Value::BigInt(agent_id),
+        Value::BigInt(project_id),
+    ];
+    if let Some(watermark) = after_watermark {
+        params.push(Value::BigInt(watermark));
+    }
+    params.push(Value::BigInt(limit.try_into().unwrap_or(i64::MAX)));
     let rows = conn
-        .query_sync(
-            &sql,
-            &[
-                Value::BigInt(ack_threshold),
-                Value::BigInt(agent_id),
-                Value::BigInt(project_id),
-                Value::BigInt(limit.try_into().unwrap_or(i64::MAX)),
-            ],
-        )
+        .query_sync(&sql, &params)
         .map_err(|e| CliError::Other(format!("inbox query failed: {e}")))?;
     mark_tail_latency_phase(&mut phase, "sqlite_query");
 
@@ -5909,10 +6179,13 @@ fn build_inbox_with_phase(
     }
     mark_tail_latency_phase(&mut phase, "downstream_actions");
 
+    let watermark = after_watermark
+        .map(|previous| entries.iter().map(|entry| entry.id).fold(previous, i64::max));
     Ok(InboxResult {
         entries,
         alerts,
         actions,
+        watermark,
     })
 }
 
@@ -7756,7 +8029,7 @@ fn build_reservations(
                  FROM file_reservations fr{active_reservation_join}
                  LEFT JOIN agents a ON a.id = fr.agent_id
                  WHERE fr.project_id = ? AND ({active_reservation_predicate}) AND fr.expires_ts > ?
-                 ORDER BY fr.expires_ts ASC"
+                 ORDER BY fr.expires_ts ASC, fr.id ASC"
             ),
             &[Value::BigInt(project_id), Value::BigInt(now_us)],
         )
@@ -9780,6 +10053,8 @@ fn build_agents_with_health(
         name: String,
         program: String,
         model: String,
+        task: String,
+        inception_ts: i64,
         last_active_ts: i64,
         msg_count: usize,
     }
@@ -9792,7 +10067,8 @@ fn build_agents_with_health(
 
     let rows = conn
         .query_sync(
-            "SELECT a.id, a.name, a.program, a.model, a.last_active_ts,
+            "SELECT a.id, a.name, a.program, a.model, a.task_description,
+                    a.inception_ts, a.last_active_ts,
                     (SELECT COUNT(*) FROM messages m WHERE m.sender_id = a.id) AS msg_count
              FROM agents a
              WHERE a.project_id = ?
@@ -9808,10 +10084,13 @@ fn build_agents_with_health(
             name: row.get_named("name").unwrap_or_default(),
             program: row.get_named("program").unwrap_or_default(),
             model: row.get_named("model").unwrap_or_default(),
+            task: row.get_named("task_description").unwrap_or_default(),
+            inception_ts: row.get_named("inception_ts").unwrap_or(0),
             last_active_ts: row.get_named("last_active_ts").unwrap_or(0),
             msg_count: row.get_named::<i64>("msg_count").unwrap_or(0).max(0) as usize,
         })
         .collect();
+    let task_updated = fetch_agent_task_updated_ts(conn, project_id);
     let health_inputs = if health_enabled {
         let agent_ids = raw_agents
             .iter()
@@ -9834,6 +10113,8 @@ fn build_agents_with_health(
             name,
             program,
             model,
+            task,
+            inception_ts,
             last_active_ts,
             msg_count,
         } = row;
@@ -9865,6 +10146,11 @@ fn build_agents_with_health(
                     ))
             });
 
+        let task_set = (!task.is_empty()).then(|| {
+            let task_ts = task_updated.get(&agent_id).copied().unwrap_or(inception_ts);
+            format_age(age_seconds_from_micros(now_us, task_ts))
+        });
+
         let logical_name = name.to_lowercase();
         let candidate = PendingAgentRow {
             row: AgentRow {
@@ -9874,6 +10160,8 @@ fn build_agents_with_health(
                 last_active: format_age(age_seconds),
                 msg_count,
                 status: status.to_string(),
+                task,
+                task_set,
                 health,
             },
             last_active_ts,
@@ -10486,6 +10774,68 @@ fn agent_health_metric_row(metric: &AgentHealthMetric) -> AgentHealthMetricRow {
     }
 }
 
+/// Agents with a non-empty task for `robot status`, most recently active first.
+///
+/// Best effort like the other task-freshness lookups: a query failure yields
+/// an empty list rather than failing the whole status.
+fn fetch_status_agent_tasks(conn: &DbConn, project_id: i64, now_us: i64) -> Vec<StatusAgentTask> {
+    let Ok(rows) = conn.query_sync(
+        "SELECT id, name, task_description, inception_ts
+         FROM agents
+         WHERE project_id = ? AND task_description <> ''
+         ORDER BY last_active_ts DESC, id ASC
+         LIMIT ?",
+        &[
+            Value::BigInt(project_id),
+            Value::BigInt(STATUS_AGENT_TASK_LIMIT),
+        ],
+    ) else {
+        return Vec::new();
+    };
+    let task_updated = fetch_agent_task_updated_ts(conn, project_id);
+    rows.iter()
+        .map(|r| {
+            let agent_id: i64 = r.get_named("id").unwrap_or(0);
+            let task_ts = task_updated
+                .get(&agent_id)
+                .copied()
+                .unwrap_or_else(|| r.get_named("inception_ts").unwrap_or(0));
+            let task_age_seconds = age_seconds_from_micros(now_us, task_ts);
+            StatusAgentTask {
+                agent: r.get_named("name").unwrap_or_default(),
+                task: r.get_named("task_description").unwrap_or_default(),
+                task_set: format_age(task_age_seconds),
+                task_age_seconds,
+            }
+        })
+        .collect()
+}
+
+/// Task-freshness stamps for a project's agents, keyed by agent id.
+///
+/// Mailboxes that predate the `agent_task_updates` sidecar yield an empty map
+/// and callers fall back to each agent's inception timestamp.
+fn fetch_agent_task_updated_ts(conn: &DbConn, project_id: i64) -> HashMap<i64, i64> {
+    conn.query_sync(
+        "SELECT t.agent_id, t.task_updated_ts
+         FROM agent_task_updates t
+         JOIN agents a ON a.id = t.agent_id
+         WHERE a.project_id = ?",
+        &[Value::BigInt(project_id)],
+    )
+    .map(|rows| {
+        rows.iter()
+            .filter_map(|row| {
+                Some((
+                    row.get_named::<i64>("agent_id").ok()?,
+                    row.get_named::<i64>("task_updated_ts").ok()?,
+                ))
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn fetch_agent_health_inputs(
     conn: &DbConn,
     project_id: i64,
@@ -10897,6 +11247,369 @@ fn build_contacts(conn: &DbConn, project_id: i64) -> Result<Vec<ContactRow>, Cli
     Ok(contacts)
 }
 
+// ── Next-actions command implementation ─────────────────────────────────────
+
+/// Reservations expiring within this window are surfaced for renew/release.
+const NEXT_ACTIONS_EXPIRING_WINDOW_US: i64 = 15 * MICROS_PER_MINUTE;
+/// Cap on the messages `robot next-actions` loads per run.
+const NEXT_ACTIONS_MESSAGE_LIMIT: i64 = 200;
+
+/// Mailbox state for one agent, as `plan_next_actions` sees it.
+#[derive(Debug, Clone, Default)]
+struct NextActionsSnapshot {
+    project_key: String,
+    agent_name: String,
+    now_us: i64,
+    /// Messages addressed to the agent that are unread or still need an ack.
+    messages: Vec<NextActionsMessage>,
+    /// The agent's active file reservations.
+    reservations: Vec<NextActionsReservation>,
+    /// Pending contact requests addressed to the agent.
+    contact_requests: Vec<NextActionsContactRequest>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct NextActionsMessage {
+    id: i64,
+    subject: String,
+    sender: String,
+    importance: String,
+    ack_required: bool,
+    created_ts: i64,
+    read: bool,
+    acked: bool,
+}
+
+impl NextActionsMessage {
+    fn is_urgent(&self) -> bool {
+        matches!(self.importance.as_str(), "urgent" | "high")
+    }
+
+    fn needs_ack(&self) -> bool {
+        self.ack_required && !self.acked
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct NextActionsReservation {
+    id: i64,
+    path: String,
+    expires_ts: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct NextActionsContactRequest {
+    from_agent: String,
+    /// Set when the requester lives in another project.
+    from_project: Option<String>,
+    reason: String,
+    created_ts: i64,
+}
+
+/// An MCP tool call the agent can make as-is.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ToolCallSkeleton {
+    tool: &'static str,
+    arguments: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct NextAction {
+    rank: usize,
+    /// `ack_overdue`, `urgent_unread`, `reservation_expiring`, `unread`, or
+    /// `contact_request`.
+    kind: &'static str,
+    summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reservation_id: Option<i64>,
+    call: ToolCallSkeleton,
+    /// Other reasonable calls, e.g. releasing instead of renewing.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    alternatives: Vec<ToolCallSkeleton>,
+}
+
+#[derive(Debug, Serialize)]
+struct NextActionsData {
+    agent: String,
+    count: usize,
+    actions: Vec<NextAction>,
+}
+
+/// Order the agent's pending work: overdue acks, unread urgent mail,
+/// reservations expiring within 15 minutes, other unread mail, then contact
+/// requests awaiting a response. Each message appears once, under its
+/// highest-priority kind; oldest items lead within a kind, soonest expiry
+/// for reservations.
+fn plan_next_actions(snapshot: &NextActionsSnapshot) -> Vec<NextAction> {
+    let project_key = snapshot.project_key.as_str();
+    let agent_name = snapshot.agent_name.as_str();
+    let ack_threshold = micros_ago(snapshot.now_us, ACK_OVERDUE_THRESHOLD_US);
+    let expiring_threshold = snapshot
+        .now_us
+        .saturating_add(NEXT_ACTIONS_EXPIRING_WINDOW_US);
+
+    let mut messages: Vec<&NextActionsMessage> = snapshot.messages.iter().collect();
+    messages.sort_by_key(|message| (message.created_ts, message.id));
+    let overdue =
+        |message: &NextActionsMessage| message.needs_ack() && message.created_ts < ack_threshold;
+    let message_action = |kind: &'static str, message: &NextActionsMessage| {
+        let call = if message.needs_ack() {
+            ToolCallSkeleton {
+                tool: "acknowledge_message",
+                arguments: serde_json::json!({
+                    "project_key": project_key,
+                    "agent_name": agent_name,
+                    "message_id": message.id,
+                }),
+            }
+        } else {
+            ToolCallSkeleton {
+                tool: "mark_message_read",
+                arguments: serde_json::json!({
+                    "project_key": project_key,
+                    "agent_name": agent_name,
+                    "message_id": message.id,
+                }),
+            }
+        };
+        NextAction {
+            rank: 0,
+            kind,
+            summary: format!("{} from {}", message.subject, message.sender),
+            message_id: Some(message.id),
+            reservation_id: None,
+            call,
+            alternatives: Vec::new(),
+        }
+    };
+
+    let mut actions: Vec<NextAction> = Vec::new();
+    actions.extend(
+        messages
+            .iter()
+            .filter(|message| overdue(message))
+            .map(|message| message_action("ack_overdue", message)),
+    );
+    actions.extend(
+        messages
+            .iter()
+            .filter(|message| !message.read && message.is_urgent() && !overdue(message))
+            .map(|message| message_action("urgent_unread", message)),
+    );
+
+    let mut expiring: Vec<&NextActionsReservation> = snapshot
+        .reservations
+        .iter()
+        .filter(|reservation| {
+            reservation.expires_ts > snapshot.now_us && reservation.expires_ts <= expiring_threshold
+        })
+        .collect();
+    expiring.sort_by_key(|reservation| (reservation.expires_ts, reservation.id));
+    actions.extend(expiring.into_iter().map(|reservation| {
+        let remaining = remaining_seconds_from_micros(snapshot.now_us, reservation.expires_ts);
+        NextAction {
+            rank: 0,
+            kind: "reservation_expiring",
+            summary: format!(
+                "{} expires in {}",
+                reservation.path,
+                format_remaining(remaining)
+            ),
+            message_id: None,
+            reservation_id: Some(reservation.id),
+            call: ToolCallSkeleton {
+                tool: "renew_file_reservations",
+                arguments: serde_json::json!({
+                    "project_key": project_key,
+                    "agent_name": agent_name,
+                    "file_reservation_ids": [reservation.id],
+                }),
+            },
+            alternatives: vec![ToolCallSkeleton {
+                tool: "release_file_reservations",
+                arguments: serde_json::json!({
+                    "project_key": project_key,
+                    "agent_name": agent_name,
+                    "file_reservation_ids": [reservation.id],
+                }),
+            }],
+        }
+    }));
+
+    actions.extend(
+        messages
+            .iter()
+            .filter(|message| !message.read && !message.is_urgent() && !overdue(message))
+            .map(|message| message_action("unread", message)),
+    );
+
+    let mut requests: Vec<&NextActionsContactRequest> = snapshot.contact_requests.iter().collect();
+    requests.sort_by_key(|request| request.created_ts);
+    actions.extend(requests.into_iter().map(|request| {
+        let respond = |accept: bool| {
+            let mut arguments = serde_json::json!({
+                "project_key": project_key,
+                "to_agent": agent_name,
+                "from_agent": request.from_agent,
+                "accept": accept,
+            });
+            if let Some(from_project) = &request.from_project {
+                arguments["from_project"] = serde_json::json!(from_project);
+            }
+            ToolCallSkeleton {
+                tool: "respond_contact",
+                arguments,
+            }
+        };
+        let summary = if request.reason.is_empty() {
+            format!("contact request from {}", request.from_agent)
+        } else {
+            format!(
+                "contact request from {}: {}",
+                request.from_agent, request.reason
+            )
+        };
+        NextAction {
+            rank: 0,
+            kind: "contact_request",
+            summary,
+            message_id: None,
+            reservation_id: None,
+            call: respond(true),
+            alternatives: vec![respond(false)],
+        }
+    }));
+
+    for (index, action) in actions.iter_mut().enumerate() {
+        action.rank = index + 1;
+    }
+    actions
+}
+
+fn load_next_actions_snapshot(
+    conn: &DbConn,
+    project_id: i64,
+    project_slug: &str,
+    agent_id: i64,
+    agent_name: &str,
+) -> Result<NextActionsSnapshot, CliError> {
+    let now_us = mcp_agent_mail_db::now_micros();
+
+    let rows = conn
+        .query_sync(
+            &format!(
+                "SELECT m.id, m.subject, m.importance, m.ack_required, m.created_ts,
+                        mr.read_ts, mr.ack_ts,
+                        COALESCE(a.name, '{UNKNOWN_SENDER_DISPLAY}') AS sender_name
+                 FROM message_recipients mr
+                 JOIN messages m ON m.id = mr.message_id
+                 LEFT JOIN agents a ON a.id = m.sender_id
+                 WHERE mr.agent_id = ? AND m.project_id = ?
+                   AND (mr.read_ts IS NULL OR (m.ack_required = 1 AND mr.ack_ts IS NULL))
+                 ORDER BY m.created_ts ASC, m.id ASC
+                 LIMIT ?"
+            ),
+            &[
+                Value::BigInt(agent_id),
+                Value::BigInt(project_id),
+                Value::BigInt(NEXT_ACTIONS_MESSAGE_LIMIT),
+            ],
+        )
+        .map_err(|e| CliError::Other(format!("next-actions message query failed: {e}")))?;
+    let messages = rows
+        .iter()
+        .map(|row| NextActionsMessage {
+            id: row.get_named("id").unwrap_or(0),
+            subject: row.get_named("subject").unwrap_or_default(),
+            sender: row.get_named("sender_name").unwrap_or_default(),
+            importance: row.get_named("importance").unwrap_or_default(),
+            ack_required: row.get_named::<i64>("ack_required").unwrap_or(0) != 0,
+            created_ts: row.get_named("created_ts").unwrap_or(0),
+            read: row.get_named::<i64>("read_ts").is_ok(),
+            acked: row.get_named::<i64>("ack_ts").is_ok(),
+        })
+        .collect();
+
+    let has_release_ledger = has_file_reservation_release_ledger(conn);
+    let has_legacy_released_ts_column = has_file_reservations_released_ts_column(conn);
+    let active_reservation_join =
+        active_reservation_release_join_sql(has_release_ledger, "fr", "rr");
+    let active_reservation_predicate = active_reservation_filter_sql(
+        has_release_ledger,
+        has_legacy_released_ts_column,
+        "fr",
+        "rr",
+    );
+    let rows = conn
+        .query_sync(
+            &format!(
+                "SELECT fr.id, fr.path_pattern, fr.expires_ts
+                 FROM file_reservations fr{active_reservation_join}
+                 WHERE fr.project_id = ? AND fr.agent_id = ?
+                   AND ({active_reservation_predicate}) AND fr.expires_ts > ?
+                 ORDER BY fr.expires_ts ASC, fr.id ASC"
+            ),
+            &[
+                Value::BigInt(project_id),
+                Value::BigInt(agent_id),
+                Value::BigInt(now_us),
+            ],
+        )
+        .map_err(|e| CliError::Other(format!("next-actions reservation query failed: {e}")))?;
+    let reservations = rows
+        .iter()
+        .map(|row| NextActionsReservation {
+            id: row.get_named("id").unwrap_or(0),
+            path: row.get_named("path_pattern").unwrap_or_default(),
+            expires_ts: row.get_named("expires_ts").unwrap_or(0),
+        })
+        .collect();
+
+    let rows = conn
+        .query_sync(
+            "SELECT al.a_project_id, al.reason, al.created_ts,
+                    COALESCE(NULLIF(a.name, ''), '[unknown-agent-' || al.a_agent_id || ']') AS from_agent,
+                    p.slug AS from_project
+             FROM agent_links al
+             LEFT JOIN agents a ON a.id = al.a_agent_id
+             LEFT JOIN projects p ON p.id = al.a_project_id
+             WHERE al.b_project_id = ? AND al.b_agent_id = ? AND al.status = 'pending'
+               AND (al.expires_ts IS NULL OR al.expires_ts > ?)
+             ORDER BY al.created_ts ASC",
+            &[
+                Value::BigInt(project_id),
+                Value::BigInt(agent_id),
+                Value::BigInt(now_us),
+            ],
+        )
+        .map_err(|e| CliError::Other(format!("next-actions contact query failed: {e}")))?;
+    let contact_requests = rows
+        .iter()
+        .map(|row| {
+            let from_project_id: i64 = row.get_named("a_project_id").unwrap_or(project_id);
+            NextActionsContactRequest {
+                from_agent: row.get_named("from_agent").unwrap_or_default(),
+                from_project: (from_project_id != project_id)
+                    .then(|| row.get_named::<String>("from_project").ok())
+                    .flatten(),
+                reason: row.get_named("reason").unwrap_or_default(),
+                created_ts: row.get_named("created_ts").unwrap_or(0),
+            }
+        })
+        .collect();
+
+    Ok(NextActionsSnapshot {
+        project_key: project_slug.to_string(),
+        agent_name: agent_name.to_string(),
+        now_us,
+        messages,
+        reservations,
+        contact_requests,
+    })
+}
+
 // ── Projects command implementation ─────────────────────────────────────────
 
 fn build_projects(conn: &DbConn) -> Result<Vec<ProjectRow>, CliError> {
@@ -11564,7 +12277,11 @@ where
 }
 
 fn atc_live_endpoint_from_config(config: &mcp_agent_mail_core::Config) -> AtcLiveEndpoint {
-    atc_live_endpoint_from_reader(|key| std::env::var(key).ok(), config)
+    let mut endpoint = atc_live_endpoint_from_reader(|key| std::env::var(key).ok(), config);
+    if endpoint.bearer_token.is_none() {
+        endpoint.bearer_token = crate::profiles::profile_bearer_token().ok().flatten();
+    }
+    endpoint
 }
 
 /// Fetch and parse the raw `/mail/ws-state` JSON payload from a live server.
@@ -12904,8 +13621,13 @@ fn build_atc_data(
     }
 }
 
-fn emit_robot_output(out: &str) {
-    ftui_runtime::ftui_println!("{out}");
+fn emit_robot_output(out: &str, format: OutputFormat) {
+    let format = match format {
+        OutputFormat::Json => crate::output::CliOutputFormat::Json,
+        OutputFormat::Toon => crate::output::CliOutputFormat::Toon,
+        OutputFormat::Markdown => crate::output::CliOutputFormat::Markdown,
+    };
+    crate::output::emit_guarded(out, format);
 }
 
 fn build_navigate_config_environment() -> (NavigateResult, Option<String>) {
@@ -13909,6 +14631,7 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
             all,
             limit,
             include_bodies,
+            after_watermark,
         } => {
             let mut phase = TailLatencyPhaseRecorder::new("robot_inbox");
             phase.set_include_bodies(include_bodies);
@@ -13923,6 +14646,7 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
                 all,
                 limit,
                 include_bodies,
+                after_watermark,
             )? {
                 match try_build_inbox_via_server(&request, &config)? {
                     Some(result) => {
@@ -13949,6 +14673,7 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
                             all,
                             limit,
                             include_bodies,
+                            after_watermark,
                             phase,
                         )?
                     }
@@ -13965,6 +14690,7 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
                     all,
                     limit,
                     include_bodies,
+                    after_watermark,
                     phase,
                 )?
             }
@@ -14046,7 +14772,7 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
                 agent,
                 all,
                 conflicts,
-                expiring,
+                expiring.map(crate::duration_arg::minutes_ceil),
             )?;
             let mut env = RobotEnvelope::new(cmd_name, format, data);
             env._meta.project = Some(scope.project_slug);
@@ -14992,6 +15718,37 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
             env._meta.project = Some(scope.project_slug);
             format_output(&env, format)?
         }
+        RobotSubcommand::NextActions => {
+            let scope = resolve_robot_scope(args.project.as_deref(), args.agent.as_deref())?;
+            let (agent_id, agent_name) = scope.agent.clone().ok_or_else(|| {
+                CliError::InvalidArgument(
+                    "agent required for next-actions — use --agent or set \
+                     AGENT_MAIL_AGENT/AGENT_NAME"
+                        .to_string(),
+                )
+            })?;
+            let snapshot = load_next_actions_snapshot(
+                scope.conn(),
+                scope.project_id,
+                &scope.project_slug,
+                agent_id,
+                &agent_name,
+            )?;
+            let actions = plan_next_actions(&snapshot);
+            let count = actions.len();
+            let mut env = RobotEnvelope::new(
+                cmd_name,
+                format,
+                NextActionsData {
+                    agent: agent_name.clone(),
+                    count,
+                    actions,
+                },
+            );
+            env._meta.project = Some(scope.project_slug);
+            env._meta.agent = Some(agent_name);
+            format_output(&env, format)?
+        }
         RobotSubcommand::Projects => {
             let conn = crate::open_db_sync_robot()?;
             let projects = build_projects_with_snapshot_cache(&conn)?;
@@ -15098,9 +15855,9 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
             let scope = resolve_robot_project_scope(args.project.as_deref())?;
             let (data, actions) = build_handoff(
                 &scope,
-                stale_minutes,
-                active_minutes,
-                fresh_comment_minutes,
+                crate::duration_arg::minutes_ceil(stale_minutes),
+                crate::duration_arg::minutes_ceil(active_minutes),
+                crate::duration_arg::minutes_ceil(fresh_comment_minutes),
                 include_fresh,
                 limit,
                 dry_run,
@@ -15341,7 +16098,7 @@ pub fn handle_robot(args: RobotArgs) -> Result<(), CliError> {
         }
     };
 
-    emit_robot_output(&out);
+    emit_robot_output(&out, format);
     Ok(())
 }
 
@@ -15495,6 +16252,8 @@ mod tests {
                 name TEXT NOT NULL,
                 program TEXT NOT NULL DEFAULT '',
                 model TEXT NOT NULL DEFAULT '',
+                task_description TEXT NOT NULL DEFAULT '',
+                inception_ts INTEGER NOT NULL DEFAULT 0,
                 last_active_ts INTEGER NOT NULL
             )",
             &empty,
@@ -15816,6 +16575,151 @@ mod tests {
         assert_eq!(result.reason_codes, vec!["no_owner"]);
     }
 
+    fn next_actions_message(id: i64, created_ts: i64) -> NextActionsMessage {
+        NextActionsMessage {
+            id,
+            subject: format!("subject {id}"),
+            sender: "RedFox".to_string(),
+            importance: "normal".to_string(),
+            created_ts,
+            ..NextActionsMessage::default()
+        }
+    }
+
+    #[test]
+    fn plan_next_actions_orders_by_category() {
+        let now = 1_000 * MICROS_PER_MINUTE;
+        let mut overdue = next_actions_message(1, now - 60 * MICROS_PER_MINUTE);
+        overdue.ack_required = true;
+        overdue.read = true;
+        // Urgent and overdue: listed once, as an overdue ack.
+        let mut urgent_overdue = next_actions_message(2, now - 90 * MICROS_PER_MINUTE);
+        urgent_overdue.ack_required = true;
+        urgent_overdue.importance = "urgent".to_string();
+        let mut urgent = next_actions_message(3, now - MICROS_PER_MINUTE);
+        urgent.importance = "high".to_string();
+        let unread = next_actions_message(4, now - 5 * MICROS_PER_MINUTE);
+        // Read and acked (or never ack-required): nothing to do.
+        let mut done = next_actions_message(5, now - 120 * MICROS_PER_MINUTE);
+        done.read = true;
+        let snapshot = NextActionsSnapshot {
+            project_key: "proj".to_string(),
+            agent_name: "BlueLake".to_string(),
+            now_us: now,
+            messages: vec![unread, urgent, done, overdue, urgent_overdue],
+            reservations: vec![
+                NextActionsReservation {
+                    id: 10,
+                    path: "src/**".to_string(),
+                    expires_ts: now + 10 * MICROS_PER_MINUTE,
+                },
+                NextActionsReservation {
+                    id: 11,
+                    path: "docs/**".to_string(),
+                    expires_ts: now + 60 * MICROS_PER_MINUTE,
+                },
+                NextActionsReservation {
+                    id: 12,
+                    path: "api/**".to_string(),
+                    expires_ts: now + 2 * MICROS_PER_MINUTE,
+                },
+            ],
+            contact_requests: vec![NextActionsContactRequest {
+                from_agent: "GreenCastle".to_string(),
+                from_project: Some("other".to_string()),
+                reason: "review".to_string(),
+                created_ts: now - MICROS_PER_MINUTE,
+            }],
+        };
+
+        let actions = plan_next_actions(&snapshot);
+        let order: Vec<(&str, Option<i64>, Option<i64>)> = actions
+            .iter()
+            .map(|action| (action.kind, action.message_id, action.reservation_id))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("ack_overdue", Some(2), None),
+                ("ack_overdue", Some(1), None),
+                ("urgent_unread", Some(3), None),
+                ("reservation_expiring", None, Some(12)),
+                ("reservation_expiring", None, Some(10)),
+                ("unread", Some(4), None),
+                ("contact_request", None, None),
+            ]
+        );
+        let ranks: Vec<usize> = actions.iter().map(|action| action.rank).collect();
+        assert_eq!(ranks, [1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn plan_next_actions_emits_callable_tool_skeletons() {
+        let now = 1_000 * MICROS_PER_MINUTE;
+        let mut needs_ack = next_actions_message(7, now - MICROS_PER_MINUTE);
+        needs_ack.ack_required = true;
+        let snapshot = NextActionsSnapshot {
+            project_key: "proj".to_string(),
+            agent_name: "BlueLake".to_string(),
+            now_us: now,
+            messages: vec![needs_ack, next_actions_message(8, now)],
+            reservations: vec![NextActionsReservation {
+                id: 10,
+                path: "src/**".to_string(),
+                expires_ts: now + MICROS_PER_MINUTE,
+            }],
+            contact_requests: vec![NextActionsContactRequest {
+                from_agent: "GreenCastle".to_string(),
+                created_ts: now,
+                ..NextActionsContactRequest::default()
+            }],
+        };
+
+        let actions = plan_next_actions(&snapshot);
+        // An unread message that needs an ack is acknowledged (which also
+        // marks it read); one that does not is just marked read.
+        assert_eq!(actions[1].call.tool, "acknowledge_message");
+        assert_eq!(
+            actions[1].call.arguments,
+            serde_json::json!({"project_key": "proj", "agent_name": "BlueLake", "message_id": 7})
+        );
+        assert_eq!(actions[2].call.tool, "mark_message_read");
+
+        assert_eq!(actions[0].call.tool, "renew_file_reservations");
+        assert_eq!(
+            actions[0].call.arguments["file_reservation_ids"],
+            serde_json::json!([10])
+        );
+        assert_eq!(actions[0].alternatives[0].tool, "release_file_reservations");
+
+        let contact = &actions[3];
+        assert_eq!(contact.call.tool, "respond_contact");
+        assert_eq!(
+            contact.call.arguments,
+            serde_json::json!({
+                "project_key": "proj",
+                "to_agent": "BlueLake",
+                "from_agent": "GreenCastle",
+                "accept": true,
+            })
+        );
+        assert_eq!(contact.alternatives[0].arguments["accept"], false);
+    }
+
+    #[test]
+    fn plan_next_actions_is_empty_for_a_clear_mailbox() {
+        let snapshot = NextActionsSnapshot {
+            now_us: MICROS_PER_HOUR,
+            reservations: vec![NextActionsReservation {
+                id: 1,
+                path: "src/**".to_string(),
+                expires_ts: 2 * MICROS_PER_HOUR,
+            }],
+            ..NextActionsSnapshot::default()
+        };
+        assert!(plan_next_actions(&snapshot).is_empty());
+    }
+
     #[test]
     fn reservation_reason_mentions_bracketed_issue_id() {
         assert!(reservation_reason_mentions_issue(
@@ -16998,6 +17902,8 @@ mod tests {
             last_active: "2m ago".into(),
             msg_count: 15,
             status: "active".into(),
+            task: String::new(),
+            task_set: None,
             health: None,
         }];
         let json = serde_json::to_string(&agents).unwrap();
@@ -17029,6 +17935,8 @@ mod tests {
                 last_active: format!("{i}m ago"),
                 msg_count: i * 10,
                 status: "active".into(),
+                task: String::new(),
+                task_set: None,
                 health: None,
             })
             .collect();
@@ -17221,6 +18129,7 @@ mod tests {
             all: false,
             limit: 20,
             include_bodies: false,
+            after_watermark: None,
         };
         let default_args = robot_inbox_server_arguments(&default_request);
         assert_eq!(default_args["unread_only"], true);
@@ -17317,6 +18226,7 @@ mod tests {
             all: false,
             limit: 10,
             include_bodies: false,
+            after_watermark: None,
         };
 
         let result = build_inbox_from_server_payload(&payload, &request).expect("server inbox");
@@ -18072,6 +18982,8 @@ mod tests {
             last_active: "5m ago".into(),
             msg_count: 42,
             status: "active".into(),
+            task: "migrating auth schema".into(),
+            task_set: Some("3h ago".into()),
             health: None,
         };
         let v: Value = serde_json::to_value(&agent).unwrap();
@@ -18079,6 +18991,8 @@ mod tests {
         assert_eq!(v["program"], "claude-code");
         assert_eq!(v["msg_count"], 42);
         assert_eq!(v["status"], "active");
+        assert_eq!(v["task"], "migrating auth schema");
+        assert_eq!(v["task_set"], "3h ago");
     }
 
     #[test]
@@ -18090,6 +19004,8 @@ mod tests {
             last_active: "5m ago".into(),
             msg_count: 42,
             status: "active".into(),
+            task: String::new(),
+            task_set: None,
             health: Some(AgentHealthView {
                 badge: "B 78".into(),
                 score: 78,
@@ -18362,6 +19278,8 @@ mod tests {
                         last_active: "2m ago".into(),
                         msg_count: 50,
                         status: "active".into(),
+                        task: String::new(),
+                        task_set: None,
                         health: None,
                     },
                     AgentRow {
@@ -18371,6 +19289,8 @@ mod tests {
                         last_active: "1h ago".into(),
                         msg_count: 10,
                         status: "idle".into(),
+                        task: String::new(),
+                        task_set: None,
                         health: None,
                     },
                 ],
@@ -18429,6 +19349,13 @@ mod tests {
             reservations_expiring_soon: 1,
             active_agents: 3,
             recent_messages: 12,
+            health_level: HealthLevel::Green,
+            shedding_enabled: false,
+            safe_mode: false,
+            top_unread: None,
+            pending_ack_ids: None,
+            held_reservations: None,
+            agent_tasks: vec![],
             my_reservations: vec![],
             top_threads: vec![],
             anomalies: vec![],
@@ -18457,6 +19384,13 @@ mod tests {
             reservations_expiring_soon: 0,
             active_agents: 1,
             recent_messages: 0,
+            health_level: HealthLevel::Green,
+            shedding_enabled: false,
+            safe_mode: false,
+            top_unread: None,
+            pending_ack_ids: None,
+            held_reservations: None,
+            agent_tasks: vec![],
             my_reservations: vec![],
             top_threads: vec![],
             anomalies: vec![AnomalyCard {
@@ -18885,6 +19819,97 @@ mod tests {
         assert_eq!(forecast.conflict_hotspots[0].conflict_pairs, 1);
     }
 
+    #[test]
+    fn build_status_reports_agent_orientation_fields() {
+        let (_temp_dir, conn) = setup_robot_status_snapshot_test_db();
+        let now_us = mcp_agent_mail_db::now_micros();
+        conn.query_sync(
+            "INSERT INTO messages
+             (id, project_id, sender_id, thread_id, subject, created_ts, ack_required, importance)
+             VALUES
+                (2, 1, 1, 'thread-2', 'Please ack', ?, 1, 'high'),
+                (3, 1, 1, 'thread-3', 'Already read', ?, 1, 'normal')",
+            &[
+                mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us + 1),
+                mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us + 2),
+            ],
+        )
+        .expect("seed messages");
+        conn.query_sync(
+            "INSERT INTO message_recipients (id, message_id, agent_id, kind, read_ts, ack_ts)
+             VALUES (2, 2, 2, 'to', NULL, NULL), (3, 3, 2, 'to', ?, NULL)",
+            &[mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us + 3)],
+        )
+        .expect("seed recipients");
+        conn.query_sync(
+            "INSERT INTO file_reservations
+             (id, project_id, agent_id, path_pattern, exclusive, reason, created_ts, expires_ts, released_ts)
+             VALUES
+                (7, 1, 2, 'src/lib.rs', 1, 'edit', ?, ?, NULL),
+                (8, 1, 1, 'docs/**', 0, 'other agent', ?, ?, NULL)",
+            &[
+                mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us),
+                mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us + 3_600_000_000),
+                mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us),
+                mcp_agent_mail_db::sqlmodel_core::Value::BigInt(now_us + 3_600_000_000),
+            ],
+        )
+        .expect("seed reservations");
+        conn.query_sync(
+            "UPDATE agents SET task_description = 'triaging inbox', inception_ts = ? WHERE id = 2",
+            &[mcp_agent_mail_db::sqlmodel_core::Value::BigInt(
+                now_us - 2 * MICROS_PER_HOUR,
+            )],
+        )
+        .expect("set reader task");
+
+        let (status, _) =
+            build_status(&conn, 1, "demo", Some((2, "Reader".into()))).expect("build status");
+        let subjects: Vec<_> = status
+            .top_unread
+            .as_ref()
+            .expect("agent status has unread list")
+            .iter()
+            .map(|m| (m.id, m.from.as_str(), m.subject.as_str()))
+            .collect();
+        assert_eq!(
+            subjects,
+            vec![
+                (2, "Sender", "Please ack"),
+                (1, "Sender", "Initial subject")
+            ]
+        );
+        assert_eq!(status.ack_required, 2);
+        assert_eq!(status.pending_ack_ids, Some(vec![2, 3]));
+        let held = status
+            .held_reservations
+            .as_ref()
+            .expect("agent status has held reservations");
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].id, 7);
+        assert_eq!(held[0].path, "src/lib.rs");
+        assert!(held[0].remaining_seconds > 3_500);
+        assert_eq!(
+            held[0].expires_at,
+            mcp_agent_mail_db::micros_to_iso(now_us + 3_600_000_000)
+        );
+        assert_eq!(status.my_reservations.len(), 1);
+        // Without an agent_task_updates row, freshness falls back to inception.
+        assert_eq!(status.agent_tasks.len(), 1);
+        assert_eq!(status.agent_tasks[0].agent, "Reader");
+        assert_eq!(status.agent_tasks[0].task, "triaging inbox");
+        assert_eq!(status.agent_tasks[0].task_set, "2h ago");
+        assert!(status.agent_tasks[0].task_age_seconds >= 7_200);
+
+        let (anonymous, _) = build_status(&conn, 1, "demo", None).expect("build status");
+        let json = serde_json::to_value(&anonymous).expect("serialize status");
+        assert!(json["top_unread"].is_null());
+        assert!(json["pending_ack_ids"].is_null());
+        assert!(json["held_reservations"].is_null());
+        assert!(json["health_level"].is_string());
+        assert!(json["shedding_enabled"].is_boolean());
+    }
+
     #[test]
     fn build_status_snapshot_cache_reuses_fresh_generation() {
         let _guard = ROBOT_COMMAND_TEST_LOCK
@@ -19352,6 +20377,13 @@ mod tests {
             reservations_expiring_soon: 0,
             active_agents: 0,
             recent_messages: 0,
+            health_level: HealthLevel::Green,
+            shedding_enabled: false,
+            safe_mode: false,
+            top_unread: None,
+            pending_ack_ids: None,
+            held_reservations: None,
+            agent_tasks: vec![],
             my_reservations: vec![],
             top_threads: vec![],
             anomalies: anomalies.clone(),
@@ -19941,6 +20973,8 @@ mod tests {
                 status: "active".into(),
                 msg_count: 5,
                 last_active: "5m".into(),
+                task: String::new(),
+                task_set: None,
                 health: None,
             }],
         };
@@ -22787,6 +23821,74 @@ mod tests {
         assert_eq!(result.entries[0].from, UNKNOWN_SENDER_DISPLAY);
     }
 
+    #[test]
+    fn test_build_inbox_window_pages_by_watermark_despite_equal_and_skewed_timestamps() {
+        let (_temp_dir, conn) = setup_robot_thread_message_test_db();
+        // 131 shares 130's timestamp; 132 and 133 were committed later with a
+        // clock that ran behind, and 133 is urgent so it sorts first in a page.
+        conn.query_sync(
+            "INSERT INTO messages
+             (id, project_id, sender_id, subject, thread_id, importance, ack_required, created_ts, body_md, attachments)
+             VALUES
+                (130, 1, 1, 'Twin A', 'WM', 'normal', 0, 50, 'body', '[]'),
+                (131, 1, 1, 'Twin B', 'WM', 'normal', 0, 50, 'body', '[]'),
+                (132, 1, 1, 'Skewed', 'WM', 'normal', 0, 20, 'body', '[]'),
+                (133, 1, 1, 'Skewed urgent', 'WM', 'urgent', 0, 10, 'body', '[]')",
+            &[],
+        )
+        .expect("insert inbox messages");
+        conn.query_sync(
+            "INSERT INTO message_recipients (id, message_id, agent_id, kind, read_ts, ack_ts)
+             VALUES
+                (130, 130, 2, 'to', NULL, NULL),
+                (131, 131, 2, 'to', NULL, NULL),
+                (132, 132, 2, 'to', NULL, NULL),
+                (133, 133, 2, 'to', NULL, NULL)",
+            &[],
+        )
+        .expect("insert inbox recipients");
+
+        let poll = |watermark: i64| {
+            build_inbox_window(
+                &conn,
+                1,
+                "proj",
+                2,
+                "Bob",
+                false,
+                false,
+                true,
+                false,
+                2,
+                false,
+                Some(watermark),
+                None,
+            )
+            .expect("build inbox window")
+        };
+
+        let first = poll(0);
+        let mut first_ids: Vec<i64> = first.entries.iter().map(|entry| entry.id).collect();
+        first_ids.sort_unstable();
+        assert_eq!(first_ids, vec![130, 131]);
+        assert_eq!(first.watermark, Some(131));
+
+        let second = poll(131);
+        let second_ids: Vec<i64> = second.entries.iter().map(|entry| entry.id).collect();
+        assert_eq!(second_ids, vec![133, 132]);
+        assert_eq!(second.watermark, Some(133));
+
+        let drained = poll(133);
+        assert!(drained.entries.is_empty());
+        assert_eq!(drained.watermark, Some(133));
+
+        let unwindowed = build_inbox(
+            &conn, 1, "proj", 2, "Bob", false, false, true, false, 20, false,
+        )
+        .expect("build inbox");
+        assert_eq!(unwindowed.watermark, None);
+    }
+
     #[test]
     fn test_build_inbox_respects_include_bodies_flag() {
         let (_temp_dir, conn) = setup_robot_thread_message_test_db();
@@ -25859,6 +26961,13 @@ mod tests {
             reservations_expiring_soon: 0,
             active_agents: 1,
             recent_messages: 0,
+            health_level: HealthLevel::Green,
+            shedding_enabled: false,
+            safe_mode: false,
+            top_unread: None,
+            pending_ack_ids: None,
+            held_reservations: None,
+            agent_tasks: vec![],
             my_reservations: vec![],
             top_threads: vec![],
             anomalies: vec![],
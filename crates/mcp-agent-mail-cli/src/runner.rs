@@ -0,0 +1,146 @@
+//! Library entry point for running CLI commands without argv parsing.
+//!
+//! [`CliRunner`] takes an already-built [`Commands`] value, optionally points
+//! it at an injected database / storage root, and returns the structured
+//! payloads the command would have printed with `--json`:
+//!
+//! ```rust,ignore
+//! use mcp_agent_mail_cli::{AgentsCommand, CliRunner, Commands};
+//!
+//! let out = CliRunner::new()
+//!     .database_url("sqlite:////tmp/mail/storage.sqlite3")
+//!     .storage_root("/tmp/mail")
+//!     .run(Commands::Agents { action: AgentsCommand::List { /* ... */ } })?;
+//! let agents: Vec<serde_json::Value> = out.parse()?;
+//! ```
+//!
+//! `am` itself runs every command through the same path with capture
+//! disabled, so embedded and interactive runs share one dispatcher.
+//!
+//! # Limitations
+//!
+//! - Capture is thread-local and only records what goes through
+//!   [`output::emit_output`], [`output::json_or_table`], the empty-result
+//!   helpers and the `success`/`info`/`warn` message helpers. Commands that
+//!   write with `println!`, render TUI frames, or print from another thread
+//!   still reach the real stdout/stderr.
+//! - The database, storage root and [`CliRunner::env`] overrides are not
+//!   passed to the handlers; they are installed in a process-wide override
+//!   table that `Config` reads. Runs with overrides hold a process-wide lock,
+//!   but anything else reading the configuration meanwhile (another thread,
+//!   or a runner without overrides) sees the overridden values. Embedders
+//!   that run commands concurrently must serialize them behind their own
+//!   lock.
+
+#![forbid(unsafe_code)]
+
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use crate::output::{self, CapturedOutput};
+use crate::{CliError, CliResult, Commands};
+
+/// Structured result of a [`CliRunner::run`].
+pub type CliRunOutput = CapturedOutput;
+
+impl CapturedOutput {
+    /// The command's result payload (the last one emitted).
+    #[must_use]
+    pub fn payload(&self) -> Option<&serde_json::Value> {
+        self.payloads.last()
+    }
+
+    /// Deserialize the result payload into `T`.
+    pub fn parse<T: DeserializeOwned>(&self) -> CliResult<T> {
+        let payload = self
+            .payload()
+            .ok_or_else(|| CliError::Other("command produced no payload".to_string()))?;
+        serde_json::from_value(payload.clone())
+            .map_err(|e| CliError::Other(format!("unexpected command payload: {e}")))
+    }
+}
+
+/// Runs [`Commands`] in-process and returns their output as data.
+#[derive(Debug, Clone)]
+pub struct CliRunner {
+    env: Vec<(String, String)>,
+    capture: bool,
+}
+
+impl Default for CliRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CliRunner {
+    /// A runner that captures output and uses the ambient configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            env: Vec::new(),
+            capture: true,
+        }
+    }
+
+    /// A runner that prints like `am` does; used by the binary.
+    pub(crate) const fn printing() -> Self {
+        Self {
+            env: Vec::new(),
+            capture: false,
+        }
+    }
+
+    /// Use this database (`DATABASE_URL`) for the run.
+    #[must_use]
+    pub fn database_url(self, url: impl Into<String>) -> Self {
+        self.env("DATABASE_URL", url)
+    }
+
+    /// Use this archive root (`STORAGE_ROOT`) for the run.
+    #[must_use]
+    pub fn storage_root(self, root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref().display().to_string();
+        self.env("STORAGE_ROOT", root)
+    }
+
+    /// Override any configuration variable for the run, e.g. `HTTP_PORT` to
+    /// keep server-first commands from reaching a live `serve-http`.
+    #[must_use]
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Run `command`. Errors are returned, never printed; with capture on,
+    /// output from the output helpers is recorded instead of printed (see
+    /// the module docs for what escapes capture).
+    ///
+    /// Overrides are layered over the process-wide configuration for the
+    /// duration of the call. Runs with overrides are serialized against each
+    /// other, not against other configuration readers, and must not nest.
+    pub fn run(&self, command: Commands) -> CliResult<CliRunOutput> {
+        if self.env.is_empty() {
+            return self.dispatch(command);
+        }
+        let overrides: Vec<(&str, &str)> = self
+            .env
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        mcp_agent_mail_core::config::with_process_env_overrides(&overrides, || {
+            self.dispatch(command)
+        })
+    }
+
+    fn dispatch(&self, command: Commands) -> CliResult<CliRunOutput> {
+        if !self.capture {
+            return crate::execute_command(command).map(|()| CliRunOutput::default());
+        }
+        let previous = output::begin_capture();
+        let result = crate::execute_command(command);
+        let captured = output::finish_capture(previous);
+        result.map(|()| captured)
+    }
+}
@@ -0,0 +1,454 @@
+//! Near-miss subcommand suggestions for clap parse failures.
+//!
+//! clap only suggests siblings of the level where parsing failed, so
+//! `am reservations list` (meant: `am file_reservations list`) or `am snd`
+//! (meant: `am mail send`) produce a bare error and agents burn a turn
+//! re-reading `--help`. This module walks the *whole* command tree, scores
+//! every command path against what was typed, and renders a "did you mean"
+//! block plus the usage line of the best match.
+//!
+//! It also owns the small misnomer-alias table that rewrites argv for the
+//! most common wrong group names before clap ever sees them.
+
+#![forbid(unsafe_code)]
+
+use std::ffi::OsString;
+
+use clap::error::ErrorKind;
+
+/// Maximum number of suggestions rendered in the "did you mean" block.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Misnomer group names and the canonical group they stand for.
+///
+/// A rewrite only fires when the token after the misnomer is a real
+/// subcommand of the canonical group, so the existing top-level
+/// `am reservations` snapshot command keeps working unchanged while
+/// `am reservations list <project>` routes to `am file_reservations list`.
+const MISNOMER_ALIASES: &[(&str, &str)] = &[
+    ("reservations", "file_reservations"),
+    ("reservation", "file_reservations"),
+];
+
+/// Misnomer top-level commands and the nested command they stand for.
+///
+/// `am inbox` is itself the robot inbox alias, so the rewrite only fires
+/// when the arguments do not parse there but do parse under the nested
+/// path: `am inbox -p proj -a BlueLake` routes to `am mail inbox`.
+const MISNOMER_COMMAND_ALIASES: &[(&str, &[&str])] = &[("inbox", &["mail", "inbox"])];
+
+/// One candidate command path (e.g. `["mail", "send"]`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandSuggestion {
+    pub path: Vec<String>,
+    pub score: usize,
+}
+
+impl CommandSuggestion {
+    /// Render as a full invocation (`am mail send`).
+    #[must_use]
+    pub fn invocation(&self, bin: &str) -> String {
+        let mut out = bin.to_string();
+        for seg in &self.path {
+            out.push(' ');
+            out.push_str(seg);
+        }
+        out
+    }
+}
+
+/// Rewrite misnomer group names in `args` (argv including argv0).
+///
+/// Returns `true` when a rewrite happened.
+pub fn rewrite_misnomer_aliases(cmd: &clap::Command, args: &mut Vec<OsString>) -> bool {
+    rewrite_misnomer_command(cmd, args) || rewrite_misnomer_group(cmd, args)
+}
+
+fn rewrite_misnomer_command(cmd: &clap::Command, args: &mut Vec<OsString>) -> bool {
+    let Some(first) = args.get(1).and_then(|arg| arg.to_str()) else {
+        return false;
+    };
+    let Some((_, path)) = MISNOMER_COMMAND_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == first)
+    else {
+        return false;
+    };
+    if cmd.clone().try_get_matches_from(args.iter()).is_ok() {
+        return false;
+    }
+    let mut rewritten: Vec<OsString> = Vec::with_capacity(args.len() + path.len());
+    rewritten.push(args[0].clone());
+    rewritten.extend(path.iter().map(OsString::from));
+    rewritten.extend(args[2..].iter().cloned());
+    if cmd.clone().try_get_matches_from(rewritten.iter()).is_err() {
+        return false;
+    }
+    *args = rewritten;
+    true
+}
+
+fn rewrite_misnomer_group(cmd: &clap::Command, args: &mut [OsString]) -> bool {
+    let (Some(first), Some(second)) = (args.get(1), args.get(2)) else {
+        return false;
+    };
+    let (Some(first), Some(second)) = (first.to_str(), second.to_str()) else {
+        return false;
+    };
+    let Some((_, canonical)) = MISNOMER_ALIASES.iter().find(|(alias, _)| *alias == first) else {
+        return false;
+    };
+    let Some(group) = find_subcommand(cmd, canonical) else {
+        return false;
+    };
+    if find_subcommand(group, second).is_none() {
+        return false;
+    }
+    args[1] = OsString::from(*canonical);
+    true
+}
+
+/// Whether a clap error kind is one we try to enrich with suggestions.
+#[must_use]
+pub const fn is_suggestible(kind: ErrorKind) -> bool {
+    matches!(
+        kind,
+        ErrorKind::InvalidSubcommand | ErrorKind::UnknownArgument
+    )
+}
+
+/// Compute nearest command paths for the positional tokens in `args`.
+///
+/// `args` excludes argv0. Parsing stops at the first flag-like token. If
+/// every positional resolves to a real subcommand there is nothing to
+/// suggest and an empty list is returned.
+#[must_use]
+pub fn suggest_command_paths(cmd: &clap::Command, args: &[String]) -> Vec<CommandSuggestion> {
+    let typed: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .take_while(|tok| !tok.starts_with('-'))
+        .collect();
+
+    // Walk the tree as far as the typed tokens resolve.
+    let mut node = cmd;
+    let mut resolved = 0;
+    for tok in &typed {
+        match find_subcommand(node, tok) {
+            Some(sub) => {
+                node = sub;
+                resolved += 1;
+            }
+            None => break,
+        }
+    }
+    if resolved == typed.len() {
+        return Vec::new();
+    }
+
+    // Query: the last resolved segment (if any) plus the first unresolved
+    // token and, when present, the token after it. This covers both a
+    // misspelled leaf (`mail snd`) and a wrong group (`reservations list`).
+    let start = resolved.saturating_sub(1);
+    let end = (resolved + 2).min(typed.len());
+    let query: Vec<&str> = typed[start..end].to_vec();
+
+    let mut paths = Vec::new();
+    collect_paths(cmd, &mut Vec::new(), &mut paths);
+
+    let mut scored: Vec<CommandSuggestion> = paths
+        .into_iter()
+        .filter_map(|path| score_path(&query, &path).map(|score| CommandSuggestion { path, score }))
+        .collect();
+    scored.sort_by(|a, b| a.score.cmp(&b.score).then_with(|| a.path.cmp(&b.path)));
+    scored.dedup_by(|a, b| a.path == b.path);
+    scored.truncate(MAX_SUGGESTIONS);
+    scored
+}
+
+/// Render the "did you mean" block for a failed parse, or `None` when there
+/// is nothing useful to say.
+#[must_use]
+pub fn render_suggestions(cmd: &clap::Command, bin: &str, args: &[String]) -> Option<String> {
+    let suggestions = suggest_command_paths(cmd, args);
+    let best = suggestions.first()?;
+
+    let mut out = String::from("\ndid you mean:\n");
+    for suggestion in &suggestions {
+        out.push_str("  ");
+        out.push_str(&suggestion.invocation(bin));
+        out.push('\n');
+    }
+    if let Some(usage) = usage_line(cmd, &best.path) {
+        out.push('\n');
+        out.push_str("usage: ");
+        out.push_str(&usage);
+        out.push('\n');
+    }
+    Some(out)
+}
+
+fn usage_line(cmd: &clap::Command, path: &[String]) -> Option<String> {
+    let mut built = cmd.clone();
+    built.build();
+    let mut node = &mut built;
+    for seg in path {
+        node = node.find_subcommand_mut(seg)?;
+    }
+    let rendered = node.render_usage().to_string();
+    let line = rendered.lines().next()?.trim();
+    Some(
+        line.strip_prefix("Usage:")
+            .unwrap_or(line)
+            .trim()
+            .to_string(),
+    )
+}
+
+fn find_subcommand<'a>(cmd: &'a clap::Command, name: &str) -> Option<&'a clap::Command> {
+    cmd.get_subcommands()
+        .find(|sub| sub.get_name() == name || sub.get_all_aliases().any(|alias| alias == name))
+}
+
+fn collect_paths(cmd: &clap::Command, prefix: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() || sub.get_name() == "help" {
+            continue;
+        }
+        prefix.push(sub.get_name().to_string());
+        out.push(prefix.clone());
+        collect_paths(sub, prefix, out);
+        prefix.pop();
+    }
+}
+
+/// Score `path` against `query`; lower is better, `None` means no match.
+///
+/// Paths of the same depth are compared segment by segment. A path one
+/// segment deeper may match with its first segment treated as an omitted
+/// group (`am snd` -> `am mail send`), and a path one segment shallower may
+/// match the typed prefix (`am reservatons list` -> `am reservations`), both
+/// at a fixed extra cost.
+fn score_path(query: &[&str], path: &[String]) -> Option<usize> {
+    if path.len() == query.len() {
+        return score_segments(query, path);
+    }
+    if path.len() == query.len() + 1 {
+        return score_segments(query, &path[1..]).map(|score| score + 2);
+    }
+    if query.len() > 1 && path.len() == query.len() - 1 {
+        return score_segments(&query[..path.len()], path).map(|score| score + 2);
+    }
+    None
+}
+
+fn score_segments(query: &[&str], path: &[String]) -> Option<usize> {
+    let mut total = 0;
+    let mut exact = 0;
+    for (typed, candidate) in query.iter().zip(path) {
+        let cost = segment_distance(typed, candidate)?;
+        if cost == 0 {
+            exact += 1;
+        }
+        total += cost;
+    }
+    // A path that matches exactly everywhere is what was typed; it cannot be
+    // the fix.
+    (exact < query.len()).then_some(total)
+}
+
+fn segment_distance(typed: &str, candidate: &str) -> Option<usize> {
+    let typed = normalize_segment(typed);
+    let candidate = normalize_segment(candidate);
+    if typed == candidate {
+        return Some(0);
+    }
+    // `reservations` vs `file_reservations`, `reserve` vs `reservations`.
+    if typed.len() >= 4 && (candidate.contains(&typed) || typed.contains(&candidate)) {
+        return Some(1);
+    }
+    let distance = levenshtein_distance(&typed, &candidate);
+    let budget = (typed.chars().count().max(candidate.chars().count()) / 3).max(1);
+    (distance <= budget).then_some(distance)
+}
+
+fn normalize_segment(seg: &str) -> String {
+    seg.trim().to_ascii_lowercase().replace('_', "-")
+}
+
+/// Return up to `limit` candidates within a third of `typed`'s length in edit
+/// distance (case-insensitive), closest first, ties broken by name.
+pub(crate) fn closest_names(typed: &str, candidates: &[String], limit: usize) -> Vec<String> {
+    let typed = typed.trim().to_ascii_lowercase();
+    if typed.is_empty() {
+        return Vec::new();
+    }
+    let budget = (typed.chars().count() / 3).max(1);
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .filter_map(|candidate| {
+            let distance = levenshtein_distance(&typed, &candidate.to_ascii_lowercase());
+            (distance <= budget).then_some((distance, candidate))
+        })
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, name)| name.clone())
+        .collect()
+}
+
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    if a == b {
+        return 0;
+    }
+    if a.is_empty() {
+        return b.chars().count();
+    }
+    if b.is_empty() {
+        return a.chars().count();
+    }
+
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut cur_row = vec![0; b_chars.len() + 1];
+
+    for (i, a_ch) in a.chars().enumerate() {
+        cur_row[0] = i + 1;
+        for (j, b_ch) in b_chars.iter().enumerate() {
+            let cost = usize::from(a_ch != *b_ch);
+            cur_row[j + 1] = (prev_row[j + 1] + 1)
+                .min(cur_row[j] + 1)
+                .min(prev_row[j] + cost);
+        }
+        prev_row.copy_from_slice(&cur_row);
+    }
+    prev_row[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn cli() -> clap::Command {
+        crate::Cli::command()
+    }
+
+    fn args(tokens: &[&str]) -> Vec<String> {
+        tokens.iter().map(ToString::to_string).collect()
+    }
+
+    fn best(tokens: &[&str]) -> Option<String> {
+        suggest_command_paths(&cli(), &args(tokens))
+            .first()
+            .map(|s| s.invocation("am"))
+    }
+
+    #[test]
+    fn misspelled_nested_leaf_suggests_sibling() {
+        assert_eq!(best(&["mail", "snd"]).as_deref(), Some("am mail send"));
+    }
+
+    #[test]
+    fn wrong_group_name_suggests_canonical_group() {
+        assert_eq!(
+            best(&["reservations", "list"]).as_deref(),
+            Some("am file_reservations list")
+        );
+    }
+
+    #[test]
+    fn omitted_group_suggests_nested_command() {
+        assert_eq!(
+            best(&["summarize-thred"]).as_deref(),
+            Some("am mail summarize-thread")
+        );
+    }
+
+    #[test]
+    fn misspelled_group_with_unknown_leaf_suggests_group() {
+        assert_eq!(
+            best(&["reservatons", "list"]).as_deref(),
+            Some("am reservations")
+        );
+    }
+
+    #[test]
+    fn fully_resolved_tokens_suggest_nothing() {
+        assert!(suggest_command_paths(&cli(), &args(&["mail", "send", "--bogus"])).is_empty());
+    }
+
+    #[test]
+    fn flags_stop_positional_walk() {
+        assert!(suggest_command_paths(&cli(), &args(&["--bogus", "mail"])).is_empty());
+    }
+
+    #[test]
+    fn unrelated_token_has_no_suggestion() {
+        assert!(best(&["zzzzqqqq"]).is_none());
+    }
+
+    #[test]
+    fn misnomer_rewrite_requires_real_subcommand() {
+        let cmd = cli();
+        let mut rewritten: Vec<OsString> = ["am", "reservations", "list", "proj"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        assert!(rewrite_misnomer_aliases(&cmd, &mut rewritten));
+        assert_eq!(rewritten[1], OsString::from("file_reservations"));
+
+        let mut untouched: Vec<OsString> = ["am", "reservations", "--all"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        assert!(!rewrite_misnomer_aliases(&cmd, &mut untouched));
+        assert_eq!(untouched[1], OsString::from("reservations"));
+    }
+
+    #[test]
+    fn inbox_misnomer_routes_to_mail_inbox_only_when_robot_inbox_rejects_it() {
+        let cmd = cli();
+        let mut rewritten: Vec<OsString> = ["am", "inbox", "-p", "proj", "-a", "BlueLake"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        assert!(rewrite_misnomer_aliases(&cmd, &mut rewritten));
+        let expected: Vec<OsString> = ["am", "mail", "inbox", "-p", "proj", "-a", "BlueLake"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        assert_eq!(rewritten, expected);
+
+        let mut untouched: Vec<OsString> = ["am", "inbox", "--project", "proj", "--urgent"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        assert!(!rewrite_misnomer_aliases(&cmd, &mut untouched));
+        assert_eq!(untouched[1], OsString::from("inbox"));
+    }
+
+    #[test]
+    fn levenshtein_basic() {
+        assert_eq!(levenshtein_distance("send", "snd"), 1);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+        assert_eq!(levenshtein_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn closest_names_ranks_by_distance_then_name() {
+        let names: Vec<String> = ["BlueLake", "BlueLark", "GreenCastle", "BlueLakes"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            closest_names("bluelake", &names, 3),
+            vec!["BlueLake", "BlueLakes", "BlueLark"]
+        );
+        assert_eq!(closest_names("BluLake", &names, 1), vec!["BlueLake"]);
+        assert!(closest_names("Zzzzzz", &names, 3).is_empty());
+    }
+}
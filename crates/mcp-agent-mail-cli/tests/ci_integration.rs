@@ -296,10 +296,10 @@ fn test_default_gates_count() {
 
     let gates = default_gates();
     // Keep in lockstep with the registry unit test in ci.rs
-    // (`assert_eq!(gates.len(), 20, ...)`) — this integration copy went stale
+    // (`assert_eq!(gates.len(), 21, ...)`) — this integration copy went stale
     // at 19 when the N1 reliability-coverage gate landed (0ce61f9a fixed only
     // the unit-test site).
-    assert_eq!(gates.len(), 20, "should have 20 default gates");
+    assert_eq!(gates.len(), 21, "should have 21 default gates");
 }
 
 #[test]
@@ -938,6 +938,85 @@ fn assert_json_snapshot(env: &TestEnv, case: &str, cwd: Option<&Path>, args: &[&
     }
 }
 
+/// Run `args` twice against the same fixture and require byte-identical
+/// stdout: agents diff consecutive outputs, so any ordering that leaks from
+/// hash maps, directory listings, or unordered SQL shows up here.
+fn assert_deterministic_output(env: &TestEnv, case: &str, cwd: Option<&Path>, args: &[&str]) {
+    let (status, first, stderr) = run_json_cmd(env, cwd, args);
+    assert!(
+        status.success(),
+        "expected success for {case} args={args:?}, got status={:?}\nstdout:\n{first}\nstderr:\n{stderr}",
+        status.code()
+    );
+    let (status, second, stderr) = run_json_cmd(env, cwd, args);
+    assert!(
+        status.success(),
+        "second run of {case} failed: status={:?}\nstderr:\n{stderr}",
+        status.code()
+    );
+    if first != second {
+        write_artifact(&format!("{case}_first"), &first);
+        write_artifact(&format!("{case}_second"), &second);
+        panic!(
+            "nondeterministic output for {case} ({args:?})\n\n{}",
+            unified_diff(&first, &second)
+        );
+    }
+}
+
+/// Rows whose natural sort keys tie, so only an explicit tie-breaker keeps
+/// the listings stable.
+fn seed_ordering_ties(env: &TestEnv) {
+    use mcp_agent_mail_db::sqlmodel::Value as SqlValue;
+
+    let tie_ts = 1_704_067_200_000_000i64;
+    let far_future = 4_102_444_800_000_000i64; // 2100-01-01T00:00:00Z
+    let conn = mcp_agent_mail_db::DbConn::open_file(env.db_path.display().to_string())
+        .expect("open sqlite db");
+    for (a_project, a_agent, b_project, b_agent) in [(1, 1, 1, 3), (1, 1, 2, 4), (1, 3, 1, 1)] {
+        conn.execute_sync(
+            "INSERT INTO agent_links (a_project_id, a_agent_id, b_project_id, b_agent_id, \
+             status, reason, created_ts, updated_ts) VALUES (?, ?, ?, ?, 'approved', 'tie', ?, ?)",
+            &[
+                SqlValue::BigInt(a_project),
+                SqlValue::BigInt(a_agent),
+                SqlValue::BigInt(b_project),
+                SqlValue::BigInt(b_agent),
+                SqlValue::BigInt(tie_ts),
+                SqlValue::BigInt(tie_ts),
+            ],
+        )
+        .unwrap();
+    }
+    for (agent, pattern) in [(1, "src/**"), (3, "src/lib.rs"), (1, "src/main.rs")] {
+        conn.execute_sync(
+            "INSERT INTO file_reservations (project_id, agent_id, path_pattern, \"exclusive\", \
+             reason, created_ts, expires_ts) VALUES (1, ?, ?, 1, 'tie', ?, ?)",
+            &[
+                SqlValue::BigInt(agent),
+                SqlValue::Text(pattern.to_string()),
+                SqlValue::BigInt(tie_ts),
+                SqlValue::BigInt(far_future),
+            ],
+        )
+        .unwrap();
+    }
+    close_and_checkpoint_seeded_db(conn, &env.db_path, "seed_ordering_ties");
+
+    let backups = env.storage_root().join("backups");
+    std::fs::create_dir_all(&backups).expect("create backups dir");
+    let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_704_067_200);
+    for name in ["zeta.sqlite3", "alpha.sqlite3", "mid.sqlite3.bak.1"] {
+        let path = backups.join(name);
+        std::fs::write(&path, b"backup").expect("write backup");
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(mtime))
+            .expect("set backup mtime");
+    }
+}
+
 #[test]
 fn cli_json_snapshots() {
     // br-2ei.5.7.4: JSON output stability
@@ -1030,6 +1109,61 @@ fn cli_json_snapshots() {
     );
 }
 
+#[test]
+fn cli_json_outputs_are_deterministic() {
+    let env = TestEnv::new();
+    seed_cli_json_db(&env.db_path, env.tmp.path());
+    seed_archive_fixture(env.tmp.path());
+    seed_ordering_ties(&env);
+    let golden_dir = env.tmp.path().join("golden");
+    let golden_dir = golden_dir.display().to_string();
+
+    let cases: [(&str, Option<&Path>, Vec<&str>); 7] = [
+        (
+            "list_projects",
+            None,
+            vec!["list-projects", "--include-agents", "--json"],
+        ),
+        (
+            "setup_status",
+            None,
+            vec!["setup", "status", "--json", "--no-user-config"],
+        ),
+        ("doctor_backups", None, vec!["doctor", "backups", "--json"]),
+        (
+            "golden_list",
+            None,
+            vec!["golden", "list", "--dir", &golden_dir, "--json"],
+        ),
+        (
+            "archive_list",
+            Some(env.tmp.path()),
+            vec!["archive", "list", "--json"],
+        ),
+        (
+            "contacts_list",
+            None,
+            vec![
+                "contacts",
+                "list",
+                "--project",
+                "proj-alpha",
+                "--agent",
+                "GreenCastle",
+                "--json",
+            ],
+        ),
+        (
+            "reservation_conflicts",
+            None,
+            vec!["file_reservations", "conflicts", "proj-alpha", "src/lib.rs"],
+        ),
+    ];
+    for (case, cwd, args) in &cases {
+        assert_deterministic_output(&env, case, *cwd, args);
+    }
+}
+
 #[test]
 fn cli_acks_smoke() {
     // br-2ei.5.7.2: regression guard for `acks` / `list-acks` (ensure DB schema joins are valid).
@@ -0,0 +1,214 @@
+//! `am serve-http --no-tui` graceful shutdown, against a real spawned server.
+//!
+//! SIGTERM must stop the listener without resetting a request that is already
+//! in flight: the request is held open by dribbling its body, the signal is
+//! delivered mid-request, and the request must still be answered before the
+//! process exits 0. A second signal during the drain force-exits with the
+//! distinct forced-shutdown code.
+
+#![cfg(unix)]
+#![forbid(unsafe_code)]
+
+use std::io::{Read as _, Write as _};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use nix::sys::signal::{Signal, kill};
+use nix::unistd::Pid;
+
+const TEST_BEARER_TOKEN: &str = "graceful-shutdown-token";
+/// Mirrors `mcp_agent_mail_server::graceful_shutdown::FORCED_SHUTDOWN_EXIT_CODE`.
+const FORCED_SHUTDOWN_EXIT_CODE: i32 = 3;
+const MCP_BODY: &str = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list","params":{}}"#;
+
+fn pick_free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|l| l.local_addr())
+        .map(|a| a.port())
+        .expect("pick a free port")
+}
+
+fn healthz_ok(port: u16) -> bool {
+    let Ok(addr) = format!("127.0.0.1:{port}").parse::<SocketAddr>() else {
+        return false;
+    };
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, Duration::from_secs(2)) else {
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let request =
+        format!("GET /healthz HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n\r\n");
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+    let mut buf = [0_u8; 64];
+    let n = stream.read(&mut buf).unwrap_or(0);
+    String::from_utf8_lossy(&buf[..n]).starts_with("HTTP/1.1 200")
+}
+
+fn spawn_server(am_bin: &Path, work: &Path, port: u16) -> std::io::Result<Child> {
+    let db_path = work.join("storage.sqlite3");
+    let storage_root = work.join("archive");
+    std::fs::create_dir_all(&storage_root)?;
+    Command::new(am_bin)
+        .args(["serve-http", "--no-tui"])
+        .current_dir(work)
+        .env("HTTP_BEARER_TOKEN", TEST_BEARER_TOKEN)
+        .env_remove("HTTP_JWT_ENABLED")
+        .env("DATABASE_URL", format!("sqlite:///{}", db_path.display()))
+        .env("STORAGE_ROOT", &storage_root)
+        .env("HTTP_HOST", "127.0.0.1")
+        .env("HTTP_PORT", port.to_string())
+        .env("TUI_ENABLED", "false")
+        .env("AGENT_MAIL_SHUTDOWN_DRAIN_SECONDS", "30")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+struct ChildGuard(Child);
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+impl ChildGuard {
+    fn signal(&self, signal: Signal) {
+        let pid = i32::try_from(self.0.id()).expect("pid fits i32");
+        kill(Pid::from_raw(pid), signal).expect("deliver signal");
+    }
+
+    fn wait_with_deadline(&mut self, deadline: Duration) -> ExitStatus {
+        let start = Instant::now();
+        loop {
+            if let Some(status) = self.0.try_wait().expect("poll server process") {
+                return status;
+            }
+            assert!(
+                start.elapsed() < deadline,
+                "server did not exit within {deadline:?} of the shutdown signal"
+            );
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
+
+/// Spawn a server and wait for `/healthz`, retrying port picks on collisions.
+fn start_ready_server(am_bin: &Path, work: &Path) -> (ChildGuard, u16) {
+    for _attempt in 0..3 {
+        let port = pick_free_port();
+        let mut guard = ChildGuard(spawn_server(am_bin, work, port).expect("spawn am serve-http"));
+        let deadline = Instant::now() + Duration::from_secs(120);
+        while Instant::now() < deadline {
+            if healthz_ok(port) {
+                return (guard, port);
+            }
+            if let Ok(Some(_)) = guard.0.try_wait() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(500));
+        }
+    }
+    panic!("server did not become ready on any attempted port");
+}
+
+/// Open `POST /mcp` and send everything except the tail of the body, so the
+/// server holds the request in flight until [`finish_slow_request`].
+fn begin_slow_request(port: u16) -> TcpStream {
+    let addr: SocketAddr = format!("127.0.0.1:{port}").parse().expect("addr");
+    let mut stream =
+        TcpStream::connect_timeout(&addr, Duration::from_secs(10)).expect("connect to server");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .expect("read timeout");
+    let head = format!(
+        "POST /mcp HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nAuthorization: Bearer {TEST_BEARER_TOKEN}\r\n\
+         Content-Type: application/json\r\nAccept: application/json, text/event-stream\r\n\
+         Connection: close\r\nContent-Length: {}\r\n\r\n",
+        MCP_BODY.len()
+    );
+    stream.write_all(head.as_bytes()).expect("write head");
+    stream
+        .write_all(&MCP_BODY.as_bytes()[..MCP_BODY.len() / 2])
+        .expect("write first half of body");
+    stream.flush().expect("flush");
+    stream
+}
+
+/// Send the rest of the body and return the response status line.
+fn finish_slow_request(mut stream: TcpStream) -> Option<String> {
+    stream
+        .write_all(&MCP_BODY.as_bytes()[MCP_BODY.len() / 2..])
+        .ok()?;
+    let mut buf = Vec::new();
+    let mut chunk = [0_u8; 4096];
+    while !buf.contains(&b'\n') {
+        match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+    let text = String::from_utf8_lossy(&buf);
+    text.lines()
+        .next()
+        .filter(|line| line.starts_with("HTTP/"))
+        .map(str::to_string)
+}
+
+#[test]
+fn sigterm_drains_in_flight_request_and_exits_zero() {
+    let am_bin = Path::new(env!("CARGO_BIN_EXE_am"));
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (mut guard, port) = start_ready_server(am_bin, dir.path());
+
+    let stream = begin_slow_request(port);
+    std::thread::sleep(Duration::from_millis(300));
+    guard.signal(Signal::SIGTERM);
+
+    // Give the supervisor time to notice and start draining; the process must
+    // still be alive because a request is in flight.
+    std::thread::sleep(Duration::from_secs(1));
+    assert!(
+        guard.0.try_wait().expect("poll server").is_none(),
+        "server exited while a request was still in flight"
+    );
+
+    let status_line =
+        finish_slow_request(stream).expect("in-flight request was reset instead of answered");
+    assert!(
+        !status_line.contains(" 503"),
+        "drained request was rejected instead of served: {status_line}"
+    );
+
+    let status = guard.wait_with_deadline(Duration::from_secs(60));
+    assert_eq!(
+        status.code(),
+        Some(0),
+        "graceful shutdown exit status: {status:?}"
+    );
+}
+
+#[test]
+fn second_signal_during_drain_forces_exit() {
+    let am_bin = Path::new(env!("CARGO_BIN_EXE_am"));
+    let dir = tempfile::tempdir().expect("tempdir");
+    let (mut guard, port) = start_ready_server(am_bin, dir.path());
+
+    let _stream = begin_slow_request(port);
+    std::thread::sleep(Duration::from_millis(300));
+    guard.signal(Signal::SIGTERM);
+    std::thread::sleep(Duration::from_secs(1));
+    guard.signal(Signal::SIGINT);
+
+    let status = guard.wait_with_deadline(Duration::from_secs(10));
+    assert_eq!(
+        status.code(),
+        Some(FORCED_SHUTDOWN_EXIT_CODE),
+        "forced shutdown exit status: {status:?}"
+    );
+}
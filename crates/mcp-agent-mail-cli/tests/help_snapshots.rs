@@ -184,3 +184,85 @@ fn cli_help_snapshots() {
         assert_help_snapshot(case, args);
     }
 }
+
+fn suggestions_fixtures_dir() -> PathBuf {
+    repo_root().join("tests/fixtures/cli_suggestions")
+}
+
+/// Run a failing invocation and return the "did you mean" block from stderr.
+fn run_suggestion(args: &[&str]) -> String {
+    let out = Command::new(am_bin())
+        .args(args)
+        .env("COLUMNS", "120")
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("failed to spawn am");
+
+    assert_eq!(
+        out.status.code(),
+        Some(2),
+        "expected clap usage exit for args={args:?}\nstderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    let start = stderr
+        .find("did you mean:")
+        .unwrap_or_else(|| panic!("no suggestion block for args={args:?}\nstderr:\n{stderr}"));
+    stderr[start..].to_string()
+}
+
+#[test]
+fn cli_suggestion_snapshots() {
+    // Near-miss subcommand suggestions over the full command tree.
+    let cases: &[(&str, &[&str])] = &[
+        ("mail_snd", &["mail", "snd", "--project", "p"]),
+        ("acks_pendng", &["acks", "pendng", "proj", "agent"]),
+        (
+            "file_reservation_list",
+            &["file_reservation", "list", "proj"],
+        ),
+        ("snd", &["snd"]),
+        ("reservatons_list", &["reservatons", "list"]),
+    ];
+
+    let update = std::env::var("UPDATE_CLI_HELP_SNAPSHOTS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .is_some();
+
+    for (case, args) in cases {
+        let actual = normalize_help(run_suggestion(args));
+        let fixture_path = suggestions_fixtures_dir().join(format!("{case}.txt"));
+        match read_fixture(&fixture_path) {
+            Some(expected) if normalize_help(expected.clone()) == actual => {}
+            _ if update => write_fixture(&fixture_path, &actual),
+            Some(expected) => {
+                write_artifact(case, &actual);
+                let diff = unified_diff(&normalize_help(expected), &actual);
+                panic!(
+                    "suggestion snapshot mismatch for {case} ({args:?})\n\
+                     Hint: set UPDATE_CLI_HELP_SNAPSHOTS=1 to update fixtures\n\n{diff}"
+                );
+            }
+            None => {
+                write_artifact(case, &actual);
+                panic!(
+                    "missing suggestion fixture {path}\n\
+                     Hint: generate fixtures with UPDATE_CLI_HELP_SNAPSHOTS=1",
+                    path = fixture_path.display()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn reservations_misnomer_routes_to_file_reservations() {
+    // `am reservations list --help` must render the file_reservations help
+    // rather than failing on the snapshot command's unknown argument.
+    let help = run_help(&["reservations", "list", "--help"]);
+    assert!(
+        help.contains("file_reservations list"),
+        "expected file_reservations list help, got:\n{help}"
+    );
+}
@@ -2274,6 +2274,7 @@ fn guard_check_conflict_exits_1_when_not_advisory() {
 
     // Active exclusive reservation held by someone else.
     let reservation = serde_json::json!({
+        "id": 7,
         "path_pattern": "foo.txt",
         "agent_name": "OtherAgent",
         "exclusive": true,
@@ -2285,6 +2286,20 @@ fn guard_check_conflict_exits_1_when_not_advisory() {
         serde_json::to_string_pretty(&reservation).unwrap(),
     )
     .expect("write reservation");
+    std::fs::write(repo.join("project.json"), r#"{"slug": "guard-proj"}"#)
+        .expect("write project.json");
+    std::fs::create_dir_all(repo.join("agents").join("OtherAgent")).expect("create agent dir");
+    let profile = serde_json::json!({
+        "name": "OtherAgent",
+        "program": "codex-cli",
+        "model": "gpt-5",
+        "last_active_ts": "2026-01-01T00:00:00+00:00",
+    });
+    std::fs::write(
+        repo.join("agents").join("OtherAgent").join("profile.json"),
+        profile.to_string(),
+    )
+    .expect("write profile");
 
     let repo_str = repo.to_string_lossy().to_string();
     let out = run_am(
@@ -2300,11 +2315,21 @@ fn guard_check_conflict_exits_1_when_not_advisory() {
         String::from_utf8_lossy(&out.stdout),
         String::from_utf8_lossy(&out.stderr)
     );
+    let stderr = String::from_utf8_lossy(&out.stderr);
     assert!(
-        String::from_utf8_lossy(&out.stderr).contains("CONFLICT: pattern"),
-        "expected conflict marker in stderr, got:\n{}",
-        String::from_utf8_lossy(&out.stderr)
+        stderr.contains("CONFLICT: pattern"),
+        "expected conflict marker in stderr, got:\n{stderr}"
     );
+    for expected in [
+        "codex-cli/gpt-5, exclusive, last active 2026-01-01T00:00:00+00:00",
+        "am file_reservations release guard-proj OtherAgent --ids 7",
+        "send mail to OtherAgent",
+    ] {
+        assert!(
+            stderr.contains(expected),
+            "expected {expected:?} in stderr, got:\n{stderr}"
+        );
+    }
 }
 
 #[test]
@@ -5013,3 +5038,33 @@ fn doctor_repair_dry_run_exits_zero() {
         String::from_utf8_lossy(&out.stderr)
     );
 }
+
+#[test]
+fn completion_commands_ignore_profiles() {
+    let env = TestEnv::new();
+    let mut vars = env.isolated_env();
+    vars.push(("AM_PROFILE".to_string(), "missing".to_string()));
+
+    for args in [
+        &["completions", "bash"][..],
+        &["__complete", "benchmarks", ""][..],
+    ] {
+        let out = run_am(&vars, Some(env.tmp.path()), args, None);
+        assert!(
+            out.status.success(),
+            "{args:?} must not load the profile\nstdout:\n{}\nstderr:\n{}",
+            String::from_utf8_lossy(&out.stdout),
+            String::from_utf8_lossy(&out.stderr)
+        );
+    }
+    let out = run_am(
+        &vars,
+        Some(env.tmp.path()),
+        &["config", "list", "--json"],
+        None,
+    );
+    assert!(
+        !out.status.success(),
+        "other commands still need the profile"
+    );
+}
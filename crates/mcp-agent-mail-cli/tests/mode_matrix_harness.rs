@@ -326,6 +326,7 @@ const CLI_ALLOW_COMMANDS: &[&[&str]] = &[
     &["migrate", "--help"],
     &["list-projects", "--help"],
     &["clear-and-reset-everything", "--help"],
+    &["completions", "--help"],
     &["config", "--help"],
     &["amctl", "--help"],
     &["projects", "--help"],
@@ -339,6 +340,7 @@ const CLI_ALLOW_COMMANDS: &[&[&str]] = &[
     &["contacts", "--help"],
     &["beads", "--help"],
     &["file_reservations", "--help"],
+    &["init", "--help"],
     &["setup", "--help"],
     &["golden", "--help"],
     &["flake-triage", "--help"],
@@ -386,6 +388,7 @@ const MCP_DENY_COMMANDS: &[&[&str]] = &[
     &["products"],
     &["file_reservations"],
     &["beads"],
+    &["init"],
     &["setup"],
     &["golden"],
     &["flake-triage"],
@@ -3,11 +3,13 @@
 use std::path::{Path, PathBuf};
 
 use mcp_agent_mail_cli::robot::{
-    AnomalyCard, AttachmentInfo, FacetEntry, MessageContext, OutputFormat, ReservationEntry,
-    RobotEnvelope, SearchData, SearchResult, SearchRouteDiagnostic, StatusData,
-    SwarmTopologyCoverage, SwarmTopologyEdge, SwarmTopologyHotspot, SwarmTopologyNode,
-    SwarmTopologySummary, ThreadMessage, ThreadSummary, format_output, format_output_md,
+    AnomalyCard, AttachmentInfo, FacetEntry, HeldReservation, MessageContext, OutputFormat,
+    ReservationEntry, RobotEnvelope, SearchData, SearchResult, SearchRouteDiagnostic,
+    StatusAgentTask, StatusData, StatusUnreadMessage, SwarmTopologyCoverage, SwarmTopologyEdge,
+    SwarmTopologyHotspot, SwarmTopologyNode, SwarmTopologySummary, ThreadMessage, ThreadSummary,
+    format_output, format_output_md,
 };
+use mcp_agent_mail_core::HealthLevel;
 use mcp_agent_mail_db::query_assistance::{AppliedFilterHint, DidYouMeanHint};
 use mcp_agent_mail_db::search_planner::{RecoverySuggestion, ZeroResultGuidance};
 use serde::Serialize;
@@ -62,6 +64,30 @@ fn status_envelope() -> RobotEnvelope<StatusData> {
             reservations_expiring_soon: 0,
             active_agents: 2,
             recent_messages: 3,
+            health_level: HealthLevel::Green,
+            shedding_enabled: false,
+            safe_mode: false,
+            top_unread: Some(vec![StatusUnreadMessage {
+                id: 101,
+                from: "BlueLake".to_string(),
+                subject: "Freeze robot output".to_string(),
+                importance: "high".to_string(),
+                created_at: "2026-01-02T03:04:00Z".to_string(),
+            }]),
+            pending_ack_ids: Some(vec![101]),
+            held_reservations: Some(vec![HeldReservation {
+                id: 7,
+                path: "crates/mcp-agent-mail-cli/src/**".to_string(),
+                exclusive: true,
+                expires_at: "2026-01-02T04:04:05Z".to_string(),
+                remaining_seconds: 3600,
+            }]),
+            agent_tasks: vec![StatusAgentTask {
+                agent: "BlueLake".to_string(),
+                task: "migrating auth schema".to_string(),
+                task_set: "3h ago".to_string(),
+                task_age_seconds: 10_800,
+            }],
             my_reservations: vec![ReservationEntry {
                 agent: Some("RedFox".to_string()),
                 path: "crates/mcp-agent-mail-cli/src/**".to_string(),
@@ -238,6 +238,7 @@ fn error_code_catalog_is_stable() {
     let actual = collect_declared_error_codes();
     let expected: BTreeSet<String> = [
         "ACK_INTENT_WRITE_FAILED",
+        "AGENT_NAME_RESERVED",
         "AGENT_NOT_FOUND",
         "ARCHIVE_ERROR",
         "BROADCAST_DISABLED",
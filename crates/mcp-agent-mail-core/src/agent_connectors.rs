@@ -0,0 +1,643 @@
+//! User-defined agent connectors and the detection capability matrix.
+//!
+//! Built-in detection comes from [`crate::agent_detect`]. In-house agent CLIs
+//! can be described in `~/.config/agent-mail/connectors.toml`:
+//!
+//! ```toml
+//! [[connector]]
+//! name = "Acme Agent"
+//! slug = "acme"
+//! binaries = ["acme-agent"]
+//! globs = ["~/.acme/settings.json"]
+//! config_path = "{project_dir}/.acme/mcp.json"
+//! servers_key = "mcpServers"
+//!
+//! [connector.capabilities]
+//! mcp_http = true
+//! stdio = true
+//! hooks = false
+//! ```
+//!
+//! An invalid entry is reported in [`ConnectorDefinitions::errors`] and
+//! skipped; it never aborts detection.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent_detect::InstalledAgentDetectionReport;
+use crate::setup::AgentPlatform;
+
+/// File name of the user connector definitions.
+pub const CONNECTORS_FILE_NAME: &str = "connectors.toml";
+
+/// `servers_key` values a custom connector may use.
+const SERVERS_KEYS: &[&str] = &["mcpServers", "servers", "mcp", "mcp_servers"];
+
+/// Most paths a single glob may report.
+const MAX_GLOB_MATCHES: usize = 16;
+
+/// Deepest directory level a glob walk descends below its literal prefix.
+const MAX_GLOB_DEPTH: usize = 6;
+
+/// Report format of [`ConnectorMatrixReport`].
+pub const CONNECTOR_MATRIX_FORMAT_VERSION: u32 = 1;
+
+/// What a connector can be configured with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConnectorCapabilities {
+    /// Accepts an MCP streamable-HTTP server entry.
+    pub mcp_http: bool,
+    /// Accepts a stdio (`command` + `args`) server entry.
+    pub stdio: bool,
+    /// Runs agent hooks (e.g. `am check-inbox` on prompt submit).
+    pub hooks: bool,
+}
+
+/// Where a matrix entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorSource {
+    Builtin,
+    Custom,
+}
+
+/// A validated user-defined connector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CustomConnector {
+    pub name: String,
+    pub slug: String,
+    /// Executable names looked up on `PATH`, or absolute paths.
+    pub binaries: Vec<String>,
+    /// Path globs; `~` and `{home}` expand to the home directory.
+    pub globs: Vec<String>,
+    /// Config file `setup` merges the server entry into. `{home}` and
+    /// `{project_dir}` are substituted; relative paths are under the project.
+    pub config_path: Option<String>,
+    /// JSON object holding MCP servers in `config_path`.
+    pub servers_key: &'static str,
+    pub capabilities: ConnectorCapabilities,
+}
+
+/// An entry of the connectors file that was skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectorDefinitionError {
+    /// Zero-based position in the file; `None` when the whole file is bad.
+    pub index: Option<usize>,
+    pub slug: Option<String>,
+    pub error: String,
+}
+
+/// Connectors loaded from a connectors file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ConnectorDefinitions {
+    pub connectors: Vec<CustomConnector>,
+    pub errors: Vec<ConnectorDefinitionError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConnector {
+    name: Option<String>,
+    slug: Option<String>,
+    #[serde(default)]
+    binaries: Vec<String>,
+    #[serde(default)]
+    globs: Vec<String>,
+    config_path: Option<String>,
+    servers_key: Option<String>,
+    #[serde(default)]
+    capabilities: ConnectorCapabilities,
+}
+
+/// Default connectors file: `$XDG_CONFIG_HOME/agent-mail/connectors.toml`,
+/// falling back to `~/.config/agent-mail/connectors.toml`.
+#[must_use]
+pub fn default_connectors_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+        .map(|dir| dir.join("agent-mail").join(CONNECTORS_FILE_NAME))
+}
+
+/// Read and parse a connectors file.
+///
+/// # Errors
+///
+/// Returns the I/O error when the file cannot be read (including
+/// `NotFound`). Parse and validation problems are reported in
+/// [`ConnectorDefinitions::errors`] instead.
+pub fn load_connector_definitions(path: &Path) -> std::io::Result<ConnectorDefinitions> {
+    std::fs::read_to_string(path).map(|content| parse_connector_definitions(&content))
+}
+
+/// Parse connectors TOML, validating each `[[connector]]` on its own.
+#[must_use]
+pub fn parse_connector_definitions(content: &str) -> ConnectorDefinitions {
+    let mut definitions = ConnectorDefinitions::default();
+    let mut table = match content.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(error) => {
+            definitions.errors.push(ConnectorDefinitionError {
+                index: None,
+                slug: None,
+                error: format!("invalid TOML: {}", error.message()),
+            });
+            return definitions;
+        }
+    };
+    let entries = match table.remove("connector") {
+        None => return definitions,
+        Some(toml::Value::Array(entries)) => entries,
+        Some(_) => {
+            definitions.errors.push(ConnectorDefinitionError {
+                index: None,
+                slug: None,
+                error: "`connector` must be an array of tables ([[connector]])".to_string(),
+            });
+            return definitions;
+        }
+    };
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let slug_hint = entry
+            .get("slug")
+            .and_then(toml::Value::as_str)
+            .map(str::to_string);
+        let result = entry
+            .try_into::<RawConnector>()
+            .map_err(|error| error.message().to_string())
+            .and_then(|raw| validate_connector(raw, &definitions.connectors));
+        match result {
+            Ok(connector) => definitions.connectors.push(connector),
+            Err(error) => definitions.errors.push(ConnectorDefinitionError {
+                index: Some(index),
+                slug: slug_hint,
+                error,
+            }),
+        }
+    }
+    definitions
+}
+
+fn validate_connector(
+    raw: RawConnector,
+    accepted: &[CustomConnector],
+) -> Result<CustomConnector, String> {
+    let name = raw
+        .name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or("missing `name`")?;
+    let slug = raw
+        .slug
+        .map(|slug| slug.trim().to_ascii_lowercase())
+        .filter(|slug| !slug.is_empty())
+        .ok_or("missing `slug`")?;
+    if !slug
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    {
+        return Err(format!("slug `{slug}` may only contain a-z, 0-9 and `-`"));
+    }
+    if AgentPlatform::from_slug(&slug).is_some() {
+        return Err(format!("slug `{slug}` collides with a built-in connector"));
+    }
+    if accepted.iter().any(|existing| existing.slug == slug) {
+        return Err(format!("duplicate slug `{slug}`"));
+    }
+    let binaries: Vec<String> = raw
+        .binaries
+        .into_iter()
+        .map(|binary| binary.trim().to_string())
+        .filter(|binary| !binary.is_empty())
+        .collect();
+    let globs: Vec<String> = raw
+        .globs
+        .into_iter()
+        .map(|glob| glob.trim().to_string())
+        .filter(|glob| !glob.is_empty())
+        .collect();
+    if binaries.is_empty() && globs.is_empty() {
+        return Err("needs at least one of `binaries` or `globs`".to_string());
+    }
+    for glob in &globs {
+        globset::Glob::new(glob).map_err(|error| format!("invalid glob `{glob}`: {error}"))?;
+    }
+    let servers_key = match raw.servers_key.as_deref().map(str::trim) {
+        None | Some("") => SERVERS_KEYS[0],
+        Some(key) => SERVERS_KEYS
+            .iter()
+            .copied()
+            .find(|known| *known == key)
+            .ok_or_else(|| {
+                format!(
+                    "servers_key `{key}` must be one of {}",
+                    SERVERS_KEYS.join(", ")
+                )
+            })?,
+    };
+    let config_path = raw
+        .config_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+    if config_path.is_some() && !raw.capabilities.mcp_http && !raw.capabilities.stdio {
+        return Err("`config_path` needs capabilities.mcp_http or capabilities.stdio".to_string());
+    }
+
+    Ok(CustomConnector {
+        name,
+        slug,
+        binaries,
+        globs,
+        config_path,
+        servers_key,
+        capabilities: raw.capabilities,
+    })
+}
+
+/// Capabilities of a built-in connector, as far as `setup` configures it.
+#[must_use]
+pub fn builtin_capabilities(slug: &str) -> ConnectorCapabilities {
+    AgentPlatform::from_slug(slug).map_or_else(ConnectorCapabilities::default, |platform| {
+        platform.capabilities()
+    })
+}
+
+/// Outcome of probing one custom connector.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CustomDetection {
+    pub detected: bool,
+    pub evidence: Vec<String>,
+    pub root_paths: Vec<String>,
+}
+
+impl CustomConnector {
+    /// Look for the connector's binaries on `path_env` and its globs on disk.
+    #[must_use]
+    pub fn detect(&self, home: Option<&Path>, path_env: Option<&OsStr>) -> CustomDetection {
+        let mut detection = CustomDetection::default();
+        for binary in &self.binaries {
+            if let Some(found) = find_binary(binary, home, path_env) {
+                detection
+                    .evidence
+                    .push(format!("binary:{}", found.display()));
+                detection.root_paths.push(found.display().to_string());
+            }
+        }
+        for glob in &self.globs {
+            let matches = glob_paths(&expand_home(glob, home));
+            if !matches.is_empty() {
+                detection.evidence.push(format!("glob:{glob}"));
+                detection
+                    .root_paths
+                    .extend(matches.iter().map(|path| path.display().to_string()));
+            }
+        }
+        detection.detected = !detection.evidence.is_empty();
+        detection
+    }
+
+    /// Config file `setup` writes for this connector, if it has one.
+    #[must_use]
+    pub fn resolved_config_path(&self, home: Option<&Path>, project_dir: &Path) -> Option<PathBuf> {
+        let template = self.config_path.as_deref()?;
+        let expanded = expand_home(template, home)
+            .replace("{project_dir}", &project_dir.display().to_string());
+        let path = PathBuf::from(expanded);
+        Some(if path.is_absolute() {
+            path
+        } else {
+            project_dir.join(path)
+        })
+    }
+}
+
+fn expand_home(raw: &str, home: Option<&Path>) -> String {
+    let Some(home) = home else {
+        return raw.to_string();
+    };
+    let home = home.display().to_string();
+    let expanded = raw.replace("{home}", &home);
+    if expanded == "~" {
+        home
+    } else if let Some(rest) = expanded.strip_prefix("~/") {
+        format!("{home}/{rest}")
+    } else {
+        expanded
+    }
+}
+
+fn find_binary(binary: &str, home: Option<&Path>, path_env: Option<&OsStr>) -> Option<PathBuf> {
+    let expanded = PathBuf::from(expand_home(binary, home));
+    if expanded.components().count() > 1 {
+        return expanded.is_file().then_some(expanded);
+    }
+    std::env::split_paths(path_env?)
+        .map(|dir| dir.join(&expanded))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Paths matching `pattern`, walking from its longest literal prefix.
+fn glob_paths(pattern: &str) -> Vec<PathBuf> {
+    let Ok(glob) = globset::GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+    else {
+        return Vec::new();
+    };
+    let matcher = glob.compile_matcher();
+    let mut base = PathBuf::new();
+    let mut has_meta = false;
+    for component in Path::new(pattern).components() {
+        let text = component.as_os_str().to_string_lossy();
+        if text.contains(['*', '?', '[', '{']) {
+            has_meta = true;
+            break;
+        }
+        base.push(component);
+    }
+    if !has_meta {
+        return if base.exists() {
+            vec![base]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let mut matches = Vec::new();
+    let mut stack = vec![(base, 0_usize)];
+    'walk: while let Some((dir, depth)) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if matcher.is_match(&path) {
+                matches.push(path.clone());
+                if matches.len() == MAX_GLOB_MATCHES {
+                    break 'walk;
+                }
+            }
+            if depth < MAX_GLOB_DEPTH && entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                stack.push((path, depth + 1));
+            }
+        }
+    }
+    matches.sort();
+    matches
+}
+
+/// One connector in the capability matrix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectorMatrixEntry {
+    pub slug: String,
+    pub name: String,
+    pub source: ConnectorSource,
+    pub detected: bool,
+    pub evidence: Vec<String>,
+    pub root_paths: Vec<String>,
+    pub capabilities: ConnectorCapabilities,
+}
+
+/// Detection counts across built-in and custom connectors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectorMatrixSummary {
+    pub detected_count: usize,
+    pub total_count: usize,
+}
+
+/// `am agents detect` output: built-in and custom connectors with what
+/// each supports, plus any connector definitions that were skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectorMatrixReport {
+    pub format_version: u32,
+    pub generated_at: String,
+    pub installed_agents: Vec<ConnectorMatrixEntry>,
+    pub summary: ConnectorMatrixSummary,
+    pub connector_errors: Vec<ConnectorDefinitionError>,
+}
+
+/// Merge built-in detection with custom connectors into one matrix.
+///
+/// `custom` lists each custom connector with its detection result; undetected
+/// ones are dropped unless `include_undetected` is set, matching how the
+/// built-in report is filtered.
+#[must_use]
+pub fn build_connector_matrix(
+    builtin: Option<InstalledAgentDetectionReport>,
+    custom: &[(CustomConnector, CustomDetection)],
+    errors: Vec<ConnectorDefinitionError>,
+    include_undetected: bool,
+) -> ConnectorMatrixReport {
+    let generated_at = builtin.as_ref().map_or_else(
+        || chrono::Utc::now().to_rfc3339(),
+        |report| report.generated_at.clone(),
+    );
+    let mut installed_agents: Vec<ConnectorMatrixEntry> = builtin
+        .into_iter()
+        .flat_map(|report| report.installed_agents)
+        .map(|entry| ConnectorMatrixEntry {
+            name: AgentPlatform::from_slug(&entry.slug)
+                .map_or_else(|| entry.slug.clone(), |p| p.display_name().to_string()),
+            capabilities: builtin_capabilities(&entry.slug),
+            slug: entry.slug,
+            source: ConnectorSource::Builtin,
+            detected: entry.detected,
+            evidence: entry.evidence,
+            root_paths: entry.root_paths,
+        })
+        .collect();
+    for (connector, detection) in custom {
+        if !detection.detected && !include_undetected {
+            continue;
+        }
+        installed_agents.push(ConnectorMatrixEntry {
+            slug: connector.slug.clone(),
+            name: connector.name.clone(),
+            source: ConnectorSource::Custom,
+            detected: detection.detected,
+            evidence: detection.evidence.clone(),
+            root_paths: detection.root_paths.clone(),
+            capabilities: connector.capabilities,
+        });
+    }
+    let summary = ConnectorMatrixSummary {
+        detected_count: installed_agents.iter().filter(|e| e.detected).count(),
+        total_count: installed_agents.len(),
+    };
+    ConnectorMatrixReport {
+        format_version: CONNECTOR_MATRIX_FORMAT_VERSION,
+        generated_at,
+        installed_agents,
+        summary,
+        connector_errors: errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACME: &str = r#"
+[[connector]]
+name = "Acme Agent"
+slug = "acme"
+binaries = ["acme-agent"]
+config_path = "{project_dir}/.acme/mcp.json"
+
+[connector.capabilities]
+mcp_http = true
+stdio = true
+"#;
+
+    #[test]
+    fn parses_valid_connector_with_defaults() {
+        let defs = parse_connector_definitions(ACME);
+        assert!(defs.errors.is_empty(), "{:?}", defs.errors);
+        let acme = &defs.connectors[0];
+        assert_eq!(acme.slug, "acme");
+        assert_eq!(acme.servers_key, "mcpServers");
+        assert!(acme.capabilities.mcp_http && acme.capabilities.stdio);
+        assert!(!acme.capabilities.hooks);
+        assert_eq!(
+            acme.resolved_config_path(None, Path::new("/work/p")),
+            Some(PathBuf::from("/work/p/.acme/mcp.json"))
+        );
+    }
+
+    #[test]
+    fn invalid_entries_are_reported_and_skipped() {
+        let content = format!(
+            "{ACME}
+[[connector]]
+name = \"No probes\"
+slug = \"empty\"
+
+[[connector]]
+name = \"Shadow\"
+slug = \"claude\"
+binaries = [\"claude\"]
+
+[[connector]]
+name = \"Dup\"
+slug = \"acme\"
+binaries = [\"x\"]
+
+[[connector]]
+name = \"Typo\"
+slug = \"typo\"
+binaries = [\"x\"]
+capabilites = {{ stdio = true }}
+
+[[connector]]
+name = \"Bad key\"
+slug = \"badkey\"
+binaries = [\"x\"]
+servers_key = \"tools\"
+"
+        );
+        let defs = parse_connector_definitions(&content);
+        assert_eq!(defs.connectors.len(), 1);
+        let rejected: Vec<_> = defs
+            .errors
+            .iter()
+            .map(|e| (e.index, e.slug.as_deref()))
+            .collect();
+        assert_eq!(
+            rejected,
+            vec![
+                (Some(1), Some("empty")),
+                (Some(2), Some("claude")),
+                (Some(3), Some("acme")),
+                (Some(4), Some("typo")),
+                (Some(5), Some("badkey")),
+            ]
+        );
+        assert!(defs.errors[1].error.contains("built-in"));
+        assert!(defs.errors[2].error.contains("duplicate"));
+    }
+
+    #[test]
+    fn unparsable_file_is_one_error_not_a_failure() {
+        let defs = parse_connector_definitions("[[connector]\nname = ");
+        assert!(defs.connectors.is_empty());
+        assert_eq!(defs.errors.len(), 1);
+        assert_eq!(defs.errors[0].index, None);
+    }
+
+    #[test]
+    fn detects_binary_on_path_and_globs_under_home() {
+        let tmp = tempfile::tempdir().unwrap();
+        let bin = tmp.path().join("bin");
+        let home = tmp.path().join("home");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(home.join(".acme").join("profiles")).unwrap();
+        std::fs::write(bin.join("acme-agent"), b"#!/bin/sh\n").unwrap();
+        std::fs::write(home.join(".acme/profiles/default.json"), b"{}").unwrap();
+
+        let mut acme = parse_connector_definitions(ACME).connectors.remove(0);
+        acme.globs = vec!["~/.acme/**/*.json".to_string()];
+        let detection = acme.detect(Some(&home), Some(bin.as_os_str()));
+        assert!(detection.detected);
+        assert_eq!(
+            detection.evidence,
+            vec![
+                format!("binary:{}", bin.join("acme-agent").display()),
+                "glob:~/.acme/**/*.json".to_string(),
+            ]
+        );
+        assert!(
+            detection.root_paths.contains(
+                &home
+                    .join(".acme/profiles/default.json")
+                    .display()
+                    .to_string()
+            )
+        );
+
+        let missing = acme.detect(Some(tmp.path()), Some(OsStr::new("/nonexistent")));
+        assert!(!missing.detected);
+    }
+
+    #[cfg(not(feature = "agent-detect"))]
+    #[test]
+    fn matrix_merges_builtin_and_custom_with_capabilities() {
+        let builtin = InstalledAgentDetectionReport {
+            format_version: 1,
+            generated_at: "2026-01-01T00:00:00Z".to_string(),
+            installed_agents: vec![crate::agent_detect::InstalledAgentDetectionEntry {
+                slug: "claude".to_string(),
+                detected: true,
+                evidence: vec!["binary:claude".to_string()],
+                root_paths: Vec::new(),
+            }],
+            summary: crate::agent_detect::InstalledAgentDetectionSummary {
+                detected_count: 1,
+                total_count: 1,
+            },
+        };
+        let acme = parse_connector_definitions(ACME).connectors.remove(0);
+        let detected = CustomDetection {
+            detected: true,
+            evidence: vec!["binary:/bin/acme-agent".to_string()],
+            root_paths: Vec::new(),
+        };
+        let report = build_connector_matrix(Some(builtin), &[(acme, detected)], Vec::new(), false);
+        assert_eq!(report.summary.detected_count, 2);
+        let claude = &report.installed_agents[0];
+        assert_eq!(claude.source, ConnectorSource::Builtin);
+        assert_eq!(claude.name, "Claude Code");
+        assert!(claude.capabilities.hooks && claude.capabilities.stdio);
+        let acme = &report.installed_agents[1];
+        assert_eq!(acme.source, ConnectorSource::Custom);
+        assert!(acme.capabilities.mcp_http);
+
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(value["installed_agents"][1]["capabilities"]["stdio"], true);
+        assert_eq!(value["installed_agents"][1]["source"], "custom");
+    }
+}
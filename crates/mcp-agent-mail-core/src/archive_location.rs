@@ -0,0 +1,161 @@
+//! Where each project's Git archive lives.
+//!
+//! An archive root is a Git repository holding `projects/<slug>/` for the
+//! projects stored in it. Most projects live under the default root
+//! ([`Config::archive_root`](crate::Config::archive_root)); `am archive
+//! relocate` can move one to another root, and records the move in
+//! [`ARCHIVE_LOCATIONS_FILE`] in the default root. Everything that reads a
+//! project's archive resolves it through this module so relocated projects
+//! are found without each reader recomputing the path.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Index of relocated projects, kept in the default archive root.
+pub const ARCHIVE_LOCATIONS_FILE: &str = "archive_locations.json";
+
+/// Relocated projects, keyed by slug. Projects absent from the index live
+/// under the default root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveLocations {
+    #[serde(default)]
+    pub projects: BTreeMap<String, PathBuf>,
+}
+
+impl ArchiveLocations {
+    /// Read the index from `default_root`. A missing or unreadable index
+    /// means nothing has been relocated.
+    #[must_use]
+    pub fn load(default_root: &Path) -> Self {
+        let path = default_root.join(ARCHIVE_LOCATIONS_FILE);
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            return Self::default();
+        };
+        if !metadata.file_type().is_file() {
+            return Self::default();
+        }
+        std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the index to `default_root`, replacing it atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns the underlying I/O error if the index cannot be written.
+    pub fn save(&self, default_root: &Path) -> io::Result<()> {
+        let path = default_root.join(ARCHIVE_LOCATIONS_FILE);
+        let tmp = default_root.join(format!("{ARCHIVE_LOCATIONS_FILE}.tmp"));
+        let body = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        std::fs::write(&tmp, body)?;
+        std::fs::rename(&tmp, &path)
+    }
+
+    /// Archive root holding `slug`.
+    #[must_use]
+    pub fn root_for(&self, default_root: &Path, slug: &str) -> PathBuf {
+        self.projects
+            .get(slug)
+            .cloned()
+            .unwrap_or_else(|| default_root.to_path_buf())
+    }
+
+    /// Record that `slug` now lives under `root`; moving it back to the
+    /// default root drops the entry.
+    pub fn set(&mut self, default_root: &Path, slug: &str, root: &Path) {
+        if root == default_root {
+            self.projects.remove(slug);
+        } else {
+            self.projects.insert(slug.to_string(), root.to_path_buf());
+        }
+    }
+}
+
+/// Archive root (Git repository) holding `slug`.
+#[must_use]
+pub fn project_archive_root(default_root: &Path, slug: &str) -> PathBuf {
+    ArchiveLocations::load(default_root).root_for(default_root, slug)
+}
+
+/// `projects/<slug>` directory for `slug`.
+#[must_use]
+pub fn project_archive_dir(default_root: &Path, slug: &str) -> PathBuf {
+    project_archive_root(default_root, slug)
+        .join("projects")
+        .join(slug)
+}
+
+/// Every project archive directory, sorted by slug: the real directories
+/// under `<default_root>/projects` plus relocated projects. A relocated
+/// project's entry replaces any leftover directory under the default root.
+#[must_use]
+pub fn project_archive_dirs(default_root: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs: BTreeMap<String, PathBuf> = BTreeMap::new();
+    if let Ok(entries) = std::fs::read_dir(default_root.join("projects")) {
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if !file_type.is_dir() || file_type.is_symlink() {
+                continue;
+            }
+            if let Some(slug) = entry.file_name().to_str() {
+                dirs.insert(slug.to_string(), entry.path());
+            }
+        }
+    }
+    for (slug, root) in ArchiveLocations::load(default_root).projects {
+        let dir = root.join("projects").join(&slug);
+        if std::fs::symlink_metadata(&dir).is_ok_and(|meta| meta.file_type().is_dir()) {
+            dirs.insert(slug, dir);
+        } else {
+            dirs.remove(&slug);
+        }
+    }
+    dirs.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relocated_projects_resolve_through_the_index() {
+        let tmp = tempfile::tempdir().unwrap();
+        let default_root = tmp.path().join("default");
+        let other_root = tmp.path().join("other");
+        for dir in [
+            default_root.join("projects").join("alpha"),
+            default_root.join("projects").join("beta"),
+            other_root.join("projects").join("beta"),
+        ] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        assert_eq!(project_archive_root(&default_root, "beta"), default_root);
+
+        let mut locations = ArchiveLocations::load(&default_root);
+        locations.set(&default_root, "beta", &other_root);
+        locations.save(&default_root).unwrap();
+
+        assert_eq!(
+            project_archive_dir(&default_root, "beta"),
+            other_root.join("projects").join("beta")
+        );
+        assert_eq!(
+            project_archive_dirs(&default_root),
+            [
+                ("alpha".to_string(), default_root.join("projects/alpha")),
+                ("beta".to_string(), other_root.join("projects/beta")),
+            ]
+        );
+
+        let mut locations = ArchiveLocations::load(&default_root);
+        locations.set(&default_root, "beta", &default_root);
+        assert!(locations.projects.is_empty());
+    }
+}
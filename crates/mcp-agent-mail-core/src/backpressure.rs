@@ -11,7 +11,9 @@
 //! - **Observable**: exposed via `health_check` + tooling/metrics resources.
 
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Mutex, RwLock};
 
 use crate::metrics::{GlobalMetricsSnapshot, global_metrics};
 use crate::slo;
@@ -425,6 +427,7 @@ pub fn refresh_health_level() -> (HealthLevel, bool) {
         let _ = LEVEL_TRANSITIONS.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
             Some(v.saturating_add(1))
         });
+        record_level_transition(HealthLevel::from_u8(prev), new, now_micros_u64());
     }
     (new, changed)
 }
@@ -435,6 +438,46 @@ pub fn level_transitions() -> u8 {
     LEVEL_TRANSITIONS.load(Ordering::Relaxed)
 }
 
+/// Maximum number of level transitions kept by [`recent_level_transitions`].
+pub const LEVEL_TRANSITION_HISTORY_CAPACITY: usize = 32;
+
+/// One change of the cached health level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelTransition {
+    pub from: HealthLevel,
+    pub to: HealthLevel,
+    /// When the change was observed (Unix epoch microseconds).
+    pub at_us: u64,
+}
+
+/// Bounded history of level changes. Only touched when the level actually
+/// changes, so the dispatch fast path stays lock-free.
+static LEVEL_TRANSITION_HISTORY: Mutex<VecDeque<LevelTransition>> = Mutex::new(VecDeque::new());
+
+fn record_level_transition(from: HealthLevel, to: HealthLevel, at_us: u64) {
+    let mut history = LEVEL_TRANSITION_HISTORY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if history.len() >= LEVEL_TRANSITION_HISTORY_CAPACITY {
+        history.pop_front();
+    }
+    history.push_back(LevelTransition { from, to, at_us });
+}
+
+/// The most recent level changes, oldest first.
+///
+/// Unlike [`level_transitions`], this shows *when* the level moved, which is
+/// what makes flapping between Yellow and Red visible during an incident.
+#[must_use]
+pub fn recent_level_transitions() -> Vec<LevelTransition> {
+    LEVEL_TRANSITION_HISTORY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .copied()
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Global shedding gate (AtomicBool, off by default)
 // ---------------------------------------------------------------------------
@@ -452,11 +495,122 @@ pub fn shedding_enabled() -> bool {
 
 /// Set the global shedding-enabled flag.
 ///
-/// Called once at server startup from `Config::backpressure_shedding_enabled`.
+/// Called at server startup from `Config::backpressure_shedding_enabled`,
+/// and at runtime by operators flipping the gate during an incident.
 pub fn set_shedding_enabled(enabled: bool) {
     SHEDDING_ENABLED.store(enabled, Ordering::Relaxed);
 }
 
+/// Point-in-time view of the shedding gate for operator tooling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SheddingStatus {
+    pub enabled: bool,
+    pub health_level: HealthLevel,
+    pub level_transitions: u8,
+    pub recent_transitions: Vec<LevelTransition>,
+}
+
+/// Snapshot the shedding gate, cached level, and transition history.
+#[must_use]
+pub fn shedding_status() -> SheddingStatus {
+    SheddingStatus {
+        enabled: shedding_enabled(),
+        health_level: cached_health_level(),
+        level_transitions: level_transitions(),
+        recent_transitions: recent_level_transitions(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Health-gated inactivity reaper
+// ---------------------------------------------------------------------------
+
+/// Maximum number of ticks kept by [`recent_reaper_decisions`].
+pub const REAPER_DECISION_HISTORY_CAPACITY: usize = 32;
+
+/// Why the inactivity reaper sat out a tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReaperPauseReason {
+    /// Mailbox writes were blocked within the inactivity window, so agents
+    /// could not record activity.
+    WritesDisabled,
+    /// Health is Red right now.
+    HealthRed,
+    /// Health was Red at some point within the inactivity window.
+    HealthRedWithinWindow,
+}
+
+/// Outcome of one inactivity-reaper tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaperDecision {
+    /// When the tick ran (Unix epoch microseconds).
+    pub at_us: u64,
+    /// `false` when the tick was skipped; see `reason`.
+    pub ran: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<ReaperPauseReason>,
+    /// Reservations whose holders were checked for inactivity.
+    pub evaluated: usize,
+    /// Reservations released as stale.
+    pub released: usize,
+}
+
+static REAPER_DECISION_HISTORY: Mutex<VecDeque<ReaperDecision>> = Mutex::new(VecDeque::new());
+
+/// Decide whether the inactivity reaper must skip this tick.
+///
+/// Agents cannot bump their activity while writes are blocked or the server
+/// is shedding under Red health, so an inactivity gap that overlaps such a
+/// window says nothing about the agent. Skipping every tick until the
+/// unhealthy period has left the window gives each agent a full window of
+/// healthy time, i.e. extends every deadline by the outage.
+///
+/// `writes_blocked_at_us` is the last time the caller saw mailbox writes
+/// blocked.
+#[must_use]
+pub fn reaper_pause_reason(
+    now_us: u64,
+    window_us: u64,
+    level: HealthLevel,
+    transitions: &[LevelTransition],
+    writes_blocked_at_us: Option<u64>,
+) -> Option<ReaperPauseReason> {
+    let within_window = |at_us: u64| now_us.saturating_sub(at_us) <= window_us;
+    if writes_blocked_at_us.is_some_and(within_window) {
+        return Some(ReaperPauseReason::WritesDisabled);
+    }
+    if level == HealthLevel::Red {
+        return Some(ReaperPauseReason::HealthRed);
+    }
+    transitions
+        .iter()
+        .any(|t| (t.from == HealthLevel::Red || t.to == HealthLevel::Red) && within_window(t.at_us))
+        .then_some(ReaperPauseReason::HealthRedWithinWindow)
+}
+
+/// Remember the outcome of an inactivity-reaper tick for diagnostics.
+pub fn record_reaper_decision(decision: ReaperDecision) {
+    let mut history = REAPER_DECISION_HISTORY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if history.len() >= REAPER_DECISION_HISTORY_CAPACITY {
+        history.pop_front();
+    }
+    history.push_back(decision);
+}
+
+/// The most recent inactivity-reaper ticks in this process, oldest first.
+#[must_use]
+pub fn recent_reaper_decisions() -> Vec<ReaperDecision> {
+    REAPER_DECISION_HISTORY
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .cloned()
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Shedable tool classification
 // ---------------------------------------------------------------------------
@@ -482,6 +636,21 @@ const SHEDABLE_TOOLS: &[&str] = &[
     "fetch_inbox_product",
 ];
 
+/// Extension tools registered as shedable by an embedding server.
+static EXTENSION_SHEDABLE_TOOLS: RwLock<Vec<&'static str>> = RwLock::new(Vec::new());
+
+/// Classify an extension tool (registered at server construction) as
+/// shedable under Red-level backpressure. Registering the same name twice
+/// is a no-op.
+pub fn register_shedable_tool(tool_name: &'static str) {
+    let mut tools = EXTENSION_SHEDABLE_TOOLS
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if !tools.contains(&tool_name) {
+        tools.push(tool_name);
+    }
+}
+
 /// Returns `true` if the named tool is considered low-priority and can
 /// be rejected under Red-level backpressure.
 ///
@@ -491,6 +660,17 @@ const SHEDABLE_TOOLS: &[&str] = &[
 #[must_use]
 pub fn is_shedable_tool(tool_name: &str) -> bool {
     SHEDABLE_TOOLS.contains(&tool_name)
+        || EXTENSION_SHEDABLE_TOOLS
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .contains(&tool_name)
+}
+
+/// Lowest health level at which the named tool is rejected when shedding is
+/// enabled, or `None` for coordination-critical tools that are never shed.
+#[must_use]
+pub fn shed_threshold(tool_name: &str) -> Option<HealthLevel> {
+    is_shedable_tool(tool_name).then_some(HealthLevel::Red)
 }
 
 /// Combined dispatch-layer decision: should this tool call be rejected?
@@ -798,6 +978,23 @@ mod tests {
         assert!(is_shedable_tool("fetch_inbox_product"));
     }
 
+    #[test]
+    fn registered_extension_tools_are_shedable() {
+        assert!(!is_shedable_tool("ext_backpressure_probe"));
+        register_shedable_tool("ext_backpressure_probe");
+        register_shedable_tool("ext_backpressure_probe");
+        assert!(is_shedable_tool("ext_backpressure_probe"));
+        assert_eq!(
+            EXTENSION_SHEDABLE_TOOLS
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|name| **name == "ext_backpressure_probe")
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn critical_tools_not_shedable() {
         // Infrastructure
@@ -925,6 +1122,8 @@ mod tests {
                     p99: 0,
                 },
                 contact_enforcement_bypass_total: 0,
+                webhooks_delivered_total: 0,
+                webhooks_failed_total: 0,
             },
             db: DbMetricsSnapshot {
                 pool_acquires_total: 0,
@@ -1021,6 +1220,7 @@ mod tests {
                 commit_batch_size_last: 0,
                 lockfree_commits_total: 0,
                 lockfree_commit_fallbacks_total: 0,
+                reservation_expiry_warnings_total: 0,
             },
             system: SystemMetricsSnapshot {
                 disk_storage_free_bytes: 0,
@@ -1434,6 +1634,76 @@ mod tests {
         let _t: u8 = level_transitions();
     }
 
+    #[test]
+    fn level_transition_history_is_bounded_and_ordered() {
+        for i in 0..(LEVEL_TRANSITION_HISTORY_CAPACITY + 5) {
+            let at_us = u64::MAX - 1_000 + u64::try_from(i).unwrap();
+            record_level_transition(HealthLevel::Yellow, HealthLevel::Red, at_us);
+        }
+        let history = recent_level_transitions();
+        assert!(history.len() <= LEVEL_TRANSITION_HISTORY_CAPACITY);
+        // Other tests may refresh the global level concurrently; our writes
+        // are still the newest entries bearing the sentinel timestamps.
+        let ours: Vec<_> = history
+            .iter()
+            .filter(|t| t.at_us >= u64::MAX - 1_000)
+            .collect();
+        assert!(ours.windows(2).all(|w| w[0].at_us < w[1].at_us));
+        assert_eq!(
+            ours.last().map(|t| t.at_us),
+            Some(u64::MAX - 1_000 + u64::try_from(LEVEL_TRANSITION_HISTORY_CAPACITY + 4).unwrap())
+        );
+    }
+
+    #[test]
+    fn reaper_pauses_for_outages_inside_the_window() {
+        let window = 1_800_000_000;
+        let now = 10_000_000_000;
+        let red_spell = [
+            LevelTransition {
+                from: HealthLevel::Green,
+                to: HealthLevel::Red,
+                at_us: now - 3_000_000_000,
+            },
+            LevelTransition {
+                from: HealthLevel::Red,
+                to: HealthLevel::Green,
+                at_us: now - 1_000_000_000,
+            },
+        ];
+        assert_eq!(
+            reaper_pause_reason(now, window, HealthLevel::Green, &red_spell, None),
+            Some(ReaperPauseReason::HealthRedWithinWindow)
+        );
+        assert_eq!(
+            reaper_pause_reason(now, window, HealthLevel::Red, &[], None),
+            Some(ReaperPauseReason::HealthRed)
+        );
+        assert_eq!(
+            reaper_pause_reason(now, window, HealthLevel::Green, &[], Some(now - 60_000_000)),
+            Some(ReaperPauseReason::WritesDisabled)
+        );
+        // Once the outage has left the window, every agent has had a full
+        // window of healthy time and the reaper runs again.
+        assert_eq!(
+            reaper_pause_reason(
+                now + window,
+                window,
+                HealthLevel::Green,
+                &red_spell,
+                Some(now - 60_000_000),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn shed_threshold_is_red_for_shedable_tools_only() {
+        assert_eq!(shed_threshold("search_messages"), Some(HealthLevel::Red));
+        assert_eq!(shed_threshold("send_message"), None);
+        assert_eq!(shed_threshold("file_reservation_paths"), None);
+    }
+
     #[test]
     fn serde_roundtrip_all_levels() {
         for level in [HealthLevel::Green, HealthLevel::Yellow, HealthLevel::Red] {
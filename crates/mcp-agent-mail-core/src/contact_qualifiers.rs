@@ -0,0 +1,168 @@
+//! Optional qualifiers on the `auto` contact policy.
+//!
+//! An agent whose policy is `auto` may narrow which requesters get approved
+//! without a manual `respond_contact`:
+//!
+//! - `auto_allow`: comma-separated agent-name globs (`*` / `?`, matched
+//!   case-insensitively like agent lookup) whose contact is approved when
+//!   they first message the agent.
+//! - `auto_ttl_seconds`: lifetime of links approved that way.
+//! - `auto_daily_cap`: how many auto-approvals one requester may collect
+//!   over a rolling 24 hours before further handshakes stay pending.
+//!
+//! Patterns are validated when the policy is set; stored values are assumed
+//! valid and any pattern that no longer compiles simply never matches.
+
+#![forbid(unsafe_code)]
+
+use globset::{GlobBuilder, GlobMatcher};
+
+/// Window over which [`ContactAutoQualifiers::daily_cap`] is counted.
+pub const AUTO_APPROVAL_WINDOW_MICROS: i64 = 24 * 60 * 60 * 1_000_000;
+
+/// Parsed `auto` policy qualifiers for one agent.
+#[derive(Debug, Clone, Default)]
+pub struct ContactAutoQualifiers {
+    patterns: Vec<(String, GlobMatcher)>,
+    /// TTL for links approved automatically; `None` keeps the caller's default.
+    pub ttl_seconds: Option<i64>,
+    /// Maximum auto-approvals per requester per day; `None` is unlimited.
+    pub daily_cap: Option<i64>,
+}
+
+impl ContactAutoQualifiers {
+    /// Validate and compile qualifiers supplied when setting a policy.
+    ///
+    /// # Errors
+    /// Returns a message naming the first invalid glob, or a non-positive
+    /// TTL or cap.
+    pub fn parse(
+        auto_allow: Option<&str>,
+        ttl_seconds: Option<i64>,
+        daily_cap: Option<i64>,
+    ) -> Result<Self, String> {
+        if let Some(ttl) = ttl_seconds
+            && ttl <= 0
+        {
+            return Err(format!("auto TTL must be positive, got {ttl}"));
+        }
+        if let Some(cap) = daily_cap
+            && cap <= 0
+        {
+            return Err(format!("auto daily cap must be at least 1, got {cap}"));
+        }
+        let mut patterns = Vec::new();
+        for pattern in split_auto_allow(auto_allow.unwrap_or_default()) {
+            if pattern.contains(['/', '\\']) || pattern.contains(char::is_whitespace) {
+                return Err(format!(
+                    "invalid auto-allow glob `{pattern}`: agent names contain no whitespace or path separators"
+                ));
+            }
+            let glob = GlobBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|error| format!("invalid auto-allow glob `{pattern}`: {error}"))?;
+            patterns.push((pattern.to_string(), glob.compile_matcher()));
+        }
+        Ok(Self {
+            patterns,
+            ttl_seconds,
+            daily_cap,
+        })
+    }
+
+    /// Rebuild qualifiers from their stored form, dropping patterns that no
+    /// longer compile.
+    #[must_use]
+    pub fn from_stored(auto_allow: &str, ttl_seconds: Option<i64>, daily_cap: Option<i64>) -> Self {
+        let patterns = split_auto_allow(auto_allow)
+            .filter_map(|pattern| {
+                GlobBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .ok()
+                    .map(|glob| (pattern.to_string(), glob.compile_matcher()))
+            })
+            .collect();
+        Self {
+            patterns,
+            ttl_seconds: ttl_seconds.filter(|ttl| *ttl > 0),
+            daily_cap: daily_cap.filter(|cap| *cap > 0),
+        }
+    }
+
+    /// `true` when no qualifier is set, i.e. plain `auto`.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.ttl_seconds.is_none() && self.daily_cap.is_none()
+    }
+
+    /// `true` when an allowlist restricts which requesters are auto-approved.
+    #[must_use]
+    pub fn has_allowlist(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    /// The allowlist patterns, in the order they were given.
+    pub fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.patterns.iter().map(|(pattern, _)| pattern.as_str())
+    }
+
+    /// The allowlist in its stored, comma-separated form.
+    #[must_use]
+    pub fn auto_allow(&self) -> String {
+        self.patterns().collect::<Vec<_>>().join(",")
+    }
+
+    /// The first allowlist pattern that matches `name`, if any.
+    #[must_use]
+    pub fn matching_pattern(&self, name: &str) -> Option<&str> {
+        let name = name.trim();
+        self.patterns
+            .iter()
+            .find(|(_, matcher)| matcher.is_match(name))
+            .map(|(pattern, _)| pattern.as_str())
+    }
+}
+
+fn split_auto_allow(raw: &str) -> impl Iterator<Item = &str> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pattern| !pattern.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowlist_matches_first_pattern_case_insensitively() {
+        let qualifiers =
+            ContactAutoQualifiers::parse(Some("Blue*, GreenLake"), Some(3600), Some(5))
+                .expect("valid qualifiers");
+        assert_eq!(qualifiers.auto_allow(), "Blue*,GreenLake");
+        assert_eq!(qualifiers.matching_pattern("bluelake"), Some("Blue*"));
+        assert_eq!(qualifiers.matching_pattern("GreenLake"), Some("GreenLake"));
+        assert_eq!(qualifiers.matching_pattern("RedFox"), None);
+        assert_eq!(qualifiers.ttl_seconds, Some(3600));
+        assert_eq!(qualifiers.daily_cap, Some(5));
+    }
+
+    #[test]
+    fn bad_globs_and_limits_are_rejected() {
+        let err = ContactAutoQualifiers::parse(Some("Blue*,[Green"), None, None)
+            .expect_err("unclosed class");
+        assert!(err.contains("[Green"), "{err}");
+        assert!(ContactAutoQualifiers::parse(Some("team/Blue*"), None, None).is_err());
+        assert!(ContactAutoQualifiers::parse(None, Some(0), None).is_err());
+        assert!(ContactAutoQualifiers::parse(None, None, Some(0)).is_err());
+    }
+
+    #[test]
+    fn empty_input_means_plain_auto() {
+        let qualifiers = ContactAutoQualifiers::parse(Some(" , "), None, None).expect("empty");
+        assert!(qualifiers.is_empty());
+        assert!(!qualifiers.has_allowlist());
+        assert!(ContactAutoQualifiers::from_stored("", None, None).is_empty());
+    }
+}
@@ -17,7 +17,7 @@ use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
-use crate::backpressure::{self, HealthLevel, HealthSignals};
+use crate::backpressure::{self, HealthLevel, HealthSignals, ReaperDecision};
 use crate::lock_order::{LockContentionEntry, lock_contention_snapshot};
 use crate::metrics::{
     DbMetricsSnapshot, GlobalMetricsSnapshot, HttpMetricsSnapshot, SearchMetricsSnapshot,
@@ -220,6 +220,9 @@ pub struct DiagnosticReport {
     pub disk: SystemMetricsSnapshot,
     /// Lock contention metrics.
     pub locks: Vec<LockContentionEntry>,
+    /// Recent stale-reservation reaper ticks, oldest first: whether each ran
+    /// or was skipped for an outage, and what it released.
+    pub reaper: Vec<ReaperDecision>,
     /// Automated recommendations based on current metrics.
     pub recommendations: Vec<Recommendation>,
 }
@@ -1456,6 +1459,7 @@ impl DiagnosticReport {
             search: snap.search,
             disk: snap.system,
             locks: lock_snap,
+            reaper: backpressure::recent_reaper_decisions(),
             recommendations: recs,
         }
     }
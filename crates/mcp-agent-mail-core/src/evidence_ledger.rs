@@ -246,6 +246,12 @@ pub fn evidence_ledger() -> &'static EvidenceLedger {
     })
 }
 
+/// Flush and fsync the global ledger's JSONL output, if the ledger was ever
+/// used. Called on graceful server shutdown; never initialises the ledger.
+pub fn flush_evidence_ledger() -> io::Result<()> {
+    GLOBAL_LEDGER.get().map_or(Ok(()), EvidenceLedger::flush)
+}
+
 // ---------------------------------------------------------------------------
 // Stateful evidence ledger (ring buffer + JSONL + queries)
 // ---------------------------------------------------------------------------
@@ -373,6 +379,20 @@ impl EvidenceLedger {
         }
     }
 
+    /// Flush buffered JSONL output and sync it to disk. A no-op for
+    /// in-memory-only ledgers.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut guard = self
+            .writer
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(ref mut w) = *guard {
+            w.flush()?;
+            w.get_ref().sync_data()?;
+        }
+        Ok(())
+    }
+
     /// Return the last `n` entries, ordered newest-first.
     #[must_use]
     pub fn recent(&self, n: usize) -> Vec<EvidenceLedgerEntry> {
@@ -260,6 +260,10 @@ fn current_ack_escalation_enabled(config: &Config) -> String {
     bool_string(config.ack_escalation_enabled)
 }
 
+fn current_ack_reminder_enabled(config: &Config) -> String {
+    bool_string(config.ack_reminder_enabled)
+}
+
 fn current_retention_report_enabled(config: &Config) -> String {
     bool_string(config.retention_report_enabled)
 }
@@ -284,6 +288,24 @@ pub const FLAG_REGISTRY: &[FlagDefinition] = &[
         resolve_value: current_ack_escalation_enabled,
         resolve_source: |config| process_or_config_source(config, "ACK_ESCALATION_ENABLED"),
     },
+    FlagDefinition {
+        name: "ACK_REMINDER_ENABLED",
+        env_var: "ACK_REMINDER_ENABLED",
+        kind: FlagKind::Bool,
+        default_value: "false",
+        doc: "Send automatic in-thread reminders for unacked high and urgent messages.",
+        stability: FlagStability::Experimental,
+        subsystem: "messaging",
+        affected_subsystems: &["messaging"],
+        dynamic_toggle: false,
+        restart_required: true,
+        notes: Some(
+            "Server processes read this at startup. Tune with ACK_REMINDER_URGENT_SECONDS, \
+             ACK_REMINDER_HIGH_SECONDS, and ACK_REMINDER_MAX_PER_MESSAGE.",
+        ),
+        resolve_value: current_ack_reminder_enabled,
+        resolve_source: |config| process_or_config_source(config, "ACK_REMINDER_ENABLED"),
+    },
     FlagDefinition {
         name: "ACK_TTL_ENABLED",
         env_var: "ACK_TTL_ENABLED",
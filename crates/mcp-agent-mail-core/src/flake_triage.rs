@@ -196,6 +196,9 @@ pub struct FlakeReport {
     pub verdict: FlakeVerdict,
     /// Suggested remediation.
     pub remediation: String,
+    /// Failing-seed forensics from `detect --analyze` (see [`SeedAnalysis`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<SeedAnalysis>,
 }
 
 /// Verdict from flake analysis.
@@ -289,6 +292,7 @@ impl FlakeReport {
             failing_seeds,
             verdict,
             remediation,
+            analysis: None,
         }
     }
 
@@ -659,6 +663,12 @@ pub struct MultiSeedConfig {
     pub packages: Vec<String>,
     /// Timeout per individual test run.
     pub timeout: std::time::Duration,
+    /// Re-run failing seeds with debug logging and correlate the outcome
+    /// with captured factors (see [`analyze_failing_seeds`]).
+    pub analyze: bool,
+    /// Where `analyze` writes per-run logs and artifacts (default:
+    /// `tests/artifacts/flake_triage/<timestamp>_<test>`).
+    pub artifact_dir: Option<std::path::PathBuf>,
 }
 
 impl Default for MultiSeedConfig {
@@ -672,6 +682,8 @@ impl Default for MultiSeedConfig {
                 "mcp-agent-mail-db".to_string(),
             ],
             timeout: std::time::Duration::from_mins(1),
+            analyze: false,
+            artifact_dir: None,
         }
     }
 }
@@ -754,7 +766,416 @@ pub fn run_multi_seed_subprocess(config: &MultiSeedConfig) -> FlakeReport {
         });
     }
 
-    FlakeReport::from_runs(&config.test_name, runs)
+    let mut report = FlakeReport::from_runs(&config.test_name, runs);
+    if config.analyze && !report.failing_seeds.is_empty() {
+        let analysis = analyze_failing_seeds(config, &report);
+        if let Some(top) = analysis.correlations.first() {
+            report.remediation = format!("{} Correlated: {}.", report.remediation, top.summary);
+        }
+        report.analysis = Some(analysis);
+    }
+    report
+}
+
+// ── Failing-Seed Analysis ───────────────────────────────────────────
+
+/// Lines of captured output kept in [`SeedRunCapture::log_tail`].
+const LOG_TAIL_LINES: usize = 20;
+
+/// Factors that are unique per run by construction and never correlate.
+const PER_RUN_FACTORS: &[&str] = &["seed", "tmpdir"];
+
+/// One instrumented re-run of a seed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedRunCapture {
+    /// Seed replayed via `HARNESS_SEED`.
+    pub seed: u64,
+    /// Whether the test passed.
+    pub passed: bool,
+    /// Whether the run was killed at the per-seed timeout.
+    pub timed_out: bool,
+    /// Process exit code (`None` if killed or not started).
+    pub exit_code: Option<i32>,
+    /// Duration in milliseconds.
+    pub duration_ms: u64,
+    /// Captured variables (seed, seed residues, thread count, tmpdir, timing).
+    pub factors: BTreeMap<String, String>,
+    /// Combined stdout/stderr captured with `RUST_LOG=debug`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_path: Option<std::path::PathBuf>,
+    /// Last lines of the captured output.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub log_tail: Vec<String>,
+    /// `failure_context.json` written by the test, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_artifact: Option<std::path::PathBuf>,
+}
+
+/// A factor whose value differs between two runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactorDiff {
+    pub factor: String,
+    pub passing: Option<String>,
+    pub failing: Option<String>,
+}
+
+/// Smallest difference between a failing run and any passing run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedEnvDiff {
+    pub failing_seed: u64,
+    /// Seed of the closest passing run (`None` if nothing passed).
+    pub passing_seed: Option<u64>,
+    pub differences: Vec<FactorDiff>,
+}
+
+/// A factor value that every failure shares and no pass does.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactorCorrelation {
+    pub factor: String,
+    pub value: String,
+    /// Runs with this value.
+    pub runs: u32,
+    /// Failures among those runs.
+    pub failures: u32,
+    /// Human-readable summary, e.g. `fails only when seed_mod_4 == 1`.
+    pub summary: String,
+}
+
+/// Failing-seed forensics attached to a [`FlakeReport`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedAnalysis {
+    /// Directory holding per-run logs and artifacts.
+    pub artifact_dir: std::path::PathBuf,
+    /// Instrumented re-runs (failing seeds, single-threaded variants, and one
+    /// passing baseline).
+    pub runs: Vec<SeedRunCapture>,
+    /// Minimal factor diff per failing seed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_diffs: Vec<SeedEnvDiff>,
+    /// Factor values that separate failures from passes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub correlations: Vec<FactorCorrelation>,
+}
+
+/// Re-run each failing seed with `RUST_LOG=debug`, once as-is and once with
+/// `RUST_TEST_THREADS=1`, plus the first passing seed as a baseline. Each run
+/// is bounded by `config.timeout` and gets its own `TMPDIR` under the
+/// artifact directory, where its log and any `failure_context.json` land.
+/// Outcomes from the original runs and these re-runs are then grouped by
+/// factor to find values that only ever appear with failures.
+#[must_use]
+pub fn analyze_failing_seeds(config: &MultiSeedConfig, report: &FlakeReport) -> SeedAnalysis {
+    let artifact_dir = config.artifact_dir.clone().unwrap_or_else(|| {
+        let stamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+        let test = sanitize_path_component(&config.test_name);
+        std::path::PathBuf::from(format!("tests/artifacts/flake_triage/{stamp}_{test}"))
+    });
+    let default_threads = default_test_threads();
+
+    let mut plan: Vec<(u64, Option<&str>)> = Vec::new();
+    for &seed in &report.failing_seeds {
+        plan.push((seed, None));
+        plan.push((seed, Some("1")));
+    }
+    if let Some(seed) = report.runs.iter().find(|r| r.passed).and_then(|r| r.seed) {
+        plan.push((seed, None));
+    }
+
+    let runs: Vec<SeedRunCapture> = plan
+        .into_iter()
+        .map(|(seed, threads)| {
+            let label = threads.map_or_else(
+                || format!("seed_{seed}"),
+                |t| format!("seed_{seed}_threads{t}"),
+            );
+            capture_seed_run(
+                config,
+                seed,
+                threads.unwrap_or(default_threads.as_str()),
+                &artifact_dir.join(label),
+            )
+        })
+        .collect();
+
+    let mut observations: Vec<(BTreeMap<String, String>, bool)> = report
+        .runs
+        .iter()
+        .filter_map(|run| {
+            let seed = run.seed?;
+            let timed_out = run.duration_ms >= duration_ms(config.timeout);
+            Some((
+                seed_factors(
+                    seed,
+                    &default_threads,
+                    run.duration_ms,
+                    config.timeout,
+                    timed_out,
+                ),
+                run.passed,
+            ))
+        })
+        .collect();
+    observations.extend(runs.iter().map(|run| {
+        let mut factors = run.factors.clone();
+        factors.remove("tmpdir");
+        (factors, run.passed)
+    }));
+
+    let passing: Vec<&SeedRunCapture> = runs.iter().filter(|r| r.passed).collect();
+    let env_diffs = report
+        .failing_seeds
+        .iter()
+        .filter_map(|&seed| {
+            let failing = runs.iter().find(|r| r.seed == seed && !r.passed)?;
+            Some(minimal_env_diff(failing, &passing))
+        })
+        .collect();
+
+    SeedAnalysis {
+        artifact_dir,
+        runs,
+        env_diffs,
+        correlations: correlate_factors(&observations),
+    }
+}
+
+/// Factors recorded for a run. `timing` buckets the duration against the
+/// per-seed timeout so slow-but-passing runs are distinguishable.
+#[must_use]
+pub fn seed_factors(
+    seed: u64,
+    test_threads: &str,
+    duration_ms_taken: u64,
+    timeout: std::time::Duration,
+    timed_out: bool,
+) -> BTreeMap<String, String> {
+    let budget = duration_ms(timeout).max(1);
+    let timing = if timed_out {
+        "timeout"
+    } else if duration_ms_taken.saturating_mul(4) >= budget {
+        "slow"
+    } else {
+        "fast"
+    };
+    BTreeMap::from([
+        ("seed".to_string(), seed.to_string()),
+        ("seed_mod_2".to_string(), (seed % 2).to_string()),
+        ("seed_mod_4".to_string(), (seed % 4).to_string()),
+        ("test_threads".to_string(), test_threads.to_string()),
+        ("timing".to_string(), timing.to_string()),
+    ])
+}
+
+/// Group runs by each factor and report values that every failure shares
+/// while no passing run does. Factors that are unique per run are skipped.
+#[must_use]
+pub fn correlate_factors(
+    observations: &[(BTreeMap<String, String>, bool)],
+) -> Vec<FactorCorrelation> {
+    let total_failures = u32::try_from(observations.iter().filter(|(_, passed)| !passed).count())
+        .unwrap_or(u32::MAX);
+    if total_failures == 0 {
+        return Vec::new();
+    }
+    let mut groups: BTreeMap<&str, BTreeMap<&str, (u32, u32)>> = BTreeMap::new();
+    for (factors, passed) in observations {
+        for (factor, value) in factors {
+            if PER_RUN_FACTORS.contains(&factor.as_str()) {
+                continue;
+            }
+            let entry = groups
+                .entry(factor.as_str())
+                .or_default()
+                .entry(value.as_str())
+                .or_default();
+            entry.0 += 1;
+            if !passed {
+                entry.1 += 1;
+            }
+        }
+    }
+
+    let mut correlations = Vec::new();
+    for (factor, values) in groups {
+        if values.len() < 2 {
+            continue;
+        }
+        for (value, &(runs, failures)) in &values {
+            let others_fail = values
+                .iter()
+                .any(|(other, &(_, f))| other != value && f > 0);
+            if failures == total_failures && !others_fail {
+                correlations.push(FactorCorrelation {
+                    factor: factor.to_string(),
+                    value: (*value).to_string(),
+                    runs,
+                    failures,
+                    summary: format!(
+                        "fails only when {factor} == {value} ({failures}/{runs} runs failed)"
+                    ),
+                });
+            }
+        }
+    }
+    correlations.sort_by(|a, b| {
+        let rate = |c: &FactorCorrelation| f64::from(c.failures) / f64::from(c.runs.max(1));
+        rate(b)
+            .total_cmp(&rate(a))
+            .then_with(|| a.factor.cmp(&b.factor))
+    });
+    correlations
+}
+
+/// Diff `failing` against the passing run that differs in the fewest factors
+/// (ignoring `tmpdir`, which is per run).
+#[must_use]
+pub fn minimal_env_diff(failing: &SeedRunCapture, passing: &[&SeedRunCapture]) -> SeedEnvDiff {
+    let diff = |other: &SeedRunCapture| -> Vec<FactorDiff> {
+        let keys: std::collections::BTreeSet<&String> =
+            failing.factors.keys().chain(other.factors.keys()).collect();
+        keys.into_iter()
+            .filter(|key| key.as_str() != "tmpdir")
+            .filter(|key| failing.factors.get(*key) != other.factors.get(*key))
+            .map(|key| FactorDiff {
+                factor: key.clone(),
+                passing: other.factors.get(key).cloned(),
+                failing: failing.factors.get(key).cloned(),
+            })
+            .collect()
+    };
+    passing
+        .iter()
+        .map(|run| (run.seed, diff(run)))
+        .min_by_key(|(_, differences)| differences.len())
+        .map_or_else(
+            || SeedEnvDiff {
+                failing_seed: failing.seed,
+                passing_seed: None,
+                differences: Vec::new(),
+            },
+            |(seed, differences)| SeedEnvDiff {
+                failing_seed: failing.seed,
+                passing_seed: Some(seed),
+                differences,
+            },
+        )
+}
+
+fn capture_seed_run(
+    config: &MultiSeedConfig,
+    seed: u64,
+    test_threads: &str,
+    run_dir: &Path,
+) -> SeedRunCapture {
+    let tmpdir = run_dir.join("tmp");
+    let log_path = run_dir.join("output.log");
+    let mut capture = SeedRunCapture {
+        seed,
+        passed: false,
+        timed_out: false,
+        exit_code: None,
+        duration_ms: 0,
+        factors: BTreeMap::new(),
+        log_path: None,
+        log_tail: Vec::new(),
+        failure_artifact: None,
+    };
+
+    let start = std::time::Instant::now();
+    let outcome = std::fs::create_dir_all(&tmpdir)
+        .and_then(|()| std::fs::File::create(&log_path))
+        .and_then(|log| {
+            let mut cmd = std::process::Command::new("cargo");
+            cmd.arg("test");
+            for pkg in &config.packages {
+                cmd.arg("-p").arg(pkg);
+            }
+            cmd.arg(&config.test_name)
+                .arg("--")
+                .arg("--nocapture")
+                .env("HARNESS_SEED", seed.to_string())
+                .env("RUST_LOG", "debug")
+                .env("RUST_TEST_THREADS", test_threads)
+                .env("TMPDIR", &tmpdir)
+                .stdout(log.try_clone()?)
+                .stderr(log);
+            wait_with_timeout(cmd.spawn()?, config.timeout)
+        });
+    capture.duration_ms = duration_ms(start.elapsed());
+
+    match outcome {
+        Ok(Some(status)) => {
+            capture.passed = status.success();
+            capture.exit_code = status.code();
+        }
+        Ok(None) => capture.timed_out = true,
+        Err(e) => capture
+            .log_tail
+            .push(format!("failed to run cargo test: {e}")),
+    }
+    if let Ok(log) = std::fs::read_to_string(&log_path) {
+        let lines: Vec<&str> = log.lines().collect();
+        let skip = lines.len().saturating_sub(LOG_TAIL_LINES);
+        capture
+            .log_tail
+            .extend(lines[skip..].iter().map(|line| (*line).to_string()));
+        capture.log_path = Some(log_path);
+    }
+    capture.failure_artifact = scan_artifacts(run_dir).into_iter().next().map(|a| a.path);
+    capture.factors = seed_factors(
+        seed,
+        test_threads,
+        capture.duration_ms,
+        config.timeout,
+        capture.timed_out,
+    );
+    capture
+        .factors
+        .insert("tmpdir".to_string(), tmpdir.display().to_string());
+    capture
+}
+
+/// Wait for `child`, killing it once `timeout` elapses (`Ok(None)`).
+fn wait_with_timeout(
+    mut child: std::process::Child,
+    timeout: std::time::Duration,
+) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if std::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+}
+
+fn default_test_threads() -> String {
+    std::env::var("RUST_TEST_THREADS").unwrap_or_else(|_| {
+        std::thread::available_parallelism()
+            .map_or(1, std::num::NonZero::get)
+            .to_string()
+    })
+}
+
+fn duration_ms(duration: std::time::Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn sanitize_path_component(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
 }
 
 // ── Tests ────────────────────────────────────────────────────────────
@@ -1393,6 +1814,71 @@ mod tests {
         assert_eq!(config.num_seeds, DEFAULT_FLAKE_SEEDS.len());
         assert_eq!(config.packages.len(), 3);
         assert_eq!(config.timeout, std::time::Duration::from_mins(1));
+        assert!(!config.analyze);
+    }
+
+    fn factors(seed: u64, threads: &str) -> BTreeMap<String, String> {
+        seed_factors(seed, threads, 10, std::time::Duration::from_mins(1), false)
+    }
+
+    #[test]
+    fn correlate_factors_finds_seed_residue_and_thread_count() {
+        let observations = vec![
+            (factors(1, "8"), false),
+            (factors(5, "8"), false),
+            (factors(2, "8"), true),
+            (factors(4, "8"), true),
+            (factors(1, "1"), true),
+        ];
+        let correlations = correlate_factors(&observations);
+        let summaries: Vec<&str> = correlations.iter().map(|c| c.summary.as_str()).collect();
+        assert!(summaries.contains(&"fails only when seed_mod_4 == 1 (2/3 runs failed)"));
+        assert!(summaries.contains(&"fails only when seed_mod_2 == 1 (2/3 runs failed)"));
+        assert!(summaries.contains(&"fails only when test_threads == 8 (2/4 runs failed)"));
+        assert!(summaries.iter().all(|s| !s.contains("timing")));
+        assert!(summaries.iter().all(|s| !s.contains("seed ==")));
+    }
+
+    #[test]
+    fn correlate_factors_ignores_constant_factors() {
+        let observations = vec![(factors(3, "4"), false), (factors(7, "4"), true)];
+        assert!(correlate_factors(&observations).is_empty());
+    }
+
+    #[test]
+    fn minimal_env_diff_prefers_closest_passing_run() {
+        let run = |seed, threads: &str, passed| SeedRunCapture {
+            seed,
+            passed,
+            timed_out: false,
+            exit_code: Some(i32::from(!passed)),
+            duration_ms: 10,
+            factors: factors(seed, threads),
+            log_path: None,
+            log_tail: Vec::new(),
+            failure_artifact: None,
+        };
+        let failing = run(5, "8", false);
+        let single_threaded = run(5, "1", true);
+        let baseline = run(2, "8", true);
+        let diff = minimal_env_diff(&failing, &[&baseline, &single_threaded]);
+        assert_eq!(diff.passing_seed, Some(5));
+        assert_eq!(
+            diff.differences,
+            vec![FactorDiff {
+                factor: "test_threads".to_string(),
+                passing: Some("1".to_string()),
+                failing: Some("8".to_string()),
+            }]
+        );
+        assert!(minimal_env_diff(&failing, &[]).passing_seed.is_none());
+    }
+
+    #[test]
+    fn flake_report_omits_analysis_unless_present() {
+        let report = FlakeReport::from_runs("t", Vec::new());
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json.get("analysis").is_none());
     }
 
     #[test]
@@ -0,0 +1,572 @@
+//! Local event hooks: run an operator-provided command when mail events occur.
+//!
+//! Hooks are configured in a JSON file (`AM_HOOKS_FILE`, default `hooks.json`
+//! next to `config.env`) with the same setting keys per project and per agent:
+//!
+//! ```json
+//! {
+//!   "timeout_seconds": 5,
+//!   "projects": { "backend": { "on_message_hook": ["notify-send", "Agent Mail"] } },
+//!   "agents": { "backend/BlueLake": { "on_reservation_conflict_hook": ["/usr/local/bin/tmux-alert"] } }
+//! }
+//! ```
+//!
+//! Project keys are slugs or absolute human keys; agent keys are
+//! `<project>/<AgentName>`, and an agent binding wins over its project's.
+//! Commands are argv arrays and never go through a shell.
+//!
+//! The file is only honoured when it is owned by root or the current user and
+//! grants no group or other permissions (`chmod 600`). Hook commands therefore
+//! cannot be planted through the database, tool calls, or a world-writable
+//! file. The child gets the JSON event on stdin, a minimal environment (see
+//! [`HOOK_ENV_PASSTHROUGH`]), and is killed once the timeout elapses.
+//! [`dispatch`] runs it on a background thread so the mail operation that
+//! triggered it never waits, and repeated failures are logged at most once a
+//! minute per command.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write as _;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Config;
+
+/// File name of the hooks file inside the config directory.
+pub const HOOKS_FILE: &str = "hooks.json";
+
+/// Setting key for the hook run when an agent receives a message.
+pub const ON_MESSAGE_HOOK: &str = "on_message_hook";
+
+/// Setting key for the hook run when an agent's reservation request conflicts.
+pub const ON_RESERVATION_CONFLICT_HOOK: &str = "on_reservation_conflict_hook";
+
+/// Timeout applied when the hooks file does not set `timeout_seconds`.
+pub const DEFAULT_HOOK_TIMEOUT_SECONDS: u64 = 5;
+
+/// Upper bound on `timeout_seconds`, so a typo cannot pin a hook thread for hours.
+pub const MAX_HOOK_TIMEOUT_SECONDS: u64 = 60;
+
+/// Hooks allowed to run at once; further events are dropped (and logged).
+const MAX_IN_FLIGHT_HOOKS: usize = 8;
+
+/// Minimum gap between repeated failure logs for the same command.
+const FAILURE_LOG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// `PATH` given to hook commands.
+const HOOK_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// Variables copied from the server environment into the hook's otherwise
+/// empty environment, enough for desktop notifications and tmux.
+pub const HOOK_ENV_PASSTHROUGH: &[&str] = &[
+    "HOME",
+    "USER",
+    "LANG",
+    "DISPLAY",
+    "WAYLAND_DISPLAY",
+    "DBUS_SESSION_BUS_ADDRESS",
+    "XDG_RUNTIME_DIR",
+    "TMUX",
+];
+
+/// Events that can trigger a hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEventKind {
+    Message,
+    ReservationConflict,
+}
+
+impl HookEventKind {
+    /// Value of the `event` field in the JSON payload.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Message => "message",
+            Self::ReservationConflict => "reservation_conflict",
+        }
+    }
+
+    /// Setting key that configures this event's hook.
+    #[must_use]
+    pub const fn setting_key(self) -> &'static str {
+        match self {
+            Self::Message => ON_MESSAGE_HOOK,
+            Self::ReservationConflict => ON_RESERVATION_CONFLICT_HOOK,
+        }
+    }
+}
+
+/// Hook commands bound to one project or agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_message_hook: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_reservation_conflict_hook: Option<Vec<String>>,
+}
+
+impl HookSettings {
+    fn command(&self, kind: HookEventKind) -> Option<&[String]> {
+        match kind {
+            HookEventKind::Message => self.on_message_hook.as_deref(),
+            HookEventKind::ReservationConflict => self.on_reservation_conflict_hook.as_deref(),
+        }
+        .filter(|argv| {
+            argv.first()
+                .is_some_and(|program| !program.trim().is_empty())
+        })
+    }
+}
+
+/// Parsed hooks file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HooksFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+    /// Keyed by project slug or human key.
+    #[serde(default)]
+    pub projects: BTreeMap<String, HookSettings>,
+    /// Keyed by `<project slug or human key>/<AgentName>`.
+    #[serde(default)]
+    pub agents: BTreeMap<String, HookSettings>,
+}
+
+/// Why a hooks file was not loaded.
+#[derive(Debug, thiserror::Error)]
+pub enum HookError {
+    #[error("failed to read hooks file {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("hooks file {path} is not valid JSON: {source}")]
+    Parse {
+        path: String,
+        source: serde_json::Error,
+    },
+    #[error("refusing hooks file {path}: {reason}")]
+    Insecure { path: String, reason: String },
+}
+
+impl HooksFile {
+    /// Load `path` after checking its ownership and mode.
+    ///
+    /// Returns `Ok(None)` when the file does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HookError`] when the file is unreadable, malformed, or not
+    /// restricted to its owner.
+    pub fn load_secure(path: &Path) -> Result<Option<Self>, HookError> {
+        let display = path.display().to_string();
+        let meta = match std::fs::metadata(path) {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(HookError::Read {
+                    path: display,
+                    source,
+                });
+            }
+        };
+        check_permissions(&meta).map_err(|reason| HookError::Insecure {
+            path: display.clone(),
+            reason,
+        })?;
+        let raw = std::fs::read_to_string(path).map_err(|source| HookError::Read {
+            path: display.clone(),
+            source,
+        })?;
+        serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|source| HookError::Parse {
+                path: display,
+                source,
+            })
+    }
+
+    /// Command for `kind` bound to `agent` in the project, falling back to the
+    /// project's own binding. `project_keys` lists the slug and human key.
+    #[must_use]
+    pub fn command_for(
+        &self,
+        kind: HookEventKind,
+        project_keys: &[&str],
+        agent: Option<&str>,
+    ) -> Option<&[String]> {
+        if let Some(agent) = agent {
+            let bound = self.agents.iter().find_map(|(key, settings)| {
+                let (project, name) = key.rsplit_once('/')?;
+                (project_keys.contains(&project) && name.eq_ignore_ascii_case(agent))
+                    .then(|| settings.command(kind))
+                    .flatten()
+            });
+            if bound.is_some() {
+                return bound;
+            }
+        }
+        project_keys
+            .iter()
+            .find_map(|key| self.projects.get(*key))
+            .and_then(|settings| settings.command(kind))
+    }
+
+    #[must_use]
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.timeout_seconds
+                .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECONDS)
+                .clamp(1, MAX_HOOK_TIMEOUT_SECONDS),
+        )
+    }
+}
+
+fn check_permissions(meta: &std::fs::Metadata) -> Result<(), String> {
+    use std::os::unix::fs::MetadataExt as _;
+
+    if !meta.is_file() {
+        return Err("not a regular file".to_string());
+    }
+    let owner = meta.uid();
+    let euid = nix::unistd::geteuid().as_raw();
+    if owner != 0 && owner != euid {
+        return Err(format!("owned by uid {owner}, expected root or uid {euid}"));
+    }
+    let mode = meta.mode() & 0o777;
+    if mode & 0o077 != 0 {
+        return Err(format!(
+            "mode {mode:o} grants group/other access; run chmod 600"
+        ));
+    }
+    Ok(())
+}
+
+/// A hook invocation: the event kind, who it concerns, and its JSON payload.
+#[derive(Debug, Clone)]
+pub struct HookEvent {
+    pub kind: HookEventKind,
+    pub project_slug: String,
+    pub project_human_key: String,
+    pub agent: Option<String>,
+    /// Event-specific fields merged into the payload (`message`, `conflicts`).
+    pub detail: serde_json::Value,
+}
+
+impl HookEvent {
+    /// JSON written to the hook's stdin.
+    #[must_use]
+    pub fn payload(&self) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "event": self.kind.as_str(),
+            "project": self.project_slug,
+            "project_key": self.project_human_key,
+            "agent": self.agent,
+            "ts": crate::timestamps::micros_to_iso(crate::timestamps::now_micros()),
+        });
+        if let (Some(out), Some(detail)) = (payload.as_object_mut(), self.detail.as_object()) {
+            for (key, value) in detail {
+                out.insert(key.clone(), value.clone());
+            }
+        }
+        payload
+    }
+}
+
+/// Result of a hook that ran to completion or timed out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookRun {
+    pub command: Vec<String>,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub elapsed_ms: u64,
+}
+
+impl HookRun {
+    #[must_use]
+    pub fn succeeded(&self) -> bool {
+        !self.timed_out && self.exit_code == Some(0)
+    }
+}
+
+/// Run `argv` with `payload` on stdin, waiting at most `timeout`.
+///
+/// # Errors
+///
+/// Returns a message when the command cannot be spawned or waited on.
+pub fn run_hook(
+    argv: &[String],
+    payload: &serde_json::Value,
+    timeout: Duration,
+) -> Result<HookRun, String> {
+    let (program, args) = argv
+        .split_first()
+        .ok_or_else(|| "hook command is empty".to_string())?;
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .env_clear()
+        .env("PATH", HOOK_PATH)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    for key in HOOK_ENV_PASSTHROUGH {
+        if let Some(value) = std::env::var_os(key) {
+            command.env(key, value);
+        }
+    }
+    if let Some(event) = payload.get("event").and_then(serde_json::Value::as_str) {
+        command.env("AM_HOOK_EVENT", event);
+    }
+
+    let started = Instant::now();
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("failed to spawn {program}: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin may exit before reading; that is fine.
+        let _ = stdin.write_all(payload.to_string().as_bytes());
+    }
+
+    let deadline = started + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                return Ok(HookRun {
+                    command: argv.to_vec(),
+                    exit_code: status.code(),
+                    timed_out: false,
+                    elapsed_ms: elapsed_ms(started),
+                });
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Ok(HookRun {
+                    command: argv.to_vec(),
+                    exit_code: None,
+                    timed_out: true,
+                    elapsed_ms: elapsed_ms(started),
+                });
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+            Err(err) => return Err(format!("failed to wait for {program}: {err}")),
+        }
+    }
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+static FAILURE_LOG: std::sync::LazyLock<Mutex<HashMap<String, (Instant, u64)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Log a hook failure unless the same key logged within the last minute.
+fn log_failure_rate_limited(key: &str, message: &str) {
+    let mut log = FAILURE_LOG
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let now = Instant::now();
+    match log.get_mut(key) {
+        Some((last, suppressed)) if now.duration_since(*last) < FAILURE_LOG_INTERVAL => {
+            *suppressed = suppressed.saturating_add(1);
+        }
+        Some((last, suppressed)) => {
+            tracing::warn!(hook = key, suppressed = *suppressed, "{message}");
+            *last = now;
+            *suppressed = 0;
+        }
+        None => {
+            tracing::warn!(hook = key, "{message}");
+            log.insert(key.to_string(), (now, 0));
+        }
+    }
+}
+
+/// Fire the hook configured for `event`, if any, on a background thread.
+///
+/// Never blocks on the hook itself and never fails: configuration problems
+/// and hook failures are only logged (rate-limited).
+pub fn dispatch(config: &Config, event: HookEvent) {
+    let Some(path) = config.hooks_path.as_deref() else {
+        return;
+    };
+    let hooks = match HooksFile::load_secure(path) {
+        Ok(Some(hooks)) => hooks,
+        Ok(None) => return,
+        Err(err) => {
+            log_failure_rate_limited(HOOKS_FILE, &err.to_string());
+            return;
+        }
+    };
+    let project_keys = [
+        event.project_slug.as_str(),
+        event.project_human_key.as_str(),
+    ];
+    let Some(argv) = hooks
+        .command_for(event.kind, &project_keys, event.agent.as_deref())
+        .map(<[String]>::to_vec)
+    else {
+        return;
+    };
+    let key = format!("{}:{}", event.kind.setting_key(), argv[0]);
+    if IN_FLIGHT.fetch_add(1, Ordering::AcqRel) >= MAX_IN_FLIGHT_HOOKS {
+        IN_FLIGHT.fetch_sub(1, Ordering::AcqRel);
+        log_failure_rate_limited(&key, "dropping hook event: too many hooks already running");
+        return;
+    }
+    let timeout = hooks.timeout();
+    let payload = event.payload();
+    let spawned = std::thread::Builder::new()
+        .name("am-hook".into())
+        .spawn(move || {
+            match run_hook(&argv, &payload, timeout) {
+                Ok(run) if run.succeeded() => {}
+                Ok(run) if run.timed_out => log_failure_rate_limited(
+                    &key,
+                    &format!("hook killed after {}ms timeout", run.elapsed_ms),
+                ),
+                Ok(run) => log_failure_rate_limited(
+                    &key,
+                    &format!("hook exited with status {:?}", run.exit_code),
+                ),
+                Err(err) => log_failure_rate_limited(&key, &err),
+            }
+            IN_FLIGHT.fetch_sub(1, Ordering::AcqRel);
+        });
+    if let Err(err) = spawned {
+        IN_FLIGHT.fetch_sub(1, Ordering::AcqRel);
+        log_failure_rate_limited(HOOKS_FILE, &format!("failed to start hook thread: {err}"));
+    }
+}
+
+/// Block until hooks started by [`dispatch`] have finished, or `max_wait`
+/// elapses. Short-lived processes (CLI direct sends) call this before exiting
+/// so their hooks are not cut off; each hook is still bounded by its timeout.
+pub fn wait_for_pending(max_wait: Duration) {
+    let deadline = Instant::now() + max_wait;
+    while IN_FLIGHT.load(Ordering::Acquire) > 0 && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt as _;
+
+    fn write_hooks(dir: &Path, body: &str, mode: u32) -> std::path::PathBuf {
+        let path = dir.join(HOOKS_FILE);
+        std::fs::write(&path, body).expect("write hooks");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).expect("chmod");
+        path
+    }
+
+    #[test]
+    fn load_secure_rejects_group_or_world_accessible_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let body = r#"{"projects": {"backend": {"on_message_hook": ["true"]}}}"#;
+        let path = write_hooks(dir.path(), body, 0o644);
+        let err = HooksFile::load_secure(&path).expect_err("0644 must be refused");
+        assert!(err.to_string().contains("chmod 600"), "{err}");
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).expect("chmod");
+        let hooks = HooksFile::load_secure(&path)
+            .expect("0600 loads")
+            .expect("file exists");
+        assert_eq!(hooks.projects.len(), 1);
+        assert!(
+            HooksFile::load_secure(&dir.path().join("missing.json"))
+                .expect("missing is fine")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn agent_binding_wins_over_project_binding() {
+        let hooks: HooksFile = serde_json::from_str(
+            r#"{
+                "projects": {
+                    "backend": {"on_message_hook": ["project-hook"]},
+                    "/work/api": {"on_reservation_conflict_hook": ["api-conflict"]}
+                },
+                "agents": {"backend/BlueLake": {"on_message_hook": ["agent-hook"]}}
+            }"#,
+        )
+        .expect("parse");
+        let keys = ["backend", "/work/backend"];
+        let argv = |kind, agent| {
+            hooks
+                .command_for(kind, &keys, agent)
+                .map(|argv| argv[0].clone())
+        };
+        assert_eq!(
+            argv(HookEventKind::Message, Some("bluelake")).as_deref(),
+            Some("agent-hook")
+        );
+        assert_eq!(
+            argv(HookEventKind::Message, Some("RedStone")).as_deref(),
+            Some("project-hook")
+        );
+        assert_eq!(
+            argv(HookEventKind::ReservationConflict, Some("BlueLake")),
+            None
+        );
+        assert_eq!(
+            hooks
+                .command_for(
+                    HookEventKind::ReservationConflict,
+                    &["api", "/work/api"],
+                    None
+                )
+                .map(|argv| argv[0].as_str()),
+            Some("api-conflict")
+        );
+        assert_eq!(
+            hooks.timeout(),
+            Duration::from_secs(DEFAULT_HOOK_TIMEOUT_SECONDS)
+        );
+    }
+
+    #[test]
+    fn run_hook_feeds_payload_on_stdin_with_minimal_env_and_enforces_timeout() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("event.json");
+        // `cargo test` exports CARGO_MANIFEST_DIR; the hook must not see it.
+        let script = format!(
+            "cat > '{}'; printf '%s' \"$AM_HOOK_EVENT:$CARGO_MANIFEST_DIR\" >> '{}'",
+            out.display(),
+            out.display()
+        );
+        let event = HookEvent {
+            kind: HookEventKind::Message,
+            project_slug: "backend".to_string(),
+            project_human_key: "/work/backend".to_string(),
+            agent: Some("BlueLake".to_string()),
+            detail: serde_json::json!({"message": {"id": 7, "subject": "deploy"}}),
+        };
+        let argv = vec!["/bin/sh".to_string(), "-c".to_string(), script];
+        let run = run_hook(&argv, &event.payload(), Duration::from_secs(5)).expect("run hook");
+        assert!(run.succeeded(), "{run:?}");
+        let written = std::fs::read_to_string(&out).expect("hook output");
+        assert!(written.contains(r#""event":"message""#), "{written}");
+        assert!(written.contains(r#""subject":"deploy""#), "{written}");
+        assert!(written.ends_with("message:"), "env leaked: {written}");
+
+        let sleeper = vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            "sleep 5".to_string(),
+        ];
+        let run =
+            run_hook(&sleeper, &event.payload(), Duration::from_millis(100)).expect("run sleeper");
+        assert!(run.timed_out);
+        assert!(!run.succeeded());
+    }
+}
@@ -0,0 +1,169 @@
+//! Importance-aware inbox ordering and filtering.
+//!
+//! The CLI `am mail inbox` and the `fetch_inbox` tool both rank messages with
+//! [`inbox_importance_rank`], so `--order` / `order` and `--min-importance` /
+//! `min_importance` agree across interfaces. Both report the rank next to each
+//! message so clients can re-sort without duplicating the mapping.
+
+use serde::{Deserialize, Serialize};
+
+use crate::project_settings::importance_rank;
+
+/// Rank of `normal` importance, also used for unrecognised values.
+pub const NORMAL_IMPORTANCE_RANK: u8 = 1;
+/// Rank of `high` importance; the floor implied by `urgent_only`.
+pub const HIGH_IMPORTANCE_RANK: u8 = 2;
+/// Rank of `urgent` importance.
+pub const URGENT_IMPORTANCE_RANK: u8 = 3;
+
+/// Rank of a message importance (`low` = 0 .. `urgent` = 3).
+///
+/// Unrecognised values rank as `normal`, the importance a message gets when
+/// the sender does not choose one.
+#[must_use]
+pub fn inbox_importance_rank(importance: &str) -> u8 {
+    importance_rank(importance)
+        .and_then(|rank| u8::try_from(rank).ok())
+        .unwrap_or(NORMAL_IMPORTANCE_RANK)
+}
+
+/// Parse a `--min-importance` / `min_importance` threshold into its rank.
+/// Accepts `normal`, `high`, or `urgent` (case-insensitive).
+#[must_use]
+pub fn parse_min_importance(raw: &str) -> Option<u8> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "normal" => Some(NORMAL_IMPORTANCE_RANK),
+        "high" => Some(HIGH_IMPORTANCE_RANK),
+        "urgent" => Some(URGENT_IMPORTANCE_RANK),
+        _ => None,
+    }
+}
+
+/// Inbox ordering.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxOrder {
+    /// Newest first (the historical order).
+    #[default]
+    Time,
+    /// Highest importance rank first, newest first within a rank.
+    Importance,
+    /// `high`/`urgent` ahead of `normal`/`low`, newest first within each
+    /// bucket, so a fresh `high` message is not buried under older `urgent`
+    /// ones.
+    Hybrid,
+}
+
+impl InboxOrder {
+    /// Parse `time`, `importance`, or `hybrid` (case-insensitive).
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "time" => Some(Self::Time),
+            "importance" => Some(Self::Importance),
+            "hybrid" => Some(Self::Hybrid),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Time => "time",
+            Self::Importance => "importance",
+            Self::Hybrid => "hybrid",
+        }
+    }
+
+    /// Primary sort key for a message of importance `rank`; higher sorts first.
+    fn bucket(self, rank: u8) -> u8 {
+        match self {
+            Self::Time => 0,
+            Self::Importance => rank,
+            Self::Hybrid => u8::from(rank >= HIGH_IMPORTANCE_RANK),
+        }
+    }
+}
+
+/// Sort `items` in place by `order`. `key` returns each item's importance and
+/// creation time; ties keep their incoming relative order.
+pub fn sort_inbox<T, F>(items: &mut [T], order: InboxOrder, key: F)
+where
+    F: Fn(&T) -> (u8, i64),
+{
+    items.sort_by(|a, b| {
+        let (rank_a, created_a) = key(a);
+        let (rank_b, created_b) = key(b);
+        order
+            .bucket(rank_b)
+            .cmp(&order.bucket(rank_a))
+            .then(created_b.cmp(&created_a))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_maps_levels_and_defaults_unknown_to_normal() {
+        assert_eq!(inbox_importance_rank("low"), 0);
+        assert_eq!(inbox_importance_rank("Normal"), NORMAL_IMPORTANCE_RANK);
+        assert_eq!(inbox_importance_rank("high"), HIGH_IMPORTANCE_RANK);
+        assert_eq!(inbox_importance_rank(" URGENT "), URGENT_IMPORTANCE_RANK);
+        assert_eq!(inbox_importance_rank("critical"), NORMAL_IMPORTANCE_RANK);
+        assert_eq!(parse_min_importance("HIGH"), Some(HIGH_IMPORTANCE_RANK));
+        assert_eq!(parse_min_importance("low"), None);
+    }
+
+    #[test]
+    fn sort_orders_by_time_importance_and_hybrid() {
+        let messages = [
+            ("old-urgent", "urgent", 10),
+            ("new-high", "high", 40),
+            ("newest-normal", "normal", 50),
+            ("mid-urgent", "urgent", 30),
+            ("low", "low", 20),
+        ];
+        let sorted = |order| {
+            let mut items = messages.to_vec();
+            sort_inbox(&mut items, order, |(_, importance, ts)| {
+                (inbox_importance_rank(importance), *ts)
+            });
+            items.iter().map(|(name, _, _)| *name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            sorted(InboxOrder::Time),
+            [
+                "newest-normal",
+                "new-high",
+                "mid-urgent",
+                "low",
+                "old-urgent"
+            ]
+        );
+        assert_eq!(
+            sorted(InboxOrder::Importance),
+            [
+                "mid-urgent",
+                "old-urgent",
+                "new-high",
+                "newest-normal",
+                "low"
+            ]
+        );
+        assert_eq!(
+            sorted(InboxOrder::Hybrid),
+            [
+                "new-high",
+                "mid-urgent",
+                "old-urgent",
+                "newest-normal",
+                "low"
+            ]
+        );
+        assert_eq!(InboxOrder::parse(" Hybrid"), Some(InboxOrder::Hybrid));
+        assert_eq!(InboxOrder::parse("priority"), None);
+    }
+}
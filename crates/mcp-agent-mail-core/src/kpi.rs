@@ -1681,6 +1681,8 @@ mod tests {
                 tool_errors_total: tool_errors,
                 tool_latency_us: make_histogram(1000, 5000, 10_000),
                 contact_enforcement_bypass_total: 0,
+                webhooks_delivered_total: 0,
+                webhooks_failed_total: 0,
             },
             db: DbMetricsSnapshot {
                 pool_acquires_total: tool_calls,
@@ -1732,6 +1734,7 @@ mod tests {
                 commit_batch_size_last: 3,
                 lockfree_commits_total: commit_drained / 2,
                 lockfree_commit_fallbacks_total: 0,
+                reservation_expiry_warnings_total: 0,
             },
             system: SystemMetricsSnapshot {
                 disk_storage_free_bytes: 10_000_000_000,
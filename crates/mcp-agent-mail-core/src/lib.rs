@@ -8,9 +8,11 @@
 
 #![forbid(unsafe_code)]
 
+pub mod agent_connectors;
 pub mod agent_detect;
 pub mod agent_health;
 pub mod am_version;
+pub mod archive_location;
 pub mod atc_adaptation;
 pub mod atc_admissibility;
 pub mod atc_assumptions;
@@ -36,6 +38,7 @@ pub mod backpressure;
 pub mod bocpd;
 pub mod config;
 pub mod conformal;
+pub mod contact_qualifiers;
 pub mod diagnostics;
 pub mod disk;
 pub mod ephemeral;
@@ -47,8 +50,10 @@ pub mod flake_triage;
 pub mod git_binary;
 pub mod git_cmd;
 pub mod git_lock;
+pub mod hooks;
 pub mod host_health;
 pub mod identity;
+pub mod inbox_order;
 pub mod intern;
 pub mod kpi;
 pub mod lock_order;
@@ -59,7 +64,11 @@ pub mod metrics;
 pub mod models;
 pub mod pane_identity;
 pub mod pattern_overlap;
+pub mod project_settings;
+pub mod reserved_names;
+pub mod safe_mode;
 pub mod search_types;
+pub mod settings;
 pub mod setup;
 pub mod slo;
 pub mod test_harness;
@@ -102,16 +111,19 @@ pub use atc_user_surfaces::{
     load_latest_atc_canary_report,
 };
 pub use backpressure::{
-    CapacityAction, CapacityGovernorDecision, HealthLevel, HealthSignals, cached_health_level,
+    CapacityAction, CapacityGovernorDecision, HealthLevel, HealthSignals, LevelTransition,
+    ReaperDecision, ReaperPauseReason, SheddingStatus, cached_health_level,
     capacity_governor_decision, capacity_governor_decision_from_parts,
     compute_capacity_governor_decision, compute_health_level, compute_health_level_with_signals,
-    is_shedable_tool, level_transitions, refresh_health_level, set_shedding_enabled,
-    shedding_enabled, should_shed_tool,
+    is_shedable_tool, level_transitions, reaper_pause_reason, recent_level_transitions,
+    recent_reaper_decisions, record_reaper_decision, refresh_health_level, register_shedable_tool,
+    set_shedding_enabled, shed_threshold, shedding_enabled, shedding_status, should_shed_tool,
 };
 pub use config::{
-    AppEnvironment, AtcWriteMode, Config, InterfaceMode, ProjectIdentityMode, RateLimitBackend,
-    compute_ephemeral_storage_root,
+    AppEnvironment, ArchiveMirrorMode, AtcWriteMode, Config, InterfaceMode, ProjectIdentityMode,
+    RateLimitBackend, ToolRateLimit, compute_ephemeral_storage_root, parse_tool_rate_limits,
 };
+pub use contact_qualifiers::{AUTO_APPROVAL_WINDOW_MICROS, ContactAutoQualifiers};
 pub use diagnostics::{
     ArchiveScanDedupeRule, ArchiveScanDiagnostic, ArchiveScanScope, ArchiveScanSeverityBucket,
     ArchiveScanSummary, ArchiveScanSummaryBucket, ArchiveScanSummaryFinding, ArtifactPointer,
@@ -130,6 +142,7 @@ pub use error::{Error as MailError, Result as MailResult};
 pub use evidence_ledger::{
     EVIDENCE_LEDGER_PATH_ENV, EvidenceLedger, EvidenceLedgerEntry,
     append_evidence_entry_if_configured, append_evidence_entry_to_path, evidence_ledger,
+    flush_evidence_ledger,
 };
 pub use experience::{
     EffectKind, ExperienceBuilder, ExperienceOutcome, ExperienceRow, ExperienceState,
@@ -197,6 +210,9 @@ pub use pane_identity::{
     resolve_identity_current_pane, resolve_identity_with_optional_pane, resolve_identity_with_path,
     write_identity, write_identity_current_pane, write_identity_with_optional_pane,
 };
+pub use reserved_names::{
+    ReservedAgentNameCache, ReservedAgentNames, reserved_agent_name_pattern, reserved_agent_names,
+};
 pub use search_types::{
     DateRange, DocChange, DocId, DocKind, Document, ExplainComposerConfig, ExplainReasonCode,
     ExplainReport, ExplainStage, ExplainVerbosity, HighlightRange, HitExplanation,
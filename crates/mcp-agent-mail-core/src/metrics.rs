@@ -368,6 +368,11 @@ pub struct ToolsMetrics {
     /// falls back to empty results (fail-open). Allows alerting on silent
     /// enforcement degradation.
     pub contact_enforcement_bypass_total: Counter,
+    /// Outbound webhook events accepted by the receiver (2xx).
+    pub webhooks_delivered_total: Counter,
+    /// Outbound webhook events dropped after exhausting retries or because
+    /// the delivery queue was full.
+    pub webhooks_failed_total: Counter,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -376,6 +381,8 @@ pub struct ToolsMetricsSnapshot {
     pub tool_errors_total: u64,
     pub tool_latency_us: HistogramSnapshot,
     pub contact_enforcement_bypass_total: u64,
+    pub webhooks_delivered_total: u64,
+    pub webhooks_failed_total: u64,
 }
 
 impl Default for ToolsMetrics {
@@ -385,6 +392,8 @@ impl Default for ToolsMetrics {
             tool_errors_total: Counter::new(),
             tool_latency_us: Log2Histogram::new(),
             contact_enforcement_bypass_total: Counter::new(),
+            webhooks_delivered_total: Counter::new(),
+            webhooks_failed_total: Counter::new(),
         }
     }
 }
@@ -406,6 +415,8 @@ impl ToolsMetrics {
             tool_errors_total: self.tool_errors_total.load(),
             tool_latency_us: self.tool_latency_us.snapshot(),
             contact_enforcement_bypass_total: self.contact_enforcement_bypass_total.load(),
+            webhooks_delivered_total: self.webhooks_delivered_total.load(),
+            webhooks_failed_total: self.webhooks_failed_total.load(),
         }
     }
 }
@@ -715,6 +726,9 @@ pub struct StorageMetrics {
     pub lockfree_commits_total: Counter,
     /// Lock-free commit attempts that failed and fell back to index-based commit.
     pub lockfree_commit_fallbacks_total: Counter,
+
+    /// Reservation expiry warnings sent to holding agents by the server sweep.
+    pub reservation_expiry_warnings_total: Counter,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -753,6 +767,8 @@ pub struct StorageMetricsSnapshot {
     pub commit_batch_size_last: u64,
     pub lockfree_commits_total: u64,
     pub lockfree_commit_fallbacks_total: u64,
+
+    pub reservation_expiry_warnings_total: u64,
 }
 
 #[derive(Debug)]
@@ -1842,6 +1858,7 @@ impl Default for StorageMetrics {
             commit_batch_size_last: GaugeU64::new(),
             lockfree_commits_total: Counter::new(),
             lockfree_commit_fallbacks_total: Counter::new(),
+            reservation_expiry_warnings_total: Counter::new(),
         }
     }
 }
@@ -1884,6 +1901,7 @@ impl StorageMetrics {
             commit_batch_size_last: self.commit_batch_size_last.load(),
             lockfree_commits_total: self.lockfree_commits_total.load(),
             lockfree_commit_fallbacks_total: self.lockfree_commit_fallbacks_total.load(),
+            reservation_expiry_warnings_total: self.reservation_expiry_warnings_total.load(),
         }
     }
 }
@@ -127,6 +127,11 @@ pub struct Message {
     pub created_ts: NaiveDateTime,
     /// JSON array of attachment metadata
     pub attachments: String,
+    /// Optional expiry for ephemeral messages. Expired, unread,
+    /// non-ack-required messages are hidden from unread views and eventually
+    /// purged. Stored in the `message_expiries` sidecar table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_ts: Option<NaiveDateTime>,
 }
 
 impl Default for Message {
@@ -142,6 +147,7 @@ impl Default for Message {
             ack_required: false,
             created_ts: chrono::Utc::now().naive_utc(),
             attachments: "[]".to_string(),
+            expires_ts: None,
         }
     }
 }
@@ -640,8 +646,11 @@ pub fn is_valid_agent_name(name: &str) -> bool {
 /// to compose messages; it is inserted via `insert_system_agent` without name
 /// validation. Health / diagnostic checks must exempt these from the
 /// `malformed_agent_name` warning so the operator identity does not generate a
-/// permanent, un-actionable warning (see #243 Bug 3).
-pub const RESERVED_OPERATOR_AGENT_NAMES: &[&str] = &["HumanOverseer"];
+/// permanent, un-actionable warning (see #243 Bug 3). `AckReminder` authors
+/// the automatic ack reminders sent by the server, and `ReservationNotifier`
+/// the reservation expiry warnings.
+pub const RESERVED_OPERATOR_AGENT_NAMES: &[&str] =
+    &["HumanOverseer", "AckReminder", "ReservationNotifier"];
 
 /// Returns `true` if `name` is a reserved operator / system identity that is
 /// exempt from adjective+noun validation (case-insensitive, trimmed).
@@ -926,9 +935,30 @@ fn push_pascal_case_word(out: &mut String, word: &str) {
     out.push_str(chars.as_str());
 }
 
-/// Generates a random valid agent name.
+/// Attempts [`generate_agent_name`] makes to avoid the reserved-name deny list
+/// before giving up and returning the last candidate.
+const GENERATE_AGENT_NAME_ATTEMPTS: usize = 64;
+
+/// Generates a random valid agent name that is not on the operator's
+/// reserved-name deny list.
+///
+/// If every attempt lands on a reserved name (an overly broad pattern), the
+/// last candidate is returned and registration rejects it with
+/// `AGENT_NAME_RESERVED`.
 #[must_use]
 pub fn generate_agent_name() -> String {
+    let reserved = crate::reserved_names::reserved_agent_names();
+    let mut candidate = random_agent_name();
+    for _ in 1..GENERATE_AGENT_NAME_ATTEMPTS {
+        if reserved.matching_pattern(&candidate).is_none() {
+            break;
+        }
+        candidate = random_agent_name();
+    }
+    candidate
+}
+
+fn random_agent_name() -> String {
     let mut seed_bytes = [0u8; 8];
     // Fall back to a time-based pseudo-random value if getrandom fails
     if getrandom::fill(&mut seed_bytes).is_err() {
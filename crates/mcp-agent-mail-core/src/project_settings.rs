@@ -0,0 +1,345 @@
+//! Per-project settings persisted in the `project_settings` table.
+//!
+//! Settings are plain `key = value` text rows scoped to one project. This
+//! module owns the set of recognised keys, normalizes values before they are
+//! stored, and interprets them for the features that consume them (the
+//! auto-CC send policy, sender authentication, and message retention).
+
+/// Agents appended to CC on sends that meet [`AUTO_CC_MIN_IMPORTANCE`].
+/// Stored as a comma-separated list of agent names.
+pub const AUTO_CC_AGENTS: &str = "auto_cc_agents";
+
+/// Lowest importance (`low`, `normal`, `high`, `urgent`) that triggers
+/// auto-CC. Defaults to [`DEFAULT_AUTO_CC_MIN_IMPORTANCE`].
+pub const AUTO_CC_MIN_IMPORTANCE: &str = "auto_cc_min_importance";
+
+/// Threshold applied when [`AUTO_CC_MIN_IMPORTANCE`] is unset.
+pub const DEFAULT_AUTO_CC_MIN_IMPORTANCE: &str = "urgent";
+
+/// Recorded on a project that `am projects adopt` merged into another; the
+/// value is the target slug. Written by the CLI, not settable by operators.
+pub const ADOPTED_INTO: &str = "adopted_into";
+
+/// Age in days after which `am mail apply-retention` removes a message from
+/// the database. Unset means the project keeps every message.
+pub const RETENTION_DAYS: &str = "retention_days";
+
+/// What retention does with expired messages: `archive` (keep the Git
+/// archive copy) or `delete`. Defaults to [`DEFAULT_RETENTION_ACTION`].
+pub const RETENTION_ACTION: &str = "retention_action";
+
+/// Action applied when [`RETENTION_ACTION`] is unset.
+pub const DEFAULT_RETENTION_ACTION: &str = "archive";
+
+/// When `true`, sends and replies must present the sender's per-agent
+/// secret. Unset or `false` leaves sends unauthenticated.
+pub const REQUIRE_SENDER_AUTH: &str = "require_sender_auth";
+
+/// Every key accepted by `am projects settings set`.
+pub const KNOWN_PROJECT_SETTINGS: &[&str] = &[
+    AUTO_CC_AGENTS,
+    AUTO_CC_MIN_IMPORTANCE,
+    REQUIRE_SENDER_AUTH,
+    RETENTION_ACTION,
+    RETENTION_DAYS,
+];
+
+const IMPORTANCE_LEVELS: &[&str] = &["low", "normal", "high", "urgent"];
+
+/// Rank of an importance level (`low` = 0 .. `urgent` = 3), or `None` for
+/// unrecognised values.
+#[must_use]
+pub fn importance_rank(importance: &str) -> Option<usize> {
+    let lowered = importance.trim().to_ascii_lowercase();
+    IMPORTANCE_LEVELS.iter().position(|level| *level == lowered)
+}
+
+/// Validate `raw` for `key` and return the canonical stored form.
+///
+/// Agent lists are trimmed and deduplicated case-insensitively (first
+/// spelling wins); importance thresholds and retention actions are
+/// lowercased; retention days must be a positive integer; flags are stored
+/// as `true` or `false`.
+///
+/// # Errors
+///
+/// Returns a human-readable message for unknown keys or invalid values.
+pub fn normalize_project_setting(key: &str, raw: &str) -> Result<String, String> {
+    match key {
+        AUTO_CC_AGENTS => Ok(split_agent_list(raw).join(",")),
+        AUTO_CC_MIN_IMPORTANCE => {
+            let lowered = raw.trim().to_ascii_lowercase();
+            if importance_rank(&lowered).is_some() {
+                Ok(lowered)
+            } else {
+                Err(format!(
+                    "invalid {AUTO_CC_MIN_IMPORTANCE} '{raw}': expected one of low, normal, high, urgent"
+                ))
+            }
+        }
+        REQUIRE_SENDER_AUTH => parse_flag(raw).map(|flag| flag.to_string()).ok_or_else(|| {
+            format!("invalid {REQUIRE_SENDER_AUTH} '{raw}': expected true or false")
+        }),
+        RETENTION_DAYS => match raw.trim().parse::<u32>() {
+            Ok(days) if days > 0 => Ok(days.to_string()),
+            _ => Err(format!(
+                "invalid {RETENTION_DAYS} '{raw}': expected a positive number of days"
+            )),
+        },
+        RETENTION_ACTION => RetentionAction::parse(raw)
+            .map(|action| action.as_str().to_string())
+            .ok_or_else(|| {
+                format!("invalid {RETENTION_ACTION} '{raw}': expected archive or delete")
+            }),
+        other => Err(format!(
+            "unknown project setting '{other}'; known settings: {}",
+            KNOWN_PROJECT_SETTINGS.join(", ")
+        )),
+    }
+}
+
+fn parse_flag(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether the project's `(key, value)` rows turn on [`REQUIRE_SENDER_AUTH`].
+#[must_use]
+pub fn sender_auth_required<'a, I>(settings: I) -> bool
+where
+    I: IntoIterator<Item = (&'a str, &'a str)>,
+{
+    settings
+        .into_iter()
+        .any(|(key, value)| key == REQUIRE_SENDER_AUTH && parse_flag(value) == Some(true))
+}
+
+fn split_agent_list(raw: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for name in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        if !out.iter().any(|seen| seen.eq_ignore_ascii_case(name)) {
+            out.push(name.to_string());
+        }
+    }
+    out
+}
+
+/// Auto-CC policy derived from a project's settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoCcPolicy {
+    pub agents: Vec<String>,
+    pub min_importance: String,
+}
+
+impl AutoCcPolicy {
+    /// Build the policy from `(key, value)` rows. Returns `None` when no
+    /// auto-CC agents are configured.
+    #[must_use]
+    pub fn from_settings<'a, I>(settings: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut agents = Vec::new();
+        let mut min_importance = DEFAULT_AUTO_CC_MIN_IMPORTANCE.to_string();
+        for (key, value) in settings {
+            match key {
+                AUTO_CC_AGENTS => agents = split_agent_list(value),
+                AUTO_CC_MIN_IMPORTANCE if importance_rank(value).is_some() => {
+                    min_importance = value.trim().to_ascii_lowercase();
+                }
+                _ => {}
+            }
+        }
+        (!agents.is_empty()).then_some(Self {
+            agents,
+            min_importance,
+        })
+    }
+
+    /// Whether a message of `importance` meets the policy threshold.
+    #[must_use]
+    pub fn applies_to(&self, importance: &str) -> bool {
+        match (
+            importance_rank(importance),
+            importance_rank(&self.min_importance),
+        ) {
+            (Some(rank), Some(min)) => rank >= min,
+            _ => false,
+        }
+    }
+}
+
+/// What happens to a message once it falls outside the retention window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionAction {
+    /// Make sure the Git archive holds the message, then drop the DB row.
+    Archive,
+    /// Drop the DB row without touching the archive.
+    Delete,
+}
+
+impl RetentionAction {
+    /// Parse `archive` / `delete` (case-insensitive).
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "archive" => Some(Self::Archive),
+            "delete" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Archive => "archive",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Retention policy derived from a project's settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub days: u32,
+    pub action: RetentionAction,
+}
+
+impl RetentionPolicy {
+    /// Build the policy from `(key, value)` rows. Returns `None` when
+    /// [`RETENTION_DAYS`] is unset or invalid.
+    #[must_use]
+    pub fn from_settings<'a, I>(settings: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut days = None;
+        let mut action = RetentionAction::Archive;
+        for (key, value) in settings {
+            match key {
+                RETENTION_DAYS => {
+                    days = value.trim().parse::<u32>().ok().filter(|days| *days > 0);
+                }
+                RETENTION_ACTION => {
+                    if let Some(parsed) = RetentionAction::parse(value) {
+                        action = parsed;
+                    }
+                }
+                _ => {}
+            }
+        }
+        days.map(|days| Self { days, action })
+    }
+
+    /// Messages created before this timestamp (microseconds) are expired.
+    #[must_use]
+    pub fn cutoff_micros(&self, now_us: i64) -> i64 {
+        now_us.saturating_sub(i64::from(self.days) * 86_400 * 1_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_dedupes_agents_and_lowercases_importance() {
+        assert_eq!(
+            normalize_project_setting(AUTO_CC_AGENTS, " Conductor, overseer ,conductor,,Overseer")
+                .unwrap(),
+            "Conductor,overseer"
+        );
+        assert_eq!(
+            normalize_project_setting(AUTO_CC_MIN_IMPORTANCE, "HIGH").unwrap(),
+            "high"
+        );
+        assert!(normalize_project_setting(AUTO_CC_MIN_IMPORTANCE, "critical").is_err());
+        assert!(normalize_project_setting("auto_bcc_agents", "x").is_err());
+    }
+
+    #[test]
+    fn policy_defaults_to_urgent_threshold() {
+        let policy = AutoCcPolicy::from_settings([(AUTO_CC_AGENTS, "Conductor")]).unwrap();
+        assert_eq!(policy.min_importance, "urgent");
+        assert!(policy.applies_to("urgent"));
+        assert!(policy.applies_to("URGENT"));
+        assert!(!policy.applies_to("high"));
+        assert!(!policy.applies_to("bogus"));
+    }
+
+    #[test]
+    fn policy_honors_threshold_and_requires_agents() {
+        let policy = AutoCcPolicy::from_settings([
+            (AUTO_CC_MIN_IMPORTANCE, "high"),
+            (AUTO_CC_AGENTS, "Conductor,Overseer"),
+        ])
+        .unwrap();
+        assert_eq!(policy.agents, vec!["Conductor", "Overseer"]);
+        assert!(policy.applies_to("high"));
+        assert!(!policy.applies_to("normal"));
+
+        assert!(AutoCcPolicy::from_settings([(AUTO_CC_MIN_IMPORTANCE, "low")]).is_none());
+        assert!(AutoCcPolicy::from_settings([(AUTO_CC_AGENTS, " , ")]).is_none());
+    }
+
+    #[test]
+    fn normalize_validates_retention_settings() {
+        assert_eq!(
+            normalize_project_setting(RETENTION_DAYS, " 30 ").unwrap(),
+            "30"
+        );
+        assert!(normalize_project_setting(RETENTION_DAYS, "0").is_err());
+        assert!(normalize_project_setting(RETENTION_DAYS, "-3").is_err());
+        assert!(normalize_project_setting(RETENTION_DAYS, "30d").is_err());
+        assert_eq!(
+            normalize_project_setting(RETENTION_ACTION, "Delete").unwrap(),
+            "delete"
+        );
+        assert!(normalize_project_setting(RETENTION_ACTION, "purge").is_err());
+    }
+
+    #[test]
+    fn sender_auth_flag_normalizes_and_defaults_off() {
+        assert_eq!(
+            normalize_project_setting(REQUIRE_SENDER_AUTH, " Yes ").unwrap(),
+            "true"
+        );
+        assert_eq!(
+            normalize_project_setting(REQUIRE_SENDER_AUTH, "0").unwrap(),
+            "false"
+        );
+        assert!(normalize_project_setting(REQUIRE_SENDER_AUTH, "maybe").is_err());
+
+        assert!(!sender_auth_required([(AUTO_CC_AGENTS, "Conductor")]));
+        assert!(!sender_auth_required([(REQUIRE_SENDER_AUTH, "false")]));
+        assert!(sender_auth_required([(REQUIRE_SENDER_AUTH, "true")]));
+    }
+
+    #[test]
+    fn retention_policy_requires_days_and_defaults_to_archive() {
+        assert!(RetentionPolicy::from_settings([(RETENTION_ACTION, "delete")]).is_none());
+        assert!(RetentionPolicy::from_settings([(RETENTION_DAYS, "0")]).is_none());
+
+        let policy = RetentionPolicy::from_settings([(RETENTION_DAYS, "7")]).unwrap();
+        assert_eq!(policy.action, RetentionAction::Archive);
+        assert_eq!(policy.cutoff_micros(8 * 86_400_000_000), 86_400_000_000);
+
+        let policy =
+            RetentionPolicy::from_settings([(RETENTION_ACTION, "delete"), (RETENTION_DAYS, "90")])
+                .unwrap();
+        assert_eq!(
+            policy,
+            RetentionPolicy {
+                days: 90,
+                action: RetentionAction::Delete,
+            }
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Operator-maintained deny list of agent names.
+//!
+//! Patterns come from `AGENT_MAIL_RESERVED_AGENT_NAMES` (comma-separated) and
+//! the optional `AGENT_MAIL_RESERVED_AGENT_NAMES_FILE` (one pattern per line,
+//! `#` starts a comment). A pattern is either an exact name or a glob using
+//! `*` / `?`; matching is case-insensitive, like agent name lookup.
+//!
+//! The file is re-read whenever its modification time changes, so a running
+//! server picks up edits on the next registration without a restart.
+
+#![forbid(unsafe_code)]
+
+use globset::{GlobBuilder, GlobMatcher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::Config;
+
+/// A compiled set of reserved-name patterns.
+#[derive(Debug, Clone, Default)]
+pub struct ReservedAgentNames {
+    patterns: Vec<(String, GlobMatcher)>,
+}
+
+impl ReservedAgentNames {
+    /// Compile patterns, skipping blanks and `#` comments. Returns the set and
+    /// the patterns that failed to compile.
+    #[must_use]
+    pub fn compile<'a>(patterns: impl IntoIterator<Item = &'a str>) -> (Self, Vec<String>) {
+        let mut compiled = Vec::new();
+        let mut rejected = Vec::new();
+        for raw in patterns {
+            let pattern = raw.split('#').next().unwrap_or_default().trim();
+            if pattern.is_empty() {
+                continue;
+            }
+            match GlobBuilder::new(pattern).case_insensitive(true).build() {
+                Ok(glob) => compiled.push((pattern.to_string(), glob.compile_matcher())),
+                Err(_) => rejected.push(pattern.to_string()),
+            }
+        }
+        (Self { patterns: compiled }, rejected)
+    }
+
+    /// Parse a deny-list file body (one pattern per line).
+    #[must_use]
+    pub fn parse_file(text: &str) -> (Self, Vec<String>) {
+        Self::compile(text.lines())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The first pattern that matches `name`, if any.
+    #[must_use]
+    pub fn matching_pattern(&self, name: &str) -> Option<&str> {
+        let name = name.trim();
+        self.patterns
+            .iter()
+            .find(|(_, matcher)| matcher.is_match(name))
+            .map(|(pattern, _)| pattern.as_str())
+    }
+
+    fn extend(&mut self, other: &Self) {
+        self.patterns.extend(other.patterns.iter().cloned());
+    }
+}
+
+/// Inline patterns plus an optional file that is reloaded when it changes.
+#[derive(Debug)]
+pub struct ReservedAgentNameCache {
+    inline: Vec<String>,
+    path: Option<PathBuf>,
+    state: Mutex<(Option<SystemTime>, Arc<ReservedAgentNames>)>,
+}
+
+impl ReservedAgentNameCache {
+    #[must_use]
+    pub fn new(inline: Vec<String>, path: Option<PathBuf>) -> Self {
+        let (names, rejected) = ReservedAgentNames::compile(inline.iter().map(String::as_str));
+        for pattern in rejected {
+            tracing::warn!(
+                pattern = %pattern,
+                "AGENT_MAIL_RESERVED_AGENT_NAMES: ignoring invalid pattern"
+            );
+        }
+        Self {
+            inline,
+            path,
+            state: Mutex::new((None, Arc::new(names))),
+        }
+    }
+
+    /// Current deny list, re-reading the file if its mtime moved.
+    #[must_use]
+    pub fn names(&self) -> Arc<ReservedAgentNames> {
+        let Some(path) = self.path.as_deref() else {
+            return Arc::clone(&self.lock().1);
+        };
+        let modified = std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        let mut state = self.lock();
+        if state.0 != modified {
+            let (mut names, _) =
+                ReservedAgentNames::compile(self.inline.iter().map(String::as_str));
+            if modified.is_some() {
+                names.extend(&load_file(path));
+            }
+            *state = (modified, Arc::new(names));
+        }
+        Arc::clone(&state.1)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, (Option<SystemTime>, Arc<ReservedAgentNames>)> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn matches_config(&self, config: &Config) -> bool {
+        self.inline == config.reserved_agent_names && self.path == config.reserved_agent_names_file
+    }
+}
+
+fn load_file(path: &Path) -> ReservedAgentNames {
+    match std::fs::read_to_string(path) {
+        Ok(text) => {
+            let (names, rejected) = ReservedAgentNames::parse_file(&text);
+            for pattern in rejected {
+                tracing::warn!(
+                    path = %path.display(),
+                    pattern = %pattern,
+                    "ignoring invalid reserved agent name pattern"
+                );
+            }
+            names
+        }
+        Err(error) => {
+            tracing::warn!(
+                path = %path.display(),
+                %error,
+                "ignoring unreadable reserved agent names file"
+            );
+            ReservedAgentNames::default()
+        }
+    }
+}
+
+static GLOBAL_CACHE: Mutex<Option<Arc<ReservedAgentNameCache>>> = Mutex::new(None);
+
+/// Deny list for the current [`Config`]. The cache is rebuilt if the
+/// configured patterns or file path change (e.g. after [`Config::reset_cached`]).
+#[must_use]
+pub fn reserved_agent_names() -> Arc<ReservedAgentNames> {
+    let config = Config::get();
+    let cache = {
+        let mut global = GLOBAL_CACHE
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        match global.as_ref() {
+            Some(cache) if cache.matches_config(&config) => Arc::clone(cache),
+            _ => {
+                let cache = Arc::new(ReservedAgentNameCache::new(
+                    config.reserved_agent_names.clone(),
+                    config.reserved_agent_names_file.clone(),
+                ));
+                *global = Some(Arc::clone(&cache));
+                cache
+            }
+        }
+    };
+    cache.names()
+}
+
+/// The configured pattern that reserves `name`, if any.
+#[must_use]
+pub fn reserved_agent_name_pattern(name: &str) -> Option<String> {
+    reserved_agent_names()
+        .matching_pattern(name)
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_and_glob_patterns_match_case_insensitively() {
+        let (names, rejected) = ReservedAgentNames::compile(["RedFox", "Gold*", "?lueLake"]);
+        assert!(rejected.is_empty());
+        assert_eq!(names.matching_pattern("redfox"), Some("RedFox"));
+        assert_eq!(names.matching_pattern("GOLDHAWK"), Some("Gold*"));
+        assert_eq!(names.matching_pattern("BlueLake"), Some("?lueLake"));
+        assert_eq!(names.matching_pattern("GreenLake"), None);
+    }
+
+    #[test]
+    fn file_parsing_skips_comments_and_reports_invalid_patterns() {
+        let (names, rejected) =
+            ReservedAgentNames::parse_file("# staff names\nRedFox  # lead\n\n[Gold\n");
+        assert_eq!(names.matching_pattern("RedFox"), Some("RedFox"));
+        assert_eq!(rejected, vec!["[Gold".to_string()]);
+    }
+
+    #[test]
+    fn cache_reloads_file_when_it_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("reserved.txt");
+        let cache = ReservedAgentNameCache::new(vec!["RedFox".to_string()], Some(path.clone()));
+        assert!(cache.names().matching_pattern("BlueLake").is_none());
+
+        std::fs::write(&path, "Blue*\n").expect("write deny list");
+        let names = cache.names();
+        assert_eq!(names.matching_pattern("BlueLake"), Some("Blue*"));
+        assert_eq!(names.matching_pattern("RedFox"), Some("RedFox"));
+
+        std::fs::remove_file(&path).expect("remove deny list");
+        assert!(cache.names().matching_pattern("BlueLake").is_none());
+    }
+}
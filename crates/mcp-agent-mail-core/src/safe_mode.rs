@@ -0,0 +1,122 @@
+//! Safe mode: an emergency, read-only server for getting mail out of a huge
+//! or partially damaged mailbox.
+//!
+//! `am serve-http --safe-mode` (or `AM_SAFE_MODE=1`) skips setup self-heal,
+//! port auto-clear, startup probes, migrations, and every background worker,
+//! opens the database query-only, and rejects every tool that could write with
+//! a `SAFE_MODE` error. The process-wide flag below is what tool dispatch and
+//! the DB pool helpers consult; it is never cleared, so leaving safe mode
+//! takes a normal restart.
+//!
+//! While running, the server drops a `safe_mode.json` marker in the storage
+//! root so `am robot status` in another process can report the mode.
+
+#![forbid(unsafe_code)]
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable that enables safe mode, equivalent to `--safe-mode`.
+pub const ENV_VAR: &str = "AM_SAFE_MODE";
+
+/// `error_type` carried by tool errors rejected in safe mode.
+pub const ERROR_TYPE: &str = "SAFE_MODE";
+
+/// Marker file written to the storage root while a safe-mode server runs.
+pub const MARKER_FILE: &str = "safe_mode.json";
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Switch this process into safe mode. There is no way back.
+pub fn activate() {
+    ACTIVE.store(true, Ordering::Release);
+}
+
+/// Whether this process is serving in safe mode.
+#[must_use]
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Acquire)
+}
+
+/// Contents of the storage-root marker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SafeModeMarker {
+    pub pid: u32,
+    pub started_at: String,
+    pub http_host: String,
+    pub http_port: u16,
+}
+
+#[must_use]
+pub fn marker_path(storage_root: &Path) -> PathBuf {
+    storage_root.join(MARKER_FILE)
+}
+
+/// Read the marker left by a safe-mode server, if any. The caller decides
+/// whether the recorded process is still alive.
+#[must_use]
+pub fn read_marker(storage_root: &Path) -> Option<SafeModeMarker> {
+    let raw = std::fs::read_to_string(marker_path(storage_root)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Removes the marker when the safe-mode server exits.
+#[derive(Debug)]
+pub struct MarkerGuard {
+    path: PathBuf,
+}
+
+impl MarkerGuard {
+    /// Write the marker for this process. Failure is non-fatal: a read-only
+    /// storage root must not stop the emergency server from starting.
+    pub fn write(storage_root: &Path, http_host: &str, http_port: u16) -> Option<Self> {
+        let marker = SafeModeMarker {
+            pid: std::process::id(),
+            started_at: crate::timestamps::micros_to_iso(crate::timestamps::now_micros()),
+            http_host: http_host.to_string(),
+            http_port,
+        };
+        let path = marker_path(storage_root);
+        let body = serde_json::to_string_pretty(&marker).ok()?;
+        match std::fs::write(&path, body) {
+            Ok(()) => Some(Self { path }),
+            Err(error) => {
+                tracing::warn!(path = %path.display(), %error, "failed to write safe-mode marker");
+                None
+            }
+        }
+    }
+}
+
+impl Drop for MarkerGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_round_trips_and_is_removed_on_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_marker(dir.path()), None);
+
+        let guard = MarkerGuard::write(dir.path(), "127.0.0.1", 8765).expect("write marker");
+        let marker = read_marker(dir.path()).expect("marker present");
+        assert_eq!(marker.pid, std::process::id());
+        assert_eq!(marker.http_port, 8765);
+
+        drop(guard);
+        assert!(!marker_path(dir.path()).exists());
+    }
+
+    #[test]
+    fn unreadable_marker_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(marker_path(dir.path()), "not json").unwrap();
+        assert_eq!(read_marker(dir.path()), None);
+    }
+}
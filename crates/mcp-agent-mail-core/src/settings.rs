@@ -0,0 +1,645 @@
+//! Registry of configuration keys that `am config get|set|list` understands.
+//!
+//! Each entry names the env key, its value type, and how to read the effective
+//! value back out of [`Config`]. Values are validated and normalized against
+//! the type before they are written, so a typo in `am config set` fails loudly
+//! instead of being silently ignored by `Config::from_env` on the next start.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::Config;
+use crate::config::{ConfigSource, RateLimitBackend, detect_source, mask_secret, redact_db_url};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    Bool,
+    /// TCP port, 1-65535.
+    Port,
+    /// Non-negative integer.
+    Integer,
+    /// Whole seconds; also accepts duration strings such as `90s`, `5m`, `1h30m`.
+    Seconds,
+    Enum(&'static [&'static str]),
+    Path,
+    /// `http://` or `https://` URL.
+    Url,
+    Text,
+}
+
+impl SettingKind {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Port => "port",
+            Self::Integer => "integer",
+            Self::Seconds => "seconds",
+            Self::Enum(_) => "enum",
+            Self::Path => "path",
+            Self::Url => "url",
+            Self::Text => "text",
+        }
+    }
+
+    #[must_use]
+    pub const fn allowed_values(self) -> &'static [&'static str] {
+        match self {
+            Self::Bool => &["true", "false"],
+            Self::Enum(values) => values,
+            _ => &[],
+        }
+    }
+}
+
+pub struct SettingDefinition {
+    pub key: &'static str,
+    pub kind: SettingKind,
+    pub doc: &'static str,
+    /// Shown masked by `am config list|get`.
+    pub secret: bool,
+    /// Read only from the process environment and the user-global env file;
+    /// a project `.env` is ignored for this key.
+    pub infra: bool,
+    /// Older names for the key, removed from the env file on `set`.
+    pub aliases: &'static [&'static str],
+    resolve_value: fn(&Config) -> String,
+}
+
+impl SettingDefinition {
+    /// Effective value under `config`; empty when unset. Secrets are masked.
+    #[must_use]
+    pub fn current_value(&self, config: &Config) -> String {
+        let value = (self.resolve_value)(config);
+        if self.secret && !value.is_empty() {
+            mask_secret(&value)
+        } else {
+            value
+        }
+    }
+
+    #[must_use]
+    pub fn default_value(&self) -> String {
+        self.current_value(&Config::default())
+    }
+
+    #[must_use]
+    pub fn current_source(&self) -> ConfigSource {
+        match detect_source(self.key) {
+            ConfigSource::ProjectDotenv if self.infra => ConfigSource::Default,
+            source => source,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SettingSnapshot {
+    pub key: String,
+    pub kind: String,
+    pub allowed_values: Vec<String>,
+    pub value: String,
+    pub default_value: String,
+    pub source: String,
+    pub doc: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SettingError {
+    #[error("unknown setting '{0}'")]
+    UnknownSetting(String),
+    #[error("invalid value '{value}' for {key}: expected {expected}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: String,
+    },
+    #[error("failed to update {path}: {source}")]
+    Persist {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+const APP_ENVIRONMENT_VALUES: &[&str] = &["development", "production"];
+const RATE_LIMIT_BACKEND_VALUES: &[&str] = &["memory", "redis"];
+const TUI_THEME_VALUES: &[&str] = &[
+    "default",
+    "solarized",
+    "dracula",
+    "nord",
+    "gruvbox",
+    "frankenstein",
+];
+
+fn bool_string(value: bool) -> String {
+    if value {
+        "true".to_string()
+    } else {
+        "false".to_string()
+    }
+}
+
+pub const SETTING_REGISTRY: &[SettingDefinition] = &[
+    SettingDefinition {
+        key: "ACK_TTL_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Warn about acknowledgements that stay pending past ACK_TTL_SECONDS.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.ack_ttl_enabled),
+    },
+    SettingDefinition {
+        key: "ACK_TTL_SECONDS",
+        kind: SettingKind::Seconds,
+        doc: "How long an ack may stay pending before it counts as overdue.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.ack_ttl_seconds.to_string(),
+    },
+    SettingDefinition {
+        key: "AGENT_MAIL_UDS_ALLOW_UNAUTHENTICATED",
+        kind: SettingKind::Bool,
+        doc: "Serve requests on the unix socket without a bearer token.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.http_uds_allow_unauthenticated),
+    },
+    SettingDefinition {
+        key: "AGENT_MAIL_UDS_PATH",
+        kind: SettingKind::Path,
+        doc: "Unix socket serve-http listens on alongside TCP.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| {
+            config
+                .http_uds_path
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default()
+        },
+    },
+    SettingDefinition {
+        key: "AGENT_MAIL_WEBHOOK_INCLUDE_BODY",
+        kind: SettingKind::Bool,
+        doc: "Include message bodies in message.sent webhook events.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.webhook_include_body),
+    },
+    SettingDefinition {
+        key: "AGENT_MAIL_WEBHOOK_URL",
+        kind: SettingKind::Url,
+        doc: "URL that receives mail event webhooks.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.webhook_url.clone().unwrap_or_default(),
+    },
+    SettingDefinition {
+        key: "APP_ENVIRONMENT",
+        kind: SettingKind::Enum(APP_ENVIRONMENT_VALUES),
+        doc: "Deployment environment; production tightens CORS defaults.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.app_environment.to_string(),
+    },
+    SettingDefinition {
+        key: "CONTACT_ENFORCEMENT_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Require an approved contact before agents can message each other.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.contact_enforcement_enabled),
+    },
+    SettingDefinition {
+        key: "DATABASE_URL",
+        kind: SettingKind::Text,
+        doc: "Mailbox database URL.",
+        secret: false,
+        infra: true,
+        aliases: &[],
+        resolve_value: |config| redact_db_url(&config.database_url),
+    },
+    SettingDefinition {
+        key: "FILE_RESERVATIONS_CLEANUP_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Periodically release expired and abandoned file reservations.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.file_reservations_cleanup_enabled),
+    },
+    SettingDefinition {
+        key: "FILE_RESERVATIONS_CLEANUP_INTERVAL_SECONDS",
+        kind: SettingKind::Seconds,
+        doc: "Interval between reservation cleanup sweeps.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| {
+            config
+                .file_reservations_cleanup_interval_seconds
+                .to_string()
+        },
+    },
+    SettingDefinition {
+        key: "FILE_RESERVATION_ACTIVITY_GRACE_SECONDS",
+        kind: SettingKind::Seconds,
+        doc: "Recent holder activity that keeps an inactive reservation alive.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.file_reservation_activity_grace_seconds.to_string(),
+    },
+    SettingDefinition {
+        key: "FILE_RESERVATION_INACTIVITY_SECONDS",
+        kind: SettingKind::Seconds,
+        doc: "Holder inactivity after which the reaper may release a reservation.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.file_reservation_inactivity_seconds.to_string(),
+    },
+    SettingDefinition {
+        key: "HTTP_ALLOW_LOCALHOST_UNAUTHENTICATED",
+        kind: SettingKind::Bool,
+        doc: "Accept unauthenticated requests from localhost.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.http_allow_localhost_unauthenticated),
+    },
+    SettingDefinition {
+        key: "HTTP_BEARER_TOKEN",
+        kind: SettingKind::Text,
+        doc: "Bearer token required by the HTTP server.",
+        secret: true,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.http_bearer_token.clone().unwrap_or_default(),
+    },
+    SettingDefinition {
+        key: "HTTP_CORS_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Send CORS headers from the HTTP server.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.http_cors_enabled),
+    },
+    SettingDefinition {
+        key: "HTTP_HOST",
+        kind: SettingKind::Text,
+        doc: "Address the HTTP server binds to.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.http_host.clone(),
+    },
+    SettingDefinition {
+        key: "HTTP_PATH",
+        kind: SettingKind::Text,
+        doc: "Mount path of the MCP HTTP endpoint.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.http_path.clone(),
+    },
+    SettingDefinition {
+        key: "HTTP_PORT",
+        kind: SettingKind::Port,
+        doc: "Port the HTTP server listens on.",
+        secret: false,
+        infra: false,
+        aliases: &["AGENT_MAIL_HTTP_PORT", "AM_HTTP_PORT"],
+        resolve_value: |config| config.http_port.to_string(),
+    },
+    SettingDefinition {
+        key: "HTTP_RATE_LIMIT_BACKEND",
+        kind: SettingKind::Enum(RATE_LIMIT_BACKEND_VALUES),
+        doc: "Where rate-limit counters live.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| match config.http_rate_limit_backend {
+            RateLimitBackend::Memory => "memory".to_string(),
+            RateLimitBackend::Redis => "redis".to_string(),
+        },
+    },
+    SettingDefinition {
+        key: "HTTP_RATE_LIMIT_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Rate-limit HTTP requests per client.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.http_rate_limit_enabled),
+    },
+    SettingDefinition {
+        key: "HTTP_RATE_LIMIT_PER_MINUTE",
+        kind: SettingKind::Integer,
+        doc: "Requests per minute allowed per client.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.http_rate_limit_per_minute.to_string(),
+    },
+    SettingDefinition {
+        key: "LLM_DEFAULT_MODEL",
+        kind: SettingKind::Text,
+        doc: "Model used for thread summaries and other LLM features.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.llm_default_model.clone(),
+    },
+    SettingDefinition {
+        key: "LLM_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Enable LLM-backed features.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.llm_enabled),
+    },
+    SettingDefinition {
+        key: "LOG_LEVEL",
+        kind: SettingKind::Text,
+        doc: "Server log level.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.log_level.clone(),
+    },
+    SettingDefinition {
+        key: "MAX_MESSAGE_BODY_BYTES",
+        kind: SettingKind::Integer,
+        doc: "Largest message body accepted by send and reply.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.max_message_body_bytes.to_string(),
+    },
+    SettingDefinition {
+        key: "NOTIFICATIONS_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Write signal files when agents receive mail.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.notifications_enabled),
+    },
+    SettingDefinition {
+        key: "STORAGE_ROOT",
+        kind: SettingKind::Path,
+        doc: "Root directory of the mail archive.",
+        secret: false,
+        infra: true,
+        aliases: &[],
+        resolve_value: |config| config.storage_root.display().to_string(),
+    },
+    SettingDefinition {
+        key: "TUI_ENABLED",
+        kind: SettingKind::Bool,
+        doc: "Show the operator TUI when the server runs in a terminal.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| bool_string(config.tui_enabled),
+    },
+    SettingDefinition {
+        key: "TUI_THEME",
+        kind: SettingKind::Enum(TUI_THEME_VALUES),
+        doc: "Color theme of the operator TUI.",
+        secret: false,
+        infra: false,
+        aliases: &[],
+        resolve_value: |config| config.tui_theme.clone(),
+    },
+];
+
+#[must_use]
+pub const fn setting_registry() -> &'static [SettingDefinition] {
+    SETTING_REGISTRY
+}
+
+#[must_use]
+pub fn find_setting(key: &str) -> Option<&'static SettingDefinition> {
+    let key = key.trim();
+    SETTING_REGISTRY
+        .iter()
+        .find(|setting| setting.key.eq_ignore_ascii_case(key))
+}
+
+#[must_use]
+pub fn setting_snapshots(config: &Config) -> Vec<SettingSnapshot> {
+    SETTING_REGISTRY
+        .iter()
+        .map(|setting| setting_snapshot(config, setting))
+        .collect()
+}
+
+#[must_use]
+pub fn setting_snapshot(config: &Config, setting: &SettingDefinition) -> SettingSnapshot {
+    SettingSnapshot {
+        key: setting.key.to_string(),
+        kind: setting.kind.label().to_string(),
+        allowed_values: setting
+            .kind
+            .allowed_values()
+            .iter()
+            .map(|value| (*value).to_string())
+            .collect(),
+        value: setting.current_value(config),
+        default_value: setting.default_value(),
+        source: setting.current_source().to_string(),
+        doc: setting.doc.to_string(),
+    }
+}
+
+/// Parse `90`, `90s`, `5m`, `1h30m`, or `2d` into whole seconds.
+fn parse_seconds(raw: &str) -> Option<u64> {
+    if let Ok(seconds) = raw.parse::<u64>() {
+        return Some(seconds);
+    }
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for ch in raw.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let scale = match ch.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3_600,
+            'd' => 86_400,
+            _ => return None,
+        };
+        let amount: u64 = digits.parse().ok()?;
+        total = total.checked_add(amount.checked_mul(scale)?)?;
+        digits.clear();
+    }
+    digits.is_empty().then_some(total)
+}
+
+/// Check `raw` against the setting's type and return the form to store
+/// (booleans as `true`/`false`, durations as whole seconds, enum values
+/// lowercased).
+pub fn validate_setting_value(
+    setting: &SettingDefinition,
+    raw: &str,
+) -> Result<String, SettingError> {
+    let value = raw.trim();
+    let invalid = |expected: String| SettingError::InvalidValue {
+        key: setting.key.to_string(),
+        value: raw.to_string(),
+        expected,
+    };
+    if value.contains(['\n', '\r']) {
+        return Err(invalid("a single line".to_string()));
+    }
+    match setting.kind {
+        SettingKind::Bool => match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "t" | "yes" | "y" | "on" => Ok("true".to_string()),
+            "0" | "false" | "f" | "no" | "n" | "off" => Ok("false".to_string()),
+            _ => Err(invalid("true or false".to_string())),
+        },
+        SettingKind::Port => match value.parse::<u16>() {
+            Ok(port) if port > 0 => Ok(port.to_string()),
+            _ => Err(invalid("a port between 1 and 65535".to_string())),
+        },
+        SettingKind::Integer => value
+            .parse::<u64>()
+            .map(|number| number.to_string())
+            .map_err(|_| invalid("a non-negative integer".to_string())),
+        SettingKind::Seconds => parse_seconds(value)
+            .map(|seconds| seconds.to_string())
+            .ok_or_else(|| invalid("seconds or a duration such as 90s, 5m, 1h30m".to_string())),
+        SettingKind::Enum(values) => {
+            let lowered = value.to_ascii_lowercase();
+            if values.contains(&lowered.as_str()) {
+                Ok(lowered)
+            } else {
+                Err(invalid(format!("one of {}", values.join(", "))))
+            }
+        }
+        SettingKind::Url => {
+            if (value.starts_with("http://") || value.starts_with("https://"))
+                && !value.contains(char::is_whitespace)
+            {
+                Ok(value.to_string())
+            } else {
+                Err(invalid("an http:// or https:// URL".to_string()))
+            }
+        }
+        SettingKind::Path | SettingKind::Text => {
+            if value.is_empty() {
+                Err(invalid("a non-empty value".to_string()))
+            } else {
+                Ok(value.to_string())
+            }
+        }
+    }
+}
+
+/// Validate `raw` and write it to the env file at `path`, replacing the key
+/// (and any legacy alias) in place and preserving every other line. Returns
+/// the stored value.
+pub fn persist_setting(
+    path: &Path,
+    setting: &SettingDefinition,
+    raw: &str,
+) -> Result<String, SettingError> {
+    let value = validate_setting_value(setting, raw)?;
+    let updates = HashMap::from([(setting.key, value.clone())]);
+    crate::config::update_envfile_removing(path, &updates, setting.aliases).map_err(|source| {
+        SettingError::Persist {
+            path: path.display().to_string(),
+            source,
+        }
+    })?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_keys_are_unique_and_sorted() {
+        let keys: Vec<&str> = setting_registry().iter().map(|s| s.key).collect();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn validation_normalizes_by_kind() {
+        let port = find_setting("http_port").expect("HTTP_PORT");
+        assert_eq!(validate_setting_value(port, " 9000 ").unwrap(), "9000");
+        assert!(validate_setting_value(port, "70000").is_err());
+        assert!(validate_setting_value(port, "0").is_err());
+
+        let seconds = find_setting("FILE_RESERVATION_INACTIVITY_SECONDS").unwrap();
+        assert_eq!(validate_setting_value(seconds, "1h30m").unwrap(), "5400");
+        assert_eq!(validate_setting_value(seconds, "120").unwrap(), "120");
+        assert!(validate_setting_value(seconds, "soon").is_err());
+        assert!(validate_setting_value(seconds, "5m3").is_err());
+
+        let theme = find_setting("TUI_THEME").unwrap();
+        assert_eq!(validate_setting_value(theme, "Nord").unwrap(), "nord");
+        let err = validate_setting_value(theme, "neon").unwrap_err();
+        assert!(err.to_string().contains("one of default"), "{err}");
+
+        let flag = find_setting("TUI_ENABLED").unwrap();
+        assert_eq!(validate_setting_value(flag, "yes").unwrap(), "true");
+        assert!(validate_setting_value(flag, "maybe").is_err());
+
+        let url = find_setting("AGENT_MAIL_WEBHOOK_URL").unwrap();
+        assert!(validate_setting_value(url, "ftp://example.com").is_err());
+    }
+
+    #[test]
+    fn secrets_are_masked() {
+        let token = find_setting("HTTP_BEARER_TOKEN").unwrap();
+        let config = Config {
+            http_bearer_token: Some("supersecrettoken".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(token.current_value(&config), "****oken");
+    }
+
+    #[test]
+    fn persist_setting_preserves_comments_and_drops_aliases() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join(".env");
+        std::fs::write(
+            &path,
+            "# local overrides\nAM_HTTP_PORT=7000\nHTTP_PORT=8000 # old\nOTHER=1\n",
+        )
+        .expect("seed env");
+
+        let port = find_setting("HTTP_PORT").unwrap();
+        assert_eq!(persist_setting(&path, port, "9000").unwrap(), "9000");
+        let written = std::fs::read_to_string(&path).expect("read env");
+        assert_eq!(
+            written,
+            "# local overrides\nHTTP_PORT=9000 # old\nOTHER=1\n"
+        );
+
+        assert!(matches!(
+            persist_setting(&path, port, "nope"),
+            Err(SettingError::InvalidValue { .. })
+        ));
+    }
+}
@@ -4,6 +4,7 @@
 //! token management, JSON merge, atomic file writes. Lives in core (not cli)
 //! so it can be reused by the server or tests.
 
+use crate::agent_connectors::{ConnectorCapabilities, CustomConnector};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value, json};
 use std::fmt;
@@ -125,6 +126,31 @@ impl AgentPlatform {
         }
     }
 
+    /// Server entry kinds `setup` can write for this platform, and whether it
+    /// installs agent hooks for it.
+    #[must_use]
+    pub const fn capabilities(self) -> ConnectorCapabilities {
+        ConnectorCapabilities {
+            mcp_http: true,
+            stdio: matches!(
+                self,
+                Self::Claude | Self::Cursor | Self::Cline | Self::Windsurf | Self::GithubCopilot
+            ),
+            hooks: matches!(self, Self::Claude),
+        }
+    }
+
+    /// Project-relative file this platform reads standing instructions from.
+    /// Platforms without a file of their own read `AGENTS.md`.
+    #[must_use]
+    pub const fn instructions_file(self) -> &'static str {
+        match self {
+            Self::Claude => "CLAUDE.md",
+            Self::Cursor => ".cursorrules",
+            _ => "AGENTS.md",
+        }
+    }
+
     /// Project-relative config files this platform writes into `project_dir`
     /// that may embed the bearer token (security issue #148: these MUST be
     /// covered by the auto-generated `.gitignore` so `git add -A` never commits
@@ -180,7 +206,8 @@ pub fn parse_agent_list(input: &str) -> Result<Vec<AgentPlatform>, SetupError> {
 
 /// A single config file write operation (the unit of work).
 pub struct ConfigAction {
-    pub platform: AgentPlatform,
+    /// `None` for user-defined connectors from `connectors.toml`.
+    pub platform: Option<AgentPlatform>,
     pub file_path: PathBuf,
     pub description: String,
     pub content: ConfigContent,
@@ -188,6 +215,16 @@ pub struct ConfigAction {
     pub backup: bool,
 }
 
+impl ConfigAction {
+    /// Whether this action only configures `project_dir`: a file inside it, or
+    /// Claude's per-project scope in `~/.claude.json`.
+    #[must_use]
+    pub fn is_project_scoped(&self, project_dir: &Path) -> bool {
+        matches!(self.content, ConfigContent::ClaudeLocalScopeMcp { .. })
+            || self.file_path.starts_with(project_dir)
+    }
+}
+
 /// How to produce the final file content.
 pub enum ConfigContent {
     /// Merge an MCP server entry into existing JSON (or create fresh).
@@ -218,6 +255,10 @@ pub enum ConfigContent {
         section_header: String,
         key_values: Vec<(String, String)>,
     },
+    /// Insert or replace the fenced agent-instructions block in a Markdown or
+    /// rules file, or strip it when `block` is `None`. Text outside the fence
+    /// is left untouched.
+    InstructionsBlock { block: Option<String> },
 }
 
 /// Parameters driving the setup.
@@ -234,6 +275,30 @@ pub struct SetupParams {
     pub skip_hooks: bool,
     pub project_slug: String,
     pub agent_name: String,
+    /// Also remove `mcp-agent-mail` server entries whose URL no longer matches
+    /// [`SetupParams::server_url`]. Unrelated servers are never touched.
+    pub prune: bool,
+    /// `token` is scoped to this project: only write configs that belong to
+    /// `project_dir`, so the token never lands in a user-level config.
+    pub project_token: bool,
+    /// `serve-http` unix socket. When set, platforms that speak stdio MCP get
+    /// an `am mcp-bridge --uds <path>` entry instead of the HTTP URL.
+    pub uds_path: Option<PathBuf>,
+    /// User-defined connectors from `connectors.toml` to configure alongside
+    /// `agents`. Connectors without a `config_path` are skipped.
+    pub custom_connectors: Vec<CustomConnector>,
+    /// Also write or strip the fenced Agent Mail section in each platform's
+    /// instructions file (`AGENTS.md`, `CLAUDE.md`, `.cursorrules`).
+    pub instructions: Option<InstructionsEdit>,
+}
+
+/// What `setup run` does with agent instruction files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstructionsEdit {
+    /// Insert or refresh the fenced section.
+    Write,
+    /// Remove the fenced section, leaving the rest of the file as is.
+    Remove,
 }
 
 impl Default for SetupParams {
@@ -251,6 +316,11 @@ impl Default for SetupParams {
             skip_hooks: false,
             project_slug: String::new(),
             agent_name: String::new(),
+            prune: false,
+            project_token: false,
+            uds_path: None,
+            custom_connectors: Vec::new(),
+            instructions: None,
         }
     }
 }
@@ -266,6 +336,15 @@ impl SetupParams {
             self.path
         )
     }
+
+    /// Server entry for platforms that accept either HTTP or stdio servers:
+    /// the socket bridge when [`SetupParams::uds_path`] is set, else HTTP.
+    fn standard_server_value(&self, url: &str, token: &str) -> Value {
+        self.uds_path.as_deref().map_or_else(
+            || standard_http_server_value(url, token),
+            uds_bridge_server_value,
+        )
+    }
 }
 
 #[must_use]
@@ -304,6 +383,14 @@ pub struct ActionResult {
     pub file_path: String,
     pub description: String,
     pub outcome: ActionOutcome,
+    /// Dry-run only: what a real run would do (`created`, `updated`, or
+    /// `unchanged`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned: Option<ActionOutcome>,
+    /// Dry-run only: unified diff of the planned change with bearer tokens
+    /// masked. Empty when the file would be left unchanged.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
 }
 
 /// Outcome of a config write.
@@ -315,6 +402,11 @@ pub enum ActionOutcome {
     Unchanged,
     Skipped,
     BackedUp(String),
+    /// A stale `mcp-agent-mail` entry was pruned (or would be, under dry-run).
+    Removed {
+        entry: String,
+        dry_run: bool,
+    },
     Failed(String),
 }
 
@@ -326,6 +418,14 @@ impl fmt::Display for ActionOutcome {
             Self::Unchanged => write!(f, "unchanged"),
             Self::Skipped => write!(f, "skipped (dry-run)"),
             Self::BackedUp(p) => write!(f, "backed up to {p}"),
+            Self::Removed {
+                entry,
+                dry_run: false,
+            } => write!(f, "removed {entry}"),
+            Self::Removed {
+                entry,
+                dry_run: true,
+            } => write!(f, "would remove {entry}"),
             Self::Failed(e) => write!(f, "FAILED: {e}"),
         }
     }
@@ -486,8 +586,21 @@ pub fn save_token_to_env_file(env_path: &Path, token: &str) -> Result<(), SetupE
     if token.contains('\n') || token.contains('\r') {
         return Err(SetupError::Other("Token must not contain newlines".into()));
     }
-    ensure_setup_parent_dir(env_path, "token env file")?;
-    validate_setup_file_target(env_path, "token env file")?;
+    save_value_to_env_file(env_path, "HTTP_BEARER_TOKEN", token)?;
+    Ok(())
+}
+
+/// Set `key` in a .env file (create or update), keeping it owner-only.
+///
+/// Returns `false` when the file already held that value and was left alone.
+pub fn save_value_to_env_file(env_path: &Path, key: &str, value: &str) -> Result<bool, SetupError> {
+    if value.contains('\n') || value.contains('\r') {
+        return Err(SetupError::Other(format!(
+            "{key} must not contain newlines"
+        )));
+    }
+    ensure_setup_parent_dir(env_path, "env file")?;
+    validate_setup_file_target(env_path, "env file")?;
 
     let existing_content = if env_path.exists() {
         Some(std::fs::read_to_string(env_path)?)
@@ -495,39 +608,131 @@ pub fn save_token_to_env_file(env_path: &Path, token: &str) -> Result<(), SetupE
         None
     };
 
-    let content = existing_content.as_deref().map_or_else(
-        || format!("HTTP_BEARER_TOKEN={token}\n"),
-        |existing| {
-            let mut found = false;
-            let updated: Vec<String> = existing
-                .lines()
-                .map(|line| {
-                    if line.trim_start().starts_with("HTTP_BEARER_TOKEN=") {
-                        found = true;
-                        format!("HTTP_BEARER_TOKEN={token}")
-                    } else {
-                        line.to_string()
-                    }
-                })
-                .collect();
-            if found {
-                updated.join("\n") + "\n"
-            } else {
-                let sep = if existing.ends_with('\n') { "" } else { "\n" };
-                format!("{existing}{sep}HTTP_BEARER_TOKEN={token}\n")
-            }
-        },
+    let updates = std::collections::HashMap::from([(key, value.to_string())]);
+    let content = crate::config::render_envfile_update(
+        existing_content.as_deref().unwrap_or_default(),
+        &updates,
+        &[],
     );
 
     if existing_content
         .as_deref()
         .is_some_and(|existing| existing == content)
     {
-        return Ok(());
+        return Ok(false);
     }
 
-    write_setup_file_atomic(env_path, content.as_bytes(), 0o600, "token env file")?;
-    Ok(())
+    write_setup_file_atomic(env_path, content.as_bytes(), 0o600, "env file")?;
+    Ok(true)
+}
+
+// ---------------------------------------------------------------------------
+// Project-scoped tokens
+// ---------------------------------------------------------------------------
+
+/// File name of the project token registry, kept next to `config.env`.
+pub const PROJECT_TOKENS_FILE: &str = "project_tokens.json";
+
+/// One project-scoped bearer token. Only the SHA-256 digest is recorded here;
+/// the token itself lives in the project's `.env` and project-local MCP configs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectTokenEntry {
+    pub slug: String,
+    pub human_key: String,
+    pub sha256: String,
+}
+
+/// Registry the HTTP server consults to map a presented bearer token to the
+/// single project it may act on. The global `HTTP_BEARER_TOKEN` is not listed
+/// and keeps full access.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectTokenRegistry {
+    #[serde(default)]
+    pub projects: Vec<ProjectTokenEntry>,
+}
+
+/// Lowercase hex SHA-256 of a bearer token.
+#[must_use]
+pub fn project_token_digest(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+impl ProjectTokenRegistry {
+    /// Load the registry; a missing file is an empty registry.
+    pub fn load(path: &Path) -> Result<Self, SetupError> {
+        match std::fs::read_to_string(path) {
+            Ok(text) if text.trim().is_empty() => Ok(Self::default()),
+            Ok(text) => Ok(serde_json::from_str(&text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), SetupError> {
+        ensure_setup_parent_dir(path, "project token registry")?;
+        validate_setup_file_target(path, "project token registry")?;
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        write_setup_file_atomic(path, content.as_bytes(), 0o600, "project token registry")
+    }
+
+    /// Record `token` for a project, replacing any earlier token registered
+    /// under the same slug or project directory.
+    pub fn upsert(&mut self, slug: &str, human_key: &str, token: &str) {
+        self.projects
+            .retain(|entry| entry.slug != slug && entry.human_key != human_key);
+        self.projects.push(ProjectTokenEntry {
+            slug: slug.to_string(),
+            human_key: human_key.to_string(),
+            sha256: project_token_digest(token),
+        });
+        self.projects.sort_by(|a, b| a.slug.cmp(&b.slug));
+    }
+
+    /// The project `token` is scoped to, if any. Every entry is compared so
+    /// the lookup time does not depend on which entry matches.
+    #[must_use]
+    pub fn find_token(&self, token: &str) -> Option<&ProjectTokenEntry> {
+        let digest = project_token_digest(token);
+        let mut found = None;
+        for entry in &self.projects {
+            let diff = entry
+                .sha256
+                .bytes()
+                .zip(digest.bytes())
+                .fold(entry.sha256.len() ^ digest.len(), |acc, (a, b)| {
+                    acc | usize::from(a ^ b)
+                });
+            if diff == 0 && found.is_none() {
+                found = Some(entry);
+            }
+        }
+        found
+    }
+}
+
+/// Resolve the token for `setup run --project-token`: reuse the project's
+/// `.env` token when the registry already scopes it to this project, otherwise
+/// generate a fresh one. The global token is never reused.
+pub fn resolve_project_token(
+    project_env_file: &Path,
+    registry: &ProjectTokenRegistry,
+    slug: &str,
+    global_token: &str,
+) -> Result<String, SetupError> {
+    if let Some(existing) = read_env_file_token(project_env_file)
+        && existing != global_token
+        && registry
+            .find_token(&existing)
+            .is_some_and(|entry| entry.slug == slug)
+    {
+        return Ok(existing);
+    }
+    generate_token()
 }
 
 // ---------------------------------------------------------------------------
@@ -890,6 +1095,160 @@ fn parse_toml_section_header(line: &str) -> Option<&str> {
     line.strip_prefix('[')?.strip_suffix(']')
 }
 
+// ---------------------------------------------------------------------------
+// Stale entry pruning
+// ---------------------------------------------------------------------------
+
+/// Whether a server name belongs to Agent Mail: either canonical alias, or an
+/// alias-prefixed variant such as `mcp-agent-mail-old` left by earlier setups.
+fn is_agent_mail_server_name(name: &str) -> bool {
+    ["mcp-agent-mail", "mcp_agent_mail"].iter().any(|alias| {
+        name.strip_prefix(alias)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['-', '_']))
+    })
+}
+
+/// Remove `mcp-agent-mail` entries whose URL does not match `expected_url`
+/// from already-merged config text.
+///
+/// Only the containers the action writes into are scanned. Returns the new
+/// content (the input unchanged when nothing was pruned) and the location of
+/// each removed entry.
+fn prune_stale_server_entries(
+    content: &ConfigContent,
+    text: &str,
+    expected_url: &str,
+) -> Result<(String, Vec<String>), SetupError> {
+    match content {
+        ConfigContent::JsonMerge { .. } => prune_json_server_entries(text, &[], expected_url),
+        ConfigContent::ClaudeLocalScopeMcp { project_path, .. } => {
+            prune_json_server_entries(text, &["projects", project_path.as_str()], expected_url)
+        }
+        ConfigContent::TomlSection { section_header, .. }
+            if section_header.starts_with("[mcp_servers.") =>
+        {
+            Ok(prune_toml_server_sections(text, expected_url))
+        }
+        _ => Ok((text.to_string(), Vec::new())),
+    }
+}
+
+fn prune_json_server_entries(
+    text: &str,
+    scope: &[&str],
+    expected_url: &str,
+) -> Result<(String, Vec<String>), SetupError> {
+    let mut doc: Value = serde_json::from_str(text)?;
+    let mut removed = Vec::new();
+
+    let mut target = Some(&mut doc);
+    for key in scope {
+        target = target.and_then(|value| value.get_mut(*key));
+    }
+    let prefix: String = scope.iter().map(|key| format!("{key}.")).collect();
+    if let Some(obj) = target.and_then(Value::as_object_mut) {
+        for container in ["mcpServers", "mcp", "servers", "mcp_servers"] {
+            let Some(servers) = obj.get_mut(container).and_then(Value::as_object_mut) else {
+                continue;
+            };
+            let stale: Vec<String> = servers
+                .iter()
+                .filter(|(name, entry)| {
+                    is_agent_mail_server_name(name)
+                        && !json_entry_url(entry)
+                            .is_some_and(|url| urls_match_for_status(url, expected_url))
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
+            for name in stale {
+                servers.shift_remove(&name);
+                removed.push(format!("{prefix}{container}.{name}"));
+            }
+        }
+    }
+
+    if removed.is_empty() {
+        return Ok((text.to_string(), removed));
+    }
+    Ok((serde_json::to_string_pretty(&doc)? + "\n", removed))
+}
+
+/// Drop `[mcp_servers.<agent-mail name>]` tables (and their sub-tables) whose
+/// `url` differs from `expected_url`, leaving every other line byte-for-byte.
+fn prune_toml_server_sections(text: &str, expected_url: &str) -> (String, Vec<String>) {
+    // Split into blocks: leading preamble, then one block per table header.
+    let mut blocks: Vec<(Option<String>, Vec<&str>)> = vec![(None, Vec::new())];
+    for line in text.split_inclusive('\n') {
+        if let Some(section) = parse_toml_section_header(line) {
+            blocks.push((Some(section.to_string()), Vec::new()));
+        }
+        if let Some((_, lines)) = blocks.last_mut() {
+            lines.push(line);
+        }
+    }
+
+    let server_of = |section: &str| -> Option<String> {
+        let rest = section.strip_prefix("mcp_servers.")?;
+        let name = match rest.strip_prefix('"') {
+            Some(quoted) => &quoted[..quoted.find('"')?],
+            None => rest.split('.').next().unwrap_or(rest),
+        };
+        is_agent_mail_server_name(name).then(|| name.to_string())
+    };
+    let is_server_table = |section: &str, name: &str| {
+        section == format!("mcp_servers.{name}") || section == format!("mcp_servers.\"{name}\"")
+    };
+
+    let stale: Vec<String> = blocks
+        .iter()
+        .filter_map(|(section, lines)| {
+            let section = section.as_deref()?;
+            let name = server_of(section)?;
+            // Only the server's own table carries its url.
+            if !is_server_table(section, &name) {
+                return None;
+            }
+            let url = lines
+                .iter()
+                .find_map(|line| match parse_toml_key_value(line) {
+                    Some((key, Value::String(url))) if key == "url" || key == "httpUrl" => {
+                        Some(url)
+                    }
+                    _ => None,
+                });
+            (!url.is_some_and(|url| urls_match_for_status(&url, expected_url))).then_some(name)
+        })
+        .collect();
+
+    if stale.is_empty() {
+        return (text.to_string(), Vec::new());
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut removed = Vec::new();
+    for (section, lines) in &blocks {
+        let drop = section.as_deref().and_then(|section| {
+            server_of(section)
+                .filter(|name| stale.contains(name))
+                .map(|_| section)
+        });
+        let Some(section) = drop else {
+            out.extend(lines.iter().copied());
+            continue;
+        };
+        if server_of(section).is_some_and(|name| is_server_table(section, &name)) {
+            removed.push(section.to_string());
+        }
+        // Comments directly above the next header belong to that header.
+        let keep_from = lines
+            .iter()
+            .rposition(|line| !line.trim_start().starts_with('#'))
+            .map_or(0, |idx| idx + 1);
+        out.extend(lines[keep_from..].iter().copied());
+    }
+    (out, removed)
+}
+
 // ---------------------------------------------------------------------------
 // Per-agent config generation
 // ---------------------------------------------------------------------------
@@ -916,6 +1275,64 @@ fn standard_http_server_value(url: &str, token: &str) -> Value {
     }
 }
 
+/// Build a stdio MCP server entry that relays to `serve-http`'s unix socket.
+///
+/// No token is written: `am mcp-bridge` reads it from the local config.
+fn uds_bridge_server_value(socket: &Path) -> Value {
+    json!({
+        "type": "stdio",
+        "command": "am",
+        "args": ["mcp-bridge", "--uds", socket.display().to_string()]
+    })
+}
+
+/// Build the stdio MCP server entry that runs Agent Mail in-process.
+fn serve_stdio_server_value() -> Value {
+    json!({
+        "type": "stdio",
+        "command": "am",
+        "args": ["serve-stdio"]
+    })
+}
+
+/// Build the config action for a user-defined connector, picking the server
+/// entry from its declared capabilities. `Ok(None)` when it has no
+/// `config_path`.
+fn custom_connector_action(
+    connector: &CustomConnector,
+    params: &SetupParams,
+) -> Result<Option<ConfigAction>, SetupError> {
+    let home = params.home_dir_override.clone().or_else(dirs::home_dir);
+    let Some(file_path) = connector.resolved_config_path(home.as_deref(), &params.project_dir)
+    else {
+        return Ok(None);
+    };
+    let caps = connector.capabilities;
+    let server_value = match params.uds_path.as_deref() {
+        Some(socket) if caps.stdio => uds_bridge_server_value(socket),
+        _ if caps.mcp_http => standard_http_server_value(&params.server_url(), &params.token),
+        _ if caps.stdio => serve_stdio_server_value(),
+        _ => {
+            return Err(SetupError::Other(format!(
+                "connector `{}` supports neither MCP HTTP nor stdio",
+                connector.slug
+            )));
+        }
+    };
+    Ok(Some(ConfigAction {
+        platform: None,
+        file_path,
+        description: format!("{} MCP config (connectors.toml)", connector.name),
+        content: ConfigContent::JsonMerge {
+            servers_key: connector.servers_key,
+            server_name: "mcp-agent-mail",
+            server_value,
+        },
+        permissions: 0o600,
+        backup: true,
+    }))
+}
+
 /// Build the `headers` object for an MCP server entry, omitting the
 /// `Authorization` header entirely when `token` is empty (issue #148).
 fn auth_headers_value(token: &str) -> Value {
@@ -938,7 +1355,7 @@ fn project_local_action(
     description: &str,
 ) -> ConfigAction {
     ConfigAction {
-        platform,
+        platform: Some(platform),
         file_path: pdir.join(filename),
         description: description.into(),
         content: ConfigContent::JsonMerge {
@@ -973,7 +1390,7 @@ impl AgentPlatform {
                 pdir,
                 "cline.mcp.json",
                 "mcpServers",
-                standard_http_server_value(&url, token),
+                params.standard_server_value(&url, token),
                 "Cline project-local MCP config",
             )],
             Self::Windsurf => vec![project_local_action(
@@ -981,7 +1398,7 @@ impl AgentPlatform {
                 pdir,
                 "windsurf.mcp.json",
                 "mcpServers",
-                standard_http_server_value(&url, token),
+                params.standard_server_value(&url, token),
                 "Windsurf project-local MCP config",
             )],
             Self::Codex => {
@@ -999,7 +1416,7 @@ impl AgentPlatform {
                     ));
                 }
                 vec![ConfigAction {
-                    platform: self,
+                    platform: Some(self),
                     file_path: home.join(".codex").join("config.toml"),
                     description: "Codex CLI TOML config (~/.codex/config.toml)".into(),
                     content: ConfigContent::TomlSection {
@@ -1027,13 +1444,13 @@ impl AgentPlatform {
             )],
             Self::FactoryDroid => self.factory_actions(params, &url, token, pdir, &home),
             Self::GithubCopilot => vec![ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: pdir.join(".vscode").join("mcp.json"),
                 description: "GitHub Copilot MCP config".into(),
                 content: ConfigContent::JsonMerge {
                     servers_key: "servers",
                     server_name: "mcp-agent-mail",
-                    server_value: standard_http_server_value(&url, token),
+                    server_value: params.standard_server_value(&url, token),
                 },
                 permissions: 0o600,
                 backup: true,
@@ -1060,28 +1477,28 @@ impl AgentPlatform {
         let claude_json = home.join(".claude.json");
         let project_key = pdir.to_string_lossy().into_owned();
         let mut actions = vec![ConfigAction {
-            platform: self,
+            platform: Some(self),
             file_path: claude_json.clone(),
             description:
                 "Claude Code project-local MCP config (~/.claude.json local scope; secrets)".into(),
             content: ConfigContent::ClaudeLocalScopeMcp {
                 project_path: project_key,
                 server_name: "mcp-agent-mail",
-                server_value: standard_http_server_value(url, token),
+                server_value: params.standard_server_value(url, token),
             },
             permissions: 0o600,
             backup: true,
         }];
         if !params.skip_user_config {
             actions.push(ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: claude_json,
                 description: "Claude Code user-level MCP config (~/.claude.json top-level mcpServers)"
                     .into(),
                 content: ConfigContent::JsonMerge {
                     servers_key: "mcpServers",
                     server_name: "mcp-agent-mail",
-                    server_value: standard_http_server_value(url, token),
+                    server_value: params.standard_server_value(url, token),
                 },
                 permissions: 0o600,
                 backup: true,
@@ -1090,7 +1507,7 @@ impl AgentPlatform {
 
         if !params.skip_hooks {
             actions.push(ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: pdir.join(".claude").join("settings.json"),
                 description: "Claude Code hooks (git-tracked)".into(),
                 content: ConfigContent::HooksMerge {
@@ -1118,18 +1535,21 @@ impl AgentPlatform {
             pdir,
             "cursor.mcp.json",
             "mcpServers",
-            standard_http_server_value(url, token),
+            params.standard_server_value(url, token),
             "Cursor project-local MCP config",
         )];
         if !params.skip_user_config {
             actions.push(ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: home.join(".cursor").join("mcp.json"),
                 description: "Cursor user-level MCP config".into(),
                 content: ConfigContent::JsonMerge {
                     servers_key: "mcpServers",
                     server_name: "mcp-agent-mail",
-                    server_value: json!({ "type": "http", "url": url }),
+                    server_value: params.uds_path.as_deref().map_or_else(
+                        || json!({ "type": "http", "url": url }),
+                        uds_bridge_server_value,
+                    ),
                 },
                 permissions: 0o644,
                 backup: true,
@@ -1159,7 +1579,7 @@ impl AgentPlatform {
         )];
         if !params.skip_user_config {
             actions.push(ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: home.join(".gemini").join("settings.json"),
                 description: "Gemini CLI user-level MCP config".into(),
                 content: ConfigContent::JsonMerge {
@@ -1210,7 +1630,7 @@ impl AgentPlatform {
         )];
         if !params.skip_user_config {
             actions.push(ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: home.join(".gemini").join("config").join("mcp_config.json"),
                 description: "Antigravity (agy) user-level MCP config \
                               (~/.gemini/config/mcp_config.json)"
@@ -1248,7 +1668,7 @@ impl AgentPlatform {
         )];
         if !params.skip_user_config {
             actions.push(ConfigAction {
-                platform: self,
+                platform: Some(self),
                 file_path: home.join(".factory").join("mcp.json"),
                 description: "Factory Droid user-level MCP config".into(),
                 content: ConfigContent::JsonMerge {
@@ -1448,52 +1868,150 @@ fn copy_setup_file_to_backup(
     Ok(())
 }
 
-/// Execute a single config write action, returning the outcome.
-pub fn write_config_atomic(action: &ConfigAction) -> Result<ActionOutcome, SetupError> {
-    let parent = action.file_path.parent().unwrap_or_else(|| Path::new("."));
-    ensure_setup_parent_dir(&action.file_path, "config file")?;
-    validate_setup_file_target(&action.file_path, "config file")?;
-
-    let existing = std::fs::read_to_string(&action.file_path).ok();
-
-    let new_content = match &action.content {
+/// Render the content `action` would leave on disk given the current file text.
+fn render_config_content(
+    action: &ConfigAction,
+    existing: Option<&str>,
+) -> Result<String, SetupError> {
+    Ok(match &action.content {
         ConfigContent::JsonMerge {
             servers_key,
             server_name,
             server_value,
-        } => merge_mcp_server(
-            existing.as_deref(),
-            servers_key,
-            server_name,
-            server_value.clone(),
-        )?,
+        } => merge_mcp_server(existing, servers_key, server_name, server_value.clone())?,
         ConfigContent::ClaudeLocalScopeMcp {
             project_path,
             server_name,
             server_value,
-        } => merge_claude_local_scope_mcp(
-            existing.as_deref(),
-            project_path,
-            server_name,
-            server_value.clone(),
-        )?,
+        } => {
+            merge_claude_local_scope_mcp(existing, project_path, server_name, server_value.clone())?
+        }
         ConfigContent::JsonFull(val) => serde_json::to_string_pretty(val)? + "\n",
         ConfigContent::HooksMerge {
             project_slug,
             agent_name,
-        } => merge_claude_hooks(existing.as_deref(), project_slug, agent_name)?,
+        } => merge_claude_hooks(existing, project_slug, agent_name)?,
         ConfigContent::TomlSection {
             section_header,
             key_values,
-        } => merge_toml_section(existing.as_deref(), section_header, key_values),
-    };
+        } => merge_toml_section(existing, section_header, key_values),
+        ConfigContent::InstructionsBlock { block } => match block {
+            Some(block) => upsert_instructions_block(existing.unwrap_or_default(), block)?,
+            None => remove_instructions_block(existing.unwrap_or_default())?,
+        },
+    })
+}
+
+/// The content a config action would leave on disk, computed without writing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigPlan {
+    pub file_path: PathBuf,
+    /// Current file text, or `None` when the file does not exist yet.
+    pub existing: Option<String>,
+    pub content: String,
+    /// Stale `mcp-agent-mail` entries dropped from `content` by pruning.
+    pub removed: Vec<String>,
+}
 
-    // Check if unchanged
-    if existing.as_deref() == Some(&new_content) {
-        return Ok(ActionOutcome::Unchanged);
+impl ConfigPlan {
+    /// What writing the plan would report.
+    #[must_use]
+    pub fn outcome(&self) -> ActionOutcome {
+        match self.existing.as_deref() {
+            None => ActionOutcome::Created,
+            Some(existing) if existing == self.content => ActionOutcome::Unchanged,
+            Some(_) => ActionOutcome::Updated,
+        }
     }
 
-    let was_existing = existing.is_some();
+    /// Unified diff from the current file to the planned content, with bearer
+    /// tokens masked via [`mask_config_secrets`]. Empty when unchanged.
+    #[must_use]
+    pub fn masked_diff(&self) -> String {
+        let before = mask_config_secrets(self.existing.as_deref().unwrap_or_default());
+        let after = mask_config_secrets(&self.content);
+        if self.existing.is_some() && before == after {
+            return String::new();
+        }
+        let path = self.file_path.display().to_string();
+        let old_header = if self.existing.is_some() {
+            path.clone()
+        } else {
+            "/dev/null".to_string()
+        };
+        similar::TextDiff::from_lines(&before, &after)
+            .unified_diff()
+            .header(&old_header, &path)
+            .to_string()
+    }
+}
+
+/// Mask every `Bearer <token>` value in config text the way the CLI masks
+/// secrets elsewhere (`****` plus the last four characters).
+#[must_use]
+pub fn mask_config_secrets(text: &str) -> String {
+    const MARKER: &str = "Bearer ";
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find(MARKER) {
+        let (head, tail) = rest.split_at(idx + MARKER.len());
+        out.push_str(head);
+        let end = tail
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '"' | '\'' | ',' | '}'))
+            .unwrap_or(tail.len());
+        if end > 0 {
+            out.push_str(&crate::config::mask_secret(&tail[..end]));
+        }
+        rest = &tail[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Compute what `action` would write (after pruning stale entries when
+/// `prune_url` is set) without touching the file system.
+pub fn plan_config(
+    action: &ConfigAction,
+    prune_url: Option<&str>,
+) -> Result<ConfigPlan, SetupError> {
+    let existing = std::fs::read_to_string(&action.file_path).ok();
+    let mut content = render_config_content(action, existing.as_deref())?;
+    let mut removed = Vec::new();
+    if let Some(url) = prune_url {
+        (content, removed) = prune_stale_server_entries(&action.content, &content, url)?;
+    }
+    Ok(ConfigPlan {
+        file_path: action.file_path.clone(),
+        existing,
+        content,
+        removed,
+    })
+}
+
+/// Execute a single config write action, returning the outcome.
+pub fn write_config_atomic(action: &ConfigAction) -> Result<ActionOutcome, SetupError> {
+    write_config_with_prune(action, None).map(|(outcome, _)| outcome)
+}
+
+/// Execute a config write action and, when `prune_url` is set, drop stale
+/// `mcp-agent-mail` entries whose URL differs from it in the same write.
+///
+/// Returns the write outcome plus the location of every pruned entry.
+pub fn write_config_with_prune(
+    action: &ConfigAction,
+    prune_url: Option<&str>,
+) -> Result<(ActionOutcome, Vec<String>), SetupError> {
+    let parent = action.file_path.parent().unwrap_or_else(|| Path::new("."));
+    ensure_setup_parent_dir(&action.file_path, "config file")?;
+    validate_setup_file_target(&action.file_path, "config file")?;
+
+    let plan = plan_config(action, prune_url)?;
+    let outcome = plan.outcome();
+    if outcome == ActionOutcome::Unchanged {
+        return Ok((outcome, plan.removed));
+    }
+
+    let was_existing = plan.existing.is_some();
 
     // Backup existing file
     if action.backup && was_existing {
@@ -1507,16 +2025,148 @@ pub fn write_config_atomic(action: &ConfigAction) -> Result<ActionOutcome, Setup
 
     write_setup_file_atomic(
         &action.file_path,
-        new_content.as_bytes(),
+        plan.content.as_bytes(),
         action.permissions,
         "config file",
     )?;
 
-    if was_existing {
-        Ok(ActionOutcome::Updated)
-    } else {
-        Ok(ActionOutcome::Created)
+    Ok((outcome, plan.removed))
+}
+
+/// List the stale entries a pruning write of `action` would remove, without
+/// touching the file (the dry-run half of [`write_config_with_prune`]).
+pub fn plan_config_prune(action: &ConfigAction, url: &str) -> Result<Vec<String>, SetupError> {
+    plan_config(action, Some(url)).map(|plan| plan.removed)
+}
+
+// ---------------------------------------------------------------------------
+// Agent instruction files
+// ---------------------------------------------------------------------------
+
+/// Opening fence of the section `setup run --write-instructions` manages.
+pub const INSTRUCTIONS_START_MARKER: &str = "<!-- agent-mail:start -->";
+/// Closing fence of the managed section.
+pub const INSTRUCTIONS_END_MARKER: &str = "<!-- agent-mail:end -->";
+
+/// Render the fenced instructions section, markers included, without a
+/// trailing newline.
+#[must_use]
+pub fn render_instructions_block(
+    project_key: &str,
+    project_slug: &str,
+    server_url: &str,
+) -> String {
+    format!(
+        "{INSTRUCTIONS_START_MARKER}
+## MCP Agent Mail
+
+This project coordinates agents through MCP Agent Mail.
+
+- Server: `{server_url}` (MCP server `mcp-agent-mail`)
+- Project key: `{project_key}` (slug `{project_slug}`)
+
+Workflow:
+
+1. Start each session with `macro_start_session` using the project key above; it registers you and returns your inbox.
+2. Check your inbox with `fetch_inbox` before editing, and `acknowledge_message` anything marked `ack_required`.
+3. Reserve files with `file_reservation_paths` before writing them; honour conflicts instead of editing around them.
+4. Reply in the existing thread with `reply_message` so decisions stay together.
+5. Release your reservations with `release_file_reservations` when the work is done.
+{INSTRUCTIONS_END_MARKER}"
+    )
+}
+
+/// Byte range of the managed section in `text`, from the start marker
+/// through the end marker. Errors on a start marker with no end after it.
+fn instructions_block_span(text: &str) -> Result<Option<(usize, usize)>, SetupError> {
+    let Some(start) = text.find(INSTRUCTIONS_START_MARKER) else {
+        return Ok(None);
+    };
+    let end = text[start..]
+        .find(INSTRUCTIONS_END_MARKER)
+        .map(|offset| start + offset + INSTRUCTIONS_END_MARKER.len())
+        .ok_or_else(|| {
+            SetupError::Other(format!(
+                "found {INSTRUCTIONS_START_MARKER} without a matching {INSTRUCTIONS_END_MARKER}"
+            ))
+        })?;
+    Ok(Some((start, end)))
+}
+
+/// Replace the managed section in `existing` with `block`, or append it after
+/// a blank line. Bytes outside the fence are preserved, so a second run with
+/// the same block is a no-op.
+fn upsert_instructions_block(existing: &str, block: &str) -> Result<String, SetupError> {
+    if let Some((start, end)) = instructions_block_span(existing)? {
+        return Ok(format!("{}{block}{}", &existing[..start], &existing[end..]));
+    }
+    let mut out = existing.to_string();
+    if !out.is_empty() {
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out.push_str(block);
+    out.push('\n');
+    Ok(out)
+}
+
+/// Strip the managed section and the line break after it. When the section
+/// ended the file, the blank separator line `upsert` added goes too.
+fn remove_instructions_block(existing: &str) -> Result<String, SetupError> {
+    let Some((start, end)) = instructions_block_span(existing)? else {
+        return Ok(existing.to_string());
+    };
+    let mut before = &existing[..start];
+    let after = &existing[end..];
+    let after = after
+        .strip_prefix("\r\n")
+        .or_else(|| after.strip_prefix('\n'))
+        .unwrap_or(after);
+    if after.is_empty() && before.ends_with("\n\n") {
+        before = &before[..before.len() - 1];
+    }
+    Ok(format!("{before}{after}"))
+}
+
+/// Instruction-file actions for `platforms`, one per distinct file. Removal
+/// skips files that do not exist rather than creating empty ones.
+fn instructions_actions(platforms: &[AgentPlatform], params: &SetupParams) -> Vec<ConfigAction> {
+    let Some(edit) = params.instructions else {
+        return Vec::new();
+    };
+    let block = (edit == InstructionsEdit::Write).then(|| {
+        render_instructions_block(
+            &params.project_dir.display().to_string(),
+            &params.project_slug,
+            &params.server_url(),
+        )
+    });
+    let mut actions: Vec<ConfigAction> = Vec::new();
+    for platform in platforms {
+        let file_path = params.project_dir.join(platform.instructions_file());
+        if actions.iter().any(|action| action.file_path == file_path)
+            || (block.is_none() && !file_path.is_file())
+        {
+            continue;
+        }
+        actions.push(ConfigAction {
+            platform: Some(*platform),
+            file_path,
+            description: if block.is_some() {
+                "Agent Mail instructions section".to_string()
+            } else {
+                "Agent Mail instructions section (removal)".to_string()
+            },
+            content: ConfigContent::InstructionsBlock {
+                block: block.clone(),
+            },
+            permissions: 0o644,
+            backup: false,
+        });
     }
+    actions
 }
 
 // ---------------------------------------------------------------------------
@@ -1530,33 +2180,49 @@ pub fn run_setup(params: &SetupParams) -> Vec<SetupResult> {
         .agents
         .clone()
         .unwrap_or_else(|| AgentPlatform::ALL.to_vec());
+    let prune_url = params.server_url();
 
     let mut results = Vec::new();
 
     for platform in &platforms {
-        let actions = platform.config_actions(params);
-        let mut action_results = Vec::new();
+        let mut actions = platform.config_actions(params);
+        if params.project_token {
+            actions.retain(|action| action.is_project_scoped(&params.project_dir));
+        }
+        results.push(SetupResult {
+            platform: platform.display_name().to_string(),
+            actions: apply_config_actions(&actions, params, &prune_url),
+        });
+    }
 
-        for action in &actions {
-            let outcome = if params.dry_run {
-                ActionOutcome::Skipped
-            } else {
-                match write_config_atomic(action) {
-                    Ok(o) => o,
-                    Err(e) => ActionOutcome::Failed(e.to_string()),
+    for connector in &params.custom_connectors {
+        let actions = match custom_connector_action(connector, params) {
+            Ok(Some(action)) => {
+                if params.project_token && !action.is_project_scoped(&params.project_dir) {
+                    continue;
                 }
-            };
-
-            action_results.push(ActionResult {
-                file_path: action.file_path.display().to_string(),
-                description: action.description.clone(),
-                outcome,
-            });
-        }
+                apply_config_actions(&[action], params, &prune_url)
+            }
+            Ok(None) => continue,
+            Err(e) => vec![ActionResult {
+                file_path: String::new(),
+                description: format!("{} MCP config (connectors.toml)", connector.name),
+                outcome: ActionOutcome::Failed(e.to_string()),
+                planned: None,
+                diff: None,
+            }],
+        };
+        results.push(SetupResult {
+            platform: connector.name.clone(),
+            actions,
+        });
+    }
 
+    let instructions = instructions_actions(&platforms, params);
+    if !instructions.is_empty() {
         results.push(SetupResult {
-            platform: platform.display_name().to_string(),
-            actions: action_results,
+            platform: "Agent instructions".to_string(),
+            actions: apply_config_actions(&instructions, params, &prune_url),
         });
     }
 
@@ -1579,6 +2245,19 @@ pub fn run_setup(params: &SetupParams) -> Vec<SetupResult> {
                 }
             }
         }
+        let home = params.home_dir_override.clone().or_else(dirs::home_dir);
+        for connector in &params.custom_connectors {
+            let relative = connector
+                .resolved_config_path(home.as_deref(), &params.project_dir)
+                .and_then(|path| {
+                    path.strip_prefix(&params.project_dir)
+                        .ok()
+                        .map(|rel| rel.display().to_string())
+                });
+            if let Some(entry) = relative.filter(|entry| !entries.contains(entry)) {
+                entries.push(entry);
+            }
+        }
         let entry_refs: Vec<&str> = entries.iter().map(String::as_str).collect();
         let _ = ensure_gitignore_entries(&gitignore, &entry_refs);
     }
@@ -1586,6 +2265,59 @@ pub fn run_setup(params: &SetupParams) -> Vec<SetupResult> {
     results
 }
 
+/// Write (or, on dry runs, plan) each action and collect the results.
+fn apply_config_actions(
+    actions: &[ConfigAction],
+    params: &SetupParams,
+    prune_url: &str,
+) -> Vec<ActionResult> {
+    let mut action_results = Vec::new();
+
+    for action in actions {
+        let file_path = action.file_path.display().to_string();
+        let prune = params.prune.then_some(prune_url);
+        let mut planned = None;
+        let mut diff = None;
+        let (outcome, removed) = if params.dry_run {
+            match plan_config(action, prune) {
+                Ok(plan) => {
+                    planned = Some(plan.outcome());
+                    diff = Some(plan.masked_diff());
+                    (ActionOutcome::Skipped, plan.removed)
+                }
+                Err(e) => (ActionOutcome::Failed(e.to_string()), Vec::new()),
+            }
+        } else {
+            match write_config_with_prune(action, prune) {
+                Ok(written) => written,
+                Err(e) => (ActionOutcome::Failed(e.to_string()), Vec::new()),
+            }
+        };
+
+        action_results.push(ActionResult {
+            file_path: file_path.clone(),
+            description: action.description.clone(),
+            outcome,
+            planned,
+            diff,
+        });
+        for entry in removed {
+            action_results.push(ActionResult {
+                file_path: file_path.clone(),
+                description: "stale mcp-agent-mail entry (URL differs from current config)"
+                    .to_string(),
+                outcome: ActionOutcome::Removed {
+                    entry,
+                    dry_run: params.dry_run,
+                },
+                planned: None,
+                diff: None,
+            });
+        }
+    }
+    action_results
+}
+
 // ---------------------------------------------------------------------------
 // Status checking
 // ---------------------------------------------------------------------------
@@ -1705,12 +2437,21 @@ impl ConfigFileStatus {
 }
 
 /// Check config status for detected agents.
+///
+/// Statuses follow [`AgentPlatform::ALL`] order, one per platform, however
+/// `params.agents` listed them, so `setup status` output is stable.
 #[must_use]
 pub fn check_status(params: &SetupParams) -> Vec<AgentConfigStatus> {
-    let platforms = params
-        .agents
-        .clone()
-        .unwrap_or_else(|| AgentPlatform::ALL.to_vec());
+    let platforms: Vec<AgentPlatform> = AgentPlatform::ALL
+        .iter()
+        .copied()
+        .filter(|platform| {
+            params
+                .agents
+                .as_ref()
+                .is_none_or(|agents| agents.contains(platform))
+        })
+        .collect();
     let url = params.server_url();
 
     let mut statuses = Vec::new();
@@ -1753,6 +2494,8 @@ fn config_file_status_for_action(
     params: &SetupParams,
     expected_url: &str,
 ) -> ConfigFileStatus {
+    let bridge_url = expected_bridge_url_for_action(action);
+    let expected_url = bridge_url.as_deref().unwrap_or(expected_url);
     let home = params.home_dir_override.clone().or_else(dirs::home_dir);
     let expected_entry =
         redact_value_for_status(expected_entry_for_action(action), home.as_deref());
@@ -1906,16 +2649,17 @@ fn analyze_json_config_content(
     }
 
     let url_matches = entries.iter().any(|entry| {
-        json_entry_url(entry.entry).is_some_and(|url| urls_match_for_status(url, expected_url))
+        json_entry_status_url(entry.entry)
+            .is_some_and(|url| urls_match_for_status(&url, expected_url))
     });
     let actual_url = entries
         .iter()
-        .find_map(|entry| json_entry_url(entry.entry).map(str::to_string));
+        .find_map(|entry| json_entry_status_url(entry.entry));
     if !url_matches {
-        if entries
-            .iter()
-            .any(|entry| json_entry_has_legacy_stdio(entry.entry))
-        {
+        if entries.iter().any(|entry| {
+            json_entry_has_legacy_stdio(entry.entry)
+                && json_entry_bridge_socket(entry.entry).is_none()
+        }) {
             push_drift_reason(&mut drift_reasons, ConfigDriftReason::LegacyStdio);
         } else if actual_url.is_some() {
             push_drift_reason(&mut drift_reasons, ConfigDriftReason::StaleHttpPath);
@@ -1974,11 +2718,41 @@ fn collect_json_server_entries(doc: &Value) -> Vec<JsonServerEntry<'_>> {
     entries
 }
 
-fn json_entry_url(entry: &Value) -> Option<&str> {
-    entry
-        .get("url")
-        .or_else(|| entry.get("httpUrl"))
-        .and_then(Value::as_str)
+fn json_entry_url(entry: &Value) -> Option<&str> {
+    entry
+        .get("url")
+        .or_else(|| entry.get("httpUrl"))
+        .and_then(Value::as_str)
+}
+
+/// Socket path of an `am mcp-bridge --uds <path>` entry.
+fn json_entry_bridge_socket(entry: &Value) -> Option<&str> {
+    let args = entry.get("args")?.as_array()?;
+    if args.first().and_then(Value::as_str) != Some("mcp-bridge") {
+        return None;
+    }
+    args.iter()
+        .position(|arg| arg.as_str() == Some("--uds"))
+        .and_then(|index| args.get(index + 1))
+        .and_then(Value::as_str)
+}
+
+/// The URL status compares: the HTTP URL, or `unix:<path>` for bridge entries.
+fn json_entry_status_url(entry: &Value) -> Option<String> {
+    json_entry_url(entry)
+        .map(str::to_string)
+        .or_else(|| json_entry_bridge_socket(entry).map(|socket| format!("unix:{socket}")))
+}
+
+/// Expected status URL for actions that write a socket bridge entry.
+fn expected_bridge_url_for_action(action: &ConfigAction) -> Option<String> {
+    match &action.content {
+        ConfigContent::JsonMerge { server_value, .. }
+        | ConfigContent::ClaudeLocalScopeMcp { server_value, .. } => {
+            json_entry_bridge_socket(server_value).map(|socket| format!("unix:{socket}"))
+        }
+        _ => None,
+    }
 }
 
 fn json_entry_authorization(entry: &Value) -> Option<&str> {
@@ -2223,7 +2997,7 @@ fn expected_entry_for_action(action: &ConfigAction) -> Value {
             "entry": server_value,
         }),
         ConfigContent::JsonFull(value) => value.clone(),
-        ConfigContent::HooksMerge { .. } => json!({}),
+        ConfigContent::HooksMerge { .. } | ConfigContent::InstructionsBlock { .. } => json!({}),
         ConfigContent::TomlSection {
             section_header,
             key_values,
@@ -2256,12 +3030,14 @@ fn expected_authorization_for_action(action: &ConfigAction, token: &str) -> Opti
                     .flatten()
             })
         }
-        ConfigContent::JsonFull(_) | ConfigContent::HooksMerge { .. } => None,
+        ConfigContent::JsonFull(_)
+        | ConfigContent::HooksMerge { .. }
+        | ConfigContent::InstructionsBlock { .. } => None,
     }
 }
 
 fn expected_startup_timeout_for_action(action: &ConfigAction) -> Option<u64> {
-    if action.platform != AgentPlatform::Codex {
+    if action.platform != Some(AgentPlatform::Codex) {
         return None;
     }
     match &action.content {
@@ -2275,7 +3051,8 @@ fn expected_startup_timeout_for_action(action: &ConfigAction) -> Option<u64> {
         ConfigContent::JsonMerge { .. }
         | ConfigContent::ClaudeLocalScopeMcp { .. }
         | ConfigContent::JsonFull(_)
-        | ConfigContent::HooksMerge { .. } => None,
+        | ConfigContent::HooksMerge { .. }
+        | ConfigContent::InstructionsBlock { .. } => None,
     }
 }
 
@@ -2403,7 +3180,7 @@ fn setup_status_remediation(
     let project_dir = redact_path_for_status(&params.project_dir, home.as_deref());
     let args = format!(
         "--agent {} --host {} --port {} --path {} --project-dir {}{}{}",
-        action.platform.slug(),
+        action.platform.map_or("", AgentPlatform::slug),
         params.host,
         params.port,
         params.path,
@@ -3232,7 +4009,7 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let deep = tmp.path().join("a").join("b").join("c").join("config.json");
         let action = ConfigAction {
-            platform: AgentPlatform::Cursor,
+            platform: Some(AgentPlatform::Cursor),
             file_path: deep.clone(),
             description: "test".into(),
             content: ConfigContent::JsonFull(json!({"hello": "world"})),
@@ -3254,7 +4031,7 @@ mod tests {
         std::fs::write(&path, r#"{"old": true}"#).unwrap();
 
         let action = ConfigAction {
-            platform: AgentPlatform::Cursor,
+            platform: Some(AgentPlatform::Cursor),
             file_path: path,
             description: "test".into(),
             content: ConfigContent::JsonFull(json!({"new": true})),
@@ -3285,7 +4062,7 @@ mod tests {
         symlink(&outside, &linked).unwrap();
 
         let action = ConfigAction {
-            platform: AgentPlatform::Cursor,
+            platform: Some(AgentPlatform::Cursor),
             file_path: linked,
             description: "test".into(),
             content: ConfigContent::JsonFull(json!({"new": true})),
@@ -3313,7 +4090,7 @@ mod tests {
         symlink(&outside_dir, &linked_dir).unwrap();
 
         let action = ConfigAction {
-            platform: AgentPlatform::Cursor,
+            platform: Some(AgentPlatform::Cursor),
             file_path: linked_dir.join("config.json"),
             description: "test".into(),
             content: ConfigContent::JsonFull(json!({"new": true})),
@@ -3341,7 +4118,7 @@ mod tests {
         std::fs::write(&path, &initial).unwrap();
 
         let action = ConfigAction {
-            platform: AgentPlatform::Cursor,
+            platform: Some(AgentPlatform::Cursor),
             file_path: path,
             description: "test".into(),
             content: ConfigContent::JsonMerge {
@@ -3410,6 +4187,91 @@ mod tests {
         assert!(!all_text.contains("OldAgent"));
     }
 
+    #[test]
+    fn project_token_registry_round_trips_and_finds_tokens() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(PROJECT_TOKENS_FILE);
+        assert_eq!(
+            ProjectTokenRegistry::load(&path).unwrap(),
+            ProjectTokenRegistry::default()
+        );
+
+        let mut registry = ProjectTokenRegistry::default();
+        registry.upsert("beta", "/work/beta", "tok-beta");
+        registry.upsert("alpha", "/work/alpha", "tok-alpha-old");
+        registry.upsert("alpha", "/work/alpha", "tok-alpha");
+        registry.save(&path).unwrap();
+
+        let loaded = ProjectTokenRegistry::load(&path).unwrap();
+        assert_eq!(loaded, registry);
+        let slugs: Vec<&str> = loaded.projects.iter().map(|e| e.slug.as_str()).collect();
+        assert_eq!(slugs, ["alpha", "beta"]);
+        assert_eq!(loaded.find_token("tok-alpha").unwrap().slug, "alpha");
+        assert_eq!(
+            loaded.find_token("tok-beta").unwrap().human_key,
+            "/work/beta"
+        );
+        assert!(loaded.find_token("tok-alpha-old").is_none());
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(
+            !raw.contains("tok-alpha"),
+            "registry must only hold digests"
+        );
+    }
+
+    #[test]
+    fn resolve_project_token_reuses_only_registered_project_token() {
+        let tmp = tempfile::tempdir().unwrap();
+        let env_path = tmp.path().join(".env");
+        let mut registry = ProjectTokenRegistry::default();
+
+        save_token_to_env_file(&env_path, "global-token").unwrap();
+        let fresh = resolve_project_token(&env_path, &registry, "alpha", "global-token").unwrap();
+        assert_ne!(fresh, "global-token");
+
+        save_token_to_env_file(&env_path, &fresh).unwrap();
+        registry.upsert("alpha", "/work/alpha", &fresh);
+        assert_eq!(
+            resolve_project_token(&env_path, &registry, "alpha", "global-token").unwrap(),
+            fresh
+        );
+        assert_ne!(
+            resolve_project_token(&env_path, &registry, "beta", "global-token").unwrap(),
+            fresh
+        );
+    }
+
+    #[test]
+    fn run_setup_with_project_token_only_writes_project_scoped_configs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project_dir = tmp.path().join("project");
+        let home = tmp.path().join("home");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let params = SetupParams {
+            token: "project-scoped-token".into(),
+            project_dir: project_dir.clone(),
+            home_dir_override: Some(home.clone()),
+            agents: Some(AgentPlatform::ALL.to_vec()),
+            skip_hooks: true,
+            project_token: true,
+            ..Default::default()
+        };
+        let results = run_setup(&params);
+
+        let claude_json = home.join(".claude.json");
+        let mut wrote_project_file = false;
+        for action in results.iter().flat_map(|r| &r.actions) {
+            let path = Path::new(&action.file_path);
+            wrote_project_file |= path.starts_with(&project_dir);
+            assert!(
+                path.starts_with(&project_dir) || path == claude_json,
+                "project token leaked into user-level config {}",
+                action.file_path
+            );
+        }
+        assert!(wrote_project_file);
+    }
+
     #[test]
     fn save_token_to_env_file_creates() {
         let tmp = tempfile::tempdir().unwrap();
@@ -4026,6 +4888,441 @@ http_headers = { Authorization = "Bearer tok" }
         }
     }
 
+    #[test]
+    fn custom_connector_round_trip_detect_then_dry_run_setup() {
+        let tmp = tempfile::tempdir().unwrap();
+        let bin = tmp.path().join("bin");
+        let project = tmp.path().join("project");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(bin.join("acme-agent"), b"#!/bin/sh\n").unwrap();
+
+        let defs = crate::agent_connectors::parse_connector_definitions(
+            r#"
+[[connector]]
+name = "Acme Agent"
+slug = "acme"
+binaries = ["acme-agent"]
+config_path = ".acme/mcp.json"
+servers_key = "servers"
+capabilities = { mcp_http = true }
+"#,
+        );
+        assert!(defs.errors.is_empty(), "{:?}", defs.errors);
+        let acme = defs.connectors[0].clone();
+        let detection = acme.detect(None, Some(bin.as_os_str()));
+        assert!(detection.detected, "fake binary should be detected");
+
+        let params = SetupParams {
+            token: "tok".into(),
+            project_dir: project.clone(),
+            home_dir_override: Some(tmp.path().join("home")),
+            agents: Some(Vec::new()),
+            custom_connectors: vec![acme],
+            dry_run: true,
+            ..Default::default()
+        };
+        let results = run_setup(&params);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].platform, "Acme Agent");
+        let action = &results[0].actions[0];
+        assert_eq!(
+            action.file_path,
+            project.join(".acme/mcp.json").display().to_string()
+        );
+        assert_eq!(action.outcome, ActionOutcome::Skipped);
+        assert_eq!(action.planned, Some(ActionOutcome::Created));
+        let diff = action.diff.as_deref().unwrap();
+        assert!(diff.contains("\"servers\""), "{diff}");
+        assert!(diff.contains("\"type\": \"http\""), "{diff}");
+        assert!(!project.join(".acme").exists(), "dry run must not write");
+    }
+
+    #[test]
+    fn custom_connector_prefers_bridge_when_stdio_and_socket() {
+        let mut acme = crate::agent_connectors::parse_connector_definitions(
+            r#"
+[[connector]]
+name = "Acme Agent"
+slug = "acme"
+binaries = ["acme-agent"]
+config_path = "{home}/.acme/mcp.json"
+capabilities = { mcp_http = true, stdio = true }
+"#,
+        )
+        .connectors
+        .remove(0);
+        let params = SetupParams {
+            home_dir_override: Some(PathBuf::from("/tmp/home")),
+            uds_path: Some(PathBuf::from("/run/am.sock")),
+            ..Default::default()
+        };
+        let action = custom_connector_action(&acme, &params).unwrap().unwrap();
+        assert_eq!(action.file_path, PathBuf::from("/tmp/home/.acme/mcp.json"));
+        let ConfigContent::JsonMerge { server_value, .. } = &action.content else {
+            panic!("expected JsonMerge");
+        };
+        assert_eq!(server_value["args"][0], "mcp-bridge");
+
+        acme.capabilities.stdio = false;
+        let action = custom_connector_action(&acme, &params).unwrap().unwrap();
+        let ConfigContent::JsonMerge { server_value, .. } = &action.content else {
+            panic!("expected JsonMerge");
+        };
+        assert_eq!(server_value["type"], "http");
+    }
+
+    #[test]
+    fn run_setup_dry_run_reports_masked_diffs_without_writing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cline.mcp.json");
+        let existing = "{\n  \"mcpServers\": {\n    \"github\": { \"url\": \"https://example.test\" }\n  }\n}\n";
+        std::fs::write(&path, existing).unwrap();
+        let params = SetupParams {
+            token: "0123456789abcdef".into(),
+            project_dir: tmp.path().to_path_buf(),
+            home_dir_override: Some(tmp.path().to_path_buf()),
+            agents: Some(vec![AgentPlatform::Cline]),
+            dry_run: true,
+            skip_hooks: true,
+            ..Default::default()
+        };
+
+        let results = run_setup(&params);
+        let action = &results[0].actions[0];
+        assert_eq!(action.outcome, ActionOutcome::Skipped);
+        assert_eq!(action.planned, Some(ActionOutcome::Updated));
+        let diff = action.diff.as_deref().unwrap();
+        assert!(diff.contains("+    \"mcp-agent-mail\""), "{diff}");
+        assert!(diff.contains("Bearer ****cdef"), "{diff}");
+        assert!(!diff.contains("0123456789abcdef"), "{diff}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), existing);
+
+        // Once written, a second dry run confirms there is nothing to change.
+        let write = SetupParams {
+            dry_run: false,
+            ..params
+        };
+        run_setup(&write);
+        let rerun = SetupParams {
+            dry_run: true,
+            ..write
+        };
+        let results = run_setup(&rerun);
+        let action = &results[0].actions[0];
+        assert_eq!(action.planned, Some(ActionOutcome::Unchanged));
+        assert_eq!(action.diff.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn plan_config_for_missing_file_diffs_from_dev_null() {
+        let tmp = tempfile::tempdir().unwrap();
+        let action = project_local_action(
+            AgentPlatform::Cline,
+            tmp.path(),
+            "cline.mcp.json",
+            "mcpServers",
+            standard_http_server_value("http://127.0.0.1:8765/mcp/", ""),
+            "test",
+        );
+        let plan = plan_config(&action, None).unwrap();
+        assert_eq!(plan.outcome(), ActionOutcome::Created);
+        assert!(plan.masked_diff().starts_with("--- /dev/null\n"));
+        assert!(!action.file_path.exists());
+    }
+
+    #[test]
+    fn mask_config_secrets_masks_json_and_toml_bearer_values() {
+        assert_eq!(
+            mask_config_secrets(r#""Authorization": "Bearer 0123456789abcdef""#),
+            r#""Authorization": "Bearer ****cdef""#
+        );
+        assert_eq!(
+            mask_config_secrets(r#"http_headers = { Authorization = "Bearer short" }"#),
+            r#"http_headers = { Authorization = "Bearer ****" }"#
+        );
+        assert_eq!(mask_config_secrets("no secrets here"), "no secrets here");
+    }
+
+    const PRUNE_JSON_FIXTURE: &str = include_str!("../tests/fixtures/setup_prune/cline.mcp.json");
+    const PRUNE_TOML_FIXTURE: &str =
+        include_str!("../tests/fixtures/setup_prune/codex_config.toml");
+
+    #[test]
+    fn instructions_block_upsert_is_idempotent_and_removal_restores_file() {
+        let block = render_instructions_block("/abs/proj", "proj", "http://127.0.0.1:8765/mcp/");
+        assert!(block.starts_with(INSTRUCTIONS_START_MARKER));
+        assert!(block.ends_with(INSTRUCTIONS_END_MARKER));
+        assert!(block.contains("`/abs/proj` (slug `proj`)"));
+
+        let original = "# Notes\r\n\nKeep  this   spacing.\n";
+        let written = upsert_instructions_block(original, &block).unwrap();
+        assert!(written.starts_with(original));
+        assert_eq!(
+            upsert_instructions_block(&written, &block).unwrap(),
+            written
+        );
+        assert_eq!(remove_instructions_block(&written).unwrap(), original);
+
+        // A fenced block in the middle is replaced in place.
+        let middle =
+            format!("intro\n{INSTRUCTIONS_START_MARKER}\nold\n{INSTRUCTIONS_END_MARKER}\noutro\n");
+        let refreshed = upsert_instructions_block(&middle, &block).unwrap();
+        assert_eq!(refreshed, format!("intro\n{block}\noutro\n"));
+        assert_eq!(
+            remove_instructions_block(&refreshed).unwrap(),
+            "intro\noutro\n"
+        );
+
+        assert!(upsert_instructions_block(INSTRUCTIONS_START_MARKER, &block).is_err());
+    }
+
+    #[test]
+    fn run_setup_writes_and_removes_instruction_files_per_platform() {
+        let tmp = tempfile::tempdir().unwrap();
+        let project = tmp.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let agents_md = project.join("AGENTS.md");
+        std::fs::write(&agents_md, "# House rules\n").unwrap();
+
+        let params = SetupParams {
+            project_dir: project.clone(),
+            home_dir_override: Some(tmp.path().join("home")),
+            agents: Some(vec![
+                AgentPlatform::Claude,
+                AgentPlatform::Codex,
+                AgentPlatform::Cursor,
+                AgentPlatform::Gemini,
+            ]),
+            skip_user_config: true,
+            skip_hooks: true,
+            project_slug: "project".into(),
+            instructions: Some(InstructionsEdit::Write),
+            dry_run: true,
+            ..Default::default()
+        };
+        let instruction_actions = |results: &[SetupResult]| {
+            results
+                .iter()
+                .find(|result| result.platform == "Agent instructions")
+                .map(|result| {
+                    result
+                        .actions
+                        .iter()
+                        .map(|action| {
+                            (
+                                action.file_path.clone(),
+                                action
+                                    .planned
+                                    .clone()
+                                    .unwrap_or_else(|| action.outcome.clone()),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        let results = run_setup(&params);
+        let planned = instruction_actions(&results);
+        assert_eq!(
+            planned,
+            vec![
+                (
+                    project.join("CLAUDE.md").display().to_string(),
+                    ActionOutcome::Created
+                ),
+                (agents_md.display().to_string(), ActionOutcome::Updated),
+                (
+                    project.join(".cursorrules").display().to_string(),
+                    ActionOutcome::Created
+                ),
+            ]
+        );
+        let diff = results
+            .iter()
+            .flat_map(|result| &result.actions)
+            .find_map(|action| {
+                action
+                    .diff
+                    .clone()
+                    .filter(|diff| diff.contains("AGENTS.md"))
+            })
+            .unwrap();
+        assert!(
+            diff.contains(&format!("+{INSTRUCTIONS_START_MARKER}")),
+            "{diff}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&agents_md).unwrap(),
+            "# House rules\n"
+        );
+        assert!(!project.join("CLAUDE.md").exists());
+
+        let write = SetupParams {
+            dry_run: false,
+            ..params
+        };
+        run_setup(&write);
+        let written = std::fs::read_to_string(&agents_md).unwrap();
+        assert!(written.starts_with("# House rules\n\n<!-- agent-mail:start -->"));
+        let rerun = instruction_actions(&run_setup(&write));
+        assert!(
+            rerun
+                .iter()
+                .all(|(_, outcome)| *outcome == ActionOutcome::Unchanged),
+            "{rerun:?}"
+        );
+
+        std::fs::remove_file(project.join(".cursorrules")).unwrap();
+        let remove = SetupParams {
+            instructions: Some(InstructionsEdit::Remove),
+            ..write
+        };
+        let removed = instruction_actions(&run_setup(&remove));
+        assert_eq!(
+            removed.len(),
+            2,
+            "missing files are not recreated: {removed:?}"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&agents_md).unwrap(),
+            "# House rules\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(project.join("CLAUDE.md")).unwrap(),
+            ""
+        );
+        assert!(!project.join(".cursorrules").exists());
+    }
+
+    fn prune_params(dir: &Path, agent: AgentPlatform, dry_run: bool) -> SetupParams {
+        SetupParams {
+            project_dir: dir.to_path_buf(),
+            home_dir_override: Some(dir.to_path_buf()),
+            agents: Some(vec![agent]),
+            dry_run,
+            skip_hooks: true,
+            prune: true,
+            ..Default::default()
+        }
+    }
+
+    fn removed_entries(results: &[SetupResult]) -> Vec<(String, bool)> {
+        results
+            .iter()
+            .flat_map(|result| &result.actions)
+            .filter_map(|action| match &action.outcome {
+                ActionOutcome::Removed { entry, dry_run } => Some((entry.clone(), *dry_run)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn run_setup_prune_removes_stale_json_entries_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cline.mcp.json");
+        std::fs::write(&path, PRUNE_JSON_FIXTURE).unwrap();
+
+        let results = run_setup(&prune_params(tmp.path(), AgentPlatform::Cline, false));
+        assert_eq!(
+            removed_entries(&results),
+            vec![
+                ("mcpServers.mcp-agent-mail-old".to_string(), false),
+                ("servers.mcp_agent_mail".to_string(), false),
+            ]
+        );
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        // Unrelated servers survive byte-for-byte, even one sharing the stale URL.
+        let github = &PRUNE_JSON_FIXTURE[PRUNE_JSON_FIXTURE.find("    \"github\"").unwrap()
+            ..PRUNE_JSON_FIXTURE.find("    \"mcp-agent-mail\"").unwrap()];
+        assert!(
+            written.contains(github),
+            "github entry rewritten:\n{written}"
+        );
+        assert!(written.contains(
+            "    \"postgres\": {\n      \"type\": \"http\",\n      \"url\": \"http://127.0.0.1:9000/api/\"\n    }"
+        ));
+        assert!(!written.contains("mcp-agent-mail-old"));
+        assert!(!written.contains("mcp_agent_mail"));
+        let doc: Value = serde_json::from_str(&written).unwrap();
+        assert_eq!(
+            doc["mcpServers"]["mcp-agent-mail"]["url"],
+            "http://127.0.0.1:8765/mcp/"
+        );
+    }
+
+    #[test]
+    fn run_setup_prune_removes_stale_toml_tables_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join(".codex").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, PRUNE_TOML_FIXTURE).unwrap();
+
+        let results = run_setup(&prune_params(tmp.path(), AgentPlatform::Codex, false));
+        assert_eq!(
+            removed_entries(&results),
+            vec![("mcp_servers.\"mcp-agent-mail\"".to_string(), false)]
+        );
+
+        let stale = "[mcp_servers.\"mcp-agent-mail\"]\nurl = \"http://127.0.0.1:9000/api/\"\n\n\
+                     [mcp_servers.\"mcp-agent-mail\".env]\nLEGACY = \"1\"\n\n";
+        assert!(PRUNE_TOML_FIXTURE.contains(stale));
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, PRUNE_TOML_FIXTURE.replace(stale, ""));
+    }
+
+    #[test]
+    fn run_setup_prune_dry_run_reports_without_writing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cline.mcp.json");
+        std::fs::write(&path, PRUNE_JSON_FIXTURE).unwrap();
+
+        let results = run_setup(&prune_params(tmp.path(), AgentPlatform::Cline, true));
+        assert_eq!(
+            removed_entries(&results),
+            vec![
+                ("mcpServers.mcp-agent-mail-old".to_string(), true),
+                ("servers.mcp_agent_mail".to_string(), true),
+            ]
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), PRUNE_JSON_FIXTURE);
+        assert_eq!(
+            ActionOutcome::Removed {
+                entry: "servers.mcp_agent_mail".into(),
+                dry_run: true,
+            }
+            .to_string(),
+            "would remove servers.mcp_agent_mail"
+        );
+    }
+
+    #[test]
+    fn run_setup_without_prune_keeps_stale_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("cline.mcp.json");
+        std::fs::write(&path, PRUNE_JSON_FIXTURE).unwrap();
+
+        let mut params = prune_params(tmp.path(), AgentPlatform::Cline, false);
+        params.prune = false;
+        let results = run_setup(&params);
+        assert!(removed_entries(&results).is_empty());
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("mcp-agent-mail-old"));
+        assert!(written.contains("mcp_agent_mail"));
+    }
+
+    #[test]
+    fn agent_mail_server_name_matching() {
+        assert!(is_agent_mail_server_name("mcp-agent-mail"));
+        assert!(is_agent_mail_server_name("mcp_agent_mail"));
+        assert!(is_agent_mail_server_name("mcp-agent-mail-staging"));
+        assert!(!is_agent_mail_server_name("mcp-agent-mailbox"));
+        assert!(!is_agent_mail_server_name("github"));
+    }
+
     #[test]
     fn run_setup_creates_gitignore_entries() {
         let tmp = tempfile::tempdir().unwrap();
@@ -4372,6 +5669,81 @@ http_headers = { Authorization = "Bearer tok" }
         );
     }
 
+    #[test]
+    fn check_status_orders_platforms_canonically() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut params = setup_status_test_params(tmp.path(), AgentPlatform::Codex);
+        params.agents = Some(vec![
+            AgentPlatform::Cursor,
+            AgentPlatform::Claude,
+            AgentPlatform::Cursor,
+        ]);
+        let slugs: Vec<String> = check_status(&params)
+            .into_iter()
+            .map(|status| status.slug)
+            .collect();
+        assert_eq!(
+            slugs,
+            vec![
+                AgentPlatform::Claude.slug().to_string(),
+                AgentPlatform::Cursor.slug().to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn uds_path_writes_bridge_entries_without_token() {
+        let params = SetupParams {
+            token: "tok".into(),
+            uds_path: Some(PathBuf::from("/run/agent-mail/am.sock")),
+            ..Default::default()
+        };
+        let bridge = json!({
+            "type": "stdio",
+            "command": "am",
+            "args": ["mcp-bridge", "--uds", "/run/agent-mail/am.sock"]
+        });
+        for platform in [AgentPlatform::Cline, AgentPlatform::GithubCopilot] {
+            let actions = platform.config_actions(&params);
+            let ConfigContent::JsonMerge { server_value, .. } = &actions[0].content else {
+                panic!("expected JSON merge for {platform:?}");
+            };
+            assert_eq!(server_value, &bridge, "{platform:?}");
+        }
+
+        // Platforms without stdio support keep the HTTP URL.
+        let actions = AgentPlatform::Codex.config_actions(&params);
+        let ConfigContent::TomlSection { key_values, .. } = &actions[0].content else {
+            panic!("expected TOML section for Codex");
+        };
+        assert!(key_values.iter().any(|(key, _)| key == "url"));
+    }
+
+    #[test]
+    fn check_status_accepts_matching_bridge_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut params = setup_status_test_params(tmp.path(), AgentPlatform::Cline);
+        params.uds_path = Some(PathBuf::from("/run/agent-mail/am.sock"));
+        std::fs::write(
+            params.project_dir.join("cline.mcp.json"),
+            r#"{"mcpServers":{"mcp-agent-mail":{"type":"stdio","command":"am","args":["mcp-bridge","--uds","/run/agent-mail/am.sock"]}}}"#,
+        )
+        .unwrap();
+        let file = first_setup_status_file(&params);
+        assert_eq!(file.primary_drift_reason, ConfigDriftReason::Ok);
+        assert!(file.url_matches);
+        assert_eq!(file.expected_url, "unix:/run/agent-mail/am.sock");
+
+        // The same entry is stale once setup expects HTTP again.
+        params.uds_path = None;
+        let file = first_setup_status_file(&params);
+        assert_eq!(file.primary_drift_reason, ConfigDriftReason::StaleHttpPath);
+        assert_eq!(
+            file.actual_url.as_deref(),
+            Some("unix:/run/agent-mail/am.sock")
+        );
+    }
+
     fn setup_status_test_params(root: &Path, platform: AgentPlatform) -> SetupParams {
         let project_dir = root.join("project");
         let home_dir = root.join("home");
@@ -1,7 +1,8 @@
 //! Criterion benchmark for the inbox-fetch hot path.
 //!
 //! Seeds an in-memory DB with 10K messages distributed across agents,
-//! then measures `fetch_inbox_rows_from_conn` at various limits.
+//! then measures `fetch_inbox_rows_from_conn` at various limits, plus the
+//! `--count-only` aggregate against a full unbounded fetch.
 
 use std::cell::OnceCell;
 use std::hint::black_box;
@@ -9,7 +10,9 @@ use std::hint::black_box;
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 use mcp_agent_mail_db::DbConn;
 use mcp_agent_mail_db::schema;
-use mcp_agent_mail_db::sync::{fetch_inbox_metadata_rows_from_conn, fetch_inbox_rows_from_conn};
+use mcp_agent_mail_db::sync::{
+    count_inbox_rows_from_conn, fetch_inbox_metadata_rows_from_conn, fetch_inbox_rows_from_conn,
+};
 use sqlmodel_core::Value;
 
 fn block_on<F, Fut, T>(f: F) -> T
@@ -284,11 +287,60 @@ fn bench_inbox_stats_query(c: &mut Criterion) {
     });
 }
 
+/// `--count-only` must stay an order of magnitude cheaper than materializing
+/// the same (unbounded) inbox it summarizes.
+fn bench_inbox_count_only(c: &mut Criterion) {
+    let state = OnceCell::new();
+
+    let mut group = c.benchmark_group("inbox_count_only_100k");
+    group.sample_size(20);
+
+    group.bench_function("full_metadata_fetch", |b| {
+        let (conn, project_id, agent_ids) = state.get_or_init(|| seeded_conn(100_000));
+        b.iter(|| {
+            black_box(
+                fetch_inbox_metadata_rows_from_conn(
+                    conn,
+                    *project_id,
+                    agent_ids[1],
+                    false,
+                    false,
+                    false,
+                    None,
+                    100_000,
+                )
+                .expect("full inbox fetch"),
+            )
+        });
+    });
+
+    group.bench_function("count_only", |b| {
+        let (conn, project_id, agent_ids) = state.get_or_init(|| seeded_conn(100_000));
+        b.iter(|| {
+            black_box(
+                count_inbox_rows_from_conn(
+                    conn,
+                    *project_id,
+                    agent_ids[1],
+                    false,
+                    None,
+                    None,
+                    Some(i64::MAX),
+                )
+                .expect("inbox count"),
+            )
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_inbox_fetch,
     bench_inbox_fetch_since,
     bench_inbox_fetch_body_policy,
     bench_inbox_stats_query,
+    bench_inbox_count_only,
 );
 criterion_main!(benches);
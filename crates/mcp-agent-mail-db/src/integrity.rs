@@ -21,8 +21,8 @@ use crate::error::{DbError, DbResult};
 use serde::{Deserialize, Serialize};
 use sqlmodel_core::{Row, Value};
 use std::path::Path;
-use std::sync::OnceLock;
 use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock, PoisonError};
 
 /// Result of an integrity check.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -132,6 +132,140 @@ pub struct MailboxIntegrityVerdict {
     pub detail: String,
 }
 
+/// Environment variable selecting the integrity probe authority.
+pub const INTEGRITY_PROBE_ENV: &str = "AGENT_MAIL_INTEGRITY_PROBE";
+
+/// Which engine's integrity verdict decides mailbox health
+/// (`AGENT_MAIL_INTEGRITY_PROBE=canonical|bespoke|both`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityProbeAuthority {
+    /// Canonical SQLite only; the bespoke probe is not run.
+    Canonical,
+    /// The bespoke probe only; corruption complaints are never second-guessed.
+    Bespoke,
+    /// The bespoke probe, with canonical SQLite reconciling any corruption
+    /// complaint it raises.
+    #[default]
+    Both,
+}
+
+impl IntegrityProbeAuthority {
+    /// Parse `canonical`, `bespoke`, or `both` (case-insensitive).
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "canonical" => Some(Self::Canonical),
+            "bespoke" => Some(Self::Bespoke),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Canonical => "canonical",
+            Self::Bespoke => "bespoke",
+            Self::Both => "both",
+        }
+    }
+
+    /// Read [`INTEGRITY_PROBE_ENV`]; unset or unrecognised values mean
+    /// [`Self::Both`].
+    #[must_use]
+    pub fn from_env() -> Self {
+        let Some(raw) = mcp_agent_mail_core::config::env_value(INTEGRITY_PROBE_ENV) else {
+            return Self::default();
+        };
+        Self::parse(&raw).unwrap_or_else(|| {
+            tracing::warn!(
+                value = %raw,
+                "unrecognised {INTEGRITY_PROBE_ENV}; expected canonical, bespoke, or both"
+            );
+            Self::default()
+        })
+    }
+}
+
+/// Engine whose verdict stood on an integrity check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityProbeSource {
+    Bespoke,
+    Canonical,
+}
+
+impl IntegrityProbeSource {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Bespoke => "bespoke",
+            Self::Canonical => "canonical",
+        }
+    }
+}
+
+/// Provenance of the most recent integrity verdict.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityProbeVerdict {
+    pub authority: IntegrityProbeAuthority,
+    pub source: IntegrityProbeSource,
+    /// Bespoke findings that canonical SQLite overruled, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discrepancy: Option<String>,
+    pub ts: i64,
+}
+
+static LAST_PROBE_VERDICT: Mutex<Option<IntegrityProbeVerdict>> = Mutex::new(None);
+
+/// Provenance of the most recent integrity verdict, if any check has run.
+#[must_use]
+pub fn last_probe_verdict() -> Option<IntegrityProbeVerdict> {
+    LAST_PROBE_VERDICT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+pub(crate) fn record_probe_verdict(
+    authority: IntegrityProbeAuthority,
+    source: IntegrityProbeSource,
+    discrepancy: Option<String>,
+) {
+    *LAST_PROBE_VERDICT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner) = Some(IntegrityProbeVerdict {
+        authority,
+        source,
+        discrepancy,
+        ts: crate::now_micros(),
+    });
+}
+
+/// Record that canonical SQLite overruled a bespoke corruption complaint.
+///
+/// The bespoke failure already counted toward `failures_total`; this marks
+/// the mailbox clean again exactly as a passing check would, so health
+/// surfaces and the corruption breaker do not keep reading it as corrupt,
+/// and keeps `discrepancy` for diagnostics.
+pub(crate) fn record_canonical_override(kind: CheckKind, discrepancy: String) {
+    let s = state();
+    let now = crate::now_micros();
+    s.last_check_ts.store(now, Ordering::Relaxed);
+    s.last_ok_ts.store(now, Ordering::Relaxed);
+    if kind == CheckKind::Full {
+        s.last_full_check_ts.store(now, Ordering::Relaxed);
+    }
+    s.failures_since_last_ok.store(0, Ordering::Relaxed);
+    crate::corruption_circuit_breaker().reset();
+    record_probe_verdict(
+        IntegrityProbeAuthority::Both,
+        IntegrityProbeSource::Canonical,
+        Some(discrepancy),
+    );
+}
+
 /// Get current integrity check metrics.
 #[must_use]
 pub fn integrity_metrics() -> IntegrityMetrics {
@@ -178,24 +312,32 @@ pub fn inspect_mailbox_integrity(db_path: &Path, kind: CheckKind) -> MailboxInte
         };
     }
 
-    let path_str = db_path.display().to_string();
-    let conn = match crate::DbConn::open_file(&path_str) {
-        Ok(conn) => conn,
-        Err(error) => {
-            return MailboxIntegrityVerdict {
-                status: MailboxIntegrityStatus::Broken,
-                metrics: metrics_before,
-                check: None,
-                detail: format!("Cannot open database for integrity check: {error}"),
-            };
-        }
-    };
+    // Same authority and canonical reconciliation as the startup probe and
+    // the integrity guard, so the verdict (and the recovery mode derived from
+    // it) agrees with them.
+    let checked = crate::pool::integrity_check_with_authority(
+        IntegrityProbeAuthority::from_env(),
+        db_path,
+        kind,
+        "verdict",
+        || {
+            let path_str = db_path.display().to_string();
+            let conn = crate::DbConn::open_file(&path_str).map_err(|error| {
+                DbError::Sqlite(format!("Cannot open database for integrity check: {error}"))
+            })?;
+            run_check(&conn, kind)
+        },
+    );
 
-    match run_check(&conn, kind) {
+    match checked {
         Ok(check) => MailboxIntegrityVerdict {
             status: MailboxIntegrityStatus::Healthy,
             metrics: integrity_metrics(),
-            detail: format!("{} passed", check.kind),
+            detail: if details_indicate_ok(&check.details) {
+                format!("{} passed", check.kind)
+            } else {
+                format!("{} passed ({})", check.kind, check.details.join("; "))
+            },
             check: Some(check),
         },
         Err(DbError::IntegrityCorruption { details, .. })
@@ -226,6 +368,22 @@ pub fn inspect_mailbox_integrity(db_path: &Path, kind: CheckKind) -> MailboxInte
     }
 }
 
+/// Whether every finding is an index ordering or shape complaint (entries out
+/// of order, missing or surplus index entries) rather than page- or
+/// table-level damage. These are the findings on which the bespoke probe is
+/// known to diverge from canonical SQLite.
+#[must_use]
+pub fn integrity_details_are_index_shape_only(details: &[String]) -> bool {
+    !details.is_empty()
+        && details.iter().all(|detail| {
+            let lower = detail.to_ascii_lowercase();
+            lower.contains("out of order")
+                || lower.contains("missing from index")
+                || lower.contains("wrong # of entries in index")
+                || lower.contains("non-unique entry in index")
+        })
+}
+
 #[must_use]
 pub fn integrity_details_are_suspect(details: &[String]) -> bool {
     !details.is_empty()
@@ -372,7 +530,16 @@ pub fn evaluate_check_rows(
     kind: CheckKind,
     duration_us: u64,
 ) -> DbResult<IntegrityCheckResult> {
-    let details = extract_check_details(rows, kind);
+    evaluate_check_details(extract_check_details(rows, kind), kind, duration_us)
+}
+
+/// Evaluate already-extracted check output (e.g. from the canonical engine)
+/// and update global integrity metrics.
+pub fn evaluate_check_details(
+    details: Vec<String>,
+    kind: CheckKind,
+    duration_us: u64,
+) -> DbResult<IntegrityCheckResult> {
     let ok = details_indicate_ok(&details);
 
     // Update global state.
@@ -1033,4 +1200,66 @@ mod tests {
             "recovery shm should be removed"
         );
     }
+
+    #[test]
+    fn probe_authority_parses_and_index_shape_findings_are_classified() {
+        assert_eq!(
+            IntegrityProbeAuthority::parse(" Canonical"),
+            Some(IntegrityProbeAuthority::Canonical)
+        );
+        assert_eq!(
+            IntegrityProbeAuthority::parse("BESPOKE"),
+            Some(IntegrityProbeAuthority::Bespoke)
+        );
+        assert_eq!(IntegrityProbeAuthority::parse("sqlite"), None);
+        assert_eq!(
+            IntegrityProbeAuthority::default(),
+            IntegrityProbeAuthority::Both
+        );
+
+        assert!(integrity_details_are_index_shape_only(&[
+            "row 5: entries are out of order for index idx_agents_project_name_nocase".to_string(),
+            "wrong # of entries in index idx_messages_thread".to_string(),
+        ]));
+        assert!(!integrity_details_are_index_shape_only(&[
+            "row 5: entries are out of order for index idx_agents_project_name_nocase".to_string(),
+            "*** in database main *** Page 7: btreeInitPage() returns error code 11".to_string(),
+        ]));
+        assert!(!integrity_details_are_index_shape_only(&[]));
+    }
+
+    #[test]
+    fn canonical_override_marks_state_clean_and_records_discrepancy() {
+        let _guard = TEST_STATE_LOCK.lock().unwrap();
+        let s = state();
+        let state_before = (
+            s.last_ok_ts.load(Ordering::Relaxed),
+            s.last_check_ts.load(Ordering::Relaxed),
+            s.last_full_check_ts.load(Ordering::Relaxed),
+            s.checks_total.load(Ordering::Relaxed),
+            s.failures_total.load(Ordering::Relaxed),
+        );
+
+        // The bespoke probe just failed: the last check is newer than the last ok.
+        set_state_for_tests(1_000, 2_000, 0, 4, 1);
+        let complaint = "row 5: entries are out of order for index idx_agents_project_name_nocase";
+        record_canonical_override(CheckKind::Quick, complaint.to_string());
+
+        let metrics = integrity_metrics();
+        assert_eq!(metrics.failures_since_last_ok, 0);
+        assert_eq!(metrics.last_ok_ts, metrics.last_check_ts);
+        assert!(metrics.failures_total >= 1, "lifetime tally is kept");
+        let verdict = last_probe_verdict().expect("override records a verdict");
+        assert_eq!(verdict.source, IntegrityProbeSource::Canonical);
+        assert_eq!(verdict.authority, IntegrityProbeAuthority::Both);
+        assert_eq!(verdict.discrepancy.as_deref(), Some(complaint));
+
+        set_state_for_tests(
+            state_before.0,
+            state_before.1,
+            state_before.2,
+            state_before.3,
+            state_before.4,
+        );
+    }
 }
@@ -213,9 +213,11 @@ pub use forensics::{
     capture_mailbox_forensic_bundle, capture_pre_recovery_snapshot,
 };
 pub use integrity::{
-    CheckKind, IntegrityCheckResult, IntegrityMetrics, MailboxIntegrityStatus,
+    CheckKind, INTEGRITY_PROBE_ENV, IntegrityCheckResult, IntegrityMetrics,
+    IntegrityProbeAuthority, IntegrityProbeSource, IntegrityProbeVerdict, MailboxIntegrityStatus,
     MailboxIntegrityVerdict, attempt_vacuum_recovery, full_check, incremental_check,
-    inspect_mailbox_integrity, integrity_details_are_suspect, integrity_metrics, is_full_check_due,
+    inspect_mailbox_integrity, integrity_details_are_index_shape_only,
+    integrity_details_are_suspect, integrity_metrics, is_full_check_due, last_probe_verdict,
     quick_check,
 };
 pub use invariants::{
@@ -80,6 +80,10 @@ pub struct ProductRow {
     pub name: String,
 
     pub created_at: i64,
+
+    /// Archive root that `am archive relocate` moves the product's projects
+    /// to by default. `None` keeps the configured archive root.
+    pub archive_root: Option<String>,
 }
 
 impl Default for ProductRow {
@@ -89,6 +93,7 @@ impl Default for ProductRow {
             product_uid: String::new(),
             name: String::new(),
             created_at: now_micros(),
+            archive_root: None,
         }
     }
 }
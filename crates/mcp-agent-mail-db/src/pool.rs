@@ -2740,17 +2740,12 @@ impl DbPool {
         conn: &DbConn,
         phase: &str,
     ) -> DbResult<integrity::IntegrityCheckResult> {
-        reconcile_with_canonical(
-            integrity::quick_check(conn),
+        integrity_check_with_authority(
+            integrity::IntegrityProbeAuthority::from_env(),
+            Path::new(&self.sqlite_path),
             integrity::CheckKind::Quick,
             phase,
-            &self.sqlite_path,
-            || {
-                sqlite_canonical_file_check_is_ok(
-                    Path::new(&self.sqlite_path),
-                    integrity::CheckKind::Quick,
-                )
-            },
+            || integrity::quick_check(conn),
         )
     }
 
@@ -2815,17 +2810,12 @@ impl DbPool {
             "full integrity check connection",
         );
 
-        reconcile_with_canonical(
-            integrity::full_check(&conn),
+        integrity_check_with_authority(
+            integrity::IntegrityProbeAuthority::from_env(),
+            Path::new(&self.sqlite_path),
             integrity::CheckKind::Full,
             "full-cycle",
-            &self.sqlite_path,
-            || {
-                sqlite_canonical_file_check_is_ok(
-                    Path::new(&self.sqlite_path),
-                    integrity::CheckKind::Full,
-                )
-            },
+            || integrity::full_check(&conn),
         )
     }
 
@@ -4938,6 +4928,72 @@ fn sqlite_canonical_file_check_is_ok(
     sqlite_pragma_check_is_ok_canonical(&conn, kind)
 }
 
+/// Canonical SQLite's confirmation for [`reconcile_with_canonical`]: both
+/// `quick_check` and the full `integrity_check` must accept the file before it
+/// may overrule a bespoke corruption complaint.
+#[allow(clippy::result_large_err)]
+fn sqlite_canonical_file_confirms_healthy(path: &Path) -> Result<bool, SqlError> {
+    let path_str = path.to_string_lossy();
+    let conn = crate::CanonicalDbConn::open_file(path_str.as_ref())?;
+    Ok(
+        sqlite_pragma_check_is_ok_canonical(&conn, integrity::CheckKind::Quick)?
+            && sqlite_pragma_check_is_ok_canonical(&conn, integrity::CheckKind::Full)?,
+    )
+}
+
+/// Canonical-only integrity check (`AGENT_MAIL_INTEGRITY_PROBE=canonical`),
+/// recorded in the integrity metrics like a bespoke check.
+fn sqlite_canonical_integrity_check(
+    path: &Path,
+    kind: integrity::CheckKind,
+) -> DbResult<integrity::IntegrityCheckResult> {
+    let start = Instant::now();
+    let path_str = path.to_string_lossy();
+    let conn = crate::CanonicalDbConn::open_file(path_str.as_ref())
+        .map_err(|e| DbError::Sqlite(format!("canonical {kind} open failed: {e}")))?;
+    let details = sqlite_pragma_check_details_canonical(&conn, kind)
+        .map_err(|e| DbError::Sqlite(format!("canonical {kind} failed: {e}")))?;
+    let duration_us = u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX);
+    integrity::evaluate_check_details(details, kind, duration_us)
+}
+
+/// Run a `kind` integrity check under `authority`
+/// (`AGENT_MAIL_INTEGRITY_PROBE`) and record which engine's verdict stood.
+///
+/// `bespoke` runs the primary probe and is skipped entirely under
+/// `canonical`. Under `both`, its corruption complaints go through
+/// [`reconcile_with_canonical`]. The startup probe, the integrity guard's
+/// quick and full cycles, and the mailbox verdict
+/// ([`integrity::inspect_mailbox_integrity`]) all come through here, so they
+/// cannot reach different conclusions about the same file.
+pub(crate) fn integrity_check_with_authority(
+    authority: integrity::IntegrityProbeAuthority,
+    path: &Path,
+    kind: integrity::CheckKind,
+    phase: &str,
+    bespoke: impl FnOnce() -> DbResult<integrity::IntegrityCheckResult>,
+) -> DbResult<integrity::IntegrityCheckResult> {
+    use integrity::{IntegrityProbeAuthority, IntegrityProbeSource};
+
+    match authority {
+        IntegrityProbeAuthority::Bespoke => {
+            let result = bespoke();
+            integrity::record_probe_verdict(authority, IntegrityProbeSource::Bespoke, None);
+            result
+        }
+        IntegrityProbeAuthority::Canonical => {
+            let result = sqlite_canonical_integrity_check(path, kind);
+            integrity::record_probe_verdict(authority, IntegrityProbeSource::Canonical, None);
+            result
+        }
+        IntegrityProbeAuthority::Both => {
+            reconcile_with_canonical(bespoke(), kind, phase, &path.display().to_string(), || {
+                sqlite_canonical_file_confirms_healthy(path)
+            })
+        }
+    }
+}
+
 /// Whether a primary-probe corruption complaint is the KNOWN frankensqlite
 /// `COLLATE NOCASE` index-order false positive (GH#185, upstream fsqlite#112):
 /// the primary `PRAGMA integrity_check` compares index leaf entries with raw
@@ -5028,7 +5084,9 @@ fn sqlite_canonical_incremental_check_is_ok(
 /// If the primary probe reports [`DbError::IntegrityCorruption`] but the
 /// `canonical_probe` closure proves the file is acceptable to canonical
 /// SQLite, the verdict is reclassified as healthy
-/// (`details = ["ok (canonical fallback)"]`). When canonical *also* rejects
+/// (`details = ["ok (canonical fallback)"]`), the integrity metrics are marked
+/// clean, and the complaint is kept as the recorded verdict's discrepancy
+/// ([`integrity::last_probe_verdict`]). When canonical *also* rejects
 /// the file, or the canonical probe cannot run, the original corruption
 /// verdict is preserved (fail-closed: we never silence a verdict both engines
 /// agree on, and we never invent health we could not confirm). `Ok` results
@@ -5051,8 +5109,18 @@ fn reconcile_with_canonical(
     path_for_log: &str,
     canonical_probe: impl FnOnce() -> Result<bool, SqlError>,
 ) -> DbResult<integrity::IntegrityCheckResult> {
+    let record_bespoke = || {
+        integrity::record_probe_verdict(
+            integrity::IntegrityProbeAuthority::Both,
+            integrity::IntegrityProbeSource::Bespoke,
+            None,
+        );
+    };
     match primary {
-        Ok(res) => Ok(res),
+        Ok(res) => {
+            record_bespoke();
+            Ok(res)
+        }
         Err(DbError::IntegrityCorruption { message, details }) => match canonical_probe() {
             Ok(true) => {
                 // GH#185: the `COLLATE NOCASE` index-order complaint (e.g.
@@ -5065,7 +5133,9 @@ fn reconcile_with_canonical(
                 // pollutes operator logs and erodes trust in the health
                 // signal, so classify it and log at INFO with an explicit
                 // explanation. Any OTHER divergence stays a WARN.
-                if is_known_nocase_index_order_false_positive(&message) {
+                if is_known_nocase_index_order_false_positive(&message)
+                    || integrity::integrity_details_are_index_shape_only(&details)
+                {
                     tracing::info!(
                         phase,
                         path = %path_for_log,
@@ -5084,6 +5154,11 @@ fn reconcile_with_canonical(
                         "integrity probe rejected the file but canonical SQLite accepted it; treating as healthy"
                     );
                 }
+                // Mark the mailbox clean again so the rejected bespoke check
+                // does not keep health surfaces (and with them the recovery
+                // mode) reading as corrupt; the complaint stays visible as
+                // the recorded discrepancy.
+                integrity::record_canonical_override(kind, message);
                 Ok(integrity::IntegrityCheckResult {
                     ok: true,
                     details: vec!["ok (canonical fallback)".to_string()],
@@ -5107,6 +5182,7 @@ fn reconcile_with_canonical(
                         "primary probe details (canonical also rejected)"
                     );
                 }
+                record_bespoke();
                 Err(DbError::IntegrityCorruption { message, details })
             }
             Err(canonical_error) => {
@@ -6129,6 +6205,199 @@ pub fn wal_checkpoint_truncate_path(db_path: &Path) -> DbResult<u64> {
     parse_wal_checkpoint_rows(&rows, "checkpoint", true)
 }
 
+/// WAL checkpoint mode used by [`compact_sqlite_path`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactCheckpoint {
+    /// Checkpoint everything and truncate the WAL to zero bytes.
+    #[default]
+    Truncate,
+    /// Checkpoint everything but leave the WAL file at its current size.
+    Full,
+}
+
+impl CompactCheckpoint {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Truncate => "truncate",
+            Self::Full => "full",
+        }
+    }
+
+    const fn pragma(self) -> &'static str {
+        match self {
+            Self::Truncate => "PRAGMA wal_checkpoint(TRUNCATE);",
+            Self::Full => "PRAGMA wal_checkpoint(FULL);",
+        }
+    }
+}
+
+/// How [`compact_sqlite_path`] reclaimed free pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactVacuum {
+    /// `VACUUM`: rebuilds the whole file.
+    Full,
+    /// `PRAGMA incremental_vacuum`: used when `auto_vacuum = INCREMENTAL`.
+    Incremental,
+}
+
+impl CompactVacuum {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Incremental => "incremental",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactOptions {
+    pub checkpoint: CompactCheckpoint,
+    pub vacuum: bool,
+    /// Measure and plan only; no checkpoint or vacuum runs.
+    pub dry_run: bool,
+}
+
+/// On-disk size of a SQLite database and its WAL.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SqliteFileStats {
+    pub main_bytes: u64,
+    pub wal_bytes: u64,
+    pub page_size: i64,
+    pub page_count: i64,
+    pub freelist_count: i64,
+}
+
+impl SqliteFileStats {
+    #[must_use]
+    pub const fn total_bytes(&self) -> u64 {
+        self.main_bytes.saturating_add(self.wal_bytes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactReport {
+    pub sqlite_path: String,
+    pub dry_run: bool,
+    pub checkpoint: CompactCheckpoint,
+    /// `None` when no vacuum was requested.
+    pub vacuum: Option<CompactVacuum>,
+    /// `none`, `full`, or `incremental`, from `PRAGMA auto_vacuum`.
+    pub auto_vacuum: String,
+    pub wal_frames_checkpointed: u64,
+    pub before: SqliteFileStats,
+    pub after: SqliteFileStats,
+}
+
+impl CompactReport {
+    /// Bytes freed across the main file and WAL (negative if they grew).
+    #[must_use]
+    pub fn bytes_reclaimed(&self) -> i64 {
+        let before = i64::try_from(self.before.total_bytes()).unwrap_or(i64::MAX);
+        let after = i64::try_from(self.after.total_bytes()).unwrap_or(i64::MAX);
+        before.saturating_sub(after)
+    }
+}
+
+fn first_column_i64(rows: &[sqlmodel_core::Row]) -> i64 {
+    match rows.first().and_then(|row| row.get(0)) {
+        Some(Value::BigInt(n)) => *n,
+        Some(Value::Int(n)) => i64::from(*n),
+        _ => 0,
+    }
+}
+
+fn sqlite_file_stats(conn: &crate::CanonicalDbConn, db_path: &Path) -> DbResult<SqliteFileStats> {
+    let pragma = |name: &str| -> DbResult<i64> {
+        let rows = conn
+            .query_sync(&format!("PRAGMA {name};"), &[])
+            .map_err(|e| DbError::Sqlite(format!("compact: {name}: {e}")))?;
+        Ok(first_column_i64(&rows))
+    };
+    let file_len = |path: &Path| std::fs::metadata(path).map_or(0, |meta| meta.len());
+    Ok(SqliteFileStats {
+        main_bytes: file_len(db_path),
+        wal_bytes: file_len(&sqlite_path_with_suffix(db_path, "-wal")),
+        page_size: pragma("page_size")?,
+        page_count: pragma("page_count")?,
+        freelist_count: pragma("freelist_count")?,
+    })
+}
+
+/// Checkpoint the WAL of the database at `db_path` and, when asked, vacuum
+/// it, reporting file sizes before and after.
+///
+/// Vacuum uses `PRAGMA incremental_vacuum` when the database was created with
+/// `auto_vacuum = INCREMENTAL` and a full `VACUUM` otherwise. In WAL mode a
+/// vacuum writes its rebuilt pages to the WAL, so the checkpoint runs again
+/// afterwards to move them into the main file. The caller must make sure no
+/// other process is writing: `VACUUM` needs an exclusive lock for as long as
+/// it takes to rewrite the file.
+pub fn compact_sqlite_path(db_path: &Path, options: CompactOptions) -> DbResult<CompactReport> {
+    if db_path.as_os_str() == ":memory:" {
+        return Err(DbError::Sqlite(
+            "compact: in-memory databases have nothing to compact".to_string(),
+        ));
+    }
+    let path_str = db_path.to_string_lossy();
+    let conn = open_sqlite_file_with_lock_retry_canonical(path_str.as_ref())
+        .map_err(|e| DbError::Sqlite(format!("compact: open failed: {e}")))?;
+    conn.execute_raw("PRAGMA busy_timeout = 60000;")
+        .map_err(|e| DbError::Sqlite(format!("compact: busy_timeout: {e}")))?;
+
+    let before = sqlite_file_stats(&conn, db_path)?;
+    let auto_vacuum = first_column_i64(
+        &conn
+            .query_sync("PRAGMA auto_vacuum;", &[])
+            .map_err(|e| DbError::Sqlite(format!("compact: auto_vacuum: {e}")))?,
+    );
+    let vacuum = options.vacuum.then_some(if auto_vacuum == 2 {
+        CompactVacuum::Incremental
+    } else {
+        CompactVacuum::Full
+    });
+    let mut report = CompactReport {
+        sqlite_path: path_str.into_owned(),
+        dry_run: options.dry_run,
+        checkpoint: options.checkpoint,
+        vacuum,
+        auto_vacuum: match auto_vacuum {
+            1 => "full",
+            2 => "incremental",
+            _ => "none",
+        }
+        .to_string(),
+        wal_frames_checkpointed: 0,
+        before,
+        after: before,
+    };
+    if options.dry_run {
+        return Ok(report);
+    }
+
+    let checkpoint = |context: &str| -> DbResult<u64> {
+        let rows = conn
+            .query_sync(options.checkpoint.pragma(), &[])
+            .map_err(|e| DbError::Sqlite(format!("{context}: {e}")))?;
+        parse_wal_checkpoint_rows(&rows, context, true)
+    };
+    report.wal_frames_checkpointed = checkpoint("compact checkpoint")?;
+    if let Some(vacuum) = vacuum {
+        let sql = match vacuum {
+            CompactVacuum::Full => "VACUUM;",
+            CompactVacuum::Incremental => "PRAGMA incremental_vacuum;",
+        };
+        conn.execute_raw(sql)
+            .map_err(|e| DbError::Sqlite(format!("compact: {sql} {e}")))?;
+        checkpoint("compact post-vacuum checkpoint")?;
+    }
+    report.after = sqlite_file_stats(&conn, db_path)?;
+    Ok(report)
+}
+
 fn is_real_directory(path: &Path) -> bool {
     std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_dir())
 }
@@ -10720,6 +10989,57 @@ mod tests {
         assert_eq!(frames, 0, "memory DB checkpoint should return 0");
     }
 
+    #[test]
+    fn compact_sqlite_path_vacuum_shrinks_file_after_deletes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("compact.db");
+        let conn = crate::CanonicalDbConn::open_file(db_path.display().to_string()).expect("open");
+        conn.execute_raw("PRAGMA journal_mode = WAL;").expect("wal");
+        conn.execute_raw("CREATE TABLE bulk (id INTEGER PRIMARY KEY, body TEXT NOT NULL)")
+            .expect("create");
+        let body = "x".repeat(4096);
+        for id in 0..500_i64 {
+            conn.execute_sync(
+                "INSERT INTO bulk (id, body) VALUES (?, ?)",
+                &[Value::BigInt(id), Value::Text(body.clone())],
+            )
+            .expect("insert");
+        }
+        conn.execute_raw("DELETE FROM bulk WHERE id >= 50")
+            .expect("delete");
+        drop(conn);
+
+        let dry = compact_sqlite_path(
+            &db_path,
+            CompactOptions {
+                vacuum: true,
+                dry_run: true,
+                ..CompactOptions::default()
+            },
+        )
+        .expect("dry run");
+        assert_eq!(dry.vacuum, Some(CompactVacuum::Full));
+        assert_eq!(dry.before, dry.after, "a dry run changes nothing");
+        assert_eq!(dry.wal_frames_checkpointed, 0);
+
+        let report = compact_sqlite_path(
+            &db_path,
+            CompactOptions {
+                vacuum: true,
+                ..CompactOptions::default()
+            },
+        )
+        .expect("compact");
+        assert_eq!(report.after.wal_bytes, 0, "truncate empties the WAL");
+        assert_eq!(report.after.freelist_count, 0);
+        assert!(
+            report.after.total_bytes() < report.before.total_bytes()
+                && report.after.page_count < report.before.page_count,
+            "vacuum should shrink the database: {report:?}"
+        );
+        assert!(report.bytes_reclaimed() > 0);
+    }
+
     fn sqlite_marker_value(path: &Path) -> Option<String> {
         let path_str = path.to_string_lossy();
         let conn = DbConn::open_file(path_str.as_ref()).ok()?;
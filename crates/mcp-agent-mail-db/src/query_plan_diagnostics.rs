@@ -2,6 +2,13 @@
 //!
 //! This module intentionally runs only on explicit diagnostic surfaces. It
 //! never sits on the production query hot path.
+//!
+//! Every [`HotQueryPath`] must map to a [`HotQuerySpec`] in [`spec_for`]; the
+//! match is exhaustive, so a new hot path cannot be registered without its
+//! index expectations. Where possible a spec explains the exact statement the
+//! query module issues rather than a copy of it. The `query_plan_guard`
+//! integration test (also an `am ci` gate) fails when any registered plan
+//! regresses to a scan.
 
 use crate::DbConn;
 use crate::queries::UNKNOWN_SENDER_DISPLAY;
@@ -9,6 +16,7 @@ use crate::search_planner::{PlanParam, SearchQuery, plan_search};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use sqlmodel_core::Value;
+use std::fmt::Write as _;
 
 const PLAN_HASH_SCHEMA_VERSION: &str = "query-plan-diagnostics:v1";
 const DEFAULT_LIMIT: i64 = 20;
@@ -20,9 +28,24 @@ pub enum HotQueryPath {
     ProductInbox,
     ActiveReservations,
     SearchFallback,
+    AckPending,
+    ReservationConflicts,
+    WatermarkPolling,
+    AgentLookup,
 }
 
 impl HotQueryPath {
+    pub const ALL: [Self; 8] = [
+        Self::Inbox,
+        Self::ProductInbox,
+        Self::ActiveReservations,
+        Self::SearchFallback,
+        Self::AckPending,
+        Self::ReservationConflicts,
+        Self::WatermarkPolling,
+        Self::AgentLookup,
+    ];
+
     #[must_use]
     pub const fn as_str(self) -> &'static str {
         match self {
@@ -30,6 +53,10 @@ impl HotQueryPath {
             Self::ProductInbox => "product_inbox",
             Self::ActiveReservations => "active_reservations",
             Self::SearchFallback => "search_fallback",
+            Self::AckPending => "ack_pending",
+            Self::ReservationConflicts => "reservation_conflicts",
+            Self::WatermarkPolling => "watermark_polling",
+            Self::AgentLookup => "agent_lookup",
         }
     }
 }
@@ -65,6 +92,8 @@ pub struct QueryPlanDiagnostic {
     pub detail: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_indexes: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub missing_expected_indexes: Vec<String>,
     pub steps: Vec<QueryPlanStep>,
@@ -77,6 +106,67 @@ pub struct QueryPlanSummary {
     pub diagnostics: Vec<QueryPlanDiagnostic>,
 }
 
+impl QueryPlanSummary {
+    /// Expected-vs-actual report for every path whose plan lost an expected
+    /// index or scans a sensitive table, or `None` when all plans hold.
+    ///
+    /// Offending plan lines are prefixed with `!`; expected index groups the
+    /// plan no longer uses are prefixed with `-`.
+    #[must_use]
+    pub fn regression_report(&self) -> Option<String> {
+        let regressed = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.status != "ok")
+            .collect::<Vec<_>>();
+        if regressed.is_empty() {
+            return None;
+        }
+        let mut report = format!(
+            "{} of {} hot query plan(s) regressed\n",
+            regressed.len(),
+            self.diagnostics.len()
+        );
+        for diagnostic in regressed {
+            let _ = write!(report, "\n== {} ==\nexpected indexes:\n", diagnostic.path);
+            for expected in &diagnostic.expected_indexes {
+                let label = expected.split(" via ").next().unwrap_or(expected);
+                let marker = if diagnostic
+                    .missing_expected_indexes
+                    .iter()
+                    .any(|missing| missing == label)
+                {
+                    '-'
+                } else {
+                    ' '
+                };
+                let _ = writeln!(report, "  {marker} {expected}");
+            }
+            report.push_str("actual plan:\n");
+            let flagged = diagnostic
+                .warnings
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            for step in &diagnostic.steps {
+                let normalized = step
+                    .detail
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .to_ascii_lowercase();
+                let marker = if flagged.contains(&normalized.as_str()) {
+                    '!'
+                } else {
+                    ' '
+                };
+                let _ = writeln!(report, "  {marker} {}", step.detail);
+            }
+        }
+        Some(report)
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum QueryPlanError {
     #[error("query-plan explain failed for {path}: {source}")]
@@ -127,12 +217,22 @@ pub fn search_fallback_plan_diagnostic(
 }
 
 fn default_hot_query_specs() -> Vec<HotQuerySpec> {
-    vec![
-        inbox_spec(),
-        product_inbox_spec(),
-        active_reservations_spec(),
-        search_fallback_spec(&SearchQuery::messages("needle".to_string(), 1)),
-    ]
+    HotQueryPath::ALL.into_iter().map(spec_for).collect()
+}
+
+fn spec_for(path: HotQueryPath) -> HotQuerySpec {
+    match path {
+        HotQueryPath::Inbox => inbox_spec(),
+        HotQueryPath::ProductInbox => product_inbox_spec(),
+        HotQueryPath::ActiveReservations => active_reservations_spec(),
+        HotQueryPath::SearchFallback => {
+            search_fallback_spec(&SearchQuery::messages("needle".to_string(), 1))
+        }
+        HotQueryPath::AckPending => ack_pending_spec(),
+        HotQueryPath::ReservationConflicts => reservation_conflicts_spec(),
+        HotQueryPath::WatermarkPolling => watermark_polling_spec(),
+        HotQueryPath::AgentLookup => agent_lookup_spec(),
+    }
 }
 
 fn explain_query_plan(
@@ -199,6 +299,17 @@ fn explain_query_plan(
         plan_hash: plan_hash(spec.path, &steps),
         detail,
         warnings,
+        expected_indexes: spec
+            .expected_indexes
+            .iter()
+            .map(|expectation| {
+                format!(
+                    "{} via {}",
+                    expectation.label,
+                    expectation.alternatives.join(" | ")
+                )
+            })
+            .collect(),
         missing_expected_indexes,
         steps,
     })
@@ -296,31 +407,15 @@ fn plan_hash(path: HotQueryPath, steps: &[QueryPlanStep]) -> String {
 }
 
 fn inbox_spec() -> HotQuerySpec {
+    let (sql, params) = crate::sync::inbox_latest_query(1, 1, DEFAULT_LIMIT);
     HotQuerySpec {
         path: HotQueryPath::Inbox,
-        sql: format!(
-            "SELECT m.id, m.project_id, m.sender_id, m.thread_id, m.subject, '' AS body_md, \
-                    m.importance, m.ack_required, m.created_ts, m.recipients_json, m.attachments, \
-                    r.kind, COALESCE(s.name, '{UNKNOWN_SENDER_DISPLAY}') AS sender_name, r.read_ts, r.ack_ts \
-             FROM message_recipients r \
-             JOIN messages m ON m.id = r.message_id \
-             LEFT JOIN agents s ON s.id = m.sender_id \
-             WHERE r.agent_id = ? AND m.project_id = ? \
-             ORDER BY m.created_ts DESC LIMIT ?"
-        ),
-        params: vec![
-            Value::BigInt(1),
-            Value::BigInt(1),
-            Value::BigInt(DEFAULT_LIMIT),
-        ],
+        sql,
+        params,
         expected_indexes: vec![
             IndexExpectation {
                 label: "message_recipients(agent_id)",
-                alternatives: &[
-                    "idx_message_recipients_agent",
-                    "idx_message_recipients_agent_message",
-                    "idx_mr_agent_ack",
-                ],
+                alternatives: MESSAGE_RECIPIENT_AGENT_INDEXES,
             },
             IndexExpectation {
                 label: "messages primary key",
@@ -365,11 +460,7 @@ fn product_inbox_spec() -> HotQuerySpec {
             },
             IndexExpectation {
                 label: "message_recipients(agent_id)",
-                alternatives: &[
-                    "idx_message_recipients_agent",
-                    "idx_message_recipients_agent_message",
-                    "idx_mr_agent_ack",
-                ],
+                alternatives: MESSAGE_RECIPIENT_AGENT_INDEXES,
             },
         ],
         scan_sensitive_sources: &[
@@ -430,6 +521,88 @@ fn search_fallback_spec(query: &SearchQuery) -> HotQuerySpec {
     }
 }
 
+const MESSAGE_RECIPIENT_AGENT_INDEXES: &[&str] = &[
+    "idx_message_recipients_agent",
+    "idx_message_recipients_agent_message",
+    "idx_mr_agent_ack",
+];
+
+fn ack_pending_spec() -> HotQuerySpec {
+    HotQuerySpec {
+        path: HotQueryPath::AckPending,
+        sql: crate::queries::unacked_for_agent_sql(),
+        params: vec![
+            Value::BigInt(1),
+            Value::BigInt(1),
+            Value::BigInt(DEFAULT_LIMIT),
+        ],
+        expected_indexes: vec![
+            IndexExpectation {
+                label: "message_recipients(agent_id, ack_ts)",
+                alternatives: MESSAGE_RECIPIENT_AGENT_INDEXES,
+            },
+            IndexExpectation {
+                label: "messages primary key",
+                alternatives: &["integer primary key", "primary key"],
+            },
+        ],
+        scan_sensitive_sources: &["r", "message_recipients", "m", "messages"],
+    }
+}
+
+fn reservation_conflicts_spec() -> HotQuerySpec {
+    HotQuerySpec {
+        path: HotQueryPath::ReservationConflicts,
+        sql: crate::queries::reservation_conflict_scan_sql(),
+        params: vec![
+            Value::BigInt(1),
+            Value::BigInt(1_700_000_000_000_000),
+            Value::BigInt(DEFAULT_LIMIT + 1),
+        ],
+        expected_indexes: vec![IndexExpectation {
+            label: "file_reservations(project_id, released_ts, expires_ts)",
+            alternatives: &[
+                "idx_file_reservations_project_released_expires",
+                "idx_file_reservations_project_agent_released",
+            ],
+        }],
+        scan_sensitive_sources: &["fr", "file_reservations"],
+    }
+}
+
+fn watermark_polling_spec() -> HotQuerySpec {
+    let (sql, params) = crate::sync::inbox_watermark_poll_query(1, 1, 0, DEFAULT_LIMIT);
+    HotQuerySpec {
+        path: HotQueryPath::WatermarkPolling,
+        sql,
+        params,
+        expected_indexes: vec![
+            IndexExpectation {
+                label: "message_recipients(agent_id)",
+                alternatives: MESSAGE_RECIPIENT_AGENT_INDEXES,
+            },
+            IndexExpectation {
+                label: "messages primary key",
+                alternatives: &["integer primary key", "primary key"],
+            },
+        ],
+        scan_sensitive_sources: &["r", "message_recipients", "m", "messages"],
+    }
+}
+
+fn agent_lookup_spec() -> HotQuerySpec {
+    HotQuerySpec {
+        path: HotQueryPath::AgentLookup,
+        sql: crate::queries::AGENT_BY_NAME_SQL.to_string(),
+        params: vec![Value::BigInt(1), Value::Text("BlueLake".to_string())],
+        expected_indexes: vec![IndexExpectation {
+            label: "agents(project_id, name)",
+            alternatives: &["idx_agents_project_name", "sqlite_autoindex_agents"],
+        }],
+        scan_sensitive_sources: &["agents"],
+    }
+}
+
 fn plan_param_to_value(param: PlanParam) -> Value {
     match param {
         PlanParam::Int(value) => Value::BigInt(value),
@@ -476,19 +649,17 @@ mod tests {
         let conn = test_conn();
         let summary = summarize_hot_query_plans(&conn).expect("summarize hot plans");
 
-        assert_eq!(summary.diagnostics.len(), 4);
+        assert_eq!(summary.diagnostics.len(), HotQueryPath::ALL.len());
         assert_eq!(
             summary
                 .diagnostics
                 .iter()
                 .map(|diagnostic| diagnostic.path.as_str())
                 .collect::<Vec<_>>(),
-            vec![
-                "inbox",
-                "product_inbox",
-                "active_reservations",
-                "search_fallback"
-            ]
+            HotQueryPath::ALL
+                .iter()
+                .map(|path| path.as_str())
+                .collect::<Vec<_>>()
         );
         assert!(
             summary
@@ -594,6 +765,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn regression_report_flags_scans_and_missing_indexes() {
+        let conn = test_conn();
+        let spec = HotQuerySpec {
+            path: HotQueryPath::WatermarkPolling,
+            sql: "SELECT m.id FROM messages m WHERE m.body_md LIKE ? LIMIT ?".to_string(),
+            params: vec![
+                Value::Text("%needle%".to_string()),
+                Value::BigInt(DEFAULT_LIMIT),
+            ],
+            expected_indexes: vec![IndexExpectation {
+                label: "messages(project_id, created_ts)",
+                alternatives: &["idx_messages_project_created"],
+            }],
+            scan_sensitive_sources: &["m", "messages"],
+        };
+        let regressed = explain_query_plan(&conn, &spec).expect("explain scan query");
+        let healthy = explain_query_plan(&conn, &agent_lookup_spec()).expect("agent lookup");
+        let summary = QueryPlanSummary {
+            status: "warn".to_string(),
+            detail: String::new(),
+            diagnostics: vec![healthy, regressed],
+        };
+
+        let report = summary.regression_report().expect("regression report");
+        assert!(
+            report.starts_with("1 of 2 hot query plan(s) regressed"),
+            "{report}"
+        );
+        assert!(report.contains("== watermark_polling =="), "{report}");
+        assert!(!report.contains("== agent_lookup =="), "{report}");
+        assert!(
+            report.contains("- messages(project_id, created_ts) via idx_messages_project_created"),
+            "{report}"
+        );
+        assert!(
+            report
+                .lines()
+                .any(|line| line.starts_with("  ! ") && line.to_ascii_lowercase().contains("scan")),
+            "{report}"
+        );
+    }
+
     #[test]
     fn plan_hash_changes_when_plan_shape_changes() {
         let conn = test_conn();
@@ -21,6 +21,7 @@
 
 use crate::error::{DbError, DbResult};
 use crate::schema;
+use mcp_agent_mail_core::archive_location::project_archive_dirs;
 use serde::Serialize;
 use sqlmodel_core::{Error as SqlError, Value};
 use sqlmodel_schema::Migration;
@@ -767,23 +768,7 @@ impl ArchiveDriftReport {
 pub fn scan_archive_message_ids(storage_root: &Path) -> (BTreeSet<i64>, usize) {
     let mut ids = BTreeSet::new();
     let mut parse_errors: usize = 0;
-    let projects_dir = storage_root.join("projects");
-    if !is_real_directory(&projects_dir) {
-        return (ids, parse_errors);
-    }
-
-    let Ok(project_entries) = std::fs::read_dir(&projects_dir) else {
-        return (ids, parse_errors);
-    };
-
-    for entry in project_entries.flatten() {
-        let path = entry.path();
-        let Ok(file_type) = entry.file_type() else {
-            continue;
-        };
-        if !file_type.is_dir() || file_type.is_symlink() {
-            continue;
-        }
+    for (_slug, path) in project_archive_dirs(storage_root) {
         collect_project_archive_message_ids(&path.join("messages"), &mut ids, &mut parse_errors);
     }
 
@@ -1118,26 +1103,10 @@ pub fn collect_db_project_identities(
 #[must_use]
 pub fn scan_archive_message_inventory(storage_root: &Path) -> ArchiveMessageInventory {
     let mut inventory = ArchiveMessageInventory::default();
-    let projects_dir = storage_root.join("projects");
-    if !is_real_directory(&projects_dir) {
-        return inventory;
-    }
-
-    let Ok(project_entries) = std::fs::read_dir(&projects_dir) else {
-        return inventory;
-    };
-
     let mut seen_ids = BTreeSet::new();
     let mut duplicate_ids = BTreeSet::new();
 
-    for entry in project_entries.flatten() {
-        let path = entry.path();
-        let Ok(file_type) = entry.file_type() else {
-            continue;
-        };
-        if !file_type.is_dir() || file_type.is_symlink() {
-            continue;
-        }
+    for (_slug, path) in project_archive_dirs(storage_root) {
         inventory.projects += 1;
         if let Some(identity) = scan_archive_project_identity(&path) {
             inventory.project_identities.insert(identity);
@@ -1381,24 +1350,9 @@ fn reconstruct_from_archive_impl(
     let projects_dir = storage_root.join("projects");
     let mut project_dirs: Vec<(String, PathBuf)> = Vec::new();
     if is_real_directory(storage_root) {
-        if is_real_directory(&projects_dir) {
-            if let Ok(entries) = std::fs::read_dir(&projects_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    let Ok(file_type) = entry.file_type() else {
-                        continue;
-                    };
-                    if !file_type.is_dir() || file_type.is_symlink() {
-                        continue;
-                    }
-                    let Some(slug) = path.file_name().and_then(|n| n.to_str()).map(String::from)
-                    else {
-                        continue;
-                    };
-                    project_dirs.push((slug, path));
-                }
-            }
-        } else {
+        // Includes projects moved elsewhere by `am archive relocate`.
+        project_dirs = project_archive_dirs(storage_root);
+        if !is_real_directory(&projects_dir) && project_dirs.is_empty() {
             stats.warnings.push(format!(
                 "No projects directory found at {}",
                 projects_dir.display()
@@ -1416,7 +1370,6 @@ fn reconstruct_from_archive_impl(
             return Ok(stats);
         }
     }
-    project_dirs.sort_by(|a, b| a.0.cmp(&b.0));
     if project_dirs.is_empty() {
         stats.warnings.push(format!(
             "No project archives found under {}",
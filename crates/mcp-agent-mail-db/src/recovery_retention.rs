@@ -14,13 +14,26 @@
 //!   excess into one reversible `doctor/reclaimable/<ts>/` directory (a
 //!   rename, never a delete — matching the doctor's quarantine philosophy).
 //!
+//! Quarantined corrupt-DB siblings additionally have a DELETE path, grouped
+//! per recovery event ([`QuarantineSet`]): the operator's `am doctor
+//! quarantine prune`, and an opt-in pruning pass in the same maintenance
+//! sweep. Both go through [`delete_quarantine_sets`], which refuses to touch
+//! anything unless a healthy main database exists. Forensic bundles are never
+//! deleted.
+//!
+//! Database backups in the doctor backup directory follow a similar delete
+//! path through [`select_backups_to_prune`], which never selects the newest
+//! healthy backup. `am doctor backups prune` is the only caller today: the
+//! server's verified-snapshot routine refreshes a single rolling `.bak` in
+//! place and so has nothing to prune.
+//!
 //! The selection logic ([`select_recovery_debris_to_reclaim`]) is PURE so it is
 //! exhaustively unit-testable; the filesystem enumeration and the move are
 //! thin IO wrappers around it.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::UNIX_EPOCH;
+use std::time::{Duration, UNIX_EPOCH};
 
 /// Which kind of recovery debris an artifact is. Retention is applied
 /// independently per category so a burst of one kind cannot evict the other.
@@ -326,6 +339,268 @@ fn unique_dest_path(dest_dir: &Path, name: &std::ffi::OsStr) -> PathBuf {
     dest_dir.join(name)
 }
 
+/// One recovery event's quarantined files: the main DB plus any `-wal`/`-shm`
+/// siblings renamed with the same stamp.
+#[derive(Debug, Clone)]
+pub struct QuarantineSet {
+    /// Quarantine stamp shared by the set, e.g. `corrupt-20260618_145230_042`.
+    pub stamp: String,
+    pub files: Vec<DebrisArtifact>,
+    pub bytes: u64,
+    /// Newest last-modified time across the set's files.
+    pub modified_us: i64,
+}
+
+/// Retention for quarantine sets: always keep the `keep_latest` newest sets
+/// and any set younger than `min_age_secs`.
+#[derive(Debug, Clone, Copy)]
+pub struct QuarantinePrunePolicy {
+    pub keep_latest: usize,
+    pub min_age_secs: u64,
+}
+
+/// Sets split by [`select_quarantine_sets_to_prune`].
+#[derive(Debug, Clone, Default)]
+pub struct QuarantinePrunePlan {
+    /// Retained sets, newest-first.
+    pub keep: Vec<QuarantineSet>,
+    /// Sets to delete, oldest-first.
+    pub prune: Vec<QuarantineSet>,
+    pub prune_bytes: u64,
+}
+
+/// Outcome of [`delete_quarantine_sets`].
+#[derive(Debug, Clone, Default)]
+pub struct QuarantineDeleteOutcome {
+    pub deleted: usize,
+    pub deleted_bytes: u64,
+    /// `(path, error_message)` for files that could not be removed.
+    pub failures: Vec<(PathBuf, String)>,
+}
+
+/// The stamp that ties a quarantine file to its recovery event: everything
+/// after the quarantine marker, so `storage.sqlite3-wal.corrupt-<ts>` and
+/// `storage.sqlite3.corrupt-<ts>` share `corrupt-<ts>`.
+#[must_use]
+pub fn quarantine_stamp(name: &str) -> Option<&str> {
+    [
+        ".corrupt-",
+        ".reconstruct-failed-",
+        ".archive-reconcile-restore-",
+    ]
+    .iter()
+    .filter_map(|marker| name.find(marker))
+    .min()
+    .map(|idx| &name[idx + 1..])
+}
+
+/// PURE: group [`DebrisCategory::CorruptQuarantine`] artifacts into per-event
+/// sets, newest-first. Other categories are ignored.
+#[must_use]
+pub fn group_quarantine_sets(artifacts: Vec<DebrisArtifact>) -> Vec<QuarantineSet> {
+    let mut by_stamp: HashMap<String, QuarantineSet> = HashMap::new();
+    for art in artifacts {
+        if art.category != DebrisCategory::CorruptQuarantine {
+            continue;
+        }
+        let Some(stamp) = art
+            .path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(quarantine_stamp)
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let set = by_stamp
+            .entry(stamp.clone())
+            .or_insert_with(|| QuarantineSet {
+                stamp,
+                files: Vec::new(),
+                bytes: 0,
+                modified_us: 0,
+            });
+        set.bytes = set.bytes.saturating_add(art.bytes);
+        set.modified_us = set.modified_us.max(art.modified_us);
+        set.files.push(art);
+    }
+    let mut sets: Vec<QuarantineSet> = by_stamp.into_values().collect();
+    for set in &mut sets {
+        set.files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    sets.sort_by(|a, b| {
+        b.modified_us
+            .cmp(&a.modified_us)
+            .then_with(|| b.stamp.cmp(&a.stamp))
+    });
+    sets
+}
+
+/// PURE: choose which quarantine sets to delete under `policy`.
+///
+/// A set is pruned only when it is BOTH beyond the `keep_latest` newest AND at
+/// least `min_age_secs` old. The prune list is ordered oldest-first.
+#[must_use]
+pub fn select_quarantine_sets_to_prune(
+    mut sets: Vec<QuarantineSet>,
+    policy: QuarantinePrunePolicy,
+    now_us: i64,
+) -> QuarantinePrunePlan {
+    sets.sort_by_key(|set| std::cmp::Reverse(set.modified_us));
+    let min_age_us = i128::from(policy.min_age_secs).saturating_mul(1_000_000);
+    let mut plan = QuarantinePrunePlan::default();
+    for (rank, set) in sets.into_iter().enumerate() {
+        let age_us = i128::from(now_us).saturating_sub(i128::from(set.modified_us));
+        if rank < policy.keep_latest || age_us < min_age_us {
+            plan.keep.push(set);
+        } else {
+            plan.prune_bytes = plan.prune_bytes.saturating_add(set.bytes);
+            plan.prune.push(set);
+        }
+    }
+    plan.prune.reverse();
+    plan
+}
+
+/// Whether a usable main database sits at `db_path`: a regular, non-empty file
+/// carrying the SQLite header. Quarantined copies are the only fallback when
+/// it is not, so callers must never delete them in that state.
+#[must_use]
+pub fn healthy_main_db_exists(db_path: &Path) -> bool {
+    use std::io::Read;
+    let Ok(meta) = std::fs::symlink_metadata(db_path) else {
+        return false;
+    };
+    if !meta.file_type().is_file() || meta.len() == 0 {
+        return false;
+    }
+    let Ok(mut file) = std::fs::File::open(db_path) else {
+        return false;
+    };
+    let mut header = [0_u8; 16];
+    file.read_exact(&mut header).is_ok() && &header == b"SQLite format 3\0"
+}
+
+/// Delete the files of `sets`, which must be quarantine siblings of `db_path`.
+///
+/// Refuses (returns an error, touching nothing) unless
+/// [`healthy_main_db_exists`] holds for `db_path`. Files outside the
+/// database's directory or without a quarantine stamp are reported as
+/// failures rather than removed.
+pub fn delete_quarantine_sets(
+    db_path: &Path,
+    sets: &[QuarantineSet],
+) -> std::io::Result<QuarantineDeleteOutcome> {
+    if !healthy_main_db_exists(db_path) {
+        return Err(std::io::Error::other(format!(
+            "no healthy main database at {}; refusing to delete quarantined files",
+            db_path.display()
+        )));
+    }
+    let parent = db_path.parent();
+    let mut outcome = QuarantineDeleteOutcome::default();
+    for art in sets.iter().flat_map(|set| &set.files) {
+        let is_sibling = art.path.parent() == parent
+            && art
+                .path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(quarantine_stamp)
+                .is_some();
+        if !is_sibling {
+            outcome.failures.push((
+                art.path.clone(),
+                "not a quarantine sibling of the database".to_string(),
+            ));
+            continue;
+        }
+        match std::fs::remove_file(&art.path) {
+            Ok(()) => {
+                outcome.deleted += 1;
+                outcome.deleted_bytes = outcome.deleted_bytes.saturating_add(art.bytes);
+            }
+            Err(err) => outcome.failures.push((art.path.clone(), err.to_string())),
+        }
+    }
+    Ok(outcome)
+}
+
+/// One database backup file, e.g. `storage.sqlite3.bak.<ts>` in the doctor
+/// backup directory.
+#[derive(Debug, Clone)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+    /// Last-modified time in microseconds since the Unix epoch (`0` if unknown).
+    pub modified_us: i64,
+}
+
+/// Retention for database backups: keep the `keep_last` newest and anything
+/// younger than `keep_for`. An unset bound retains nothing on its own.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BackupPrunePolicy {
+    pub keep_last: Option<usize>,
+    pub keep_for: Option<Duration>,
+}
+
+/// Backups split by [`select_backups_to_prune`].
+#[derive(Debug, Clone, Default)]
+pub struct BackupPrunePlan {
+    /// Retained backups, newest-first.
+    pub keep: Vec<BackupFile>,
+    /// Backups to delete, oldest-first.
+    pub prune: Vec<BackupFile>,
+    pub prune_bytes: u64,
+    /// Newest backup that passed `is_healthy`; always in `keep`.
+    pub newest_healthy: Option<PathBuf>,
+}
+
+/// Choose which backups to delete under `policy`.
+///
+/// Every backup outside the policy is pruned, except the newest one for which
+/// `is_healthy` holds: it is kept even when the policy would drop it, so a
+/// prune can never leave the operator without a restorable backup. Health is
+/// probed newest-first and stops at the first healthy backup. The prune list
+/// is ordered oldest-first.
+#[must_use]
+pub fn select_backups_to_prune(
+    mut backups: Vec<BackupFile>,
+    policy: BackupPrunePolicy,
+    now_us: i64,
+    mut is_healthy: impl FnMut(&Path) -> bool,
+) -> BackupPrunePlan {
+    backups.sort_by(|a, b| {
+        b.modified_us
+            .cmp(&a.modified_us)
+            .then_with(|| b.path.cmp(&a.path))
+    });
+    let newest_healthy = backups
+        .iter()
+        .find(|backup| is_healthy(&backup.path))
+        .map(|backup| backup.path.clone());
+    let max_age_us = policy
+        .keep_for
+        .map(|age| i128::try_from(age.as_micros()).unwrap_or(i128::MAX));
+    let mut plan = BackupPrunePlan {
+        newest_healthy,
+        ..BackupPrunePlan::default()
+    };
+    for (rank, backup) in backups.into_iter().enumerate() {
+        let age_us = i128::from(now_us).saturating_sub(i128::from(backup.modified_us));
+        let retained = policy.keep_last.is_some_and(|keep| rank < keep)
+            || max_age_us.is_some_and(|max_age| age_us < max_age)
+            || plan.newest_healthy.as_deref() == Some(backup.path.as_path());
+        if retained {
+            plan.keep.push(backup);
+        } else {
+            plan.prune_bytes = plan.prune_bytes.saturating_add(backup.bytes);
+            plan.prune.push(backup);
+        }
+    }
+    plan.prune.reverse();
+    plan
+}
+
 /// Recursive on-disk byte total for a directory, without following symlinks
 /// (so a symlinked entry contributes nothing and cannot cause a cycle).
 #[must_use]
@@ -703,4 +978,130 @@ mod tests {
                 .exists()
         );
     }
+
+    #[test]
+    fn quarantine_sets_group_sidecars_and_prune_oldest_beyond_policy() {
+        let now = 100 * DAY_US;
+        let q = |name: &str, bytes: u64, age_days: i64| {
+            art(
+                name,
+                bytes,
+                now - age_days * DAY_US,
+                DebrisCategory::CorruptQuarantine,
+            )
+        };
+        let artifacts = vec![
+            q("storage.sqlite3.corrupt-20260101_000000_000", 100, 60),
+            q("storage.sqlite3-wal.corrupt-20260101_000000_000", 10, 60),
+            q("storage.sqlite3.corrupt-20260201_000000_000", 100, 40),
+            q("storage.sqlite3.corrupt-20260301_000000_000", 100, 20),
+            q("storage.sqlite3.corrupt-20260320_000000_000", 100, 5),
+            art("b1", 10, now - 90 * DAY_US, DebrisCategory::ForensicBundle),
+        ];
+        let sets = group_quarantine_sets(artifacts);
+        assert_eq!(sets.len(), 4, "forensic bundles are not quarantine sets");
+        assert_eq!(sets[0].stamp, "corrupt-20260320_000000_000");
+        assert_eq!(sets[3].files.len(), 2, "wal sidecar joins its event");
+        assert_eq!(sets[3].bytes, 110);
+
+        let plan = select_quarantine_sets_to_prune(
+            sets,
+            QuarantinePrunePolicy {
+                keep_latest: 1,
+                min_age_secs: 30 * 24 * 3_600,
+            },
+            now,
+        );
+        // Newest kept by count, 20-day-old kept by age, the two older pruned.
+        assert_eq!(plan.keep.len(), 2);
+        assert_eq!(plan.prune.len(), 2);
+        assert_eq!(plan.prune[0].stamp, "corrupt-20260101_000000_000");
+        assert_eq!(plan.prune_bytes, 210);
+    }
+
+    #[test]
+    fn backup_prune_honours_policy_and_keeps_newest_healthy() {
+        let now = 100 * DAY_US;
+        let backup = |name: &str, age_days: i64| BackupFile {
+            path: PathBuf::from(name),
+            bytes: 100,
+            modified_us: now - age_days * DAY_US,
+        };
+        let backups = vec![
+            backup("storage.sqlite3.bak.a", 40),
+            backup("storage.sqlite3.bak.b", 20),
+            backup("storage.sqlite3.bak.c", 10),
+            backup("storage.sqlite3.bak.d", 1),
+        ];
+
+        // Only the oldest is healthy: it survives even though the policy drops it.
+        let plan = select_backups_to_prune(
+            backups.clone(),
+            BackupPrunePolicy {
+                keep_last: Some(1),
+                keep_for: Some(Duration::from_secs(15 * 86_400)),
+            },
+            now,
+            |path| path == Path::new("storage.sqlite3.bak.a"),
+        );
+        assert_eq!(
+            plan.newest_healthy.as_deref(),
+            Some(Path::new("storage.sqlite3.bak.a"))
+        );
+        assert_eq!(plan.keep.len(), 3);
+        assert_eq!(plan.prune.len(), 1);
+        assert_eq!(plan.prune[0].path, PathBuf::from("storage.sqlite3.bak.b"));
+        assert_eq!(plan.prune_bytes, 100);
+
+        // Healthy newest: count-only policy prunes the rest oldest-first.
+        let mut probed = 0;
+        let plan = select_backups_to_prune(
+            backups,
+            BackupPrunePolicy {
+                keep_last: Some(2),
+                keep_for: None,
+            },
+            now,
+            |_| {
+                probed += 1;
+                true
+            },
+        );
+        assert_eq!(
+            probed, 1,
+            "health probing stops at the first healthy backup"
+        );
+        assert_eq!(plan.keep.len(), 2);
+        assert_eq!(plan.prune[0].path, PathBuf::from("storage.sqlite3.bak.a"));
+        assert_eq!(plan.prune[1].path, PathBuf::from("storage.sqlite3.bak.b"));
+    }
+
+    #[test]
+    fn delete_quarantine_sets_requires_healthy_main_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("storage.sqlite3");
+        let quarantined = dir
+            .path()
+            .join("storage.sqlite3.corrupt-20260101_000000_000");
+        std::fs::write(&quarantined, vec![0_u8; 64]).unwrap();
+        let sets = group_quarantine_sets(enumerate_corrupt_quarantines(&db_path));
+        assert_eq!(sets.len(), 1);
+
+        // Missing main DB: refuse and keep the quarantine.
+        assert!(delete_quarantine_sets(&db_path, &sets).is_err());
+        // A non-SQLite main file is not healthy either.
+        std::fs::write(&db_path, b"garbage").unwrap();
+        assert!(!healthy_main_db_exists(&db_path));
+        assert!(delete_quarantine_sets(&db_path, &sets).is_err());
+        assert!(quarantined.exists());
+
+        let mut header = b"SQLite format 3\0".to_vec();
+        header.resize(4096, 0);
+        std::fs::write(&db_path, header).unwrap();
+        let outcome = delete_quarantine_sets(&db_path, &sets).unwrap();
+        assert_eq!(outcome.deleted, 1);
+        assert_eq!(outcome.deleted_bytes, 64);
+        assert!(!quarantined.exists());
+        assert!(db_path.exists());
+    }
 }
@@ -29,7 +29,8 @@ CREATE TABLE IF NOT EXISTS products (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
     product_uid TEXT NOT NULL UNIQUE,
     name TEXT NOT NULL UNIQUE,
-    created_at INTEGER NOT NULL
+    created_at INTEGER NOT NULL,
+    archive_root TEXT
 );
 CREATE INDEX IF NOT EXISTS idx_products_uid ON products(product_uid);
 CREATE INDEX IF NOT EXISTS idx_products_name ON products(name);
@@ -123,6 +124,21 @@ CREATE TABLE IF NOT EXISTS file_reservation_releases (
 );
 CREATE INDEX IF NOT EXISTS idx_file_reservation_releases_ts ON file_reservation_releases(released_ts);
 
+-- Lease-token fences for `--fenced` reservations (hash only) and the audit
+-- log of operator overrides
+CREATE TABLE IF NOT EXISTS file_reservation_fences (
+    reservation_id INTEGER PRIMARY KEY REFERENCES file_reservations(id),
+    lease_token_hash TEXT NOT NULL,
+    created_ts INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS file_reservation_fence_overrides (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    reservation_id INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    operator TEXT NOT NULL,
+    created_ts INTEGER NOT NULL
+);
+
 -- Agent links (contact relationships)
 CREATE TABLE IF NOT EXISTS agent_links (
     id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -2132,6 +2148,591 @@ pub fn schema_migrations() -> Vec<Migration> {
         String::new(),
     ));
 
+    // ── v25: message expiry sidecar ────────────────────────────────────
+    //
+    // Optional TTL for ephemeral status messages. Kept as a sidecar keyed by
+    // message id (mirrors `file_reservation_releases`) so the hot `messages`
+    // row shape and every existing INSERT/SELECT stay untouched; messages
+    // without a TTL simply have no row here.
+    migrations.push(Migration::new(
+        "v25_create_message_expiries".to_string(),
+        "create sidecar expiry ledger for messages".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_expiries (\
+            message_id INTEGER PRIMARY KEY REFERENCES messages(id),\
+            expires_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v25b_idx_message_expiries_expires_ts".to_string(),
+        "index on message_expiries.expires_ts for the purge sweep".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_message_expiries_expires_ts \
+            ON message_expiries(expires_ts)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v25c_trg_messages_cascade_expiries".to_string(),
+        "cascade-delete message_expiries when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_expiries \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM message_expiries WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v26: agent task freshness ─────────────────────────────────────
+    //
+    // `agents.last_active_ts` moves on every tool call, so it says nothing
+    // about when `task_description` was last set. Track that separately in
+    // a sidecar keyed by agent id; agents without a row fall back to their
+    // inception timestamp.
+    migrations.push(Migration::new(
+        "v26_create_agent_task_updates".to_string(),
+        "create sidecar ledger of when each agent's task was last set".to_string(),
+        "CREATE TABLE IF NOT EXISTS agent_task_updates (\
+            agent_id INTEGER PRIMARY KEY REFERENCES agents(id),\
+            task_updated_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v26b_trg_agents_cascade_task_updates".to_string(),
+        "cascade-delete agent_task_updates when a parent agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_task_updates \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM agent_task_updates WHERE agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v27: project settings + policy-added recipients ───────────────
+    //
+    // Free-form per-project `key = value` settings (validated in
+    // `mcp_agent_mail_core::project_settings`), plus a sidecar recording
+    // which recipients of a message were appended by a project policy such
+    // as auto-CC rather than named by the sender.
+    migrations.push(Migration::new(
+        "v27_create_project_settings".to_string(),
+        "create per-project key/value settings table".to_string(),
+        "CREATE TABLE IF NOT EXISTS project_settings (\
+            project_id INTEGER NOT NULL REFERENCES projects(id),\
+            key TEXT NOT NULL,\
+            value TEXT NOT NULL,\
+            updated_ts INTEGER NOT NULL,\
+            PRIMARY KEY (project_id, key)\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v27b_create_message_policy_recipients".to_string(),
+        "create sidecar of recipients added to a message by project policy".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_policy_recipients (\
+            message_id INTEGER NOT NULL REFERENCES messages(id),\
+            agent_id INTEGER NOT NULL,\
+            policy TEXT NOT NULL,\
+            PRIMARY KEY (message_id, agent_id)\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v27c_trg_messages_cascade_policy_recipients".to_string(),
+        "cascade-delete message_policy_recipients when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_policy_recipients \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM message_policy_recipients WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v28: broadcast correlation ids ─────────────────────────────────
+    //
+    // `am mail send` with several projects fans out one message per project;
+    // each copy records the shared correlation id here so recipients can
+    // tell a broadcast apart from a message addressed to their project only.
+    migrations.push(Migration::new(
+        "v28_create_message_correlations".to_string(),
+        "create sidecar of broadcast correlation ids for messages".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_correlations (\
+            message_id INTEGER PRIMARY KEY REFERENCES messages(id),\
+            correlation_id TEXT NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v28b_idx_message_correlations_correlation_id".to_string(),
+        "index on message_correlations.correlation_id".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_message_correlations_correlation_id \
+            ON message_correlations(correlation_id)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v28c_trg_messages_cascade_correlations".to_string(),
+        "cascade-delete message_correlations when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_correlations \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM message_correlations WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v29: automatic ack reminders ───────────────────────────────────
+    //
+    // The ack reminder worker records how many follow-ups it has sent for
+    // each ack-required message so a restart neither re-sends nor exceeds
+    // the per-message cap.
+    migrations.push(Migration::new(
+        "v29_create_ack_reminders".to_string(),
+        "create ledger of automatic ack reminders per message".to_string(),
+        "CREATE TABLE IF NOT EXISTS ack_reminders (\
+            message_id INTEGER PRIMARY KEY REFERENCES messages(id),\
+            reminders_sent INTEGER NOT NULL DEFAULT 0,\
+            last_reminder_ts INTEGER NOT NULL,\
+            last_reminder_message_id INTEGER\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v29b_trg_messages_cascade_ack_reminders".to_string(),
+        "cascade-delete ack_reminders when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_ack_reminders \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM ack_reminders WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v30: file handoff references ───────────────────────────────────
+    //
+    // `am mail send --ref-file` records each referenced file's
+    // project-relative path, size, mtime, and sha256 at send time, so the
+    // recipient can check with `am mail verify-refs` whether the file on
+    // disk is still the one the sender meant.
+    migrations.push(Migration::new(
+        "v30_create_message_file_refs".to_string(),
+        "create sidecar of file handoff references per message".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_file_refs (\
+            message_id INTEGER NOT NULL REFERENCES messages(id),\
+            position INTEGER NOT NULL,\
+            path TEXT NOT NULL,\
+            size_bytes INTEGER NOT NULL,\
+            mtime_ts INTEGER NOT NULL,\
+            sha256 TEXT NOT NULL,\
+            PRIMARY KEY (message_id, path)\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v30b_trg_messages_cascade_file_refs".to_string(),
+        "cascade-delete message_file_refs when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_file_refs \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM message_file_refs WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v31: message drafts ────────────────────────────────────────────
+    //
+    // `am mail draft` lets an agent assemble a message across several CLI
+    // calls and send it in one go. A draft belongs to one (project, agent)
+    // pair; recipients are JSON arrays of agent names, checked only when the
+    // draft is sent.
+    migrations.push(Migration::new(
+        "v31_create_message_drafts".to_string(),
+        "create table of unsent message drafts".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_drafts (\
+            id INTEGER PRIMARY KEY AUTOINCREMENT,\
+            project_id INTEGER NOT NULL REFERENCES projects(id),\
+            agent_id INTEGER NOT NULL REFERENCES agents(id),\
+            subject TEXT NOT NULL DEFAULT '',\
+            body_md TEXT NOT NULL DEFAULT '',\
+            to_names TEXT NOT NULL DEFAULT '[]',\
+            cc_names TEXT NOT NULL DEFAULT '[]',\
+            importance TEXT NOT NULL DEFAULT 'normal',\
+            ack_required INTEGER NOT NULL DEFAULT 0,\
+            thread_id TEXT,\
+            created_ts INTEGER NOT NULL,\
+            updated_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v31b_idx_message_drafts_owner".to_string(),
+        "index on message_drafts(project_id, agent_id)".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_message_drafts_owner \
+         ON message_drafts(project_id, agent_id)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v31c_trg_agents_cascade_message_drafts".to_string(),
+        "cascade-delete message_drafts when the owning agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_message_drafts \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM message_drafts WHERE agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v32: fenced file reservations ──────────────────────────────────
+    //
+    // A reservation created with `--fenced` can only be renewed or released by
+    // a caller presenting the lease token returned at creation. Only the
+    // token's SHA-256 is stored, in a sidecar keyed by reservation id (like
+    // the release ledger) so the hot reservation rows keep their shape.
+    // Operator overrides (`--force`) drop the fence and leave an audit row.
+    migrations.push(Migration::new(
+        "v32_create_file_reservation_fences".to_string(),
+        "create lease-token sidecar for fenced file reservations".to_string(),
+        "CREATE TABLE IF NOT EXISTS file_reservation_fences (\
+            reservation_id INTEGER PRIMARY KEY REFERENCES file_reservations(id),\
+            lease_token_hash TEXT NOT NULL,\
+            created_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v32a_trg_file_reservations_cascade_fences".to_string(),
+        "cascade-delete file_reservation_fences when a reservation row is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_file_reservations_cascade_fences \
+         AFTER DELETE ON file_reservations \
+         BEGIN \
+             DELETE FROM file_reservation_fences WHERE reservation_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v32b_create_file_reservation_fence_overrides".to_string(),
+        "create audit log of operator overrides of reservation fences".to_string(),
+        "CREATE TABLE IF NOT EXISTS file_reservation_fence_overrides (\
+            id INTEGER PRIMARY KEY AUTOINCREMENT,\
+            reservation_id INTEGER NOT NULL,\
+            action TEXT NOT NULL,\
+            operator TEXT NOT NULL,\
+            created_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v33: ack receipts ──────────────────────────────────────────────
+    //
+    // A sender can ask to be told when an ack-required message is
+    // acknowledged. The opt-in lives in its own table keyed by message id;
+    // every receipt actually sent claims one (message, recipient) row, so a
+    // repeated acknowledgement never produces a second receipt. The receipt
+    // message id is kept so inbox consumers can tell receipts apart.
+    migrations.push(Migration::new(
+        "v33_create_ack_receipt_requests".to_string(),
+        "create opt-in table for ack receipts per message".to_string(),
+        "CREATE TABLE IF NOT EXISTS ack_receipt_requests (\
+            message_id INTEGER PRIMARY KEY REFERENCES messages(id),\
+            requested_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v33a_create_ack_receipts".to_string(),
+        "create ledger of ack receipts sent per message and recipient".to_string(),
+        "CREATE TABLE IF NOT EXISTS ack_receipts (\
+            message_id INTEGER NOT NULL REFERENCES messages(id),\
+            agent_id INTEGER NOT NULL,\
+            receipt_message_id INTEGER,\
+            created_ts INTEGER NOT NULL,\
+            PRIMARY KEY (message_id, agent_id)\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v33b_idx_ack_receipts_receipt_message".to_string(),
+        "index ack receipts by receipt message id".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_ack_receipts_receipt_message \
+         ON ack_receipts(receipt_message_id)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v33c_trg_messages_cascade_ack_receipts".to_string(),
+        "cascade-delete ack receipt rows when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_ack_receipts \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM ack_receipt_requests WHERE message_id = OLD.id; \
+             DELETE FROM ack_receipts WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v34: reservation expiry warnings ───────────────────────────────
+    //
+    // The server warns the holder of an active file reservation shortly
+    // before it lapses. One row per warned reservation keeps that to a
+    // single warning, even across restarts or several servers.
+    migrations.push(Migration::new(
+        "v34_create_file_reservation_expiry_warnings".to_string(),
+        "create ledger of expiry warnings sent per file reservation".to_string(),
+        "CREATE TABLE IF NOT EXISTS file_reservation_expiry_warnings (\
+            reservation_id INTEGER PRIMARY KEY REFERENCES file_reservations(id),\
+            warned_ts INTEGER NOT NULL,\
+            message_id INTEGER\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v34a_trg_file_reservations_cascade_expiry_warnings".to_string(),
+        "cascade-delete expiry warnings when a reservation row is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_file_reservations_cascade_expiry_warnings \
+         AFTER DELETE ON file_reservations \
+         BEGIN \
+             DELETE FROM file_reservation_expiry_warnings WHERE reservation_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v35: agent retirement ─────────────────────────────────────────
+    //
+    // `am agents apply --prune` retires agents that left the manifest.
+    // Deleting them would orphan their messages, so retirement is a
+    // sidecar row keyed by agent id; re-applying a manifest that lists the
+    // agent again removes the row.
+    migrations.push(Migration::new(
+        "v35_create_agent_retirements".to_string(),
+        "create sidecar ledger of retired agents".to_string(),
+        "CREATE TABLE IF NOT EXISTS agent_retirements (\
+            agent_id INTEGER PRIMARY KEY REFERENCES agents(id),\
+            retired_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v35a_trg_agents_cascade_retirements".to_string(),
+        "cascade-delete agent_retirements when a parent agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_retirements \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM agent_retirements WHERE agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v36: per-product archive root ─────────────────────────────────
+    //
+    // Default destination for `am archive relocate` when a project linked
+    // to the product is moved; NULL keeps the configured archive root.
+    migrations.push(Migration::new(
+        "v36_products_add_archive_root".to_string(),
+        "add optional archive_root override to products".to_string(),
+        "ALTER TABLE products ADD COLUMN archive_root TEXT".to_string(),
+        String::new(),
+    ));
+
+    // ── v37: per-agent sender secrets ─────────────────────────────────
+    //
+    // Projects with `require_sender_auth` only accept mail from a sender
+    // that presents its secret. Only the SHA-256 of the secret is stored.
+    // Messages whose sender was verified get a sidecar row so inbox and
+    // thread views can flag them.
+    migrations.push(Migration::new(
+        "v37_create_agent_secrets".to_string(),
+        "create sidecar table of hashed per-agent sender secrets".to_string(),
+        "CREATE TABLE IF NOT EXISTS agent_secrets (\
+            agent_id INTEGER PRIMARY KEY REFERENCES agents(id),\
+            secret_hash TEXT NOT NULL,\
+            created_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v37a_trg_agents_cascade_secrets".to_string(),
+        "cascade-delete agent_secrets when a parent agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_secrets \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM agent_secrets WHERE agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v37b_create_message_sender_verifications".to_string(),
+        "create sidecar ledger of messages sent with a verified sender secret".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_sender_verifications (\
+            message_id INTEGER PRIMARY KEY REFERENCES messages(id),\
+            verified_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v37c_trg_messages_cascade_sender_verifications".to_string(),
+        "cascade-delete sender verifications when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_sender_verifications \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM message_sender_verifications WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v38: `auto` contact policy qualifiers ─────────────────────────
+    //
+    // An `auto` agent may carry an allowlist of requester-name globs, a TTL
+    // for links approved automatically, and a per-requester daily cap.
+    // Every approval is also appended to `contact_approvals` so `contacts
+    // list` can say whether a link was approved by hand or automatically,
+    // and the daily cap can count recent auto-approvals.
+    migrations.push(Migration::new(
+        "v38_create_contact_policy_qualifiers".to_string(),
+        "create sidecar table of auto contact policy qualifiers".to_string(),
+        "CREATE TABLE IF NOT EXISTS contact_policy_qualifiers (\
+            agent_id INTEGER PRIMARY KEY REFERENCES agents(id),\
+            auto_allow TEXT NOT NULL DEFAULT '',\
+            auto_ttl_seconds INTEGER,\
+            auto_daily_cap INTEGER,\
+            updated_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v38a_trg_agents_cascade_contact_policy_qualifiers".to_string(),
+        "cascade-delete contact policy qualifiers when a parent agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_contact_policy_qualifiers \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM contact_policy_qualifiers WHERE agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v38b_create_contact_approvals".to_string(),
+        "create ledger of contact link approvals and how they were granted".to_string(),
+        "CREATE TABLE IF NOT EXISTS contact_approvals (\
+            id INTEGER PRIMARY KEY AUTOINCREMENT,\
+            a_project_id INTEGER NOT NULL,\
+            a_agent_id INTEGER NOT NULL,\
+            b_project_id INTEGER NOT NULL,\
+            b_agent_id INTEGER NOT NULL,\
+            source TEXT NOT NULL,\
+            pattern TEXT,\
+            approved_ts INTEGER NOT NULL\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v38c_idx_contact_approvals_requester".to_string(),
+        "index contact approvals by requester and time for the daily auto cap".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_contact_approvals_requester \
+         ON contact_approvals(a_agent_id, source, approved_ts)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v38d_idx_contact_approvals_target".to_string(),
+        "index contact approvals by target for incoming contact listings".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_contact_approvals_target \
+         ON contact_approvals(b_agent_id, approved_ts)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v38e_trg_agents_cascade_contact_approvals".to_string(),
+        "cascade-delete contact approvals when either endpoint agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_contact_approvals \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM contact_approvals \
+             WHERE a_agent_id = OLD.id OR b_agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
+    // ── v39: deferred delivery to retired agents ───────────────────────
+    //
+    // Mail addressed to a retired agent is still stored, with its recipient
+    // row marked deferred here. Reviving the agent deletes the rows, which
+    // makes the queued mail deliverable.
+    migrations.push(Migration::new(
+        "v39_create_message_recipient_deferrals".to_string(),
+        "create sidecar ledger of recipient rows deferred for retired agents".to_string(),
+        "CREATE TABLE IF NOT EXISTS message_recipient_deferrals (\
+            message_id INTEGER NOT NULL REFERENCES messages(id),\
+            agent_id INTEGER NOT NULL REFERENCES agents(id),\
+            deferred_ts INTEGER NOT NULL,\
+            PRIMARY KEY (message_id, agent_id)\
+        )"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v39a_idx_message_recipient_deferrals_agent".to_string(),
+        "index deferred recipient rows by agent for revival".to_string(),
+        "CREATE INDEX IF NOT EXISTS idx_message_recipient_deferrals_agent \
+         ON message_recipient_deferrals(agent_id, deferred_ts)"
+            .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v39b_trg_messages_cascade_recipient_deferrals".to_string(),
+        "cascade-delete recipient deferrals when a parent message is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_messages_cascade_recipient_deferrals \
+         AFTER DELETE ON messages \
+         BEGIN \
+             DELETE FROM message_recipient_deferrals WHERE message_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+    migrations.push(Migration::new(
+        "v39c_trg_agents_cascade_recipient_deferrals".to_string(),
+        "cascade-delete recipient deferrals when a parent agent is removed".to_string(),
+        "CREATE TRIGGER IF NOT EXISTS trg_agents_cascade_recipient_deferrals \
+         AFTER DELETE ON agents \
+         BEGIN \
+             DELETE FROM message_recipient_deferrals WHERE agent_id = OLD.id; \
+         END"
+        .to_string(),
+        String::new(),
+    ));
+
     migrations
 }
 
@@ -1,7 +1,8 @@
 //! Global query planner for unified search across messages, agents, and projects.
 //!
 //! Converts a [`SearchQuery`] into SQL + params, supporting:
-//! - Faceted filtering (importance, direction, time range, project, agent, thread)
+//! - Faceted filtering (importance, direction, time range, project, agent, sender,
+//!   recipient, thread) and field-scoped terms (`subject:`, `body:`)
 //! - Stable SQL fallback ordering for searches handled outside the primary index
 //! - Stable cursor-based pagination using (score, id)
 //! - Query explain output for debugging/trust
@@ -97,6 +98,43 @@ impl TimeRange {
     }
 }
 
+/// Message field a scoped query term is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageField {
+    Subject,
+    Body,
+}
+
+impl MessageField {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Subject => "subject",
+            Self::Body => "body",
+        }
+    }
+
+    /// Parse a field prefix (`subject`, `body`), case-insensitively.
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        if s.eq_ignore_ascii_case("subject") {
+            Some(Self::Subject)
+        } else if s.eq_ignore_ascii_case("body") {
+            Some(Self::Body)
+        } else {
+            None
+        }
+    }
+}
+
+/// A query term that must appear in one message field (`subject:payment`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FieldTerm {
+    pub field: MessageField,
+    pub term: String,
+}
+
 /// Ranking strategy for search results.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -274,6 +312,16 @@ pub struct SearchQuery {
     /// Filter by agent name (sender for outbox, recipient for inbox).
     pub agent_name: Option<String>,
 
+    /// Filter by sender name; combines with `recipient_name` and `agent_name`.
+    pub sender_name: Option<String>,
+
+    /// Filter by recipient name (to, cc, or bcc).
+    pub recipient_name: Option<String>,
+
+    /// Field-scoped terms, each of which must match (ANDed with `text`).
+    #[serde(default)]
+    pub field_terms: Vec<FieldTerm>,
+
     /// Filter by thread ID.
     pub thread_id: Option<String>,
 
@@ -745,15 +793,20 @@ const fn empty_plan_sql(kind: DocKind) -> &'static str {
     }
 }
 
+/// `%term%` with LIKE wildcards in `term` escaped, for `LIKE ? ESCAPE '\'`.
+fn like_contains_pattern(term: &str) -> String {
+    format!("%{}%", crate::queries::like_escape(term))
+}
+
 #[allow(clippy::too_many_lines)]
 fn plan_message_search(query: &SearchQuery) -> SearchPlan {
     let limit = query.effective_limit();
     let mut facets_applied = Vec::new();
 
     // Determine method — LIKE-based SQL only (Tantivy handles relevance search).
-    let method = if !query.text.is_empty() {
+    let method = if !query.text.is_empty() || !query.field_terms.is_empty() {
         let terms = extract_like_terms(&query.text, 5);
-        if terms.is_empty() {
+        if terms.is_empty() && query.field_terms.is_empty() {
             PlanMethod::Empty
         } else {
             PlanMethod::Like
@@ -786,19 +839,16 @@ fn plan_message_search(query: &SearchQuery) -> SearchPlan {
             let terms = extract_like_terms(&query.text, 5);
             let mut like_parts = Vec::new();
             for term in &terms {
-                let escaped = term
-                    .replace('\\', "\\\\")
-                    .replace('%', "\\%")
-                    .replace('_', "\\_");
                 like_parts.push(
                     "(m.subject LIKE ? ESCAPE '\\' OR m.body_md LIKE ? ESCAPE '\\')".to_string(),
                 );
-                let pattern = format!("%{escaped}%");
+                let pattern = like_contains_pattern(term);
                 params.push(PlanParam::Text(pattern.clone()));
                 params.push(PlanParam::Text(pattern));
             }
-            let like_filter = like_parts.join(" AND ");
-            where_clauses.push(like_filter);
+            if !like_parts.is_empty() {
+                where_clauses.push(like_parts.join(" AND "));
+            }
 
             (
                 format!(
@@ -883,6 +933,32 @@ fn plan_message_search(query: &SearchQuery) -> SearchPlan {
         facets_applied.push("time_range_max".to_string());
     }
 
+    for field_term in &query.field_terms {
+        let column = match field_term.field {
+            MessageField::Subject => "m.subject",
+            MessageField::Body => "m.body_md",
+        };
+        where_clauses.push(format!("{column} LIKE ? ESCAPE '\\'"));
+        params.push(PlanParam::Text(like_contains_pattern(&field_term.term)));
+    }
+    if !query.field_terms.is_empty() {
+        facets_applied.push("field_terms".to_string());
+    }
+
+    if let Some(sender) = &query.sender_name {
+        where_clauses.push("a.name = ? COLLATE NOCASE".to_string());
+        params.push(PlanParam::Text(sender.clone()));
+        facets_applied.push("sender_name".to_string());
+    }
+    if let Some(recipient) = &query.recipient_name {
+        where_clauses
+            .push("instr(lower(COALESCE(m.recipients_json, '')), lower(?)) > 0".to_string());
+        params.push(PlanParam::Text(
+            serde_json::to_string(recipient).unwrap_or_else(|_| format!("\"{recipient}\"")),
+        ));
+        facets_applied.push("recipient_name".to_string());
+    }
+
     // Direction filter must survive recipient metadata drift. The message
     // envelope (`recipients_json`) remains durable even if `agents` rows are
     // missing, so planner filtering uses that payload instead of inner-joining
@@ -1132,6 +1208,8 @@ const fn has_any_message_facet(query: &SearchQuery) -> bool {
     !query.importance.is_empty()
         || query.direction.is_some()
         || query.agent_name.is_some()
+        || query.sender_name.is_some()
+        || query.recipient_name.is_some()
         || query.thread_id.is_some()
         || query.ack_required.is_some()
         || !query.time_range.is_empty()
@@ -1546,6 +1624,49 @@ mod tests {
         assert!(plan.facets_applied.contains(&"agent_name".to_string()));
     }
 
+    #[test]
+    fn plan_sender_and_recipient_filters_combine() {
+        let mut q = SearchQuery::messages("deploy", 1);
+        q.sender_name = Some("BlueLake".to_string());
+        q.recipient_name = Some("RedFox".to_string());
+        let plan = plan_search(&q);
+        assert!(plan.sql.contains("a.name = ? COLLATE NOCASE"));
+        assert!(plan.sql.contains("m.recipients_json"));
+        assert!(plan.facets_applied.contains(&"sender_name".to_string()));
+        assert!(plan.facets_applied.contains(&"recipient_name".to_string()));
+        assert!(
+            plan.params
+                .iter()
+                .any(|p| matches!(p, PlanParam::Text(t) if t == "\"RedFox\""))
+        );
+    }
+
+    #[test]
+    fn plan_field_terms_without_free_text() {
+        let mut q = SearchQuery::messages("", 1);
+        q.field_terms = vec![FieldTerm {
+            field: MessageField::Subject,
+            term: "50%_off".to_string(),
+        }];
+        let plan = plan_search(&q);
+        assert_eq!(plan.method, PlanMethod::Like);
+        assert!(plan.sql.contains("m.subject LIKE ? ESCAPE '\\'"));
+        assert!(!plan.sql.contains("m.body_md LIKE"));
+        assert!(plan.facets_applied.contains(&"field_terms".to_string()));
+        assert!(
+            plan.params
+                .iter()
+                .any(|p| matches!(p, PlanParam::Text(t) if t == "%50\\%\\_off%"))
+        );
+    }
+
+    #[test]
+    fn message_field_parse_is_case_insensitive() {
+        assert_eq!(MessageField::parse("Subject"), Some(MessageField::Subject));
+        assert_eq!(MessageField::parse("body"), Some(MessageField::Body));
+        assert_eq!(MessageField::parse("from"), None);
+    }
+
     // ── plan_search: cursor pagination ─────────────────────────────
 
     #[test]
@@ -12,9 +12,9 @@
 use crate::error::DbError;
 use crate::pool::DbPool;
 use crate::search_planner::{
-    Direction, DocKind, Importance, PlanMethod, PlanParam, RankingMode, RecoverySuggestion,
-    ScopePolicy, SearchCursor, SearchQuery, SearchResponse, SearchResult, ZeroResultGuidance,
-    plan_search,
+    Direction, DocKind, Importance, MessageField, PlanMethod, PlanParam, RankingMode,
+    RecoverySuggestion, ScopePolicy, SearchCursor, SearchQuery, SearchResponse, SearchResult,
+    ZeroResultGuidance, plan_search,
 };
 use crate::search_scope::{
     RedactionPolicy, ScopeAuditSummary, ScopeContext, ScopedSearchResult, apply_scope,
@@ -383,8 +383,8 @@ fn try_tantivy_search(query: &SearchQuery) -> Option<Vec<SearchResult>> {
 
 fn query_needs_recipient_filter(query: &SearchQuery) -> bool {
     matches!(query.doc_kind, DocKind::Message | DocKind::Thread)
-        && query.agent_name.is_some()
-        && !matches!(query.direction, Some(Direction::Outbox))
+        && (query.recipient_name.is_some()
+            || (query.agent_name.is_some() && !matches!(query.direction, Some(Direction::Outbox))))
 }
 
 fn message_query_uses_sql_plan(query: &SearchQuery) -> bool {
@@ -434,6 +434,8 @@ fn message_query_requires_sql_plan(query: &SearchQuery) -> bool {
         || query.ranking == RankingMode::Recency
         || query.product_id.is_some()
         || query.ack_required.is_some()
+        || query.sender_name.is_some()
+        || !query.field_terms.is_empty()
         || query_needs_recipient_filter(query)
         || importance_filter_requires_sql_plan(query)
         || scoped_project_set_requires_sql_plan(query)
@@ -449,6 +451,9 @@ fn legacy_candidate_limit(query: &SearchQuery) -> usize {
         || !query.importance.is_empty()
         || query.direction.is_some()
         || query.agent_name.is_some()
+        || query.sender_name.is_some()
+        || query.recipient_name.is_some()
+        || !query.field_terms.is_empty()
         || query.thread_id.is_some()
         || query.ack_required.is_some()
         || !query.time_range.is_empty();
@@ -589,6 +594,20 @@ fn unresolved_result_matches_agent_filter(query: &SearchQuery, result: &SearchRe
     }
 }
 
+/// Case-insensitive containment check for `field_terms`, mirroring the
+/// planner's `LIKE '%term%'` predicates.
+fn text_matches_field_terms(query: &SearchQuery, subject: &str, body: &str) -> bool {
+    query.field_terms.iter().all(|field_term| {
+        let haystack = match field_term.field {
+            MessageField::Subject => subject,
+            MessageField::Body => body,
+        };
+        haystack
+            .to_lowercase()
+            .contains(&field_term.term.to_lowercase())
+    })
+}
+
 fn detail_matches_query_filters(
     query: &SearchQuery,
     detail: &crate::queries::ThreadMessageRow,
@@ -608,6 +627,23 @@ fn detail_matches_query_filters(
     if !detail_matches_agent_filter(query, &detail.from, recipient_names) {
         return false;
     }
+    if let Some(sender) = query.sender_name.as_deref()
+        && !detail.from.eq_ignore_ascii_case(sender)
+    {
+        return false;
+    }
+    if let Some(recipient) = query.recipient_name.as_deref()
+        && !recipient_names.is_some_and(|names| {
+            names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(recipient))
+        })
+    {
+        return false;
+    }
+    if !text_matches_field_terms(query, &detail.subject, &detail.body_md) {
+        return false;
+    }
     if let Some(thread_id) = query.thread_id.as_deref()
         && detail.thread_id.as_deref() != Some(thread_id)
     {
@@ -660,6 +696,27 @@ fn raw_result_matches_query_filters(
     if !unresolved_result_matches_agent_filter(query, result) {
         return false;
     }
+    if let Some(sender) = query.sender_name.as_deref()
+        && !result
+            .from_agent
+            .as_deref()
+            .is_some_and(|from| from.eq_ignore_ascii_case(sender))
+    {
+        return false;
+    }
+    if let Some(recipient) = query.recipient_name.as_deref() {
+        let is_recipient = [&result.to, &result.cc, &result.bcc]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(|name| name.eq_ignore_ascii_case(recipient));
+        if !is_recipient {
+            return false;
+        }
+    }
+    if !text_matches_field_terms(query, &result.title, &result.body) {
+        return false;
+    }
     if let Some(thread_id) = query.thread_id.as_deref()
         && result.thread_id.as_deref() != Some(thread_id)
     {
@@ -3610,6 +3667,15 @@ fn collect_query_facets(query: &SearchQuery) -> Vec<String> {
     if query.agent_name.is_some() {
         facets.push("agent_name".to_string());
     }
+    if query.sender_name.is_some() {
+        facets.push("sender_name".to_string());
+    }
+    if query.recipient_name.is_some() {
+        facets.push("recipient_name".to_string());
+    }
+    if !query.field_terms.is_empty() {
+        facets.push("field_terms".to_string());
+    }
     if query.thread_id.is_some() {
         facets.push("thread_id".to_string());
     }
@@ -3757,6 +3823,9 @@ fn cache_scope_discriminator(query: &SearchQuery) -> u64 {
     }
 
     query.explain.hash(&mut hasher);
+    query.sender_name.hash(&mut hasher);
+    query.recipient_name.hash(&mut hasher);
+    query.field_terms.hash(&mut hasher);
 
     let mut importance_levels: Vec<&'static str> = query
         .importance
@@ -6333,6 +6402,55 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn detail_filter_combines_sender_recipient_and_field_terms() {
+        let detail = detail_row(24, 1, "RedPeak", Some("br-240"), "high", 0, 2_400);
+        let recipients = vec!["BlueLake".to_string()];
+        let query = SearchQuery {
+            sender_name: Some("redpeak".to_string()),
+            recipient_name: Some("BlueLake".to_string()),
+            field_terms: vec![crate::search_planner::FieldTerm {
+                field: MessageField::Subject,
+                term: "SUBJ".to_string(),
+            }],
+            ..Default::default()
+        };
+        assert!(query_needs_recipient_filter(&query));
+        assert!(message_query_requires_sql_plan(&query));
+        assert!(detail_matches_query_filters(
+            &query,
+            &detail,
+            Some(recipients.as_slice()),
+            None
+        ));
+
+        let wrong_recipient = SearchQuery {
+            recipient_name: Some("GreenCastle".to_string()),
+            ..query.clone()
+        };
+        assert!(!detail_matches_query_filters(
+            &wrong_recipient,
+            &detail,
+            Some(recipients.as_slice()),
+            None
+        ));
+
+        // The term is in the subject, not the body.
+        let body_scoped = SearchQuery {
+            field_terms: vec![crate::search_planner::FieldTerm {
+                field: MessageField::Body,
+                term: "subj".to_string(),
+            }],
+            ..query
+        };
+        assert!(!detail_matches_query_filters(
+            &body_scoped,
+            &detail,
+            Some(recipients.as_slice()),
+            None
+        ));
+    }
+
     #[test]
     fn detail_filter_enforces_importance_and_time_range() {
         let detail = detail_row(3, 2, "RedPeak", None, "normal", 0, 5_000);
@@ -11,6 +11,41 @@ use sqlmodel_core::Value;
 
 const MAX_SYNC_IN_CLAUSE_ITEMS: usize = 500;
 
+/// Inbox filter for unread, non-ack messages whose `message_expiries`
+/// deadline has passed. Binds one parameter: the reference time.
+const EXCLUDE_EXPIRED_UNREAD_CLAUSE: &str = " AND NOT (m.ack_required = 0 AND r.read_ts IS NULL \
+     AND EXISTS (SELECT 1 FROM message_expiries me WHERE me.message_id = m.id AND me.expires_ts <= ?))";
+
+/// Inbox filter for recipient rows deferred while the agent was retired;
+/// reviving the agent deletes the deferral rows.
+const EXCLUDE_DEFERRED_CLAUSE: &str = " AND NOT EXISTS (SELECT 1 FROM message_recipient_deferrals d \
+     WHERE d.message_id = r.message_id AND d.agent_id = r.agent_id)";
+
+fn table_exists(conn: &DbConn, table: &str) -> Result<bool, DbError> {
+    let rows = conn
+        .query_sync(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ? LIMIT 1",
+            &[Value::Text(table.to_string())],
+        )
+        .map_err(|e| DbError::Sqlite(format!("check {table} existence: {e}")))?;
+    Ok(!rows.is_empty())
+}
+
+/// `exclude_expired_at`, dropped when the database predates the
+/// `message_expiries` sidecar (nothing there can have expired).
+fn expiry_filter(conn: &DbConn, exclude_expired_at: Option<i64>) -> Result<Option<i64>, DbError> {
+    let Some(now) = exclude_expired_at else {
+        return Ok(None);
+    };
+    Ok(table_exists(conn, "message_expiries")?.then_some(now))
+}
+
+/// `exclude_deferred`, dropped when the database predates the
+/// `message_recipient_deferrals` sidecar (nothing there can be deferred).
+fn deferral_filter(conn: &DbConn, exclude_deferred: bool) -> Result<bool, DbError> {
+    Ok(exclude_deferred && table_exists(conn, "message_recipient_deferrals")?)
+}
+
 /// Synchronously update the thread ID of a message.
 ///
 /// Returns `Ok(true)` if the thread ID was updated, `Ok(false)` if it was already the target ID.
@@ -76,6 +111,10 @@ pub fn fetch_inbox_rows_from_conn(
             unread_only,
             ack_required_only,
             ack_overdue_before: None,
+            after_watermark: None,
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
             body_policy: InboxBodyPolicy::Full,
         },
     )
@@ -103,6 +142,10 @@ pub fn fetch_inbox_metadata_rows_from_conn(
             unread_only,
             ack_required_only,
             ack_overdue_before: None,
+            after_watermark: None,
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
             body_policy: InboxBodyPolicy::MetadataOnly,
         },
     )
@@ -129,6 +172,10 @@ pub fn fetch_inbox_ack_overdue_rows_from_conn(
             unread_only: false,
             ack_required_only: false,
             ack_overdue_before: Some(ack_overdue_before),
+            after_watermark: None,
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
             body_policy: InboxBodyPolicy::Full,
         },
     )
@@ -155,11 +202,217 @@ pub fn fetch_inbox_ack_overdue_metadata_rows_from_conn(
             unread_only: false,
             ack_required_only: false,
             ack_overdue_before: Some(ack_overdue_before),
+            after_watermark: None,
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
             body_policy: InboxBodyPolicy::MetadataOnly,
         },
     )
 }
 
+/// Fetch inbox rows strictly after a message-id watermark, oldest first.
+///
+/// The watermark is the message id, which SQLite assigns monotonically
+/// (`AUTOINCREMENT`) in commit order. Unlike `created_ts`, it cannot tie or
+/// run backwards under clock skew, so a poller that feeds back the highest
+/// returned id never skips or re-reads a message. Rows are ordered by id
+/// ascending so a `limit`-truncated page resumes exactly where it stopped.
+#[allow(clippy::too_many_arguments)]
+pub fn fetch_inbox_rows_after_watermark_from_conn(
+    conn: &DbConn,
+    project_id: i64,
+    agent_id: i64,
+    urgent_only: bool,
+    unread_only: bool,
+    after_watermark: i64,
+    limit: usize,
+    include_bodies: bool,
+) -> Result<Vec<InboxRow>, DbError> {
+    fetch_inbox_rows_from_conn_impl(
+        conn,
+        project_id,
+        agent_id,
+        None,
+        limit,
+        InboxFetchOptions {
+            urgent_only,
+            unread_only,
+            ack_required_only: false,
+            ack_overdue_before: None,
+            after_watermark: Some(after_watermark),
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
+            body_policy: if include_bodies {
+                InboxBodyPolicy::Full
+            } else {
+                InboxBodyPolicy::MetadataOnly
+            },
+        },
+    )
+}
+
+/// Fetch inbox rows without leaving out expired unread messages, which every
+/// other `fetch_inbox_*_from_conn` helper filters before applying `limit`.
+///
+/// With `after_watermark` set, rows are paged oldest first like
+/// [`fetch_inbox_rows_after_watermark_from_conn`]; otherwise newest first.
+#[allow(clippy::too_many_arguments)]
+pub fn fetch_inbox_rows_including_expired_from_conn(
+    conn: &DbConn,
+    project_id: i64,
+    agent_id: i64,
+    urgent_only: bool,
+    unread_only: bool,
+    since_ts: Option<i64>,
+    after_watermark: Option<i64>,
+    limit: usize,
+    include_bodies: bool,
+) -> Result<Vec<InboxRow>, DbError> {
+    fetch_inbox_rows_from_conn_impl(
+        conn,
+        project_id,
+        agent_id,
+        since_ts,
+        limit,
+        InboxFetchOptions {
+            urgent_only,
+            unread_only,
+            ack_required_only: false,
+            ack_overdue_before: None,
+            after_watermark,
+            created_at_or_before: None,
+            exclude_expired_at: None,
+            exclude_deferred: true,
+            body_policy: if include_bodies {
+                InboxBodyPolicy::Full
+            } else {
+                InboxBodyPolicy::MetadataOnly
+            },
+        },
+    )
+}
+
+/// Reconstruct an agent's inbox as it stood at `as_of` (microseconds).
+///
+/// Only messages created at or before `as_of` are returned, newest first, and
+/// a `read_ts`/`ack_ts` stamped after `as_of` is cleared, so a message read
+/// later shows as unread. An event at exactly `as_of` counts as having
+/// happened.
+#[allow(clippy::too_many_arguments)]
+pub fn fetch_inbox_rows_as_of_from_conn(
+    conn: &DbConn,
+    project_id: i64,
+    agent_id: i64,
+    urgent_only: bool,
+    since_ts: Option<i64>,
+    as_of: i64,
+    limit: usize,
+    include_bodies: bool,
+) -> Result<Vec<InboxRow>, DbError> {
+    let mut rows = fetch_inbox_rows_from_conn_impl(
+        conn,
+        project_id,
+        agent_id,
+        since_ts,
+        limit,
+        InboxFetchOptions {
+            urgent_only,
+            unread_only: false,
+            ack_required_only: false,
+            ack_overdue_before: None,
+            after_watermark: None,
+            created_at_or_before: Some(as_of),
+            exclude_expired_at: Some(as_of),
+            exclude_deferred: true,
+            body_policy: if include_bodies {
+                InboxBodyPolicy::Full
+            } else {
+                InboxBodyPolicy::MetadataOnly
+            },
+        },
+    )?;
+    for row in &mut rows {
+        row.read_ts = row.read_ts.filter(|ts| *ts <= as_of);
+        row.ack_ts = row.ack_ts.filter(|ts| *ts <= as_of);
+    }
+    Ok(rows)
+}
+
+/// Aggregate inbox counts, as reported by `--count-only` inbox views.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboxCounts {
+    pub total: i64,
+    pub unread: i64,
+    /// Messages with `high` or `urgent` importance.
+    pub urgent: i64,
+}
+
+/// Count inbox rows with the same filters as the `fetch_inbox_*_from_conn`
+/// family, without materializing any message rows.
+///
+/// `limit` is deliberately absent: the count covers every matching row.
+/// When `exclude_expired_at` is set, unread non-ack messages whose
+/// `message_expiries` deadline is at or before it are left out, matching the
+/// default exclusion applied by the `fetch_inbox_*_from_conn` family. Mail
+/// deferred while the agent was retired is never counted.
+#[allow(clippy::too_many_arguments)]
+pub fn count_inbox_rows_from_conn(
+    conn: &DbConn,
+    project_id: i64,
+    agent_id: i64,
+    urgent_only: bool,
+    since_ts: Option<i64>,
+    after_watermark: Option<i64>,
+    exclude_expired_at: Option<i64>,
+) -> Result<InboxCounts, DbError> {
+    let _ = conn.execute_raw("PRAGMA busy_timeout = 250");
+    let mut sql = String::from(
+        "SELECT COUNT(*) AS total, \
+                COALESCE(SUM(CASE WHEN r.read_ts IS NULL THEN 1 ELSE 0 END), 0) AS unread, \
+                COALESCE(SUM(CASE WHEN m.importance IN ('high', 'urgent') THEN 1 ELSE 0 END), 0) AS urgent \
+         FROM message_recipients r \
+         JOIN messages m ON m.id = r.message_id \
+         WHERE r.agent_id = ? AND m.project_id = ?",
+    );
+    let mut params = vec![Value::BigInt(agent_id), Value::BigInt(project_id)];
+    if urgent_only {
+        sql.push_str(" AND m.importance IN ('high', 'urgent')");
+    }
+    if let Some(ts) = since_ts {
+        sql.push_str(" AND m.created_ts > ?");
+        params.push(Value::BigInt(ts));
+    }
+    if let Some(watermark) = after_watermark {
+        sql.push_str(" AND m.id > ?");
+        params.push(Value::BigInt(watermark));
+    }
+    if let Some(now) = expiry_filter(conn, exclude_expired_at)? {
+        sql.push_str(EXCLUDE_EXPIRED_UNREAD_CLAUSE);
+        params.push(Value::BigInt(now));
+    }
+    if deferral_filter(conn, true)? {
+        sql.push_str(EXCLUDE_DEFERRED_CLAUSE);
+    }
+
+    let rows = conn
+        .query_sync(&sql, &params)
+        .map_err(|e| DbError::Sqlite(e.to_string()))?;
+    let Some(row) = rows.first() else {
+        return Ok(InboxCounts::default());
+    };
+    let count = |column: &str| -> Result<i64, DbError> {
+        row.get_named(column)
+            .map_err(|e| DbError::Sqlite(e.to_string()))
+    };
+    Ok(InboxCounts {
+        total: count("total")?,
+        unread: count("unread")?,
+        urgent: count("urgent")?,
+    })
+}
+
 #[derive(Clone, Copy)]
 enum InboxBodyPolicy {
     Full,
@@ -172,18 +425,23 @@ struct InboxFetchOptions {
     unread_only: bool,
     ack_required_only: bool,
     ack_overdue_before: Option<i64>,
+    after_watermark: Option<i64>,
+    /// Upper bound (inclusive) on `created_ts`, for as-of reconstructions.
+    created_at_or_before: Option<i64>,
+    /// Leave out unread non-ack messages that expired at or before this time.
+    exclude_expired_at: Option<i64>,
+    /// Leave out mail deferred while the agent was retired.
+    exclude_deferred: bool,
     body_policy: InboxBodyPolicy,
 }
 
-fn fetch_inbox_rows_from_conn_impl(
-    conn: &DbConn,
+fn inbox_fetch_query(
     project_id: i64,
     agent_id: i64,
     since_ts: Option<i64>,
-    limit: usize,
+    limit: i64,
     options: InboxFetchOptions,
-) -> Result<Vec<InboxRow>, DbError> {
-    let _ = conn.execute_raw("PRAGMA busy_timeout = 250");
+) -> (String, Vec<Value>) {
     let body_select = match options.body_policy {
         InboxBodyPolicy::Full => "m.body_md",
         InboxBodyPolicy::MetadataOnly => "'' AS body_md",
@@ -217,11 +475,100 @@ fn fetch_inbox_rows_from_conn_impl(
         sql.push_str(" AND m.created_ts > ?");
         params.push(Value::BigInt(ts));
     }
+    if let Some(ts) = options.created_at_or_before {
+        sql.push_str(" AND m.created_ts <= ?");
+        params.push(Value::BigInt(ts));
+    }
+    if let Some(watermark) = options.after_watermark {
+        sql.push_str(" AND m.id > ?");
+        params.push(Value::BigInt(watermark));
+    }
+    if let Some(now) = options.exclude_expired_at {
+        sql.push_str(EXCLUDE_EXPIRED_UNREAD_CLAUSE);
+        params.push(Value::BigInt(now));
+    }
+    if options.exclude_deferred {
+        sql.push_str(EXCLUDE_DEFERRED_CLAUSE);
+    }
+
+    if options.after_watermark.is_some() {
+        sql.push_str(" ORDER BY m.id ASC LIMIT ?");
+    } else {
+        sql.push_str(" ORDER BY m.created_ts DESC LIMIT ?");
+    }
+    params.push(Value::BigInt(limit));
+    (sql, params)
+}
+
+/// The inbox statement for one watermark poll, as issued by
+/// [`fetch_inbox_rows_after_watermark_from_conn`] without bodies.
+pub(crate) fn inbox_watermark_poll_query(
+    project_id: i64,
+    agent_id: i64,
+    after_watermark: i64,
+    limit: i64,
+) -> (String, Vec<Value>) {
+    inbox_fetch_query(
+        project_id,
+        agent_id,
+        None,
+        limit,
+        InboxFetchOptions {
+            urgent_only: false,
+            unread_only: false,
+            ack_required_only: false,
+            ack_overdue_before: None,
+            after_watermark: Some(after_watermark),
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
+            body_policy: InboxBodyPolicy::MetadataOnly,
+        },
+    )
+}
+
+/// The default metadata-only inbox statement, newest first.
+pub(crate) fn inbox_latest_query(
+    project_id: i64,
+    agent_id: i64,
+    limit: i64,
+) -> (String, Vec<Value>) {
+    inbox_fetch_query(
+        project_id,
+        agent_id,
+        None,
+        limit,
+        InboxFetchOptions {
+            urgent_only: false,
+            unread_only: false,
+            ack_required_only: false,
+            ack_overdue_before: None,
+            after_watermark: None,
+            created_at_or_before: None,
+            exclude_expired_at: Some(crate::now_micros()),
+            exclude_deferred: true,
+            body_policy: InboxBodyPolicy::MetadataOnly,
+        },
+    )
+}
 
+fn fetch_inbox_rows_from_conn_impl(
+    conn: &DbConn,
+    project_id: i64,
+    agent_id: i64,
+    since_ts: Option<i64>,
+    limit: usize,
+    options: InboxFetchOptions,
+) -> Result<Vec<InboxRow>, DbError> {
+    let _ = conn.execute_raw("PRAGMA busy_timeout = 250");
     let limit_i64 =
         i64::try_from(limit).map_err(|_| DbError::invalid("limit", "limit exceeds i64::MAX"))?;
-    sql.push_str(" ORDER BY m.created_ts DESC LIMIT ?");
-    params.push(Value::BigInt(limit_i64));
+    let options = InboxFetchOptions {
+        exclude_expired_at: expiry_filter(conn, options.exclude_expired_at)?,
+        exclude_deferred: deferral_filter(conn, options.exclude_deferred)?,
+        ..options
+    };
+    let (sql, params) = inbox_fetch_query(project_id, agent_id, since_ts, limit_i64, options);
 
     let rows = conn
         .query_sync(&sql, &params)
@@ -563,6 +910,115 @@ pub fn mark_messages_read_batch_sync(
     result
 }
 
+/// Read/ack state of one recipient row after a synchronous single-message update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecipientStateTransition {
+    pub read_ts: Option<i64>,
+    pub ack_ts: Option<i64>,
+    /// True when this call wrote the requested state; false when it was already set.
+    pub changed: bool,
+}
+
+/// Synchronously mark one message read for a recipient agent.
+///
+/// Mirrors the async `mark_message_read` semantics on a caller-owned
+/// connection: the row is looked up and updated in one `BEGIN IMMEDIATE`
+/// transaction, `ack_ts` is left untouched, and `inbox_stats` is rebuilt.
+/// Returns `DbError::NotFound` when the agent is not a recipient.
+pub fn mark_message_read_sync(
+    conn: &DbConn,
+    agent_id: i64,
+    message_id: i64,
+) -> Result<RecipientStateTransition, DbError> {
+    apply_recipient_state_sync(conn, agent_id, message_id, false)
+}
+
+/// Synchronously acknowledge one message for a recipient agent.
+///
+/// Like the async `acknowledge_message`, acknowledging also stamps `read_ts`
+/// when the message was still unread.
+pub fn acknowledge_message_sync(
+    conn: &DbConn,
+    agent_id: i64,
+    message_id: i64,
+) -> Result<RecipientStateTransition, DbError> {
+    apply_recipient_state_sync(conn, agent_id, message_id, true)
+}
+
+fn apply_recipient_state_sync(
+    conn: &DbConn,
+    agent_id: i64,
+    message_id: i64,
+    acknowledge: bool,
+) -> Result<RecipientStateTransition, DbError> {
+    let now = crate::now_micros();
+    begin_sync_write_tx(conn)?;
+
+    let result = (|| -> Result<RecipientStateTransition, DbError> {
+        let rows = conn
+            .query_sync(
+                "SELECT read_ts, ack_ts FROM message_recipients \
+                 WHERE agent_id = ? AND message_id = ? LIMIT 1",
+                &[Value::BigInt(agent_id), Value::BigInt(message_id)],
+            )
+            .map_err(|e| DbError::Sqlite(e.to_string()))?;
+        let row = rows.first().ok_or_else(|| {
+            DbError::not_found("MessageRecipient", format!("{agent_id}:{message_id}"))
+        })?;
+        let prior_read_ts = row.get_named::<i64>("read_ts").ok();
+        let prior_ack_ts = row.get_named::<i64>("ack_ts").ok();
+
+        let changed = if acknowledge {
+            prior_ack_ts.is_none()
+        } else {
+            prior_read_ts.is_none()
+        };
+        if changed {
+            if acknowledge {
+                conn.execute_sync(
+                    "UPDATE message_recipients SET read_ts = COALESCE(read_ts, ?), ack_ts = ? \
+                     WHERE agent_id = ? AND message_id = ? AND ack_ts IS NULL",
+                    &[
+                        Value::BigInt(now),
+                        Value::BigInt(now),
+                        Value::BigInt(agent_id),
+                        Value::BigInt(message_id),
+                    ],
+                )
+            } else {
+                conn.execute_sync(
+                    "UPDATE message_recipients SET read_ts = ? \
+                     WHERE agent_id = ? AND message_id = ? AND read_ts IS NULL",
+                    &[
+                        Value::BigInt(now),
+                        Value::BigInt(agent_id),
+                        Value::BigInt(message_id),
+                    ],
+                )
+            }
+            .map_err(|e| DbError::Sqlite(e.to_string()))?;
+            rebuild_agent_inbox_stats_sync(conn, agent_id)?;
+        }
+
+        Ok(RecipientStateTransition {
+            read_ts: prior_read_ts.or(changed.then_some(now)),
+            ack_ts: prior_ack_ts.or((acknowledge && changed).then_some(now)),
+            changed,
+        })
+    })();
+
+    match result {
+        Ok(transition) => {
+            commit_sync_write_tx(conn)?;
+            Ok(transition)
+        }
+        Err(err) => {
+            rollback_sync_write_tx(conn);
+            Err(err)
+        }
+    }
+}
+
 fn begin_sync_write_tx(conn: &DbConn) -> Result<(), DbError> {
     conn.execute_sync("BEGIN IMMEDIATE", &[])
         .map(|_| ())
@@ -924,6 +1380,321 @@ mod tests {
             .expect("get message id")
     }
 
+    /// Deliver a message with an explicit `created_ts` to `recipient_id`.
+    fn deliver_at(
+        conn: &DbConn,
+        project_id: i64,
+        sender_id: i64,
+        recipient_id: i64,
+        created_ts: i64,
+    ) -> i64 {
+        let msg_id = insert_message(conn, project_id, sender_id, "poll");
+        conn.execute_sync(
+            "UPDATE messages SET created_ts = ? WHERE id = ?",
+            &[Value::BigInt(created_ts), Value::BigInt(msg_id)],
+        )
+        .expect("set created_ts");
+        conn.execute_sync(
+            "INSERT INTO message_recipients (message_id, agent_id, kind) VALUES (?1, ?2, 'to')",
+            &[Value::BigInt(msg_id), Value::BigInt(recipient_id)],
+        )
+        .expect("insert recipient");
+        msg_id
+    }
+
+    /// Poll with the watermark fed back from the previous page until empty.
+    fn drain_by_watermark(
+        conn: &DbConn,
+        project_id: i64,
+        agent_id: i64,
+        watermark: &mut i64,
+        limit: usize,
+    ) -> Vec<i64> {
+        let mut seen = Vec::new();
+        loop {
+            let rows = fetch_inbox_rows_after_watermark_from_conn(
+                conn, project_id, agent_id, false, false, *watermark, limit, false,
+            )
+            .expect("watermark fetch");
+            if rows.is_empty() {
+                return seen;
+            }
+            for row in rows {
+                let id = row.message.id.expect("message id");
+                assert!(id > *watermark, "rows must be strictly after the watermark");
+                *watermark = id;
+                seen.push(id);
+            }
+        }
+    }
+
+    #[test]
+    fn watermark_polling_survives_same_timestamp_messages() {
+        let conn = test_conn();
+        let pid = insert_project(&conn);
+        let sender = insert_agent(&conn, pid, "Sender");
+        let recipient = insert_agent(&conn, pid, "Recipient");
+        let mut watermark = 0;
+
+        let first = deliver_at(&conn, pid, sender, recipient, 5_000_000);
+        assert_eq!(
+            drain_by_watermark(&conn, pid, recipient, &mut watermark, 10),
+            vec![first]
+        );
+
+        // A second message committed with the identical timestamp: a
+        // `since_ts` poll using the last-seen created_ts would never see it.
+        let twin = deliver_at(&conn, pid, sender, recipient, 5_000_000);
+        let since_rows = fetch_inbox_metadata_rows_from_conn(
+            &conn,
+            pid,
+            recipient,
+            false,
+            false,
+            false,
+            Some(5_000_000),
+            10,
+        )
+        .expect("since fetch");
+        assert!(since_rows.is_empty(), "since_ts is approximate by design");
+        assert_eq!(
+            drain_by_watermark(&conn, pid, recipient, &mut watermark, 10),
+            vec![twin]
+        );
+        assert!(drain_by_watermark(&conn, pid, recipient, &mut watermark, 10).is_empty());
+    }
+
+    #[test]
+    fn watermark_polling_survives_clock_skew_and_small_pages() {
+        let conn = test_conn();
+        let pid = insert_project(&conn);
+        let sender = insert_agent(&conn, pid, "Sender");
+        let recipient = insert_agent(&conn, pid, "Recipient");
+        let mut watermark = 0;
+        let mut expected = Vec::new();
+        let mut seen = Vec::new();
+
+        // Later inserts carry earlier created_ts (NTP step backwards), and
+        // pages are smaller than each burst so polls resume mid-burst.
+        for (round, stamps) in [
+            vec![9_000_000, 9_000_000, 8_000_000],
+            vec![7_000_000, 9_500_000, 6_000_000, 6_000_000],
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            for ts in stamps {
+                expected.push(deliver_at(&conn, pid, sender, recipient, ts));
+            }
+            seen.extend(drain_by_watermark(&conn, pid, recipient, &mut watermark, 2));
+            assert_eq!(seen, expected, "round {round}: no skips, no duplicates");
+        }
+        assert_eq!(watermark, *expected.last().expect("messages"));
+    }
+
+    #[test]
+    fn expiry_filter_is_skipped_only_when_the_sidecar_is_missing() {
+        let conn = test_conn();
+        let project_id = insert_project(&conn);
+        let sender = insert_agent(&conn, project_id, "BlueLake");
+        let recipient = insert_agent(&conn, project_id, "RedFox");
+        let id = deliver_at(&conn, project_id, sender, recipient, 1_000_000);
+        conn.execute_sync(
+            "INSERT INTO message_expiries (message_id, expires_ts) VALUES (?, 10)",
+            &[Value::BigInt(id)],
+        )
+        .expect("insert expiry");
+        let fetch = |conn: &DbConn| {
+            fetch_inbox_rows_from_conn(conn, project_id, recipient, false, false, false, None, 10)
+        };
+        assert!(fetch(&conn).expect("fetch with expiry").is_empty());
+
+        conn.execute_raw("DROP TABLE message_expiries")
+            .expect("drop expiry sidecar");
+        assert_eq!(fetch(&conn).expect("fetch without sidecar").len(), 1);
+        let counts =
+            count_inbox_rows_from_conn(&conn, project_id, recipient, false, None, None, Some(20))
+                .expect("count without sidecar");
+        assert_eq!(counts.unread, 1);
+    }
+
+    #[test]
+    fn deferred_recipient_rows_stay_out_of_the_inbox_until_released() {
+        let conn = test_conn();
+        let project_id = insert_project(&conn);
+        let sender = insert_agent(&conn, project_id, "BlueLake");
+        let recipient = insert_agent(&conn, project_id, "RedFox");
+        deliver_at(&conn, project_id, sender, recipient, 1_000_000);
+        let deferred = deliver_at(&conn, project_id, sender, recipient, 1_000_001);
+        conn.execute_sync(
+            "INSERT INTO message_recipient_deferrals (message_id, agent_id, deferred_ts) \
+             VALUES (?, ?, 10)",
+            &[Value::BigInt(deferred), Value::BigInt(recipient)],
+        )
+        .expect("insert deferral");
+        let fetch = |conn: &DbConn| {
+            fetch_inbox_rows_from_conn(conn, project_id, recipient, false, false, false, None, 10)
+        };
+        let count = |conn: &DbConn| {
+            count_inbox_rows_from_conn(conn, project_id, recipient, false, None, None, None)
+                .expect("count")
+                .total
+        };
+        assert_eq!(fetch(&conn).expect("fetch while deferred").len(), 1);
+        assert_eq!(count(&conn), 1);
+
+        conn.execute_sync(
+            "DELETE FROM message_recipient_deferrals WHERE agent_id = ?",
+            &[Value::BigInt(recipient)],
+        )
+        .expect("release deferrals");
+        assert_eq!(fetch(&conn).expect("fetch after release").len(), 2);
+        assert_eq!(count(&conn), 2);
+    }
+
+    #[test]
+    fn count_inbox_rows_matches_fetch_filters() {
+        let conn = test_conn();
+        let project_id = insert_project(&conn);
+        let sender = insert_agent(&conn, project_id, "BlueLake");
+        let recipient = insert_agent(&conn, project_id, "RedFox");
+        let ids: Vec<i64> = (0..6)
+            .map(|i| deliver_at(&conn, project_id, sender, recipient, 1_000_000 + i))
+            .collect();
+        conn.execute_sync(
+            "UPDATE messages SET importance = 'urgent' WHERE id IN (?, ?)",
+            &[Value::BigInt(ids[0]), Value::BigInt(ids[4])],
+        )
+        .expect("mark urgent");
+        conn.execute_sync(
+            "UPDATE message_recipients SET read_ts = 5 WHERE message_id IN (?, ?)",
+            &[Value::BigInt(ids[1]), Value::BigInt(ids[4])],
+        )
+        .expect("mark read");
+        // An expired unread message drops out; an expired read one stays.
+        for id in [ids[2], ids[4]] {
+            conn.execute_sync(
+                "INSERT INTO message_expiries (message_id, expires_ts) VALUES (?, 10)",
+                &[Value::BigInt(id)],
+            )
+            .expect("insert expiry");
+        }
+
+        let all = count_inbox_rows_from_conn(&conn, project_id, recipient, false, None, None, None)
+            .expect("count all");
+        assert_eq!(
+            all,
+            InboxCounts {
+                total: 6,
+                unread: 4,
+                urgent: 2
+            }
+        );
+        let fetched = fetch_inbox_metadata_rows_from_conn(
+            &conn, project_id, recipient, false, false, false, None, 100,
+        )
+        .expect("fetch all");
+        assert_eq!(usize::try_from(all.total).unwrap(), fetched.len());
+
+        let urgent =
+            count_inbox_rows_from_conn(&conn, project_id, recipient, true, None, None, None)
+                .expect("count urgent");
+        assert_eq!(urgent.total, 2);
+        let since = count_inbox_rows_from_conn(
+            &conn,
+            project_id,
+            recipient,
+            false,
+            Some(1_000_002),
+            None,
+            None,
+        )
+        .expect("count since");
+        assert_eq!(since.total, 3);
+        let after = count_inbox_rows_from_conn(
+            &conn,
+            project_id,
+            recipient,
+            false,
+            None,
+            Some(ids[3]),
+            None,
+        )
+        .expect("count after watermark");
+        assert_eq!(after.total, 2);
+        let unexpired =
+            count_inbox_rows_from_conn(&conn, project_id, recipient, false, None, None, Some(100))
+                .expect("count unexpired");
+        assert_eq!(
+            unexpired,
+            InboxCounts {
+                total: 5,
+                unread: 3,
+                urgent: 2
+            }
+        );
+    }
+
+    #[test]
+    fn fetch_inbox_as_of_includes_boundary_and_masks_later_reads() {
+        let conn = test_conn();
+        let pid = insert_project(&conn);
+        let sender = insert_agent(&conn, pid, "BlueLake");
+        let recipient = insert_agent(&conn, pid, "RedFox");
+        let as_of = 2_000_000;
+        let before = deliver_at(&conn, pid, sender, recipient, as_of - 1);
+        let at = deliver_at(&conn, pid, sender, recipient, as_of);
+        let after = deliver_at(&conn, pid, sender, recipient, as_of + 1);
+        // `before` was read exactly at the instant, `at` only afterwards.
+        for (id, read_ts) in [(before, as_of), (at, as_of + 1), (after, as_of + 2)] {
+            conn.execute_sync(
+                "UPDATE message_recipients SET read_ts = ?, ack_ts = ? WHERE message_id = ?",
+                &[
+                    Value::BigInt(read_ts),
+                    Value::BigInt(read_ts),
+                    Value::BigInt(id),
+                ],
+            )
+            .expect("mark read");
+        }
+
+        let rows =
+            fetch_inbox_rows_as_of_from_conn(&conn, pid, recipient, false, None, as_of, 10, false)
+                .expect("as-of fetch");
+        let ids: Vec<i64> = rows.iter().filter_map(|row| row.message.id).collect();
+        assert_eq!(
+            ids,
+            vec![at, before],
+            "created exactly at as_of is included"
+        );
+        assert_eq!(rows[0].read_ts, None, "read after as_of counts as unread");
+        assert_eq!(rows[0].ack_ts, None);
+        assert_eq!(rows[1].read_ts, Some(as_of), "read exactly at as_of counts");
+        assert_eq!(rows[1].ack_ts, Some(as_of));
+
+        let earlier = fetch_inbox_rows_as_of_from_conn(
+            &conn,
+            pid,
+            recipient,
+            false,
+            None,
+            as_of - 1,
+            10,
+            false,
+        )
+        .expect("earlier as-of fetch");
+        assert_eq!(earlier.len(), 1);
+        assert_eq!(earlier[0].message.id, Some(before));
+        assert_eq!(earlier[0].read_ts, None);
+
+        let limited =
+            fetch_inbox_rows_as_of_from_conn(&conn, pid, recipient, false, None, as_of, 1, true)
+                .expect("limited as-of fetch");
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].message.id, Some(at));
+        assert_eq!(limited[0].message.body_md, "test body");
+    }
+
     #[test]
     fn fetch_inbox_metadata_rows_omit_body_payload() {
         let conn = test_conn();
@@ -1774,4 +2545,43 @@ mod tests {
             "empty mark-read batch should not create or open a live DB"
         );
     }
+
+    #[test]
+    fn acknowledge_message_sync_stamps_read_and_ack_once() {
+        let conn = test_conn();
+        let pid = insert_project(&conn);
+        let sender = insert_agent(&conn, pid, "Sender");
+        let recipient = insert_agent(&conn, pid, "BlueLake");
+        let msg_id = deliver_at(&conn, pid, sender, recipient, 1_000_000);
+
+        let first = acknowledge_message_sync(&conn, recipient, msg_id).expect("first ack");
+        assert!(first.changed);
+        assert!(first.ack_ts.is_some());
+        assert_eq!(
+            first.read_ts, first.ack_ts,
+            "ack should also mark unread rows read"
+        );
+
+        let second = acknowledge_message_sync(&conn, recipient, msg_id).expect("repeat ack");
+        assert!(!second.changed, "repeat ack must report no change");
+        assert_eq!(second.ack_ts, first.ack_ts);
+    }
+
+    #[test]
+    fn mark_message_read_sync_leaves_ack_untouched_and_rejects_non_recipients() {
+        let conn = test_conn();
+        let pid = insert_project(&conn);
+        let sender = insert_agent(&conn, pid, "Sender");
+        let recipient = insert_agent(&conn, pid, "BlueLake");
+        let msg_id = deliver_at(&conn, pid, sender, recipient, 1_000_000);
+
+        let read = mark_message_read_sync(&conn, recipient, msg_id).expect("mark read");
+        assert!(read.changed);
+        assert!(read.read_ts.is_some());
+        assert_eq!(read.ack_ts, None);
+
+        let err =
+            mark_message_read_sync(&conn, sender, msg_id).expect_err("sender is not a recipient");
+        assert!(matches!(err, DbError::NotFound { .. }));
+    }
 }
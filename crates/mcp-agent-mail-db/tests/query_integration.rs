@@ -474,21 +474,26 @@ fn acknowledge_message_idempotent() {
 
     // First ack (agent_id, message_id)
     let pool2 = pool.clone();
-    block_on(|cx| async move {
+    let first = block_on(|cx| async move {
         match queries::acknowledge_message(&cx, &pool2, recip_id, msg_id).await {
-            Outcome::Ok(_) => {}
+            Outcome::Ok(ack) => ack,
             other => panic!("first ack failed: {other:?}"),
         }
     });
+    assert!(first.changed);
+    assert_eq!(first.already(), None);
 
-    // Second ack — should succeed (idempotent)
+    // Second ack — should succeed (idempotent) and report the prior state
     let pool3 = pool.clone();
-    block_on(|cx| async move {
+    let second = block_on(|cx| async move {
         match queries::acknowledge_message(&cx, &pool3, recip_id, msg_id).await {
-            Outcome::Ok(_) => {}
+            Outcome::Ok(ack) => ack,
             other => panic!("second ack failed: {other:?}"),
         }
     });
+    assert!(!second.changed);
+    assert_eq!(second.already(), Some("acked"));
+    assert_eq!(second.ack_ts, first.ack_ts);
 }
 
 #[test]
@@ -528,11 +533,11 @@ fn acknowledge_messages_batch_integration_large_ack_wave_uses_fixed_recipient_qu
     });
 
     let first_message_id = message_ids[0];
-    let (original_read_ts, original_ack_ts) = block_on(|cx| {
+    let original = block_on(|cx| {
         let pool = pool.clone();
         async move {
             match queries::acknowledge_message(&cx, &pool, recip_id, first_message_id).await {
-                Outcome::Ok(timestamps) => timestamps,
+                Outcome::Ok(ack) => ack,
                 other => panic!("pre-ack first message failed: {other:?}"),
             }
         }
@@ -583,8 +588,12 @@ fn acknowledge_messages_batch_integration_large_ack_wave_uses_fixed_recipient_qu
         .iter()
         .find(|result| result.message_id == message_ids[0])
         .expect("pre-acked message should be present");
-    assert_eq!(first.read_ts, Some(original_read_ts));
-    assert_eq!(first.ack_ts, Some(original_ack_ts));
+    assert_eq!(first.read_ts, Some(original.read_ts));
+    assert_eq!(first.ack_ts, Some(original.ack_ts));
+    assert!(
+        !first.changed,
+        "pre-acked message should not report a change"
+    );
 
     assert!(
         snapshot.total <= 7,
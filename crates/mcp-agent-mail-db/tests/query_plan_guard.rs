@@ -0,0 +1,155 @@
+//! Query-plan guard for the registered hot queries.
+//!
+//! Seeds a small but representative mailbox, runs `EXPLAIN QUERY PLAN` for
+//! every [`HotQueryPath`], and fails with an expected-vs-actual report when a
+//! plan stops using its expected index or scans a sensitive table. Runs as a
+//! plain cargo test and as the "Hot query plan guard" `am ci` gate.
+
+mod common;
+
+use mcp_agent_mail_db::DbConn;
+use mcp_agent_mail_db::query_plan_diagnostics::{HotQueryPath, summarize_hot_query_plans};
+use sqlmodel_core::Value;
+
+const PROJECTS: i64 = 3;
+const AGENTS_PER_PROJECT: i64 = 8;
+const MESSAGES_PER_PROJECT: i64 = 120;
+const RESERVATIONS_PER_PROJECT: i64 = 24;
+const NOW_US: i64 = 1_700_000_000_000_000;
+
+const AGENT_NAMES: [&str; 8] = [
+    "BlueLake",
+    "GreenCastle",
+    "RedStone",
+    "LavenderBear",
+    "SilverFox",
+    "AmberHill",
+    "CopperRiver",
+    "IvoryPeak",
+];
+
+fn seeded_conn(dir: &tempfile::TempDir) -> DbConn {
+    let path = dir.path().join("plan_guard.sqlite3");
+    let conn = DbConn::open_file(path.display().to_string()).expect("open DB file");
+    conn.execute_raw(mcp_agent_mail_db::schema::PRAGMA_DB_INIT_SQL)
+        .expect("apply PRAGMAs");
+    common::block_on(|cx| {
+        let conn = &conn;
+        async move {
+            mcp_agent_mail_db::schema::migrate_to_latest_base(&cx, conn)
+                .await
+                .into_result()
+                .expect("apply migrations");
+        }
+    });
+
+    let exec = |sql: &str, params: &[Value]| {
+        conn.execute_sync(sql, params)
+            .unwrap_or_else(|error| panic!("seed failed for {sql}: {error}"));
+    };
+    exec(
+        "INSERT INTO products (product_uid, name, created_at) VALUES (?, ?, ?)",
+        &[
+            Value::Text("plan-guard".to_string()),
+            Value::Text("plan-guard".to_string()),
+            Value::BigInt(NOW_US),
+        ],
+    );
+    for project in 1..=PROJECTS {
+        exec(
+            "INSERT INTO projects (id, slug, human_key, created_at) VALUES (?, ?, ?, ?)",
+            &[
+                Value::BigInt(project),
+                Value::Text(format!("project-{project}")),
+                Value::Text(format!("/work/project-{project}")),
+                Value::BigInt(NOW_US),
+            ],
+        );
+        exec(
+            "INSERT INTO product_project_links (product_id, project_id, created_at) VALUES (1, ?, ?)",
+            &[Value::BigInt(project), Value::BigInt(NOW_US)],
+        );
+        let first_agent = (project - 1) * AGENTS_PER_PROJECT + 1;
+        for (offset, name) in (0..AGENTS_PER_PROJECT).zip(AGENT_NAMES) {
+            exec(
+                "INSERT INTO agents (id, project_id, name, program, model, inception_ts, last_active_ts) \
+                 VALUES (?, ?, ?, 'codex-cli', 'gpt-5', ?, ?)",
+                &[
+                    Value::BigInt(first_agent + offset),
+                    Value::BigInt(project),
+                    Value::Text(name.to_string()),
+                    Value::BigInt(NOW_US),
+                    Value::BigInt(NOW_US),
+                ],
+            );
+        }
+        for n in 0..MESSAGES_PER_PROJECT {
+            let message_id = (project - 1) * MESSAGES_PER_PROJECT + n + 1;
+            let sender = first_agent + n % AGENTS_PER_PROJECT;
+            let recipient = first_agent + (n + 1) % AGENTS_PER_PROJECT;
+            let ack_required = i64::from(n % 4 == 0);
+            let importance = if n % 10 == 0 { "urgent" } else { "normal" };
+            exec(
+                "INSERT INTO messages (id, project_id, sender_id, thread_id, subject, body_md, \
+                 importance, ack_required, created_ts) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                &[
+                    Value::BigInt(message_id),
+                    Value::BigInt(project),
+                    Value::BigInt(sender),
+                    Value::Text(format!("thread-{}", n % 12)),
+                    Value::Text(format!("Subject {n}")),
+                    Value::Text(format!("Body for message {n} needle")),
+                    Value::Text(importance.to_string()),
+                    Value::BigInt(ack_required),
+                    Value::BigInt(NOW_US + n),
+                ],
+            );
+            let ack_ts = if n % 8 == 0 {
+                Value::BigInt(NOW_US + n + 1)
+            } else {
+                Value::Null
+            };
+            exec(
+                "INSERT INTO message_recipients (message_id, agent_id, kind, ack_ts) \
+                 VALUES (?, ?, 'to', ?)",
+                &[Value::BigInt(message_id), Value::BigInt(recipient), ack_ts],
+            );
+        }
+        for n in 0..RESERVATIONS_PER_PROJECT {
+            let released = if n % 3 == 0 {
+                Value::BigInt(NOW_US + n)
+            } else {
+                Value::Null
+            };
+            exec(
+                "INSERT INTO file_reservations (project_id, agent_id, path_pattern, \"exclusive\", \
+                 reason, created_ts, expires_ts, released_ts) VALUES (?, ?, ?, ?, 'guard', ?, ?, ?)",
+                &[
+                    Value::BigInt(project),
+                    Value::BigInt(first_agent + n % AGENTS_PER_PROJECT),
+                    Value::Text(format!("src/module_{n}/**")),
+                    Value::BigInt(i64::from(n % 2 == 0)),
+                    Value::BigInt(NOW_US),
+                    Value::BigInt(NOW_US + 3_600_000_000),
+                    released,
+                ],
+            );
+        }
+    }
+    conn
+}
+
+#[test]
+fn hot_query_plans_use_expected_indexes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let conn = seeded_conn(&dir);
+    let summary = summarize_hot_query_plans(&conn).expect("explain hot query plans");
+
+    assert_eq!(summary.diagnostics.len(), HotQueryPath::ALL.len());
+    if let Some(report) = summary.regression_report() {
+        panic!("hot query plan guard failed\n\n{report}");
+    }
+    for diagnostic in &summary.diagnostics {
+        eprintln!("[plan-guard] {}: {}", diagnostic.path, diagnostic.detail);
+    }
+}
@@ -1202,6 +1202,9 @@ fn serde_search_query_complex() {
         importance: vec![Importance::Urgent, Importance::High],
         direction: Some(Direction::Inbox),
         agent_name: Some("BlueLake".to_string()),
+        sender_name: None,
+        recipient_name: None,
+        field_terms: Vec::new(),
         thread_id: Some("thread-42".to_string()),
         ack_required: Some(true),
         time_range: TimeRange {
@@ -1676,6 +1679,9 @@ fn complex_combined_query() {
         importance: vec![Importance::High, Importance::Urgent],
         direction: Some(Direction::Inbox),
         agent_name: Some("RedHarbor".to_string()),
+        sender_name: None,
+        recipient_name: None,
+        field_terms: Vec::new(),
         thread_id: Some("br-123".to_string()),
         ack_required: Some(true),
         time_range: TimeRange {
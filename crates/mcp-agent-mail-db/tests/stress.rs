@@ -1032,6 +1032,92 @@ fn stress_concurrent_read_ack() {
     });
 }
 
+// =============================================================================
+// Test: Racing acknowledgements report the transition exactly once
+// =============================================================================
+
+#[test]
+fn stress_racing_acks_report_change_exactly_once() {
+    const ROUNDS: usize = 16;
+
+    let (pool, _dir) = make_pool();
+    let human_key = format!("/data/stress/ack_once_{}", unique_suffix());
+
+    let (receiver_id, message_ids) = {
+        let p = pool.clone();
+        block_on(|cx| async move {
+            let pid = match queries::ensure_project(&cx, &p, &human_key).await {
+                Outcome::Ok(r) => r.id.unwrap(),
+                _ => panic!("ensure_project failed"),
+            };
+            let mut agent_ids = Vec::new();
+            for name in ["BoldFox", "QuietOwl"] {
+                match queries::register_agent(&cx, &p, pid, name, "test", "test", None, None, None)
+                    .await
+                {
+                    Outcome::Ok(r) => agent_ids.push(r.id.unwrap()),
+                    _ => panic!("register_agent failed"),
+                }
+            }
+            let mut message_ids = Vec::with_capacity(ROUNDS);
+            for round in 0..ROUNDS {
+                let msg = match queries::create_message_with_recipients(
+                    &cx,
+                    &p,
+                    pid,
+                    agent_ids[0],
+                    &format!("ack race {round}"),
+                    "Body",
+                    None,
+                    "normal",
+                    true,
+                    "[]",
+                    &[(agent_ids[1], "to")],
+                )
+                .await
+                {
+                    Outcome::Ok(r) => r,
+                    _ => panic!("create_message_with_recipients failed"),
+                };
+                message_ids.push(msg.id.unwrap());
+            }
+            (agent_ids[1], message_ids)
+        })
+    };
+
+    for msg_id in message_ids {
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let pool = pool.clone();
+                let barrier = Arc::clone(&barrier);
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    acknowledge_message(&pool, receiver_id, msg_id)
+                })
+            })
+            .collect();
+        let acks: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(
+            acks.iter().filter(|ack| ack.changed).count(),
+            1,
+            "exactly one racing ack should report the change for message {msg_id}: {acks:?}"
+        );
+        assert_eq!(
+            acks[0].ack_ts, acks[1].ack_ts,
+            "both callers see the stored ack_ts"
+        );
+        let loser = acks.iter().find(|ack| !ack.changed).unwrap();
+        assert_eq!(loser.already(), Some("acked"));
+
+        let read = mark_message_read(&pool, receiver_id, msg_id);
+        assert!(!read.changed);
+        assert_eq!(read.already(), Some("acked"));
+        assert_eq!(read.ack_ts, Some(acks[0].ack_ts));
+    }
+}
+
 // =============================================================================
 // Test: Pool exhaustion recovery (all connections busy → wait → succeed)
 // =============================================================================
@@ -2135,12 +2221,12 @@ fn get_inbox_stats(pool: &DbPool, agent_id: i64) -> InboxStatsRow {
         .unwrap_or_else(|| panic!("expected inbox stats row for agent {agent_id}, got None"))
 }
 
-fn mark_message_read(pool: &DbPool, agent_id: i64, message_id: i64) -> i64 {
+fn mark_message_read(pool: &DbPool, agent_id: i64, message_id: i64) -> queries::ReadTransition {
     block_on(|cx| {
         let pool = pool.clone();
         async move {
             match queries::mark_message_read(&cx, &pool, agent_id, message_id).await {
-                Outcome::Ok(ts) => ts,
+                Outcome::Ok(state) => state,
                 Outcome::Err(e) => {
                     panic!("mark_message_read failed for {agent_id}:{message_id}: {e:?}")
                 }
@@ -2153,12 +2239,12 @@ fn mark_message_read(pool: &DbPool, agent_id: i64, message_id: i64) -> i64 {
     })
 }
 
-fn acknowledge_message(pool: &DbPool, agent_id: i64, message_id: i64) -> (i64, i64) {
+fn acknowledge_message(pool: &DbPool, agent_id: i64, message_id: i64) -> queries::AckTransition {
     block_on(|cx| {
         let pool = pool.clone();
         async move {
             match queries::acknowledge_message(&cx, &pool, agent_id, message_id).await {
-                Outcome::Ok(ts) => ts,
+                Outcome::Ok(state) => state,
                 Outcome::Err(e) => {
                     panic!("acknowledge_message failed for {agent_id}:{message_id}: {e:?}")
                 }
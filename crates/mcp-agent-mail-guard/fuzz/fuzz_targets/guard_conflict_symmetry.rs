@@ -23,6 +23,7 @@ struct ConflictInput {
 
 fn reservation(input: &ReservationInput, ignorecase: bool) -> FileReservationRecord {
     FileReservationRecord {
+        id: None,
         path_pattern: input.pattern.clone(),
         agent_name: input.holder.clone(),
         exclusive: input.exclusive,
@@ -82,6 +83,7 @@ fuzz_target!(|input: ConflictInput| {
         };
 
         let self_owned = FileReservationRecord {
+            id: None,
             path_pattern: path.clone(),
             agent_name: input.self_agent.clone(),
             exclusive: true,
@@ -103,6 +105,7 @@ fuzz_target!(|input: ConflictInput| {
         );
 
         let other_owned = FileReservationRecord {
+            id: None,
             path_pattern: path.clone(),
             agent_name: other_agent.to_string(),
             exclusive: true,
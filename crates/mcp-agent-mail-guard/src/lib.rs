@@ -52,19 +52,107 @@ pub struct GuardStatus {
     pub hooks_dir: String,
     pub pre_commit_present: bool,
     pub pre_push_present: bool,
+    pub commit_context_present: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct GuardConflict {
     pub path: String,
     pub pattern: String,
     pub holder: String,
     pub expires_ts: String,
+    /// Reservation id, when the archive record carries one.
+    pub reservation_id: Option<i64>,
+    pub exclusive: bool,
+    /// Holder details from the archived agent profile, when one exists.
+    pub holder_program: Option<String>,
+    pub holder_model: Option<String>,
+    pub holder_last_active_ts: Option<String>,
+}
+
+impl GuardConflict {
+    /// Seconds until the reservation lapses, or `None` if `expires_ts` does not parse.
+    #[must_use]
+    pub fn remaining_secs(&self, now: &chrono::DateTime<chrono::Utc>) -> Option<i64> {
+        parse_reservation_ts(&self.expires_ts).map(|ts| (ts - *now).num_seconds().max(0))
+    }
+
+    /// Remaining TTL in human units, e.g. `1h 5m`.
+    #[must_use]
+    pub fn remaining_ttl(&self, now: &chrono::DateTime<chrono::Utc>) -> Option<String> {
+        self.remaining_secs(now).map(format_ttl)
+    }
+
+    /// Command an operator can run to release the reservation. Falls back to
+    /// `--paths` for archive records written before ids were recorded.
+    #[must_use]
+    pub fn release_command(&self, project: &str) -> String {
+        let target = match self.reservation_id {
+            Some(id) => format!("--ids {id}"),
+            None => format!("--paths '{}'", self.pattern.replace('\'', "'\\''")),
+        };
+        format!(
+            "am file_reservations release {project} {} {target}",
+            self.holder
+        )
+    }
+
+    /// Resolution for agents, who should ask the holder rather than release.
+    #[must_use]
+    pub fn mail_hint(&self) -> String {
+        format!(
+            "send mail to {} asking them to release '{}'",
+            self.holder, self.pattern
+        )
+    }
+
+    /// `program/model, exclusive, last active <ts>` for the holder; fields
+    /// without a profile are omitted.
+    #[must_use]
+    pub fn holder_details(&self) -> String {
+        let mut details = Vec::new();
+        match (&self.holder_program, &self.holder_model) {
+            (Some(program), Some(model)) => details.push(format!("{program}/{model}")),
+            (Some(only), None) | (None, Some(only)) => details.push(only.clone()),
+            (None, None) => {}
+        }
+        let mode = if self.exclusive {
+            "exclusive"
+        } else {
+            "shared"
+        };
+        details.push(mode.to_string());
+        if let Some(last_active) = &self.holder_last_active_ts {
+            details.push(format!("last active {last_active}"));
+        }
+        details.join(", ")
+    }
+}
+
+/// Format a TTL in seconds as `2d 3h`, `1h 5m`, `4m 10s`, or `45s`.
+fn format_ttl(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        (secs % 86_400) / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
 }
 
 /// A parsed file reservation from the archive JSON files.
 #[derive(Debug, Clone)]
 pub struct FileReservationRecord {
+    pub id: Option<i64>,
     pub path_pattern: String,
     pub agent_name: String,
     pub exclusive: bool,
@@ -250,6 +338,7 @@ fn is_legacy_single_file_guard(contents: &str) -> bool {
 
 fn render_chain_runner_script(hook_name: &str) -> String {
     // Mirrors legacy behavior: run hooks.d/<hook>/* in lexical order; forward stdin for pre-push.
+    // Hook arguments (e.g. the message file for prepare-commit-msg) go to every child.
     let mut lines: Vec<String> = vec![
         "#!/usr/bin/env python3".to_string(),
         format!("# mcp-agent-mail chain-runner ({hook_name})"),
@@ -288,9 +377,9 @@ fn render_chain_runner_script(hook_name: &str) -> String {
         "def _run_child(path: Path, * , stdin_bytes=None):".to_string(),
         "    # On Windows, prefer 'python' for .py plugins to avoid PATHEXT reliance.".to_string(),
         "    if os.name != 'posix' and path.suffix.lower() == '.py':".to_string(),
-        "        return subprocess.run([sys.executable, str(path)], input=stdin_bytes, check=False).returncode"
+        "        return subprocess.run([sys.executable, str(path), *sys.argv[1:]], input=stdin_bytes, check=False).returncode"
             .to_string(),
-        "    return subprocess.run([str(path)], input=stdin_bytes, check=False).returncode"
+        "    return subprocess.run([str(path), *sys.argv[1:]], input=stdin_bytes, check=False).returncode"
             .to_string(),
         "".to_string(),
     ];
@@ -328,29 +417,11 @@ fn render_chain_runner_script(hook_name: &str) -> String {
     format!("{}\n", lines.join("\n"))
 }
 
-fn render_guard_plugin_script(project: &str, hook_name: &str) -> String {
-    // Real guard plugin: checks active file reservations against staged changes (pre-commit)
-    // or pushed commits (pre-push).
-    let project_json = serde_json::to_string(project).unwrap_or_else(|_| "\"\"".to_string());
-    let hook_name_json = serde_json::to_string(hook_name).unwrap_or_else(|_| "\"\"".to_string());
-    let template = r#"#!/usr/bin/env python3
-# mcp-agent-mail guard plugin (__HOOK_NAME_TEXT__)
-# project: __PROJECT_TEXT__
-# Auto-generated by mcp-agent-mail install_guard
-
-import datetime
-import json
-import os
-import re
-import subprocess
-import sys
-
-PROJECT = __PROJECT_JSON__
-HOOK_NAME = __HOOK_NAME_JSON__
-AGENT_NAME = os.environ.get("AGENT_NAME", "").strip()
-GUARD_MODE = os.environ.get("AGENT_MAIL_GUARD_MODE", "block")
-
-# br-8ujfs.5.5 (E5) — Python-side SIGSEGV retry for git 2.51.0 index race.
+/// Python helpers shared by the generated hook plugins: SIGSEGV-tolerant git
+/// calls, staged/pushed path discovery, archive resolution, active
+/// reservation loading, and the reservation glob matcher. Each plugin defines
+/// `PROJECT` before splicing these in.
+const GUARD_PLUGIN_HELPERS: &str = r#"# br-8ujfs.5.5 (E5) — Python-side SIGSEGV retry for git 2.51.0 index race.
 # Matches the Rust retry policy (3 retries, 100/400/1600ms jittered).
 # Only retries on segfault-shaped exits (139 = 128+11, 135 = 128+7,
 # negative -11/-7 on POSIX); all other nonzero returncodes propagate.
@@ -1010,7 +1081,31 @@ def glob_match(path, pattern):
         )
         return True
 
-def check_conflicts(paths, reservations):
+"#;
+
+fn render_guard_plugin_script(project: &str, hook_name: &str) -> String {
+    // Real guard plugin: checks active file reservations against staged changes (pre-commit)
+    // or pushed commits (pre-push).
+    let project_json = serde_json::to_string(project).unwrap_or_else(|_| "\"\"".to_string());
+    let hook_name_json = serde_json::to_string(hook_name).unwrap_or_else(|_| "\"\"".to_string());
+    let template = r#"#!/usr/bin/env python3
+# mcp-agent-mail guard plugin (__HOOK_NAME_TEXT__)
+# project: __PROJECT_TEXT__
+# Auto-generated by mcp-agent-mail install_guard
+
+import datetime
+import json
+import os
+import re
+import subprocess
+import sys
+
+PROJECT = __PROJECT_JSON__
+HOOK_NAME = __HOOK_NAME_JSON__
+AGENT_NAME = os.environ.get("AGENT_NAME", "").strip()
+GUARD_MODE = os.environ.get("AGENT_MAIL_GUARD_MODE", "block")
+
+__GUARD_HELPERS__def check_conflicts(paths, reservations):
     """Check if any paths conflict with active reservations."""
     self_agent = AGENT_NAME.lower()
     conflicts = []
@@ -1100,12 +1195,268 @@ if __name__ == "__main__":
 "#;
 
     template
+        .replace("__GUARD_HELPERS__", GUARD_PLUGIN_HELPERS)
         .replace("__HOOK_NAME_TEXT__", hook_name)
         .replace("__PROJECT_TEXT__", &project.replace(['\n', '\r'], " "))
         .replace("__PROJECT_JSON__", &project_json)
         .replace("__HOOK_NAME_JSON__", &hook_name_json)
 }
 
+/// Hook that receives the commit-context plugin.
+pub const COMMIT_CONTEXT_HOOK: &str = "prepare-commit-msg";
+
+/// First line of the block the commit-context plugin appends, after the
+/// comment character.
+pub const COMMIT_CONTEXT_BEGIN: &str = "agent-mail context (comment lines never reach the commit)";
+
+fn render_commit_context_plugin_script(project: &str) -> String {
+    // Advisory prepare-commit-msg plugin: appends a commented summary of the
+    // agent's reservations over the staged files plus urgent-unread and
+    // pending-ack counts. Never blocks and never writes a non-comment line.
+    let project_json = serde_json::to_string(project).unwrap_or_else(|_| "\"\"".to_string());
+    let begin_json =
+        serde_json::to_string(COMMIT_CONTEXT_BEGIN).unwrap_or_else(|_| "\"\"".to_string());
+    let template = r##"#!/usr/bin/env python3
+# mcp-agent-mail commit-context plugin (prepare-commit-msg)
+# project: __PROJECT_TEXT__
+# Auto-generated by mcp-agent-mail install_commit_context_hook
+
+import datetime
+import json
+import os
+import re
+import shutil
+import subprocess
+import sys
+import time
+
+STARTED = time.monotonic()
+
+PROJECT = __PROJECT_JSON__
+HOOK_NAME = "prepare-commit-msg"
+AGENT_NAME = os.environ.get("AGENT_NAME", "").strip()
+BLOCK_BEGIN = __BEGIN_JSON__
+BLOCK_END = "end agent-mail context"
+
+def budget_seconds():
+    raw = os.environ.get("AGENT_MAIL_COMMIT_CONTEXT_BUDGET_MS", "").strip()
+    try:
+        value = int(raw) if raw else 100
+    except ValueError:
+        value = 100
+    return max(value, 0) / 1000.0
+
+DEADLINE = STARTED + budget_seconds()
+
+__GUARD_HELPERS__
+def is_truthy(val):
+    if not val:
+        return False
+    return str(val).strip().lower() in ("1", "true", "t", "yes", "y")
+
+def git_config(key):
+    res = _run_git_with_retry(["git", "config", key], capture_output=True, text=True)
+    if res.returncode != 0:
+        return ""
+    return (res.stdout or "").strip()
+
+def comment_prefix():
+    """Comment character git strips, or None when the message is not stripped."""
+    if git_config("commit.cleanup").lower() not in ("", "default", "strip"):
+        return None
+    value = git_config("core.commentChar")
+    if not value:
+        return "#"
+    if value == "auto":
+        return None
+    return value
+
+def am_binary():
+    explicit = os.environ.get("AM_BIN", "").strip()
+    if explicit:
+        return explicit
+    return shutil.which("am")
+
+def start_count_queries():
+    """Launch the count-only queries so they run while the archive is read."""
+    am = am_binary()
+    if not am:
+        return {}
+    queries = {
+        "urgent_unread": (
+            [am, "mail", "inbox", "--project", PROJECT, "--agent", AGENT_NAME,
+             "--urgent-only", "--count-only", "--json"],
+            "unread_count",
+        ),
+        "pending_acks": (
+            [am, "acks", "pending", PROJECT, AGENT_NAME, "--count-only", "--json"],
+            "total",
+        ),
+    }
+    started = {}
+    for key, (argv, field) in queries.items():
+        try:
+            proc = subprocess.Popen(
+                argv,
+                stdin=subprocess.DEVNULL,
+                stdout=subprocess.PIPE,
+                stderr=subprocess.DEVNULL,
+            )
+        except Exception:
+            continue
+        started[key] = (proc, field)
+    return started
+
+def collect_count(proc, field):
+    """Read one count before the deadline; None on timeout or any failure."""
+    try:
+        remaining = DEADLINE - time.monotonic()
+        if remaining <= 0:
+            raise subprocess.TimeoutExpired(proc.args, 0)
+        out, _ = proc.communicate(timeout=remaining)
+    except Exception:
+        try:
+            proc.kill()
+            proc.wait(timeout=0.05)
+        except Exception:
+            pass
+        return None
+    if proc.returncode != 0:
+        return None
+    try:
+        value = json.loads(out)[field]
+    except Exception:
+        return None
+    if isinstance(value, bool) or not isinstance(value, int) or value < 0:
+        return None
+    return value
+
+def reservation_covers(path, pattern):
+    normalized_f = normalize_match_input(path)
+    normalized_pattern = normalize_match_input(pattern)
+    if not normalized_f or not normalized_pattern:
+        return False
+    if glob_match(normalized_f, normalized_pattern):
+        return True
+    if normalized_pattern.startswith(normalized_f + "/"):
+        return True
+    has_glob = any(c in pattern for c in "*?[{")
+    return not has_glob and normalized_f.startswith(normalized_pattern + "/")
+
+def own_reservations_over(paths):
+    self_agent = AGENT_NAME.lower()
+    covered = []
+    for res in get_active_reservations():
+        if res.get("agent_name", "").lower() != self_agent:
+            continue
+        hits = [f for f in paths if reservation_covers(f, res["path_pattern"])]
+        if hits:
+            covered.append((res["path_pattern"], res.get("expires_ts"), len(hits)))
+    return covered
+
+def render_block(comment, reservations, counts):
+    lines = ["", f"{comment} {BLOCK_BEGIN}", f"{comment} agent: {AGENT_NAME}"]
+    if reservations:
+        lines.append(f"{comment} reservations covering staged files:")
+        for pattern, expires, hits in reservations:
+            noun = "file" if hits == 1 else "files"
+            lines.append(f"{comment}   {pattern} ({hits} staged {noun}, expires {expires})")
+    else:
+        lines.append(f"{comment} reservations covering staged files: none")
+    if counts.get("urgent_unread") is not None:
+        lines.append(f"{comment} unread urgent messages: {counts['urgent_unread']}")
+    if counts.get("pending_acks") is not None:
+        lines.append(f"{comment} pending acks: {counts['pending_acks']}")
+    lines.append(f"{comment} {BLOCK_END}")
+    return "\n".join(lines) + "\n"
+
+def main():
+    if len(sys.argv) < 2 or not AGENT_NAME:
+        return
+    if is_truthy(os.environ.get("AGENT_MAIL_BYPASS")):
+        return
+    # Only messages that go through the editor are comment-stripped; -m, -F,
+    # merges, squashes, and amends keep whatever this hook would write.
+    source = sys.argv[2] if len(sys.argv) > 2 else ""
+    if source not in ("", "template"):
+        return
+    comment = comment_prefix()
+    if comment is None:
+        return
+    message_path = sys.argv[1]
+    with open(message_path, "r", encoding="utf-8") as handle:
+        if BLOCK_BEGIN in handle.read():
+            return
+
+    queries = start_count_queries()
+    reservations = own_reservations_over(get_staged_files())
+    counts = {key: collect_count(proc, field) for key, (proc, field) in queries.items()}
+    if not reservations and all(value is None for value in counts.values()):
+        return
+
+    with open(message_path, "a", encoding="utf-8") as handle:
+        handle.write(render_block(comment, reservations, counts))
+
+if __name__ == "__main__":
+    try:
+        main()
+    except BaseException:
+        # Advisory only: any failure leaves the message untouched.
+        pass
+    sys.exit(0)
+"##;
+
+    template
+        .replace("__GUARD_HELPERS__", GUARD_PLUGIN_HELPERS)
+        .replace("__PROJECT_TEXT__", &project.replace(['\n', '\r'], " "))
+        .replace("__PROJECT_JSON__", &project_json)
+        .replace("__BEGIN_JSON__", &begin_json)
+}
+
+/// Install the chain-runner for `name` in `hooks_dir` (backing up a foreign
+/// hook to `<name>.orig`) and drop `plugin` into `hooks.d/<name>/`.
+fn install_hook_plugin(hooks_dir: &Path, name: &str, plugin: &str) -> GuardResult<()> {
+    // Ensure hooks.d/<name> exists
+    let run_dir = hooks_dir.join("hooks.d").join(name);
+    std::fs::create_dir_all(&run_dir)?;
+
+    let chain_path = hooks_dir.join(name);
+    if chain_path.exists() {
+        let content = std::fs::read_to_string(&chain_path).unwrap_or_default();
+        let content = content.trim();
+        // Idempotent: backup if not ours
+        if !content.contains(&format!("mcp-agent-mail chain-runner ({name})")) {
+            let orig = hooks_dir.join(format!("{name}.orig"));
+            if !orig.exists() {
+                std::fs::rename(&chain_path, &orig)?;
+            }
+        }
+    }
+
+    // Write chain-runner
+    let chain_script = render_chain_runner_script(name);
+    write_guard_file_atomic(&chain_path, &chain_script, true)?;
+
+    // Windows shims
+    let cmd_path = hooks_dir.join(format!("{name}.cmd"));
+    if !cmd_path.exists() {
+        let body = format!(
+            "@echo off\r\nsetlocal\r\nset \"DIR=%~dp0\"\r\npython \"%DIR%{name}\" %*\r\nexit /b %ERRORLEVEL%\r\n"
+        );
+        write_guard_file_atomic(&cmd_path, &body, false)?;
+    }
+    let ps1_path = hooks_dir.join(format!("{name}.ps1"));
+    if !ps1_path.exists() {
+        let body = format!(
+            "$ErrorActionPreference = 'Stop'\n$hook = Join-Path $PSScriptRoot '{name}'\npython $hook @args\nexit $LASTEXITCODE\n"
+        );
+        write_guard_file_atomic(&ps1_path, &body, false)?;
+    }
+
+    // Write plugin
+    write_guard_file_atomic(&run_dir.join(PLUGIN_FILE_NAME), plugin, true)
+}
+
 pub fn install_guard(project: &str, repo: &Path, install_prepush: bool) -> GuardResult<()> {
     if !repo.exists() {
         return Err(GuardError::InvalidRepo {
@@ -1116,54 +1467,8 @@ pub fn install_guard(project: &str, repo: &Path, install_prepush: bool) -> Guard
     let hooks_dir = resolve_hooks_dir(repo)?;
     std::fs::create_dir_all(&hooks_dir)?;
 
-    // Helper to install a single hook type
     let install_hook = |name: &str| -> GuardResult<()> {
-        // Ensure hooks.d/<name> exists
-        let run_dir = hooks_dir.join("hooks.d").join(name);
-        std::fs::create_dir_all(&run_dir)?;
-
-        let chain_path = hooks_dir.join(name);
-        if chain_path.exists() {
-            let content = std::fs::read_to_string(&chain_path).unwrap_or_default();
-            let content = content.trim();
-            // Idempotent: backup if not ours
-            if !content.contains(&format!("mcp-agent-mail chain-runner ({name})")) {
-                let orig = hooks_dir.join(format!("{name}.orig"));
-                if !orig.exists() {
-                    std::fs::rename(&chain_path, &orig)?;
-                }
-            }
-        }
-
-        // Write chain-runner
-        let chain_script = render_chain_runner_script(name);
-        write_guard_file_atomic(&chain_path, &chain_script, true)?;
-
-        // Windows shims
-        let cmd_path = hooks_dir.join(format!("{name}.cmd"));
-        if !cmd_path.exists() {
-            let body = format!(
-                "@echo off\r\nsetlocal\r\nset \"DIR=%~dp0\"\r\npython \"%DIR%{name}\" %*\r\nexit /b %ERRORLEVEL%\r\n"
-            );
-            write_guard_file_atomic(&cmd_path, &body, false)?;
-        }
-        let ps1_path = hooks_dir.join(format!("{name}.ps1"));
-        if !ps1_path.exists() {
-            let body = format!(
-                "$ErrorActionPreference = 'Stop'\n$hook = Join-Path $PSScriptRoot '{name}'\npython $hook @args\nexit $LASTEXITCODE\n"
-            );
-            write_guard_file_atomic(&ps1_path, &body, false)?;
-        }
-
-        // Write guard plugin
-        let plugin_path = run_dir.join(PLUGIN_FILE_NAME);
-        write_guard_file_atomic(
-            &plugin_path,
-            &render_guard_plugin_script(project, name),
-            true,
-        )?;
-
-        Ok(())
+        install_hook_plugin(&hooks_dir, name, &render_guard_plugin_script(project, name))
     };
 
     install_hook("pre-commit")?;
@@ -1175,6 +1480,24 @@ pub fn install_guard(project: &str, repo: &Path, install_prepush: bool) -> Guard
     Ok(())
 }
 
+/// Install the advisory `prepare-commit-msg` plugin that appends the agent's
+/// coordination context to the commit message template.
+pub fn install_commit_context_hook(project: &str, repo: &Path) -> GuardResult<()> {
+    if !repo.exists() {
+        return Err(GuardError::InvalidRepo {
+            path: repo.display().to_string(),
+        });
+    }
+
+    let hooks_dir = resolve_hooks_dir(repo)?;
+    std::fs::create_dir_all(&hooks_dir)?;
+    install_hook_plugin(
+        &hooks_dir,
+        COMMIT_CONTEXT_HOOK,
+        &render_commit_context_plugin_script(project),
+    )
+}
+
 pub fn uninstall_guard(repo: &Path) -> GuardResult<()> {
     if !repo.exists() {
         return Err(GuardError::InvalidRepo {
@@ -1199,7 +1522,7 @@ pub fn uninstall_guard(repo: &Path) -> GuardResult<()> {
     }
 
     // Remove our hooks.d plugins if present.
-    for sub in ["pre-commit", "pre-push"] {
+    for sub in ["pre-commit", "pre-push", COMMIT_CONTEXT_HOOK] {
         let plugin = hooks_dir.join("hooks.d").join(sub).join(PLUGIN_FILE_NAME);
         if plugin.exists() {
             let _ = std::fs::remove_file(plugin);
@@ -1208,7 +1531,7 @@ pub fn uninstall_guard(repo: &Path) -> GuardResult<()> {
 
     // Legacy top-level single-file uninstall (pre-chain-runner installs)
     // Only remove chain-runner if no other plugins depend on it.
-    for hook_name in ["pre-commit", "pre-push"] {
+    for hook_name in ["pre-commit", "pre-push", COMMIT_CONTEXT_HOOK] {
         let hook_path = hooks_dir.join(hook_name);
         if !hook_path.exists() {
             continue;
@@ -1270,6 +1593,15 @@ pub fn guard_status(repo: &Path) -> GuardResult<GuardStatus> {
             .map(|c| c.contains("mcp-agent-mail"))
             .unwrap_or(false);
 
+    let commit_context_present = hooks_dir
+        .join("hooks.d")
+        .join(COMMIT_CONTEXT_HOOK)
+        .join(PLUGIN_FILE_NAME)
+        .is_file()
+        && std::fs::read_to_string(hooks_dir.join(COMMIT_CONTEXT_HOOK))
+            .map(|c| c.contains("mcp-agent-mail chain-runner"))
+            .unwrap_or(false);
+
     // Check if worktrees are enabled (core.hooksPath set)
     let worktrees_enabled = {
         let git_repo = git2::Repository::discover(repo)?;
@@ -1286,6 +1618,7 @@ pub fn guard_status(repo: &Path) -> GuardResult<GuardStatus> {
         hooks_dir: hooks_dir.display().to_string(),
         pre_commit_present,
         pre_push_present,
+        commit_context_present,
     })
 }
 
@@ -1392,15 +1725,54 @@ pub fn guard_check_full(
 ///
 /// This is the Rust-native equivalent of the guard plugin's conflict detection.
 /// Lower-level API: reads from archive, no gate/bypass handling.
+///
+/// Reservations held by `agent_name` (or `AGENT_NAME` when `None`) never
+/// conflict.
 pub fn guard_check(
     archive_root: &Path,
     repo_root: &Path,
     paths: &[String],
-    _advisory: bool,
+    advisory: bool,
+    agent_name: Option<&str>,
 ) -> GuardResult<Vec<GuardConflict>> {
+    guard_check_report(archive_root, repo_root, paths, advisory, agent_name)
+        .map(|report| report.conflicts)
+}
+
+/// Conflicts plus the self-held matches that were excluded from them.
+#[derive(Debug, Default)]
+pub struct GuardCheckReport {
+    pub conflicts: Vec<GuardConflict>,
+    /// Exclusive reservations held by the checking agent that matched a path.
+    pub self_held: Vec<GuardConflict>,
+    /// Project slug from the archive's `project.json`, when present.
+    pub project: Option<String>,
+    /// Where advisory mode wrote the conflict details, if it did.
+    pub conflict_file: Option<PathBuf>,
+}
+
+/// File in the git dir that advisory checks write conflicts to, for editor
+/// integrations to pick up.
+pub const LAST_CONFLICT_FILE: &str = "agent-mail-last-conflict.json";
+
+/// Like [`guard_check`], but also reports the checking agent's own
+/// exclusive reservations that matched and were skipped.
+///
+/// Conflicts carry the holder's archived profile. In advisory mode the
+/// conflicts are also written to [`LAST_CONFLICT_FILE`] in the git dir, and
+/// a clean run removes a stale one.
+pub fn guard_check_report(
+    archive_root: &Path,
+    repo_root: &Path,
+    paths: &[String],
+    advisory: bool,
+    agent_name: Option<&str>,
+) -> GuardResult<GuardCheckReport> {
     let ignorecase = detect_core_ignorecase(repo_root);
-    // Get current agent name from env
-    let agent_name = std::env::var("AGENT_NAME").unwrap_or_default();
+    let agent_name = match agent_name.map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => std::env::var("AGENT_NAME").unwrap_or_default(),
+    };
     if agent_name.is_empty() {
         return Err(GuardError::MissingAgentName);
     }
@@ -1408,7 +1780,145 @@ pub fn guard_check(
     // Read reservations from archive JSON files
     let reservations = read_active_reservations_from_archive(archive_root, ignorecase)?;
 
-    check_path_conflicts(paths, &reservations, &agent_name, ignorecase)
+    let mut conflicts = check_path_conflicts(paths, &reservations, &agent_name, ignorecase)?;
+    let own: Vec<FileReservationRecord> = reservations
+        .into_iter()
+        .filter(|res| res.agent_name.eq_ignore_ascii_case(&agent_name))
+        .collect();
+    // No holder name is empty, so nothing is skipped on this pass.
+    let self_held = check_path_conflicts(paths, &own, "", ignorecase)?;
+    attach_holder_profiles(archive_root, &mut conflicts);
+
+    let project = read_archive_project_slug(archive_root);
+    let conflict_file = if advisory {
+        write_last_conflict_file(repo_root, project.as_deref(), &agent_name, &conflicts)
+    } else {
+        None
+    };
+
+    Ok(GuardCheckReport {
+        conflicts,
+        self_held,
+        project,
+        conflict_file,
+    })
+}
+
+/// Fill in program, model, and last activity from each holder's
+/// `agents/<name>/profile.json` in the archive.
+fn attach_holder_profiles(archive_root: &Path, conflicts: &mut [GuardConflict]) {
+    let mut profiles: std::collections::HashMap<String, Option<serde_json::Value>> =
+        std::collections::HashMap::new();
+    for conflict in conflicts {
+        let profile = profiles
+            .entry(conflict.holder.clone())
+            .or_insert_with(|| read_agent_profile(archive_root, &conflict.holder));
+        let Some(profile) = profile else {
+            continue;
+        };
+        let field = |key: &str| {
+            profile[key]
+                .as_str()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+        conflict.holder_program = field("program");
+        conflict.holder_model = field("model");
+        conflict.holder_last_active_ts = field("last_active_ts");
+    }
+}
+
+fn read_agent_profile(archive_root: &Path, agent_name: &str) -> Option<serde_json::Value> {
+    if matches!(agent_name, "" | "." | "..") || agent_name.contains(['/', '\\']) {
+        return None;
+    }
+    let agent_dir = archive_root.join("agents").join(agent_name);
+    if !is_real_directory(&agent_dir) {
+        return None;
+    }
+    read_small_json_file(&agent_dir.join("profile.json"))
+}
+
+fn read_archive_project_slug(archive_root: &Path) -> Option<String> {
+    read_small_json_file(&archive_root.join("project.json"))?["slug"]
+        .as_str()
+        .map(str::trim)
+        .filter(|slug| !slug.is_empty())
+        .map(String::from)
+}
+
+/// Parse a regular (non-symlink) JSON file of at most 1MB.
+fn read_small_json_file(path: &Path) -> Option<serde_json::Value> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    if !metadata.file_type().is_file() || metadata.len() > 1024 * 1024 {
+        return None;
+    }
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// Write (or, with no conflicts, remove) the advisory conflict file.
+/// Best-effort: a repo without a git dir or an unwritable one only warns.
+fn write_last_conflict_file(
+    repo_root: &Path,
+    project: Option<&str>,
+    agent_name: &str,
+    conflicts: &[GuardConflict],
+) -> Option<PathBuf> {
+    let repo = git2::Repository::discover(repo_root).ok()?;
+    let path = repo.path().join(LAST_CONFLICT_FILE);
+    if conflicts.is_empty() {
+        if std::fs::symlink_metadata(&path).is_ok_and(|meta| meta.file_type().is_file()) {
+            let _ = std::fs::remove_file(&path);
+        }
+        return None;
+    }
+
+    let now = chrono::Utc::now();
+    let entries: Vec<serde_json::Value> = conflicts
+        .iter()
+        .map(|conflict| {
+            let mut entry = serde_json::to_value(conflict).unwrap_or_default();
+            entry["remaining_seconds"] = conflict.remaining_secs(&now).into();
+            entry["remaining_ttl"] = conflict.remaining_ttl(&now).into();
+            entry["release_command"] = project
+                .map(|project| conflict.release_command(project))
+                .into();
+            entry["mail_hint"] = conflict.mail_hint().into();
+            entry
+        })
+        .collect();
+    let payload = serde_json::json!({
+        "generated_ts": now.to_rfc3339(),
+        "project": project,
+        "agent": agent_name,
+        "conflicts": entries,
+    });
+    let written = serde_json::to_string_pretty(&payload)
+        .map_err(std::io::Error::other)
+        .and_then(|body| std::fs::write(&path, body));
+    match written {
+        Ok(()) => Some(path),
+        Err(err) => {
+            eprintln!(
+                "[agent-mail guard] warning: could not write {}: {err}",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
+fn conflict_for(path: &str, res: &FileReservationRecord) -> GuardConflict {
+    GuardConflict {
+        path: path.to_string(),
+        pattern: res.path_pattern.clone(),
+        holder: res.agent_name.clone(),
+        expires_ts: res.expires_ts.clone(),
+        reservation_id: res.id,
+        exclusive: res.exclusive,
+        ..GuardConflict::default()
+    }
 }
 
 /// Core conflict detection: check paths against reservations using globset.
@@ -1467,12 +1977,7 @@ fn check_path_conflicts(
             // Report the first match
             let idx = matches[0];
             let res = active_indices[idx];
-            conflicts.push(GuardConflict {
-                path: path.clone(),
-                pattern: res.path_pattern.clone(),
-                holder: res.agent_name.clone(),
-                expires_ts: res.expires_ts.clone(),
-            });
+            conflicts.push(conflict_for(path, res));
             continue;
         }
 
@@ -1498,12 +2003,7 @@ fn check_path_conflicts(
                         .get(normalized.len())
                         .is_some_and(|&c| c == b'/'))
             {
-                conflicts.push(GuardConflict {
-                    path: path.clone(),
-                    pattern: res.path_pattern.clone(),
-                    holder: res.agent_name.clone(),
-                    expires_ts: res.expires_ts.clone(),
-                });
+                conflicts.push(conflict_for(path, res));
                 break;
             }
 
@@ -1521,12 +2021,7 @@ fn check_path_conflicts(
                             .get(literal_base.len())
                             .is_some_and(|&c| c == b'/'))
                 {
-                    conflicts.push(GuardConflict {
-                        path: path.clone(),
-                        pattern: res.path_pattern.clone(),
-                        holder: res.agent_name.clone(),
-                        expires_ts: res.expires_ts.clone(),
-                    });
+                    conflicts.push(conflict_for(path, res));
                     break;
                 }
             }
@@ -1700,6 +2195,7 @@ fn read_active_reservations_from_archive(
         let has_glob = contains_glob(&pattern);
 
         records.push(FileReservationRecord {
+            id: val["id"].as_i64(),
             path_pattern: pattern,
             agent_name,
             exclusive,
@@ -1745,28 +2241,30 @@ fn released_ts_marks_record_released(value: &serde_json::Value) -> bool {
 }
 
 /// Check if a timestamp string is expired relative to `now`.
+///
+/// Uses `<=` to match the DB layer's `expires_ts > now` semantics (i.e.,
+/// expired means `expires_ts <= now`).
 fn is_expired(ts_str: &str, now: &chrono::DateTime<chrono::Utc>) -> bool {
-    // Try parsing ISO-8601 with timezone
+    // If we can't parse, treat as NOT expired (conservative/fail-closed)
+    parse_reservation_ts(ts_str).is_some_and(|ts| ts <= *now)
+}
+
+/// Parse a reservation timestamp: RFC 3339, ISO-8601 without a timezone
+/// (assumed UTC), or string-wrapped microseconds.
+fn parse_reservation_ts(ts_str: &str) -> Option<chrono::DateTime<chrono::Utc>> {
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(ts_str) {
-        return dt <= *now;
+        return Some(dt.with_timezone(&chrono::Utc));
     }
-    // Try parsing ISO-8601 without timezone (assume UTC).
-    // Use `<=` to match the RFC3339 branch and the DB layer's `expires_ts > now`
-    // semantics (i.e., expired means expires_ts <= now).
     if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(ts_str, "%Y-%m-%dT%H:%M:%S%.f") {
-        let utc = dt.and_utc();
-        return utc <= *now;
+        return Some(dt.and_utc());
     }
     if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(ts_str, "%Y-%m-%dT%H:%M:%S") {
-        let utc = dt.and_utc();
-        return utc <= *now;
+        return Some(dt.and_utc());
     }
-    // Fallback for string-wrapped numeric timestamps (microseconds)
-    if let Ok(num) = ts_str.parse::<i64>() {
-        return num <= now.timestamp_micros();
-    }
-    // If we can't parse, treat as NOT expired (conservative/fail-closed)
-    false
+    ts_str
+        .parse::<i64>()
+        .ok()
+        .and_then(chrono::DateTime::from_timestamp_micros)
 }
 
 // ---------------------------------------------------------------------------
@@ -2507,6 +3005,7 @@ mod tests {
 
     fn reservation(pattern: &str, holder: &str, exclusive: bool) -> FileReservationRecord {
         FileReservationRecord {
+            id: None,
             path_pattern: pattern.to_string(),
             agent_name: holder.to_string(),
             exclusive,
@@ -2681,6 +3180,7 @@ mod tests {
     #[test]
     fn check_path_conflicts_root_reservation_blocks_everything() {
         let reservations = vec![FileReservationRecord {
+            id: None,
             path_pattern: "".to_string(),
             agent_name: "OtherAgent".to_string(),
             exclusive: true,
@@ -2706,6 +3206,7 @@ mod tests {
     #[test]
     fn check_path_conflicts_glob_prefix_blocks_subdirectories() {
         let reservations = vec![FileReservationRecord {
+            id: None,
             path_pattern: "src/*".to_string(),
             agent_name: "OtherAgent".to_string(),
             exclusive: true,
@@ -2886,6 +3387,51 @@ mod tests {
         assert!(!is_expired("not-a-date", &now));
     }
 
+    #[test]
+    fn conflict_remaining_ttl_is_human_readable() {
+        let now = chrono::Utc::now();
+        let conflict = GuardConflict {
+            expires_ts: (now + chrono::Duration::seconds(3900)).to_rfc3339(),
+            ..GuardConflict::default()
+        };
+        assert_eq!(conflict.remaining_ttl(&now).as_deref(), Some("1h 5m"));
+        assert_eq!(format_ttl(250), "4m 10s");
+        assert_eq!(format_ttl(45), "45s");
+        assert_eq!(format_ttl(2 * 86_400 + 3 * 3600), "2d 3h");
+        assert_eq!(format_ttl(-5), "0s");
+
+        let unparseable = GuardConflict {
+            expires_ts: "soon".to_string(),
+            ..GuardConflict::default()
+        };
+        assert_eq!(unparseable.remaining_ttl(&now), None);
+    }
+
+    #[test]
+    fn conflict_resolution_hints() {
+        let mut conflict = GuardConflict {
+            pattern: "src/it's/**".to_string(),
+            holder: "BlueLake".to_string(),
+            reservation_id: Some(12),
+            exclusive: true,
+            holder_program: Some("codex-cli".to_string()),
+            holder_model: Some("gpt-5".to_string()),
+            ..GuardConflict::default()
+        };
+        assert_eq!(
+            conflict.release_command("backend"),
+            "am file_reservations release backend BlueLake --ids 12"
+        );
+        assert_eq!(conflict.holder_details(), "codex-cli/gpt-5, exclusive");
+        assert!(conflict.mail_hint().starts_with("send mail to BlueLake"));
+
+        conflict.reservation_id = None;
+        assert_eq!(
+            conflict.release_command("backend"),
+            "am file_reservations release backend BlueLake --paths 'src/it'\\''s/**'"
+        );
+    }
+
     // -----------------------------------------------------------------------
     // parse_name_status_z tests
     // -----------------------------------------------------------------------
@@ -3393,6 +3939,228 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_context_hook_is_reported_and_uninstalled() {
+        let td = tempfile::TempDir::new().expect("tempdir");
+        let repo_dir = td.path().join("repo");
+        std::fs::create_dir_all(&repo_dir).expect("mkdir");
+        run_git(&repo_dir, &["init", "-q"]);
+
+        install_guard("/test/project", &repo_dir, false).expect("install");
+        assert!(
+            !guard_status(&repo_dir)
+                .expect("status")
+                .commit_context_present
+        );
+
+        install_commit_context_hook("/test/project", &repo_dir).expect("install context");
+        let status = guard_status(&repo_dir).expect("status");
+        assert!(status.pre_commit_present);
+        assert!(status.commit_context_present);
+
+        uninstall_guard(&repo_dir).expect("uninstall");
+        let hooks_dir = resolve_hooks_dir(&repo_dir).expect("hooks dir");
+        assert!(!hooks_dir.join(COMMIT_CONTEXT_HOOK).exists());
+        let status = guard_status(&repo_dir).expect("status");
+        assert!(!status.pre_commit_present);
+        assert!(!status.commit_context_present);
+    }
+
+    /// Repo with the commit-context hook installed, an archive holding one
+    /// reservation for `agent` over `src/**` and one for another agent over
+    /// `docs/**`, and a fake `am` answering the count-only queries.
+    #[cfg(unix)]
+    fn commit_context_fixture(
+        td: &Path,
+        agent: &str,
+        am_body: &str,
+    ) -> (PathBuf, PathBuf, PathBuf) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let repo_dir = td.join("repo");
+        let storage_root = td.join("storage");
+        std::fs::create_dir_all(&repo_dir).expect("mkdir repo");
+        run_git(&repo_dir, &["init", "-q"]);
+        run_git(&repo_dir, &["config", "user.email", "test@test.com"]);
+        run_git(&repo_dir, &["config", "user.name", "test"]);
+        let project = repo_dir.to_string_lossy().to_string();
+        install_commit_context_hook(&project, &repo_dir).expect("install context hook");
+
+        let identity = mcp_agent_mail_core::resolve_project_identity(&project);
+        let archive_root = storage_root.join("projects").join(&identity.slug);
+        let reservations_dir = archive_root.join("file_reservations");
+        std::fs::create_dir_all(&reservations_dir).expect("mkdir reservations");
+        std::fs::write(
+            archive_root.join("project.json"),
+            serde_json::json!({"slug": identity.slug, "human_key": project}).to_string(),
+        )
+        .expect("write project metadata");
+        let expires = (chrono::Utc::now() + chrono::Duration::hours(1)).to_rfc3339();
+        for (file, pattern, holder) in [
+            ("mine.json", "src/**", agent),
+            ("theirs.json", "docs/**", "OtherAgent"),
+        ] {
+            std::fs::write(
+                reservations_dir.join(file),
+                serde_json::json!({
+                    "path_pattern": pattern,
+                    "agent_name": holder,
+                    "exclusive": true,
+                    "expires_ts": expires,
+                    "released_ts": serde_json::Value::Null,
+                })
+                .to_string(),
+            )
+            .expect("write reservation");
+        }
+
+        let am = td.join("am");
+        std::fs::write(&am, format!("#!/bin/sh\n{am_body}\n")).expect("write fake am");
+        std::fs::set_permissions(&am, std::fs::Permissions::from_mode(0o755)).expect("chmod am");
+        (repo_dir, storage_root, am)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commit_context_hook_appends_comment_block_to_editor_template() {
+        use std::os::unix::fs::PermissionsExt;
+
+        if python_executable().is_none() {
+            return;
+        }
+        let td = tempfile::TempDir::new().expect("tempdir");
+        let (repo_dir, storage_root, am) = commit_context_fixture(
+            td.path(),
+            "PinkStone",
+            r#"case "$1 $2" in
+  "mail inbox") echo '{"total":5,"unread_count":2,"urgent_or_high_count":2}' ;;
+  "acks pending") echo '{"total":1}' ;;
+  *) exit 1 ;;
+esac"#,
+        );
+
+        // The editor keeps a copy of the template git prepared, then writes a
+        // subject above it like a person would.
+        let captured = td.path().join("template.txt");
+        let editor = td.path().join("editor.sh");
+        std::fs::write(
+            &editor,
+            "#!/bin/sh\ncp \"$1\" \"$CAPTURE\" && { echo 'Add lib'; cat \"$CAPTURE\"; } > \"$1\"\n",
+        )
+        .expect("write editor");
+        std::fs::set_permissions(&editor, std::fs::Permissions::from_mode(0o755))
+            .expect("chmod editor");
+
+        for file in ["src/lib.rs", "src/main.rs", "README.md"] {
+            let path = repo_dir.join(file);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(&path, "x\n").expect("write staged file");
+        }
+        run_git(&repo_dir, &["add", "."]);
+
+        let output = Command::new("git")
+            .current_dir(&repo_dir)
+            .args(["commit", "-q"])
+            .env("GIT_EDITOR", &editor)
+            .env("CAPTURE", &captured)
+            .env("AGENT_NAME", "PinkStone")
+            .env("STORAGE_ROOT", &storage_root)
+            .env("AM_BIN", &am)
+            .env("AGENT_MAIL_COMMIT_CONTEXT_BUDGET_MS", "10000")
+            .output()
+            .expect("git commit");
+        assert!(
+            output.status.success(),
+            "commit failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let template = std::fs::read_to_string(&captured).expect("captured template");
+        assert!(
+            template.contains(&format!("# {COMMIT_CONTEXT_BEGIN}\n# agent: PinkStone\n")),
+            "{template}"
+        );
+        assert!(
+            template.contains("#   src/** (2 staged files, expires "),
+            "{template}"
+        );
+        assert!(!template.contains("docs/**"), "{template}");
+        assert!(
+            template.contains("# unread urgent messages: 2\n"),
+            "{template}"
+        );
+        assert!(template.contains("# pending acks: 1\n"), "{template}");
+        assert!(
+            template.contains("# end agent-mail context\n"),
+            "{template}"
+        );
+
+        assert_eq!(
+            run_git_stdout(&repo_dir, &["log", "-1", "--format=%B"]).trim(),
+            "Add lib",
+            "comment lines must not land in the commit"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commit_context_hook_is_silent_for_inline_messages_and_slow_counts() {
+        let Some(python) = python_executable() else {
+            return;
+        };
+        let td = tempfile::TempDir::new().expect("tempdir");
+        let (repo_dir, storage_root, am) =
+            commit_context_fixture(td.path(), "PinkStone", "sleep 2\necho '{\"total\":9}'");
+        std::fs::create_dir_all(repo_dir.join("src")).expect("mkdir src");
+        std::fs::write(repo_dir.join("src/lib.rs"), "x\n").expect("write");
+        run_git(&repo_dir, &["add", "."]);
+
+        // `-m` messages are not comment-stripped, so the hook adds nothing.
+        let output = Command::new("git")
+            .current_dir(&repo_dir)
+            .args(["commit", "-qm", "Inline subject"])
+            .env("AGENT_NAME", "PinkStone")
+            .env("STORAGE_ROOT", &storage_root)
+            .env("AM_BIN", &am)
+            .output()
+            .expect("git commit");
+        assert!(output.status.success());
+        assert_eq!(
+            run_git_stdout(&repo_dir, &["log", "-1", "--format=%B"]).trim(),
+            "Inline subject"
+        );
+
+        // Counts slower than the budget are dropped, not waited on. With no
+        // reservation over the staged file there is nothing left to report.
+        std::fs::write(repo_dir.join("notes.txt"), "x\n").expect("write");
+        run_git(&repo_dir, &["add", "notes.txt"]);
+        let script = td.path().join("commit_context.py");
+        std::fs::write(
+            &script,
+            render_commit_context_plugin_script(&repo_dir.to_string_lossy()),
+        )
+        .expect("write plugin");
+        let message = td.path().join("COMMIT_EDITMSG");
+        std::fs::write(&message, "\n").expect("write message");
+        let started = std::time::Instant::now();
+        let output = Command::new(&python)
+            .current_dir(&repo_dir)
+            .arg(&script)
+            .arg(&message)
+            .env("AGENT_NAME", "PinkStone")
+            .env("STORAGE_ROOT", &storage_root)
+            .env("AM_BIN", &am)
+            .output()
+            .expect("run plugin");
+        assert!(output.status.success());
+        assert!(
+            started.elapsed() < std::time::Duration::from_millis(1500),
+            "plugin waited on slow counts: {:?}",
+            started.elapsed()
+        );
+        assert_eq!(std::fs::read_to_string(&message).expect("read"), "\n");
+    }
+
     #[test]
     fn guard_status_invalid_repo_returns_error() {
         let td = tempfile::TempDir::new().expect("tempdir");
@@ -4,7 +4,9 @@
 //! and Rust 2024 edition makes `set_var`/`remove_var` unsafe.
 #![allow(unsafe_code)]
 
-use mcp_agent_mail_guard::{GuardError, GuardMode, guard_check, guard_check_full};
+use mcp_agent_mail_guard::{
+    GuardError, GuardMode, LAST_CONFLICT_FILE, guard_check, guard_check_full, guard_check_report,
+};
 use std::path::Path;
 use std::sync::Mutex;
 
@@ -327,7 +329,13 @@ fn guard_check_missing_agent_name_returns_error() {
 
     unsafe { std::env::remove_var("AGENT_NAME") };
 
-    let result = guard_check(&archive, &archive, &["app/api/users.py".to_string()], false);
+    let result = guard_check(
+        &archive,
+        &archive,
+        &["app/api/users.py".to_string()],
+        false,
+        None,
+    );
     assert!(result.is_err());
     assert!(matches!(result.unwrap_err(), GuardError::MissingAgentName));
 }
@@ -342,8 +350,14 @@ fn guard_check_detects_conflicts() {
 
     unsafe { std::env::set_var("AGENT_NAME", "DifferentAgent") };
 
-    let conflicts = guard_check(&archive, &archive, &["app/api/users.py".to_string()], false)
-        .expect("guard_check");
+    let conflicts = guard_check(
+        &archive,
+        &archive,
+        &["app/api/users.py".to_string()],
+        false,
+        None,
+    )
+    .expect("guard_check");
     assert_eq!(conflicts.len(), 1);
     assert_eq!(conflicts[0].holder, "OtherAgent");
 }
@@ -363,6 +377,7 @@ fn guard_check_no_conflicts_for_unrelated_paths() {
         &archive,
         &["totally/unrelated.txt".to_string()],
         false,
+        None,
     )
     .expect("guard_check");
     assert!(conflicts.is_empty());
@@ -378,10 +393,163 @@ fn guard_check_empty_paths_no_conflicts() {
 
     unsafe { std::env::set_var("AGENT_NAME", "SomeAgent") };
 
-    let conflicts = guard_check(&archive, &archive, &[], false).expect("guard_check");
+    let conflicts = guard_check(&archive, &archive, &[], false, None).expect("guard_check");
     assert!(conflicts.is_empty());
 }
 
+#[test]
+fn guard_check_explicit_agent_skips_self_held_exclusive() {
+    let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _guard = EnvGuard::save(&["AGENT_NAME"]);
+
+    let td = tempfile::TempDir::new().expect("tempdir");
+    let archive = make_archive_with_reservations(td.path());
+
+    unsafe { std::env::remove_var("AGENT_NAME") };
+
+    let paths = ["my/stuff/notes.md".to_string()];
+    let report = guard_check_report(&archive, &archive, &paths, false, Some("myagent"))
+        .expect("guard_check_report");
+    assert!(report.conflicts.is_empty());
+    assert_eq!(report.self_held.len(), 1);
+    assert_eq!(report.self_held[0].holder, "MyAgent");
+    assert_eq!(report.self_held[0].path, "my/stuff/notes.md");
+}
+
+#[test]
+fn guard_check_explicit_agent_still_blocks_other_agents() {
+    let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _guard = EnvGuard::save(&["AGENT_NAME"]);
+
+    let td = tempfile::TempDir::new().expect("tempdir");
+    let archive = make_archive_with_reservations(td.path());
+
+    // The explicit agent wins over the environment.
+    unsafe { std::env::set_var("AGENT_NAME", "OtherAgent") };
+
+    let paths = [
+        "app/api/users.py".to_string(),
+        "my/stuff/notes.md".to_string(),
+    ];
+    let report = guard_check_report(&archive, &archive, &paths, false, Some("MyAgent"))
+        .expect("guard_check_report");
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].holder, "OtherAgent");
+    assert_eq!(report.self_held.len(), 1);
+    assert_eq!(report.self_held[0].holder, "MyAgent");
+}
+
+#[test]
+fn guard_check_shared_reservations_never_block() {
+    let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _guard = EnvGuard::save(&["AGENT_NAME"]);
+
+    let td = tempfile::TempDir::new().expect("tempdir");
+    let archive = make_archive_with_reservations(td.path());
+    let future = chrono::Utc::now() + chrono::Duration::hours(1);
+    let shared = serde_json::json!({
+        "path_pattern": "docs/**",
+        "agent_name": "OtherAgent",
+        "exclusive": false,
+        "expires_ts": future.to_rfc3339(),
+        "released_ts": null
+    });
+    std::fs::write(
+        archive.join("file_reservations").join("shared.json"),
+        shared.to_string(),
+    )
+    .expect("write");
+
+    unsafe { std::env::remove_var("AGENT_NAME") };
+
+    let paths = ["docs/guide.md".to_string()];
+    for agent in ["MyAgent", "OtherAgent"] {
+        let report = guard_check_report(&archive, &archive, &paths, false, Some(agent))
+            .expect("guard_check_report");
+        assert!(report.conflicts.is_empty(), "{agent}: {report:?}");
+        assert!(report.self_held.is_empty(), "{agent}: {report:?}");
+    }
+}
+
+#[test]
+fn guard_check_advisory_writes_enriched_conflict_file() {
+    let _lock = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _guard = EnvGuard::save(&["AGENT_NAME"]);
+
+    let td = tempfile::TempDir::new().expect("tempdir");
+    let archive = make_archive_with_reservations(td.path());
+    std::fs::write(archive.join("project.json"), r#"{"slug": "guard-proj"}"#).expect("write");
+    let future = chrono::Utc::now() + chrono::Duration::hours(2);
+    let with_id = serde_json::json!({
+        "id": 42,
+        "path_pattern": "src/**",
+        "agent": "OtherAgent",
+        "exclusive": true,
+        "expires_ts": future.to_rfc3339(),
+    });
+    std::fs::write(
+        archive.join("file_reservations").join("id-42.json"),
+        with_id.to_string(),
+    )
+    .expect("write");
+    let profile_dir = archive.join("agents").join("OtherAgent");
+    std::fs::create_dir_all(&profile_dir).expect("mkdir");
+    let profile = serde_json::json!({
+        "name": "OtherAgent",
+        "program": "claude-code",
+        "model": "opus",
+        "last_active_ts": "2026-01-01T00:00:00+00:00",
+    });
+    std::fs::write(profile_dir.join("profile.json"), profile.to_string()).expect("write");
+
+    let repo = td.path().join("repo");
+    let git = git2::Repository::init(&repo).expect("git init");
+    let conflict_path = git.path().join(LAST_CONFLICT_FILE);
+
+    unsafe { std::env::remove_var("AGENT_NAME") };
+
+    let paths = ["src/lib.rs".to_string()];
+    let report = guard_check_report(&archive, &repo, &paths, true, Some("MyAgent"))
+        .expect("guard_check_report");
+    assert_eq!(report.project.as_deref(), Some("guard-proj"));
+    let conflict = &report.conflicts[0];
+    assert_eq!(conflict.reservation_id, Some(42));
+    assert!(conflict.exclusive);
+    assert_eq!(conflict.holder_program.as_deref(), Some("claude-code"));
+    assert_eq!(conflict.holder_model.as_deref(), Some("opus"));
+    assert_eq!(
+        conflict.holder_last_active_ts.as_deref(),
+        Some("2026-01-01T00:00:00+00:00")
+    );
+    assert_eq!(
+        report.conflict_file.as_deref(),
+        Some(conflict_path.as_path())
+    );
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&conflict_path).expect("read"))
+            .expect("parse");
+    assert_eq!(written["agent"], "MyAgent");
+    let entry = &written["conflicts"][0];
+    assert_eq!(entry["holder"], "OtherAgent");
+    assert_eq!(
+        entry["release_command"],
+        "am file_reservations release guard-proj OtherAgent --ids 42"
+    );
+    assert!(
+        entry["remaining_seconds"]
+            .as_i64()
+            .is_some_and(|s| s > 3600)
+    );
+
+    // Blocking mode leaves the file alone; a clean advisory run removes it.
+    guard_check_report(&archive, &repo, &paths, false, Some("MyAgent")).expect("block check");
+    assert!(conflict_path.exists());
+    let clean = guard_check_report(&archive, &repo, &[], true, Some("MyAgent")).expect("clean");
+    assert!(clean.conflict_file.is_none());
+    assert!(!conflict_path.exists());
+}
+
 // -----------------------------------------------------------------------
 // is_guard_gated / is_bypass_active integration tests
 // -----------------------------------------------------------------------
@@ -0,0 +1,78 @@
+//! Embed the Agent Mail server with two product-specific MCP tools.
+//!
+//! The custom tools are served next to the built-in mail tools and share their
+//! auth, rate limiting, backpressure shedding, and metrics; they also show up
+//! in `resource://tooling/{directory,schemas,metrics}`.
+//!
+//! ```sh
+//! HTTP_PORT=8765 cargo run -p mcp-agent-mail-server --example custom_tools
+//! ```
+
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_server::run_http_with_tui_and_tools;
+use mcp_agent_mail_server::tool_registry::{CustomTool, ShedClass, ToolRegistry};
+use serde_json::{Value, json};
+
+fn ticket_lookup() -> CustomTool {
+    CustomTool::new("ticket_lookup", "tickets", |ctx, args| {
+        Box::pin(async move {
+            let project = ctx.resolve_project().await?;
+            let ticket_id = args.get("ticket_id").and_then(Value::as_str).unwrap_or("");
+            // A real integration would query the issue tracker here.
+            Ok(json!({
+                "project": project.slug,
+                "ticket_id": ticket_id,
+                "status": "open",
+            }))
+        })
+    })
+    .description("Look up a ticket in the product issue tracker.")
+    .input_schema(json!({
+        "type": "object",
+        "properties": {
+            "project_key": { "type": "string" },
+            "ticket_id": { "type": "string" }
+        },
+        "required": ["project_key", "ticket_id"]
+    }))
+    .capabilities(["tickets"])
+    .complexity("low")
+    .shed_class(ShedClass::Shedable)
+}
+
+fn deploy_trigger() -> CustomTool {
+    CustomTool::new("deploy_trigger", "deploys", |ctx, args| {
+        Box::pin(async move {
+            let agent = ctx.resolve_calling_agent().await?;
+            let environment = args
+                .get("environment")
+                .and_then(Value::as_str)
+                .unwrap_or("staging");
+            Ok(json!({
+                "requested_by": agent.name,
+                "environment": environment,
+                "queued": true,
+            }))
+        })
+    })
+    .description("Queue a deploy of the project on behalf of the calling agent.")
+    .input_schema(json!({
+        "type": "object",
+        "properties": {
+            "project_key": { "type": "string" },
+            "agent_name": { "type": "string" },
+            "environment": { "type": "string", "enum": ["staging", "production"] }
+        },
+        "required": ["project_key", "agent_name"]
+    }))
+    .capabilities(["deploys", "write"])
+    .complexity("medium")
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let registry = ToolRegistry::with_builtins()
+        .register(ticket_lookup())?
+        .register(deploy_trigger())?;
+    run_http_with_tui_and_tools(&Config::from_env(), registry)?;
+    Ok(())
+}
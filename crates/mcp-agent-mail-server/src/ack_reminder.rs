@@ -0,0 +1,467 @@
+//! Background worker that sends follow-up reminders for unacked messages.
+//!
+//! Opt-in via `ACK_REMINDER_ENABLED`. Each scan looks for ack-required
+//! messages of importance `urgent` or `high` that still have pending
+//! recipients. Once the per-importance TTL has elapsed since the original
+//! message (or since the previous reminder), the worker posts a reminder in
+//! the same thread from the `AckReminder` system agent, addressed only to the
+//! recipients that have not acknowledged yet.
+//!
+//! The number of reminders per original message is capped by
+//! `ACK_REMINDER_MAX_PER_MESSAGE` and persisted in the `ack_reminders` table,
+//! so a restart neither re-sends nor exceeds the cap. Reminders are never
+//! ack-required themselves, which keeps them out of later scans.
+
+#![forbid(unsafe_code)]
+
+use asupersync::{Cx, Outcome};
+use fastmcp_core::block_on;
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_db::{
+    DbPool, DbPoolConfig, create_pool, micros_to_iso, now_micros,
+    queries::{self, AckReminderCandidate},
+};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// System agent that authors reminder messages.
+pub const ACK_REMINDER_AGENT_NAME: &str = "AckReminder";
+
+/// Global shutdown flag for the ack reminder worker.
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// Worker handle for join-on-shutdown.
+static WORKER: std::sync::LazyLock<Mutex<Option<std::thread::JoinHandle<()>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+/// Start the ack reminder worker (if enabled).
+///
+/// Must be called at most once. Subsequent calls are no-ops.
+pub fn start(config: &Config) {
+    if !config.ack_reminder_enabled {
+        return;
+    }
+
+    let mut worker = WORKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if worker
+        .as_ref()
+        .is_some_and(std::thread::JoinHandle::is_finished)
+        && let Some(stale) = worker.take()
+    {
+        let _ = stale.join();
+    }
+    if worker.is_none() {
+        let config = config.clone();
+        SHUTDOWN.store(false, Ordering::Release);
+        match std::thread::Builder::new()
+            .name("ack-reminder".into())
+            .spawn(move || {
+                ack_reminder_loop(&config);
+            }) {
+            Ok(handle) => {
+                *worker = Some(handle);
+            }
+            Err(err) => {
+                drop(worker);
+                warn!(
+                    error = %err,
+                    "failed to spawn ack reminder worker; continuing without automatic reminders"
+                );
+                return;
+            }
+        }
+    }
+    drop(worker);
+}
+
+/// Signal the worker to stop.
+pub fn shutdown() {
+    SHUTDOWN.store(true, Ordering::Release);
+    let mut worker = WORKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(handle) = worker.take() {
+        let _ = handle.join();
+    }
+}
+
+fn ack_reminder_loop(config: &Config) {
+    let interval = std::time::Duration::from_secs(config.ack_reminder_scan_interval_seconds.max(5));
+
+    let mut pool_config = DbPoolConfig::from_env();
+    pool_config.database_url.clone_from(&config.database_url);
+    pool_config.min_connections = 1;
+    pool_config.max_connections = 1;
+    pool_config.warmup_connections = 0;
+    pool_config.run_migrations = false;
+    let pool = match create_pool(&pool_config) {
+        Ok(p) => p,
+        Err(e) => {
+            warn!(error = %e, "ack reminder worker: failed to create DB pool, exiting");
+            return;
+        }
+    };
+
+    info!(
+        interval_secs = interval.as_secs(),
+        urgent_secs = config.ack_reminder_urgent_seconds,
+        high_secs = config.ack_reminder_high_seconds,
+        max_per_message = config.ack_reminder_max_per_message,
+        "ack reminder worker started"
+    );
+
+    loop {
+        if sleep_with_shutdown(interval) {
+            info!("ack reminder worker shutting down");
+            return;
+        }
+        match run_ack_reminder_cycle(config, &pool, now_micros()) {
+            Ok(sent) if sent > 0 => {
+                info!(event = "ack_reminder_scan", sent, "ack reminders sent");
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "ack reminder cycle failed"),
+        }
+    }
+}
+
+fn sleep_with_shutdown(duration: std::time::Duration) -> bool {
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        if SHUTDOWN.load(Ordering::Acquire) {
+            return true;
+        }
+        let chunk = remaining.min(std::time::Duration::from_secs(1));
+        std::thread::sleep(chunk);
+        remaining = remaining.saturating_sub(chunk);
+    }
+    SHUTDOWN.load(Ordering::Acquire)
+}
+
+/// Reminder TTL for `importance`, or `None` when it is not reminded about.
+fn reminder_ttl_seconds(config: &Config, importance: &str) -> Option<u64> {
+    match importance.trim().to_ascii_lowercase().as_str() {
+        "urgent" => Some(config.ack_reminder_urgent_seconds),
+        "high" => Some(config.ack_reminder_high_seconds),
+        _ => None,
+    }
+}
+
+/// Whether `candidate` is due for its next reminder at `now`.
+fn reminder_due(config: &Config, candidate: &AckReminderCandidate, now: i64) -> bool {
+    let Some(ttl_seconds) = reminder_ttl_seconds(config, &candidate.importance) else {
+        return false;
+    };
+    let ttl_us = i64::try_from(ttl_seconds)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(1_000_000);
+    let since = candidate.last_reminder_ts.unwrap_or(candidate.created_ts);
+    now.saturating_sub(since) >= ttl_us
+}
+
+fn reminder_body(candidate: &AckReminderCandidate, attempt: i64, max: u64) -> String {
+    format!(
+        "Reminder {attempt}/{max}: message #{id} (\"{subject}\", importance {importance}) \
+         was sent at {sent} and still needs your acknowledgement.\n\n\
+         Call `acknowledge_message` with message_id {id} once you have handled it.",
+        id = candidate.message_id,
+        subject = candidate.subject,
+        importance = candidate.importance,
+        sent = micros_to_iso(candidate.created_ts),
+    )
+}
+
+/// Run a single scan, sending every reminder that is due at `now`.
+///
+/// Returns the number of reminder messages sent.
+fn run_ack_reminder_cycle(config: &Config, pool: &DbPool, now: i64) -> Result<usize, String> {
+    // Same ambient-Cx approach as the ACK TTL worker: this thread runs outside
+    // the async runtime, so borrow the Cx installed by `block_on`.
+    let cx = block_on(async {
+        Cx::current().expect("Runtime::block_on installs an ambient Cx for the polled future")
+    });
+    let max = config.ack_reminder_max_per_message;
+    if max == 0 {
+        return Ok(0);
+    }
+    let candidates = match block_on(async {
+        queries::list_ack_reminder_candidates(
+            &cx,
+            pool,
+            &["urgent", "high"],
+            i64::try_from(max).unwrap_or(i64::MAX),
+        )
+        .await
+    }) {
+        Outcome::Ok(rows) => rows,
+        other => return Err(format!("failed to list ack reminder candidates: {other:?}")),
+    };
+
+    let mut sent = 0usize;
+    for candidate in candidates
+        .iter()
+        .filter(|candidate| reminder_due(config, candidate, now))
+    {
+        match send_reminder(config, pool, &cx, candidate, now) {
+            Ok(()) => sent = sent.saturating_add(1),
+            Err(e) => warn!(
+                message_id = candidate.message_id,
+                error = %e,
+                "failed to send ack reminder"
+            ),
+        }
+    }
+    Ok(sent)
+}
+
+fn send_reminder(
+    config: &Config,
+    pool: &DbPool,
+    cx: &Cx,
+    candidate: &AckReminderCandidate,
+    now: i64,
+) -> Result<(), String> {
+    let sender = match block_on(async {
+        queries::insert_system_agent(
+            cx,
+            pool,
+            candidate.project_id,
+            ACK_REMINDER_AGENT_NAME,
+            "mcp-agent-mail",
+            "system",
+            "Sends automatic reminders for unacknowledged messages",
+        )
+        .await
+    }) {
+        Outcome::Ok(agent) => agent,
+        other => return Err(format!("failed to ensure reminder agent: {other:?}")),
+    };
+    let sender_id = sender
+        .id
+        .ok_or_else(|| "reminder agent has no id".to_string())?;
+
+    let thread_id = candidate
+        .thread_id
+        .clone()
+        .unwrap_or_else(|| candidate.message_id.to_string());
+    let attempt = candidate.reminders_sent.saturating_add(1);
+    let subject = format!("Reminder: {}", candidate.subject);
+    let body = reminder_body(candidate, attempt, config.ack_reminder_max_per_message);
+    let recipients: Vec<(i64, &str)> = candidate
+        .pending_agent_ids
+        .iter()
+        .map(|agent_id| (*agent_id, "to"))
+        .collect();
+
+    let reminder = match block_on(async {
+        queries::create_message_with_recipients(
+            cx,
+            pool,
+            candidate.project_id,
+            sender_id,
+            &subject,
+            &body,
+            Some(&thread_id),
+            &candidate.importance,
+            false,
+            "[]",
+            &recipients,
+        )
+        .await
+    }) {
+        Outcome::Ok(message) => message,
+        other => return Err(format!("failed to create reminder message: {other:?}")),
+    };
+    let reminder_id = reminder.id.unwrap_or(0);
+
+    match block_on(async {
+        queries::record_ack_reminder(cx, pool, candidate.message_id, reminder_id, now).await
+    }) {
+        Outcome::Ok(total) => {
+            info!(
+                event = "ack_reminder_sent",
+                message_id = candidate.message_id,
+                reminder_message_id = reminder_id,
+                project_id = candidate.project_id,
+                recipients = recipients.len(),
+                reminders_sent = total,
+                "ack reminder sent"
+            );
+            Ok(())
+        }
+        other => Err(format!("failed to record ack reminder: {other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_test_pool(tmp: &tempfile::TempDir) -> DbPool {
+        let db_path = tmp.path().join("db.sqlite3");
+        let db_url = format!(
+            "sqlite:////{}",
+            db_path.to_string_lossy().trim_start_matches('/')
+        );
+        let pool_config = DbPoolConfig {
+            database_url: db_url,
+            min_connections: 1,
+            max_connections: 1,
+            ..Default::default()
+        };
+        create_pool(&pool_config).expect("create pool")
+    }
+
+    fn candidate(importance: &str, created_ts: i64, last: Option<i64>) -> AckReminderCandidate {
+        AckReminderCandidate {
+            message_id: 7,
+            project_id: 1,
+            thread_id: None,
+            subject: "deploy".to_string(),
+            importance: importance.to_string(),
+            created_ts,
+            reminders_sent: i64::from(last.is_some()),
+            last_reminder_ts: last,
+            pending_agent_ids: vec![2],
+        }
+    }
+
+    #[test]
+    fn reminder_due_uses_per_importance_ttl_and_last_reminder() {
+        let config = Config::default();
+        let minute = 60 * 1_000_000;
+        let now = 1_000 * minute;
+        assert!(reminder_due(
+            &config,
+            &candidate("urgent", now - 15 * minute, None),
+            now
+        ));
+        assert!(!reminder_due(
+            &config,
+            &candidate("urgent", now - 14 * minute, None),
+            now
+        ));
+        assert!(!reminder_due(
+            &config,
+            &candidate("high", now - 30 * minute, None),
+            now
+        ));
+        assert!(reminder_due(
+            &config,
+            &candidate("HIGH", now - 60 * minute, None),
+            now
+        ));
+        assert!(!reminder_due(
+            &config,
+            &candidate("urgent", now - 90 * minute, Some(now - 5 * minute)),
+            now
+        ));
+        assert!(!reminder_due(&config, &candidate("normal", 0, None), now));
+    }
+
+    #[test]
+    fn cycle_sends_capped_reminders_to_pending_recipients_in_thread() {
+        let tmp = tempfile::tempdir().unwrap();
+        let pool = make_test_pool(&tmp);
+        let cx = Cx::for_testing();
+        let human_key = tmp.path().join("project_root");
+        std::fs::create_dir_all(&human_key).unwrap();
+        let human_key = human_key.to_string_lossy().to_string();
+
+        let project = mcp_agent_mail_core::config::with_process_env_overrides_for_test(
+            &[("AM_ALLOW_EPHEMERAL_PROJECT_ROOTS", "1")],
+            || match block_on(async { queries::ensure_project(&cx, &pool, &human_key).await }) {
+                Outcome::Ok(project) => project,
+                other => panic!("ensure_project failed: {other:?}"),
+            },
+        );
+        let project_id = project.id.expect("project id");
+        let mut agent_ids = Vec::new();
+        for name in ["RedFox", "BlueLake"] {
+            match block_on(async {
+                queries::register_agent(
+                    &cx, &pool, project_id, name, "test", "test", None, None, None,
+                )
+                .await
+            }) {
+                Outcome::Ok(agent) => agent_ids.push(agent.id.expect("agent id")),
+                other => panic!("register_agent({name}) failed: {other:?}"),
+            }
+        }
+        let original = match block_on(async {
+            queries::create_message_with_recipients(
+                &cx,
+                &pool,
+                project_id,
+                agent_ids[0],
+                "Deploy freeze",
+                "ack please",
+                None,
+                "urgent",
+                true,
+                "[]",
+                &[(agent_ids[1], "to")],
+            )
+            .await
+        }) {
+            Outcome::Ok(message) => message,
+            other => panic!("create message failed: {other:?}"),
+        };
+        let original_id = original.id.expect("message id");
+
+        let config = Config {
+            ack_reminder_enabled: true,
+            ack_reminder_max_per_message: 2,
+            ..Config::default()
+        };
+        let hour = 3_600 * 1_000_000;
+        let start = original.created_ts;
+        assert_eq!(run_ack_reminder_cycle(&config, &pool, start).unwrap(), 0);
+        assert_eq!(
+            run_ack_reminder_cycle(&config, &pool, start + hour).unwrap(),
+            1
+        );
+        // Not due again until another TTL has passed since the last reminder.
+        assert_eq!(
+            run_ack_reminder_cycle(&config, &pool, start + hour).unwrap(),
+            0
+        );
+        assert_eq!(
+            run_ack_reminder_cycle(&config, &pool, start + 2 * hour).unwrap(),
+            1
+        );
+        // The cap holds even well past the TTL.
+        assert_eq!(
+            run_ack_reminder_cycle(&config, &pool, start + 9 * hour).unwrap(),
+            0
+        );
+
+        let inbox = match block_on(async {
+            queries::fetch_inbox(&cx, &pool, project_id, agent_ids[1], false, None, 20).await
+        }) {
+            Outcome::Ok(rows) => rows,
+            other => panic!("fetch_inbox failed: {other:?}"),
+        };
+        let reminders: Vec<_> = inbox
+            .iter()
+            .filter(|row| row.message.subject == "Reminder: Deploy freeze")
+            .collect();
+        assert_eq!(reminders.len(), 2);
+        for reminder in reminders {
+            assert_eq!(
+                reminder.message.thread_id.as_deref(),
+                Some(original_id.to_string().as_str())
+            );
+            assert_eq!(reminder.message.ack_required, 0);
+            assert!(
+                reminder
+                    .message
+                    .body_md
+                    .contains(&format!("#{original_id}"))
+            );
+        }
+    }
+}
@@ -5,6 +5,11 @@
 //! - Phase 2: release stale reservations by inactivity heuristics
 //! - Logs via structlog + optional rich panel
 //!
+//! Phase 2 is health-gated: while mailbox writes were blocked or health was
+//! Red within the inactivity window, agents could not record activity, so the
+//! tick skips stale release and records why in the evidence ledger and the
+//! reaper history surfaced by diagnostics.
+//!
 //! The worker runs on a dedicated OS thread with `std::thread::sleep` between
 //! iterations, matching the WBQ pattern in `mcp-agent-mail-storage`.
 
@@ -12,9 +17,14 @@
 
 use asupersync::{Cx, Outcome};
 use fastmcp_core::block_on;
-use mcp_agent_mail_core::{Config, pattern_overlap::CompiledPattern};
+use mcp_agent_mail_core::{
+    Config, ReaperDecision, ReaperPauseReason, cached_health_level,
+    pattern_overlap::CompiledPattern, reaper_pause_reason, recent_level_transitions,
+    record_reaper_decision,
+};
 use mcp_agent_mail_db::{
-    DbPool, DbPoolConfig, FileReservationRow, create_pool, now_micros,
+    DbPool, DbPoolConfig, FileReservationRow, MailboxState, VerdictOptions,
+    compute_mailbox_verdict, create_pool, now_micros,
     queries::{
         self, get_agent_last_mail_activity, list_unreleased_file_reservations,
         project_ids_with_active_reservations, prune_released_file_reservations,
@@ -156,6 +166,10 @@ fn cleanup_loop(config: &Config) {
     }
 
     let mut probe_cache = CleanupProbeCache::default();
+    let inactivity_window_us = config
+        .file_reservation_inactivity_seconds
+        .saturating_mul(1_000_000);
+    let mut writes_blocked_at_us = None;
 
     loop {
         if SHUTDOWN.load(Ordering::Acquire) {
@@ -163,8 +177,20 @@ fn cleanup_loop(config: &Config) {
             return;
         }
 
+        let now_us = u64::try_from(now_micros()).unwrap_or(0);
+        if mailbox_writes_blocked(config) {
+            writes_blocked_at_us = Some(now_us);
+        }
+        let stale_pause = reaper_pause_reason(
+            now_us,
+            inactivity_window_us,
+            cached_health_level(),
+            &recent_level_transitions(),
+            writes_blocked_at_us,
+        );
+
         // Run one cleanup cycle, suppressing all errors (legacy: never crash server).
-        match run_cleanup_cycle_with_cache(config, &pool, &mut probe_cache) {
+        match run_cleanup_cycle_with_cache(config, &pool, &mut probe_cache, stale_pause) {
             Ok((projects_scanned, released)) => {
                 info!(
                     event = "file_reservations_cleanup",
@@ -191,6 +217,24 @@ fn cleanup_loop(config: &Config) {
     }
 }
 
+/// Whether the mailbox is in a state that refuses writes, which keeps agents
+/// from bumping `last_active_ts`. Advisory states (stale archive, suspect) do
+/// not count.
+fn mailbox_writes_blocked(config: &Config) -> bool {
+    let verdict = compute_mailbox_verdict(
+        &config.database_url,
+        config.storage_root.as_path(),
+        &VerdictOptions::fast(),
+    );
+    matches!(
+        verdict.state,
+        MailboxState::DegradedReadOnly
+            | MailboxState::Recovering
+            | MailboxState::Broken
+            | MailboxState::Escalate
+    )
+}
+
 fn sleep_with_shutdown(duration: std::time::Duration) -> bool {
     let mut remaining = duration;
     while !remaining.is_zero() {
@@ -210,13 +254,16 @@ fn sleep_with_shutdown(duration: std::time::Duration) -> bool {
 #[cfg(test)]
 fn run_cleanup_cycle(config: &Config, pool: &DbPool) -> Result<(usize, usize), String> {
     let mut probe_cache = CleanupProbeCache::default();
-    run_cleanup_cycle_with_cache(config, pool, &mut probe_cache)
+    run_cleanup_cycle_with_cache(config, pool, &mut probe_cache, None)
 }
 
+/// Run a single cleanup cycle. When `stale_pause` is set, Phase 2 is skipped
+/// for every project and the skipped tick is recorded.
 fn run_cleanup_cycle_with_cache(
     config: &Config,
     pool: &DbPool,
     probe_cache: &mut CleanupProbeCache,
+    stale_pause: Option<ReaperPauseReason>,
 ) -> Result<(usize, usize), String> {
     // This worker runs on a dedicated OS thread outside the async runtime, so
     // there is no parent Cx to derive from. Borrow the runtime-backed ambient
@@ -236,7 +283,28 @@ fn run_cleanup_cycle_with_cache(
             other => return Err(format!("failed to list projects: {other:?}")),
         };
 
+    if let Some(reason) = stale_pause {
+        mcp_agent_mail_core::evidence_ledger().record(
+            "cleanup.stale_release",
+            serde_json::json!({
+                "reason": reason,
+                "inactivity_window_secs": config.file_reservation_inactivity_seconds,
+                "projects": project_ids.len(),
+            }),
+            "skip",
+            Some("no release for inactivity explained by an outage".into()),
+            1.0,
+            "health_gate_v1",
+        );
+        warn!(
+            reason = ?reason,
+            "cleanup: stale reservation release skipped; health was degraded within the inactivity window"
+        );
+    }
+
     let mut total_released = 0usize;
+    let mut stale_evaluated = 0usize;
+    let mut stale_released = 0usize;
 
     for pid in &project_ids {
         // Phase 1: release expired.
@@ -270,17 +338,24 @@ fn run_cleanup_cycle_with_cache(
         total_released += expired_ids.len();
 
         // Phase 2: detect and release stale.
-        let stale_ids = match detect_and_release_stale(config, pool, &cx, *pid, probe_cache) {
-            Ok(ids) => ids,
-            Err(err) => {
-                warn!(
-                    project_id = *pid,
-                    error = %err,
-                    "cleanup: failed to detect and release stale reservations for project"
-                );
-                Vec::new()
-            }
+        let stale_ids = match stale_pause {
+            Some(_) => Vec::new(),
+            None => match detect_and_release_stale(config, pool, &cx, *pid, probe_cache) {
+                Ok(sweep) => {
+                    stale_evaluated += sweep.evaluated;
+                    sweep.released_ids
+                }
+                Err(err) => {
+                    warn!(
+                        project_id = *pid,
+                        error = %err,
+                        "cleanup: failed to detect and release stale reservations for project"
+                    );
+                    Vec::new()
+                }
+            },
         };
+        stale_released += stale_ids.len();
         total_released += stale_ids.len();
 
         // Write archive artifacts for released reservations.
@@ -347,10 +422,26 @@ fn run_cleanup_cycle_with_cache(
         }
     }
 
+    record_reaper_decision(ReaperDecision {
+        at_us: u64::try_from(now_micros()).unwrap_or(0),
+        ran: stale_pause.is_none(),
+        reason: stale_pause,
+        evaluated: stale_evaluated,
+        released: stale_released,
+    });
+
     probe_cache.prune_stale(now_micros());
     Ok((project_ids.len(), total_released))
 }
 
+/// Result of Phase 2 for one project.
+#[derive(Debug)]
+struct StaleSweep {
+    /// Active reservations checked for inactivity.
+    evaluated: usize,
+    released_ids: Vec<i64>,
+}
+
 /// Phase 2: Detect stale reservations by inactivity heuristics and release them.
 ///
 /// A reservation is stale when ALL of:
@@ -365,7 +456,7 @@ fn detect_and_release_stale(
     cx: &Cx,
     project_id: i64,
     probe_cache: &mut CleanupProbeCache,
-) -> Result<Vec<i64>, String> {
+) -> Result<StaleSweep, String> {
     let inactivity_us = i64::try_from(config.file_reservation_inactivity_seconds)
         .unwrap_or(1800)
         .saturating_mul(1_000_000);
@@ -376,7 +467,10 @@ fn detect_and_release_stale(
 
     let active = active_reservations_for_stale_cleanup(cx, pool, project_id, now)?;
     if active.is_empty() {
-        return Ok(Vec::new());
+        return Ok(StaleSweep {
+            evaluated: 0,
+            released_ids: Vec::new(),
+        });
     }
 
     // Project workspace is identical for every reservation in this cycle.
@@ -463,14 +557,21 @@ fn detect_and_release_stale(
         }
     }
 
+    let evaluated = active.len();
     if stale_ids.is_empty() {
-        return Ok(Vec::new());
+        return Ok(StaleSweep {
+            evaluated,
+            released_ids: Vec::new(),
+        });
     }
 
     // Bulk-release stale reservations.
     match block_on(async { release_reservations_by_ids_returning_ids(cx, pool, &stale_ids).await })
     {
-        Outcome::Ok(released_ids) => Ok(released_ids),
+        Outcome::Ok(released_ids) => Ok(StaleSweep {
+            evaluated,
+            released_ids,
+        }),
         other => Err(format!("failed to release stale reservations: {other:?}")),
     }
 }
@@ -1647,9 +1748,10 @@ mod tests {
         config.file_reservation_activity_grace_seconds = 900;
 
         let mut probe_cache = CleanupProbeCache::default();
-        let released = detect_and_release_stale(&config, &pool, &cx, project_id, &mut probe_cache)
+        let sweep = detect_and_release_stale(&config, &pool, &cx, project_id, &mut probe_cache)
             .expect("stale pass");
-        assert!(released.is_empty());
+        assert_eq!(sweep.evaluated, 1);
+        assert!(sweep.released_ids.is_empty());
 
         let rows = match fastmcp_core::block_on(async {
             queries::list_file_reservations(&cx, &pool, project_id, false).await
@@ -1678,10 +1780,9 @@ mod tests {
         config.file_reservation_activity_grace_seconds = 0;
 
         let mut probe_cache = CleanupProbeCache::default();
-        let released = detect_and_release_stale(&config, &pool, &cx, project_id, &mut probe_cache)
+        let sweep = detect_and_release_stale(&config, &pool, &cx, project_id, &mut probe_cache)
             .expect("stale pass");
-        assert_eq!(released.len(), 1);
-        assert_eq!(released[0], reservation_id);
+        assert_eq!(sweep.released_ids, [reservation_id]);
 
         let rows = match fastmcp_core::block_on(async {
             queries::list_file_reservations(&cx, &pool, project_id, false).await
@@ -1699,6 +1800,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cleanup_cycle_skips_stale_release_for_gaps_explained_by_an_outage() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (pool, cx, project_id, _agent_id, reservation_id, _human_key, _pattern) =
+            seed_active_reservation(&tmp);
+
+        let mut config = Config::from_env();
+        config.file_reservation_inactivity_seconds = 0;
+        config.file_reservation_activity_grace_seconds = 0;
+
+        // Health went Red and recovered inside the (one hour) window, so the
+        // holder's inactivity is explained by the outage.
+        let now_us = u64::try_from(now_micros()).unwrap();
+        let outage = [
+            mcp_agent_mail_core::LevelTransition {
+                from: mcp_agent_mail_core::HealthLevel::Green,
+                to: mcp_agent_mail_core::HealthLevel::Red,
+                at_us: now_us - 1_200_000_000,
+            },
+            mcp_agent_mail_core::LevelTransition {
+                from: mcp_agent_mail_core::HealthLevel::Red,
+                to: mcp_agent_mail_core::HealthLevel::Green,
+                at_us: now_us - 600_000_000,
+            },
+        ];
+        let pause = reaper_pause_reason(
+            now_us,
+            3_600_000_000,
+            mcp_agent_mail_core::HealthLevel::Green,
+            &outage,
+            None,
+        );
+        assert_eq!(pause, Some(ReaperPauseReason::HealthRedWithinWindow));
+
+        let mut probe_cache = CleanupProbeCache::default();
+        let (_, released) = run_cleanup_cycle_with_cache(&config, &pool, &mut probe_cache, pause)
+            .expect("paused cleanup");
+        assert_eq!(released, 0);
+        assert!(
+            mcp_agent_mail_core::recent_reaper_decisions()
+                .iter()
+                .any(|d| !d.ran && d.reason == pause && d.released == 0),
+            "skipped tick must be recorded"
+        );
+        let rows = match fastmcp_core::block_on(async {
+            queries::list_file_reservations(&cx, &pool, project_id, false).await
+        }) {
+            Outcome::Ok(r) => r,
+            other => panic!("list_file_reservations failed: {other:?}"),
+        };
+        assert!(
+            rows.iter()
+                .any(|r| r.id == Some(reservation_id) && r.released_ts.is_none()),
+            "reservation must survive a paused tick"
+        );
+
+        let (_, released) = run_cleanup_cycle_with_cache(&config, &pool, &mut probe_cache, None)
+            .expect("healthy cleanup");
+        assert_eq!(released, 1, "a healthy tick releases the stale reservation");
+    }
+
     #[test]
     fn detect_and_release_stale_errors_when_workspace_is_missing() {
         let tmp = tempfile::tempdir().unwrap();
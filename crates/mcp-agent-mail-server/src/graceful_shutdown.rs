@@ -0,0 +1,140 @@
+//! Signal-driven graceful shutdown for the HTTP server.
+//!
+//! `serve-http --no-tui` traps SIGTERM/SIGINT: the first signal flips a
+//! process-wide flag the HTTP supervisor polls, after which it stops
+//! accepting connections and lets in-flight requests finish for up to
+//! `AGENT_MAIL_SHUTDOWN_DRAIN_SECONDS` before force-closing. [`finish`] then
+//! flushes buffered KPI/evidence state and checkpoints the WAL so the process
+//! exits 0 with nothing lost. A second signal during the drain skips all of
+//! that and exits immediately with [`FORCED_SHUTDOWN_EXIT_CODE`].
+//!
+//! The TUI quit action goes through the same drain ([`drain_timeout`]) and
+//! [`finish`] path; only the signal trap itself is headless-only, because the
+//! TUI runtime owns terminal signals.
+
+#![forbid(unsafe_code)]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use mcp_agent_mail_core::Config;
+
+/// Exit code when a second SIGTERM/SIGINT arrives while draining.
+pub const FORCED_SHUTDOWN_EXIT_CODE: i32 = 3;
+
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Ask the running server to drain and exit. Returns `true` for the first
+/// request, `false` when a shutdown was already underway.
+#[must_use]
+pub fn request() -> bool {
+    !SHUTDOWN_REQUESTED.swap(true, Ordering::SeqCst)
+}
+
+/// Whether a graceful shutdown has been requested.
+#[must_use]
+pub fn is_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+/// How long in-flight requests may keep running once shutdown starts.
+#[must_use]
+pub const fn drain_timeout(config: &Config) -> Duration {
+    Duration::from_secs(config.shutdown_drain_secs)
+}
+
+/// Flush buffered observability state and checkpoint the WAL.
+///
+/// Runs after the listener has drained and background workers have stopped.
+/// Safe mode writes nothing, so it skips the checkpoint.
+pub fn finish(config: &Config) {
+    // The metrics worker samples on a timer; take one last sample so the
+    // interval since the previous tick is not lost.
+    mcp_agent_mail_core::kpi_record_sample();
+    if let Err(error) = mcp_agent_mail_core::flush_evidence_ledger() {
+        tracing::warn!(error = %error, "failed to flush evidence ledger during shutdown");
+    }
+    if !config.safe_mode {
+        crate::cleanup_shutdown_sqlite_sidecars(config);
+    }
+    tracing::info!("graceful shutdown complete");
+}
+
+/// Installed SIGTERM/SIGINT trap; dropping it stops the listener thread.
+#[cfg(unix)]
+pub struct SignalTrap {
+    handle: signal_hook::iterator::Handle,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(unix)]
+impl SignalTrap {
+    /// Route SIGTERM/SIGINT into [`request`]; a repeat signal force-exits.
+    pub fn install() -> std::io::Result<Self> {
+        use signal_hook::consts::signal::{SIGINT, SIGTERM};
+        use signal_hook::iterator::Signals;
+
+        let mut signals = Signals::new([SIGTERM, SIGINT])?;
+        let handle = signals.handle();
+        let thread = std::thread::Builder::new()
+            .name("am-shutdown-signals".to_string())
+            .spawn(move || {
+                for signal in signals.forever() {
+                    if request() {
+                        tracing::info!(
+                            signal,
+                            "shutdown signal received; draining in-flight requests"
+                        );
+                    } else {
+                        tracing::warn!(
+                            signal,
+                            "second shutdown signal received; exiting immediately"
+                        );
+                        eprintln!(
+                            "[shutdown] second signal received; exiting without draining \
+                             (exit {FORCED_SHUTDOWN_EXIT_CODE})"
+                        );
+                        std::process::exit(FORCED_SHUTDOWN_EXIT_CODE);
+                    }
+                }
+            })?;
+        Ok(Self {
+            handle,
+            thread: Some(thread),
+        })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for SignalTrap {
+    fn drop(&mut self) {
+        self.handle.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Non-unix targets keep the platform's default Ctrl-C behaviour.
+#[cfg(not(unix))]
+pub struct SignalTrap;
+
+#[cfg(not(unix))]
+impl SignalTrap {
+    pub const fn install() -> std::io::Result<Self> {
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_timeout_follows_config() {
+        let mut config = Config::default();
+        assert_eq!(drain_timeout(&config), Duration::from_secs(15));
+        config.shutdown_drain_secs = 2;
+        assert_eq!(drain_timeout(&config), Duration::from_secs(2));
+    }
+}
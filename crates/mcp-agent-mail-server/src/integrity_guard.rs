@@ -185,6 +185,7 @@ fn monitor_loop(config: &Config, sqlite_path: &Path) {
     let mut last_vacuum: Option<Instant> = Some(maintenance_start);
     let mut last_atc_retention: Option<Instant> = Some(maintenance_start);
     let mut last_doctor_retention: Option<Instant> = Some(maintenance_start);
+    let mut last_quarantine_prune: Option<Instant> = Some(maintenance_start);
     let mut skip_first_quick_cycle = SKIP_NEXT_QUICK_CYCLE.swap(false, Ordering::AcqRel);
 
     loop {
@@ -230,6 +231,7 @@ fn monitor_loop(config: &Config, sqlite_path: &Path) {
             &mut last_vacuum,
             &mut last_atc_retention,
             &mut last_doctor_retention,
+            &mut last_quarantine_prune,
         );
 
         // Sleep in short increments so shutdown reacts quickly.
@@ -381,6 +383,7 @@ fn run_db_maintenance_cycle(
     last_vacuum: &mut Option<Instant>,
     last_atc_retention: &mut Option<Instant>,
     last_doctor_retention: &mut Option<Instant>,
+    last_quarantine_prune: &mut Option<Instant>,
 ) {
     if !config.db_maintenance_enabled {
         return;
@@ -525,6 +528,83 @@ fn run_db_maintenance_cycle(
         }
         *last_doctor_retention = Some(now);
     }
+
+    // Opt-in deletion of old `.corrupt-*` quarantine sets. Unlike the sweep
+    // above this DOES delete, so it is off unless
+    // DOCTOR_QUARANTINE_PRUNE_ENABLED is set, always keeps the newest set plus
+    // anything under the min age, and `delete_quarantine_sets` refuses outright
+    // when no healthy main DB exists. Every pass that deletes is recorded in
+    // the evidence ledger.
+    if config.doctor_quarantine_prune_enabled
+        && maintenance_task_due(
+            config.doctor_quarantine_prune_interval_secs,
+            *last_quarantine_prune,
+            now,
+        )
+    {
+        run_quarantine_prune_pass(config, sqlite_path);
+        *last_quarantine_prune = Some(now);
+    }
+}
+
+fn run_quarantine_prune_pass(config: &Config, sqlite_path: &Path) {
+    use mcp_agent_mail_db::recovery_retention as rr;
+
+    let sets = rr::group_quarantine_sets(rr::enumerate_corrupt_quarantines(sqlite_path));
+    let now_us = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .and_then(|d| i64::try_from(d.as_micros()).ok())
+        .unwrap_or(0);
+    let plan = rr::select_quarantine_sets_to_prune(
+        sets,
+        rr::QuarantinePrunePolicy {
+            // The automatic pass always keeps at least the newest set.
+            keep_latest: usize::try_from(config.doctor_quarantine_keep_latest.max(1))
+                .unwrap_or(usize::MAX),
+            min_age_secs: config.doctor_quarantine_min_age_secs,
+        },
+        now_us,
+    );
+    if plan.prune.is_empty() {
+        return;
+    }
+    let stamps: Vec<&str> = plan.prune.iter().map(|set| set.stamp.as_str()).collect();
+    match rr::delete_quarantine_sets(sqlite_path, &plan.prune) {
+        Ok(outcome) => {
+            tracing::info!(
+                deleted_files = outcome.deleted,
+                deleted_bytes = outcome.deleted_bytes,
+                kept_sets = plan.keep.len(),
+                failures = outcome.failures.len(),
+                "quarantine prune removed old corrupt-DB quarantine sets"
+            );
+            mcp_agent_mail_core::evidence_ledger().record(
+                "doctor.quarantine_prune",
+                serde_json::json!({
+                    "database": sqlite_path.display().to_string(),
+                    "pruned_sets": stamps,
+                    "kept_sets": plan.keep.len(),
+                    "deleted_files": outcome.deleted,
+                    "deleted_bytes": outcome.deleted_bytes,
+                    "failures": outcome.failures.len(),
+                    "keep_latest": config.doctor_quarantine_keep_latest.max(1),
+                    "min_age_secs": config.doctor_quarantine_min_age_secs,
+                }),
+                "delete",
+                Some("healthy main db present".into()),
+                1.0,
+                "quarantine_retention_v1",
+            );
+        }
+        Err(err) => {
+            tracing::warn!(
+                error = %err,
+                pruned_sets = stamps.len(),
+                "quarantine prune skipped"
+            );
+        }
+    }
 }
 
 fn handle_integrity_error(
@@ -952,6 +1032,7 @@ mod tests {
         let mut va = None;
         let mut atc = None;
         let mut dr = None;
+        let mut qp = None;
         run_db_maintenance_cycle(
             &pool,
             &config,
@@ -962,6 +1043,7 @@ mod tests {
             &mut va,
             &mut atc,
             &mut dr,
+            &mut qp,
         );
         assert!(
             cp.is_none() && an.is_none() && va.is_none() && atc.is_none() && dr.is_none(),
@@ -969,6 +1051,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn quarantine_prune_pass_keeps_newest_and_young_sets() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sqlite_path = tmp.path().join("storage.sqlite3");
+        let mut header = b"SQLite format 3\0".to_vec();
+        header.resize(4096, 0);
+        std::fs::write(&sqlite_path, header).unwrap();
+
+        let day = Duration::from_secs(86_400);
+        let quarantine = |name: &str, age: Duration| {
+            let path = tmp.path().join(name);
+            std::fs::write(&path, vec![0_u8; 32]).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(std::time::SystemTime::now() - age)
+                .unwrap();
+            path
+        };
+        let oldest = quarantine("storage.sqlite3.corrupt-20260101_000000_000", day * 60);
+        let oldest_wal = quarantine("storage.sqlite3-wal.corrupt-20260101_000000_000", day * 60);
+        let older = quarantine("storage.sqlite3.corrupt-20260201_000000_000", day * 30);
+        let newest = quarantine("storage.sqlite3.corrupt-20260301_000000_000", day * 20);
+        let young = quarantine("storage.sqlite3.corrupt-20260320_000000_000", day);
+
+        let config = Config {
+            doctor_quarantine_prune_enabled: true,
+            doctor_quarantine_keep_latest: 0,
+            doctor_quarantine_min_age_secs: 14 * 86_400,
+            ..Config::default()
+        };
+        run_quarantine_prune_pass(&config, &sqlite_path);
+
+        // keep_latest is floored at 1 for the automatic pass: the newest set
+        // (young) survives by count, everything past 14 days goes.
+        assert!(young.exists());
+        assert!(!newest.exists());
+        assert!(!older.exists());
+        assert!(!oldest.exists());
+        assert!(!oldest_wal.exists());
+        assert!(sqlite_path.exists());
+    }
+
+    #[test]
+    fn quarantine_prune_pass_never_deletes_without_healthy_db() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sqlite_path = tmp.path().join("storage.sqlite3");
+        let quarantined = tmp
+            .path()
+            .join("storage.sqlite3.corrupt-20260101_000000_000");
+        std::fs::write(&quarantined, b"only copy").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&quarantined)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - Duration::from_secs(90 * 86_400))
+            .unwrap();
+        // A second, newer set so the old one is beyond keep_latest.
+        std::fs::write(
+            tmp.path()
+                .join("storage.sqlite3.corrupt-20260301_000000_000"),
+            b"newer",
+        )
+        .unwrap();
+
+        let config = Config {
+            doctor_quarantine_prune_enabled: true,
+            doctor_quarantine_min_age_secs: 0,
+            ..Config::default()
+        };
+        run_quarantine_prune_pass(&config, &sqlite_path);
+        assert!(quarantined.exists(), "no main DB => quarantine is kept");
+    }
+
     #[test]
     fn run_db_maintenance_cycle_advances_due_tasks_and_backs_off() {
         let config = Config {
@@ -994,6 +1151,7 @@ mod tests {
         let mut va = None;
         let mut atc = None;
         let mut dr = None;
+        let mut qp = None;
         run_db_maintenance_cycle(
             &pool,
             &config,
@@ -1004,6 +1162,7 @@ mod tests {
             &mut va,
             &mut atc,
             &mut dr,
+            &mut qp,
         );
         assert_eq!(cp, Some(now), "checkpoint cursor advanced");
         assert_eq!(an, Some(now), "analyze cursor advanced");
@@ -1013,6 +1172,7 @@ mod tests {
             dr, None,
             "doctor retention sweep disabled => cursor untouched"
         );
+        assert_eq!(qp, None, "quarantine prune is opt-in => cursor untouched");
 
         // Re-running at the same instant: cursors are fresh, so nothing is due
         // and they must stay put (off-hot-path back-off).
@@ -1027,6 +1187,7 @@ mod tests {
             &mut va,
             &mut atc,
             &mut dr,
+            &mut qp,
         );
         assert_eq!(
             (cp, an, va, atc),
@@ -58,6 +58,7 @@ pub const COMPAT_HEALTH_PATHS: &[&str] = &[
     "/health/readiness",
     "/health",
     "/healthz",
+    "/readyz",
 ];
 
 /// Locked OAuth well-known endpoint path.
@@ -69,6 +70,7 @@ pub const COMPAT_MAIL_UI_PREFIX: &str = "/mail";
 /// Locked MCP base path aliases (interchangeable for dev convenience).
 pub const COMPAT_MCP_ALIASES: &[&str] = &["/api", "/mcp"];
 
+mod ack_reminder;
 mod ack_ttl;
 pub mod atc;
 pub mod atc_replay;
@@ -76,10 +78,13 @@ pub mod backup_rotation;
 mod cleanup;
 pub mod console;
 mod disk_monitor;
+pub mod graceful_shutdown;
 mod integrity_guard;
 mod mail_ui;
 pub mod maintenance;
 mod markdown;
+mod project_tokens;
+mod reservation_expiry;
 pub mod retention;
 pub mod startup_checks;
 pub mod static_export;
@@ -87,6 +92,7 @@ mod static_files;
 mod templates;
 pub mod theme;
 mod tool_metrics;
+pub mod tool_registry;
 pub mod tui_action_menu;
 pub mod tui_app;
 pub mod tui_bridge;
@@ -119,6 +125,7 @@ pub mod tui_web_dashboard;
 pub mod tui_widgets;
 mod tui_ws_input;
 mod tui_ws_state;
+mod uds;
 
 use asupersync::channel::mpsc;
 use asupersync::http::h1::HttpClient;
@@ -153,32 +160,23 @@ use jsonwebtoken::{DecodingKey, Validation};
 use mcp_agent_mail_core::config::{ConsoleSplitMode, ConsoleUiAnchor};
 use mcp_agent_mail_core::{
     EffectKind, ExperienceBuilder, ExperienceOutcome, ExperienceRow, ExperienceState,
-    ExperienceSubsystem, FeatureExtension, FeatureVector, NonExecutionReason, loss_to_bp,
-    prob_to_bp, saturating_u8,
+    ExperienceSubsystem, FeatureExtension, FeatureVector, NonExecutionReason, ToolRateLimit,
+    loss_to_bp, prob_to_bp, saturating_u8,
 };
 use mcp_agent_mail_db::{
     DbConn, DbPoolConfig, QueryTracker, active_tracker, create_pool, set_active_tracker,
 };
 use mcp_agent_mail_tools::{
-    AcknowledgeMessage, AcquireBuildSlot, AgentsListResource, CheckFileReservationConflicts,
-    CleanupPaneIdentities, ConfigEnvironmentQueryResource, ConfigEnvironmentResource,
-    CreateAgentIdentity, EnsureProduct, EnsureProject, FetchInbox, FetchInboxProduct,
-    FileReservationPaths, FileReservationsResource, ForceReleaseFileReservation, HealthCheck,
-    IdentityProjectResource, InboxResource, InstallPrecommitGuard, ListAgents, ListContacts,
-    MacroContactHandshake, MacroFileReservationCycle, MacroPrepareThread, MacroStartSession,
-    MailboxResource, MailboxWithCommitsResource, MarkMessageRead, MessageDetailsResource,
-    OutboxResource, ProductDetailsResource, ProductsLink, ProjectDetailsResource,
-    ProjectsListQueryResource, ProjectsListResource, RegisterAgent, ReleaseBuildSlot,
-    ReleaseFileReservations, RenewBuildSlot, RenewFileReservations, ReplyMessage, RequestContact,
-    ResolvePaneIdentity, RespondContact, SearchMessages, SearchMessagesProduct, SendMessage,
-    SetContactPolicy, SummarizeThread, SummarizeThreadProduct, ThreadDetailsResource,
+    AgentsListResource, ConfigEnvironmentQueryResource, ConfigEnvironmentResource,
+    FileReservationsResource, IdentityProjectResource, InboxResource, MailboxResource,
+    MailboxWithCommitsResource, MessageDetailsResource, OutboxResource, ProductDetailsResource,
+    ProjectDetailsResource, ProjectsListQueryResource, ProjectsListResource, ThreadDetailsResource,
     ToolingCapabilitiesResource, ToolingDiagnosticsQueryResource, ToolingDiagnosticsResource,
     ToolingDirectoryQueryResource, ToolingDirectoryResource, ToolingLocksQueryResource,
     ToolingLocksResource, ToolingMetricsCoreQueryResource, ToolingMetricsCoreResource,
     ToolingMetricsQueryResource, ToolingMetricsResource, ToolingRecentResource,
-    ToolingSchemasQueryResource, ToolingSchemasResource, UninstallPrecommitGuard,
-    ViewsAckOverdueResource, ViewsAckRequiredResource, ViewsAcksStaleResource,
-    ViewsUrgentUnreadResource, Whois, clusters,
+    ToolingSchemasQueryResource, ToolingSchemasResource, ViewsAckOverdueResource,
+    ViewsAckRequiredResource, ViewsAcksStaleResource, ViewsUrgentUnreadResource,
 };
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
@@ -249,6 +247,11 @@ impl<T: fastmcp::ToolHandler> fastmcp::ToolHandler for InstrumentedTool<T> {
         // Keep cached health current; otherwise stale red can linger until an explicit
         // health-check call refreshes the cache.
         let _ = mcp_agent_mail_core::refresh_health_level();
+        if let Some(err) =
+            safe_mode_rejection(self.tool_name, mcp_agent_mail_core::safe_mode::is_active())
+        {
+            return Err(err);
+        }
         // Backpressure gate: reject shedable tools under Red (when enabled)
         if mcp_agent_mail_core::should_shed_tool(self.tool_name) {
             return Err(McpError::new(
@@ -348,6 +351,11 @@ impl<T: fastmcp::ToolHandler> fastmcp::ToolHandler for InstrumentedTool<T> {
         // Keep cached health current; otherwise stale red can linger until an explicit
         // health-check call refreshes the cache.
         let _ = mcp_agent_mail_core::refresh_health_level();
+        if let Some(err) =
+            safe_mode_rejection(self.tool_name, mcp_agent_mail_core::safe_mode::is_active())
+        {
+            return Box::pin(std::future::ready(fastmcp_core::Outcome::Err(err)));
+        }
         // Backpressure gate: reject shedable tools under Red (when enabled)
         if mcp_agent_mail_core::should_shed_tool(self.tool_name) {
             return Box::pin(std::future::ready(fastmcp_core::Outcome::Err(
@@ -447,6 +455,13 @@ impl<T: fastmcp::ToolHandler> fastmcp::ToolHandler for InstrumentedTool<T> {
     }
 }
 
+/// Safe-mode gate: every tool outside the read-only allowlist gets a
+/// `SAFE_MODE` error before it can touch the mailbox.
+fn safe_mode_rejection(tool_name: &str, safe_mode_active: bool) -> Option<McpError> {
+    (safe_mode_active && !mcp_agent_mail_tools::is_safe_mode_tool(tool_name))
+        .then(|| mcp_agent_mail_tools::safe_mode_tool_error(tool_name))
+}
+
 /// Extract `project_key` and agent name from tool arguments for event tagging.
 fn extract_project_agent(args: &serde_json::Value) -> (Option<String>, Option<String>) {
     let obj = args.as_object();
@@ -538,18 +553,16 @@ fn add_tool<T: fastmcp::ToolHandler + 'static>(
 ) -> fastmcp_server::ServerBuilder {
     if config.should_expose_tool(tool_name, cluster) {
         if let Some(tool_index) = mcp_agent_mail_tools::tool_index(tool_name) {
-            // Resolve the static tool name from TOOL_CLUSTER_MAP for event emission
-            let static_name = mcp_agent_mail_tools::TOOL_CLUSTER_MAP[tool_index].0;
             server.tool(InstrumentedTool {
                 tool_index,
-                tool_name: static_name,
+                tool_name,
                 inner: tool,
             })
         } else {
             tracing::error!(
                 tool = tool_name,
                 cluster,
-                "tool missing from TOOL_CLUSTER_MAP; registering without instrumentation"
+                "tool missing from tool metrics index; registering without instrumentation"
             );
             server.tool(tool)
         }
@@ -558,280 +571,48 @@ fn add_tool<T: fastmcp::ToolHandler + 'static>(
     }
 }
 
+/// Build the MCP server with the active tool registry: the one installed by
+/// [`run_http_with_tui_and_tools`], or the built-in tools.
 #[must_use]
-#[allow(clippy::too_many_lines)]
 pub fn build_server(config: &mcp_agent_mail_core::Config) -> Server {
+    build_server_with_tools(config, &tool_registry::active_tool_registry())
+}
+
+/// Per-tool rate limit entries that do not match any tool in `registry`.
+fn unknown_rate_limited_tools<'a>(
+    config: &'a mcp_agent_mail_core::Config,
+    registry: &tool_registry::ToolRegistry,
+) -> Vec<&'a str> {
+    config
+        .tool_rate_limits
+        .keys()
+        .map(String::as_str)
+        .filter(|tool| !registry.contains(tool))
+        .collect()
+}
+
+/// Build the MCP server exposing the tools in `registry`.
+#[must_use]
+pub fn build_server_with_tools(
+    config: &mcp_agent_mail_core::Config,
+    registry: &tool_registry::ToolRegistry,
+) -> Server {
     // Wire the config flag into the global atomic gate.
     mcp_agent_mail_core::set_shedding_enabled(config.backpressure_shedding_enabled);
+    for tool in unknown_rate_limited_tools(config, registry) {
+        tracing::warn!(
+            tool = %tool,
+            "AGENT_MAIL_TOOL_RATE_LIMITS names a tool this server does not expose; ignoring"
+        );
+    }
 
     let shutdown_config = config.clone();
     let server = Server::new("mcp-agent-mail", env!("CARGO_PKG_VERSION")).on_shutdown(move || {
         shutdown_runtime_services(&shutdown_config);
     });
-
-    let server = add_tool(
-        server,
-        config,
-        "health_check",
-        clusters::INFRASTRUCTURE,
-        HealthCheck,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "ensure_project",
-        clusters::INFRASTRUCTURE,
-        EnsureProject,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "register_agent",
-        clusters::IDENTITY,
-        RegisterAgent,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "create_agent_identity",
-        clusters::IDENTITY,
-        CreateAgentIdentity,
-    );
-    let server = add_tool(server, config, "whois", clusters::IDENTITY, Whois);
-    let server = add_tool(
-        server,
-        config,
-        "resolve_pane_identity",
-        clusters::IDENTITY,
-        ResolvePaneIdentity,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "cleanup_pane_identities",
-        clusters::IDENTITY,
-        CleanupPaneIdentities,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "list_agents",
-        clusters::IDENTITY,
-        ListAgents,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "send_message",
-        clusters::MESSAGING,
-        SendMessage,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "reply_message",
-        clusters::MESSAGING,
-        ReplyMessage,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "fetch_inbox",
-        clusters::MESSAGING,
-        FetchInbox,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "mark_message_read",
-        clusters::MESSAGING,
-        MarkMessageRead,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "acknowledge_message",
-        clusters::MESSAGING,
-        AcknowledgeMessage,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "request_contact",
-        clusters::CONTACT,
-        RequestContact,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "respond_contact",
-        clusters::CONTACT,
-        RespondContact,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "list_contacts",
-        clusters::CONTACT,
-        ListContacts,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "set_contact_policy",
-        clusters::CONTACT,
-        SetContactPolicy,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "check_file_reservation_conflicts",
-        clusters::FILE_RESERVATIONS,
-        CheckFileReservationConflicts,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "file_reservation_paths",
-        clusters::FILE_RESERVATIONS,
-        FileReservationPaths,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "release_file_reservations",
-        clusters::FILE_RESERVATIONS,
-        ReleaseFileReservations,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "renew_file_reservations",
-        clusters::FILE_RESERVATIONS,
-        RenewFileReservations,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "force_release_file_reservation",
-        clusters::FILE_RESERVATIONS,
-        ForceReleaseFileReservation,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "install_precommit_guard",
-        clusters::INFRASTRUCTURE,
-        InstallPrecommitGuard,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "uninstall_precommit_guard",
-        clusters::INFRASTRUCTURE,
-        UninstallPrecommitGuard,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "search_messages",
-        clusters::SEARCH,
-        SearchMessages,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "summarize_thread",
-        clusters::SEARCH,
-        SummarizeThread,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "macro_start_session",
-        clusters::WORKFLOW_MACROS,
-        MacroStartSession,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "macro_prepare_thread",
-        clusters::WORKFLOW_MACROS,
-        MacroPrepareThread,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "macro_file_reservation_cycle",
-        clusters::WORKFLOW_MACROS,
-        MacroFileReservationCycle,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "macro_contact_handshake",
-        clusters::WORKFLOW_MACROS,
-        MacroContactHandshake,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "ensure_product",
-        clusters::PRODUCT_BUS,
-        EnsureProduct,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "products_link",
-        clusters::PRODUCT_BUS,
-        ProductsLink,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "search_messages_product",
-        clusters::PRODUCT_BUS,
-        SearchMessagesProduct,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "fetch_inbox_product",
-        clusters::PRODUCT_BUS,
-        FetchInboxProduct,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "summarize_thread_product",
-        clusters::PRODUCT_BUS,
-        SummarizeThreadProduct,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "acquire_build_slot",
-        clusters::BUILD_SLOTS,
-        AcquireBuildSlot,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "renew_build_slot",
-        clusters::BUILD_SLOTS,
-        RenewBuildSlot,
-    );
-    let server = add_tool(
-        server,
-        config,
-        "release_build_slot",
-        clusters::BUILD_SLOTS,
-        ReleaseBuildSlot,
-    );
+    let server = registry.install(server, config);
 
     server
-        // Identity
         // Resources
         .resource(ConfigEnvironmentResource)
         .resource(ConfigEnvironmentQueryResource)
@@ -3006,6 +2787,8 @@ fn start_tui_db_background_workers(config: &mcp_agent_mail_core::Config) {
     init_search_bridge(config);
     cleanup::start(config);
     ack_ttl::start(config);
+    ack_reminder::start(config);
+    reservation_expiry::start(config);
     tool_metrics::start(config);
     retention::start(config);
     maintenance::start(config);
@@ -3291,13 +3074,21 @@ pub fn run_http(config: &mcp_agent_mail_core::Config) -> std::io::Result<()> {
     // Pre-intern well-known strings to avoid first-request contention.
     mcp_agent_mail_core::pre_intern_policies();
 
-    // IMPORTANT: startup probes (inside `prepare_http_runtime_startup`) must
-    // run BEFORE acquiring runtime activity locks.  The probes take an
-    // exclusive flock on the activity lockfile to verify no other process
-    // is running; if we already hold a shared flock from
-    // `acquire_runtime_mailbox_activity_locks`, the exclusive attempt
-    // deadlocks (EAGAIN) against our own process.
-    prepare_http_runtime_startup(config)?;
+    let safe_mode = config.safe_mode;
+    if safe_mode {
+        // Set before any tool can be dispatched; there is no way back short of
+        // a restart.
+        mcp_agent_mail_core::safe_mode::activate();
+        log_safe_mode_banner(config);
+    } else {
+        // IMPORTANT: startup probes (inside `prepare_http_runtime_startup`) must
+        // run BEFORE acquiring runtime activity locks.  The probes take an
+        // exclusive flock on the activity lockfile to verify no other process
+        // is running; if we already hold a shared flock from
+        // `acquire_runtime_mailbox_activity_locks`, the exclusive attempt
+        // deadlocks (EAGAIN) against our own process.
+        prepare_http_runtime_startup(config)?;
+    }
 
     // Safe to acquire now -- probes have confirmed we are the sole owner.
     let _runtime_mailbox_locks = acquire_runtime_mailbox_activity_locks(config)?;
@@ -3305,30 +3096,54 @@ pub fn run_http(config: &mcp_agent_mail_core::Config) -> std::io::Result<()> {
     // br-5mnkl: run the DB readiness warmup on a bounded background thread so a
     // pathologically slow recovery can never wedge the listener bind. The
     // listener always comes up within the bind deadline; `/healthz` stays live
-    // and `/health` reports degraded honestly until the DB settles.
-    run_bounded_startup_readiness(config);
+    // and `/health` reports degraded honestly until the DB settles. Safe mode
+    // never migrates or recovers, so it has nothing to warm up.
+    if !safe_mode {
+        run_bounded_startup_readiness(config);
+    }
 
     log_active_database(config);
     let _ = startup_checks::write_listener_pid_hint(&config.http_host, config.http_port);
-    heal_storage_lock_artifacts(config);
-    init_search_bridge(config);
-    mcp_agent_mail_storage::wbq_start();
+    let _safe_mode_marker = if safe_mode {
+        mcp_agent_mail_core::safe_mode::MarkerGuard::write(
+            &config.storage_root,
+            &config.http_host,
+            config.http_port,
+        )
+    } else {
+        heal_storage_lock_artifacts(config);
+        init_search_bridge(config);
+        mcp_agent_mail_storage::wbq_start();
 
-    // Initialize the Air Traffic Controller engine for proactive agent coordination.
-    atc::init_global_atc(config);
-    start_atc_operator_runtime(config);
+        // Initialize the Air Traffic Controller engine for proactive agent coordination.
+        atc::init_global_atc(config);
+        start_atc_operator_runtime(config);
+        None
+    };
 
-    cleanup::start(config);
-    ack_ttl::start(config);
-    tool_metrics::start(config);
-    retention::start(config);
-    maintenance::start(config);
-    integrity_guard::start(config);
-    disk_monitor::start(config);
-    start_advisory_consistency_probe(config);
-    let dashboard = StartupDashboard::maybe_start(config);
-    set_dashboard_handle(dashboard.clone());
-    arm_startup_readiness_fast_path();
+    let workers = background_workers(config);
+    for worker in workers {
+        worker.start(config);
+    }
+    let dashboard = if safe_mode {
+        None
+    } else {
+        start_advisory_consistency_probe(config);
+        let dashboard = StartupDashboard::maybe_start(config);
+        set_dashboard_handle(dashboard.clone());
+        arm_startup_readiness_fast_path();
+        dashboard
+    };
+
+    // SIGTERM/SIGINT drain in-flight requests instead of killing the process;
+    // a trap that fails to install leaves the default (abrupt) behaviour.
+    let signal_trap = match graceful_shutdown::SignalTrap::install() {
+        Ok(trap) => Some(trap),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to install shutdown signal trap");
+            None
+        }
+    };
 
     // Keep headless HTTP (`serve --no-tui`) under the same supervised restart
     // policy as the TUI path so long-lived operator sessions self-heal from
@@ -3336,13 +3151,9 @@ pub fn run_http(config: &mcp_agent_mail_core::Config) -> std::io::Result<()> {
     let result = run_http_headless_supervisor(config.clone());
     clear_startup_readiness_fast_path();
 
-    retention::shutdown();
-    tool_metrics::shutdown();
-    ack_ttl::shutdown();
-    cleanup::shutdown();
-    integrity_guard::shutdown();
-    disk_monitor::shutdown();
-    maintenance::shutdown();
+    for worker in workers.iter().rev() {
+        worker.shutdown();
+    }
     stop_atc_operator_runtime();
     mcp_agent_mail_storage::wbq_shutdown();
     mcp_agent_mail_storage::flush_async_commits();
@@ -3350,17 +3161,132 @@ pub fn run_http(config: &mcp_agent_mail_core::Config) -> std::io::Result<()> {
         dashboard.shutdown();
     }
     set_dashboard_handle(None);
+    graceful_shutdown::finish(config);
+    drop(signal_trap);
     result
 }
 
+/// Periodic background workers owned by the HTTP server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackgroundWorker {
+    Cleanup,
+    AckTtl,
+    AckReminder,
+    ReservationExpiry,
+    ToolMetrics,
+    Retention,
+    Maintenance,
+    IntegrityGuard,
+    DiskMonitor,
+}
+
+impl BackgroundWorker {
+    const ALL: [Self; 9] = [
+        Self::Cleanup,
+        Self::AckTtl,
+        Self::AckReminder,
+        Self::ReservationExpiry,
+        Self::ToolMetrics,
+        Self::Retention,
+        Self::Maintenance,
+        Self::IntegrityGuard,
+        Self::DiskMonitor,
+    ];
+
+    fn start(self, config: &mcp_agent_mail_core::Config) {
+        match self {
+            Self::Cleanup => cleanup::start(config),
+            Self::AckTtl => ack_ttl::start(config),
+            Self::AckReminder => ack_reminder::start(config),
+            Self::ReservationExpiry => reservation_expiry::start(config),
+            Self::ToolMetrics => tool_metrics::start(config),
+            Self::Retention => retention::start(config),
+            Self::Maintenance => maintenance::start(config),
+            Self::IntegrityGuard => integrity_guard::start(config),
+            Self::DiskMonitor => disk_monitor::start(config),
+        }
+    }
+
+    fn shutdown(self) {
+        match self {
+            Self::Cleanup => cleanup::shutdown(),
+            Self::AckTtl => ack_ttl::shutdown(),
+            Self::AckReminder => ack_reminder::shutdown(),
+            Self::ReservationExpiry => reservation_expiry::shutdown(),
+            Self::ToolMetrics => tool_metrics::shutdown(),
+            Self::Retention => retention::shutdown(),
+            Self::Maintenance => maintenance::shutdown(),
+            Self::IntegrityGuard => integrity_guard::shutdown(),
+            Self::DiskMonitor => disk_monitor::shutdown(),
+        }
+    }
+}
+
+/// Workers `run_http` starts for `config`. Safe mode runs none: no reaper,
+/// retention, metrics sampling, or archive maintenance ticks.
+fn background_workers(config: &mcp_agent_mail_core::Config) -> &'static [BackgroundWorker] {
+    if config.safe_mode {
+        &[]
+    } else {
+        &BackgroundWorker::ALL
+    }
+}
+
+fn log_safe_mode_banner(config: &mcp_agent_mail_core::Config) {
+    let banner = format!(
+        "SAFE MODE: read-only emergency server on {}:{}. Setup self-heal, migrations, \
+         and background workers are off; write tools return SAFE_MODE. Restart without \
+         --safe-mode to resume normal operation.",
+        config.http_host, config.http_port
+    );
+    tracing::warn!(db = %config.database_url, "{banner}");
+    eprintln!("[SAFE MODE] {banner}");
+}
+
+#[cfg(test)]
+mod safe_mode_tests {
+    use super::*;
+
+    #[test]
+    fn safe_mode_starts_no_background_workers() {
+        let mut config = mcp_agent_mail_core::Config::default();
+        assert_eq!(background_workers(&config), BackgroundWorker::ALL);
+        config.safe_mode = true;
+        assert!(background_workers(&config).is_empty());
+    }
+
+    #[test]
+    fn safe_mode_rejects_write_tools_only() {
+        for tool in ["send_message", "reply_message", "file_reservation_paths"] {
+            let err = safe_mode_rejection(tool, true).expect("write tool rejected");
+            let data = err.data.expect("structured error payload");
+            assert_eq!(data["error"]["type"], "SAFE_MODE");
+        }
+        assert!(safe_mode_rejection("fetch_inbox", true).is_none());
+        assert!(safe_mode_rejection("send_message", false).is_none());
+    }
+}
+
+/// [`run_http_with_tui`] for embedding products: the server exposes the tools
+/// in `registry` (typically [`tool_registry::ToolRegistry::with_builtins`]
+/// plus custom tools) instead of only the built-in set. The registry also
+/// applies to server rebuilds for the rest of the process.
+pub fn run_http_with_tui_and_tools(
+    config: &mcp_agent_mail_core::Config,
+    registry: tool_registry::ToolRegistry,
+) -> std::io::Result<()> {
+    tool_registry::set_active_tool_registry(registry);
+    run_http_with_tui(config)
+}
+
 /// Run the MCP HTTP server on a background thread and the full TUI on the
 /// main thread.  This is the default mode for `am serve`.
 ///
 /// When `tui_enabled` is false (e.g. non-TTY environments or `--no-tui`),
 /// this falls back to [`run_http`].
 pub fn run_http_with_tui(config: &mcp_agent_mail_core::Config) -> std::io::Result<()> {
-    // Fall back to headless mode when not a TTY or TUI is disabled
-    if !std::io::stdout().is_terminal() || !config.tui_enabled {
+    // Fall back to headless mode when not a TTY, TUI is disabled, or in safe mode
+    if !std::io::stdout().is_terminal() || !config.tui_enabled || config.safe_mode {
         return run_http(config);
     }
 
@@ -3480,6 +3406,8 @@ pub fn run_http_with_tui(config: &mcp_agent_mail_core::Config) -> std::io::Resul
 
         retention::shutdown();
         tool_metrics::shutdown();
+        reservation_expiry::shutdown();
+        ack_reminder::shutdown();
         ack_ttl::shutdown();
         cleanup::shutdown();
         integrity_guard::shutdown();
@@ -3506,6 +3434,8 @@ pub fn run_http_with_tui(config: &mcp_agent_mail_core::Config) -> std::io::Resul
     set_tui_state_handle(None);
     retention::shutdown();
     tool_metrics::shutdown();
+    reservation_expiry::shutdown();
+    ack_reminder::shutdown();
     ack_ttl::shutdown();
     cleanup::shutdown();
     integrity_guard::shutdown();
@@ -3514,6 +3444,7 @@ pub fn run_http_with_tui(config: &mcp_agent_mail_core::Config) -> std::io::Resul
     stop_atc_operator_runtime();
     mcp_agent_mail_storage::wbq_shutdown();
     mcp_agent_mail_storage::flush_async_commits();
+    graceful_shutdown::finish(config);
 
     // Return first error encountered
     combine_tui_and_supervisor_results(tui_result, supervisor_result)
@@ -3638,6 +3569,9 @@ struct HttpServerInstance {
     connection_manager: asupersync::server::connection::ConnectionManager,
     listener_stats: Arc<Http1ListenerStats>,
     request_diagnostics: Arc<HttpRequestRuntimeDiagnostics>,
+    /// Unix socket front (`AGENT_MAIL_UDS_PATH`); dropped after the TCP
+    /// listener stops, which removes the socket file.
+    uds: Option<uds::UdsListener>,
 }
 
 #[derive(Debug, Clone)]
@@ -3937,6 +3871,11 @@ async fn spawn_http_server_instance(
         }))
         .map_err(|err| std::io::Error::other(format!("failed to spawn HTTP listener: {err}")))?;
 
+    let uds = match config.http_uds_path.clone() {
+        Some(path) => Some(bind_uds_listener(runtime_handle, &state, &config, path)?),
+        None => None,
+    };
+
     let mut updated_config = config;
     if updated_config.http_port == 0 {
         updated_config.http_port = local_addr.port();
@@ -3950,10 +3889,60 @@ async fn spawn_http_server_instance(
             connection_manager,
             listener_stats,
             request_diagnostics,
+            uds,
         },
     ))
 }
 
+/// Bind the unix socket front that feeds requests into `state` on the runtime.
+fn bind_uds_listener(
+    runtime_handle: &RuntimeHandle,
+    state: &Arc<HttpState>,
+    config: &mcp_agent_mail_core::Config,
+    path: std::path::PathBuf,
+) -> std::io::Result<uds::UdsListener> {
+    let inject_bearer = if config.http_uds_allow_unauthenticated {
+        if config.http_bearer_token.is_none() && config.http_jwt_enabled {
+            tracing::warn!(
+                "AGENT_MAIL_UDS_ALLOW_UNAUTHENTICATED has no effect without a static bearer \
+                 token; socket clients must still present a JWT"
+            );
+        }
+        config.http_bearer_token.clone()
+    } else {
+        None
+    };
+    let handler_state = Arc::clone(state);
+    let handler_runtime = runtime_handle.clone();
+    let handler: uds::UdsHandler = Arc::new(move |req| {
+        let inner = Arc::clone(&handler_state);
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        let _task = handler_runtime
+            .clone()
+            .try_spawn(async move {
+                let _ = tx.send(inner.handle(req).await);
+            })
+            .map_err(|err| std::io::Error::other(format!("spawn socket request: {err}")))?;
+        rx.recv()
+            .map_err(|_| std::io::Error::other("socket request task ended without a response"))
+    });
+    let listener = uds::UdsListener::bind(
+        uds::UdsOptions {
+            path,
+            mode: config.http_uds_mode,
+            inject_bearer,
+            max_connections: uds::DEFAULT_MAX_CONNECTIONS,
+        },
+        handler,
+    )?;
+    tracing::info!(
+        path = %listener.path().display(),
+        mode = format_args!("{:o}", config.http_uds_mode),
+        "unix socket listener bound"
+    );
+    Ok(listener)
+}
+
 async fn stop_http_server_instance(instance: HttpServerInstance) -> std::io::Result<()> {
     stop_http_server_instance_with_timeouts(
         instance,
@@ -3964,6 +3953,22 @@ async fn stop_http_server_instance(instance: HttpServerInstance) -> std::io::Res
     .await
 }
 
+/// Final stop on process shutdown (signal or TUI quit): in-flight requests get
+/// the full configured drain window instead of the restart-path timeout.
+async fn stop_http_server_instance_for_shutdown(
+    instance: HttpServerInstance,
+    config: &mcp_agent_mail_core::Config,
+) -> std::io::Result<()> {
+    let drain = graceful_shutdown::drain_timeout(config);
+    stop_http_server_instance_with_timeouts(
+        instance,
+        drain,
+        HTTP_SERVER_FORCE_CLOSE_JOIN_TIMEOUT,
+        drain,
+    )
+    .await
+}
+
 enum HttpServerJoinWait {
     Completed(std::io::Result<()>),
     TimedOut,
@@ -4208,9 +4213,10 @@ async fn run_http_server_supervisor(
         if tui_state
             .as_ref()
             .is_some_and(|state| state.is_shutdown_requested())
+            || graceful_shutdown::is_requested()
         {
             record_http_server_shutdown(tui_state.as_deref());
-            return stop_http_server_instance(instance).await;
+            return stop_http_server_instance_for_shutdown(instance, &config).await;
         }
 
         if instance.join.is_finished() {
@@ -4229,6 +4235,7 @@ async fn run_http_server_supervisor(
                 &mut last_restart_sleep_ms,
                 || {
                     cx.is_cancel_requested()
+                        || graceful_shutdown::is_requested()
                         || tui_state
                             .as_ref()
                             .is_some_and(|state| state.is_shutdown_requested())
@@ -4307,7 +4314,7 @@ async fn run_http_server_supervisor(
                     | Err(mpsc::RecvError::Disconnected | mpsc::RecvError::Cancelled),
                 ) => {
                     record_http_server_shutdown(tui_state.as_deref());
-                    return stop_http_server_instance(instance).await;
+                    return stop_http_server_instance_for_shutdown(instance, &config).await;
                 }
                 Ok(Err(mpsc::RecvError::Empty)) | Err(_) => {}
             }
@@ -4390,6 +4397,7 @@ async fn run_http_server_supervisor(
             &mut last_restart_sleep_ms,
             || {
                 cx.is_cancel_requested()
+                    || graceful_shutdown::is_requested()
                     || tui_state
                         .as_ref()
                         .is_some_and(|state| state.is_shutdown_requested())
@@ -6905,6 +6913,10 @@ fn execute_atc_advisory_effect(
                 Some(false),
                 Some(false),
                 None,
+                None,
+                None,
+                None,
+                None,
             )
             .await
         })
@@ -6941,6 +6953,10 @@ fn execute_atc_probe_effect(
                 Some(false),
                 Some(true),
                 None,
+                None,
+                None,
+                None,
+                None,
             )
             .await
         })
@@ -6963,6 +6979,7 @@ fn execute_atc_release_effect(
                 effect.agent.clone(),
                 None,
                 None,
+                None,
             )
             .await
         })
@@ -9953,6 +9970,7 @@ struct HttpState {
     server_capabilities: fastmcp_protocol::ServerCapabilities,
     config: mcp_agent_mail_core::Config,
     rate_limiter: Arc<RateLimiter>,
+    tool_rate_limiter: ToolRateLimiter,
     rate_limit_redis: Mutex<RateLimitRedisState>,
     request_timeout_secs: u64,
     handler: Arc<HttpRequestHandler>,
@@ -9961,6 +9979,8 @@ struct HttpState {
     /// Stampede guard: only one task refreshes JWKS at a time.
     /// Others serve stale cached data while refresh is in-flight.
     jwks_refreshing: AtomicBool,
+    /// Bearer tokens scoped to a single project (`setup run --project-token`).
+    project_tokens: project_tokens::ProjectTokenCache,
     /// Optional web root for SPA static file serving.
     web_root: Option<static_files::WebRoot>,
     /// Reused snapshot state for `/mail/ws-state` polling when no live TUI is active.
@@ -10137,18 +10157,22 @@ impl HttpState {
             };
         let ws_state_fallback = tui_bridge::TuiSharedState::new(&config);
         apply_latest_boot_archive_preflight_snapshot(&ws_state_fallback);
+        let project_tokens =
+            project_tokens::ProjectTokenCache::new(config.http_project_tokens_path.clone());
         Self {
             router,
             server_info,
             server_capabilities,
             config,
             rate_limiter: Arc::new(RateLimiter::new()),
+            tool_rate_limiter: ToolRateLimiter::new(),
             rate_limit_redis: Mutex::new(rate_limit_redis),
             request_timeout_secs: 30,
             handler,
             jwks_http_client: HttpClient::new(),
             jwks_cache: Mutex::new(None),
             jwks_refreshing: AtomicBool::new(false),
+            project_tokens,
             web_root,
             ws_state_fallback,
             request_diagnostics,
@@ -10240,9 +10264,13 @@ impl HttpState {
         // Legacy parity: health routes bypass bearer auth even when configured.
         //
         // Note: the legacy FastAPI stack used a `/health/` prefix check, but this
-        // server also exposes `/health` + `/healthz` aliases for operator tooling
-        // and common probe conventions.
-        if path == "/health" || path == "/healthz" || path.starts_with("/health/") {
+        // server also exposes `/health`, `/healthz`, and `/readyz` for operator
+        // tooling and orchestrator probe conventions.
+        if path == "/health"
+            || path == "/healthz"
+            || path == "/readyz"
+            || path.starts_with("/health/")
+        {
             if let Some(resp) = self.handle_special_routes(&req, &path) {
                 return resp;
             }
@@ -10420,11 +10448,46 @@ impl HttpState {
                 if !matches!(req.method, Http1Method::Get) {
                     return Some(self.error_response(req, 405, "Method Not Allowed"));
                 }
-                return Some(self.health_json_response(
-                    req,
-                    200,
-                    &serde_json::json!({"status":"alive"}),
-                ));
+                let mut body = serde_json::json!({"status":"alive"});
+                // `/healthz` is the orchestrator liveness probe: answering at
+                // all proves the event loop is responsive, and the health
+                // level rides along (atomic reads only) for debugging.
+                // `/health/liveness` keeps the legacy body.
+                if path == "/healthz" {
+                    let info = current_health_info();
+                    body["level"] = serde_json::json!(info.level);
+                    body["signals"] =
+                        serde_json::to_value(&info.signals).unwrap_or(serde_json::Value::Null);
+                }
+                if self.config.safe_mode {
+                    body["safe_mode"] = serde_json::Value::Bool(true);
+                }
+                return Some(self.health_json_response(req, 200, &body));
+            }
+            "/readyz" => {
+                if !matches!(req.method, Http1Method::Get) {
+                    return Some(self.error_response(req, 405, "Method Not Allowed"));
+                }
+                let (ready, body) = if startup_readiness_fast_path_active() {
+                    readyz_report(
+                        Err("server is warming up".to_string()),
+                        None,
+                        &current_health_info(),
+                        self.config.safe_mode,
+                    )
+                } else {
+                    readyz_report(
+                        readiness_check_request_path(&self.config),
+                        probe_runtime_durability_state(
+                            &self.config.database_url,
+                            self.config.storage_root.as_path(),
+                        ),
+                        &current_health_info(),
+                        self.config.safe_mode,
+                    )
+                };
+                let status = if ready { 200 } else { 503 };
+                return Some(self.health_json_response(req, status, &body));
             }
             "/health" | "/health/readiness" => {
                 if !matches!(req.method, Http1Method::Get) {
@@ -10459,6 +10522,10 @@ impl HttpState {
                 // archive or diagnostic scans and make a live listener look
                 // dead under contention.
                 body["durability_state"] = serde_json::json!("not_probed");
+                if self.config.safe_mode {
+                    body["safe_mode"] = serde_json::Value::Bool(true);
+                    body["mode"] = serde_json::json!("read_only");
+                }
                 return Some(self.health_json_response(req, 200, &body));
             }
             "/health/durability" => {
@@ -10548,6 +10615,38 @@ impl HttpState {
             return Some(self.json_response(req, 200, &payload));
         }
 
+        // Runtime control of the backpressure shedding gate, so operators can
+        // flip it mid-incident without restarting (`am tooling shedding`).
+        if path == "/mail/api/backpressure/shedding" || path == "/mail/api/backpressure/shedding/" {
+            match req.method {
+                Http1Method::Get => {}
+                Http1Method::Post => {
+                    if let Some(rejection) = self.mail_csrf_rejection(req) {
+                        return Some(rejection);
+                    }
+                    let Some(enabled) = serde_json::from_slice::<serde_json::Value>(&req.body)
+                        .ok()
+                        .and_then(|body| body.get("enabled").and_then(serde_json::Value::as_bool))
+                    else {
+                        return Some(self.error_response(
+                            req,
+                            400,
+                            "expected JSON body {\"enabled\": true|false}",
+                        ));
+                    };
+                    let previous = mcp_agent_mail_core::shedding_enabled();
+                    mcp_agent_mail_core::set_shedding_enabled(enabled);
+                    if previous != enabled {
+                        tracing::warn!(enabled, "backpressure shedding gate changed at runtime");
+                    }
+                }
+                _ => return Some(self.error_response(req, 405, "Method Not Allowed")),
+            }
+            let payload = serde_json::to_value(mcp_agent_mail_core::shedding_status())
+                .unwrap_or(serde_json::Value::Null);
+            return Some(self.json_response(req, 200, &payload));
+        }
+
         // ── Web Dashboard (TUI mirror in browser) ─── DEFERRED ──────
         if path == "/web-dashboard" || path == "/web-dashboard/" {
             return Some(self.raw_response(
@@ -10634,6 +10733,14 @@ impl HttpState {
         if let Some(rejection) = self.mail_csrf_rejection(req) {
             return rejection;
         }
+        if self.config.safe_mode && matches!(req.method, Http1Method::Post) {
+            let body = serde_json::json!({
+                "error": mcp_agent_mail_core::safe_mode::ERROR_TYPE,
+                "detail": "server is running in safe mode; mail UI writes are disabled",
+            })
+            .to_string();
+            return self.raw_response(req, 503, "application/json", body.into_bytes());
+        }
         let (_path_part, query_part) = split_path_query(&req.uri);
         let query_str = query_part.as_deref().unwrap_or("");
         let method_str = if matches!(req.method, Http1Method::Post) {
@@ -10788,6 +10895,19 @@ impl HttpState {
         constant_time_eq(auth, expected_header.as_str())
     }
 
+    /// The project a presented project-scoped bearer token is limited to.
+    /// The global bearer token takes precedence and is never scoped.
+    fn project_token_scope(
+        &self,
+        req: &Http1Request,
+    ) -> Option<mcp_agent_mail_core::setup::ProjectTokenEntry> {
+        if self.has_expected_bearer_header(req) {
+            return None;
+        }
+        let token = header_value(req, "authorization")?.strip_prefix("Bearer ")?;
+        self.project_tokens.registry().find_token(token).cloned()
+    }
+
     fn static_bearer_rbac_roles(&self) -> Vec<String> {
         if self.config.http_rbac_writer_roles.is_empty() {
             vec![self.config.http_rbac_default_role.clone()]
@@ -10811,7 +10931,10 @@ impl HttpState {
         cx: &Cx,
         req: &Http1Request,
     ) -> Option<Http1Response> {
-        if self.config.http_bearer_token.is_none() && !self.config.http_jwt_enabled {
+        if self.config.http_bearer_token.is_none()
+            && !self.config.http_jwt_enabled
+            && self.project_tokens.registry().projects.is_empty()
+        {
             return None;
         }
 
@@ -10834,6 +10957,21 @@ impl HttpState {
             return None;
         }
 
+        // Project-scoped tokens only reach the MCP JSON-RPC endpoint, where
+        // their scope is enforced per request in
+        // `check_rbac_and_rate_limit_with_cx`. The mail UI and dashboard routes
+        // do not check projects, so they are closed to these tokens.
+        if self.project_token_scope(req).is_some() {
+            if !is_browser_route && self.path_allowed(&path) {
+                return None;
+            }
+            return Some(self.error_response(
+                req,
+                403,
+                "Forbidden: project-scoped tokens can only use the MCP endpoint",
+            ));
+        }
+
         // D3: Accept bearer token from `?token=` query parameter on browser
         // routes. Browser-opened surfaces cannot set Authorization headers, so
         // shareable URLs embed the token as a query parameter instead.
@@ -11256,11 +11394,31 @@ to skip auth for local requests.</p>
             .allow_memory(key, per_minute, burst, now, !has_redis)
     }
 
-    #[cfg(test)]
-    async fn check_rbac_and_rate_limit(
-        &self,
-        req: &Http1Request,
-        json_rpc: &JsonRpcRequest,
+    /// Count one call against a per-tool fixed window. On rejection returns the
+    /// seconds until the window resets.
+    async fn consume_tool_rate_limit(&self, key: &str, limit: ToolRateLimit) -> Result<(), u64> {
+        let now = rate_limit_now();
+        let budget = if self.request_timeout_secs == 0 {
+            Budget::INFINITE
+        } else {
+            let deadline = wall_now() + std::time::Duration::from_secs(self.request_timeout_secs);
+            Budget::new().with_deadline(deadline)
+        };
+        let cx = Cx::for_request_with_budget(budget);
+
+        if let Some(redis) = self.rate_limit_redis_client(&cx).await
+            && let Ok(result) = consume_tool_rate_limit_redis(&cx, &redis, key, limit, now).await
+        {
+            return result;
+        }
+        self.tool_rate_limiter.allow_memory(key, limit, now)
+    }
+
+    #[cfg(test)]
+    async fn check_rbac_and_rate_limit(
+        &self,
+        req: &Http1Request,
+        json_rpc: &JsonRpcRequest,
     ) -> Option<Http1Response> {
         let cx = Cx::for_testing();
         self.check_rbac_and_rate_limit_with_cx(&cx, req, json_rpc)
@@ -11275,7 +11433,21 @@ to skip auth for local requests.</p>
     ) -> Option<Http1Response> {
         let (kind, tool_name) = classify_request(json_rpc);
         let is_local_ok = self.allow_local_unauthenticated(req);
-        let has_static_bearer = self.has_expected_bearer_header(req);
+        let project_scope = self.project_token_scope(req);
+        if let Some(scope) = project_scope.as_ref()
+            && let Some(message) =
+                project_tokens::scope_violation(&json_rpc.method, json_rpc.params.as_ref(), scope)
+        {
+            return Some(self.jsonrpc_error_response(
+                req,
+                json_rpc,
+                403,
+                McpErrorCode::ResourceForbidden,
+                &message,
+            ));
+        }
+        // A project-scoped token carries the same roles as the static bearer.
+        let has_static_bearer = self.has_expected_bearer_header(req) || project_scope.is_some();
         let local_bypass_jwt_sub = if self.config.http_rate_limit_enabled
             && is_local_ok
             && self.config.http_jwt_enabled
@@ -11383,6 +11555,36 @@ to skip auth for local requests.</p>
             }
         }
 
+        // Per-tool call budgets (AGENT_MAIL_TOOL_RATE_LIMITS). These apply even
+        // when the HTTP limiter is off; tools without an entry are unlimited.
+        if json_rpc.method == "tools/call"
+            && let Some(name) = tool_name.as_deref()
+            && let Some(limit) = self.config.tool_rate_limits.get(name).copied()
+        {
+            let (project, agent) = json_rpc
+                .params
+                .as_ref()
+                .and_then(|params| params.get("arguments"))
+                .map(extract_project_agent)
+                .unwrap_or_default();
+            let caller = match agent {
+                Some(agent) => format!("agent:{}:{agent}", project.unwrap_or_default()),
+                None => rate_limit_identity(req, jwt_sub.as_deref()),
+            };
+            let key = format!("{name}:{caller}");
+
+            if let Err(retry_after_secs) = self.consume_tool_rate_limit(&key, limit).await {
+                mcp_agent_mail_tools::record_rate_limited(name);
+                return Some(self.tool_rate_limited_response(
+                    req,
+                    json_rpc,
+                    name,
+                    limit,
+                    retry_after_secs,
+                ));
+            }
+        }
+
         None
     }
 
@@ -11895,6 +12097,44 @@ to skip auth for local requests.</p>
         self.json_response(req, status, &value)
     }
 
+    fn tool_rate_limited_response(
+        &self,
+        req: &Http1Request,
+        json_rpc: &JsonRpcRequest,
+        tool_name: &str,
+        limit: ToolRateLimit,
+        retry_after_secs: u64,
+    ) -> Http1Response {
+        let error = mcp_agent_mail_tools::tool_util::legacy_tool_error(
+            "RATE_LIMITED",
+            format!(
+                "Tool '{tool_name}' is rate limited ({limit}). Retry after {retry_after_secs}s."
+            ),
+            true,
+            serde_json::json!({
+                "tool": tool_name,
+                "limit": limit.max_calls,
+                "window_seconds": limit.window_secs,
+                "retry_after_seconds": retry_after_secs,
+            }),
+        );
+        let body = JsonRpcResponse::error(json_rpc.id.clone(), JsonRpcError::from(error));
+        let value = serde_json::to_value(&body).unwrap_or_else(|_| {
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": {
+                    "code": i32::from(McpErrorCode::InternalError),
+                    "message": "failed to encode JSON-RPC error response",
+                }
+            })
+        });
+        let mut resp = self.json_response(req, 429, &value);
+        resp.headers
+            .push(("retry-after".to_string(), retry_after_secs.to_string()));
+        resp
+    }
+
     fn json_response(
         &self,
         req: &Http1Request,
@@ -14860,7 +15100,9 @@ fn readiness_check_request_path(config: &mcp_agent_mail_core::Config) -> Result<
         return Err(error);
     }
 
-    if is_memory {
+    // Safe mode never migrates, so an older schema is expected rather than a
+    // reason to report the emergency server unready.
+    if is_memory || config.safe_mode {
         return Ok(());
     }
 
@@ -15021,6 +15263,76 @@ fn log_active_database(config: &mcp_agent_mail_core::Config) {
     }
 }
 
+/// Live health level and signals, named as in [`mcp_agent_mail_core::HealthInfo`].
+fn current_health_info() -> mcp_agent_mail_core::HealthInfo {
+    let (level, signals) = mcp_agent_mail_core::compute_health_level_with_signals();
+    mcp_agent_mail_core::HealthInfo {
+        level: level.as_str().to_string(),
+        signals,
+    }
+}
+
+/// Build the `/readyz` verdict from its three inputs: the request-path DB
+/// check (opened and migrated), the mailbox durability state (`None` for
+/// `:memory:` DBs), and the live health level. Returns whether the server is
+/// ready alongside the JSON body; `failing` names every check that is not ok.
+///
+/// Safe mode serves reads only, so a `degraded_read_only` mailbox does not
+/// make the emergency server unready.
+fn readyz_report(
+    database: Result<(), String>,
+    durability: Option<mcp_agent_mail_db::DurabilityState>,
+    health: &mcp_agent_mail_core::HealthInfo,
+    safe_mode: bool,
+) -> (bool, serde_json::Value) {
+    use mcp_agent_mail_db::DurabilityState;
+
+    let database_ok = database.is_ok();
+    let recovery_ok = match durability {
+        None | Some(DurabilityState::Healthy) => true,
+        Some(DurabilityState::DegradedReadOnly) => safe_mode,
+        Some(DurabilityState::Recovering | DurabilityState::Corrupt) => false,
+    };
+    let level_ok = health.level != mcp_agent_mail_core::HealthLevel::Red.as_str();
+    let ready = database_ok && recovery_ok && level_ok;
+
+    let failing: Vec<&str> = [
+        ("database", database_ok),
+        ("recovery", recovery_ok),
+        ("health_level", level_ok),
+    ]
+    .into_iter()
+    .filter_map(|(name, ok)| (!ok).then_some(name))
+    .collect();
+
+    let mut body = serde_json::json!({
+        "status": if ready { "ready" } else { "not_ready" },
+        "ready": ready,
+        "failing": failing,
+        "checks": {
+            "database": {
+                "ok": database_ok,
+                "error": database.err(),
+            },
+            "recovery": {
+                "ok": recovery_ok,
+                "mode": durability.map_or_else(|| "not_probed".to_string(), |state| state.to_string()),
+            },
+            "health_level": {
+                "ok": level_ok,
+                "level": health.level,
+            },
+        },
+        "level": health.level,
+        "signals": serde_json::to_value(&health.signals).unwrap_or(serde_json::Value::Null),
+    });
+    if safe_mode {
+        body["safe_mode"] = serde_json::Value::Bool(true);
+        body["mode"] = serde_json::json!("read_only");
+    }
+    (ready, body)
+}
+
 /// Fast-path mailbox-verdict probe used by `/health/durability` (#94). Returns
 /// `None` for :memory: DBs — those have no on-disk state the verdict engine can
 /// meaningfully inspect, so the readiness check is the only health signal. Any
@@ -15525,6 +15837,97 @@ impl RateLimiter {
     }
 }
 
+const TOOL_RATE_LIMIT_REDIS_LUA: &str = r"local count = redis.call('INCR', KEYS[1])
+if count == 1 then
+  redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return count
+";
+
+/// Fixed window containing `now`: `(window index, window end in seconds)`.
+fn tool_rate_window(limit: ToolRateLimit, now: f64) -> (u64, f64) {
+    let window = f64::from(limit.window_secs.max(1));
+    let index = (now.max(0.0) / window).floor();
+    #[allow(clippy::cast_sign_loss)]
+    let index_u64 = index as u64;
+    (index_u64, (index + 1.0) * window)
+}
+
+fn retry_after_secs(remaining: f64) -> u64 {
+    #[allow(clippy::cast_sign_loss)]
+    let secs = remaining.ceil().max(1.0) as u64;
+    secs
+}
+
+async fn consume_tool_rate_limit_redis(
+    cx: &Cx,
+    redis: &RedisClient,
+    key: &str,
+    limit: ToolRateLimit,
+    now: f64,
+) -> Result<Result<(), u64>, ()> {
+    let (window, window_end) = tool_rate_window(limit, now);
+    let redis_key = format!("trl:{key}:{window}");
+    let ttl_s = limit.window_secs.max(1).to_string();
+
+    let resp = redis
+        .cmd_bytes(
+            cx,
+            &[
+                b"EVAL",
+                TOOL_RATE_LIMIT_REDIS_LUA.as_bytes(),
+                b"1",
+                redis_key.as_bytes(),
+                ttl_s.as_bytes(),
+            ],
+        )
+        .await
+        .map_err(|_| ())?;
+    let count = resp.as_integer().ok_or(())?;
+    if count <= i64::from(limit.max_calls) {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(retry_after_secs(window_end - now)))
+    }
+}
+
+/// In-memory fixed-window counters for per-tool rate limits, keyed by
+/// `tool:caller`. Each entry holds `(window end, calls in window)`.
+struct ToolRateLimiter {
+    windows: Mutex<HashMap<String, (f64, u32)>>,
+}
+
+impl ToolRateLimiter {
+    /// Expired windows are pruned once the map grows past this many callers.
+    const PRUNE_THRESHOLD: usize = 4096;
+
+    fn new() -> Self {
+        Self {
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn allow_memory(&self, key: &str, limit: ToolRateLimit, now: f64) -> Result<(), u64> {
+        let (_, window_end) = tool_rate_window(limit, now);
+        let mut windows = self
+            .windows
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if windows.len() >= Self::PRUNE_THRESHOLD {
+            windows.retain(|_, (end, _)| *end > now);
+        }
+        let entry = windows.entry(key.to_string()).or_insert((window_end, 0));
+        if now >= entry.0 {
+            *entry = (window_end, 0);
+        }
+        if entry.1 >= limit.max_calls {
+            return Err(retry_after_secs(entry.0 - now));
+        }
+        entry.1 += 1;
+        Ok(())
+    }
+}
+
 fn rate_limits_for(config: &mcp_agent_mail_core::Config, kind: RequestKind) -> (u32, u32) {
     let (rpm, burst) = match kind {
         RequestKind::Tools => (
@@ -15951,6 +16354,7 @@ fn is_websocket_upgrade_request(req: &Http1Request) -> bool {
 
 fn should_suppress_tui_http_event(path: &str) -> bool {
     path.ends_with("/healthz")
+        || path == "/readyz"
         || path == "/mail/ws-state"
         || path == "/mail/ws-input"
         || path == "/web-dashboard/state"
@@ -15997,8 +16401,12 @@ fn rate_limit_identity(req: &Http1Request, jwt_sub: Option<&str>) -> String {
     if let Some(sub) = jwt_sub.filter(|s| !s.is_empty()) {
         return format!("sub:{sub}");
     }
-    req.peer_addr
-        .map_or_else(|| "ip-unknown".to_string(), peer_addr_host)
+    if let Some(peer_addr) = req.peer_addr {
+        return peer_addr_host(peer_addr);
+    }
+    // Unix socket clients have no address; the listener names them instead.
+    header_value(req, uds::UDS_PEER_HEADER)
+        .map_or_else(|| "ip-unknown".to_string(), |peer| format!("uds:{peer}"))
 }
 
 fn is_local_peer_addr(peer_addr: Option<SocketAddr>) -> bool {
@@ -18433,6 +18841,7 @@ first body
             ),
             listener_stats: Arc::new(Http1ListenerStats::default()),
             request_diagnostics: Arc::new(HttpRequestRuntimeDiagnostics::default()),
+            uds: None,
             shutdown,
         }
     }
@@ -19133,6 +19542,57 @@ first body
         );
     }
 
+    #[test]
+    fn mail_api_backpressure_shedding_toggles_gate_at_runtime() {
+        let state = build_state(mcp_agent_mail_core::Config::default());
+        let original = mcp_agent_mail_core::shedding_enabled();
+
+        let mut req = make_request(
+            Http1Method::Post,
+            "/mail/api/backpressure/shedding",
+            &[("content-type", "application/json")],
+        );
+        req.body = br#"{"enabled": true}"#.to_vec();
+        let resp = block_on(state.handle(req));
+        assert_eq!(resp.status, 200);
+        let payload: serde_json::Value =
+            serde_json::from_slice(&resp.body).expect("shedding response json");
+        assert_eq!(payload["enabled"], true);
+        assert!(payload["recent_transitions"].is_array(), "{payload}");
+        assert!(payload["health_level"].is_string(), "{payload}");
+
+        let mut bad = make_request(
+            Http1Method::Post,
+            "/mail/api/backpressure/shedding",
+            &[("content-type", "application/json")],
+        );
+        bad.body = br#"{"enabled": "yes"}"#.to_vec();
+        assert_eq!(block_on(state.handle(bad)).status, 400);
+
+        let mut forged = make_request(
+            Http1Method::Post,
+            "/mail/api/backpressure/shedding",
+            &[("content-type", "text/plain")],
+        );
+        forged.body = br#"{"enabled": false}"#.to_vec();
+        assert_eq!(block_on(state.handle(forged)).status, 403);
+
+        let resp = block_on(state.handle(make_request(
+            Http1Method::Get,
+            "/mail/api/backpressure/shedding",
+            &[],
+        )));
+        assert_eq!(resp.status, 200);
+        let payload: serde_json::Value =
+            serde_json::from_slice(&resp.body).expect("shedding status json");
+        assert_eq!(
+            payload["enabled"], true,
+            "rejected POSTs must not flip the gate"
+        );
+
+        mcp_agent_mail_core::set_shedding_enabled(original);
+    }
+
     #[test]
     fn mail_api_locks_trailing_slash_returns_json() {
         let storage_root = std::env::temp_dir().join(format!(
@@ -21830,6 +22290,113 @@ first body
         assert_eq!(resp.status, 429);
     }
 
+    #[test]
+    fn tool_rate_limiter_fixed_window_rolls_over() {
+        let limiter = ToolRateLimiter::new();
+        let limit = ToolRateLimit {
+            max_calls: 2,
+            window_secs: 60,
+        };
+
+        assert_eq!(limiter.allow_memory("send_message:a", limit, 600.0), Ok(()));
+        assert_eq!(limiter.allow_memory("send_message:a", limit, 610.0), Ok(()));
+        assert_eq!(
+            limiter.allow_memory("send_message:a", limit, 630.5),
+            Err(30),
+            "third call in the window is rejected until the boundary"
+        );
+        // Other callers have their own window.
+        assert_eq!(limiter.allow_memory("send_message:b", limit, 630.5), Ok(()));
+
+        // The counter resets at the window boundary, not 60s after the first call.
+        assert_eq!(limiter.allow_memory("send_message:a", limit, 659.9), Err(1));
+        assert_eq!(limiter.allow_memory("send_message:a", limit, 660.0), Ok(()));
+        assert_eq!(limiter.allow_memory("send_message:a", limit, 661.0), Ok(()));
+        assert_eq!(
+            limiter.allow_memory("send_message:a", limit, 662.0),
+            Err(58)
+        );
+    }
+
+    #[test]
+    fn tool_rate_limit_rejects_with_retry_after_when_http_limiter_disabled() {
+        let config = mcp_agent_mail_core::Config {
+            http_rate_limit_enabled: false,
+            tool_rate_limits: mcp_agent_mail_core::parse_tool_rate_limits("health_check=2/60").0,
+            ..Default::default()
+        };
+        let state = build_state(config);
+        let peer = SocketAddr::from(([10, 0, 0, 1], 1234));
+        let req = make_request_with_peer_addr(Http1Method::Post, "/api/", &[], Some(peer));
+
+        let params = serde_json::json!({ "name": "health_check", "arguments": {} });
+        let json_rpc = JsonRpcRequest::new("tools/call", Some(params), 1);
+        assert!(block_on(state.check_rbac_and_rate_limit(&req, &json_rpc)).is_none());
+        assert!(block_on(state.check_rbac_and_rate_limit(&req, &json_rpc)).is_none());
+
+        let resp = block_on(state.check_rbac_and_rate_limit(&req, &json_rpc))
+            .expect("third call should be rate limited");
+        assert_eq!(resp.status, 429);
+        let retry_after = resp
+            .headers
+            .iter()
+            .find(|(name, _)| name == "retry-after")
+            .map(|(_, value)| value.parse::<u64>().expect("numeric retry-after"))
+            .expect("retry-after header");
+        assert!((1..=60).contains(&retry_after));
+        let body: serde_json::Value = serde_json::from_slice(&resp.body).expect("json body");
+        let error = &body["error"]["data"]["error"];
+        assert_eq!(error["type"], "RATE_LIMITED");
+        assert_eq!(error["recoverable"], true);
+        assert_eq!(error["data"]["tool"], "health_check");
+        assert_eq!(error["data"]["retry_after_seconds"], retry_after);
+
+        // Tools without an entry stay unlimited.
+        let params = serde_json::json!({ "name": "whois", "arguments": {} });
+        let other = JsonRpcRequest::new("tools/call", Some(params), 2);
+        for _ in 0..5 {
+            assert!(block_on(state.check_rbac_and_rate_limit(&req, &other)).is_none());
+        }
+    }
+
+    #[test]
+    fn tool_rate_limit_buckets_by_calling_agent() {
+        let config = mcp_agent_mail_core::Config {
+            tool_rate_limits: mcp_agent_mail_core::parse_tool_rate_limits("health_check=1/60").0,
+            ..Default::default()
+        };
+        let state = build_state(config);
+        let peer = SocketAddr::from(([10, 0, 0, 1], 1234));
+        let req = make_request_with_peer_addr(Http1Method::Post, "/api/", &[], Some(peer));
+        let call = |agent: &str| {
+            let params = serde_json::json!({
+                "name": "health_check",
+                "arguments": { "project_key": "/p", "agent_name": agent },
+            });
+            JsonRpcRequest::new("tools/call", Some(params), 1)
+        };
+
+        assert!(block_on(state.check_rbac_and_rate_limit(&req, &call("BlueLake"))).is_none());
+        assert!(block_on(state.check_rbac_and_rate_limit(&req, &call("RedFox"))).is_none());
+        assert!(block_on(state.check_rbac_and_rate_limit(&req, &call("BlueLake"))).is_some());
+    }
+
+    #[test]
+    fn unknown_rate_limited_tools_lists_only_unregistered_names() {
+        let config = mcp_agent_mail_core::Config {
+            tool_rate_limits: mcp_agent_mail_core::parse_tool_rate_limits(
+                "send_message=30/60,send_mesage=5/60",
+            )
+            .0,
+            ..Default::default()
+        };
+        let registry = tool_registry::ToolRegistry::with_builtins();
+        assert_eq!(
+            unknown_rate_limited_tools(&config, &registry),
+            vec!["send_mesage"]
+        );
+    }
+
     #[test]
     fn rate_limits_for_defaults_burst_to_rpm_max_1() {
         let config = mcp_agent_mail_core::Config {
@@ -21953,6 +22520,19 @@ first body
         // missing peer addr
         let req_none = make_request(Http1Method::Post, "/api/", &[]);
         assert_eq!(rate_limit_identity(&req_none, None), "ip-unknown");
+
+        // unix socket clients are keyed by the listener's uid:pid header,
+        // which is ignored on requests that have a TCP peer.
+        let peer = [(uds::UDS_PEER_HEADER, "1000:4242")];
+        let req_uds = make_request(Http1Method::Post, "/api/", &peer);
+        assert_eq!(rate_limit_identity(&req_uds, None), "uds:1000:4242");
+        let req_tcp = make_request_with_peer_addr(
+            Http1Method::Post,
+            "/api/",
+            &peer,
+            Some(SocketAddr::from(([192, 168, 0, 1], 1234))),
+        );
+        assert_eq!(rate_limit_identity(&req_tcp, None), "192.168.0.1");
     }
 
     #[test]
@@ -23705,6 +24285,140 @@ first body
         );
     }
 
+    #[test]
+    fn project_scoped_token_cannot_send_into_other_project() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let registry_path = dir
+            .path()
+            .join(mcp_agent_mail_core::setup::PROJECT_TOKENS_FILE);
+        let mut registry = mcp_agent_mail_core::setup::ProjectTokenRegistry::default();
+        registry.upsert("project-a", "/work/project-a", "token-a");
+        registry.upsert("project-b", "/work/project-b", "token-b");
+        registry.save(&registry_path).expect("save registry");
+
+        let config = mcp_agent_mail_core::Config {
+            http_bearer_token: Some("global-token".to_string()),
+            http_project_tokens_path: Some(registry_path),
+            http_rbac_enabled: true,
+            http_allow_localhost_unauthenticated: false,
+            ..Default::default()
+        };
+        let state = build_state(config);
+        let peer = SocketAddr::from(([10, 0, 0, 1], 1234));
+        let send_into = |project: &str| {
+            JsonRpcRequest::new(
+                "tools/call",
+                Some(serde_json::json!({
+                    "name": "send_message",
+                    "arguments": {
+                        "project_key": project,
+                        "sender_name": "BlueLake",
+                        "to": ["RedFox"],
+                        "subject": "hi",
+                        "body_md": "hello",
+                    }
+                })),
+                1,
+            )
+        };
+        let request_with = |token: &str| {
+            let auth = format!("Bearer {token}");
+            make_request_with_peer_addr(
+                Http1Method::Post,
+                "/api/",
+                &[("Authorization", auth.as_str())],
+                Some(peer),
+            )
+        };
+
+        let req_a = request_with("token-a");
+        assert!(block_on(state.check_bearer_auth(&req_a)).is_none());
+        assert!(
+            block_on(state.check_rbac_and_rate_limit(&req_a, &send_into("/work/project-a")))
+                .is_none(),
+            "project A token should reach project A"
+        );
+        let resp = block_on(state.check_rbac_and_rate_limit(&req_a, &send_into("project-b")))
+            .expect("project A token must not reach project B");
+        assert_eq!(resp.status, 403);
+        let body: serde_json::Value = serde_json::from_slice(&resp.body).expect("json body");
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .is_some_and(|m| m.contains("scoped to project 'project-a'")),
+            "{body}"
+        );
+
+        let req_b = request_with("token-b");
+        assert!(
+            block_on(state.check_rbac_and_rate_limit(&req_b, &send_into("project-b"))).is_none()
+        );
+
+        let req_global = request_with("global-token");
+        assert!(
+            block_on(state.check_rbac_and_rate_limit(&req_global, &send_into("project-b")))
+                .is_none(),
+            "the global token is not scoped"
+        );
+
+        let req_unknown = request_with("token-c");
+        assert_eq!(
+            block_on(state.check_bearer_auth(&req_unknown)).map(|r| r.status),
+            Some(401)
+        );
+    }
+
+    #[test]
+    fn project_scoped_token_cannot_use_mail_routes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let registry_path = dir
+            .path()
+            .join(mcp_agent_mail_core::setup::PROJECT_TOKENS_FILE);
+        let mut registry = mcp_agent_mail_core::setup::ProjectTokenRegistry::default();
+        registry.upsert("project-a", "/work/project-a", "token-a");
+        registry.save(&registry_path).expect("save registry");
+
+        let config = mcp_agent_mail_core::Config {
+            http_bearer_token: Some("global-token".to_string()),
+            http_project_tokens_path: Some(registry_path),
+            http_allow_localhost_unauthenticated: false,
+            ..Default::default()
+        };
+        let state = build_state(config);
+        let peer = SocketAddr::from(([10, 0, 0, 1], 1234));
+        let mut req = make_request_with_peer_addr(
+            Http1Method::Post,
+            "/mail/project-b/overseer/send",
+            &[
+                ("Authorization", "Bearer token-a"),
+                ("Content-Type", "application/json"),
+            ],
+            Some(peer),
+        );
+        req.body = serde_json::to_vec(&serde_json::json!({
+            "recipients": ["BlueLake"],
+            "subject": "hi",
+            "body_md": "hello",
+        }))
+        .expect("body");
+        let resp = block_on(state.handle(req));
+        assert_eq!(resp.status, 403, "project A token must not post into B");
+
+        for path in ["/mail/project-a", "/web-dashboard/state"] {
+            let req = make_request_with_peer_addr(
+                Http1Method::Get,
+                path,
+                &[("Authorization", "Bearer token-a")],
+                Some(peer),
+            );
+            assert_eq!(
+                block_on(state.check_bearer_auth(&req)).map(|r| r.status),
+                Some(403),
+                "{path}"
+            );
+        }
+    }
+
     #[test]
     fn jwt_roles_enforced_for_tools() {
         let config = mcp_agent_mail_core::Config {
@@ -25454,7 +26168,110 @@ first body
         let resp = block_on(state.handle(req));
         assert_eq!(resp.status, 200);
         let body: serde_json::Value = serde_json::from_slice(&resp.body).unwrap();
-        assert_eq!(body, serde_json::json!({"status": "alive"}));
+        assert_eq!(body["status"], "alive");
+        assert!(
+            matches!(body["level"].as_str(), Some("green" | "yellow" | "red")),
+            "healthz must report the health level: {body}"
+        );
+        assert!(body["signals"]["pool_utilization_pct"].is_u64());
+    }
+
+    #[test]
+    fn readyz_returns_ready_json_with_health_fields() {
+        with_serialized_health_route(|| {
+            let config = isolated_health_config();
+            let state = build_state(config);
+            let req = make_request(Http1Method::Get, "/readyz", &[]);
+            let resp = block_on(state.handle(req));
+            let body: serde_json::Value = serde_json::from_slice(&resp.body).unwrap();
+            if body["checks"]["health_level"]["ok"] == false {
+                // Another test drove the global metrics red; the verdict must
+                // still be a 503 naming that check.
+                assert_eq!(resp.status, 503);
+                assert_eq!(body["failing"], serde_json::json!(["health_level"]));
+                return;
+            }
+            assert_eq!(resp.status, 200, "{body}");
+            assert_eq!(body["status"], "ready");
+            assert_eq!(body["ready"], true);
+            assert_eq!(body["failing"], serde_json::json!([]));
+            assert_eq!(body["checks"]["database"]["ok"], true);
+            assert_eq!(body["checks"]["recovery"]["mode"], "not_probed");
+            assert_eq!(body["level"], body["checks"]["health_level"]["level"]);
+            assert!(body["signals"].is_object());
+            assert_eq!(
+                response_header(&resp, startup_checks::HEALTH_SIGNATURE_HEADER_NAME),
+                Some(startup_checks::HEALTH_SIGNATURE_HEADER_VALUE)
+            );
+        });
+    }
+
+    #[test]
+    fn readyz_returns_503_while_warming_up() {
+        with_serialized_health_route(|| {
+            let state = build_state(isolated_health_config());
+            arm_startup_readiness_fast_path();
+            let req = make_request(Http1Method::Get, "/readyz", &[]);
+            let resp = block_on(state.handle(req));
+            assert_eq!(resp.status, 503);
+            let body: serde_json::Value = serde_json::from_slice(&resp.body).unwrap();
+            assert_eq!(body["status"], "not_ready");
+            assert_eq!(body["checks"]["database"]["error"], "server is warming up");
+        });
+    }
+
+    #[test]
+    fn readyz_rejects_post_with_405() {
+        let state = build_state(mcp_agent_mail_core::Config::default());
+        let req = make_request(Http1Method::Post, "/readyz", &[]);
+        let resp = block_on(state.handle(req));
+        assert_eq!(resp.status, 405);
+    }
+
+    #[test]
+    fn readyz_report_names_each_failing_check() {
+        use mcp_agent_mail_db::DurabilityState;
+
+        let mut health = current_health_info();
+        health.level = "green".to_string();
+        let (ready, _) = readyz_report(Ok(()), Some(DurabilityState::Healthy), &health, false);
+        assert!(ready);
+
+        let (ready, body) = readyz_report(
+            Ok(()),
+            Some(DurabilityState::DegradedReadOnly),
+            &health,
+            false,
+        );
+        assert!(!ready);
+        assert_eq!(body["failing"], serde_json::json!(["recovery"]));
+        assert_eq!(body["checks"]["recovery"]["mode"], "degraded_read_only");
+
+        // The read-only emergency server stays ready on a degraded mailbox.
+        let (ready, body) = readyz_report(
+            Ok(()),
+            Some(DurabilityState::DegradedReadOnly),
+            &health,
+            true,
+        );
+        assert!(ready);
+        assert_eq!(body["mode"], "read_only");
+
+        health.level = "red".to_string();
+        let (ready, body) = readyz_report(
+            Err("schema is behind".to_string()),
+            Some(DurabilityState::Recovering),
+            &health,
+            false,
+        );
+        assert!(!ready);
+        assert_eq!(body["status"], "not_ready");
+        assert_eq!(
+            body["failing"],
+            serde_json::json!(["database", "recovery", "health_level"])
+        );
+        assert_eq!(body["checks"]["database"]["error"], "schema is behind");
+        assert_eq!(body["level"], "red");
     }
 
     /// Issue #145: the blocking liveness probe used by the CLI's
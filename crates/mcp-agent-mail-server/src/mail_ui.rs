@@ -1283,7 +1283,16 @@ first body
 }
 
 fn get_pool() -> Result<DbPool, (u16, String)> {
-    let cfg = DbPoolConfig::from_env();
+    let mut cfg = DbPoolConfig::from_env();
+    if mcp_agent_mail_core::safe_mode::is_active()
+        && !mcp_agent_mail_core::disk::is_sqlite_memory_database_url(&cfg.database_url)
+    {
+        // Safe mode: never migrate or recover the mailbox behind a page view.
+        cfg.run_migrations = false;
+        cfg.warmup_connections = 0;
+        return mcp_agent_mail_db::create_query_only_pool(&cfg)
+            .map_err(|e| (500, format!("Database error: {e}")));
+    }
     get_or_create_pool(&cfg).map_err(|e| (500, format!("Database error: {e}")))
 }
 
@@ -4495,6 +4504,13 @@ fn handle_api_route(
             return render_api_project_agents(cx, read_pool, project_slug);
         }
     }
+    // /api/maintenance/compact → POST (WAL checkpoint + optional vacuum for `am doctor compact`)
+    if sub == "/api/maintenance/compact" {
+        if method != "POST" {
+            return Err((405, "Method Not Allowed".to_string()));
+        }
+        return handle_maintenance_compact(live_pool, body);
+    }
     // Other API routes handled elsewhere (e.g., /mail/api/locks is in handle_special_routes).
     Ok(None)
 }
@@ -5153,10 +5169,9 @@ fn handle_mark_read(
     let mut marked_count = 0i64;
     let mut already_read_count = 0i64;
     for mid in &unique_message_ids {
-        let request_started = now_micros();
         match block_on_outcome(cx, queries::mark_message_read(cx, pool, aid, *mid)) {
-            Ok(read_ts) => {
-                if read_ts >= request_started {
+            Ok(read) => {
+                if read.changed {
                     marked_count += 1;
                 } else {
                     already_read_count += 1;
@@ -5454,6 +5469,55 @@ fn handle_overseer_send(
     }))
 }
 
+// ---------------------------------------------------------------------------
+// POST: /mail/api/maintenance/compact
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CompactRequest {
+    checkpoint: mcp_agent_mail_db::pool::CompactCheckpoint,
+    vacuum: bool,
+    dry_run: bool,
+}
+
+/// Compact the live database from inside the process that owns it, so the
+/// CLI never has to open a competing writer while the server is running.
+fn handle_maintenance_compact(
+    live_pool: &DbPool,
+    body: &str,
+) -> Result<Option<String>, (u16, String)> {
+    let request: CompactRequest = if body.trim().is_empty() {
+        CompactRequest::default()
+    } else {
+        match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return json_err(400, &format!("Invalid JSON: {e}")),
+        }
+    };
+    let options = mcp_agent_mail_db::pool::CompactOptions {
+        checkpoint: request.checkpoint,
+        vacuum: request.vacuum,
+        dry_run: request.dry_run,
+    };
+    match mcp_agent_mail_db::pool::compact_sqlite_path(Path::new(live_pool.sqlite_path()), options)
+    {
+        Ok(report) => {
+            tracing::info!(
+                vacuum = ?report.vacuum,
+                checkpoint = report.checkpoint.as_str(),
+                dry_run = report.dry_run,
+                bytes_reclaimed = report.bytes_reclaimed(),
+                "database compacted via maintenance endpoint"
+            );
+            let json =
+                serde_json::to_string(&report).map_err(|e| (500, format!("JSON error: {e}")))?;
+            Ok(Some(json))
+        }
+        Err(e) => json_err(500, &format!("compact failed: {e}")),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // POST: /mail/api/projects/{id}/siblings/{other_id}
 // ---------------------------------------------------------------------------
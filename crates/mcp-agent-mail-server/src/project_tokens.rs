@@ -0,0 +1,369 @@
+//! Project-scoped bearer tokens written by `am setup run --project-token`.
+//!
+//! The registry maps a token digest to exactly one project. A request that
+//! presents such a token may only use the MCP JSON-RPC endpoint, and only the
+//! methods, tools and resources allowlisted below; each project-bearing call
+//! must name the token's project. Everything else is refused with a 403. The
+//! global `HTTP_BEARER_TOKEN` is not in the registry and keeps access to every
+//! project.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use mcp_agent_mail_core::setup::{ProjectTokenEntry, ProjectTokenRegistry};
+
+/// JSON-RPC methods that carry no project data: the handshake and listings.
+const PROJECT_NEUTRAL_METHODS: &[&str] = &[
+    "initialize",
+    "initialized",
+    "notifications/initialized",
+    "notifications/cancelled",
+    "ping",
+    "tools/list",
+    "resources/list",
+    "resources/templates/list",
+];
+
+/// Tools a scoped token may call, with the argument that names the project.
+/// That argument must be present and name the token's project.
+const PROJECT_TOOLS: &[(&str, &str)] = &[
+    ("ensure_project", "human_key"),
+    ("register_agent", "project_key"),
+    ("create_agent_identity", "project_key"),
+    ("whois", "project_key"),
+    ("resolve_pane_identity", "project_key"),
+    ("cleanup_pane_identities", "project_key"),
+    ("list_agents", "project_key"),
+    ("send_message", "project_key"),
+    ("reply_message", "project_key"),
+    ("fetch_inbox", "project_key"),
+    ("mark_message_read", "project_key"),
+    ("acknowledge_message", "project_key"),
+    ("request_contact", "project_key"),
+    ("respond_contact", "project_key"),
+    ("list_contacts", "project_key"),
+    ("set_contact_policy", "project_key"),
+    ("check_file_reservation_conflicts", "project_key"),
+    ("file_reservation_paths", "project_key"),
+    ("release_file_reservations", "project_key"),
+    ("renew_file_reservations", "project_key"),
+    ("force_release_file_reservation", "project_key"),
+    ("search_messages", "project_key"),
+    ("summarize_thread", "project_key"),
+    ("macro_start_session", "human_key"),
+    ("macro_prepare_thread", "project_key"),
+    ("macro_file_reservation_cycle", "project_key"),
+    ("macro_contact_handshake", "project_key"),
+    ("acquire_build_slot", "project_key"),
+    ("renew_build_slot", "project_key"),
+    ("release_build_slot", "project_key"),
+];
+
+/// Tools that read no project data.
+const PROJECT_NEUTRAL_TOOLS: &[&str] = &["health_check"];
+
+/// Optional arguments naming a second project; when given they must also name
+/// the token's project.
+const SECONDARY_PROJECT_ARGUMENT_KEYS: &[&str] = &["to_project", "from_project"];
+
+/// Resource kinds whose first path segment is a project key or slug.
+const PROJECT_PATH_RESOURCES: &[&str] = &["identity", "agents", "project", "file_reservations"];
+
+/// Resource kinds keyed by agent or id that must carry `?project=`.
+const PROJECT_QUERY_RESOURCES: &[&str] = &[
+    "message",
+    "thread",
+    "inbox",
+    "mailbox",
+    "mailbox-with-commits",
+    "outbox",
+    "views",
+];
+
+/// Server metadata resources that carry no project data.
+const PROJECT_NEUTRAL_RESOURCES: &[&str] = &[
+    "tooling/directory",
+    "tooling/schemas",
+    "tooling/capabilities",
+];
+
+/// The registry, reloaded whenever the file's modification time changes so a
+/// new `--project-token` setup takes effect without restarting the server.
+pub struct ProjectTokenCache {
+    path: Option<PathBuf>,
+    state: Mutex<(Option<SystemTime>, Arc<ProjectTokenRegistry>)>,
+}
+
+impl ProjectTokenCache {
+    #[must_use]
+    pub fn new(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            state: Mutex::new((None, Arc::default())),
+        }
+    }
+
+    #[must_use]
+    pub fn registry(&self) -> Arc<ProjectTokenRegistry> {
+        let Some(path) = self.path.as_deref() else {
+            return Arc::default();
+        };
+        let modified = std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if state.0 != modified {
+            let registry = if modified.is_some() {
+                ProjectTokenRegistry::load(path).unwrap_or_else(|error| {
+                    tracing::warn!(
+                        path = %path.display(),
+                        %error,
+                        "ignoring unreadable project token registry"
+                    );
+                    ProjectTokenRegistry::default()
+                })
+            } else {
+                ProjectTokenRegistry::default()
+            };
+            *state = (modified, Arc::new(registry));
+        }
+        Arc::clone(&state.1)
+    }
+}
+
+/// Whether a project argument refers to the scoped project, using the same
+/// rules as tool-side resolution: absolute paths go through project identity,
+/// anything else is a slug.
+fn project_value_matches(value: &str, scope: &ProjectTokenEntry) -> bool {
+    let value = value.trim();
+    if value.is_empty() {
+        return false;
+    }
+    if Path::new(value).is_absolute() {
+        value.trim_end_matches('/') == scope.human_key.trim_end_matches('/')
+            || mcp_agent_mail_core::resolve_project_identity(value).slug == scope.slug
+    } else {
+        value == scope.slug
+    }
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn tool_call_violation(
+    params: Option<&serde_json::Value>,
+    scope: &ProjectTokenEntry,
+) -> Option<String> {
+    let name = params
+        .and_then(|p| p.get("name"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    if PROJECT_NEUTRAL_TOOLS.contains(&name) {
+        return None;
+    }
+    let Some((_, project_key)) = PROJECT_TOOLS.iter().find(|(tool, _)| *tool == name) else {
+        return Some(format!("tool '{name}'"));
+    };
+    let arguments = params.and_then(|p| p.get("arguments"));
+    let argument = |key: &str| {
+        arguments
+            .and_then(|a| a.get(key))
+            .and_then(serde_json::Value::as_str)
+    };
+    match argument(project_key) {
+        Some(value) if project_value_matches(value, scope) => {}
+        Some(value) => return Some(format!("{project_key} '{value}'")),
+        None => return Some(format!("tool '{name}' without {project_key}")),
+    }
+    SECONDARY_PROJECT_ARGUMENT_KEYS.iter().find_map(|key| {
+        let value = argument(key)?;
+        (!project_value_matches(value, scope)).then(|| format!("{key} '{value}'"))
+    })
+}
+
+fn resource_read_violation(
+    params: Option<&serde_json::Value>,
+    scope: &ProjectTokenEntry,
+) -> Option<String> {
+    let uri = params
+        .and_then(|p| p.get("uri"))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    let rest = uri.strip_prefix("resource://").unwrap_or(uri);
+    let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let mut segments = path.split('/');
+    let kind = segments.next().unwrap_or_default();
+    let denied = || Some(format!("resource '{uri}'"));
+    if PROJECT_NEUTRAL_RESOURCES
+        .iter()
+        .any(|neutral| path == *neutral || path.starts_with(&format!("{neutral}/")))
+    {
+        return None;
+    }
+    let in_scope = if PROJECT_PATH_RESOURCES.contains(&kind) {
+        segments
+            .next()
+            .is_some_and(|project| project_value_matches(&percent_decode(project), scope))
+    } else {
+        PROJECT_QUERY_RESOURCES.contains(&kind)
+    };
+    if !in_scope {
+        return denied();
+    }
+    // Every `project=` pair must match, and query-keyed kinds need one.
+    let mut projects = query
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("project="))
+        .map(percent_decode)
+        .peekable();
+    if projects.peek().is_none() {
+        return (!PROJECT_PATH_RESOURCES.contains(&kind))
+            .then(|| format!("resource '{uri}' without project"));
+    }
+    if projects.all(|project| project_value_matches(&project, scope)) {
+        None
+    } else {
+        denied()
+    }
+}
+
+/// Why `method`/`params` reaches outside `scope`, or `None` when the request
+/// stays within it. Anything not allowlisted is refused.
+#[must_use]
+pub fn scope_violation(
+    method: &str,
+    params: Option<&serde_json::Value>,
+    scope: &ProjectTokenEntry,
+) -> Option<String> {
+    let target = match method {
+        "tools/call" => tool_call_violation(params, scope),
+        "resources/read" => resource_read_violation(params, scope),
+        _ if PROJECT_NEUTRAL_METHODS.contains(&method) => None,
+        _ => Some(format!("method '{method}'")),
+    }?;
+    Some(format!(
+        "Forbidden: token is scoped to project '{}' and cannot access {target}",
+        scope.slug
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scope() -> ProjectTokenEntry {
+        ProjectTokenEntry {
+            slug: "alpha".to_string(),
+            human_key: "/work/alpha".to_string(),
+            sha256: mcp_agent_mail_core::setup::project_token_digest("tok-alpha"),
+        }
+    }
+
+    #[test]
+    fn tool_calls_naming_other_projects_are_refused() {
+        let scope = scope();
+        let call = |arguments: serde_json::Value| {
+            scope_violation(
+                "tools/call",
+                Some(&json!({"name": "send_message", "arguments": arguments})),
+                &scope,
+            )
+        };
+        assert_eq!(call(json!({"project_key": "/work/alpha/"})), None);
+        assert_eq!(call(json!({"project_key": "alpha"})), None);
+        let denied = call(json!({"project_key": "beta"})).expect("beta is out of scope");
+        assert!(denied.contains("scoped to project 'alpha'"), "{denied}");
+        assert!(call(json!({"project_key": "alpha", "to_project": "beta"})).is_some());
+        assert!(call(json!({"subject": "no project"})).is_some());
+        assert!(
+            scope_violation(
+                "tools/call",
+                Some(&json!({"name": "fetch_inbox_product", "arguments": {}})),
+                &scope,
+            )
+            .is_some()
+        );
+        assert_eq!(scope_violation("tools/list", None, &scope), None);
+    }
+
+    #[test]
+    fn anything_outside_the_allowlist_is_refused() {
+        let scope = scope();
+        let tool = |name: &str| {
+            scope_violation(
+                "tools/call",
+                Some(&json!({"name": name, "arguments": {"project_key": "alpha"}})),
+                &scope,
+            )
+        };
+        assert_eq!(tool("health_check"), None);
+        assert!(tool("install_precommit_guard").is_some());
+        assert!(tool("some_extension_tool").is_some());
+        assert!(scope_violation("prompts/get", None, &scope).is_some());
+        assert!(scope_violation("tasks/list", None, &scope).is_some());
+        assert_eq!(scope_violation("initialize", None, &scope), None);
+    }
+
+    #[test]
+    fn resource_reads_are_checked_by_path_and_query() {
+        let scope = scope();
+        let read =
+            |uri: &str| scope_violation("resources/read", Some(&json!({"uri": uri})), &scope);
+        assert_eq!(read("resource://project/alpha"), None);
+        assert_eq!(
+            read("resource://inbox/BlueLake?project=alpha&limit=5"),
+            None
+        );
+        assert_eq!(read("resource://agents/%2Fwork%2Falpha"), None);
+        assert!(read("resource://project/beta").is_some());
+        assert!(read("resource://inbox/BlueLake?project=beta").is_some());
+        assert!(read("resource://inbox/BlueLake?project=alpha&project=beta").is_some());
+        assert!(read("resource://inbox/BlueLake").is_some());
+        assert!(read("resource://projects").is_some());
+        assert!(read("resource://config/environment").is_some());
+        assert!(read("resource://tooling/recent/60").is_some());
+        assert_eq!(read("resource://tooling/directory"), None);
+    }
+
+    #[test]
+    fn cache_reloads_when_registry_file_changes() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("project_tokens.json");
+        let cache = ProjectTokenCache::new(Some(path.clone()));
+        assert!(cache.registry().projects.is_empty());
+
+        let mut registry = ProjectTokenRegistry::default();
+        registry.upsert("alpha", "/work/alpha", "tok-alpha");
+        registry.save(&path).expect("save registry");
+        assert_eq!(
+            cache
+                .registry()
+                .find_token("tok-alpha")
+                .map(|e| e.slug.clone()),
+            Some("alpha".to_string())
+        );
+        assert!(ProjectTokenCache::new(None).registry().projects.is_empty());
+    }
+}
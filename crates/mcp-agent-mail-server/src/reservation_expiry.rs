@@ -0,0 +1,430 @@
+//! Background worker that warns agents before their file reservations lapse.
+//!
+//! Each scan lists unreleased reservations whose `expires_ts` falls within the
+//! next `RESERVATION_EXPIRY_WARNING_MINUTES` and sends the holding agent a
+//! normal-importance message from the `ReservationNotifier` system agent,
+//! pointing at `file_reservations renew`.
+//!
+//! Every warning is claimed in `file_reservation_expiry_warnings` before it is
+//! written, so each reservation is warned about at most once, across restarts
+//! and concurrent servers. Released reservations never match the scan.
+
+#![forbid(unsafe_code)]
+
+use asupersync::{Cx, Outcome};
+use fastmcp_core::block_on;
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_db::{
+    DbPool, DbPoolConfig, create_pool, micros_to_iso, now_micros,
+    queries::{self, ReservationExpiryCandidate},
+};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+/// System agent that authors reservation expiry warnings.
+pub const RESERVATION_NOTIFIER_AGENT_NAME: &str = "ReservationNotifier";
+
+/// Upper bound on warnings sent per scan; the rest wait for the next one.
+const MAX_WARNINGS_PER_SCAN: usize = 500;
+
+/// Global shutdown flag for the reservation expiry worker.
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+
+/// Worker handle for join-on-shutdown.
+static WORKER: std::sync::LazyLock<Mutex<Option<std::thread::JoinHandle<()>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+/// Start the reservation expiry worker (unless the warning window is 0).
+///
+/// Must be called at most once. Subsequent calls are no-ops.
+pub fn start(config: &Config) {
+    if config.reservation_expiry_warning_minutes == 0 {
+        return;
+    }
+
+    let mut worker = WORKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if worker
+        .as_ref()
+        .is_some_and(std::thread::JoinHandle::is_finished)
+        && let Some(stale) = worker.take()
+    {
+        let _ = stale.join();
+    }
+    if worker.is_none() {
+        let config = config.clone();
+        SHUTDOWN.store(false, Ordering::Release);
+        match std::thread::Builder::new()
+            .name("reservation-expiry".into())
+            .spawn(move || {
+                reservation_expiry_loop(&config);
+            }) {
+            Ok(handle) => {
+                *worker = Some(handle);
+            }
+            Err(err) => {
+                drop(worker);
+                warn!(
+                    error = %err,
+                    "failed to spawn reservation expiry worker; continuing without expiry warnings"
+                );
+                return;
+            }
+        }
+    }
+    drop(worker);
+}
+
+/// Signal the worker to stop.
+pub fn shutdown() {
+    SHUTDOWN.store(true, Ordering::Release);
+    let mut worker = WORKER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(handle) = worker.take() {
+        let _ = handle.join();
+    }
+}
+
+fn reservation_expiry_loop(config: &Config) {
+    let interval = std::time::Duration::from_secs(
+        config
+            .reservation_expiry_warning_scan_interval_seconds
+            .max(5),
+    );
+
+    let mut pool_config = DbPoolConfig::from_env();
+    pool_config.database_url.clone_from(&config.database_url);
+    pool_config.min_connections = 1;
+    pool_config.max_connections = 1;
+    pool_config.warmup_connections = 0;
+    pool_config.run_migrations = false;
+    let pool = match create_pool(&pool_config) {
+        Ok(p) => p,
+        Err(e) => {
+            warn!(error = %e, "reservation expiry worker: failed to create DB pool, exiting");
+            return;
+        }
+    };
+
+    info!(
+        interval_secs = interval.as_secs(),
+        warning_minutes = config.reservation_expiry_warning_minutes,
+        "reservation expiry worker started"
+    );
+
+    loop {
+        if sleep_with_shutdown(interval) {
+            info!("reservation expiry worker shutting down");
+            return;
+        }
+        match run_reservation_expiry_cycle(config, &pool, now_micros()) {
+            Ok(sent) if sent > 0 => {
+                info!(
+                    event = "reservation_expiry_scan",
+                    sent, "reservation expiry warnings sent"
+                );
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "reservation expiry cycle failed"),
+        }
+    }
+}
+
+fn sleep_with_shutdown(duration: std::time::Duration) -> bool {
+    let mut remaining = duration;
+    while !remaining.is_zero() {
+        if SHUTDOWN.load(Ordering::Acquire) {
+            return true;
+        }
+        let chunk = remaining.min(std::time::Duration::from_secs(1));
+        std::thread::sleep(chunk);
+        remaining = remaining.saturating_sub(chunk);
+    }
+    SHUTDOWN.load(Ordering::Acquire)
+}
+
+fn warning_subject(candidate: &ReservationExpiryCandidate) -> String {
+    format!(
+        "Reservation #{} on {} expires soon",
+        candidate.reservation_id, candidate.path_pattern
+    )
+}
+
+fn warning_body(candidate: &ReservationExpiryCandidate) -> String {
+    format!(
+        "Reservation #{id} on {pattern} expires at {expires}; renew with \
+         `file_reservations renew` (`renew_file_reservations`) if you still need it.\n\n\
+         This is the only warning for this reservation.",
+        id = candidate.reservation_id,
+        pattern = candidate.path_pattern,
+        expires = micros_to_iso(candidate.expires_ts),
+    )
+}
+
+/// Run a single scan, warning the holder of every reservation that expires
+/// within the configured window after `now`.
+///
+/// Returns the number of warnings sent.
+fn run_reservation_expiry_cycle(config: &Config, pool: &DbPool, now: i64) -> Result<usize, String> {
+    // Same ambient-Cx approach as the ack reminder worker: this thread runs
+    // outside the async runtime, so borrow the Cx installed by `block_on`.
+    let cx = block_on(async {
+        Cx::current().expect("Runtime::block_on installs an ambient Cx for the polled future")
+    });
+    let window_us = i64::try_from(config.reservation_expiry_warning_minutes)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(60_000_000);
+    let candidates = match block_on(async {
+        queries::list_reservation_expiry_candidates(
+            &cx,
+            pool,
+            now,
+            window_us,
+            MAX_WARNINGS_PER_SCAN,
+        )
+        .await
+    }) {
+        Outcome::Ok(rows) => rows,
+        other => return Err(format!("failed to list expiring reservations: {other:?}")),
+    };
+
+    let mut sent = 0usize;
+    for candidate in &candidates {
+        match send_warning(pool, &cx, candidate, now) {
+            Ok(true) => {
+                sent = sent.saturating_add(1);
+                mcp_agent_mail_core::global_metrics()
+                    .storage
+                    .reservation_expiry_warnings_total
+                    .inc();
+            }
+            Ok(false) => {}
+            Err(e) => warn!(
+                reservation_id = candidate.reservation_id,
+                error = %e,
+                "failed to send reservation expiry warning"
+            ),
+        }
+    }
+    Ok(sent)
+}
+
+/// Claim and send the warning for `candidate`. Returns `false` when another
+/// sweep already claimed it.
+fn send_warning(
+    pool: &DbPool,
+    cx: &Cx,
+    candidate: &ReservationExpiryCandidate,
+    now: i64,
+) -> Result<bool, String> {
+    match block_on(async {
+        queries::claim_reservation_expiry_warning(cx, pool, candidate.reservation_id, now).await
+    }) {
+        Outcome::Ok(true) => {}
+        Outcome::Ok(false) => return Ok(false),
+        other => return Err(format!("failed to claim expiry warning: {other:?}")),
+    }
+
+    let sender = match block_on(async {
+        queries::insert_system_agent(
+            cx,
+            pool,
+            candidate.project_id,
+            RESERVATION_NOTIFIER_AGENT_NAME,
+            "mcp-agent-mail",
+            "system",
+            "Warns agents before their file reservations expire",
+        )
+        .await
+    }) {
+        Outcome::Ok(agent) => agent,
+        other => return Err(format!("failed to ensure notifier agent: {other:?}")),
+    };
+    let sender_id = sender
+        .id
+        .ok_or_else(|| "notifier agent has no id".to_string())?;
+
+    let warning = match block_on(async {
+        queries::create_message_with_recipients(
+            cx,
+            pool,
+            candidate.project_id,
+            sender_id,
+            &warning_subject(candidate),
+            &warning_body(candidate),
+            None,
+            "normal",
+            false,
+            "[]",
+            &[(candidate.agent_id, "to")],
+        )
+        .await
+    }) {
+        Outcome::Ok(message) => message,
+        other => return Err(format!("failed to create expiry warning: {other:?}")),
+    };
+    let warning_id = warning.id.unwrap_or(0);
+
+    match block_on(async {
+        queries::record_reservation_expiry_warning_message(
+            cx,
+            pool,
+            candidate.reservation_id,
+            warning_id,
+        )
+        .await
+    }) {
+        Outcome::Ok(()) => {
+            info!(
+                event = "reservation_expiry_warning_sent",
+                reservation_id = candidate.reservation_id,
+                project_id = candidate.project_id,
+                agent_id = candidate.agent_id,
+                message_id = warning_id,
+                expires_ts = candidate.expires_ts,
+                "reservation expiry warning sent"
+            );
+            Ok(true)
+        }
+        other => Err(format!("failed to record expiry warning: {other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_test_pool(tmp: &tempfile::TempDir) -> DbPool {
+        let db_path = tmp.path().join("db.sqlite3");
+        let db_url = format!(
+            "sqlite:////{}",
+            db_path.to_string_lossy().trim_start_matches('/')
+        );
+        let pool_config = DbPoolConfig {
+            database_url: db_url,
+            min_connections: 1,
+            max_connections: 1,
+            ..Default::default()
+        };
+        create_pool(&pool_config).expect("create pool")
+    }
+
+    #[test]
+    fn warning_body_names_reservation_pattern_and_renew_command() {
+        let candidate = ReservationExpiryCandidate {
+            reservation_id: 42,
+            project_id: 1,
+            agent_id: 2,
+            path_pattern: "src/db/**".to_string(),
+            expires_ts: 1_700_000_000_000_000,
+        };
+        let body = warning_body(&candidate);
+        assert!(body.starts_with("Reservation #42 on src/db/** expires at "));
+        assert!(body.contains(&micros_to_iso(candidate.expires_ts)));
+        assert!(body.contains("file_reservations renew"));
+    }
+
+    #[test]
+    fn cycle_warns_once_per_reservation_and_skips_released() {
+        let tmp = tempfile::tempdir().unwrap();
+        let pool = make_test_pool(&tmp);
+        let cx = Cx::for_testing();
+        let human_key = tmp.path().join("project_root");
+        std::fs::create_dir_all(&human_key).unwrap();
+        let human_key = human_key.to_string_lossy().to_string();
+
+        let project = mcp_agent_mail_core::config::with_process_env_overrides_for_test(
+            &[("AM_ALLOW_EPHEMERAL_PROJECT_ROOTS", "1")],
+            || match block_on(async { queries::ensure_project(&cx, &pool, &human_key).await }) {
+                Outcome::Ok(project) => project,
+                other => panic!("ensure_project failed: {other:?}"),
+            },
+        );
+        let project_id = project.id.expect("project id");
+        let agent_id = match block_on(async {
+            queries::register_agent(
+                &cx, &pool, project_id, "RedFox", "test", "test", None, None, None,
+            )
+            .await
+        }) {
+            Outcome::Ok(agent) => agent.id.expect("agent id"),
+            other => panic!("register_agent failed: {other:?}"),
+        };
+        let reserve = |path: &str| match block_on(async {
+            queries::create_file_reservations(
+                &cx,
+                &pool,
+                project_id,
+                agent_id,
+                &[path],
+                300,
+                true,
+                "test",
+            )
+            .await
+        }) {
+            Outcome::Ok(rows) => rows[0].clone(),
+            other => panic!("create_file_reservations failed: {other:?}"),
+        };
+        let kept = reserve("src/db/**");
+        let released = reserve("src/api/**");
+        let kept_id = kept.id.expect("reservation id");
+        match block_on(async {
+            queries::release_reservations_by_ids(&cx, &pool, &[released.id.expect("id")]).await
+        }) {
+            Outcome::Ok(count) => assert_eq!(count, 1),
+            other => panic!("release failed: {other:?}"),
+        }
+
+        let config = Config {
+            reservation_expiry_warning_minutes: 10,
+            ..Config::default()
+        };
+        let minute = 60 * 1_000_000;
+        let before = mcp_agent_mail_core::global_metrics()
+            .storage
+            .reservation_expiry_warnings_total
+            .load();
+        // Well before the window opens nothing is sent.
+        assert_eq!(
+            run_reservation_expiry_cycle(&config, &pool, kept.expires_ts - 20 * minute).unwrap(),
+            0
+        );
+        assert_eq!(
+            run_reservation_expiry_cycle(&config, &pool, kept.expires_ts - 5 * minute).unwrap(),
+            1
+        );
+        // A later scan inside the window does not warn again.
+        assert_eq!(
+            run_reservation_expiry_cycle(&config, &pool, kept.expires_ts - minute).unwrap(),
+            0
+        );
+        assert!(
+            mcp_agent_mail_core::global_metrics()
+                .storage
+                .reservation_expiry_warnings_total
+                .load()
+                > before
+        );
+
+        let inbox = match block_on(async {
+            queries::fetch_inbox(&cx, &pool, project_id, agent_id, false, None, 20).await
+        }) {
+            Outcome::Ok(rows) => rows,
+            other => panic!("fetch_inbox failed: {other:?}"),
+        };
+        assert_eq!(inbox.len(), 1);
+        let warning = &inbox[0];
+        assert_eq!(warning.sender_name, RESERVATION_NOTIFIER_AGENT_NAME);
+        assert_eq!(warning.message.importance, "normal");
+        assert!(
+            warning
+                .message
+                .body_md
+                .starts_with(&format!("Reservation #{kept_id} on src/db/** expires at "))
+        );
+    }
+}
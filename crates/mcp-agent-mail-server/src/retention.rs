@@ -4,6 +4,8 @@
 //! - Walk `storage_root` to compute per-project statistics
 //! - Report old messages, inbox counts, attachment sizes
 //! - Emit quota warnings when limits exceeded
+//! - Drop mail queued for retired agents once it is older than
+//!   `DEFERRED_MAIL_TTL_DAYS`
 //! - Best-effort: suppress all errors, never crash server
 //!
 //! The worker runs on a dedicated OS thread with `std::thread::sleep` between
@@ -11,7 +13,10 @@
 
 #![forbid(unsafe_code)]
 
+use asupersync::{Cx, Outcome};
+use fastmcp_core::block_on;
 use mcp_agent_mail_core::Config;
+use mcp_agent_mail_db::{DbPool, DbPoolConfig, create_pool, now_micros, queries};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
@@ -29,11 +34,15 @@ static SHUTDOWN: AtomicBool = AtomicBool::new(false);
 static WORKER: std::sync::LazyLock<Mutex<Option<std::thread::JoinHandle<()>>>> =
     std::sync::LazyLock::new(|| Mutex::new(None));
 
-/// Start the retention/quota report worker (if enabled).
+/// Start the retention/quota report worker (if reports, quotas, or the
+/// deferred-mail TTL are enabled).
 ///
 /// Must be called at most once. Subsequent calls are no-ops.
 pub fn start(config: &Config) {
-    if !config.retention_report_enabled && !config.quota_enabled {
+    if !config.retention_report_enabled
+        && !config.quota_enabled
+        && config.deferred_mail_ttl_days == 0
+    {
         return;
     }
 
@@ -86,10 +95,29 @@ fn retention_loop(config: &Config) {
     let interval = std::time::Duration::from_secs(config.retention_report_interval_seconds.max(60));
     let startup_delay = interval.min(std::time::Duration::from_secs(10));
 
+    let deferred_pool = if config.deferred_mail_ttl_days > 0 {
+        let mut pool_config = DbPoolConfig::from_env();
+        pool_config.database_url.clone_from(&config.database_url);
+        pool_config.min_connections = 1;
+        pool_config.max_connections = 1;
+        pool_config.warmup_connections = 0;
+        pool_config.run_migrations = false;
+        match create_pool(&pool_config) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                warn!(error = %e, "retention worker: failed to create DB pool, deferred mail is kept");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     info!(
         interval_secs = interval.as_secs(),
         retention_enabled = config.retention_report_enabled,
         quota_enabled = config.quota_enabled,
+        deferred_mail_ttl_days = config.deferred_mail_ttl_days,
         storage_root = %config.storage_root.display(),
         "retention/quota report worker started"
     );
@@ -110,20 +138,39 @@ fn retention_loop(config: &Config) {
             return;
         }
 
-        match run_retention_cycle(config) {
-            Ok(report) => {
-                info!(
-                    target: "maintenance",
-                    event = "retention_quota_report",
-                    projects_scanned = report.projects_scanned,
-                    total_attachment_bytes = report.total_attachment_bytes,
-                    total_inbox_count = report.total_inbox_count,
-                    warnings = report.warnings,
-                    "retention/quota report completed"
-                );
+        if config.retention_report_enabled || config.quota_enabled {
+            match run_retention_cycle(config) {
+                Ok(report) => {
+                    info!(
+                        target: "maintenance",
+                        event = "retention_quota_report",
+                        projects_scanned = report.projects_scanned,
+                        total_attachment_bytes = report.total_attachment_bytes,
+                        total_inbox_count = report.total_inbox_count,
+                        warnings = report.warnings,
+                        "retention/quota report completed"
+                    );
+                }
+                Err(e) => {
+                    warn!(error = %e, "retention/quota report cycle failed");
+                }
             }
-            Err(e) => {
-                warn!(error = %e, "retention/quota report cycle failed");
+        }
+        if let Some(pool) = &deferred_pool {
+            match prune_deferred_mail(config, pool, now_micros()) {
+                Ok(pruned) if pruned > 0 => {
+                    info!(
+                        target: "maintenance",
+                        event = "deferred_mail_pruned",
+                        pruned,
+                        ttl_days = config.deferred_mail_ttl_days,
+                        "deferred mail for retired agents pruned"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(error = %e, "deferred mail prune failed");
+                }
             }
         }
 
@@ -589,6 +636,25 @@ fn artifact_retention_warnings(
     warnings
 }
 
+/// Drop mail deferred for retired agents more than
+/// `deferred_mail_ttl_days` before `now`. Returns the number of recipient
+/// rows removed.
+fn prune_deferred_mail(config: &Config, pool: &DbPool, now: i64) -> Result<u64, String> {
+    // This thread runs outside the async runtime, so borrow the Cx installed
+    // by `block_on` (see the ack TTL worker).
+    let cx = block_on(async {
+        Cx::current().expect("Runtime::block_on installs an ambient Cx for the polled future")
+    });
+    let ttl_us = i64::try_from(config.deferred_mail_ttl_days)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(86_400_000_000);
+    let cutoff = now.saturating_sub(ttl_us);
+    match block_on(async { queries::prune_deferred_recipients(&cx, pool, cutoff).await }) {
+        Outcome::Ok(pruned) => Ok(pruned),
+        other => Err(format!("failed to prune deferred mail: {other:?}")),
+    }
+}
+
 /// Run a single retention/quota report cycle.
 fn run_retention_cycle(config: &Config) -> Result<RetentionReport, String> {
     // Mailbox archive layout is `{storage_root}/projects/{project_slug}/...`.
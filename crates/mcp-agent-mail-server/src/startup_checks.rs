@@ -732,6 +732,137 @@ fn has_agent_mail_signature(headers: &str) -> bool {
     })
 }
 
+/// Upper bound on a `/readyz` response read by [`probe_server_readiness`].
+const MAX_READYZ_RESPONSE_BYTES: u64 = 16 * 1024;
+
+/// What the listener on a port reports at `/readyz`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerReadiness {
+    /// No Agent Mail server answered on the port.
+    Unreachable,
+    /// The server answered `/readyz` with 200.
+    Ready,
+    /// The server answered but is not ready; `failing` names the failed
+    /// checks (`database`, `recovery`, `health_level`), and `level` is the
+    /// reported health level when the body carried one.
+    Degraded {
+        failing: Vec<String>,
+        level: Option<String>,
+    },
+}
+
+impl ServerReadiness {
+    /// Short operator-facing description.
+    #[must_use]
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Unreachable => "not reachable".to_string(),
+            Self::Ready => "reachable and ready".to_string(),
+            Self::Degraded { failing, level } => {
+                let mut detail = Vec::new();
+                if !failing.is_empty() {
+                    detail.push(format!("failing: {}", failing.join(", ")));
+                }
+                if let Some(level) = level {
+                    detail.push(format!("health level {level}"));
+                }
+                if detail.is_empty() {
+                    "reachable but degraded".to_string()
+                } else {
+                    format!("reachable but degraded ({})", detail.join("; "))
+                }
+            }
+        }
+    }
+}
+
+/// Ask the Agent Mail server on `host:port` whether it is ready to serve.
+///
+/// Only a listener carrying the Agent Mail health signature counts as
+/// reachable. A server that predates `/readyz` answers 404 and is reported as
+/// degraded with no failing checks.
+#[must_use]
+pub fn probe_server_readiness(host: &str, port: u16) -> ServerReadiness {
+    let connect_host = normalize_connect_host_for_health_check(host);
+    let host_for_resolution = connect_host
+        .strip_prefix('[')
+        .and_then(|value| value.strip_suffix(']'))
+        .unwrap_or_else(|| connect_host.as_ref());
+    let Ok(addrs) = (host_for_resolution, port).to_socket_addrs() else {
+        return ServerReadiness::Unreachable;
+    };
+    addrs
+        .into_iter()
+        .find_map(|addr| probe_readyz_addr(connect_host.as_ref(), port, addr))
+        .unwrap_or(ServerReadiness::Unreachable)
+}
+
+fn probe_readyz_addr(
+    connect_host: &str,
+    port: u16,
+    addr: std::net::SocketAddr,
+) -> Option<ServerReadiness> {
+    let mut stream = TcpStream::connect_timeout(&addr, HEALTH_CHECK_TIMEOUT).ok()?;
+    let _ = stream.set_read_timeout(Some(HEALTH_CHECK_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(HEALTH_CHECK_TIMEOUT));
+
+    let request = format!(
+        "GET /readyz HTTP/1.1\r\n\
+         Host: {connect_host}:{port}\r\n\
+         Connection: close\r\n\
+         User-Agent: mcp-agent-mail-startup-check\r\n\
+         \r\n"
+    );
+    let result = (|| -> Option<ServerReadiness> {
+        stream.write_all(request.as_bytes()).ok()?;
+        let mut reader = BufReader::new((&stream).take(MAX_READYZ_RESPONSE_BYTES));
+        let mut status_line = String::new();
+        reader.read_line(&mut status_line).ok()?;
+        let status_code: u16 = status_line
+            .strip_prefix("HTTP/1.")?
+            .split_whitespace()
+            .nth(1)?
+            .parse()
+            .ok()?;
+
+        let mut headers = String::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+            if line == "\r\n" {
+                break;
+            }
+            headers.push_str(&line);
+        }
+        if !has_agent_mail_signature(&headers) {
+            return None;
+        }
+        if status_code == 200 {
+            return Some(ServerReadiness::Ready);
+        }
+
+        let mut body = Vec::new();
+        let _ = reader.read_to_end(&mut body);
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap_or_default();
+        let failing = parsed["failing"]
+            .as_array()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| name.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let level = parsed["level"].as_str().map(str::to_string);
+        Some(ServerReadiness::Degraded { failing, level })
+    })();
+
+    let _ = stream.shutdown(std::net::Shutdown::Both);
+    result
+}
+
 /// Fallback: identify the process holding `port` by PID.
 ///
 /// Uses bounded listener PID discovery (`ss` on Linux, `lsof` elsewhere), then
@@ -3515,6 +3646,77 @@ mod tests {
         assert!(matches!(result, ProbeResult::Fail(_)));
     }
 
+    fn serve_one_readyz_response(status_line: &'static str, body: &'static str) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test listener");
+        let port = listener.local_addr().expect("listener addr").port();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept readyz request");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone stream"));
+            let mut request_line = String::new();
+            reader
+                .read_line(&mut request_line)
+                .expect("read request line");
+            assert!(request_line.starts_with("GET /readyz "), "{request_line}");
+            loop {
+                let mut line = String::new();
+                let bytes = reader.read_line(&mut line).expect("read header line");
+                if bytes == 0 || line == "\r\n" {
+                    break;
+                }
+            }
+            let response = format!(
+                "{status_line}\r\n\
+                 Content-Type: application/json\r\n\
+                 X-Agent-Mail-Health: 1\r\n\
+                 Content-Length: {}\r\n\
+                 Connection: close\r\n\
+                 \r\n\
+                 {body}",
+                body.len()
+            );
+            stream
+                .write_all(response.as_bytes())
+                .expect("write readyz response");
+        });
+        port
+    }
+
+    #[test]
+    fn probe_server_readiness_distinguishes_ready_from_degraded() {
+        let port = serve_one_readyz_response("HTTP/1.1 200 OK", r#"{"status":"ready"}"#);
+        let readiness = probe_server_readiness("127.0.0.1", port);
+        assert_eq!(readiness, ServerReadiness::Ready);
+        assert_eq!(readiness.describe(), "reachable and ready");
+
+        let port = serve_one_readyz_response(
+            "HTTP/1.1 503 Service Unavailable",
+            r#"{"status":"not_ready","failing":["recovery"],"level":"yellow"}"#,
+        );
+        let readiness = probe_server_readiness("127.0.0.1", port);
+        assert_eq!(
+            readiness,
+            ServerReadiness::Degraded {
+                failing: vec!["recovery".to_string()],
+                level: Some("yellow".to_string()),
+            }
+        );
+        assert_eq!(
+            readiness.describe(),
+            "reachable but degraded (failing: recovery; health level yellow)"
+        );
+    }
+
+    #[test]
+    fn probe_server_readiness_reports_closed_port_unreachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        drop(listener);
+        assert_eq!(
+            probe_server_readiness("127.0.0.1", port),
+            ServerReadiness::Unreachable
+        );
+    }
+
     #[test]
     fn run_http_startup_preflight_probes_skips_db_sensitive_checks_when_agent_mail_listener_exists()
     {
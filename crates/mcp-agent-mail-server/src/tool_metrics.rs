@@ -1042,6 +1042,7 @@ mod tests {
             name: "send_message".to_string(),
             calls: 1,
             errors: 0,
+            rate_limited: 0,
             cluster: "messaging".to_string(),
             capabilities: Vec::new(),
             complexity: "simple".to_string(),
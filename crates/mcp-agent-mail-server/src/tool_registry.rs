@@ -0,0 +1,612 @@
+//! Tool registry: the single list of MCP tools a server instance exposes.
+//!
+//! Built-in mail tools and tools supplied by an embedding product are both
+//! installed from a [`ToolRegistry`], so they share one dispatch path:
+//! `InstrumentedTool` wrapping (safe mode, backpressure shedding, per-tool
+//! metrics, TUI events), tool filtering, HTTP auth, and rate limiting.
+//! Custom tools also appear in `resource://tooling/directory`,
+//! `resource://tooling/schemas`, and `resource://tooling/metrics`.
+//!
+//! ```ignore
+//! use mcp_agent_mail_server::tool_registry::{CustomTool, ShedClass, ToolRegistry};
+//!
+//! let registry = ToolRegistry::with_builtins().register(
+//!     CustomTool::new("ticket_lookup", "tickets", |ctx, args| {
+//!         Box::pin(async move {
+//!             let project = ctx.resolve_project().await?;
+//!             Ok(serde_json::json!({ "project": project.slug, "ticket": args["ticket_id"] }))
+//!         })
+//!     })
+//!     .description("Look up a ticket in the issue tracker.")
+//!     .input_schema(serde_json::json!({
+//!         "type": "object",
+//!         "properties": {
+//!             "project_key": { "type": "string" },
+//!             "ticket_id": { "type": "string" }
+//!         },
+//!         "required": ["project_key", "ticket_id"]
+//!     }))
+//!     .shed_class(ShedClass::Shedable),
+//! )?;
+//! mcp_agent_mail_server::run_http_with_tui_and_tools(&config, registry)?;
+//! ```
+
+#![forbid(unsafe_code)]
+
+use std::sync::{Arc, RwLock};
+
+use fastmcp::prelude::*;
+use fastmcp_core::{McpError, McpErrorCode, block_on};
+use fastmcp_server::{BoxFuture, ServerBuilder};
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_db::{AgentRow, ProjectRow};
+use mcp_agent_mail_tools::extensions::{ExtensionToolSpec, register_extension_tool};
+use mcp_agent_mail_tools::tool_util::{self, WriteDbPool, legacy_tool_error};
+use mcp_agent_mail_tools::{
+    AcknowledgeMessage, AcquireBuildSlot, CheckFileReservationConflicts, CleanupPaneIdentities,
+    CreateAgentIdentity, EnsureProduct, EnsureProject, FetchInbox, FetchInboxProduct,
+    FileReservationPaths, ForceReleaseFileReservation, HealthCheck, InstallPrecommitGuard,
+    ListAgents, ListContacts, MacroContactHandshake, MacroFileReservationCycle, MacroPrepareThread,
+    MacroStartSession, MarkMessageRead, ProductsLink, RegisterAgent, ReleaseBuildSlot,
+    ReleaseFileReservations, RenewBuildSlot, RenewFileReservations, ReplyMessage, RequestContact,
+    ResolvePaneIdentity, RespondContact, SearchMessages, SearchMessagesProduct, SendMessage,
+    SetContactPolicy, SummarizeThread, SummarizeThreadProduct, TOOL_CLUSTER_MAP,
+    UninstallPrecommitGuard, Whois, clusters,
+};
+use serde_json::{Value, json};
+
+/// Longest accepted custom tool name.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Registry installed by [`crate::run_http_with_tui_and_tools`]; server
+/// (re)builds use it instead of the built-in set.
+static ACTIVE_TOOL_REGISTRY: RwLock<Option<ToolRegistry>> = RwLock::new(None);
+
+/// Backpressure classification for a custom tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShedClass {
+    /// Always served, even when the server is overloaded.
+    #[default]
+    Critical,
+    /// Read-only or deferrable: rejected under Red health when shedding is
+    /// enabled (`BACKPRESSURE_SHEDDING_ENABLED=true`).
+    Shedable,
+}
+
+/// Async handler for a custom tool. The returned JSON value becomes the
+/// tool's text content, like the built-in tools' JSON payloads.
+pub type CustomToolFn =
+    Arc<dyn for<'a> Fn(ToolContext<'a>, Value) -> BoxFuture<'a, McpResult<Value>> + Send + Sync>;
+
+/// Per-call context handed to a custom tool handler.
+pub struct ToolContext<'a> {
+    mcp: &'a McpContext,
+    tool_name: &'static str,
+    project_key: Option<String>,
+    calling_agent: Option<String>,
+}
+
+impl<'a> ToolContext<'a> {
+    /// Underlying MCP request context (cancellation, budget, request id).
+    #[must_use]
+    pub const fn mcp(&self) -> &'a McpContext {
+        self.mcp
+    }
+
+    #[must_use]
+    pub const fn tool_name(&self) -> &'static str {
+        self.tool_name
+    }
+
+    /// `project_key` argument of the call, if any.
+    #[must_use]
+    pub fn project_key(&self) -> Option<&str> {
+        self.project_key.as_deref()
+    }
+
+    /// Agent named by the call (`agent_name`, `sender_name`, or `name`).
+    #[must_use]
+    pub fn calling_agent(&self) -> Option<&str> {
+        self.calling_agent.as_deref()
+    }
+
+    /// Mailbox database pool, the same one the built-in tools use.
+    pub fn db(&self) -> McpResult<WriteDbPool> {
+        tool_util::get_db_pool()
+    }
+
+    /// Resolve the call's `project_key` with the built-in tools' rules
+    /// (slug, human key, or identity alias).
+    pub async fn resolve_project(&self) -> McpResult<ProjectRow> {
+        let project_key = self.project_key.as_deref().ok_or_else(|| {
+            legacy_tool_error(
+                "INVALID_ARGUMENT",
+                format!("Tool '{}' requires a project_key argument.", self.tool_name),
+                true,
+                json!({ "parameter": "project_key" }),
+            )
+        })?;
+        let pool = self.db()?;
+        tool_util::resolve_project(self.mcp, &pool, project_key).await
+    }
+
+    /// Resolve the calling agent within the call's project.
+    pub async fn resolve_calling_agent(&self) -> McpResult<AgentRow> {
+        let agent_name = self.calling_agent.as_deref().ok_or_else(|| {
+            legacy_tool_error(
+                "INVALID_ARGUMENT",
+                format!("Tool '{}' requires an agent_name argument.", self.tool_name),
+                true,
+                json!({ "parameter": "agent_name" }),
+            )
+        })?;
+        let project = self.resolve_project().await?;
+        let pool = self.db()?;
+        tool_util::resolve_agent(
+            self.mcp,
+            &pool,
+            project.id.unwrap_or(0),
+            agent_name,
+            &project.slug,
+            &project.human_key,
+        )
+        .await
+    }
+}
+
+/// A tool supplied by the embedding product.
+#[derive(Clone)]
+pub struct CustomTool {
+    name: &'static str,
+    cluster: &'static str,
+    description: String,
+    input_schema: Value,
+    shed_class: ShedClass,
+    capabilities: Vec<String>,
+    complexity: String,
+    handler: CustomToolFn,
+}
+
+impl CustomTool {
+    pub fn new<F>(name: &'static str, cluster: &'static str, handler: F) -> Self
+    where
+        F: for<'a> Fn(ToolContext<'a>, Value) -> BoxFuture<'a, McpResult<Value>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            name,
+            cluster,
+            description: String::new(),
+            input_schema: json!({ "type": "object", "properties": {} }),
+            shed_class: ShedClass::default(),
+            capabilities: Vec::new(),
+            complexity: "unknown".to_string(),
+            handler: Arc::new(handler),
+        }
+    }
+
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// JSON Schema for the arguments; `properties` and `required` also feed
+    /// `resource://tooling/schemas`.
+    #[must_use]
+    pub fn input_schema(mut self, schema: Value) -> Self {
+        self.input_schema = schema;
+        self
+    }
+
+    #[must_use]
+    pub const fn shed_class(mut self, shed_class: ShedClass) -> Self {
+        self.shed_class = shed_class;
+        self
+    }
+
+    /// Capability tags shown in the tooling directory and metrics.
+    #[must_use]
+    pub fn capabilities<I, S>(mut self, capabilities: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.capabilities = capabilities.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[must_use]
+    pub fn complexity(mut self, complexity: impl Into<String>) -> Self {
+        self.complexity = complexity.into();
+        self
+    }
+
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn extension_spec(&self) -> ExtensionToolSpec {
+        ExtensionToolSpec {
+            name: self.name,
+            cluster: self.cluster,
+            summary: self.description.clone(),
+            input_schema: self.input_schema.clone(),
+            capabilities: self.capabilities.clone(),
+            complexity: self.complexity.clone(),
+        }
+    }
+
+    /// The `fastmcp` handler for this tool, without server instrumentation.
+    #[must_use]
+    pub fn handler(&self) -> CustomToolHandler {
+        CustomToolHandler { tool: self.clone() }
+    }
+}
+
+/// `fastmcp` adapter that runs a [`CustomTool`] handler.
+pub struct CustomToolHandler {
+    tool: CustomTool,
+}
+
+impl fastmcp::ToolHandler for CustomToolHandler {
+    fn definition(&self) -> Tool {
+        Tool {
+            name: self.tool.name.to_string(),
+            description: (!self.tool.description.is_empty()).then(|| self.tool.description.clone()),
+            input_schema: self.tool.input_schema.clone(),
+            output_schema: None,
+            icon: None,
+            version: None,
+            tags: self.tool.capabilities.clone(),
+            annotations: None,
+        }
+    }
+
+    fn call(&self, ctx: &McpContext, arguments: Value) -> McpResult<Vec<Content>> {
+        match block_on(self.call_async(ctx, arguments)) {
+            fastmcp_core::Outcome::Ok(contents) => Ok(contents),
+            fastmcp_core::Outcome::Err(err) => Err(err),
+            fastmcp_core::Outcome::Cancelled(_) => Err(McpError::new(
+                McpErrorCode::InternalError,
+                format!("Tool '{}' was cancelled", self.tool.name),
+            )),
+            fastmcp_core::Outcome::Panicked(panic) => Err(McpError::new(
+                McpErrorCode::InternalError,
+                format!("Tool '{}' panicked: {}", self.tool.name, panic.message()),
+            )),
+        }
+    }
+
+    fn call_async<'a>(
+        &'a self,
+        ctx: &'a McpContext,
+        arguments: Value,
+    ) -> BoxFuture<'a, McpOutcome<Vec<Content>>> {
+        let (project_key, calling_agent) = crate::extract_project_agent(&arguments);
+        let tool_ctx = ToolContext {
+            mcp: ctx,
+            tool_name: self.tool.name,
+            project_key,
+            calling_agent,
+        };
+        let fut = (self.tool.handler)(tool_ctx, arguments);
+        Box::pin(async move {
+            match fut.await {
+                Ok(value) => match serde_json::to_string(&value) {
+                    Ok(text) => fastmcp_core::Outcome::Ok(vec![Content::Text { text }]),
+                    Err(e) => fastmcp_core::Outcome::Err(McpError::new(
+                        McpErrorCode::InternalError,
+                        format!("JSON error: {e}"),
+                    )),
+                },
+                Err(err) => fastmcp_core::Outcome::Err(err),
+            }
+        })
+    }
+}
+
+/// Why a tool could not be added to a [`ToolRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolRegistryError {
+    /// Names must be 1-64 chars of lowercase ASCII letters, digits, or `_`.
+    InvalidName(String),
+    /// Cluster names must not be empty.
+    InvalidCluster { tool: String },
+    /// A tool with this name is already registered (built-in or custom).
+    Duplicate(String),
+}
+
+impl std::fmt::Display for ToolRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(
+                f,
+                "invalid tool name '{name}': use 1-{MAX_TOOL_NAME_LEN} lowercase letters, digits, or '_'"
+            ),
+            Self::InvalidCluster { tool } => write!(f, "tool '{tool}' has an empty cluster name"),
+            Self::Duplicate(name) => write!(f, "tool '{name}' is already registered"),
+        }
+    }
+}
+
+impl std::error::Error for ToolRegistryError {}
+
+type InstallFn = Arc<dyn Fn(ServerBuilder, &Config) -> ServerBuilder + Send + Sync>;
+
+#[derive(Clone)]
+struct RegistryEntry {
+    name: &'static str,
+    install: InstallFn,
+}
+
+/// Ordered set of tools to expose from a server instance.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    entries: Vec<RegistryEntry>,
+}
+
+impl ToolRegistry {
+    /// An empty registry (no built-in mail tools).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry holding every built-in Agent Mail tool.
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        Self::new()
+            .builtin("health_check", clusters::INFRASTRUCTURE, || HealthCheck)
+            .builtin("ensure_project", clusters::INFRASTRUCTURE, || EnsureProject)
+            .builtin("register_agent", clusters::IDENTITY, || RegisterAgent)
+            .builtin("create_agent_identity", clusters::IDENTITY, || {
+                CreateAgentIdentity
+            })
+            .builtin("whois", clusters::IDENTITY, || Whois)
+            .builtin("resolve_pane_identity", clusters::IDENTITY, || {
+                ResolvePaneIdentity
+            })
+            .builtin("cleanup_pane_identities", clusters::IDENTITY, || {
+                CleanupPaneIdentities
+            })
+            .builtin("list_agents", clusters::IDENTITY, || ListAgents)
+            .builtin("send_message", clusters::MESSAGING, || SendMessage)
+            .builtin("reply_message", clusters::MESSAGING, || ReplyMessage)
+            .builtin("fetch_inbox", clusters::MESSAGING, || FetchInbox)
+            .builtin("mark_message_read", clusters::MESSAGING, || MarkMessageRead)
+            .builtin("acknowledge_message", clusters::MESSAGING, || {
+                AcknowledgeMessage
+            })
+            .builtin("request_contact", clusters::CONTACT, || RequestContact)
+            .builtin("respond_contact", clusters::CONTACT, || RespondContact)
+            .builtin("list_contacts", clusters::CONTACT, || ListContacts)
+            .builtin("set_contact_policy", clusters::CONTACT, || SetContactPolicy)
+            .builtin(
+                "check_file_reservation_conflicts",
+                clusters::FILE_RESERVATIONS,
+                || CheckFileReservationConflicts,
+            )
+            .builtin(
+                "file_reservation_paths",
+                clusters::FILE_RESERVATIONS,
+                || FileReservationPaths,
+            )
+            .builtin(
+                "release_file_reservations",
+                clusters::FILE_RESERVATIONS,
+                || ReleaseFileReservations,
+            )
+            .builtin(
+                "renew_file_reservations",
+                clusters::FILE_RESERVATIONS,
+                || RenewFileReservations,
+            )
+            .builtin(
+                "force_release_file_reservation",
+                clusters::FILE_RESERVATIONS,
+                || ForceReleaseFileReservation,
+            )
+            .builtin("install_precommit_guard", clusters::INFRASTRUCTURE, || {
+                InstallPrecommitGuard
+            })
+            .builtin(
+                "uninstall_precommit_guard",
+                clusters::INFRASTRUCTURE,
+                || UninstallPrecommitGuard,
+            )
+            .builtin("search_messages", clusters::SEARCH, || SearchMessages)
+            .builtin("summarize_thread", clusters::SEARCH, || SummarizeThread)
+            .builtin("macro_start_session", clusters::WORKFLOW_MACROS, || {
+                MacroStartSession
+            })
+            .builtin("macro_prepare_thread", clusters::WORKFLOW_MACROS, || {
+                MacroPrepareThread
+            })
+            .builtin(
+                "macro_file_reservation_cycle",
+                clusters::WORKFLOW_MACROS,
+                || MacroFileReservationCycle,
+            )
+            .builtin("macro_contact_handshake", clusters::WORKFLOW_MACROS, || {
+                MacroContactHandshake
+            })
+            .builtin("ensure_product", clusters::PRODUCT_BUS, || EnsureProduct)
+            .builtin("products_link", clusters::PRODUCT_BUS, || ProductsLink)
+            .builtin("search_messages_product", clusters::PRODUCT_BUS, || {
+                SearchMessagesProduct
+            })
+            .builtin("fetch_inbox_product", clusters::PRODUCT_BUS, || {
+                FetchInboxProduct
+            })
+            .builtin("summarize_thread_product", clusters::PRODUCT_BUS, || {
+                SummarizeThreadProduct
+            })
+            .builtin("acquire_build_slot", clusters::BUILD_SLOTS, || {
+                AcquireBuildSlot
+            })
+            .builtin("renew_build_slot", clusters::BUILD_SLOTS, || RenewBuildSlot)
+            .builtin("release_build_slot", clusters::BUILD_SLOTS, || {
+                ReleaseBuildSlot
+            })
+    }
+
+    fn builtin<T, F>(mut self, name: &'static str, cluster: &'static str, make: F) -> Self
+    where
+        T: fastmcp::ToolHandler + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.entries.push(RegistryEntry {
+            name,
+            install: Arc::new(move |server, config| {
+                crate::add_tool(server, config, name, cluster, make())
+            }),
+        });
+        self
+    }
+
+    /// Add a custom tool. Names must be unique across built-in and custom
+    /// tools, including built-ins left out of this registry.
+    pub fn register(mut self, tool: CustomTool) -> Result<Self, ToolRegistryError> {
+        let name = tool.name;
+        let valid_name = !name.is_empty()
+            && name.len() <= MAX_TOOL_NAME_LEN
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_');
+        if !valid_name {
+            return Err(ToolRegistryError::InvalidName(name.to_string()));
+        }
+        if tool.cluster.trim().is_empty() {
+            return Err(ToolRegistryError::InvalidCluster {
+                tool: name.to_string(),
+            });
+        }
+        if self.contains(name) || TOOL_CLUSTER_MAP.iter().any(|(builtin, _)| *builtin == name) {
+            return Err(ToolRegistryError::Duplicate(name.to_string()));
+        }
+
+        self.entries.push(RegistryEntry {
+            name,
+            install: Arc::new(move |server, config| {
+                let _ = register_extension_tool(tool.extension_spec());
+                if tool.shed_class == ShedClass::Shedable {
+                    mcp_agent_mail_core::register_shedable_tool(tool.name);
+                }
+                crate::add_tool(server, config, tool.name, tool.cluster, tool.handler())
+            }),
+        });
+        Ok(self)
+    }
+
+    #[must_use]
+    pub fn contains(&self, tool_name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.name == tool_name)
+    }
+
+    /// Registered tool names, in installation order.
+    #[must_use]
+    pub fn tool_names(&self) -> Vec<&'static str> {
+        self.entries.iter().map(|entry| entry.name).collect()
+    }
+
+    /// Install every tool the config exposes onto `server`.
+    pub(crate) fn install(&self, server: ServerBuilder, config: &Config) -> ServerBuilder {
+        self.entries
+            .iter()
+            .fold(server, |server, entry| (entry.install)(server, config))
+    }
+}
+
+/// Make `registry` the tool set for every server built from now on.
+pub(crate) fn set_active_tool_registry(registry: ToolRegistry) {
+    *ACTIVE_TOOL_REGISTRY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(registry);
+}
+
+/// The installed registry, or the built-in tools when none was installed.
+pub(crate) fn active_tool_registry() -> ToolRegistry {
+    ACTIVE_TOOL_REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+        .unwrap_or_else(ToolRegistry::with_builtins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo_tool(name: &'static str) -> CustomTool {
+        CustomTool::new(name, "tickets", |ctx, args| {
+            Box::pin(async move {
+                Ok(json!({
+                    "tool": ctx.tool_name(),
+                    "project_key": ctx.project_key(),
+                    "agent": ctx.calling_agent(),
+                    "args": args,
+                }))
+            })
+        })
+    }
+
+    #[test]
+    fn builtins_cover_tool_cluster_map() {
+        let registry = ToolRegistry::with_builtins();
+        let names = registry.tool_names();
+        assert_eq!(names.len(), TOOL_CLUSTER_MAP.len());
+        for (name, _cluster) in TOOL_CLUSTER_MAP {
+            assert!(registry.contains(name), "missing built-in {name}");
+        }
+    }
+
+    #[test]
+    fn register_rejects_duplicates_and_bad_names() {
+        let registry = ToolRegistry::new()
+            .register(echo_tool("ticket_lookup"))
+            .expect("first registration");
+        assert!(matches!(
+            registry.clone().register(echo_tool("ticket_lookup")),
+            Err(ToolRegistryError::Duplicate(_))
+        ));
+        assert!(matches!(
+            registry.clone().register(echo_tool("send_message")),
+            Err(ToolRegistryError::Duplicate(_))
+        ));
+        assert!(matches!(
+            registry.clone().register(echo_tool("Ticket-Lookup")),
+            Err(ToolRegistryError::InvalidName(_))
+        ));
+        assert!(matches!(
+            registry.register(echo_tool("")),
+            Err(ToolRegistryError::InvalidName(_))
+        ));
+    }
+
+    #[test]
+    fn custom_tool_handler_receives_typed_context() {
+        let handler = echo_tool("ticket_echo").handler();
+        assert_eq!(handler.definition().name, "ticket_echo");
+
+        let cx = asupersync::Cx::for_testing();
+        let ctx = McpContext::new(cx, 1);
+        let contents = fastmcp::ToolHandler::call(
+            &handler,
+            &ctx,
+            json!({ "project_key": "/tmp/proj", "agent_name": "BlueLake", "ticket_id": "T-1" }),
+        )
+        .expect("custom tool call");
+        let Content::Text { text } = &contents[0] else {
+            panic!("expected text content");
+        };
+        let payload: Value = serde_json::from_str(text).expect("json payload");
+        assert_eq!(payload["tool"], "ticket_echo");
+        assert_eq!(payload["project_key"], "/tmp/proj");
+        assert_eq!(payload["agent"], "BlueLake");
+        assert_eq!(payload["args"]["ticket_id"], "T-1");
+    }
+}
@@ -588,6 +588,53 @@ pub fn messages_actions(
     actions
 }
 
+/// Build actions for a single delivery on the Inbox screen.
+///
+/// Both entries write recipient state on behalf of `recipient`, so they go
+/// through a confirmation modal. Entries whose state is already set stay
+/// listed but disabled.
+#[must_use]
+pub fn inbox_actions(
+    message_id: i64,
+    recipient: &str,
+    acknowledged: bool,
+    read: bool,
+) -> Vec<ActionEntry> {
+    let acknowledge = ActionEntry::new(
+        "Acknowledge",
+        ActionKind::ConfirmThenExecute {
+            title: "Acknowledge Message".into(),
+            message: format!("Acknowledge message #{message_id} on behalf of {recipient}?"),
+            operation: format!("acknowledge:{message_id}"),
+        },
+    )
+    .with_keybinding("a")
+    .with_description("Acknowledge for this recipient");
+    let mark_read = ActionEntry::new(
+        "Mark read",
+        ActionKind::ConfirmThenExecute {
+            title: "Mark Message Read".into(),
+            message: format!("Mark message #{message_id} read on behalf of {recipient}?"),
+            operation: format!("mark_read:{message_id}"),
+        },
+    )
+    .with_keybinding("r")
+    .with_description("Mark read for this recipient");
+
+    vec![
+        if acknowledged {
+            acknowledge.disabled("Already acknowledged")
+        } else {
+            acknowledge
+        },
+        if read {
+            mark_read.disabled("Already read")
+        } else {
+            mark_read
+        },
+    ]
+}
+
 /// Build batch actions for the Messages screen when multiple messages are selected.
 #[must_use]
 pub fn messages_batch_actions(selected_count: usize) -> Vec<ActionEntry> {
@@ -1285,6 +1332,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inbox_actions_confirm_and_disable_settled_state() {
+        let actions = inbox_actions(42, "BlueLake", true, false);
+        assert_eq!(actions.len(), 2);
+        assert!(!actions[0].enabled, "acked delivery cannot be re-acked");
+        assert!(actions[1].enabled);
+        match &actions[1].action {
+            ActionKind::ConfirmThenExecute {
+                message, operation, ..
+            } => {
+                assert_eq!(operation, "mark_read:42");
+                assert!(message.contains("BlueLake"));
+            }
+            other => panic!("expected ConfirmThenExecute, got {other:?}"),
+        }
+    }
+
     #[test]
     fn messages_actions_without_thread_omits_jump_to_thread() {
         let actions = messages_actions(42, None, "TestAgent");
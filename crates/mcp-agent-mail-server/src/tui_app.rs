@@ -61,6 +61,7 @@ use crate::tui_screens::{
     contacts::ContactsScreen,
     dashboard::DashboardScreen,
     explorer::MailExplorerScreen,
+    inbox::InboxScreen,
     messages::MessageBrowserScreen,
     projects::ProjectsScreen,
     reservations::ReservationsScreen,
@@ -152,6 +153,7 @@ const fn screen_tick_key(id: MailScreenId) -> &'static str {
         MailScreenId::Attachments => "attachments",
         MailScreenId::ArchiveBrowser => "archive_browser",
         MailScreenId::Atc => "atc",
+        MailScreenId::Inbox => "inbox",
     }
 }
 
@@ -173,6 +175,7 @@ fn screen_id_from_tick_key(id: &str) -> Option<MailScreenId> {
         "attachments" => Some(MailScreenId::Attachments),
         "archive_browser" => Some(MailScreenId::ArchiveBrowser),
         "atc" => Some(MailScreenId::Atc),
+        "inbox" => Some(MailScreenId::Inbox),
         _ => None,
     }
 }
@@ -1280,6 +1283,11 @@ const COACH_HINTS: &[CoachHint] = &[
         screen: MailScreenId::Atc,
         message: "Tip: Tab switches between Agents and Evidence Ledger, i toggles detail panel",
     },
+    CoachHint {
+        id: "inbox:actions",
+        screen: MailScreenId::Inbox,
+        message: "Tip: A acknowledges and R marks read for the row's recipient, after a y/n confirm",
+    },
 ];
 
 /// Manages one-shot dismissible coach hints, persisted across sessions.
@@ -1386,6 +1394,7 @@ impl ScreenManager {
             MailScreenId::Attachments => Box::new(AttachmentExplorerScreen::new()),
             MailScreenId::ArchiveBrowser => Box::new(ArchiveBrowserScreen::new()),
             MailScreenId::Atc => Box::new(AtcScreen::new()),
+            MailScreenId::Inbox => Box::new(InboxScreen::new()),
         }
     }
 
@@ -5640,6 +5649,7 @@ mod palette_action_ids {
     pub const SCREEN_ATTACHMENTS: &str = "screen:attachments";
     pub const SCREEN_ARCHIVE_BROWSER: &str = "screen:archive_browser";
     pub const SCREEN_ATC: &str = "screen:atc";
+    pub const SCREEN_INBOX: &str = "screen:inbox";
 }
 
 pub(crate) fn screen_from_palette_action_id(id: &str) -> Option<MailScreenId> {
@@ -5670,6 +5680,7 @@ const fn screen_palette_action_id(id: MailScreenId) -> &'static str {
         MailScreenId::Attachments => palette_action_ids::SCREEN_ATTACHMENTS,
         MailScreenId::ArchiveBrowser => palette_action_ids::SCREEN_ARCHIVE_BROWSER,
         MailScreenId::Atc => palette_action_ids::SCREEN_ATC,
+        MailScreenId::Inbox => palette_action_ids::SCREEN_INBOX,
     }
 }
 
@@ -11080,6 +11091,7 @@ first body
             MailScreenId::Attachments => "attachments",
             MailScreenId::ArchiveBrowser => "archive_browser",
             MailScreenId::Atc => "atc",
+            MailScreenId::Inbox => "inbox",
         }
     }
 
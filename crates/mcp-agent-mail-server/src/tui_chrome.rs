@@ -80,6 +80,7 @@ const fn tab_icon(id: MailScreenId) -> &'static str {
         MailScreenId::Attachments => "\u{29c9}",
         MailScreenId::ArchiveBrowser => "\u{25a4}",
         MailScreenId::Atc => "\u{2708}",
+        MailScreenId::Inbox => "\u{2913}",
     }
 }
 
@@ -591,6 +591,9 @@ const ATC_FOCUS: [FocusNodeTemplate; 3] = [
     tpl("atc.detail", FocusTarget::DetailPanel, 2, 550, 0, 450, 1000),
 ];
 
+const INBOX_FOCUS: [FocusNodeTemplate; 1] =
+    [tpl("inbox.list", FocusTarget::List(0), 0, 0, 0, 1000, 1000)];
+
 const fn focus_templates_for_screen(screen: MailScreenId) -> &'static [FocusNodeTemplate] {
     match screen {
         MailScreenId::Dashboard => &DASHBOARD_FOCUS,
@@ -609,6 +612,7 @@ const fn focus_templates_for_screen(screen: MailScreenId) -> &'static [FocusNode
         MailScreenId::Attachments => &ATTACHMENTS_FOCUS,
         MailScreenId::ArchiveBrowser => &ARCHIVE_BROWSER_FOCUS,
         MailScreenId::Atc => &ATC_FOCUS,
+        MailScreenId::Inbox => &INBOX_FOCUS,
     }
 }
 
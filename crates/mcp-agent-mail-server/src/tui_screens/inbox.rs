@@ -0,0 +1,1276 @@
+//! Inbox screen — recent deliveries across projects with operator read/ack actions.
+//!
+//! Each row is one (message, recipient) delivery so that acknowledging or
+//! marking read always targets a single agent. Queries and writes run on a
+//! dedicated worker thread; the render thread only sends requests and drains
+//! replies in [`MailScreen::tick`].
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+
+use ftui::layout::{Constraint, Rect};
+use ftui::widgets::StatefulWidget;
+use ftui::widgets::Widget;
+use ftui::widgets::block::Block;
+use ftui::widgets::borders::BorderType;
+use ftui::widgets::paragraph::Paragraph;
+use ftui::widgets::table::{Row, Table, TableState};
+use ftui::{Event, Frame, KeyCode, KeyEventKind, Style};
+use ftui_runtime::program::Cmd;
+
+use mcp_agent_mail_db::pool::DbPoolConfig;
+use mcp_agent_mail_db::sqlmodel::Value;
+use mcp_agent_mail_db::{DbConn, DbError};
+
+use crate::tui_action_menu::{ActionEntry, inbox_actions};
+use crate::tui_bridge::TuiSharedState;
+use crate::tui_screens::{HelpEntry, MailScreen, MailScreenMsg};
+
+/// Maximum deliveries fetched per refresh.
+const MAX_ROWS: usize = 300;
+/// Timer refresh cadence (100ms ticks).
+const REFRESH_EVERY_TICKS: u64 = 50;
+
+// ──────────────────────────────────────────────────────────────────────
+// Data model
+// ──────────────────────────────────────────────────────────────────────
+
+/// One recipient's copy of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InboxDelivery {
+    message_id: i64,
+    project_slug: String,
+    sender: String,
+    recipient_id: i64,
+    recipient: String,
+    subject: String,
+    importance: String,
+    ack_required: bool,
+    created_ts: i64,
+    read_ts: Option<i64>,
+    ack_ts: Option<i64>,
+}
+
+impl InboxDelivery {
+    const fn state_label(&self) -> &'static str {
+        match (self.ack_ts, self.read_ts) {
+            (Some(_), _) => "acked",
+            (None, _) if self.ack_required => "ack due",
+            (None, Some(_)) => "read",
+            (None, None) => "unread",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct InboxFilter {
+    project: Option<String>,
+    agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct InboxSnapshot {
+    rows: Vec<InboxDelivery>,
+    /// All project slugs, for the project filter cycle.
+    projects: Vec<String>,
+    /// Agent names within the filtered project, for the agent filter cycle.
+    agents: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InboxActionKind {
+    Acknowledge,
+    MarkRead,
+}
+
+impl InboxActionKind {
+    fn from_operation(op: &str) -> Option<Self> {
+        match op {
+            "acknowledge" => Some(Self::Acknowledge),
+            "mark_read" => Some(Self::MarkRead),
+            _ => None,
+        }
+    }
+
+    const fn verb(self) -> &'static str {
+        match self {
+            Self::Acknowledge => "Acknowledge",
+            Self::MarkRead => "Mark read",
+        }
+    }
+
+    const fn past_tense(self) -> &'static str {
+        match self {
+            Self::Acknowledge => "acknowledged",
+            Self::MarkRead => "marked read",
+        }
+    }
+}
+
+/// A read/ack write on behalf of one recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InboxAction {
+    kind: InboxActionKind,
+    message_id: i64,
+    agent_id: i64,
+    agent_name: String,
+}
+
+impl InboxAction {
+    fn prompt(&self) -> String {
+        format!(
+            "{} #{} on behalf of {}? [y/Enter] confirm  [n/Esc] cancel",
+            self.kind.verb(),
+            self.message_id,
+            self.agent_name
+        )
+    }
+}
+
+// ──────────────────────────────────────────────────────────────────────
+// Background worker
+// ──────────────────────────────────────────────────────────────────────
+
+enum InboxRequest {
+    Refresh(InboxFilter),
+    Apply(InboxAction),
+}
+
+enum InboxReply {
+    Snapshot(Result<InboxSnapshot, String>),
+    Applied(InboxAction, Result<bool, String>),
+}
+
+struct InboxWorker {
+    requests: Sender<InboxRequest>,
+    replies: Receiver<InboxReply>,
+}
+
+impl InboxWorker {
+    fn spawn(database_url: String, storage_root: PathBuf) -> std::io::Result<Self> {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (reply_tx, reply_rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("am-tui-inbox".to_string())
+            .spawn(move || {
+                inbox_worker_loop(&database_url, &storage_root, &request_rx, &reply_tx);
+            })?;
+        Ok(Self {
+            requests: request_tx,
+            replies: reply_rx,
+        })
+    }
+}
+
+/// Serve requests until the screen drops its sender.
+///
+/// Queued requests are drained per wakeup: writes run in arrival order and
+/// only the newest refresh is executed, so a burst of filter changes costs
+/// one query.
+fn inbox_worker_loop(
+    database_url: &str,
+    storage_root: &Path,
+    requests: &Receiver<InboxRequest>,
+    replies: &Sender<InboxReply>,
+) {
+    let mut read_conn: Option<(DbConn, Option<crate::SnapshotDirGuard>)> = None;
+    while let Ok(first) = requests.recv() {
+        let mut refresh = None;
+        let mut pending = Some(first);
+        while let Some(request) = pending.take().or_else(|| requests.try_recv().ok()) {
+            match request {
+                InboxRequest::Refresh(filter) => refresh = Some(filter),
+                InboxRequest::Apply(action) => {
+                    let result = apply_inbox_action(database_url, &action);
+                    if replies.send(InboxReply::Applied(action, result)).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+        if let Some(filter) = refresh {
+            let snapshot =
+                refresh_inbox_snapshot(&mut read_conn, database_url, storage_root, &filter);
+            if replies.send(InboxReply::Snapshot(snapshot)).is_err() {
+                break;
+            }
+        }
+    }
+    if let Some((conn, _snapshot_dir)) = read_conn.take() {
+        mcp_agent_mail_db::close_db_conn(conn, "inbox screen worker shutdown");
+    }
+}
+
+fn refresh_inbox_snapshot(
+    read_conn: &mut Option<(DbConn, Option<crate::SnapshotDirGuard>)>,
+    database_url: &str,
+    storage_root: &Path,
+    filter: &InboxFilter,
+) -> Result<InboxSnapshot, String> {
+    if read_conn.is_none() {
+        match crate::open_observability_sync_db_connection(
+            database_url,
+            storage_root,
+            "inbox screen",
+        ) {
+            Ok(Some(db)) => {
+                let (conn, _, snapshot_dir) = db.into_parts();
+                *read_conn = Some((conn, snapshot_dir));
+            }
+            Ok(None) => return Err("database connection unavailable".to_string()),
+            Err(error) => return Err(error),
+        }
+    }
+    let Some((conn, _)) = read_conn.as_ref() else {
+        return Err("database connection unavailable".to_string());
+    };
+    let result = load_inbox_snapshot(conn, filter);
+    if result.is_err() {
+        // Reopen on the next refresh in case the handle went stale.
+        if let Some((conn, _snapshot_dir)) = read_conn.take() {
+            mcp_agent_mail_db::close_db_conn(conn, "inbox screen query failure");
+        }
+    }
+    result
+}
+
+fn load_inbox_snapshot(conn: &DbConn, filter: &InboxFilter) -> Result<InboxSnapshot, String> {
+    Ok(InboxSnapshot {
+        rows: query_inbox_deliveries(conn, filter)?,
+        projects: query_project_slugs(conn)?,
+        agents: query_agent_names(conn, filter.project.as_deref())?,
+    })
+}
+
+fn query_inbox_deliveries(
+    conn: &DbConn,
+    filter: &InboxFilter,
+) -> Result<Vec<InboxDelivery>, String> {
+    let mut conditions = String::new();
+    let mut params: Vec<Value> = Vec::new();
+    if let Some(project) = &filter.project {
+        conditions.push_str(" AND p.slug = ?");
+        params.push(Value::Text(project.clone()));
+    }
+    if let Some(agent) = &filter.agent {
+        conditions.push_str(" AND a.name = ? COLLATE NOCASE");
+        params.push(Value::Text(agent.clone()));
+    }
+    let sql = format!(
+        "SELECT m.id AS message_id, m.subject, m.importance, m.ack_required, m.created_ts, \
+         p.slug AS project_slug, s.name AS sender_name, \
+         r.agent_id AS recipient_id, a.name AS recipient_name, r.read_ts, r.ack_ts \
+         FROM message_recipients r \
+         JOIN messages m ON m.id = r.message_id \
+         LEFT JOIN agents s ON s.id = m.sender_id \
+         LEFT JOIN agents a ON a.id = r.agent_id \
+         LEFT JOIN projects p ON p.id = m.project_id \
+         WHERE 1=1{conditions} \
+         ORDER BY m.created_ts DESC, m.id DESC, r.agent_id ASC \
+         LIMIT {MAX_ROWS}"
+    );
+    let rows = conn
+        .query_sync(&sql, &params)
+        .map_err(|e| format!("Inbox query: {e}"))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let message_id = row.get_named::<i64>("message_id").ok()?;
+            let recipient_id = row.get_named::<i64>("recipient_id").ok()?;
+            Some(InboxDelivery {
+                message_id,
+                project_slug: row.get_named::<String>("project_slug").unwrap_or_default(),
+                sender: row
+                    .get_named::<String>("sender_name")
+                    .unwrap_or_else(|_| "?".to_string()),
+                recipient_id,
+                recipient: row
+                    .get_named::<String>("recipient_name")
+                    .unwrap_or_else(|_| format!("agent #{recipient_id}")),
+                subject: row.get_named::<String>("subject").unwrap_or_default(),
+                importance: row
+                    .get_named::<String>("importance")
+                    .unwrap_or_else(|_| "normal".to_string()),
+                ack_required: row.get_named::<i64>("ack_required").unwrap_or(0) != 0,
+                created_ts: row.get_named::<i64>("created_ts").unwrap_or(0),
+                read_ts: row.get_named::<i64>("read_ts").ok(),
+                ack_ts: row.get_named::<i64>("ack_ts").ok(),
+            })
+        })
+        .collect())
+}
+
+fn query_project_slugs(conn: &DbConn) -> Result<Vec<String>, String> {
+    let rows = conn
+        .query_sync("SELECT slug FROM projects ORDER BY slug", &[])
+        .map_err(|e| format!("Inbox project query: {e}"))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| row.get_named::<String>("slug").ok())
+        .collect())
+}
+
+fn query_agent_names(conn: &DbConn, project: Option<&str>) -> Result<Vec<String>, String> {
+    let (sql, params) = project.map_or_else(
+        || ("SELECT DISTINCT name FROM agents ORDER BY name", Vec::new()),
+        |slug| {
+            (
+                "SELECT DISTINCT a.name AS name FROM agents a \
+                 JOIN projects p ON p.id = a.project_id \
+                 WHERE p.slug = ? ORDER BY a.name",
+                vec![Value::Text(slug.to_string())],
+            )
+        },
+    );
+    let rows = conn
+        .query_sync(sql, &params)
+        .map_err(|e| format!("Inbox agent query: {e}"))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| row.get_named::<String>("name").ok())
+        .collect())
+}
+
+/// Apply an action against the live database (never an archive snapshot).
+fn apply_inbox_action(database_url: &str, action: &InboxAction) -> Result<bool, String> {
+    let cfg = DbPoolConfig {
+        database_url: database_url.to_string(),
+        ..Default::default()
+    };
+    let path = cfg
+        .sqlite_path()
+        .map_err(|e| format!("failed to resolve sqlite path: {e}"))?;
+    let conn = crate::open_interactive_sync_db_connection(&path)
+        .map_err(|e| format!("failed to open database at {path}: {e}"))?;
+    let result = apply_inbox_action_with_conn(&conn, action);
+    mcp_agent_mail_db::close_db_conn(conn, "inbox screen action");
+    if result.is_ok() {
+        mcp_agent_mail_db::read_cache().invalidate_inbox_stats_scoped(&path, action.agent_id);
+    }
+    result
+}
+
+fn apply_inbox_action_with_conn(conn: &DbConn, action: &InboxAction) -> Result<bool, String> {
+    let result = match action.kind {
+        InboxActionKind::Acknowledge => mcp_agent_mail_db::sync::acknowledge_message_sync(
+            conn,
+            action.agent_id,
+            action.message_id,
+        ),
+        InboxActionKind::MarkRead => mcp_agent_mail_db::sync::mark_message_read_sync(
+            conn,
+            action.agent_id,
+            action.message_id,
+        ),
+    };
+    match result {
+        Ok(transition) => Ok(transition.changed),
+        Err(DbError::NotFound { .. }) => Err(format!(
+            "{} is not a recipient of message #{}",
+            action.agent_name, action.message_id
+        )),
+        Err(e) => Err(format!("update failed: {e}")),
+    }
+}
+
+// ──────────────────────────────────────────────────────────────────────
+// Screen
+// ──────────────────────────────────────────────────────────────────────
+
+/// Cross-project inbox with project/agent filters and confirmed read/ack actions.
+pub struct InboxScreen {
+    rows: Vec<InboxDelivery>,
+    table_state: TableState,
+    filter: InboxFilter,
+    project_choices: Vec<String>,
+    agent_choices: Vec<String>,
+
+    worker: Option<InboxWorker>,
+    refresh_requested: bool,
+    refresh_in_flight: bool,
+    last_refresh_tick: Option<u64>,
+    last_error: Option<String>,
+
+    /// Action awaiting `y`/`n` from the operator.
+    pending_confirm: Option<InboxAction>,
+    status: Option<String>,
+}
+
+impl InboxScreen {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            table_state: TableState::default(),
+            filter: InboxFilter::default(),
+            project_choices: Vec::new(),
+            agent_choices: Vec::new(),
+            worker: None,
+            refresh_requested: true,
+            refresh_in_flight: false,
+            last_refresh_tick: None,
+            last_error: None,
+            pending_confirm: None,
+            status: None,
+        }
+    }
+
+    fn selected(&self) -> Option<&InboxDelivery> {
+        self.rows.get(self.table_state.selected?)
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.rows.is_empty() {
+            self.table_state.selected = None;
+            return;
+        }
+        let current = self.table_state.selected.unwrap_or(0);
+        let next = current
+            .saturating_add_signed(delta)
+            .min(self.rows.len() - 1);
+        self.table_state.selected = Some(next);
+    }
+
+    /// Send a request, spawning the worker on first use.
+    fn send(&mut self, request: InboxRequest, state: &TuiSharedState) -> bool {
+        if self.worker.is_none() {
+            let cfg = state.config_snapshot();
+            match InboxWorker::spawn(cfg.raw_database_url, PathBuf::from(cfg.storage_root)) {
+                Ok(worker) => self.worker = Some(worker),
+                Err(error) => {
+                    self.last_error = Some(format!("failed to start inbox worker: {error}"));
+                    return false;
+                }
+            }
+        }
+        let sent = self
+            .worker
+            .as_ref()
+            .is_some_and(|worker| worker.requests.send(request).is_ok());
+        if !sent {
+            // Worker exited; respawn on the next request.
+            self.worker = None;
+            self.refresh_in_flight = false;
+        }
+        sent
+    }
+
+    fn request_refresh(&mut self, tick_count: u64, state: &TuiSharedState) {
+        self.refresh_requested = false;
+        self.last_refresh_tick = Some(tick_count);
+        self.refresh_in_flight = self.send(InboxRequest::Refresh(self.filter.clone()), state);
+    }
+
+    fn drain_replies(&mut self) {
+        let Some(worker) = self.worker.as_ref() else {
+            return;
+        };
+        let mut replies = Vec::new();
+        let disconnected = loop {
+            match worker.replies.try_recv() {
+                Ok(reply) => replies.push(reply),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if disconnected {
+            // Worker thread exited; respawn on the next request.
+            self.worker = None;
+            self.refresh_in_flight = false;
+        }
+        for reply in replies {
+            self.apply_reply(reply);
+        }
+    }
+
+    fn apply_reply(&mut self, reply: InboxReply) {
+        match reply {
+            InboxReply::Snapshot(Ok(snapshot)) => {
+                self.refresh_in_flight = false;
+                self.apply_snapshot(snapshot);
+                self.last_error = None;
+            }
+            InboxReply::Snapshot(Err(error)) => {
+                // Keep the last good rows visible; surface the failure.
+                self.refresh_in_flight = false;
+                self.last_error = Some(error);
+            }
+            InboxReply::Applied(action, Ok(changed)) => {
+                self.status = Some(if changed {
+                    format!(
+                        "#{} {} for {}",
+                        action.message_id,
+                        action.kind.past_tense(),
+                        action.agent_name
+                    )
+                } else {
+                    format!(
+                        "#{} was already {} for {}",
+                        action.message_id,
+                        action.kind.past_tense(),
+                        action.agent_name
+                    )
+                });
+                self.refresh_requested = true;
+            }
+            InboxReply::Applied(action, Err(error)) => {
+                self.status = Some(format!(
+                    "{} #{} failed: {error}",
+                    action.kind.verb(),
+                    action.message_id
+                ));
+            }
+        }
+    }
+
+    fn apply_snapshot(&mut self, snapshot: InboxSnapshot) {
+        // Keep the cursor on the same delivery across refreshes when possible.
+        let selected_key = self
+            .selected()
+            .map(|row| (row.message_id, row.recipient_id));
+        self.rows = snapshot.rows;
+        self.project_choices = snapshot.projects;
+        self.agent_choices = snapshot.agents;
+        self.table_state.selected = if self.rows.is_empty() {
+            None
+        } else {
+            let pos = selected_key
+                .and_then(|key| {
+                    self.rows
+                        .iter()
+                        .position(|row| (row.message_id, row.recipient_id) == key)
+                })
+                .unwrap_or_else(|| {
+                    self.table_state
+                        .selected
+                        .unwrap_or(0)
+                        .min(self.rows.len() - 1)
+                });
+            Some(pos)
+        };
+    }
+
+    fn cycle_project(&mut self) {
+        self.filter.project = next_choice(self.filter.project.as_deref(), &self.project_choices);
+        // Agent choices are scoped to the project; reset rather than filter to nothing.
+        self.filter.agent = None;
+        self.refresh_requested = true;
+    }
+
+    fn cycle_agent(&mut self) {
+        self.filter.agent = next_choice(self.filter.agent.as_deref(), &self.agent_choices);
+        self.refresh_requested = true;
+    }
+
+    fn clear_filters(&mut self) {
+        if self.filter != InboxFilter::default() {
+            self.filter = InboxFilter::default();
+            self.refresh_requested = true;
+        }
+    }
+
+    fn arm_action(&mut self, kind: InboxActionKind) {
+        let Some(row) = self.selected() else {
+            return;
+        };
+        let already = match kind {
+            InboxActionKind::Acknowledge => row.ack_ts.is_some(),
+            InboxActionKind::MarkRead => row.read_ts.is_some(),
+        };
+        if already {
+            self.status = Some(format!(
+                "#{} is already {} for {}",
+                row.message_id,
+                kind.past_tense(),
+                row.recipient
+            ));
+            return;
+        }
+        self.pending_confirm = Some(InboxAction {
+            kind,
+            message_id: row.message_id,
+            agent_id: row.recipient_id,
+            agent_name: row.recipient.clone(),
+        });
+    }
+
+    /// Hand a confirmed action to the worker, which `tick` has already started.
+    fn dispatch_action(&mut self, action: InboxAction) {
+        self.status = Some(format!(
+            "{} #{} for {}…",
+            action.kind.verb(),
+            action.message_id,
+            action.agent_name
+        ));
+        let sent = self
+            .worker
+            .as_ref()
+            .is_some_and(|worker| worker.requests.send(InboxRequest::Apply(action)).is_ok());
+        if !sent {
+            self.worker = None;
+            self.status = Some("Inbox worker unavailable; action not applied".to_string());
+        }
+    }
+
+    fn handle_confirm_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                if let Some(action) = self.pending_confirm.take() {
+                    self.dispatch_action(action);
+                }
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Escape => {
+                self.pending_confirm = None;
+                self.status = Some("Cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('j') | KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Char('G') | KeyCode::End => {
+                if !self.rows.is_empty() {
+                    self.table_state.selected = Some(self.rows.len() - 1);
+                }
+            }
+            KeyCode::Char('g') | KeyCode::Home => {
+                if !self.rows.is_empty() {
+                    self.table_state.selected = Some(0);
+                }
+            }
+            KeyCode::Char('r') => self.refresh_requested = true,
+            KeyCode::Char('p') => self.cycle_project(),
+            KeyCode::Char('a') => self.cycle_agent(),
+            KeyCode::Char('x') => self.clear_filters(),
+            KeyCode::Char('A') => self.arm_action(InboxActionKind::Acknowledge),
+            KeyCode::Char('R') => self.arm_action(InboxActionKind::MarkRead),
+            _ => {}
+        }
+    }
+
+    fn header_line(&self) -> String {
+        let refresh = if self.refresh_in_flight || self.refresh_requested {
+            " | refreshing…"
+        } else {
+            ""
+        };
+        format!(
+            "{} deliveries | Project: {} [p] | Agent: {} [a]{refresh}",
+            self.rows.len(),
+            self.filter.project.as_deref().unwrap_or("all"),
+            self.filter.agent.as_deref().unwrap_or("all"),
+        )
+    }
+
+    fn render_table(&self, frame: &mut Frame<'_>, area: Rect) {
+        let tp = crate::tui_theme::TuiThemePalette::current();
+        let narrow = area.width < 100;
+
+        let (header_cells, widths): (Vec<&str>, Vec<Constraint>) = if narrow {
+            (
+                vec!["From", "To", "Subject", "State"],
+                vec![
+                    Constraint::Percentage(20.0),
+                    Constraint::Percentage(20.0),
+                    Constraint::Percentage(45.0),
+                    Constraint::Percentage(15.0),
+                ],
+            )
+        } else {
+            (
+                vec!["When", "Project", "From", "To", "Subject", "Imp", "State"],
+                vec![
+                    Constraint::Percentage(9.0),
+                    Constraint::Percentage(14.0),
+                    Constraint::Percentage(13.0),
+                    Constraint::Percentage(13.0),
+                    Constraint::Percentage(33.0),
+                    Constraint::Percentage(8.0),
+                    Constraint::Percentage(10.0),
+                ],
+            )
+        };
+        let header = Row::new(header_cells).style(Style::default().bold());
+
+        let rows: Vec<Row> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let style = match row.state_label() {
+                    "ack due" => Style::default().fg(tp.metric_ack_bad),
+                    "unread" => Style::default().fg(tp.text_primary),
+                    _ => Style::default().fg(tp.text_muted),
+                };
+                if narrow {
+                    Row::new([
+                        row.sender.clone(),
+                        row.recipient.clone(),
+                        row.subject.clone(),
+                        row.state_label().to_string(),
+                    ])
+                    .style(style)
+                } else {
+                    Row::new([
+                        format_age(row.created_ts),
+                        row.project_slug.clone(),
+                        row.sender.clone(),
+                        row.recipient.clone(),
+                        row.subject.clone(),
+                        row.importance.clone(),
+                        row.state_label().to_string(),
+                    ])
+                    .style(style)
+                }
+            })
+            .collect();
+
+        let block = Block::default()
+            .title("Deliveries")
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(tp.panel_border));
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .highlight_style(Style::default().fg(tp.selection_fg).bg(tp.selection_bg));
+        let mut ts = self.table_state.clone();
+        StatefulWidget::render(&table, area, frame, &mut ts);
+    }
+}
+
+impl Default for InboxScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MailScreen for InboxScreen {
+    fn update(&mut self, event: &Event, _state: &TuiSharedState) -> Cmd<MailScreenMsg> {
+        let Event::Key(key) = event else {
+            return Cmd::None;
+        };
+        if key.kind != KeyEventKind::Press {
+            return Cmd::None;
+        }
+        if self.pending_confirm.is_some() {
+            self.handle_confirm_key(key.code);
+        } else {
+            self.handle_key(key.code);
+        }
+        Cmd::None
+    }
+
+    fn tick(&mut self, tick_count: u64, state: &TuiSharedState) {
+        self.drain_replies();
+        if self.refresh_in_flight {
+            return;
+        }
+        let due = self
+            .last_refresh_tick
+            .is_none_or(|last| tick_count.saturating_sub(last) >= REFRESH_EVERY_TICKS);
+        if self.refresh_requested || due {
+            self.request_refresh(tick_count, state);
+        }
+    }
+
+    fn view(&self, frame: &mut Frame<'_>, area: Rect, _state: &TuiSharedState) {
+        if area.height < 3 || area.width < 20 {
+            return;
+        }
+        let outer_block = crate::tui_panel_helpers::panel_block(" Inbox ");
+        let inner = outer_block.inner(area);
+        outer_block.render(area, frame);
+        let area = inner;
+        let tp = crate::tui_theme::TuiThemePalette::current();
+
+        let header_area = Rect::new(area.x, area.y, area.width, 1);
+        Paragraph::new(self.header_line()).render(header_area, frame);
+
+        let footer_h = u16::from(area.height >= 4);
+        let table_area = Rect::new(
+            area.x,
+            area.y + 1,
+            area.width,
+            area.height.saturating_sub(1 + footer_h),
+        );
+        if self.rows.is_empty() {
+            let hint = if self.refresh_in_flight || self.last_refresh_tick.is_none() {
+                "Loading deliveries…"
+            } else {
+                "Nothing delivered yet for this filter. Press x to clear filters."
+            };
+            crate::tui_panel_helpers::render_empty_state(
+                frame,
+                table_area,
+                "\u{1f4e5}",
+                "No Deliveries",
+                hint,
+            );
+        } else {
+            self.render_table(frame, table_area);
+        }
+
+        if footer_h > 0 {
+            let footer_area = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
+            let (text, style) = if let Some(action) = &self.pending_confirm {
+                (
+                    action.prompt(),
+                    Style::default().fg(tp.severity_warn).bold(),
+                )
+            } else if let Some(error) = &self.last_error {
+                (
+                    format!("Refresh failed: {error}"),
+                    Style::default().fg(tp.severity_error),
+                )
+            } else if let Some(status) = &self.status {
+                (status.clone(), Style::default().fg(tp.text_muted))
+            } else {
+                (
+                    "A acknowledge · R mark read · . actions".to_string(),
+                    Style::default().fg(tp.text_muted),
+                )
+            };
+            Paragraph::new(text).style(style).render(footer_area, frame);
+        }
+    }
+
+    fn keybindings(&self) -> Vec<HelpEntry> {
+        vec![
+            HelpEntry {
+                key: "j/k",
+                action: "Select delivery",
+            },
+            HelpEntry {
+                key: "g/G",
+                action: "Jump to first / last",
+            },
+            HelpEntry {
+                key: "p",
+                action: "Cycle project filter",
+            },
+            HelpEntry {
+                key: "a",
+                action: "Cycle agent (recipient) filter",
+            },
+            HelpEntry {
+                key: "x",
+                action: "Clear filters",
+            },
+            HelpEntry {
+                key: "r",
+                action: "Refresh now",
+            },
+            HelpEntry {
+                key: "A",
+                action: "Acknowledge for recipient (confirm)",
+            },
+            HelpEntry {
+                key: "R",
+                action: "Mark read for recipient (confirm)",
+            },
+            HelpEntry {
+                key: "y/Enter",
+                action: "Confirm pending action",
+            },
+            HelpEntry {
+                key: "n/Esc",
+                action: "Cancel pending action",
+            },
+        ]
+    }
+
+    fn context_help_tip(&self) -> Option<&'static str> {
+        Some("One row per recipient. A/R act on behalf of that recipient after confirmation.")
+    }
+
+    fn consumes_text_input(&self) -> bool {
+        // Route y/n/Esc to the confirmation prompt instead of global bindings.
+        self.pending_confirm.is_some()
+    }
+
+    fn handle_action(&mut self, operation: &str, context: &str) -> Cmd<MailScreenMsg> {
+        let Some((op, id)) = operation.split_once(':') else {
+            return Cmd::None;
+        };
+        let Some(kind) = InboxActionKind::from_operation(op) else {
+            return Cmd::None;
+        };
+        let (Ok(message_id), Some(Ok(agent_id))) = (
+            id.parse::<i64>(),
+            context.strip_prefix("recipient:").map(str::parse::<i64>),
+        ) else {
+            return Cmd::None;
+        };
+        let agent_name = self
+            .rows
+            .iter()
+            .find(|row| row.recipient_id == agent_id)
+            .map_or_else(|| format!("agent #{agent_id}"), |row| row.recipient.clone());
+        // The action menu already confirmed via its modal.
+        self.pending_confirm = None;
+        self.dispatch_action(InboxAction {
+            kind,
+            message_id,
+            agent_id,
+            agent_name,
+        });
+        Cmd::msg(MailScreenMsg::Noop)
+    }
+
+    fn contextual_actions(&self) -> Option<(Vec<ActionEntry>, u16, String)> {
+        let selected_idx = self.table_state.selected?;
+        let row = self.rows.get(selected_idx)?;
+        let actions = inbox_actions(
+            row.message_id,
+            &row.recipient,
+            row.ack_ts.is_some(),
+            row.read_ts.is_some(),
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        let anchor_row = (selected_idx as u16).saturating_add(3);
+        Some((
+            actions,
+            anchor_row,
+            format!("recipient:{}", row.recipient_id),
+        ))
+    }
+
+    fn copyable_content(&self) -> Option<String> {
+        let row = self.selected()?;
+        Some(format!(
+            "#{} {} ({} -> {}, {})",
+            row.message_id,
+            row.subject,
+            row.sender,
+            row.recipient,
+            row.state_label()
+        ))
+    }
+
+    fn title(&self) -> &'static str {
+        "Inbox"
+    }
+
+    fn tab_label(&self) -> &'static str {
+        "Inbox"
+    }
+}
+
+// ──────────────────────────────────────────────────────────────────────
+// Helpers
+// ──────────────────────────────────────────────────────────────────────
+
+/// Advance `current` through `choices`, wrapping back to "all" (`None`).
+fn next_choice(current: Option<&str>, choices: &[String]) -> Option<String> {
+    let next_idx = match current {
+        None => 0,
+        Some(value) => choices
+            .iter()
+            .position(|choice| choice == value)
+            .map_or(0, |idx| idx + 1),
+    };
+    choices.get(next_idx).cloned()
+}
+
+/// Compact relative age for the When column.
+fn format_age(ts_micros: i64) -> String {
+    if ts_micros == 0 {
+        return "-".to_string();
+    }
+    let now = chrono::Utc::now().timestamp_micros();
+    let delta = ((now - ts_micros) / 1_000_000).max(0).unsigned_abs();
+    if delta < 60 {
+        format!("{delta}s")
+    } else if delta < 3600 {
+        format!("{}m", delta / 60)
+    } else if delta < 86400 {
+        format!("{}h", delta / 3600)
+    } else {
+        format!("{}d", delta / 86400)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_agent_mail_core::Config;
+
+    fn test_state() -> std::sync::Arc<TuiSharedState> {
+        TuiSharedState::new(&Config::default())
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(ftui::KeyEvent::new(code))
+    }
+
+    fn delivery(message_id: i64, recipient_id: i64, recipient: &str) -> InboxDelivery {
+        InboxDelivery {
+            message_id,
+            project_slug: "alpha".into(),
+            sender: "Sender".into(),
+            recipient_id,
+            recipient: recipient.into(),
+            subject: format!("subject {message_id}"),
+            importance: "normal".into(),
+            ack_required: true,
+            created_ts: 1_000,
+            read_ts: None,
+            ack_ts: None,
+        }
+    }
+
+    fn seeded_conn() -> DbConn {
+        let conn = DbConn::open_memory().expect("open memory db");
+        conn.execute_raw(
+            "CREATE TABLE projects (id INTEGER PRIMARY KEY, slug TEXT NOT NULL);
+             CREATE TABLE agents (id INTEGER PRIMARY KEY, project_id INTEGER, name TEXT NOT NULL);
+             CREATE TABLE messages (
+                id INTEGER PRIMARY KEY,
+                project_id INTEGER NOT NULL,
+                sender_id INTEGER NOT NULL,
+                subject TEXT NOT NULL,
+                importance TEXT NOT NULL,
+                ack_required INTEGER NOT NULL,
+                created_ts INTEGER NOT NULL
+             );
+             CREATE TABLE message_recipients (
+                message_id INTEGER NOT NULL,
+                agent_id INTEGER NOT NULL,
+                kind TEXT NOT NULL DEFAULT 'to',
+                read_ts INTEGER,
+                ack_ts INTEGER,
+                PRIMARY KEY(message_id, agent_id)
+             );
+             INSERT INTO projects (id, slug) VALUES (1, 'alpha'), (2, 'beta');
+             INSERT INTO agents (id, project_id, name) VALUES
+                (1, 1, 'Sender'), (2, 1, 'BlueLake'), (3, 2, 'RedFox');
+             INSERT INTO messages VALUES
+                (10, 1, 1, 'Deploy', 'high', 1, 2000),
+                (11, 2, 3, 'Status', 'normal', 0, 1000);
+             INSERT INTO message_recipients (message_id, agent_id, read_ts, ack_ts) VALUES
+                (10, 2, NULL, NULL),
+                (10, 3, 1500, NULL),
+                (11, 3, NULL, NULL);",
+        )
+        .expect("seed inbox schema");
+        conn
+    }
+
+    #[test]
+    fn query_returns_one_row_per_recipient_newest_first() {
+        let conn = seeded_conn();
+        let snapshot = load_inbox_snapshot(&conn, &InboxFilter::default()).expect("snapshot");
+        let keys: Vec<(i64, &str)> = snapshot
+            .rows
+            .iter()
+            .map(|row| (row.message_id, row.recipient.as_str()))
+            .collect();
+        assert_eq!(keys, vec![(10, "BlueLake"), (10, "RedFox"), (11, "RedFox")]);
+        assert_eq!(snapshot.rows[0].state_label(), "ack due");
+        assert_eq!(snapshot.rows[2].state_label(), "unread");
+        assert_eq!(snapshot.projects, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn query_applies_project_and_agent_filters() {
+        let conn = seeded_conn();
+        let by_project = InboxFilter {
+            project: Some("beta".into()),
+            agent: None,
+        };
+        let snapshot = load_inbox_snapshot(&conn, &by_project).expect("snapshot");
+        assert_eq!(snapshot.rows.len(), 1);
+        assert_eq!(snapshot.rows[0].message_id, 11);
+        assert_eq!(snapshot.agents, vec!["RedFox"]);
+
+        let by_agent = InboxFilter {
+            project: None,
+            agent: Some("bluelake".into()),
+        };
+        let rows = query_inbox_deliveries(&conn, &by_agent).expect("rows");
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].recipient, "BlueLake");
+    }
+
+    #[test]
+    fn apply_action_acknowledges_only_the_target_recipient() {
+        let conn = seeded_conn();
+        let action = InboxAction {
+            kind: InboxActionKind::Acknowledge,
+            message_id: 10,
+            agent_id: 2,
+            agent_name: "BlueLake".into(),
+        };
+        assert_eq!(apply_inbox_action_with_conn(&conn, &action), Ok(true));
+        assert_eq!(apply_inbox_action_with_conn(&conn, &action), Ok(false));
+
+        let rows = query_inbox_deliveries(&conn, &InboxFilter::default()).expect("rows");
+        assert_eq!(rows[0].state_label(), "acked");
+        assert!(rows[0].read_ts.is_some(), "ack implies read");
+        assert_eq!(rows[1].ack_ts, None, "other recipient untouched");
+
+        let stranger = InboxAction {
+            agent_id: 1,
+            agent_name: "Sender".into(),
+            ..action
+        };
+        let err = apply_inbox_action_with_conn(&conn, &stranger).expect_err("not a recipient");
+        assert!(err.contains("not a recipient"));
+    }
+
+    #[test]
+    fn action_key_requires_confirmation() {
+        let state = test_state();
+        let mut screen = InboxScreen::new();
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![delivery(10, 2, "BlueLake")],
+            ..InboxSnapshot::default()
+        });
+
+        screen.update(&key(KeyCode::Char('A')), &state);
+        let pending = screen.pending_confirm.clone().expect("armed");
+        assert_eq!(pending.kind, InboxActionKind::Acknowledge);
+        assert_eq!(pending.agent_id, 2);
+        assert!(screen.consumes_text_input());
+
+        screen.update(&key(KeyCode::Escape), &state);
+        assert!(screen.pending_confirm.is_none());
+        assert!(!screen.consumes_text_input());
+    }
+
+    #[test]
+    fn settled_state_is_not_armed() {
+        let state = test_state();
+        let mut screen = InboxScreen::new();
+        let mut row = delivery(10, 2, "BlueLake");
+        row.read_ts = Some(5);
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![row],
+            ..InboxSnapshot::default()
+        });
+
+        screen.update(&key(KeyCode::Char('R')), &state);
+        assert!(screen.pending_confirm.is_none());
+        assert!(screen.status.as_deref().unwrap_or("").contains("already"));
+    }
+
+    #[test]
+    fn filter_keys_cycle_through_choices_and_request_refresh() {
+        let state = test_state();
+        let mut screen = InboxScreen::new();
+        screen.project_choices = vec!["alpha".into(), "beta".into()];
+        screen.refresh_requested = false;
+
+        screen.update(&key(KeyCode::Char('p')), &state);
+        assert_eq!(screen.filter.project.as_deref(), Some("alpha"));
+        assert!(screen.refresh_requested);
+        screen.update(&key(KeyCode::Char('p')), &state);
+        screen.update(&key(KeyCode::Char('p')), &state);
+        assert_eq!(screen.filter.project, None, "wraps back to all");
+
+        screen.filter.agent = Some("BlueLake".into());
+        screen.update(&key(KeyCode::Char('x')), &state);
+        assert_eq!(screen.filter, InboxFilter::default());
+    }
+
+    #[test]
+    fn snapshot_keeps_cursor_on_same_delivery() {
+        let mut screen = InboxScreen::new();
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![delivery(10, 2, "BlueLake"), delivery(9, 2, "BlueLake")],
+            ..InboxSnapshot::default()
+        });
+        screen.table_state.selected = Some(1);
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![
+                delivery(11, 2, "BlueLake"),
+                delivery(10, 2, "BlueLake"),
+                delivery(9, 2, "BlueLake"),
+            ],
+            ..InboxSnapshot::default()
+        });
+        assert_eq!(screen.selected().map(|row| row.message_id), Some(9));
+    }
+
+    #[test]
+    fn failed_refresh_keeps_previous_rows() {
+        let mut screen = InboxScreen::new();
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![delivery(10, 2, "BlueLake")],
+            ..InboxSnapshot::default()
+        });
+        screen.refresh_in_flight = true;
+        screen.apply_reply(InboxReply::Snapshot(Err("locked".into())));
+        assert_eq!(screen.rows.len(), 1);
+        assert!(!screen.refresh_in_flight);
+        assert_eq!(screen.last_error.as_deref(), Some("locked"));
+    }
+
+    #[test]
+    fn contextual_actions_carry_recipient_context() {
+        let mut screen = InboxScreen::new();
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![delivery(10, 2, "BlueLake")],
+            ..InboxSnapshot::default()
+        });
+        let (actions, _, ctx) = screen.contextual_actions().expect("actions");
+        assert_eq!(actions.len(), 2);
+        assert_eq!(ctx, "recipient:2");
+    }
+
+    #[test]
+    fn handle_action_claims_inbox_operations_only() {
+        let mut screen = InboxScreen::new();
+        assert!(matches!(
+            screen.handle_action("acknowledge:10", "recipient:2"),
+            Cmd::Msg(MailScreenMsg::Noop)
+        ));
+        assert!(matches!(
+            screen.handle_action("release:10", "recipient:2"),
+            Cmd::None
+        ));
+        assert!(matches!(
+            screen.handle_action("acknowledge:10", "msg:10"),
+            Cmd::None
+        ));
+    }
+
+    #[test]
+    fn renders_without_panic() {
+        let state = test_state();
+        let mut screen = InboxScreen::new();
+        let mut pool = ftui::GraphemePool::new();
+        for (w, h) in [(120, 30), (60, 10), (20, 3), (10, 2)] {
+            let mut frame = Frame::new(w, h, &mut pool);
+            screen.view(&mut frame, Rect::new(0, 0, w, h), &state);
+        }
+        screen.apply_snapshot(InboxSnapshot {
+            rows: vec![delivery(10, 2, "BlueLake")],
+            ..InboxSnapshot::default()
+        });
+        screen.pending_confirm = Some(InboxAction {
+            kind: InboxActionKind::MarkRead,
+            message_id: 10,
+            agent_id: 2,
+            agent_name: "BlueLake".into(),
+        });
+        let mut frame = Frame::new(120, 30, &mut pool);
+        screen.view(&mut frame, Rect::new(0, 0, 120, 30), &state);
+    }
+
+    #[test]
+    fn keybindings_document_actions() {
+        let screen = InboxScreen::new();
+        let bindings = screen.keybindings();
+        for binding in ["A", "R", "p", "a", "r", "y/Enter", "n/Esc"] {
+            assert!(
+                bindings.iter().any(|b| b.key == binding),
+                "missing {binding}"
+            );
+        }
+    }
+}
@@ -1655,6 +1655,10 @@ impl MessageBrowserScreen {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         ));
 
         match result {
@@ -1756,6 +1760,7 @@ impl MessageBrowserScreen {
             None,
             None,
             None, // sender_token
+            None, // agent_secret
         ));
 
         match result {
@@ -12,6 +12,7 @@ pub mod attachments;
 pub mod contacts;
 pub mod dashboard;
 pub mod explorer;
+pub mod inbox;
 pub mod inspector;
 pub mod messages;
 pub mod projects;
@@ -99,6 +100,7 @@ pub enum MailScreenId {
     Attachments,
     ArchiveBrowser,
     Atc,
+    Inbox,
 }
 
 /// All screen IDs in display order.
@@ -119,6 +121,7 @@ pub const ALL_SCREEN_IDS: &[MailScreenId] = &[
     MailScreenId::Attachments,
     MailScreenId::ArchiveBrowser,
     MailScreenId::Atc,
+    MailScreenId::Inbox,
 ];
 
 /// Shifted number-row symbols used for direct jump bindings beyond screen 10.
@@ -257,6 +260,7 @@ impl MailScreenId {
             Self::Attachments => "attachments",
             Self::ArchiveBrowser => "archive_browser",
             Self::Atc => "atc",
+            Self::Inbox => "inbox",
         }
     }
 }
@@ -745,6 +749,7 @@ const ANALYTICS_HELP_MARKDOWN: &str = include_str!("analytics/help.md");
 const ATTACHMENTS_HELP_MARKDOWN: &str = include_str!("attachments/help.md");
 const ARCHIVE_BROWSER_HELP_MARKDOWN: &str = include_str!("archive_browser/help.md");
 const ATC_HELP_MARKDOWN: &str = include_str!("atc/help.md");
+const INBOX_HELP_MARKDOWN: &str = include_str!("inbox/help.md");
 
 /// Static registry of all screens with their metadata.
 pub const MAIL_SCREEN_REGISTRY: &[MailScreenMeta] = &[
@@ -876,6 +881,14 @@ pub const MAIL_SCREEN_REGISTRY: &[MailScreenMeta] = &[
         description: "Air Traffic Controller decision engine with agent liveness, conflict, and evidence ledger",
         help_markdown: ATC_HELP_MARKDOWN,
     },
+    MailScreenMeta {
+        id: MailScreenId::Inbox,
+        title: "Inbox",
+        short_label: "Inbox",
+        category: ScreenCategory::Communication,
+        description: "Cross-project deliveries with confirmed read/ack on behalf of recipients",
+        help_markdown: INBOX_HELP_MARKDOWN,
+    },
 ];
 
 /// Look up metadata for a screen ID.
@@ -1014,7 +1027,7 @@ mod tests {
     #[test]
     fn screen_count_matches() {
         assert_eq!(ALL_SCREEN_IDS.len(), MAIL_SCREEN_REGISTRY.len());
-        assert_eq!(ALL_SCREEN_IDS.len(), 16);
+        assert_eq!(ALL_SCREEN_IDS.len(), 17);
     }
 
     #[test]
@@ -1091,13 +1104,14 @@ mod tests {
             Some(MailScreenId::ArchiveBrowser)
         );
         assert_eq!(screen_from_jump_key('^'), Some(MailScreenId::Atc));
+        assert_eq!(screen_from_jump_key('&'), Some(MailScreenId::Inbox));
         assert_eq!(screen_from_jump_key(')'), None);
     }
 
     #[test]
     fn jump_key_legend_reflects_screen_count() {
         let legend = jump_key_legend();
-        assert_eq!(legend, "1-9,0,!,@,#,$,%,^");
+        assert_eq!(legend, "1-9,0,!,@,#,$,%,^,&");
     }
 
     #[test]
@@ -1382,6 +1382,8 @@ impl ReservationsScreen {
             Some(payload.ttl_seconds),
             Some(payload.exclusive),
             payload.reason,
+            None,
+            None,
         ));
 
         match result {
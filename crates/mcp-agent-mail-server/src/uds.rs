@@ -0,0 +1,789 @@
+//! Unix domain socket front for `serve-http`.
+//!
+//! When `AGENT_MAIL_UDS_PATH` (or `serve-http --uds`) is set, the server binds
+//! a second listener on that socket next to the TCP one. Connections are
+//! parsed as plain HTTP/1.1 and handed to the same `HttpState` handler, so the
+//! socket serves exactly the routes the TCP listener does.
+//!
+//! The socket file is created under a temporary name, chmod'ed to
+//! `AGENT_MAIL_UDS_MODE` and only then renamed into place, so it is never
+//! reachable with the umask's permissions. A stale socket left by a crashed
+//! server is removed; a socket that still accepts connections is not.
+//!
+//! Socket clients have no IP address, so each request carries the peer's
+//! `SO_PEERCRED` uid/pid in [`UDS_PEER_HEADER`] and rate limiting buckets
+//! them per process. At most `max_connections` clients are served at once;
+//! the rest get a 503.
+
+#![forbid(unsafe_code)]
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use asupersync::http::h1::types::{Request as Http1Request, Response as Http1Response};
+
+/// Internal header naming the socket peer as `uid:pid`. Set by the listener
+/// (any client-supplied value is dropped) and only trusted on requests
+/// without a TCP peer address.
+pub(crate) const UDS_PEER_HEADER: &str = "x-agent-mail-uds-peer";
+
+/// Default for [`UdsOptions::max_connections`].
+pub(crate) const DEFAULT_MAX_CONNECTIONS: usize = 64;
+
+/// Runs one parsed request to completion and returns its response.
+pub(crate) type UdsHandler = Arc<dyn Fn(Http1Request) -> io::Result<Http1Response> + Send + Sync>;
+
+/// Options for [`UdsListener::bind`].
+#[derive(Debug, Clone)]
+pub(crate) struct UdsOptions {
+    pub path: PathBuf,
+    pub mode: u32,
+    /// Bearer token injected into requests that carry no `Authorization`
+    /// header (`AGENT_MAIL_UDS_ALLOW_UNAUTHENTICATED`).
+    pub inject_bearer: Option<String>,
+    /// Connections served concurrently; each holds one thread.
+    pub max_connections: usize,
+}
+
+#[cfg(unix)]
+pub(crate) use imp::UdsListener;
+
+#[cfg(not(unix))]
+pub(crate) struct UdsListener;
+
+#[cfg(not(unix))]
+impl UdsListener {
+    pub(crate) fn bind(_options: UdsOptions, _handler: UdsHandler) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "unix socket listeners are not supported on this platform",
+        ))
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        Path::new("")
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::fs;
+    use std::io::{self, BufRead, BufReader, Read, Write};
+    use std::os::unix::fs::{FileTypeExt, MetadataExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    use asupersync::http::h1::types::{
+        Method as Http1Method, Request as Http1Request, Response as Http1Response,
+        Version as Http1Version, default_reason,
+    };
+
+    use super::{UDS_PEER_HEADER, UdsHandler, UdsOptions};
+
+    /// Longest request line or header line accepted.
+    const MAX_LINE_BYTES: usize = 16 * 1024;
+    /// Most headers accepted on one request.
+    const MAX_HEADERS: usize = 128;
+    /// Largest request body accepted.
+    const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+    /// Idle time before a keep-alive connection is closed.
+    const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+    /// Poll interval of the non-blocking accept loop.
+    const ACCEPT_POLL: Duration = Duration::from_millis(50);
+
+    /// A bound socket plus its accept thread. Dropping it stops accepting and
+    /// removes the socket file if it is still the one this listener created.
+    pub(crate) struct UdsListener {
+        path: PathBuf,
+        inode: u64,
+        stop: Arc<AtomicBool>,
+        accept: Option<JoinHandle<()>>,
+    }
+
+    impl UdsListener {
+        pub(crate) fn bind(options: UdsOptions, handler: UdsHandler) -> io::Result<Self> {
+            let UdsOptions {
+                path,
+                mode,
+                inject_bearer,
+                max_connections,
+            } = options;
+            clear_stale_socket(&path)?;
+            let listener = bind_with_mode(&path, mode)?;
+            listener.set_nonblocking(true)?;
+            let inode = fs::symlink_metadata(&path)?.ino();
+
+            let stop = Arc::new(AtomicBool::new(false));
+            let accept_stop = Arc::clone(&stop);
+            let inject_bearer: Option<Arc<str>> = inject_bearer.map(Arc::from);
+            let accept = std::thread::Builder::new()
+                .name("am-uds-accept".to_string())
+                .spawn(move || {
+                    accept_loop(
+                        &listener,
+                        &accept_stop,
+                        &handler,
+                        inject_bearer,
+                        max_connections,
+                    );
+                })?;
+
+            Ok(Self {
+                path,
+                inode,
+                stop,
+                accept: Some(accept),
+            })
+        }
+
+        pub(crate) fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl Drop for UdsListener {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::SeqCst);
+            if let Some(accept) = self.accept.take() {
+                let _ = accept.join();
+            }
+            let still_ours = fs::symlink_metadata(&self.path)
+                .is_ok_and(|meta| meta.file_type().is_socket() && meta.ino() == self.inode);
+            if still_ours {
+                let _ = fs::remove_file(&self.path);
+            }
+        }
+    }
+
+    /// Remove a socket file left behind by a server that is no longer running.
+    fn clear_stale_socket(path: &Path) -> io::Result<()> {
+        let meta = match fs::symlink_metadata(path) {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if !meta.file_type().is_socket() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a unix socket", path.display()),
+            ));
+        }
+        match UnixStream::connect(path) {
+            Ok(_) => Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("{} is in use by another server", path.display()),
+            )),
+            Err(_) => fs::remove_file(path),
+        }
+    }
+
+    /// Bind under a temporary name, apply `mode`, then rename into place.
+    fn bind_with_mode(path: &Path, mode: u32) -> io::Result<UnixListener> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file_name = path.file_name().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} is not a socket file path", path.display()),
+            )
+        })?;
+        let mut staging_name = file_name.to_os_string();
+        staging_name.push(format!(".{}.tmp", std::process::id()));
+        let staging = path.with_file_name(staging_name);
+        let _ = fs::remove_file(&staging);
+
+        let listener = UnixListener::bind(&staging)?;
+        let placed = fs::set_permissions(&staging, fs::Permissions::from_mode(mode))
+            .and_then(|()| fs::rename(&staging, path));
+        if let Err(err) = placed {
+            let _ = fs::remove_file(&staging);
+            return Err(err);
+        }
+        Ok(listener)
+    }
+
+    /// One of the listener's `max_connections` slots, released on drop.
+    struct ConnectionSlot(Arc<AtomicUsize>);
+
+    impl ConnectionSlot {
+        fn acquire(active: &Arc<AtomicUsize>, max: usize) -> Option<Self> {
+            active
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                    (n < max).then_some(n + 1)
+                })
+                .ok()
+                .map(|_| Self(Arc::clone(active)))
+        }
+    }
+
+    impl Drop for ConnectionSlot {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Answer a client over the connection limit with a 503 and hang up.
+    fn refuse_connection(stream: UnixStream) {
+        let mut stream = stream;
+        if stream.set_nonblocking(false).is_ok()
+            && stream.set_write_timeout(Some(ACCEPT_POLL)).is_ok()
+        {
+            let response = Http1Response::new(503, default_reason(503), Vec::new());
+            let _ = write_response(&mut stream, response, false);
+        }
+    }
+
+    /// `uid:pid` of the connected process, when the platform reports it.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn peer_identity(stream: &UnixStream) -> Option<String> {
+        use nix::sys::socket::{getsockopt, sockopt::PeerCredentials};
+
+        let creds = getsockopt(stream, PeerCredentials).ok()?;
+        Some(format!("{}:{}", creds.uid(), creds.pid()))
+    }
+
+    /// Other unix platforms fall back to the shared no-address bucket.
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn peer_identity(_stream: &UnixStream) -> Option<String> {
+        None
+    }
+
+    fn accept_loop(
+        listener: &UnixListener,
+        stop: &AtomicBool,
+        handler: &UdsHandler,
+        inject_bearer: Option<Arc<str>>,
+        max_connections: usize,
+    ) {
+        let active = Arc::new(AtomicUsize::new(0));
+        while !stop.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let Some(slot) = ConnectionSlot::acquire(&active, max_connections) else {
+                        tracing::warn!(
+                            limit = max_connections,
+                            "unix socket connection limit reached; refusing client"
+                        );
+                        refuse_connection(stream);
+                        continue;
+                    };
+                    let handler = Arc::clone(handler);
+                    let inject_bearer = inject_bearer.clone();
+                    let spawned = std::thread::Builder::new()
+                        .name("am-uds-conn".to_string())
+                        .spawn(move || {
+                            let _slot = slot;
+                            serve_connection(stream, &handler, inject_bearer.as_deref());
+                        });
+                    if let Err(err) = spawned {
+                        tracing::warn!(error = %err, "failed to spawn unix socket connection thread");
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(ACCEPT_POLL);
+                }
+                Err(err) => {
+                    tracing::warn!(error = %err, "unix socket accept failed");
+                    std::thread::sleep(ACCEPT_POLL);
+                }
+            }
+        }
+    }
+
+    fn serve_connection(stream: UnixStream, handler: &UdsHandler, inject_bearer: Option<&str>) {
+        if stream.set_nonblocking(false).is_err()
+            || stream.set_read_timeout(Some(IDLE_TIMEOUT)).is_err()
+        {
+            return;
+        }
+        let Ok(read_half) = stream.try_clone() else {
+            return;
+        };
+        let peer = peer_identity(&stream);
+        let mut reader = BufReader::new(read_half);
+        let mut writer = stream;
+        loop {
+            let parsed = match read_request(&mut reader) {
+                Ok(Some(parsed)) => parsed,
+                Ok(None) | Err(ReadError::Io(_)) => return,
+                Err(ReadError::Reject(status)) => {
+                    let response = Http1Response::new(status, default_reason(status), Vec::new());
+                    let _ = write_response(&mut writer, response, false);
+                    return;
+                }
+            };
+            let keep_alive = parsed.keep_alive;
+            let request = parsed.into_request(inject_bearer, peer.as_deref());
+            let response = handler(request).unwrap_or_else(|err| {
+                tracing::warn!(error = %err, "unix socket request failed");
+                Http1Response::new(500, default_reason(500), Vec::new())
+            });
+            if write_response(&mut writer, response, keep_alive).is_err() || !keep_alive {
+                return;
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub(super) enum ReadError {
+        Io(io::Error),
+        /// Answer with this status and close the connection.
+        Reject(u16),
+    }
+
+    impl From<io::Error> for ReadError {
+        fn from(err: io::Error) -> Self {
+            Self::Io(err)
+        }
+    }
+
+    #[derive(Debug)]
+    pub(super) struct ParsedRequest {
+        pub method: Http1Method,
+        pub uri: String,
+        pub headers: Vec<(String, String)>,
+        pub body: Vec<u8>,
+        pub keep_alive: bool,
+    }
+
+    impl ParsedRequest {
+        pub(super) fn into_request(
+            self,
+            inject_bearer: Option<&str>,
+            peer: Option<&str>,
+        ) -> Http1Request {
+            let mut headers = self.headers;
+            headers.retain(|(name, _)| !name.eq_ignore_ascii_case(UDS_PEER_HEADER));
+            if let Some(peer) = peer {
+                headers.push((UDS_PEER_HEADER.to_string(), peer.to_string()));
+            }
+            if let Some(token) = inject_bearer {
+                let has_auth = headers
+                    .iter()
+                    .any(|(name, _)| name.eq_ignore_ascii_case("authorization"));
+                if !has_auth {
+                    headers.push(("authorization".to_string(), format!("Bearer {token}")));
+                }
+            }
+            Http1Request {
+                method: self.method,
+                uri: self.uri,
+                version: Http1Version::Http11,
+                headers,
+                body: self.body,
+                trailers: Vec::new(),
+                peer_addr: None,
+            }
+        }
+    }
+
+    fn read_line<R: BufRead>(reader: &mut R) -> Result<Option<String>, ReadError> {
+        let mut buf = Vec::new();
+        let read = reader
+            .by_ref()
+            .take(MAX_LINE_BYTES as u64 + 1)
+            .read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if !buf.ends_with(b"\n") {
+            return Err(ReadError::Reject(431));
+        }
+        while buf.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+            buf.pop();
+        }
+        String::from_utf8(buf)
+            .map(Some)
+            .map_err(|_| ReadError::Reject(400))
+    }
+
+    fn parse_method(token: &str) -> Option<Http1Method> {
+        Some(match token {
+            "GET" => Http1Method::Get,
+            "POST" => Http1Method::Post,
+            "PUT" => Http1Method::Put,
+            "DELETE" => Http1Method::Delete,
+            "HEAD" => Http1Method::Head,
+            "OPTIONS" => Http1Method::Options,
+            "PATCH" => Http1Method::Patch,
+            _ => return None,
+        })
+    }
+
+    /// Read one request; `Ok(None)` means the peer closed between requests.
+    pub(super) fn read_request<R: BufRead>(
+        reader: &mut R,
+    ) -> Result<Option<ParsedRequest>, ReadError> {
+        let request_line = loop {
+            match read_line(reader)? {
+                None => return Ok(None),
+                Some(line) if line.is_empty() => {}
+                Some(line) => break line,
+            }
+        };
+        let mut parts = request_line.split_ascii_whitespace();
+        let (Some(method), Some(uri), Some(version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ReadError::Reject(400));
+        };
+        let http10 = match version {
+            "HTTP/1.1" => false,
+            "HTTP/1.0" => true,
+            _ => return Err(ReadError::Reject(505)),
+        };
+        let method = parse_method(method).ok_or(ReadError::Reject(501))?;
+        let uri = uri.to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            let line = read_line(reader)?.ok_or(ReadError::Reject(400))?;
+            if line.is_empty() {
+                break;
+            }
+            if headers.len() == MAX_HEADERS {
+                return Err(ReadError::Reject(431));
+            }
+            let (name, value) = line.split_once(':').ok_or(ReadError::Reject(400))?;
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+
+        let header = |wanted: &str| {
+            headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(wanted))
+                .map(|(_, value)| value.as_str())
+        };
+        if header("transfer-encoding").is_some() {
+            return Err(ReadError::Reject(411));
+        }
+        let content_length = match header("content-length") {
+            None => 0,
+            Some(raw) => raw.parse::<usize>().map_err(|_| ReadError::Reject(400))?,
+        };
+        if content_length > MAX_BODY_BYTES {
+            return Err(ReadError::Reject(413));
+        }
+        let keep_alive = match header("connection") {
+            Some(value) if value.eq_ignore_ascii_case("close") => false,
+            Some(value) if value.eq_ignore_ascii_case("keep-alive") => true,
+            _ => !http10,
+        };
+
+        let mut body = vec![0_u8; content_length];
+        reader.read_exact(&mut body)?;
+
+        Ok(Some(ParsedRequest {
+            method,
+            uri,
+            headers,
+            body,
+            keep_alive,
+        }))
+    }
+
+    pub(super) fn write_response<W: Write>(
+        writer: &mut W,
+        response: Http1Response,
+        keep_alive: bool,
+    ) -> io::Result<()> {
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\n",
+            response.status,
+            default_reason(response.status)
+        );
+        for (name, value) in &response.headers {
+            if name.eq_ignore_ascii_case("content-length")
+                || name.eq_ignore_ascii_case("transfer-encoding")
+                || name.eq_ignore_ascii_case("connection")
+            {
+                continue;
+            }
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str(&format!("content-length: {}\r\n", response.body.len()));
+        head.push_str(if keep_alive {
+            "connection: keep-alive\r\n\r\n"
+        } else {
+            "connection: close\r\n\r\n"
+        });
+        writer.write_all(head.as_bytes())?;
+        writer.write_all(&response.body)?;
+        writer.flush()
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::imp::{ReadError, read_request, write_response};
+    use super::*;
+    use asupersync::http::h1::types::Method as Http1Method;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use std::os::unix::net::UnixStream;
+
+    fn parse(raw: &str) -> Result<Option<super::imp::ParsedRequest>, ReadError> {
+        read_request(&mut raw.as_bytes())
+    }
+
+    fn echo_handler() -> UdsHandler {
+        Arc::new(|req: Http1Request| {
+            let auth = req
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+                .map_or_else(String::new, |(_, value)| value.clone());
+            let body = format!("{} {} {}", req.uri, req.body.len(), auth);
+            Ok(Http1Response::new(200, "OK", body.into_bytes()))
+        })
+    }
+
+    fn roundtrip(stream: &mut UnixStream, raw: &str) -> String {
+        stream.write_all(raw.as_bytes()).expect("write request");
+        let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+        let mut content_length = 0;
+        let mut status = String::new();
+        reader.read_line(&mut status).expect("status line");
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("header line");
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("content-length: ") {
+                content_length = value.parse().expect("length");
+            }
+        }
+        let mut body = vec![0_u8; content_length];
+        reader.read_exact(&mut body).expect("body");
+        format!(
+            "{} | {}",
+            status.trim_end(),
+            String::from_utf8(body).expect("utf8")
+        )
+    }
+
+    #[test]
+    fn parses_request_with_body_and_keep_alive_defaults() {
+        let parsed = parse("POST /mcp/ HTTP/1.1\r\nHost: x\r\nContent-Length: 4\r\n\r\nbody")
+            .expect("parse")
+            .expect("request");
+        assert!(matches!(parsed.method, Http1Method::Post));
+        assert_eq!(parsed.uri, "/mcp/");
+        assert_eq!(parsed.body, b"body");
+        assert!(parsed.keep_alive);
+
+        let parsed = parse("GET / HTTP/1.0\r\n\r\n")
+            .expect("parse")
+            .expect("request");
+        assert!(!parsed.keep_alive);
+        assert!(parse("").expect("eof").is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_and_unsupported_requests() {
+        let status = |raw: &str| match parse(raw) {
+            Err(ReadError::Reject(status)) => status,
+            other => panic!("expected rejection for {raw:?}, got {other:?}"),
+        };
+        assert_eq!(status("GET /\r\n\r\n"), 400);
+        assert_eq!(status("BREW / HTTP/1.1\r\n\r\n"), 501);
+        assert_eq!(status("GET / HTTP/2\r\n\r\n"), 505);
+        assert_eq!(
+            status("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n"),
+            411
+        );
+        assert_eq!(status("GET / HTTP/1.1\r\nbad header\r\n\r\n"), 400);
+    }
+
+    #[test]
+    fn injects_bearer_only_when_authorization_missing() {
+        let parsed = parse("GET / HTTP/1.1\r\n\r\n").unwrap().unwrap();
+        let req = parsed.into_request(Some("secret"), None);
+        assert!(
+            req.headers
+                .iter()
+                .any(|(n, v)| n == "authorization" && v == "Bearer secret")
+        );
+
+        let parsed = parse("GET / HTTP/1.1\r\nAuthorization: Bearer other\r\n\r\n")
+            .unwrap()
+            .unwrap();
+        let req = parsed.into_request(Some("secret"), None);
+        let auths: Vec<_> = req
+            .headers
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case("authorization"))
+            .collect();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].1, "Bearer other");
+    }
+
+    #[test]
+    fn write_response_replaces_framing_headers() {
+        let mut response = Http1Response::new(200, "OK", b"hi".to_vec());
+        response
+            .headers
+            .push(("transfer-encoding".to_string(), "chunked".to_string()));
+        response
+            .headers
+            .push(("content-type".to_string(), "text/plain".to_string()));
+        let mut out = Vec::new();
+        write_response(&mut out, response, false).expect("write");
+        let text = String::from_utf8(out).expect("utf8");
+        assert!(text.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(text.contains("content-type: text/plain\r\n"));
+        assert!(text.contains("content-length: 2\r\n"));
+        assert!(text.contains("connection: close\r\n"));
+        assert!(!text.contains("chunked"));
+        assert!(text.ends_with("\r\n\r\nhi"));
+    }
+
+    #[test]
+    fn listener_serves_keep_alive_requests_and_cleans_up() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("am.sock");
+        let listener = UdsListener::bind(
+            UdsOptions {
+                path: path.clone(),
+                mode: 0o600,
+                inject_bearer: Some("tok".to_string()),
+                max_connections: DEFAULT_MAX_CONNECTIONS,
+            },
+            echo_handler(),
+        )
+        .expect("bind");
+        let meta = std::fs::symlink_metadata(&path).expect("socket exists");
+        assert!(meta.file_type().is_socket());
+        assert_eq!(meta.permissions().mode() & 0o777, 0o600);
+
+        let mut stream = UnixStream::connect(&path).expect("connect");
+        assert_eq!(
+            roundtrip(
+                &mut stream,
+                "POST /mcp/ HTTP/1.1\r\nContent-Length: 3\r\n\r\nabc"
+            ),
+            "HTTP/1.1 200 OK | /mcp/ 3 Bearer tok"
+        );
+        assert_eq!(
+            roundtrip(&mut stream, "GET /health HTTP/1.1\r\n\r\n"),
+            "HTTP/1.1 200 OK | /health 0 Bearer tok"
+        );
+
+        drop(stream);
+        drop(listener);
+        assert!(!path.exists(), "socket file removed on shutdown");
+    }
+
+    #[test]
+    fn bind_replaces_stale_socket_but_not_live_one_or_regular_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("am.sock");
+        let options = || UdsOptions {
+            path: path.clone(),
+            mode: 0o660,
+            inject_bearer: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        };
+
+        // A socket whose listener is gone is stale.
+        drop(std::os::unix::net::UnixListener::bind(&path).expect("stale bind"));
+        assert!(path.exists());
+        let live = UdsListener::bind(options(), echo_handler()).expect("replace stale");
+        assert_eq!(live.path(), path.as_path());
+
+        let err = UdsListener::bind(options(), echo_handler())
+            .err()
+            .expect("live socket must not be replaced");
+        assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+        drop(live);
+
+        std::fs::write(&path, b"not a socket").expect("write file");
+        let err = UdsListener::bind(options(), echo_handler())
+            .err()
+            .expect("regular file must not be replaced");
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn peer_header_is_set_by_the_listener_not_the_client() {
+        let parsed = parse("GET / HTTP/1.1\r\nX-Agent-Mail-Uds-Peer: 0:1\r\n\r\n")
+            .unwrap()
+            .unwrap();
+        let req = parsed.into_request(None, Some("1000:42"));
+        let peers: Vec<_> = req
+            .headers
+            .iter()
+            .filter(|(n, _)| n.eq_ignore_ascii_case(UDS_PEER_HEADER))
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(peers, ["1000:42"]);
+    }
+
+    #[test]
+    fn listener_refuses_clients_over_the_connection_limit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("am.sock");
+        let peer_handler: UdsHandler = Arc::new(|req: Http1Request| {
+            let peer = req
+                .headers
+                .iter()
+                .find(|(name, _)| name == UDS_PEER_HEADER)
+                .map_or_else(String::new, |(_, value)| value.clone());
+            Ok(Http1Response::new(200, "OK", peer.into_bytes()))
+        });
+        let _listener = UdsListener::bind(
+            UdsOptions {
+                path: path.clone(),
+                mode: 0o600,
+                inject_bearer: None,
+                max_connections: 1,
+            },
+            peer_handler,
+        )
+        .expect("bind");
+
+        let mut first = UnixStream::connect(&path).expect("connect");
+        let served = roundtrip(&mut first, "GET / HTTP/1.1\r\n\r\n");
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            served,
+            format!(
+                "HTTP/1.1 200 OK | {}:{}",
+                nix::unistd::getuid(),
+                std::process::id()
+            )
+        );
+        assert!(served.starts_with("HTTP/1.1 200 OK"), "{served}");
+
+        // Read without writing: the refusal may close before a request lands.
+        let second = UnixStream::connect(&path).expect("connect");
+        let mut status = String::new();
+        BufReader::new(second)
+            .read_line(&mut status)
+            .expect("status line");
+        assert!(status.starts_with("HTTP/1.1 503"), "{status}");
+
+        // The slot frees once the first client hangs up.
+        drop(first);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        loop {
+            let mut next = UnixStream::connect(&path).expect("connect");
+            let _ = next.write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n");
+            let mut status = String::new();
+            let _ = BufReader::new(next).read_line(&mut status);
+            if status.starts_with("HTTP/1.1 200") {
+                break;
+            }
+            assert!(std::time::Instant::now() < deadline, "{status}");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+}
@@ -0,0 +1,282 @@
+//! End-to-end check that a downstream crate can register a custom MCP tool
+//! through `ToolRegistry` and call it over HTTP next to the built-in tools.
+
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_core::config::with_process_env_overrides_for_test;
+use mcp_agent_mail_server::run_http_with_tui_and_tools;
+use mcp_agent_mail_server::tool_registry::{CustomTool, ShedClass, ToolRegistry};
+use serde_json::{Value, json};
+
+const SERVER_READY_TIMEOUT: Duration = Duration::from_secs(20);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+fn free_loopback_port() -> u16 {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).expect("bind ephemeral loopback port");
+    let port = listener
+        .local_addr()
+        .expect("ephemeral port local_addr")
+        .port();
+    drop(listener);
+    port
+}
+
+fn http_request(
+    port: u16,
+    method: &str,
+    path: &str,
+    body: Option<&[u8]>,
+) -> std::io::Result<(u16, Vec<u8>)> {
+    let body = body.unwrap_or(&[]);
+    let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut request =
+        format!("{method} {path} HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n");
+    if !body.is_empty() {
+        request.push_str("Content-Type: application/json\r\n");
+    }
+    let _ = write!(request, "Content-Length: {}\r\n\r\n", body.len());
+
+    stream.write_all(request.as_bytes())?;
+    if !body.is_empty() {
+        stream.write_all(body)?;
+    }
+    stream.flush()?;
+    let _ = stream.shutdown(Shutdown::Write);
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .expect("HTTP response header terminator");
+    let header = std::str::from_utf8(&response[..header_end]).expect("utf8 response header");
+    let status = header
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .expect("HTTP status code")
+        .parse::<u16>()
+        .expect("numeric HTTP status code");
+    Ok((status, response[(header_end + 4)..].to_vec()))
+}
+
+fn wait_for_readiness(port: u16, server_result_rx: &Receiver<std::io::Result<()>>) {
+    let deadline = Instant::now() + SERVER_READY_TIMEOUT;
+    loop {
+        if let Ok(result) = server_result_rx.try_recv() {
+            panic!("server exited before readiness probe completed: {result:?}");
+        }
+        if let Ok((status, body)) = http_request(port, "GET", "/health/readiness", None)
+            && status == 200
+        {
+            let value: Value = serde_json::from_slice(&body).expect("readiness JSON");
+            if value.get("status").and_then(Value::as_str) == Some("ready") {
+                return;
+            }
+        }
+        assert!(
+            Instant::now() < deadline,
+            "server never became ready on port {port}"
+        );
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn rpc(port: u16, id: u64, method: &str, params: Value) -> Value {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": params,
+    });
+    let request_body = serde_json::to_vec(&request).expect("serialize JSON-RPC request");
+    let (status, body) =
+        http_request(port, "POST", "/mcp/", Some(&request_body)).expect("POST /mcp/");
+    assert_eq!(status, 200, "{method} should return HTTP 200");
+    let response: Value = serde_json::from_slice(&body).expect("JSON-RPC response");
+    assert!(
+        response.get("error").is_none(),
+        "{method} returned JSON-RPC error: {response:#}"
+    );
+    response
+}
+
+fn tool_payload(port: u16, id: u64, tool_name: &str, arguments: Value) -> Value {
+    let response = rpc(
+        port,
+        id,
+        "tools/call",
+        json!({ "name": tool_name, "arguments": arguments }),
+    );
+    let result = response.get("result").expect("tool result object");
+    assert!(
+        !result
+            .get("isError")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        "{tool_name} returned MCP error payload: {result:#}"
+    );
+    let text = result
+        .get("content")
+        .and_then(Value::as_array)
+        .and_then(|content| content.first())
+        .and_then(|block| block.get("text"))
+        .and_then(Value::as_str)
+        .expect("text content item");
+    serde_json::from_str(text).expect("tool JSON payload")
+}
+
+fn resource_payload(port: u16, id: u64, uri: &str) -> Value {
+    let response = rpc(port, id, "resources/read", json!({ "uri": uri }));
+    let text = response
+        .get("result")
+        .and_then(|result| result.get("contents"))
+        .and_then(Value::as_array)
+        .and_then(|contents| contents.first())
+        .and_then(|block| block.get("text"))
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("{uri} text content: {response:#}"));
+    serde_json::from_str(text).expect("resource JSON payload")
+}
+
+fn ticket_lookup() -> CustomTool {
+    CustomTool::new("ticket_lookup", "tickets", |ctx, args| {
+        Box::pin(async move {
+            let project = ctx.resolve_project().await?;
+            Ok(json!({
+                "project_slug": project.slug,
+                "ticket_id": args.get("ticket_id").cloned().unwrap_or(Value::Null),
+                "calling_agent": ctx.calling_agent(),
+            }))
+        })
+    })
+    .description("Look up a ticket in the product issue tracker.")
+    .input_schema(json!({
+        "type": "object",
+        "properties": {
+            "project_key": { "type": "string" },
+            "agent_name": { "type": "string" },
+            "ticket_id": { "type": "string" }
+        },
+        "required": ["project_key", "ticket_id"]
+    }))
+    .capabilities(["tickets"])
+    .complexity("low")
+    .shed_class(ShedClass::Shedable)
+}
+
+#[test]
+fn custom_tool_is_served_with_builtins_and_listed_in_tooling_resources() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let storage_root = temp.path().join("storage-root");
+    let project_root = temp.path().join("project-root");
+    std::fs::create_dir_all(&storage_root).expect("create storage root");
+    std::fs::create_dir_all(&project_root).expect("create project root");
+
+    let port = free_loopback_port();
+    let database_url = format!(
+        "sqlite:///{}",
+        temp.path().join("custom_tools.sqlite3").display()
+    );
+    let storage_root_str = storage_root.display().to_string();
+    let project_key = project_root.display().to_string();
+    let port_str = port.to_string();
+
+    with_process_env_overrides_for_test(
+        &[
+            ("DATABASE_URL", database_url.as_str()),
+            ("STORAGE_ROOT", storage_root_str.as_str()),
+            ("HTTP_HOST", "127.0.0.1"),
+            ("HTTP_PORT", port_str.as_str()),
+            ("HTTP_PATH", "/mcp/"),
+            ("TUI_ENABLED", "false"),
+            ("HTTP_RATE_LIMIT_ENABLED", "false"),
+            ("HTTP_RBAC_ENABLED", "false"),
+            ("HTTP_ALLOW_LOCALHOST_UNAUTHENTICATED", "true"),
+            ("AM_ALLOW_EPHEMERAL_PROJECT_ROOTS", "1"),
+        ],
+        || {
+            Config::reset_cached();
+
+            let registry = ToolRegistry::with_builtins()
+                .register(ticket_lookup())
+                .expect("register ticket_lookup");
+            let server_config = Config::from_env();
+            let (server_result_tx, server_result_rx) = mpsc::channel();
+            thread::spawn(move || {
+                let result = run_http_with_tui_and_tools(&server_config, registry);
+                let _ = server_result_tx.send(result);
+            });
+            wait_for_readiness(port, &server_result_rx);
+
+            let listed = rpc(port, 1, "tools/list", json!({}));
+            let tool_names: Vec<&str> = listed["result"]["tools"]
+                .as_array()
+                .expect("tools array")
+                .iter()
+                .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+                .collect();
+            assert!(tool_names.contains(&"ticket_lookup"), "{tool_names:?}");
+            assert!(tool_names.contains(&"send_message"), "{tool_names:?}");
+
+            let project = tool_payload(
+                port,
+                2,
+                "ensure_project",
+                json!({ "human_key": project_key }),
+            );
+            let slug = project["slug"].as_str().expect("project slug").to_string();
+
+            let ticket = tool_payload(
+                port,
+                3,
+                "ticket_lookup",
+                json!({
+                    "project_key": project_key,
+                    "agent_name": "BlueLake",
+                    "ticket_id": "OPS-42"
+                }),
+            );
+            assert_eq!(ticket["project_slug"], slug.as_str());
+            assert_eq!(ticket["ticket_id"], "OPS-42");
+            assert_eq!(ticket["calling_agent"], "BlueLake");
+
+            let directory = resource_payload(port, 4, "resource://tooling/directory");
+            let cluster = directory["clusters"]
+                .as_array()
+                .expect("directory clusters")
+                .iter()
+                .find(|cluster| cluster["name"] == "tickets")
+                .expect("tickets cluster in directory");
+            assert_eq!(cluster["tools"][0]["name"], "ticket_lookup");
+
+            let schemas = resource_payload(port, 5, "resource://tooling/schemas");
+            assert_eq!(
+                schemas["tools"]["ticket_lookup"]["required"],
+                json!(["project_key", "ticket_id"])
+            );
+
+            let metrics = resource_payload(port, 6, "resource://tooling/metrics");
+            let entry = metrics["tools"]
+                .as_array()
+                .expect("metrics tools")
+                .iter()
+                .find(|tool| tool["name"] == "ticket_lookup")
+                .expect("ticket_lookup metrics entry");
+            assert_eq!(entry["cluster"], "tickets");
+            assert!(entry["calls"].as_u64().unwrap_or(0) >= 1, "{entry:#}");
+            assert!(mcp_agent_mail_core::is_shedable_tool("ticket_lookup"));
+        },
+    );
+}
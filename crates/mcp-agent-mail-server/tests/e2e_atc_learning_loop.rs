@@ -379,6 +379,10 @@ fn format_direct_send_message_diagnostic(
         None,
         None,
         None,
+        None,
+        None,
+        None,
+        None,
     )) {
         Ok(payload) => format!("direct send_message unexpectedly succeeded: {payload}"),
         Err(error) => {
@@ -9,7 +9,7 @@
 //!   - Default port 8765, default host 127.0.0.1
 //!   - Default MCP path `/mcp/` across CLI, setup, and serve
 //!   - Path aliasing: `/api/*` ↔ `/mcp/*` interchangeable
-//!   - Health endpoints bypass auth: `/health/liveness`, `/health/readiness`, `/healthz`
+//!   - Health endpoints bypass auth: `/health/liveness`, `/health/readiness`, `/healthz`, `/readyz`
 //!   - OAuth well-known at `/.well-known/oauth-authorization-server`
 //!   - Bearer token auth with exact header match
 //!   - Localhost unauthenticated bypass (default: disabled unless explicitly configured)
@@ -321,12 +321,14 @@ fn compat_health_bypass_paths_are_fixed() {
         "/health/liveness",
         "/health/readiness",
         "/healthz",
+        "/readyz",
         "/health",
     ];
     // Verify they all start with /health (the bypass prefix check in handle_inner).
     for path in &bypass_paths {
         assert!(
             path == &"/healthz"
+                || path == &"/readyz"
                 || path.starts_with("/health")
                     && (path.len() == 7 || path.as_bytes().get(7) == Some(&b'/')),
             "COMPAT LOCK: Health bypass path '{path}' must match the bypass prefix check"
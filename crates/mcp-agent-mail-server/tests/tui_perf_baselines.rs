@@ -36,9 +36,10 @@ use mcp_agent_mail_server::tui_screens::{
     ALL_SCREEN_IDS, MailScreen, MailScreenId, agents::AgentsScreen, analytics::AnalyticsScreen,
     archive_browser::ArchiveBrowserScreen, atc::AtcScreen, attachments::AttachmentExplorerScreen,
     contacts::ContactsScreen, dashboard::DashboardScreen, explorer::MailExplorerScreen,
-    messages::MessageBrowserScreen, projects::ProjectsScreen, reservations::ReservationsScreen,
-    search::SearchCockpitScreen, system_health::SystemHealthScreen, threads::ThreadExplorerScreen,
-    timeline::TimelineScreen, tool_metrics::ToolMetricsScreen,
+    inbox::InboxScreen, messages::MessageBrowserScreen, projects::ProjectsScreen,
+    reservations::ReservationsScreen, search::SearchCockpitScreen,
+    system_health::SystemHealthScreen, threads::ThreadExplorerScreen, timeline::TimelineScreen,
+    tool_metrics::ToolMetricsScreen,
 };
 use mcp_agent_mail_server::tui_theme;
 
@@ -380,6 +381,7 @@ const fn screen_name(id: MailScreenId) -> &'static str {
         MailScreenId::Attachments => "Attachments",
         MailScreenId::ArchiveBrowser => "ArchiveBrowser",
         MailScreenId::Atc => "Atc",
+        MailScreenId::Inbox => "Inbox",
     }
 }
 
@@ -401,6 +403,7 @@ fn new_screen(id: MailScreenId, state: &Arc<TuiSharedState>) -> Box<dyn MailScre
         MailScreenId::Attachments => Box::new(AttachmentExplorerScreen::new()),
         MailScreenId::ArchiveBrowser => Box::new(ArchiveBrowserScreen::new()),
         MailScreenId::Atc => Box::new(AtcScreen::new()),
+        MailScreenId::Inbox => Box::new(InboxScreen::new()),
     }
 }
 
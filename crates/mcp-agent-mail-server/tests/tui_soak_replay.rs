@@ -929,5 +929,6 @@ const fn screen_name(id: MailScreenId) -> &'static str {
         MailScreenId::Attachments => "Attachments",
         MailScreenId::ArchiveBrowser => "ArchiveBrowser",
         MailScreenId::Atc => "Atc",
+        MailScreenId::Inbox => "Inbox",
     }
 }
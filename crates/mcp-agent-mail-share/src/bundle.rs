@@ -4,7 +4,7 @@
 
 use std::collections::HashMap;
 use std::io::Read;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 
 use include_dir::{Dir, include_dir};
 use mcp_agent_mail_db::DbConn;
@@ -14,7 +14,7 @@ use sha2::{Digest, Sha256};
 use sqlmodel_core::Value as SqlValue;
 
 use crate::hosting::{self, HostingHint};
-use crate::scope::ProjectScopeResult;
+use crate::scope::{MessageFilterResult, ProjectScopeResult};
 use crate::scrub::ScrubSummary;
 use crate::{ShareError, ShareResult};
 
@@ -223,6 +223,14 @@ pub fn bundle_attachments(
                     .and_then(|v| v.as_str())
                     .unwrap_or("application/octet-stream")
                     .to_string();
+                // Content-addressed blobs are stored as `.../<sha256>/blob`, so
+                // the bundle copy takes its extension from the attachment name.
+                let name_ext = obj
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .and_then(|name| Path::new(name).extension())
+                    .and_then(|e| e.to_str())
+                    .map(str::to_string);
 
                 let process_result = (|| -> std::io::Result<()> {
                     let source_file =
@@ -309,7 +317,11 @@ pub fn bundle_attachments(
                             // Deduplicate: reuse existing path
                             existing.clone()
                         } else {
-                            let ext = source.extension().and_then(|e| e.to_str()).unwrap_or("bin");
+                            let ext = source
+                                .extension()
+                                .and_then(|e| e.to_str())
+                                .or(name_ext.as_deref())
+                                .unwrap_or("bin");
                             let subdir = &sha[..2.min(sha.len())];
                             let rel = format!("attachments/{subdir}/{sha}.{ext}");
                             copy_file_into_output(output_dir, &rel, source)?;
@@ -495,9 +507,10 @@ pub fn write_bundle_scaffolding(
     db_size_bytes: u64,
     viewer_data: Option<&ViewerDataManifest>,
     viewer_sri: &HashMap<String, String>,
+    message_filter: Option<&MessageFilterResult>,
 ) -> ShareResult<()> {
     // manifest.json (sorted keys for determinism — matches Python `sort_keys=True`)
-    let manifest = build_manifest(
+    let mut manifest = build_manifest(
         scope,
         scrub_summary,
         attachment_manifest,
@@ -512,12 +525,18 @@ pub fn write_bundle_scaffolding(
         viewer_data,
         viewer_sri,
     );
+    if let Some(filter) = message_filter {
+        record_message_filter(&mut manifest, filter);
+    }
     let sorted = sort_json_keys(&manifest);
     let manifest_json = crate::encode_json_pretty(&sorted, "bundle manifest serialization failed")?;
     write_output_bytes(output_dir, "manifest.json", manifest_json.as_bytes())?;
 
     // README.md
-    let readme = generate_readme(scope, scrub_summary);
+    let mut readme = generate_readme(scope, scrub_summary);
+    if let Some(filter) = message_filter {
+        readme.push_str(&describe_message_filter(filter));
+    }
     write_output_bytes(output_dir, "README.md", readme.as_bytes())?;
 
     // HOW_TO_DEPLOY.md
@@ -671,6 +690,7 @@ pub fn export_bundle_from_snapshot_context(
             db_size_bytes,
             Some(&viewer_data),
             &viewer_sri,
+            context.message_filter.as_ref(),
         )?;
 
         Ok(BundleExportResult {
@@ -1159,14 +1179,32 @@ fn rollback_published_bundle_outputs(
     }
 }
 
+/// Archive root a relative attachment path resolves against: paths under
+/// `projects/<slug>/` follow the project if it was relocated.
+fn attachment_archive_root(storage_root: &Path, path: &str) -> PathBuf {
+    let mut components = Path::new(path).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(first)), Some(Component::Normal(slug))) if first == "projects" => {
+            slug.to_str().map_or_else(
+                || storage_root.to_path_buf(),
+                |slug| {
+                    mcp_agent_mail_core::archive_location::project_archive_root(storage_root, slug)
+                },
+            )
+        }
+        _ => storage_root.to_path_buf(),
+    }
+}
+
 fn resolve_attachment_path(
     storage_root: &Path,
     path: &str,
     allow_absolute_paths: bool,
 ) -> std::io::Result<Option<PathBuf>> {
-    let root = match storage_root.canonicalize() {
+    let archive_root = attachment_archive_root(storage_root, path);
+    let root = match archive_root.canonicalize() {
         Ok(root) => root,
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => storage_root.to_path_buf(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => archive_root,
         Err(error) => return Err(error),
     };
     let path_path = Path::new(path);
@@ -1563,6 +1601,63 @@ fn build_manifest(
     })
 }
 
+/// Record an applied message filter in the manifest.
+///
+/// `message_filter` documents what was kept and why (including thread roots
+/// retained for context), while the bounds are mirrored into
+/// `export_config` so `share update` re-applies them.
+fn record_message_filter(manifest: &mut Value, filter: &MessageFilterResult) {
+    let Some(root) = manifest.as_object_mut() else {
+        return;
+    };
+    root.insert(
+        "message_filter".to_string(),
+        serde_json::json!({
+            "since": filter.filter.since,
+            "until": filter.filter.until,
+            "threads": filter.filter.threads,
+            "matched_messages": filter.matched_count,
+            "removed_messages": filter.removed_count,
+            "context_parents": {
+                "policy": crate::CONTEXT_PARENT_POLICY,
+                "description": "Replies kept by the filter also keep their thread root \
+                    message, even when the root falls outside the date range.",
+                "message_ids": filter.context_parent_ids,
+            },
+        }),
+    );
+    if let Some(export_config) = root.get_mut("export_config").and_then(Value::as_object_mut) {
+        export_config.insert("since".to_string(), serde_json::json!(filter.filter.since));
+        export_config.insert("until".to_string(), serde_json::json!(filter.filter.until));
+        export_config.insert(
+            "threads".to_string(),
+            serde_json::json!(filter.filter.threads),
+        );
+    }
+}
+
+fn describe_message_filter(filter: &MessageFilterResult) -> String {
+    let mut section = String::from("\n## Message Filter\n\n");
+    if let Some(ref since) = filter.filter.since {
+        section.push_str(&format!("- Since: {since}\n"));
+    }
+    if let Some(ref until) = filter.filter.until {
+        section.push_str(&format!("- Until: {until}\n"));
+    }
+    if !filter.filter.threads.is_empty() {
+        section.push_str(&format!(
+            "- Threads: {}\n",
+            filter.filter.threads.join(", ")
+        ));
+    }
+    section.push_str(&format!(
+        "- Messages kept: {} ({} thread roots kept for context)\n",
+        filter.matched_count + filter.context_parent_ids.len(),
+        filter.context_parent_ids.len()
+    ));
+    section
+}
+
 /// Recursively sort all object keys in a JSON value for deterministic serialization.
 ///
 /// Matches legacy Python's `json.dumps(sort_keys=True)` behavior.
@@ -2242,6 +2337,41 @@ mod tests {
         assert_eq!(paths[0], paths[1], "duplicate files should share same path");
     }
 
+    #[test]
+    fn bundle_follows_content_addressed_blob_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = dir.path().join("storage");
+        let sha = "ab".repeat(32);
+        let blob_rel = format!("projects/demo/attachments/ab/{}/blob", &sha[2..]);
+        std::fs::create_dir_all(storage.join(&blob_rel).parent().unwrap()).unwrap();
+        std::fs::write(storage.join(&blob_rel), vec![0x5Au8; 100 * 1024]).unwrap();
+
+        let att_json = format!(
+            r#"[{{"type":"file","name":"build.log","path":"{blob_rel}","sha256":"{sha}","media_type":"text/plain"}}]"#
+        );
+        let db = create_bundle_test_db(dir.path(), &[att_json.as_str(), att_json.as_str()]);
+        let output = dir.path().join("bundle");
+        std::fs::create_dir_all(&output).unwrap();
+
+        let result = bundle_attachments(
+            &db,
+            &output,
+            &storage,
+            crate::INLINE_ATTACHMENT_THRESHOLD,
+            crate::DETACH_ATTACHMENT_THRESHOLD,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(result.stats.copied, 2);
+        assert_eq!(result.stats.missing, 0);
+        assert_eq!(result.stats.bytes_copied, 100 * 1024);
+        let bundle_path = result.items[0].bundle_path.as_deref().unwrap();
+        assert!(bundle_path.ends_with(".log"), "{bundle_path}");
+        assert_eq!(result.items[1].bundle_path.as_deref(), Some(bundle_path));
+        assert!(output.join(bundle_path).is_file());
+    }
+
     #[test]
     fn bundle_inline_small_file() {
         let dir = tempfile::tempdir().unwrap();
@@ -3359,6 +3489,7 @@ mod tests {
                 removed_count: 0,
                 remaining: test_remaining_counts(),
             },
+            message_filter: None,
             scrub_summary: test_scrub_summary(),
             fts_enabled: false,
         };
@@ -3410,6 +3541,7 @@ mod tests {
                 removed_count: 0,
                 remaining: test_remaining_counts(),
             },
+            message_filter: None,
             scrub_summary: test_scrub_summary(),
             fts_enabled: false,
         };
@@ -4278,6 +4410,43 @@ mod tests {
         );
     }
 
+    /// An applied message filter is documented in the manifest and round-trips
+    /// through `export_config` so `share update` can re-apply it.
+    #[test]
+    fn message_filter_recorded_in_manifest_and_export_config() {
+        let mut manifest = serde_json::json!({
+            "export_config": {"projects": [], "scrub_preset": "standard"},
+        });
+        let filtered = MessageFilterResult {
+            filter: crate::MessageFilter {
+                since: Some("2026-01-05T00:00:00Z".to_string()),
+                until: None,
+                threads: vec!["1".to_string()],
+            },
+            matched_count: 1,
+            context_parent_ids: vec![1],
+            removed_count: 1,
+            remaining: test_remaining_counts(),
+        };
+        record_message_filter(&mut manifest, &filtered);
+
+        let section = &manifest["message_filter"];
+        assert_eq!(section["since"], "2026-01-05T00:00:00Z");
+        assert!(section["until"].is_null());
+        assert_eq!(
+            section["context_parents"]["policy"],
+            crate::CONTEXT_PARENT_POLICY
+        );
+        assert_eq!(section["context_parents"]["message_ids"][0], 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = dir.path().join("bundle");
+        std::fs::create_dir_all(&bundle).unwrap();
+        std::fs::write(bundle.join("manifest.json"), manifest.to_string()).unwrap();
+        let config = crate::load_bundle_export_config(&bundle).unwrap();
+        assert_eq!(config.message_filter, filtered.filter);
+    }
+
     /// Manifest that is a JSON array instead of an object is rejected.
     #[test]
     fn corrupt_manifest_json_array_instead_of_object() {
@@ -46,9 +46,15 @@ pub use finalize::{
 pub use hosting::{HostingHint, detect_hosting_hints, generate_headers_file};
 pub use planner::{PlanResult, format_plan_human, generate_plan, validate_inputs};
 pub use prompt::{WizardConfig, WizardOutcome, format_json_output, run_interactive_wizard};
-pub use scope::{ProjectRecord, ProjectScopeResult, RemainingCounts, apply_project_scope};
+pub use scope::{
+    CONTEXT_PARENT_POLICY, MessageFilter, MessageFilterResult, ProjectRecord, ProjectScopeResult,
+    RemainingCounts, apply_message_filter, apply_project_scope,
+};
 pub use scrub::{ScrubSummary, scan_for_secrets, scrub_snapshot};
-pub use snapshot::{SnapshotContext, create_snapshot_context, create_sqlite_snapshot};
+pub use snapshot::{
+    SnapshotContext, create_filtered_snapshot_context, create_snapshot_context,
+    create_snapshot_context_with, create_sqlite_snapshot,
+};
 pub use static_render::{
     SearchIndexEntry, SitemapEntry, StaticRenderConfig, StaticRenderResult, render_static_site,
 };
@@ -472,6 +478,8 @@ pub struct StoredExportConfig {
     pub chunk_threshold: i64,
     pub chunk_size: i64,
     pub scrub_preset: String,
+    /// Date/thread filter from `export_config`; empty for unfiltered bundles.
+    pub message_filter: MessageFilter,
 }
 
 fn parse_int_field(value: &Value, field: &'static str) -> ShareResult<i64> {
@@ -515,6 +523,14 @@ fn get_str_list(value: Option<&Value>) -> Vec<String> {
         .collect()
 }
 
+fn get_optional_str(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
 /// Load export configuration defaults from an existing bundle.
 pub fn load_bundle_export_config(bundle_dir: &Path) -> ShareResult<StoredExportConfig> {
     let manifest = load_bundle_manifest_json(bundle_dir)?;
@@ -539,6 +555,11 @@ pub fn load_bundle_export_config(bundle_dir: &Path) -> ShareResult<StoredExportC
         .and_then(|v| v.get("projects"))
         .or_else(|| project_scope.and_then(|v| v.get("requested")));
     let projects = get_str_list(raw_projects);
+    let message_filter = MessageFilter {
+        since: get_optional_str(export_config.and_then(|v| v.get("since"))),
+        until: get_optional_str(export_config.and_then(|v| v.get("until"))),
+        threads: get_str_list(export_config.and_then(|v| v.get("threads"))),
+    };
 
     let scrub_preset = export_config
         .and_then(|v| v.get("scrub_preset"))
@@ -634,6 +655,7 @@ pub fn load_bundle_export_config(bundle_dir: &Path) -> ShareResult<StoredExportC
                 chunk_threshold: threshold,
                 chunk_size,
                 scrub_preset,
+                message_filter,
             });
         }
         Ok(_) => {
@@ -652,6 +674,7 @@ pub fn load_bundle_export_config(bundle_dir: &Path) -> ShareResult<StoredExportC
         chunk_threshold,
         chunk_size,
         scrub_preset,
+        message_filter,
     })
 }
 
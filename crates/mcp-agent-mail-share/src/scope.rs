@@ -2,6 +2,8 @@
 //!
 //! Given a snapshot database and a list of project identifiers (slugs or
 //! human_keys), removes all data belonging to non-selected projects.
+//! [`apply_message_filter`] then optionally narrows the kept messages to a
+//! date range and/or a set of threads.
 
 use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
@@ -333,6 +335,247 @@ pub fn apply_project_scope(
     }
 }
 
+/// Message-level filters applied on top of project scoping.
+///
+/// Bounds are ISO-8601 timestamps compared against `messages.created_ts`
+/// (both inclusive). Thread identifiers match either `messages.thread_id` or
+/// the id of a thread's root message, so `--thread 42` selects message 42 and
+/// every reply threaded under it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageFilter {
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub threads: Vec<String>,
+}
+
+impl MessageFilter {
+    /// True when no filter is set and every scoped message is kept.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.since.is_none() && self.until.is_none() && self.threads.is_empty()
+    }
+
+    /// Parse the date bounds into microseconds since the epoch.
+    ///
+    /// # Errors
+    ///
+    /// [`ShareError::Validation`] if a bound is not ISO-8601 or `since` is
+    /// after `until`.
+    pub fn bounds_micros(&self) -> Result<(Option<i64>, Option<i64>), ShareError> {
+        let parse = |flag: &str, value: &Option<String>| -> Result<Option<i64>, ShareError> {
+            value
+                .as_deref()
+                .map(|raw| {
+                    mcp_agent_mail_db::iso_to_micros(raw.trim()).ok_or_else(|| {
+                        ShareError::Validation {
+                            message: format!(
+                                "{flag} must be an ISO-8601 timestamp (e.g. 2026-01-31T00:00:00Z), got {raw:?}"
+                            ),
+                        }
+                    })
+                })
+                .transpose()
+        };
+        let since = parse("--since", &self.since)?;
+        let until = parse("--until", &self.until)?;
+        if let (Some(since), Some(until)) = (since, until)
+            && since > until
+        {
+            return Err(ShareError::Validation {
+                message: "--since must not be later than --until".to_string(),
+            });
+        }
+        Ok((since, until))
+    }
+
+    fn wanted_threads(&self) -> BTreeSet<&str> {
+        self.threads
+            .iter()
+            .map(|thread| thread.trim())
+            .filter(|thread| !thread.is_empty())
+            .collect()
+    }
+}
+
+/// How replies whose thread root falls outside a [`MessageFilter`] are
+/// handled. Recorded in the bundle manifest so readers know why a message
+/// outside the requested range may be present.
+pub const CONTEXT_PARENT_POLICY: &str = "include_thread_root";
+
+/// Result of applying a [`MessageFilter`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageFilterResult {
+    /// The filter that was applied (echoed back).
+    pub filter: MessageFilter,
+    /// Messages that matched the filter directly.
+    pub matched_count: usize,
+    /// Thread roots kept only as context for matched replies.
+    pub context_parent_ids: Vec<i64>,
+    /// How many scoped messages were removed.
+    pub removed_count: usize,
+    /// Remaining row counts per table after filtering.
+    pub remaining: RemainingCounts,
+}
+
+/// Remove messages that fall outside `filter` from a scoped snapshot.
+///
+/// A message is kept when it lies within the date bounds and, if threads are
+/// given, belongs to one of them. When a kept reply's thread root (the message
+/// whose id equals the reply's numeric `thread_id`) was filtered out, the root
+/// is kept as well and reported in `context_parent_ids`
+/// ([`CONTEXT_PARENT_POLICY`]). Agents are never removed, so senders and
+/// recipients of kept messages keep their profiles.
+///
+/// # Errors
+///
+/// - [`ShareError::Validation`] if the date bounds are invalid.
+/// - [`ShareError::Sqlite`] on any SQLite error.
+pub fn apply_message_filter(
+    snapshot_path: &Path,
+    filter: &MessageFilter,
+) -> Result<MessageFilterResult, ShareError> {
+    let (since, until) = filter.bounds_micros()?;
+    let snapshot_path = crate::require_real_share_sqlite_path(snapshot_path)?;
+    let path_str = snapshot_path.display().to_string();
+    let conn = Conn::open_file(&path_str).map_err(|e| ShareError::Sqlite {
+        message: format!("cannot open snapshot {path_str}: {e}"),
+    })?;
+
+    let rows = conn
+        .query_sync(
+            "SELECT id, thread_id, created_ts FROM messages ORDER BY id ASC",
+            &[],
+        )
+        .map_err(|e| ShareError::Sqlite {
+            message: format!("SELECT messages failed: {e}"),
+        })?;
+    let wanted_threads = filter.wanted_threads();
+    let mut all_ids = BTreeSet::new();
+    let mut kept = BTreeSet::new();
+    let mut thread_roots: Vec<i64> = Vec::new();
+    for row in &rows {
+        let Ok(id) = row.get_named::<i64>("id") else {
+            continue;
+        };
+        all_ids.insert(id);
+        let thread_id = row
+            .get_named::<String>("thread_id")
+            .ok()
+            .or_else(|| {
+                row.get_named::<i64>("thread_id")
+                    .ok()
+                    .map(|thread| thread.to_string())
+            })
+            .map(|thread| thread.trim().to_string())
+            .filter(|thread| !thread.is_empty());
+        let created_ts = row.get_named::<i64>("created_ts").ok().or_else(|| {
+            // Snapshots store `*_ts` columns as text; accept both integer
+            // micros and ISO-8601 renderings.
+            row.get_named::<String>("created_ts").ok().and_then(|raw| {
+                let raw = raw.trim();
+                raw.parse::<i64>()
+                    .ok()
+                    .or_else(|| mcp_agent_mail_db::iso_to_micros(raw))
+            })
+        });
+        let in_range = match (since, until) {
+            (None, None) => true,
+            _ => created_ts.is_some_and(|ts| {
+                since.is_none_or(|since| ts >= since) && until.is_none_or(|until| ts <= until)
+            }),
+        };
+        let in_thread = wanted_threads.is_empty()
+            || wanted_threads.contains(id.to_string().as_str())
+            || thread_id
+                .as_deref()
+                .is_some_and(|thread| wanted_threads.contains(thread));
+        if in_range && in_thread {
+            kept.insert(id);
+            if let Some(root) = thread_id.and_then(|thread| thread.parse::<i64>().ok())
+                && root != id
+            {
+                thread_roots.push(root);
+            }
+        }
+    }
+
+    let matched_count = kept.len();
+    let mut context_parent_ids = Vec::new();
+    for root in thread_roots {
+        if all_ids.contains(&root) && kept.insert(root) {
+            context_parent_ids.push(root);
+        }
+    }
+    context_parent_ids.sort_unstable();
+
+    let removed: Vec<i64> = all_ids.difference(&kept).copied().collect();
+    if removed.is_empty() {
+        let remaining = count_remaining(&conn)?;
+        return Ok(MessageFilterResult {
+            filter: filter.clone(),
+            matched_count,
+            context_parent_ids,
+            removed_count: 0,
+            remaining,
+        });
+    }
+
+    let derived_artifacts = crate::scrub::detect_derived_export_artifacts(&conn)?;
+
+    conn.execute_sync("BEGIN IMMEDIATE", &[])
+        .map_err(|e| ShareError::Sqlite {
+            message: format!("BEGIN transaction failed: {e}"),
+        })?;
+
+    let result = (|| {
+        for batch in removed.chunks(MESSAGE_FILTER_DELETE_BATCH) {
+            let placeholders = build_placeholders(batch.len());
+            let values: Vec<Value> = batch.iter().map(|&id| Value::BigInt(id)).collect();
+            exec(
+                &conn,
+                &format!("DELETE FROM message_recipients WHERE message_id IN ({placeholders})"),
+                &values,
+            )?;
+            exec(
+                &conn,
+                &format!("DELETE FROM messages WHERE id IN ({placeholders})"),
+                &values,
+            )?;
+        }
+        if table_exists(&conn, "inbox_stats")? {
+            rebuild_scope_inbox_stats(&conn)?;
+        }
+        count_remaining(&conn)
+    })();
+
+    match result {
+        Ok(remaining) => {
+            conn.execute_sync("COMMIT", &[])
+                .map_err(|e| ShareError::Sqlite {
+                    message: format!("COMMIT failed: {e}"),
+                })?;
+            drop(conn);
+            if derived_artifacts.any() {
+                crate::scrub::refresh_derived_export_artifacts(&snapshot_path, derived_artifacts)?;
+            }
+            Ok(MessageFilterResult {
+                filter: filter.clone(),
+                matched_count,
+                context_parent_ids,
+                removed_count: removed.len(),
+                remaining,
+            })
+        }
+        Err(err) => {
+            let _ = conn.execute_sync("ROLLBACK", &[]);
+            Err(err)
+        }
+    }
+}
+
+/// Upper bound on bound parameters per DELETE issued by [`apply_message_filter`].
+const MESSAGE_FILTER_DELETE_BATCH: usize = 500;
+
 fn load_scope_projects(conn: &Conn) -> Result<Vec<ProjectRecord>, ShareError> {
     let project_rows = conn
         .query_sync(
@@ -1244,6 +1487,91 @@ mod tests {
         assert_eq!(rows[0].get_named::<i64>("ack_pending_count").unwrap(), 0);
     }
 
+    fn stamp_filter_test_messages(db: &Path) {
+        let conn = Conn::open_file(db.display().to_string()).unwrap();
+        conn.execute_raw("UPDATE messages SET created_ts = '2026-01-01T00:00:00Z' WHERE id = 1")
+            .unwrap();
+        conn.execute_raw(
+            "UPDATE messages SET created_ts = '2026-01-10T00:00:00Z', thread_id = '1' WHERE id = 2",
+        )
+        .unwrap();
+        conn.execute_raw("UPDATE messages SET created_ts = '2026-01-20T00:00:00Z' WHERE id = 3")
+            .unwrap();
+    }
+
+    fn remaining_message_ids(db: &Path) -> Vec<i64> {
+        let conn = Conn::open_file(db.display().to_string()).unwrap();
+        conn.query_sync("SELECT id FROM messages ORDER BY id", &[])
+            .unwrap()
+            .iter()
+            .map(|row| row.get_named::<i64>("id").unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn message_filter_trims_by_date_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_db(dir.path());
+        stamp_filter_test_messages(&db);
+
+        let filter = MessageFilter {
+            until: Some("2026-01-05T00:00:00Z".to_string()),
+            ..MessageFilter::default()
+        };
+        let result = apply_message_filter(&db, &filter).unwrap();
+        assert_eq!(result.matched_count, 1);
+        assert_eq!(result.removed_count, 2);
+        assert!(result.context_parent_ids.is_empty());
+        assert_eq!(result.remaining.messages, 1);
+        assert_eq!(result.remaining.recipients, 1);
+        // Agents referenced by removed messages keep their profiles.
+        assert_eq!(result.remaining.agents, 2);
+        assert_eq!(remaining_message_ids(&db), vec![1]);
+    }
+
+    #[test]
+    fn message_filter_keeps_thread_root_outside_range_for_context() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_db(dir.path());
+        stamp_filter_test_messages(&db);
+
+        let filter = MessageFilter {
+            since: Some("2026-01-05T00:00:00Z".to_string()),
+            until: None,
+            threads: vec!["1".to_string()],
+        };
+        let result = apply_message_filter(&db, &filter).unwrap();
+        assert_eq!(result.matched_count, 1);
+        assert_eq!(result.context_parent_ids, vec![1]);
+        assert_eq!(result.removed_count, 1);
+        assert_eq!(remaining_message_ids(&db), vec![1, 2]);
+    }
+
+    #[test]
+    fn message_filter_rejects_invalid_bounds() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = create_test_db(dir.path());
+
+        let malformed = MessageFilter {
+            since: Some("last tuesday".to_string()),
+            ..MessageFilter::default()
+        };
+        let err = apply_message_filter(&db, &malformed).unwrap_err();
+        assert!(matches!(err, ShareError::Validation { .. }));
+        assert!(err.to_string().contains("--since"));
+
+        let inverted = MessageFilter {
+            since: Some("2026-02-01T00:00:00Z".to_string()),
+            until: Some("2026-01-01T00:00:00Z".to_string()),
+            threads: Vec::new(),
+        };
+        assert!(matches!(
+            apply_message_filter(&db, &inverted),
+            Err(ShareError::Validation { .. })
+        ));
+        assert_eq!(remaining_message_ids(&db), vec![1, 2, 3]);
+    }
+
     #[test]
     fn scope_removes_filtered_recipients_from_kept_messages_and_recipients_json() {
         let dir = tempfile::tempdir().unwrap();
@@ -661,17 +661,81 @@ pub fn create_snapshot_context(
     project_filters: &[String],
     scrub_preset: crate::ScrubPreset,
 ) -> Result<SnapshotContext, ShareError> {
+    create_snapshot_context_with(source, snapshot_path, project_filters, scrub_preset, |_| {
+        Ok(())
+    })
+}
+
+/// [`create_snapshot_context`] that also narrows the scoped messages with
+/// [`crate::apply_message_filter`] before scrubbing. An empty filter behaves
+/// exactly like [`create_snapshot_context`].
+pub fn create_filtered_snapshot_context(
+    source: &Path,
+    snapshot_path: &Path,
+    project_filters: &[String],
+    message_filter: &crate::MessageFilter,
+    scrub_preset: crate::ScrubPreset,
+) -> Result<SnapshotContext, ShareError> {
+    build_snapshot_context(
+        source,
+        snapshot_path,
+        project_filters,
+        message_filter,
+        scrub_preset,
+        |_| Ok(()),
+    )
+}
+
+/// [`create_snapshot_context`] with a hook that runs on the scoped, scrubbed
+/// snapshot just before finalization, so rows it deletes never reach the
+/// search index or materialized views and are compacted away.
+pub fn create_snapshot_context_with(
+    source: &Path,
+    snapshot_path: &Path,
+    project_filters: &[String],
+    scrub_preset: crate::ScrubPreset,
+    before_finalize: impl FnOnce(&Path) -> Result<(), ShareError>,
+) -> Result<SnapshotContext, ShareError> {
+    build_snapshot_context(
+        source,
+        snapshot_path,
+        project_filters,
+        &crate::MessageFilter::default(),
+        scrub_preset,
+        before_finalize,
+    )
+}
+
+fn build_snapshot_context(
+    source: &Path,
+    snapshot_path: &Path,
+    project_filters: &[String],
+    message_filter: &crate::MessageFilter,
+    scrub_preset: crate::ScrubPreset,
+    before_finalize: impl FnOnce(&Path) -> Result<(), ShareError>,
+) -> Result<SnapshotContext, ShareError> {
+    // Reject malformed bounds before paying for the snapshot copy.
+    message_filter.bounds_micros()?;
     create_sqlite_snapshot(source, snapshot_path, true)?;
     let mut scope = crate::apply_project_scope(snapshot_path, project_filters)?;
+    let message_filter = if message_filter.is_empty() {
+        None
+    } else {
+        let filtered = crate::apply_message_filter(snapshot_path, message_filter)?;
+        scope.remaining = filtered.remaining.clone();
+        Some(filtered)
+    };
     let scrub_summary = crate::scrub_snapshot(snapshot_path, scrub_preset)?;
     if !matches!(scrub_preset, crate::ScrubPreset::Archive) {
         crate::scrub::redact_scope_project_human_keys(&mut scope);
     }
+    before_finalize(snapshot_path)?;
     let finalize = crate::finalize_export_db(snapshot_path)?;
 
     Ok(SnapshotContext {
         snapshot_path: snapshot_path.to_path_buf(),
         scope,
+        message_filter,
         scrub_summary,
         fts_enabled: finalize.fts_enabled,
     })
@@ -682,6 +746,8 @@ pub fn create_snapshot_context(
 pub struct SnapshotContext {
     pub snapshot_path: PathBuf,
     pub scope: crate::scope::ProjectScopeResult,
+    /// Present when the export was narrowed by date range or thread.
+    pub message_filter: Option<crate::scope::MessageFilterResult>,
     pub scrub_summary: crate::scrub::ScrubSummary,
     pub fts_enabled: bool,
 }
@@ -12,7 +12,9 @@
 //! - Notification signals
 
 pub mod boot_check;
+pub mod mirror;
 pub mod recovery;
+pub mod relocate;
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::ffi::OsString;
@@ -36,6 +38,7 @@ use thiserror::Error;
 
 use mcp_agent_mail_core::{
     LockLevel, OrderedMutex, OrderedRwLock,
+    archive_location::{ArchiveLocations, project_archive_root},
     config::{self, Config},
 };
 
@@ -457,6 +460,7 @@ struct WriteBehindQueue {
 const WBQ_FLUSH_INTERVAL_MS: u64 = 100;
 const WBQ_ENQUEUE_TIMEOUT_MS: u64 = 100;
 const WBQ_ENQUEUE_MAX_BACKOFF_MS: u64 = 8;
+const MIRROR_REPLAY_INTERVAL: Duration = Duration::from_secs(30);
 
 static WBQ: OnceLock<WriteBehindQueue> = OnceLock::new();
 
@@ -677,8 +681,10 @@ fn wbq_enqueue_with_sender_and_pressure(
 
 /// Enqueue a write op to the background drain thread.
 ///
-/// The DB remains the source of truth; archive writes are best-effort.
+/// The DB remains the source of truth; archive writes are best-effort. With
+/// `ARCHIVE_MIRROR=live`, message bundles are journaled first (see [`mirror`]).
 pub fn wbq_enqueue(op: WriteOp) -> WbqEnqueueResult {
+    mirror::journal_message_op(&op);
     let disk_pressure = mcp_agent_mail_core::global_metrics()
         .system
         .disk_pressure_level
@@ -1297,6 +1303,7 @@ fn wbq_drain_loop(
     let flush_interval = Duration::from_millis(WBQ_FLUSH_INTERVAL_MS);
     let mut flush_waiters: Vec<std::sync::mpsc::SyncSender<()>> = Vec::new();
     let mut shutting_down = false;
+    let mut next_mirror_replay = Instant::now();
 
     loop {
         let mut batch: Vec<WbqOpEnvelope> = Vec::new();
@@ -1309,6 +1316,7 @@ fn wbq_drain_loop(
                 for w in flush_waiters.drain(..) {
                     let _ = w.try_send(());
                 }
+                wbq_maybe_replay_mirror(&mut next_mirror_replay);
                 continue;
             }
             Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
@@ -1368,7 +1376,17 @@ fn wbq_drain_loop(
                 .unwrap_or(u64::MAX);
                 metrics.storage.wbq_queue_latency_us.record(latency_us);
             }
-            if let Err(error) = result {
+            if let Err(error) = result.as_ref()
+                && mirror::ops_retryable_by_mirror(envelopes.iter().map(|e| e.op.as_ref()))
+            {
+                // Live mirroring keeps these jobs in the crash-safe journal;
+                // the replay loop retries them, so sends are not refused.
+                mirror::record_mirror_failure(&format!(
+                    "{} message bundle(s): {error}",
+                    envelopes.len()
+                ));
+                errors += envelopes.len();
+            } else if let Err(error) = result {
                 // Retry budget was already exhausted inside wbq_execute_op /
                 // wbq_execute_message_bundle_batch. Reaching this branch means
                 // the row enqueued via the API was never persisted — that's
@@ -1418,6 +1436,7 @@ fn wbq_drain_loop(
         if shutting_down {
             break;
         }
+        wbq_maybe_replay_mirror(&mut next_mirror_replay);
     }
 
     // Drain any remaining messages after the loop exits.
@@ -1453,7 +1472,12 @@ fn wbq_drain_loop(
                 .unwrap_or(u64::MAX);
                 metrics.storage.wbq_queue_latency_us.record(latency_us);
                 metrics.storage.wbq_drained_total.inc();
-                if let Err(error) = r {
+                if let Err(error) = r.as_ref()
+                    && mirror::ops_retryable_by_mirror([envelope.op.as_ref()])
+                {
+                    metrics.storage.wbq_errors_total.inc();
+                    mirror::record_mirror_failure(&format!("post-shutdown drain: {error}"));
+                } else if let Err(error) = r {
                     // Same exhausted-retry semantics as the main drain
                     // branch — failures here are also rows the API
                     // accepted but never persisted. #122.
@@ -1476,6 +1500,40 @@ fn wbq_drain_loop(
     }
 }
 
+/// Replay journaled mirror jobs at most once per [`MIRROR_REPLAY_INTERVAL`]
+/// while `ARCHIVE_MIRROR=live`.
+fn wbq_maybe_replay_mirror(next_replay: &mut Instant) {
+    let now = Instant::now();
+    if now < *next_replay {
+        return;
+    }
+    *next_replay = now + MIRROR_REPLAY_INTERVAL;
+    let config = Config::get();
+    if !config.archive_mirror.is_live() {
+        return;
+    }
+    match mirror::replay_pending_jobs(&config, mirror::MIRROR_REPLAY_MIN_AGE_US) {
+        Ok(0) => {}
+        Ok(mirrored) => tracing::info!(mirrored, "[wbq-drain] replayed pending mirror jobs"),
+        Err(error) => mirror::record_mirror_failure(&format!("journal replay: {error}")),
+    }
+}
+
+/// Apply already-grouped message-bundle ops (same project) outside the queue.
+fn wbq_execute_message_bundle_ops(ops: &[WriteOp]) -> Result<()> {
+    if let [op] = ops {
+        return wbq_execute_op(op);
+    }
+    let envelopes = ops
+        .iter()
+        .map(|op| WbqOpEnvelope {
+            enqueued_at: Instant::now(),
+            op: Box::new(op.clone()),
+        })
+        .collect::<Vec<_>>();
+    wbq_execute_message_bundle_batch(&envelopes)
+}
+
 fn message_bundle_batch_group_key(op: &WriteOp) -> Option<(&str, &Path, &str, &str)> {
     match op {
         WriteOp::MessageBundle {
@@ -1514,7 +1572,10 @@ fn wbq_execute_message_bundle_batch(envelopes: &[WbqOpEnvelope]) -> Result<()> {
     let mut attempts = 0;
     loop {
         match wbq_execute_message_bundle_batch_inner(envelopes) {
-            Ok(()) => return Ok(()),
+            Ok(()) => {
+                mirror::note_ops_mirrored(envelopes.iter().map(|e| e.op.as_ref()));
+                return Ok(());
+            }
             Err(e) => {
                 attempts += 1;
                 if attempts >= 3 {
@@ -1578,7 +1639,10 @@ fn wbq_execute_op(op: &WriteOp) -> Result<()> {
     let mut attempts = 0;
     loop {
         match wbq_execute_op_inner(op) {
-            Ok(()) => return Ok(()),
+            Ok(()) => {
+                mirror::note_ops_mirrored([op]);
+                return Ok(());
+            }
             Err(e) => {
                 attempts += 1;
                 if attempts >= 3 {
@@ -5473,7 +5537,7 @@ const ARCHIVE_GITIGNORE_ENTRIES: &[&str] =
 ///
 /// Returns `(repo_root, was_freshly_initialized)`.
 pub fn ensure_archive_root(config: &Config) -> Result<(PathBuf, bool)> {
-    let root = config.storage_root.clone();
+    let root = config.archive_root().to_path_buf();
     ensure_dir(&root)?;
 
     let fresh = ensure_repo(&root, config)?;
@@ -5494,7 +5558,7 @@ pub fn open_archive(config: &Config, slug: &str) -> Result<Option<ProjectArchive
         ));
     }
 
-    let repo_root = config.storage_root.clone();
+    let repo_root = project_archive_root(config.archive_root(), slug);
     let project_root = repo_root.join("projects").join(slug);
     if path_existing_prefix_has_symlink(&project_root)? {
         return Ok(None);
@@ -5529,7 +5593,12 @@ pub fn ensure_archive(config: &Config, slug: &str) -> Result<ProjectArchive> {
             "invalid project slug: must not contain path separators or '..' components".to_string(),
         ));
     }
-    let (repo_root, _fresh) = ensure_archive_root(config)?;
+    let (default_root, _fresh) = ensure_archive_root(config)?;
+    let repo_root = project_archive_root(&default_root, slug);
+    if repo_root != default_root {
+        ensure_dir(&repo_root)?;
+        ensure_repo(&repo_root, config)?;
+    }
     let project_root = repo_root.join("projects").join(slug);
     ensure_dir(&project_root)?;
 
@@ -6956,6 +7025,9 @@ pub struct AttachmentMeta {
     pub media_type: String,
     pub bytes: usize,
     pub sha1: String,
+    /// Content sha256, set for content-addressed blobs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
     pub width: u32,
     pub height: u32,
     /// Base64-encoded WebP data (only for inline type)
@@ -7100,6 +7172,7 @@ fn store_attachment_from_cache(
             media_type: "image/webp".to_string(),
             bytes: webp_bytes_len,
             sha1: digest.to_string(),
+            sha256: None,
             width: manifest.width,
             height: manifest.height,
             data_base64: Some(encoded),
@@ -7112,6 +7185,7 @@ fn store_attachment_from_cache(
             media_type: "image/webp".to_string(),
             bytes: webp_bytes_len,
             sha1: digest.to_string(),
+            sha256: None,
             width: manifest.width,
             height: manifest.height,
             data_base64: None,
@@ -7297,6 +7371,7 @@ pub fn store_attachment(
             media_type: "image/webp".to_string(),
             bytes: webp_bytes.len(),
             sha1: digest,
+            sha256: None,
             width,
             height,
             data_base64: Some(encoded),
@@ -7309,6 +7384,7 @@ pub fn store_attachment(
             media_type: "image/webp".to_string(),
             bytes: webp_bytes.len(),
             sha1: digest,
+            sha256: None,
             width,
             height,
             data_base64: None,
@@ -7404,6 +7480,117 @@ pub fn store_raw_attachment(
         media_type: "application/octet-stream".to_string(),
         bytes: bytes.len(),
         sha1: digest,
+        sha256: None,
+        width: 0,
+        height: 0,
+        data_base64: None,
+        path: Some(rel_path.clone()),
+        original_path: None,
+    };
+
+    Ok(StoredAttachment {
+        meta,
+        rel_paths: vec![rel_path],
+    })
+}
+
+/// Project-relative location of a content-addressed attachment blob:
+/// `attachments/{sha256[..2]}/{sha256[2..]}/blob`.
+#[must_use]
+pub fn attachment_blob_rel_path(sha256: &str) -> String {
+    let split = 2.min(sha256.len());
+    format!("attachments/{}/{}/blob", &sha256[..split], &sha256[split..])
+}
+
+/// A content-addressed blob found under a project's `attachments/` tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentBlob {
+    pub sha256: String,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Store an attachment as a content-addressed blob.
+///
+/// The file lands at [`attachment_blob_rel_path`] under the project root, so
+/// identical content attached to many messages is stored once; messages
+/// reference the blob by path and `sha256`. Unreferenced blobs are reclaimed
+/// by `am doctor gc-attachments`, not here.
+/// Returns metadata and relative paths for git commit.
+pub fn store_attachment_blob(
+    archive: &ProjectArchive,
+    file_path: &Path,
+    max_bytes: usize,
+) -> Result<StoredAttachment> {
+    let _mutation = ArchiveMutationGuard::begin();
+    let effective_limit = if max_bytes > 0 {
+        max_bytes.max(FALLBACK_MAX_ATTACHMENT_BYTES)
+    } else {
+        FALLBACK_MAX_ATTACHMENT_BYTES
+    };
+    let meta = fs::metadata(file_path)?;
+    if meta.len() > effective_limit as u64 {
+        return Err(StorageError::InvalidPath(format!(
+            "Attachment too large ({} bytes, max {})",
+            meta.len(),
+            effective_limit,
+        )));
+    }
+
+    let bytes = fs::read(file_path)?;
+    if bytes.is_empty() {
+        return Err(StorageError::InvalidPath(
+            "Attachment file is empty".to_string(),
+        ));
+    }
+
+    let sha256 = {
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        hex::encode(hasher.finalize())
+    };
+    let sha1 = {
+        let mut hasher = sha1::Sha1::new();
+        hasher.update(&bytes);
+        hex::encode(hasher.finalize())
+    };
+
+    let project_root = archive_project_root_checked(archive)?;
+    let target_path = project_root.join(attachment_blob_rel_path(&sha256));
+    if path_existing_prefix_has_symlink(&target_path)? {
+        return Err(StorageError::InvalidPath(format!(
+            "attachment blob path must not include symlinks: {}",
+            target_path.display()
+        )));
+    }
+
+    // Content addressing makes an existing blob a hit, unless it was damaged
+    // on disk, in which case it is rewritten from the source.
+    let needs_write = match fs::symlink_metadata(&target_path) {
+        Ok(meta) if meta.file_type().is_file() => fs::read(&target_path)? != bytes,
+        Ok(_) => {
+            return Err(StorageError::InvalidPath(format!(
+                "attachment blob is not a regular file: {}",
+                target_path.display()
+            )));
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => true,
+        Err(err) => return Err(err.into()),
+    };
+    if needs_write {
+        if let Some(parent) = target_path.parent() {
+            ensure_dir(parent)?;
+        }
+        atomic_write_bytes(&target_path, &bytes, true)?;
+    }
+
+    let rel_path = rel_path_cached(&archive.canonical_repo_root, &target_path)?;
+    let meta = AttachmentMeta {
+        kind: "file".to_string(),
+        media_type: "application/octet-stream".to_string(),
+        bytes: bytes.len(),
+        sha1,
+        sha256: Some(sha256),
         width: 0,
         height: 0,
         data_base64: None,
@@ -7417,6 +7604,51 @@ pub fn store_raw_attachment(
     })
 }
 
+/// Enumerate the content-addressed blobs under `project_root/attachments`.
+///
+/// Only `{2 hex}/{62 hex}/blob` entries are reported; WebP conversions,
+/// manifests, and legacy `files/` copies living alongside are ignored.
+pub fn list_attachment_blobs(project_root: &Path) -> Result<Vec<AttachmentBlob>> {
+    fn is_hex(name: &str, len: usize) -> bool {
+        name.len() == len && name.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    let attach_dir = project_root.join("attachments");
+    let prefixes = match fs::read_dir(&attach_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut blobs = Vec::new();
+    for prefix in prefixes {
+        let prefix = prefix?;
+        let prefix_name = prefix.file_name().to_string_lossy().into_owned();
+        if !is_hex(&prefix_name, 2) || !prefix.file_type()?.is_dir() {
+            continue;
+        }
+        for rest in fs::read_dir(prefix.path())? {
+            let rest = rest?;
+            let rest_name = rest.file_name().to_string_lossy().into_owned();
+            if !is_hex(&rest_name, 62) || !rest.file_type()?.is_dir() {
+                continue;
+            }
+            let path = rest.path().join("blob");
+            let Ok(meta) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if meta.file_type().is_file() {
+                blobs.push(AttachmentBlob {
+                    sha256: format!("{prefix_name}{rest_name}").to_ascii_lowercase(),
+                    path,
+                    bytes: meta.len(),
+                });
+            }
+        }
+    }
+    blobs.sort_by(|a, b| a.sha256.cmp(&b.sha256));
+    Ok(blobs)
+}
+
 /// Process attachment paths and store them in the archive.
 ///
 /// Resolves paths sequentially (fast), then converts up to
@@ -10184,6 +10416,7 @@ pub fn check_archive_consistency(
     let mut found = 0usize;
     let mut missing = 0usize;
     let mut missing_ids: Vec<i64> = Vec::new();
+    let locations = ArchiveLocations::load(storage_root);
 
     for msg in messages {
         let project_slug = match validate_archive_component("project slug", &msg.project_slug) {
@@ -10197,8 +10430,11 @@ pub fn check_archive_consistency(
             }
         };
         // Build the expected canonical path:
-        // {storage_root}/projects/{slug}/messages/{YYYY}/{MM}/{iso}__{slug}__{id}.md
-        let project_dir = storage_root.join("projects").join(project_slug);
+        // {archive_root}/projects/{slug}/messages/{YYYY}/{MM}/{iso}__{slug}__{id}.md
+        let project_dir = locations
+            .root_for(storage_root, project_slug)
+            .join("projects")
+            .join(project_slug);
 
         // Parse the ISO timestamp to extract year/month
         let (year, month) = match parse_year_month(&msg.created_ts_iso) {
@@ -13931,6 +14167,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_store_attachment_blob_dedups_identical_content() {
+        let tmp = TempDir::new().unwrap();
+        let archive = ensure_archive(&test_config(tmp.path()), "blob-attach-proj").unwrap();
+        let first = tmp.path().join("build.log");
+        let second = tmp.path().join("build-copy.txt");
+        fs::write(&first, b"same build log").unwrap();
+        fs::write(&second, b"same build log").unwrap();
+
+        let a = store_attachment_blob(&archive, &first, 1024 * 1024).unwrap();
+        let b = store_attachment_blob(&archive, &second, 1024 * 1024).unwrap();
+
+        let sha256 = a.meta.sha256.clone().expect("blob sha256");
+        assert_eq!(b.meta.sha256.as_deref(), Some(sha256.as_str()));
+        assert_eq!(a.meta.path, b.meta.path);
+        let rel = a.meta.path.expect("blob path");
+        assert!(rel.ends_with(&attachment_blob_rel_path(&sha256)), "{rel}");
+        assert_eq!(
+            fs::read(archive.repo_root.join(&rel)).unwrap(),
+            b"same build log"
+        );
+
+        fs::create_dir_all(archive.root.join("attachments/files/ab")).unwrap();
+        fs::write(archive.root.join("attachments/files/ab/legacy.txt"), b"x").unwrap();
+        let blobs = list_attachment_blobs(&archive.root).unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].sha256, sha256);
+        assert_eq!(blobs[0].bytes, 14);
+    }
+
     #[test]
     fn test_store_raw_attachment_rewrites_mismatched_existing_target() {
         let tmp = TempDir::new().unwrap();
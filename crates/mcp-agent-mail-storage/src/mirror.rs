@@ -0,0 +1,612 @@
+//! Crash-safe journal behind `ARCHIVE_MIRROR=live` continuous mirroring.
+//!
+//! In the default `batched` mode a message archive write lives only in the
+//! in-memory write-behind queue until the drain thread applies it, so a crash
+//! drops it until a later reconcile. In `live` mode every message-bundle op is
+//! first appended to `<storage_root>/mirror_journal/messages.jsonl`:
+//!
+//! - [`journal_message_op`] appends (and fsyncs) a hash-stamped job before the
+//!   op is handed to the queue.
+//! - The drain thread appends a `done` marker once the bundle reached the
+//!   project working tree and its commit was handed to the commit coalescer,
+//!   which batches commits per repo (`AM_COALESCER_MAX_BATCH_SIZE` messages or
+//!   `AM_COALESCER_FLUSH_MS`, whichever comes first).
+//! - A failed append leaves the job pending instead of tripping the
+//!   durability gate; [`replay_pending_jobs`] retries it from the drain loop
+//!   and after a restart. Replays are idempotent: rewriting a bundle with
+//!   identical content is accepted by the canonical-id collision check.
+//! - [`mirror_lag`] reports how far the archive trails the DB for
+//!   `am doctor check`.
+//!
+//! Every journal mutation holds an exclusive `fs2` lock on a sibling lock
+//! file, so the server drain thread, a CLI direct send, and compaction never
+//! interleave partial lines. Manual `am archive save` runs under the mailbox
+//! activity lock, which the server holds exclusively while it runs; pending
+//! jobs left by a crashed server are part of the saved storage tree and are
+//! replayed on the next start.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write as IoWrite;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use mcp_agent_mail_core::Config;
+
+use crate::WriteOp;
+
+pub const MIRROR_JOURNAL_DIR: &str = "mirror_journal";
+const MIRROR_JOURNAL_FILE: &str = "messages.jsonl";
+const MIRROR_JOURNAL_LOCK_FILE: &str = ".messages.jsonl.lock";
+const MIRROR_JOB_KIND: &str = "mirror_append";
+const MIRROR_DONE_KIND: &str = "mirror_done";
+/// Jobs younger than this are assumed to still be in flight on the queue.
+pub const MIRROR_REPLAY_MIN_AGE_US: i64 = 30 * 1_000_000;
+/// Upper bound on bundles applied per replayed batch.
+const MIRROR_REPLAY_BATCH: usize = 64;
+
+static MIRROR_FAILURES_TOTAL: AtomicU64 = AtomicU64::new(0);
+static MIRROR_LAST_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+/// One journaled message-bundle append.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MirrorJob {
+    /// `<project_slug>:<message_id>`; stable across retries.
+    pub job_id: String,
+    pub enqueued_ts: i64,
+    pub project_slug: String,
+    pub message_json: serde_json::Value,
+    pub body_md: String,
+    pub sender: String,
+    pub recipients: Vec<String>,
+    pub extra_paths: Vec<String>,
+}
+
+impl MirrorJob {
+    /// Build a job from a message-bundle op. Other ops, and messages without
+    /// a positive id, are not journaled.
+    #[must_use]
+    pub fn from_write_op(op: &WriteOp, enqueued_ts: i64) -> Option<Self> {
+        let WriteOp::MessageBundle {
+            project_slug,
+            message_json,
+            body_md,
+            sender,
+            recipients,
+            extra_paths,
+            ..
+        } = op
+        else {
+            return None;
+        };
+        Some(Self {
+            job_id: mirror_job_id(project_slug, message_json)?,
+            enqueued_ts,
+            project_slug: project_slug.clone(),
+            message_json: message_json.clone(),
+            body_md: body_md.clone(),
+            sender: sender.clone(),
+            recipients: recipients.clone(),
+            extra_paths: extra_paths.clone(),
+        })
+    }
+
+    #[must_use]
+    pub fn into_write_op(self, config: &Config) -> WriteOp {
+        WriteOp::MessageBundle {
+            project_slug: self.project_slug,
+            config: config.clone(),
+            message_json: self.message_json,
+            body_md: self.body_md,
+            sender: self.sender,
+            recipients: self.recipients,
+            extra_paths: self.extra_paths,
+        }
+    }
+}
+
+/// How far the archive mirror trails the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MirrorLag {
+    pub pending_jobs: usize,
+    pub oldest_pending_ts: Option<i64>,
+    /// Age of the oldest pending job in microseconds (0 when caught up).
+    pub lag_us: i64,
+    /// Failed mirror applies in this process since start.
+    pub failures_total: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalRecord {
+    kind: String,
+    job_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    job: Option<MirrorJob>,
+}
+
+/// Stable journal key for a message bundle, or `None` without a positive id.
+#[must_use]
+pub fn mirror_job_id(project_slug: &str, message_json: &serde_json::Value) -> Option<String> {
+    let id = message_json.get("id").and_then(serde_json::Value::as_i64)?;
+    (id > 0).then(|| format!("{project_slug}:{id}"))
+}
+
+#[must_use]
+pub fn mirror_journal_path(storage_root: &Path) -> PathBuf {
+    storage_root
+        .join(MIRROR_JOURNAL_DIR)
+        .join(MIRROR_JOURNAL_FILE)
+}
+
+fn mirror_journal_lock_path(storage_root: &Path) -> PathBuf {
+    storage_root
+        .join(MIRROR_JOURNAL_DIR)
+        .join(MIRROR_JOURNAL_LOCK_FILE)
+}
+
+fn job_content_sha256(job: &MirrorJob) -> std::io::Result<String> {
+    let bytes = serde_json::to_vec(job).map_err(|err| std::io::Error::other(err.to_string()))?;
+    Ok(hex::encode(Sha256::digest(bytes)))
+}
+
+fn reject_existing_symlink(path: &Path) -> std::io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => Err(std::io::Error::other(format!(
+            "mirror journal path must not be a symlink: {}",
+            path.display()
+        ))),
+        Ok(_) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+fn with_journal_lock<T>(
+    storage_root: &Path,
+    exclusive: bool,
+    f: impl FnOnce(&Path) -> std::io::Result<T>,
+) -> std::io::Result<T> {
+    let dir = storage_root.join(MIRROR_JOURNAL_DIR);
+    reject_existing_symlink(&dir)?;
+    fs::create_dir_all(&dir)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+    }
+    let lock_path = mirror_journal_lock_path(storage_root);
+    reject_existing_symlink(&lock_path)?;
+    let lock_file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(&lock_path)?;
+    if exclusive {
+        fs2::FileExt::lock_exclusive(&lock_file)?;
+    } else {
+        fs2::FileExt::lock_shared(&lock_file)?;
+    }
+    let path = mirror_journal_path(storage_root);
+    reject_existing_symlink(&path)?;
+    let result = f(&path);
+    let _ = fs2::FileExt::unlock(&lock_file);
+    result
+}
+
+fn append_records(storage_root: &Path, records: &[JournalRecord]) -> std::io::Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    with_journal_lock(storage_root, true, |path| {
+        let mut options = fs::OpenOptions::new();
+        options.create(true).read(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(path)?;
+        // Isolate a torn final line from a prior crash on its own (skippable)
+        // line instead of corrupting the first record of this append.
+        let needs_leading_newline = if let Ok(meta) = file.metadata()
+            && meta.len() > 0
+        {
+            use std::io::{Read, Seek, SeekFrom};
+            file.seek(SeekFrom::End(-1))?;
+            let mut last = [0u8; 1];
+            file.read_exact(&mut last)?;
+            last[0] != b'\n'
+        } else {
+            false
+        };
+        let mut buf = Vec::new();
+        if needs_leading_newline {
+            buf.push(b'\n');
+        }
+        for record in records {
+            serde_json::to_writer(&mut buf, record)
+                .map_err(|err| std::io::Error::other(err.to_string()))?;
+            buf.push(b'\n');
+        }
+        file.write_all(&buf)?;
+        file.sync_all()
+    })
+}
+
+fn read_pending(path: &Path) -> std::io::Result<Vec<MirrorJob>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    // Keyed by job id so a re-journaled job (same message appended twice)
+    // collapses into one entry; a later `done` marker clears every copy.
+    let mut pending: BTreeMap<String, MirrorJob> = BTreeMap::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(record) = serde_json::from_str::<JournalRecord>(line) else {
+            continue;
+        };
+        match record.kind.as_str() {
+            MIRROR_JOB_KIND => {
+                let Some(job) = record.job else {
+                    continue;
+                };
+                let valid = record.content_sha256.as_deref().is_some_and(|expected| {
+                    job_content_sha256(&job).is_ok_and(|actual| actual == expected)
+                });
+                if !valid || job.job_id != record.job_id {
+                    tracing::warn!(job_id = %record.job_id, "skipping mirror job with invalid content hash");
+                    continue;
+                }
+                pending.entry(record.job_id).or_insert(job);
+            }
+            MIRROR_DONE_KIND => {
+                pending.remove(&record.job_id);
+            }
+            _ => {}
+        }
+    }
+    let mut jobs = pending.into_values().collect::<Vec<_>>();
+    jobs.sort_by(|a, b| {
+        a.enqueued_ts
+            .cmp(&b.enqueued_ts)
+            .then_with(|| a.job_id.cmp(&b.job_id))
+    });
+    Ok(jobs)
+}
+
+/// Append `job` to the journal and fsync it.
+pub fn append_mirror_job(storage_root: &Path, job: &MirrorJob) -> std::io::Result<()> {
+    let record = JournalRecord {
+        kind: MIRROR_JOB_KIND.to_string(),
+        job_id: job.job_id.clone(),
+        content_sha256: Some(job_content_sha256(job)?),
+        job: Some(job.clone()),
+    };
+    append_records(storage_root, &[record])
+}
+
+/// Append `done` markers for `job_ids` in one fsynced write.
+pub fn mark_mirror_jobs_done(storage_root: &Path, job_ids: &[String]) -> std::io::Result<()> {
+    let records = job_ids
+        .iter()
+        .map(|job_id| JournalRecord {
+            kind: MIRROR_DONE_KIND.to_string(),
+            job_id: job_id.clone(),
+            content_sha256: None,
+            job: None,
+        })
+        .collect::<Vec<_>>();
+    append_records(storage_root, &records)
+}
+
+/// Jobs with no `done` marker, oldest first.
+pub fn pending_mirror_jobs(storage_root: &Path) -> std::io::Result<Vec<MirrorJob>> {
+    if !mirror_journal_path(storage_root).exists() {
+        return Ok(Vec::new());
+    }
+    with_journal_lock(storage_root, false, read_pending)
+}
+
+/// Rewrite the journal with only pending jobs; returns how many remain.
+pub fn compact_mirror_journal(storage_root: &Path) -> std::io::Result<usize> {
+    if !mirror_journal_path(storage_root).exists() {
+        return Ok(0);
+    }
+    with_journal_lock(storage_root, true, |path| {
+        let pending = read_pending(path)?;
+        let tmp = path.with_extension("jsonl.tmp");
+        {
+            let mut options = fs::OpenOptions::new();
+            options.create(true).write(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            let mut file = options.open(&tmp)?;
+            let mut buf = Vec::new();
+            for job in &pending {
+                let record = JournalRecord {
+                    kind: MIRROR_JOB_KIND.to_string(),
+                    job_id: job.job_id.clone(),
+                    content_sha256: Some(job_content_sha256(job)?),
+                    job: Some(job.clone()),
+                };
+                serde_json::to_writer(&mut buf, &record)
+                    .map_err(|err| std::io::Error::other(err.to_string()))?;
+                buf.push(b'\n');
+            }
+            file.write_all(&buf)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, path)?;
+        #[cfg(unix)]
+        if let Some(parent) = path.parent() {
+            fs::File::open(parent)?.sync_all()?;
+        }
+        Ok(pending.len())
+    })
+}
+
+/// Journal a message-bundle op before it is queued. Best-effort: a journal
+/// failure is logged and counted, and the op is still queued as in batched
+/// mode.
+pub fn journal_message_op(op: &WriteOp) {
+    let WriteOp::MessageBundle { config, .. } = op else {
+        return;
+    };
+    if !config.archive_mirror.is_live() {
+        return;
+    }
+    let Some(job) = MirrorJob::from_write_op(op, crate::now_micros_i64()) else {
+        return;
+    };
+    if let Err(error) = append_mirror_job(&config.storage_root, &job) {
+        record_mirror_failure(&format!("journal append for {}: {error}", job.job_id));
+    }
+}
+
+/// Mark the live-mode jobs behind `ops` as mirrored.
+pub(crate) fn note_ops_mirrored<'a>(ops: impl IntoIterator<Item = &'a WriteOp>) {
+    let mut by_root: BTreeMap<&Path, Vec<String>> = BTreeMap::new();
+    for op in ops {
+        if let WriteOp::MessageBundle {
+            project_slug,
+            config,
+            message_json,
+            ..
+        } = op
+            && config.archive_mirror.is_live()
+            && let Some(job_id) = mirror_job_id(project_slug, message_json)
+        {
+            by_root
+                .entry(config.storage_root.as_path())
+                .or_default()
+                .push(job_id);
+        }
+    }
+    for (root, job_ids) in by_root {
+        if let Err(error) = mark_mirror_jobs_done(root, &job_ids) {
+            // The bundle is on disk; a missing marker only causes an
+            // idempotent replay later.
+            tracing::warn!(error = %error, jobs = job_ids.len(), "failed to mark mirror jobs done");
+        }
+    }
+}
+
+/// `true` when every op is a live-mode, journaled message bundle, i.e. a
+/// failure can be left to the mirror retry loop instead of the durability
+/// gate.
+pub(crate) fn ops_retryable_by_mirror<'a>(ops: impl IntoIterator<Item = &'a WriteOp>) -> bool {
+    let mut any = false;
+    for op in ops {
+        match op {
+            WriteOp::MessageBundle {
+                project_slug,
+                config,
+                message_json,
+                ..
+            } if config.archive_mirror.is_live()
+                && mirror_job_id(project_slug, message_json).is_some() =>
+            {
+                any = true;
+            }
+            _ => return false,
+        }
+    }
+    any
+}
+
+pub(crate) fn record_mirror_failure(detail: &str) {
+    MIRROR_FAILURES_TOTAL.fetch_add(1, Ordering::Relaxed);
+    tracing::warn!(
+        detail,
+        "archive mirror apply failed; job stays queued for retry"
+    );
+    *MIRROR_LAST_ERROR
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(detail.to_string());
+}
+
+/// Apply pending jobs older than `min_age_us` and compact the journal.
+/// Returns the number of jobs mirrored.
+pub fn replay_pending_jobs(config: &Config, min_age_us: i64) -> std::io::Result<usize> {
+    let storage_root = config.storage_root.as_path();
+    let cutoff = crate::now_micros_i64().saturating_sub(min_age_us);
+    let due = pending_mirror_jobs(storage_root)?
+        .into_iter()
+        .filter(|job| job.enqueued_ts <= cutoff)
+        .collect::<Vec<_>>();
+
+    let mut by_project: BTreeMap<String, Vec<MirrorJob>> = BTreeMap::new();
+    for job in due {
+        by_project
+            .entry(job.project_slug.clone())
+            .or_default()
+            .push(job);
+    }
+
+    let mut mirrored = 0usize;
+    for (project_slug, jobs) in by_project {
+        for chunk in jobs.chunks(MIRROR_REPLAY_BATCH) {
+            let ops = chunk
+                .iter()
+                .map(|job| job.clone().into_write_op(config))
+                .collect::<Vec<_>>();
+            match crate::wbq_execute_message_bundle_ops(&ops) {
+                Ok(()) => mirrored += ops.len(),
+                Err(error) => {
+                    record_mirror_failure(&format!("replay for project {project_slug}: {error}"));
+                    break;
+                }
+            }
+        }
+    }
+    compact_mirror_journal(storage_root)?;
+    Ok(mirrored)
+}
+
+/// Current mirror lag for the journal under `storage_root`.
+pub fn mirror_lag(storage_root: &Path, now_us: i64) -> std::io::Result<MirrorLag> {
+    let pending = pending_mirror_jobs(storage_root)?;
+    let oldest_pending_ts = pending.iter().map(|job| job.enqueued_ts).min();
+    Ok(MirrorLag {
+        pending_jobs: pending.len(),
+        oldest_pending_ts,
+        lag_us: oldest_pending_ts.map_or(0, |ts| now_us.saturating_sub(ts).max(0)),
+        failures_total: MIRROR_FAILURES_TOTAL.load(Ordering::Relaxed),
+        last_error: MIRROR_LAST_ERROR
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(slug: &str, id: i64, ts: i64) -> MirrorJob {
+        MirrorJob {
+            job_id: format!("{slug}:{id}"),
+            enqueued_ts: ts,
+            project_slug: slug.to_string(),
+            message_json: serde_json::json!({"id": id, "subject": "hi"}),
+            body_md: "body".to_string(),
+            sender: "BlueLake".to_string(),
+            recipients: vec!["RedFox".to_string()],
+            extra_paths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn journal_folds_done_markers_and_survives_torn_tail() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        append_mirror_job(root, &job("proj", 1, 10)).unwrap();
+        append_mirror_job(root, &job("proj", 2, 20)).unwrap();
+        append_mirror_job(root, &job("proj", 2, 20)).unwrap();
+
+        // Simulate a crash mid-append.
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(mirror_journal_path(root))
+            .unwrap();
+        file.write_all(b"{\"kind\":\"mirror_app").unwrap();
+        drop(file);
+
+        append_mirror_job(root, &job("proj", 3, 30)).unwrap();
+        mark_mirror_jobs_done(root, &["proj:1".to_string()]).unwrap();
+
+        let pending = pending_mirror_jobs(root).unwrap();
+        let ids = pending
+            .iter()
+            .map(|j| j.job_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["proj:2", "proj:3"]);
+
+        let lag = mirror_lag(root, 50).unwrap();
+        assert_eq!(lag.pending_jobs, 2);
+        assert_eq!(lag.oldest_pending_ts, Some(20));
+        assert_eq!(lag.lag_us, 30);
+
+        assert_eq!(compact_mirror_journal(root).unwrap(), 2);
+        let content = fs::read_to_string(mirror_journal_path(root)).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert_eq!(pending_mirror_jobs(root).unwrap(), pending);
+    }
+
+    #[test]
+    fn replay_mirrors_pending_jobs_idempotently() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = Config {
+            storage_root: tmp.path().to_path_buf(),
+            archive_mirror: mcp_agent_mail_core::ArchiveMirrorMode::Live,
+            ..Config::default()
+        };
+        let mut first = job("proj", 1, 0);
+        first.message_json = serde_json::json!({
+            "id": 1,
+            "subject": "Mirrored",
+            "created_ts": "2026-01-15T10:00:00Z",
+            "project": "proj",
+        });
+        let mut second = first.clone();
+        second.job_id = "proj:2".to_string();
+        second.message_json["id"] = serde_json::json!(2);
+        append_mirror_job(&config.storage_root, &first).unwrap();
+        append_mirror_job(&config.storage_root, &second).unwrap();
+
+        assert_eq!(replay_pending_jobs(&config, 0).unwrap(), 2);
+        assert!(
+            pending_mirror_jobs(&config.storage_root)
+                .unwrap()
+                .is_empty()
+        );
+        let archive = crate::ensure_archive(&config, "proj").unwrap();
+        assert!(archive.root.join("messages/2026/01").exists());
+
+        // A crash between the working-tree write and the done marker leaves
+        // the job pending; replaying it again must not be rejected.
+        append_mirror_job(&config.storage_root, &first).unwrap();
+        assert_eq!(replay_pending_jobs(&config, 0).unwrap(), 1);
+        assert_eq!(mirror_lag(&config.storage_root, 0).unwrap().pending_jobs, 0);
+        crate::flush_async_commits();
+    }
+
+    #[test]
+    fn tampered_job_is_skipped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        append_mirror_job(root, &job("proj", 7, 1)).unwrap();
+        let path = mirror_journal_path(root);
+        let content = fs::read_to_string(&path).unwrap();
+        fs::write(&path, content.replace("\"body\"", "\"edited\"")).unwrap();
+        assert!(pending_mirror_jobs(root).unwrap().is_empty());
+    }
+
+    #[test]
+    fn only_live_message_bundles_are_retryable() {
+        let mut config = Config::default();
+        let op = job("proj", 1, 0).into_write_op(&config);
+        assert!(!ops_retryable_by_mirror([&op]));
+        config.archive_mirror = mcp_agent_mail_core::ArchiveMirrorMode::Live;
+        let op = job("proj", 1, 0).into_write_op(&config);
+        assert!(ops_retryable_by_mirror([&op]));
+        let signal = WriteOp::ClearSignal {
+            config,
+            project_slug: "proj".to_string(),
+            agent_name: "BlueLake".to_string(),
+        };
+        assert!(!ops_retryable_by_mirror([&op, &signal]));
+    }
+}
@@ -0,0 +1,295 @@
+//! Move one project's archive to another archive root (`am archive relocate`).
+//!
+//! An archive root is a Git repository holding `projects/<slug>/`. Relocating
+//! a project moves that directory into the destination root (initializing it
+//! as an archive repository if needed), commits the files there, commits
+//! their removal from the source repository, and records the new location in
+//! the [`ArchiveLocations`] index so every reader finds it. The destination
+//! is then verified the way `git fsck` would check what we care about: every
+//! ref resolves, and every committed file is present in the object database
+//! and matches the working tree.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use git2::{ObjectType, Oid, Repository};
+use serde::Serialize;
+
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_core::archive_location::ArchiveLocations;
+
+use crate::{Result, StorageError};
+
+/// Outcome of [`relocate_project_archive`].
+#[derive(Debug, Clone, Serialize)]
+pub struct RelocateReport {
+    pub slug: String,
+    /// Archive root the project was read from.
+    pub from: PathBuf,
+    /// Archive root the project now lives in (or would, under `dry_run`).
+    pub to: PathBuf,
+    pub dry_run: bool,
+    /// Regular files under `projects/<slug>`.
+    pub files: usize,
+    pub bytes: u64,
+    /// Committed files checked against the destination object database;
+    /// zero under `dry_run`.
+    pub verified_files: usize,
+}
+
+/// Move `slug`'s archive from its current root to the archive root `to`.
+///
+/// `to` must be absolute and must not already hold `projects/<slug>`. With
+/// `dry_run` nothing is written; the report shows what would move. The
+/// caller must hold the mailbox activity lock so no writer touches the
+/// archive mid-move.
+///
+/// # Errors
+///
+/// Fails on an invalid slug or destination, a missing source archive, a
+/// symlink inside the archive, any I/O or Git failure, or if verification
+/// of the destination repository finds a missing or mismatched object.
+pub fn relocate_project_archive(
+    config: &Config,
+    slug: &str,
+    to: &Path,
+    dry_run: bool,
+) -> Result<RelocateReport> {
+    let slug = crate::validate_archive_component("project slug", slug)?;
+    if slug.starts_with('.') {
+        return Err(StorageError::InvalidPath(format!(
+            "invalid project slug '{slug}'"
+        )));
+    }
+    if !to.is_absolute() {
+        return Err(StorageError::InvalidPath(format!(
+            "relocation target must be an absolute path: {}",
+            to.display()
+        )));
+    }
+    let to = to.canonicalize().unwrap_or_else(|_| to.to_path_buf());
+
+    let default_root = config.archive_root().to_path_buf();
+    let mut locations = ArchiveLocations::load(&default_root);
+    let from = locations.root_for(&default_root, slug);
+    let src_dir = from.join("projects").join(slug);
+    if !fs::symlink_metadata(&src_dir).is_ok_and(|meta| meta.file_type().is_dir()) {
+        return Err(StorageError::InvalidPath(format!(
+            "no archive for project '{slug}' at {}",
+            src_dir.display()
+        )));
+    }
+    let src_dir_canonical = src_dir.canonicalize()?;
+    if to == from || to.starts_with(&src_dir_canonical) {
+        return Err(StorageError::InvalidPath(format!(
+            "project '{slug}' cannot be relocated to {}: it already lives there",
+            to.display()
+        )));
+    }
+    let dest_dir = to.join("projects").join(slug);
+    if fs::symlink_metadata(&dest_dir).is_ok() {
+        return Err(StorageError::InvalidPath(format!(
+            "{} already exists; refusing to overwrite it",
+            dest_dir.display()
+        )));
+    }
+
+    let mut rel_paths = Vec::new();
+    let mut bytes = 0;
+    collect_files(
+        &src_dir,
+        &format!("projects/{slug}"),
+        &mut rel_paths,
+        &mut bytes,
+    )?;
+    let mut report = RelocateReport {
+        slug: slug.to_string(),
+        from: from.clone(),
+        to: to.clone(),
+        dry_run,
+        files: rel_paths.len(),
+        bytes,
+        verified_files: 0,
+    };
+    if dry_run {
+        return Ok(report);
+    }
+
+    crate::ensure_dir(&to.join("projects"))?;
+    crate::ensure_repo(&to, config)?;
+    move_dir(&src_dir, &dest_dir)?;
+
+    let rel_refs: Vec<&str> = rel_paths.iter().map(String::as_str).collect();
+    crate::commit_paths_with_retry(
+        &to,
+        config,
+        &format!("archive: relocate {slug} from {}", from.display()),
+        &rel_refs,
+    )?;
+    if Repository::open(&from).is_ok() {
+        crate::commit_paths_with_retry(
+            &from,
+            config,
+            &format!("archive: relocate {slug} to {}", to.display()),
+            &rel_refs,
+        )?;
+    }
+
+    locations.set(&default_root, slug, &to);
+    locations.save(&default_root)?;
+
+    report.verified_files = verify_project_archive(&to, &rel_refs)?;
+    Ok(report)
+}
+
+/// Check that every ref in the repository at `root` resolves and that each
+/// of `rel_paths` is committed at HEAD, present in the object database, and
+/// identical to the working-tree file. Returns the number of files checked.
+///
+/// # Errors
+///
+/// Fails if the repository cannot be read or any check fails.
+pub fn verify_project_archive(root: &Path, rel_paths: &[&str]) -> Result<usize> {
+    let missing_refs = crate::recovery::detect_missing_refs(root)?;
+    if let Some(broken) = missing_refs.first() {
+        return Err(verification_error(
+            root,
+            &format!("ref {} points at missing object", broken.ref_name),
+        ));
+    }
+    let repo = Repository::open(root)?;
+    let odb = repo.odb()?;
+    let tree = repo.head()?.peel_to_tree()?;
+    for rel in rel_paths {
+        let entry = tree
+            .get_path(Path::new(rel))
+            .map_err(|_| verification_error(root, &format!("{rel} is not committed")))?;
+        if !odb.exists(entry.id()) {
+            return Err(verification_error(
+                root,
+                &format!("object {} for {rel} is missing", entry.id()),
+            ));
+        }
+        let on_disk = Oid::hash_file(ObjectType::Blob, root.join(rel))?;
+        if on_disk != entry.id() {
+            return Err(verification_error(
+                root,
+                &format!("{rel} differs from its committed blob"),
+            ));
+        }
+    }
+    Ok(rel_paths.len())
+}
+
+fn verification_error(root: &Path, detail: &str) -> StorageError {
+    StorageError::Io(io::Error::other(format!(
+        "archive verification failed in {}: {detail}",
+        root.display()
+    )))
+}
+
+/// Collect repo-relative paths of the regular files under `dir`, skipping
+/// `.lock` files. Symlinks are refused: the archive never contains them,
+/// and following one could move files from outside it.
+fn collect_files(
+    dir: &Path,
+    rel_prefix: &str,
+    rel_paths: &mut Vec<String>,
+    bytes: &mut u64,
+) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(fs::DirEntry::file_name);
+    for entry in entries {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            return Err(StorageError::InvalidPath(format!(
+                "non-UTF-8 file name in archive: {}",
+                entry.path().display()
+            )));
+        };
+        let rel = format!("{rel_prefix}/{name}");
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            return Err(StorageError::InvalidPath(format!(
+                "refusing to relocate archive containing symlink: {}",
+                entry.path().display()
+            )));
+        }
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &rel, rel_paths, bytes)?;
+        } else if file_type.is_file() && !name.ends_with(".lock") {
+            *bytes += entry.metadata()?.len();
+            rel_paths.push(rel);
+        }
+    }
+    Ok(())
+}
+
+/// Rename `src` to `dest`, falling back to copy-then-delete when they are on
+/// different filesystems.
+fn move_dir(src: &Path, dest: &Path) -> io::Result<()> {
+    if fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+    copy_dir(src, dest)?;
+    fs::remove_dir_all(src)
+}
+
+fn copy_dir(src: &Path, dest: &Path) -> io::Result<()> {
+    fs::create_dir(dest)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_for(root: &Path) -> Config {
+        Config {
+            storage_root: root.to_path_buf(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn relocate_moves_commits_and_indexes_the_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage_root = tmp.path().canonicalize().unwrap().join("storage");
+        let config = config_for(&storage_root);
+        let archive = crate::ensure_archive(&config, "proj").unwrap();
+        crate::write_project_metadata_with_config(&archive, &config, "/abs/proj").unwrap();
+        crate::flush_async_commits();
+
+        let target = tmp.path().join("volume-b");
+        let dry = relocate_project_archive(&config, "proj", &target, true).unwrap();
+        assert_eq!(dry.files, 1);
+        assert!(storage_root.join("projects/proj/project.json").exists());
+
+        let report = relocate_project_archive(&config, "proj", &target, false).unwrap();
+        assert_eq!(report.verified_files, 1);
+        assert!(!storage_root.join("projects/proj").exists());
+        assert!(target.join("projects/proj/project.json").exists());
+        assert_eq!(
+            mcp_agent_mail_core::archive_location::project_archive_dir(&storage_root, "proj"),
+            report.to.join("projects/proj")
+        );
+
+        // Archive opens and writes now land in the new root.
+        let reopened = crate::open_archive(&config, "proj").unwrap().unwrap();
+        assert_eq!(reopened.repo_root, report.to);
+
+        let err = relocate_project_archive(&config, "proj", &report.to, false).unwrap_err();
+        assert!(err.to_string().contains("already lives there"), "{err}");
+    }
+}
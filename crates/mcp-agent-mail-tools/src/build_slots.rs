@@ -5,6 +5,10 @@
 //! - Stores per-slot leases as JSON files under the per-project archive root:
 //!   `{storage_root}/projects/{project_slug}/build_slots/{slot}/{agent_hash}.json`
 //! - Conflicts are detected by scanning active (non-expired) leases.
+//! - A holder that records `holder_pid`/`heartbeat_ts` (`am-run` does) and
+//!   then stops refreshing the heartbeat for longer than
+//!   `BUILD_SLOT_HEARTBEAT_GRACE_SECONDS` no longer blocks others, even before
+//!   `expires_ts`: its process was killed or the machine went down.
 
 use fastmcp::prelude::*;
 use mcp_agent_mail_core::Config;
@@ -28,12 +32,22 @@ pub struct BuildSlotLease {
     pub expires_ts: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub released_ts: Option<String>,
+    /// PID of the process holding the slot, when it keeps a heartbeat.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder_pid: Option<u32>,
+    /// Last time the holder proved it was alive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_ts: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AcquireBuildSlotResponse {
     pub granted: BuildSlotLease,
     pub conflicts: Vec<BuildSlotLease>,
+    /// Unexpired leases that did not count as conflicts because their
+    /// holder's heartbeat went stale.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stale_heartbeat: Vec<BuildSlotLease>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -247,6 +261,43 @@ async fn resolve_canonical_agent_name(
     }
 }
 
+/// How often a heartbeating holder refreshes `heartbeat_ts`.
+pub const BUILD_SLOT_HEARTBEAT_INTERVAL_SECONDS: u64 = 30;
+
+/// Whether a lease's heartbeat is older than `grace_seconds` at `now`.
+///
+/// Leases without a heartbeat (holders that never opted in) and a grace of 0
+/// never count as stale; an unparseable heartbeat does.
+#[must_use]
+pub fn build_slot_heartbeat_is_stale(
+    heartbeat_ts: Option<&str>,
+    now: chrono::DateTime<chrono::Utc>,
+    grace_seconds: u64,
+) -> bool {
+    let Some(heartbeat_ts) = heartbeat_ts else {
+        return false;
+    };
+    if grace_seconds == 0 {
+        return false;
+    }
+    let Ok(heartbeat) = chrono::DateTime::parse_from_rfc3339(heartbeat_ts) else {
+        return true;
+    };
+    let grace = chrono::Duration::seconds(i64::try_from(grace_seconds).unwrap_or(i64::MAX));
+    heartbeat.with_timezone(&chrono::Utc) + grace < now
+}
+
+/// Split conflicts into live holders and holders whose heartbeat went stale.
+fn partition_stale_heartbeats(
+    conflicts: Vec<BuildSlotLease>,
+    now: chrono::DateTime<chrono::Utc>,
+    grace_seconds: u64,
+) -> (Vec<BuildSlotLease>, Vec<BuildSlotLease>) {
+    conflicts.into_iter().partition(|lease| {
+        !build_slot_heartbeat_is_stale(lease.heartbeat_ts.as_deref(), now, grace_seconds)
+    })
+}
+
 fn collect_slot_conflicts(
     active: Vec<BuildSlotLease>,
     agent_name: &str,
@@ -317,7 +368,11 @@ pub async fn acquire_build_slot(
         .map_err(|e| McpError::internal_error(format!("failed to create slot dir: {e}")))?;
 
     let active = read_active_leases(&slot_path, now);
-    let conflicts = collect_slot_conflicts(active, &agent_name, branch.as_deref(), is_exclusive);
+    let (conflicts, stale_heartbeat) = partition_stale_heartbeats(
+        collect_slot_conflicts(active, &agent_name, branch.as_deref(), is_exclusive),
+        now,
+        config.build_slot_heartbeat_grace_seconds,
+    );
 
     let lease_path = resolve_holder_lease_path(&slot_path, &agent_name, branch.as_deref());
 
@@ -329,13 +384,19 @@ pub async fn acquire_build_slot(
         acquired_ts: now.to_rfc3339(),
         expires_ts,
         released_ts: None,
+        holder_pid: None,
+        heartbeat_ts: None,
     };
 
     write_lease_json(&lease_path, &granted).map_err(|e| {
         McpError::internal_error(format!("failed to persist build slot lease: {e}"))
     })?;
 
-    let response = AcquireBuildSlotResponse { granted, conflicts };
+    let response = AcquireBuildSlotResponse {
+        granted,
+        conflicts,
+        stale_heartbeat,
+    };
     serde_json::to_string(&response)
         .map_err(|e| McpError::internal_error(format!("JSON error: {e}")))
 }
@@ -649,6 +710,8 @@ mod tests {
             acquired_ts: now.to_rfc3339(),
             expires_ts: (now + chrono::Duration::hours(1)).to_rfc3339(),
             released_ts: None,
+            holder_pid: None,
+            heartbeat_ts: None,
         };
         std::fs::write(
             dir.path().join("valid.json"),
@@ -711,6 +774,8 @@ mod tests {
             acquired_ts: now.to_rfc3339(),
             expires_ts: (now + chrono::Duration::hours(1)).to_rfc3339(),
             released_ts: None,
+            holder_pid: None,
+            heartbeat_ts: None,
         };
         let json = serde_json::to_string(&lease).unwrap();
         let parsed: BuildSlotLease = serde_json::from_str(&json).unwrap();
@@ -732,6 +797,8 @@ mod tests {
             acquired_ts: now.to_rfc3339(),
             expires_ts: now.to_rfc3339(),
             released_ts: Some(now.to_rfc3339()),
+            holder_pid: None,
+            heartbeat_ts: None,
         };
         let json = serde_json::to_string(&lease).unwrap();
         let parsed: BuildSlotLease = serde_json::from_str(&json).unwrap();
@@ -755,6 +822,8 @@ mod tests {
             acquired_ts: now.to_rfc3339(),
             expires_ts: (now + chrono::Duration::hours(1)).to_rfc3339(),
             released_ts: None,
+            holder_pid: None,
+            heartbeat_ts: None,
         };
         let conflict = BuildSlotLease {
             slot: "slot-x".to_string(),
@@ -764,10 +833,13 @@ mod tests {
             acquired_ts: now.to_rfc3339(),
             expires_ts: (now + chrono::Duration::hours(1)).to_rfc3339(),
             released_ts: None,
+            holder_pid: None,
+            heartbeat_ts: None,
         };
         let response = AcquireBuildSlotResponse {
             granted,
             conflicts: vec![conflict],
+            stale_heartbeat: Vec::new(),
         };
         let json = serde_json::to_string(&response).unwrap();
         let parsed: AcquireBuildSlotResponse = serde_json::from_str(&json).unwrap();
@@ -837,6 +909,8 @@ mod tests {
             acquired_ts: (now - chrono::Duration::hours(2)).to_rfc3339(),
             expires_ts: (now - chrono::Duration::hours(1)).to_rfc3339(), // expired
             released_ts: None,
+            holder_pid: None,
+            heartbeat_ts: None,
         };
         std::fs::write(
             dir.path().join("expired.json"),
@@ -883,6 +957,8 @@ mod tests {
             acquired_ts: now.to_rfc3339(),
             expires_ts: (now + chrono::Duration::minutes(30)).to_rfc3339(),
             released_ts: None,
+            holder_pid: None,
+            heartbeat_ts: None,
         }
     }
 
@@ -942,4 +1018,63 @@ mod tests {
             "the same agent should not self-conflict after a branch change"
         );
     }
+
+    // -----------------------------------------------------------------------
+    // Holder heartbeats
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn heartbeat_staleness_respects_grace() {
+        let now = chrono::Utc::now();
+        let at = |secs_ago: i64| (now - chrono::Duration::seconds(secs_ago)).to_rfc3339();
+
+        assert!(!build_slot_heartbeat_is_stale(None, now, 120));
+        assert!(!build_slot_heartbeat_is_stale(Some(&at(60)), now, 120));
+        assert!(!build_slot_heartbeat_is_stale(Some(&at(120)), now, 120));
+        assert!(build_slot_heartbeat_is_stale(Some(&at(121)), now, 120));
+        assert!(!build_slot_heartbeat_is_stale(Some(&at(3600)), now, 0));
+        assert!(build_slot_heartbeat_is_stale(Some("garbage"), now, 120));
+    }
+
+    #[test]
+    fn stale_heartbeat_holders_stop_conflicting() {
+        let now = chrono::Utc::now();
+        let mut dead = make_lease("BlueLake", Some("main"), true);
+        dead.holder_pid = Some(4242);
+        dead.heartbeat_ts = Some((now - chrono::Duration::seconds(600)).to_rfc3339());
+        let mut alive = make_lease("RedFox", Some("main"), true);
+        alive.holder_pid = Some(4343);
+        alive.heartbeat_ts = Some(now.to_rfc3339());
+        let legacy = make_lease("GreenPeak", Some("main"), true);
+
+        let conflicts = collect_slot_conflicts(vec![dead, alive, legacy], "GoldFox", None, true);
+        let (live, stale) = partition_stale_heartbeats(conflicts, now, 120);
+        let agents = |leases: &[BuildSlotLease]| -> Vec<String> {
+            leases.iter().map(|lease| lease.agent.clone()).collect()
+        };
+        assert_eq!(agents(&live), ["RedFox", "GreenPeak"]);
+        assert_eq!(agents(&stale), ["BlueLake"]);
+        assert_eq!(stale[0].holder_pid, Some(4242));
+    }
+
+    #[test]
+    fn heartbeat_fields_are_optional_on_disk() {
+        let lease = make_lease("BlueLake", None, true);
+        let json = serde_json::to_value(&lease).unwrap();
+        assert!(json.get("holder_pid").is_none());
+        assert!(json.get("heartbeat_ts").is_none());
+
+        let response = AcquireBuildSlotResponse {
+            granted: lease,
+            conflicts: Vec::new(),
+            stale_heartbeat: Vec::new(),
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("stale_heartbeat").is_none());
+        let parsed: AcquireBuildSlotResponse =
+            serde_json::from_str(r#"{"granted":{"slot":"s","agent":"a","branch":null,"exclusive":true,"acquired_ts":"t","expires_ts":"t"},"conflicts":[]}"#)
+                .unwrap();
+        assert!(parsed.stale_heartbeat.is_empty());
+        assert_eq!(parsed.granted.heartbeat_ts, None);
+    }
 }
@@ -8,6 +8,7 @@
 
 use fastmcp::prelude::*;
 use mcp_agent_mail_db::micros_to_iso;
+use mcp_agent_mail_db::queries::{AutoApproval, AutoApprovalScope};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use std::collections::HashMap;
@@ -61,6 +62,12 @@ pub struct SimpleContactEntry {
     pub reason: String,
     pub updated_ts: Option<String>,
     pub expires_ts: Option<String>,
+    /// How an approved link came to exist: `manual` or `auto`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_via: Option<String>,
+    /// Allowlist glob that matched the requester of an `auto` approval.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_pattern: Option<String>,
 }
 
 /// Agent policy response (legacy, richer format)
@@ -76,6 +83,15 @@ pub struct AgentPolicyResponse {
 pub struct SimplePolicyResponse {
     pub agent: String,
     pub policy: String,
+    /// Agent-name globs auto-approved under `auto`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_allow: Vec<String>,
+    /// TTL applied to auto-approved links.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_ttl_seconds: Option<i64>,
+    /// Auto-approvals one requester may collect per day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_daily_cap: Option<i64>,
 }
 
 /// Contact response for approve/deny.
@@ -235,6 +251,66 @@ pub(crate) fn resolve_contact_target(
     }
 }
 
+/// Approve `requester → target` on the target's behalf when its `auto`
+/// policy qualifiers allow it. Returns the approved link, or `None` when the
+/// qualifiers decline (requester not allowlisted, link blocked, or daily cap
+/// reached) and the contact is left as it was.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn auto_approve_contact(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    requester_project_id: i64,
+    requester: &mcp_agent_mail_db::AgentRow,
+    target_project_id: i64,
+    target: &mcp_agent_mail_db::AgentRow,
+    scope: AutoApprovalScope,
+    default_ttl_seconds: i64,
+) -> McpResult<Option<mcp_agent_mail_db::AgentLinkRow>> {
+    let approval = db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::auto_approve_contact(
+            ctx.cx(),
+            pool,
+            requester_project_id,
+            requester,
+            target_project_id,
+            target,
+            scope,
+            default_ttl_seconds,
+            "auto-approved by contact policy",
+        )
+        .await,
+    )?;
+    match approval {
+        AutoApproval::Approved { link, pattern } => {
+            tracing::debug!(
+                requester = %requester.name,
+                target = %target.name,
+                pattern = ?pattern,
+                "contact auto-approved"
+            );
+            Ok(Some(link))
+        }
+        AutoApproval::NotAllowed => Ok(None),
+        AutoApproval::Blocked => {
+            tracing::debug!(
+                requester = %requester.name,
+                target = %target.name,
+                "contact auto-approval skipped: link is blocked"
+            );
+            Ok(None)
+        }
+        AutoApproval::CapReached { cap } => {
+            tracing::warn!(
+                requester = %requester.name,
+                target = %target.name,
+                cap,
+                "daily auto-approval cap reached; contact stays pending"
+            );
+            Ok(None)
+        }
+    }
+}
+
 /// Parse `project:<slug>#<Name>` shorthand into a `(project_key, agent_name)` tuple.
 /// Falls back to the default project and raw agent name if no shorthand match.
 fn parse_contact_target(
@@ -543,6 +619,29 @@ pub async fn respond_contact(
         );
     }
     let (updated, link_row) = db_outcome_to_mcp_result(respond_out)?;
+    if accept
+        && let Err(e) = db_outcome_to_mcp_result(
+            mcp_agent_mail_db::queries::record_contact_approval(
+                ctx.cx(),
+                &pool,
+                source_project_id,
+                from_row.id.unwrap_or(0),
+                project_id,
+                to_row.id.unwrap_or(0),
+                mcp_agent_mail_db::queries::CONTACT_APPROVAL_MANUAL,
+                None,
+            )
+            .await,
+        )
+    {
+        // The link is already approved; only `contacts list` provenance is lost.
+        tracing::warn!(
+            from_agent = %from_agent,
+            to_agent = %to_agent,
+            error = %e,
+            "respond_contact: failed to record approval provenance"
+        );
+    }
 
     let response = RespondContactResponse {
         from: from_agent,
@@ -564,6 +663,8 @@ pub async fn respond_contact(
 ///
 /// # Returns
 /// Array of contact counterparties with `to`, `status`, `reason`, `updated_ts`, `expires_ts`.
+/// Approved links also carry `approved_via` (`manual` or `auto`) and, for
+/// allowlisted auto-approvals, the matching `approved_pattern`.
 ///
 /// The legacy Python-compatible shape uses the field name `to` for the
 /// counterparty. Outgoing links put the request target there; incoming links
@@ -636,10 +737,29 @@ pub async fn list_contacts(
         }
     }
 
+    // Approval provenance for approved links. A ledger entry older than the
+    // link's last update describes an earlier approval, so it is ignored.
+    let approvals = db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::latest_contact_approvals(ctx.cx(), &pool, agent_id).await,
+    )
+    .unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "list_contacts: failed to load approval provenance");
+        HashMap::new()
+    });
+    let provenance = |link: &mcp_agent_mail_db::AgentLinkRow| {
+        approvals
+            .get(&(link.a_agent_id, link.b_agent_id))
+            .filter(|approval| link.status == "approved" && approval.approved_ts >= link.updated_ts)
+            .map_or((None, None), |approval| {
+                (Some(approval.source.clone()), approval.pattern.clone())
+            })
+    };
+
     // Return simple array format with actual timestamps from the database.
     let mut contacts: Vec<SimpleContactEntry> =
         Vec::with_capacity(outgoing_rows.len() + incoming_rows.len());
     contacts.extend(outgoing_rows.into_iter().map(|r| {
+        let (approved_via, approved_pattern) = provenance(&r);
         SimpleContactEntry {
             to: agent_names
                 .get(&r.b_agent_id)
@@ -649,9 +769,12 @@ pub async fn list_contacts(
             reason: r.reason,
             updated_ts: Some(micros_to_iso(r.updated_ts)),
             expires_ts: r.expires_ts.map(micros_to_iso),
+            approved_via,
+            approved_pattern,
         }
     }));
     contacts.extend(incoming_rows.into_iter().map(|r| {
+        let (approved_via, approved_pattern) = provenance(&r);
         SimpleContactEntry {
             to: agent_names
                 .get(&r.a_agent_id)
@@ -661,6 +784,8 @@ pub async fn list_contacts(
             reason: r.reason,
             updated_ts: Some(micros_to_iso(r.updated_ts)),
             expires_ts: r.expires_ts.map(micros_to_iso),
+            approved_via,
+            approved_pattern,
         }
     }));
 
@@ -681,20 +806,73 @@ pub async fn list_contacts(
 /// - `project_key`: Project identifier
 /// - `agent_name`: Agent to configure
 /// - `policy`: Policy to set (open | auto | `contacts_only` | `block_all`)
+/// - `auto_allow`: Comma-separated agent-name globs approved automatically
+///   the first time they message this agent (`auto` only)
+/// - `auto_ttl_seconds`: TTL for automatically approved links (`auto` only)
+/// - `auto_daily_cap`: Auto-approvals one requester may collect per day
+///   before further handshakes stay pending (`auto` only)
+///
+/// Setting a policy replaces any qualifiers set before. Invalid globs are
+/// rejected here rather than when a contact is requested.
 ///
 /// # Returns
 /// Updated agent record
 ///
 /// # Conformance
-/// Python-parity.
-#[tool(description = "Set contact policy for an agent: open | auto | contacts_only | block_all.")]
+/// Python-parity for the first three parameters; the `auto` qualifiers are
+/// Rust-only.
+#[tool(
+    description = "Set contact policy for an agent: open | auto | contacts_only | block_all.\n\nWith `auto`, optionally auto-approve requesters whose names match `auto_allow` globs (comma-separated, e.g. \"Blue*,GreenLake\"), give those links `auto_ttl_seconds`, and cap auto-approvals per requester per day with `auto_daily_cap`."
+)]
 pub async fn set_contact_policy(
     ctx: &McpContext,
     project_key: String,
     agent_name: String,
     policy: String,
+    auto_allow: Option<String>,
+    auto_ttl_seconds: Option<i64>,
+    auto_daily_cap: Option<i64>,
 ) -> McpResult<String> {
     let policy_norm = parse_contact_policy(&policy);
+    let qualifiers = mcp_agent_mail_core::ContactAutoQualifiers::parse(
+        auto_allow.as_deref(),
+        auto_ttl_seconds,
+        auto_daily_cap,
+    )
+    .map_err(|detail| {
+        let field = if auto_ttl_seconds.is_some_and(|ttl| ttl <= 0) {
+            "auto_ttl_seconds"
+        } else if auto_daily_cap.is_some_and(|cap| cap <= 0) {
+            "auto_daily_cap"
+        } else {
+            "auto_allow"
+        };
+        legacy_tool_error(
+            "INVALID_ARGUMENT",
+            format!(
+                "Invalid argument value: {detail}. Check that all parameters have valid values."
+            ),
+            true,
+            serde_json::json!({
+                "field": field,
+                "error_detail": detail,
+            }),
+        )
+    })?;
+    if !qualifiers.is_empty() && policy_norm != "auto" {
+        return Err(legacy_tool_error(
+            "INVALID_ARGUMENT",
+            format!(
+                "Invalid argument value: auto_allow, auto_ttl_seconds and auto_daily_cap \
+only apply to policy 'auto', got '{policy_norm}'."
+            ),
+            true,
+            serde_json::json!({
+                "field": "policy",
+                "error_detail": policy_norm,
+            }),
+        ));
+    }
     // Normalize agent name
     let agent_name =
         mcp_agent_mail_core::models::normalize_agent_name(&agent_name).unwrap_or(agent_name);
@@ -715,9 +893,22 @@ pub async fn set_contact_policy(
         .await,
     )?;
 
+    db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::set_contact_policy_qualifiers(
+            ctx.cx(),
+            &pool,
+            updated_agent.id.unwrap_or(0),
+            &qualifiers,
+        )
+        .await,
+    )?;
+
     let response = SimplePolicyResponse {
         agent: updated_agent.name,
         policy: updated_agent.contact_policy,
+        auto_allow: qualifiers.patterns().map(str::to_string).collect(),
+        auto_ttl_seconds: qualifiers.ttl_seconds,
+        auto_daily_cap: qualifiers.daily_cap,
     };
 
     tracing::debug!(
@@ -829,12 +1020,16 @@ mod tests {
             reason: "collaboration".into(),
             updated_ts: None,
             expires_ts: None,
+            approved_via: Some("auto".into()),
+            approved_pattern: Some("Red*".into()),
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
         assert_eq!(json["to"], "RedFox");
         assert_eq!(json["status"], "approved");
         assert!(json["updated_ts"].is_null());
+        assert_eq!(json["approved_via"], "auto");
+        assert_eq!(json["approved_pattern"], "Red*");
     }
 
     #[test]
@@ -842,11 +1037,17 @@ mod tests {
         let r = SimplePolicyResponse {
             agent: "BlueLake".into(),
             policy: "contacts_only".into(),
+            auto_allow: Vec::new(),
+            auto_ttl_seconds: None,
+            auto_daily_cap: None,
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
         assert_eq!(json["agent"], "BlueLake");
         assert_eq!(json["policy"], "contacts_only");
+        // Qualifiers are omitted unless set, keeping the Python shape.
+        assert!(json.get("auto_allow").is_none());
+        assert!(json.get("auto_daily_cap").is_none());
     }
 
     #[test]
@@ -1066,6 +1267,8 @@ mod tests {
             reason: "testing".into(),
             updated_ts: Some("2026-02-08T00:00:00Z".into()),
             expires_ts: Some("2026-02-15T00:00:00Z".into()),
+            approved_via: None,
+            approved_pattern: None,
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
@@ -0,0 +1,242 @@
+//! Runtime registry for extension tools supplied by an embedding server.
+//!
+//! Built-in tools are described by the static tables in this crate
+//! (`TOOL_CLUSTER_MAP`, `TOOL_META_MAP`, the tooling directory). A server
+//! that embeds Agent Mail can register additional tools at construction;
+//! their metadata and counters live here so cluster filtering, metrics, and
+//! the `resource://tooling/*` resources treat them like built-ins.
+//!
+//! Extension tools get metric indices after the built-ins
+//! (`TOOL_CLUSTER_MAP.len() + slot`). Registration is append-only and keyed
+//! by name, so rebuilding the server (e.g. on supervisor restart) keeps
+//! indices stable.
+
+#![forbid(unsafe_code)]
+
+use mcp_agent_mail_core::{HistogramSnapshot, Log2Histogram};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::TOOL_CLUSTER_MAP;
+
+/// Metadata describing an extension tool.
+#[derive(Debug, Clone)]
+pub struct ExtensionToolSpec {
+    pub name: &'static str,
+    pub cluster: &'static str,
+    pub summary: String,
+    /// JSON Schema for the tool arguments (`properties` / `required` feed
+    /// `resource://tooling/schemas`).
+    pub input_schema: Value,
+    pub capabilities: Vec<String>,
+    pub complexity: String,
+}
+
+impl ExtensionToolSpec {
+    /// Required and optional argument names derived from `input_schema`.
+    #[must_use]
+    pub fn argument_names(&self) -> (Vec<String>, Vec<String>) {
+        let required: Vec<String> = self
+            .input_schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let optional = self
+            .input_schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|props| {
+                props
+                    .keys()
+                    .filter(|name| !required.contains(name))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        (required, optional)
+    }
+}
+
+struct ExtensionTool {
+    spec: ExtensionToolSpec,
+    calls: AtomicU64,
+    errors: AtomicU64,
+    rate_limited: AtomicU64,
+    latency: Log2Histogram,
+}
+
+/// Counter snapshot for one extension tool.
+pub(crate) struct ExtensionCounters {
+    pub spec: ExtensionToolSpec,
+    pub calls: u64,
+    pub errors: u64,
+    pub rate_limited: u64,
+    pub latency: HistogramSnapshot,
+}
+
+static EXTENSION_TOOLS: RwLock<Vec<Arc<ExtensionTool>>> = RwLock::new(Vec::new());
+
+fn registered() -> std::sync::RwLockReadGuard<'static, Vec<Arc<ExtensionTool>>> {
+    EXTENSION_TOOLS
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+fn slot_of(tool_index: usize) -> Option<Arc<ExtensionTool>> {
+    let slot = tool_index.checked_sub(TOOL_CLUSTER_MAP.len())?;
+    registered().get(slot).cloned()
+}
+
+/// Register an extension tool and return its metric index.
+///
+/// Re-registering a name returns the existing index; the first registration's
+/// metadata wins. Callers are expected to reject names that collide with
+/// built-in tools before getting here.
+#[must_use]
+pub fn register_extension_tool(spec: ExtensionToolSpec) -> usize {
+    let slot = {
+        let mut tools = EXTENSION_TOOLS
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(slot) = tools.iter().position(|tool| tool.spec.name == spec.name) {
+            slot
+        } else {
+            tools.push(Arc::new(ExtensionTool {
+                spec,
+                calls: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+                rate_limited: AtomicU64::new(0),
+                latency: Log2Histogram::new(),
+            }));
+            tools.len() - 1
+        }
+    };
+    TOOL_CLUSTER_MAP.len() + slot
+}
+
+/// Metric index of a registered extension tool.
+#[must_use]
+pub fn extension_tool_index(tool_name: &str) -> Option<usize> {
+    registered()
+        .iter()
+        .position(|tool| tool.spec.name == tool_name)
+        .map(|slot| TOOL_CLUSTER_MAP.len() + slot)
+}
+
+/// Cluster of a registered extension tool.
+#[must_use]
+pub fn extension_tool_cluster(tool_name: &str) -> Option<&'static str> {
+    registered()
+        .iter()
+        .find(|tool| tool.spec.name == tool_name)
+        .map(|tool| tool.spec.cluster)
+}
+
+/// Metadata for every registered extension tool, in registration order.
+#[must_use]
+pub fn extension_tools() -> Vec<ExtensionToolSpec> {
+    registered().iter().map(|tool| tool.spec.clone()).collect()
+}
+
+pub(crate) fn record_call(tool_index: usize) {
+    if let Some(tool) = slot_of(tool_index) {
+        tool.calls.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_error(tool_index: usize) {
+    if let Some(tool) = slot_of(tool_index) {
+        tool.errors.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_rate_limited(tool_index: usize) {
+    if let Some(tool) = slot_of(tool_index) {
+        tool.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn record_latency(tool_index: usize, latency_us: u64) {
+    if let Some(tool) = slot_of(tool_index) {
+        tool.latency.record(latency_us);
+    }
+}
+
+pub(crate) fn reset_counters() {
+    for tool in registered().iter() {
+        tool.calls.store(0, Ordering::Relaxed);
+        tool.errors.store(0, Ordering::Relaxed);
+        tool.rate_limited.store(0, Ordering::Relaxed);
+        tool.latency.reset();
+    }
+}
+
+pub(crate) fn reset_latencies() {
+    for tool in registered().iter() {
+        tool.latency.reset();
+    }
+}
+
+pub(crate) fn counter_snapshots() -> Vec<ExtensionCounters> {
+    registered()
+        .iter()
+        .map(|tool| ExtensionCounters {
+            spec: tool.spec.clone(),
+            calls: tool.calls.load(Ordering::Relaxed),
+            errors: tool.errors.load(Ordering::Relaxed),
+            rate_limited: tool.rate_limited.load(Ordering::Relaxed),
+            latency: tool.latency.snapshot(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(name: &'static str) -> ExtensionToolSpec {
+        ExtensionToolSpec {
+            name,
+            cluster: "ext_tests",
+            summary: "test extension".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "ticket_id": { "type": "string" },
+                    "verbose": { "type": "boolean" }
+                },
+                "required": ["ticket_id"]
+            }),
+            capabilities: vec!["tickets".to_string()],
+            complexity: "low".to_string(),
+        }
+    }
+
+    #[test]
+    fn registration_is_idempotent_and_indexed_after_builtins() {
+        let idx = register_extension_tool(spec("ext_idempotent_probe"));
+        assert!(idx >= TOOL_CLUSTER_MAP.len());
+        assert_eq!(register_extension_tool(spec("ext_idempotent_probe")), idx);
+        assert_eq!(extension_tool_index("ext_idempotent_probe"), Some(idx));
+        assert_eq!(
+            extension_tool_cluster("ext_idempotent_probe"),
+            Some("ext_tests")
+        );
+        assert_eq!(extension_tool_index("health_check"), None);
+    }
+
+    #[test]
+    fn argument_names_follow_json_schema() {
+        let (required, optional) = spec("ext_schema_probe").argument_names();
+        assert_eq!(required, vec!["ticket_id".to_string()]);
+        assert_eq!(optional, vec!["verbose".to_string()]);
+    }
+}
@@ -971,6 +971,15 @@ pub struct IntegrityHealthResponse {
     pub last_check_ts: i64,
     pub checks_total: u64,
     pub failures_total: u64,
+    /// `AGENT_MAIL_INTEGRITY_PROBE` in effect: "canonical", "bespoke", or "both".
+    #[serde(default)]
+    pub probe_authority: String,
+    /// Engine whose verdict stood on the last check: "bespoke" or "canonical".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict_probe: Option<String>,
+    /// Bespoke findings canonical SQLite overruled on the last check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probe_discrepancy: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1287,11 +1296,19 @@ pub fn health_check(_ctx: &McpContext) -> McpResult<String> {
         integrity: {
             let im = mcp_agent_mail_db::integrity_metrics();
             if im.checks_total > 0 {
+                let last_verdict = mcp_agent_mail_db::last_probe_verdict();
                 Some(IntegrityHealthResponse {
                     last_ok_ts: im.last_ok_ts,
                     last_check_ts: im.last_check_ts,
                     checks_total: im.checks_total,
                     failures_total: im.failures_total,
+                    probe_authority: mcp_agent_mail_db::IntegrityProbeAuthority::from_env()
+                        .as_str()
+                        .to_string(),
+                    verdict_probe: last_verdict
+                        .as_ref()
+                        .map(|verdict| verdict.source.as_str().to_string()),
+                    probe_discrepancy: last_verdict.and_then(|verdict| verdict.discrepancy),
                 })
             } else {
                 None
@@ -1393,6 +1410,23 @@ Check that all parameters have valid values."
         .map_err(|e| McpError::internal_error(format!("JSON error: {e}")))
 }
 
+/// Refuse names on the operator's reserved-name deny list
+/// (`AGENT_MAIL_RESERVED_AGENT_NAMES` / `AGENT_MAIL_RESERVED_AGENT_NAMES_FILE`).
+fn reject_reserved_agent_name(name: &str) -> McpResult<()> {
+    match mcp_agent_mail_core::reserved_agent_name_pattern(name) {
+        Some(pattern) => Err(legacy_tool_error(
+            "AGENT_NAME_RESERVED",
+            format!(
+                "Agent name '{name}' is reserved by the operator (matches pattern '{pattern}'). \
+                 Choose a different name or omit it to auto-generate one."
+            ),
+            true,
+            json!({ "provided": name, "pattern": pattern }),
+        )),
+        None => Ok(()),
+    }
+}
+
 /// Register or update an agent identity within a project.
 ///
 /// # Parameters
@@ -1494,6 +1528,7 @@ pub async fn register_agent(
         }
         None => generate_agent_name(),
     };
+    reject_reserved_agent_name(&agent_name)?;
 
     // Validate and normalize attachments_policy (case-insensitive, trimmed)
     let raw_policy = attachments_policy.unwrap_or_else(|| "auto".to_string());
@@ -1807,6 +1842,7 @@ pub async fn create_agent_identity(
         }
         None => generate_agent_name(),
     };
+    reject_reserved_agent_name(&agent_name)?;
 
     // Validate and normalize attachments_policy (case-insensitive, trimmed)
     let raw_policy = attachments_policy.unwrap_or_else(|| "auto".to_string());
@@ -2255,13 +2291,14 @@ pub fn cleanup_pane_identities(
 const LIST_AGENTS_DEFAULT_MAX: usize = 250;
 
 #[tool(
-    description = "List registered agents in a project, most-recently-active first.\n\nReturns agent name, role (program), model, task description, registration time (inception_ts), and last seen (last_active_ts).\n\nThe result is bounded to avoid blowing the calling agent's context window on long-lived projects that accumulate agents across many short-lived swarms: at most `limit` agents (default 250) are returned, optionally restricted to those active within `active_within_days`.\n\nParameters\n----------\nproject_key : str\n    Project slug or human key.\nlimit : Optional[int]\n    Maximum number of agents to return (most-recently-active first). Defaults to 250; values above 250 are clamped to 250.\nactive_within_days : Optional[int]\n    If provided, only return agents whose last_active_ts is within this many days. Omit to include all agents (subject to limit).\n\nReturns\n-------\nstr (JSON)\n    Array of agent objects with fields: name, program, model, task_description, inception_ts, last_active_ts, contact_policy. Ordered by last_active_ts descending."
+    description = "List registered agents in a project, most-recently-active first.\n\nReturns agent name, role (program), model, task description, when the task was last set (task_updated_ts), registration time (inception_ts), and last seen (last_active_ts).\n\nThe result is bounded to avoid blowing the calling agent's context window on long-lived projects that accumulate agents across many short-lived swarms: at most `limit` agents (default 250) are returned, optionally restricted to those active within `active_within_days`. Agents retired by `am agents apply --prune` are omitted unless `include_retired` is true.\n\nParameters\n----------\nproject_key : str\n    Project slug or human key.\nlimit : Optional[int]\n    Maximum number of agents to return (most-recently-active first). Defaults to 250; values above 250 are clamped to 250.\nactive_within_days : Optional[int]\n    If provided, only return agents whose last_active_ts is within this many days. Omit to include all agents (subject to limit).\ninclude_retired : Optional[bool]\n    Also return retired agents. Defaults to false.\n\nReturns\n-------\nstr (JSON)\n    Array of agent objects with fields: name, program, model, task_description, task_updated_ts, inception_ts, last_active_ts, contact_policy. Ordered by last_active_ts descending."
 )]
 pub async fn list_agents(
     ctx: &McpContext,
     project_key: String,
     limit: Option<u32>,
     active_within_days: Option<u32>,
+    include_retired: Option<bool>,
 ) -> McpResult<String> {
     let pool = get_read_db_pool(ctx.cx()).await?;
     let project = resolve_project(ctx, &pool, &project_key).await?;
@@ -2290,18 +2327,27 @@ pub async fn list_agents(
             project_id,
             min_last_active_ts,
             Some(effective_limit),
+            include_retired.unwrap_or(false),
         )
         .await,
     )?;
 
+    let task_updated = db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::list_agent_task_updated_ts(ctx.cx(), &pool, project_id).await,
+    )?;
+
     let entries: Vec<serde_json::Value> = agents
         .into_iter()
         .map(|a| {
+            let task_updated_ts =
+                a.id.and_then(|id| task_updated.get(&id).copied())
+                    .unwrap_or(a.inception_ts);
             json!({
                 "name": a.name,
                 "program": a.program,
                 "model": a.model,
                 "task_description": a.task_description,
+                "task_updated_ts": micros_to_iso(task_updated_ts),
                 "inception_ts": micros_to_iso(a.inception_ts),
                 "last_active_ts": micros_to_iso(a.last_active_ts),
                 "contact_policy": a.contact_policy,
@@ -3619,9 +3665,10 @@ body
                 rt.block_on(async {
                     let cx = Cx::for_testing();
                     let ctx = McpContext::new(cx.clone(), 1);
-                    let response = list_agents(&ctx, "/archive-project".to_string(), None, None)
-                        .await
-                        .expect("list_agents should succeed");
+                    let response =
+                        list_agents(&ctx, "/archive-project".to_string(), None, None, None)
+                            .await
+                            .expect("list_agents should succeed");
                     let value: serde_json::Value =
                         serde_json::from_str(&response).expect("parse list_agents json");
                     let agents = value.as_array().expect("agents array");
@@ -28,6 +28,7 @@ mod archive_read;
 pub mod build_slots;
 pub mod contacts;
 pub mod degraded_intents;
+pub mod extensions;
 pub mod identity;
 pub mod llm;
 pub mod macros;
@@ -40,6 +41,7 @@ pub mod reservation_parity;
 pub mod reservations;
 pub mod resources;
 pub mod search;
+pub mod webhooks;
 
 // Re-export tool handlers for server registration
 pub use build_slots::*;
@@ -49,8 +51,9 @@ pub use macros::*;
 pub use messaging::*;
 pub use metrics::{
     LatencySnapshot, MetricsSnapshotEntry, record_call, record_call_idx, record_error,
-    record_error_idx, record_latency, record_latency_idx, reset_tool_latencies, reset_tool_metrics,
-    slow_tools, tool_index, tool_meta, tool_metrics_snapshot, tool_metrics_snapshot_full,
+    record_error_idx, record_latency, record_latency_idx, record_rate_limited,
+    record_rate_limited_idx, reset_tool_latencies, reset_tool_metrics, slow_tools, tool_index,
+    tool_meta, tool_metrics_snapshot, tool_metrics_snapshot_full,
 };
 pub use products::*;
 pub use reservation_parity::*;
@@ -723,8 +726,13 @@ pub mod tool_util {
             &storage_root,
             sqlite_path.as_deref().map(Path::new),
         );
-        let pool = get_or_create_pool(&cfg)
-            .map_err(|error| McpError::internal_error(error.to_string()))?;
+        // Safe mode: write tools are rejected at dispatch, and the tools that
+        // remain must not migrate or recover the mailbox on first acquire.
+        let pool = if mcp_agent_mail_core::safe_mode::is_active() {
+            get_live_read_db_pool()?
+        } else {
+            get_or_create_pool(&cfg).map_err(|error| McpError::internal_error(error.to_string()))?
+        };
         Ok(WriteDbPool {
             pool,
             _guard: guard,
@@ -753,6 +761,9 @@ pub mod tool_util {
         if let Some(pool) = mcp_agent_mail_db::get_cached_pool(&cfg) {
             return Ok(pool);
         }
+        if mcp_agent_mail_core::safe_mode::is_active() {
+            return get_live_read_db_pool();
+        }
         let sqlite_path =
             if mcp_agent_mail_core::disk::is_sqlite_memory_database_url(&cfg.database_url) {
                 None
@@ -1186,6 +1197,10 @@ pub mod tool_util {
     }
 
     pub async fn get_read_db_pool(cx: &asupersync::Cx) -> McpResult<ToolReadPool> {
+        // Safe mode never publishes archive snapshots; read the live file.
+        if mcp_agent_mail_core::safe_mode::is_active() {
+            return get_live_read_db_pool().map(ToolReadPool::live);
+        }
         match open_read_db_pool(cx).await {
             Ok(Some(pool)) => Ok(pool),
             Ok(None) => get_live_read_db_pool().map(ToolReadPool::live),
@@ -2598,6 +2613,43 @@ pub fn tool_cluster(tool_name: &str) -> Option<&'static str> {
         .iter()
         .find(|(name, _)| *name == tool_name)
         .map(|(_, cluster)| *cluster)
+        .or_else(|| extensions::extension_tool_cluster(tool_name))
+}
+
+/// Tools a safe-mode server still serves: pure reads that never touch the
+/// archive or mutate the mailbox. Everything else is rejected with
+/// [`safe_mode_tool_error`].
+pub const SAFE_MODE_TOOLS: &[&str] = &[
+    "health_check",
+    "whois",
+    "list_agents",
+    "fetch_inbox",
+    "list_contacts",
+    "check_file_reservation_conflicts",
+    "search_messages",
+    "summarize_thread",
+    "search_messages_product",
+    "fetch_inbox_product",
+    "summarize_thread_product",
+];
+
+#[must_use]
+pub fn is_safe_mode_tool(tool_name: &str) -> bool {
+    SAFE_MODE_TOOLS.contains(&tool_name)
+}
+
+/// Error returned for a tool call that safe mode does not serve.
+#[must_use]
+pub fn safe_mode_tool_error(tool_name: &str) -> fastmcp::McpError {
+    tool_util::legacy_tool_error(
+        mcp_agent_mail_core::safe_mode::ERROR_TYPE,
+        format!(
+            "Server is running in safe mode (read-only emergency access); '{tool_name}' is \
+             unavailable. Restart the server without --safe-mode to write."
+        ),
+        false,
+        serde_json::json!({ "tool": tool_name }),
+    )
 }
 
 #[cfg(test)]
@@ -2658,6 +2710,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn safe_mode_serves_only_known_read_tools() {
+        for name in SAFE_MODE_TOOLS {
+            assert!(
+                tool_cluster(name).is_some(),
+                "{name} is not a registered tool"
+            );
+        }
+        for write_tool in [
+            "send_message",
+            "reply_message",
+            "mark_message_read",
+            "acknowledge_message",
+            "file_reservation_paths",
+            "release_file_reservations",
+            "register_agent",
+            "ensure_project",
+            "macro_start_session",
+            "acquire_build_slot",
+        ] {
+            assert!(
+                !is_safe_mode_tool(write_tool),
+                "{write_tool} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn safe_mode_tool_error_carries_safe_mode_type() {
+        let err = safe_mode_tool_error("send_message");
+        let data = err.data.expect("structured error payload");
+        assert_eq!(data["error"]["type"], "SAFE_MODE");
+        assert_eq!(data["error"]["recoverable"], false);
+        assert_eq!(data["error"]["data"]["tool"], "send_message");
+    }
+
     // -- patterns_overlap tests --
 
     #[test]
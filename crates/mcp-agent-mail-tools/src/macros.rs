@@ -25,8 +25,11 @@ use crate::llm;
 use crate::messaging::InboxMessage;
 use crate::reservations::{ReleaseResult, ReservationResponse};
 use crate::search::{ExampleMessage, ThreadSummary};
-use crate::tool_util::{db_outcome_to_mcp_result, get_db_pool, legacy_tool_error, resolve_project};
+use crate::tool_util::{
+    db_outcome_to_mcp_result, get_db_pool, legacy_tool_error, resolve_agent, resolve_project,
+};
 use mcp_agent_mail_db::micros_to_iso;
+use mcp_agent_mail_db::queries::AutoApprovalScope;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
@@ -37,6 +40,31 @@ pub struct StartSessionResponse {
     pub agent: AgentResponse,
     pub file_reservations: ReservationResponse,
     pub inbox: Vec<InboxMessage>,
+    /// Present when the session brought a retired agent back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revived: Option<RevivalSummary>,
+}
+
+/// Mail released into a revived agent's inbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevivalSummary {
+    pub queued_messages: u64,
+    pub message: String,
+}
+
+impl RevivalSummary {
+    #[must_use]
+    pub fn new(queued_messages: u64) -> Self {
+        let noun = if queued_messages == 1 {
+            "message was"
+        } else {
+            "messages were"
+        };
+        Self {
+            queued_messages,
+            message: format!("{queued_messages} {noun} queued while you were retired"),
+        }
+    }
 }
 
 /// Prepare thread response
@@ -114,11 +142,13 @@ fn parse_macro_inbox_limit(inbox_limit: Option<i32>) -> McpResult<i32> {
 /// - `file_reservation_ttl_seconds`: TTL for reservations
 /// - `inbox_limit`: Max inbox messages to fetch
 ///
+/// A retired agent is revived and its deferred mail released first.
+///
 /// # Conformance
 /// Python-parity.
 #[allow(clippy::too_many_arguments)]
 #[tool(
-    description = "Macro helper that boots a project session: ensure project, register agent,\noptionally file_reservation paths, and fetch the latest inbox snapshot."
+    description = "Macro helper that boots a project session: ensure project, register agent,\noptionally file_reservation paths, and fetch the latest inbox snapshot.\n\nRetired agents\n--------------\nStarting a session revives a retired agent: mail queued while it was retired is released into the\ninbox and the response carries `revived: {\"queued_messages\": N, \"message\": ...}`."
 )]
 pub async fn macro_start_session(
     ctx: &McpContext,
@@ -184,11 +214,22 @@ pub async fn macro_start_session(
     .await?;
     let agent: AgentResponse = parse_json(agent_json, "agent")?;
 
+    // Starting a session is how a retired agent comes back: lift the
+    // retirement and release the mail deferred in the meantime before the
+    // inbox is fetched below.
+    let pool = get_db_pool()?;
+    let revived = db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::revive_agent(ctx.cx(), &pool, agent.id).await,
+    )?
+    .map(RevivalSummary::new);
+
     let reservation_result = if let Some(paths) = file_reservation_paths {
         if paths.is_empty() {
             ReservationResponse {
                 granted: Vec::new(),
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             }
         } else {
             let ttl = file_reservation_ttl_seconds.map_or(3600, |t| t.clamp(60, 31_536_000));
@@ -210,6 +251,8 @@ pub async fn macro_start_session(
                 Some(ttl),
                 Some(true),
                 Some(reason),
+                None,
+                None,
             )
             .await?;
             parse_json(reservation_json, "file_reservations")?
@@ -218,6 +261,8 @@ pub async fn macro_start_session(
         ReservationResponse {
             granted: Vec::new(),
             conflicts: Vec::new(),
+            lease_token: None,
+            atomic_rollback: false,
         }
     };
 
@@ -232,6 +277,9 @@ pub async fn macro_start_session(
         None,
         None,
         None,
+        None,
+        None,
+        None,
     )
     .await?;
     let inbox: Vec<InboxMessage> = parse_json(inbox_json, "inbox")?;
@@ -241,6 +289,7 @@ pub async fn macro_start_session(
         agent,
         file_reservations: reservation_result,
         inbox,
+        revived,
     };
 
     tracing::debug!(
@@ -433,6 +482,9 @@ pub async fn macro_prepare_thread(
         None,
         None,
         None,
+        None,
+        None,
+        None,
     )
     .await?;
     let inbox: Vec<InboxMessage> = parse_json(inbox_json, "inbox")?;
@@ -530,6 +582,8 @@ pub async fn macro_file_reservation_cycle(
                 .clone()
                 .unwrap_or_else(|| "macro-file_reservation".to_string()),
         ),
+        None,
+        None,
     )
     .await
     {
@@ -550,6 +604,7 @@ pub async fn macro_file_reservation_cycle(
             agent_name.clone(),
             Some(paths),
             None,
+            None,
         )
         .await
         {
@@ -686,22 +741,52 @@ pub async fn macro_contact_handshake(
     .await?;
     let request_val: Value = parse_json(request_json, "request")?;
 
+    // Auto-accept goes through the target's `auto` qualifiers: an allowlist
+    // that excludes the requester, or an exhausted daily cap, leaves the
+    // request pending and the response empty.
     let response_val = if should_auto_accept {
-        let respond_json = crate::contacts::respond_contact(
+        let source_project_id = source_project.id.unwrap_or(0);
+        let target_project_id = target_project.id.unwrap_or(0);
+        let requester_row = resolve_agent(
             ctx,
-            target_project_key.clone(),
-            target_agent_name.clone(),
-            from_agent.clone(),
-            if is_cross_project {
-                Some(source_project_key.clone())
-            } else {
-                None
-            },
-            true,
-            Some(ttl),
+            &pool,
+            source_project_id,
+            &from_agent,
+            &source_project.slug,
+            &source_project_key,
         )
         .await?;
-        Some(parse_json(respond_json, "response")?)
+        let target_row = resolve_agent(
+            ctx,
+            &pool,
+            target_project_id,
+            &target_agent_name,
+            &target_project.slug,
+            &target_project_key,
+        )
+        .await?;
+        crate::contacts::auto_approve_contact(
+            ctx,
+            &pool,
+            source_project_id,
+            &requester_row,
+            target_project_id,
+            &target_row,
+            AutoApprovalScope::Requested,
+            ttl,
+        )
+        .await?
+        .map(|link| {
+            serde_json::to_value(crate::contacts::RespondContactResponse {
+                from: from_agent.clone(),
+                to: target_agent_name.clone(),
+                approved: true,
+                expires_ts: link.expires_ts.map(micros_to_iso),
+                updated: 1,
+            })
+        })
+        .transpose()
+        .map_err(|e| McpError::internal_error(format!("JSON serialization error: {e}")))?
     } else {
         None
     };
@@ -736,6 +821,10 @@ pub async fn macro_contact_handshake(
                 None,
                 None, // auto_contact_if_blocked
                 None, // sender_token
+                None, // ack_receipt
+                None, // agent_secret
+                None, // expires_in_seconds
+                None, // fail_on_retired
             )
             .await?;
             Some(parse_json(welcome_json, "welcome_message")?)
@@ -929,8 +1018,11 @@ mod tests {
             file_reservations: ReservationResponse {
                 granted: Vec::new(),
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             },
             inbox: Vec::new(),
+            revived: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let parsed: StartSessionResponse = serde_json::from_str(&json).unwrap();
@@ -938,6 +1030,21 @@ mod tests {
         assert_eq!(parsed.agent.name, "BlueLake");
         assert!(parsed.file_reservations.granted.is_empty());
         assert!(parsed.inbox.is_empty());
+        assert!(!json.contains("revived"));
+    }
+
+    #[test]
+    fn revival_summary_reports_queued_mail() {
+        let summary = RevivalSummary::new(3);
+        assert_eq!(summary.queued_messages, 3);
+        assert_eq!(
+            summary.message,
+            "3 messages were queued while you were retired"
+        );
+        assert_eq!(
+            RevivalSummary::new(1).message,
+            "1 message was queued while you were retired"
+        );
     }
 
     // -----------------------------------------------------------------------
@@ -950,6 +1057,8 @@ mod tests {
             file_reservations: ReservationResponse {
                 granted: Vec::new(),
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             },
             released: None,
         };
@@ -964,6 +1073,8 @@ mod tests {
             file_reservations: ReservationResponse {
                 granted: Vec::new(),
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             },
             released: Some(ReleaseResult {
                 released: 3,
@@ -1265,8 +1376,11 @@ mod tests {
                     reason: "test reservation".into(),
                 }],
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             },
             inbox: Vec::new(),
+            revived: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let parsed: StartSessionResponse = serde_json::from_str(&json).unwrap();
@@ -1304,6 +1418,8 @@ mod tests {
             file_reservations: ReservationResponse {
                 granted: Vec::new(),
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             },
             inbox: vec![InboxMessage {
                 id: 100,
@@ -1321,9 +1437,13 @@ mod tests {
                 read_ts: None,
                 ack_ts: None,
                 kind: "direct".into(),
+                message_kind: None,
+                sender_verified: false,
+                importance_rank: None,
                 attachments: Vec::new(),
                 body_md: Some("Body text".into()),
             }],
+            revived: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let parsed: StartSessionResponse = serde_json::from_str(&json).unwrap();
@@ -1398,6 +1518,8 @@ mod tests {
             file_reservations: ReservationResponse {
                 granted: Vec::new(),
                 conflicts: Vec::new(),
+                lease_token: None,
+                atomic_rollback: false,
             },
             released: Some(ReleaseResult {
                 released: 5,
@@ -14,6 +14,7 @@ use mcp_agent_mail_core::{
     Config, TailLatencyPhaseLedger, TailLatencyPhaseRecorder,
     append_tail_latency_evidence_if_configured,
 };
+use mcp_agent_mail_db::queries::AutoApprovalScope;
 use mcp_agent_mail_db::{DbError, micros_to_iso};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
@@ -27,6 +28,7 @@ use crate::tool_util::{
     legacy_tool_error, parse_attachment_metadata_json, parse_recipients_lists, resolve_agent,
     resolve_project,
 };
+use mcp_agent_mail_core::inbox_order;
 use mcp_agent_mail_core::pattern_overlap::CompiledPattern;
 
 const FETCH_INBOX_ACK_OVERDUE_THRESHOLD_US: i64 = 30 * 60 * 1_000_000;
@@ -405,6 +407,47 @@ fn reservations_prove_shared_scope_for_contact(
     })
 }
 
+/// Approve links to `blocked` recipients whose `auto` policy allowlist names
+/// the sender, returning the names that no longer need approval. Failures
+/// leave the recipient blocked.
+async fn auto_approve_allowlisted_recipients<'a>(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    project_id: i64,
+    sender: &mcp_agent_mail_db::AgentRow,
+    recipient_map: &HashMap<String, mcp_agent_mail_db::AgentRow>,
+    blocked: impl IntoIterator<Item = &'a String>,
+    ttl_seconds: i64,
+) -> HashSet<String> {
+    let mut approved = HashSet::new();
+    for name in blocked {
+        let Some(agent) = recipient_map.get(&name.to_lowercase()) else {
+            continue;
+        };
+        match crate::contacts::auto_approve_contact(
+            ctx,
+            pool,
+            project_id,
+            sender,
+            project_id,
+            agent,
+            AutoApprovalScope::Allowlisted,
+            ttl_seconds,
+        )
+        .await
+        {
+            Ok(Some(_)) => {
+                approved.insert(name.clone());
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!("contact enforcement: auto-approval failed (fail-closed): {e}");
+            }
+        }
+    }
+    approved
+}
+
 async fn resolve_or_register_agent(
     ctx: &McpContext,
     pool: &mcp_agent_mail_db::DbPool,
@@ -988,6 +1031,7 @@ async fn push_recipient(
     recipient_map: &mut HashMap<String, mcp_agent_mail_db::AgentRow>,
     all_recipients: &mut SmallVec<[(i64, String); 8]>,
     resolved_list: &mut SmallVec<[String; 4]>,
+    retired: &mut RetiredRecipients,
 ) -> McpResult<()> {
     let name = name.trim();
     let name_key = name.to_lowercase();
@@ -1027,6 +1071,30 @@ async fn push_recipient(
                 return Err(e);
             }
         };
+        // Retired agents (`am agents apply --prune`) keep their rows for
+        // history; mail to them is queued as deferred unless the sender
+        // asked for the strict behavior.
+        let is_retired = db_outcome_to_mcp_result(
+            mcp_agent_mail_db::queries::is_agent_retired(ctx.cx(), pool, agent.id.unwrap_or(0))
+                .await,
+        )?;
+        if is_retired && retired.fail_on_retired {
+            return Err(legacy_tool_error(
+                "RECIPIENT_RETIRED",
+                format!(
+                    "Unable to send message — recipient {} has been retired from project '{project_human_key}'",
+                    agent.name
+                ),
+                true,
+                json!({
+                    "retired": [agent.name],
+                    "hint": "List the agent in the roster manifest again and run `am agents apply` to reinstate it.",
+                }),
+            ));
+        }
+        if is_retired {
+            retired.ids.insert(agent.id.unwrap_or(0));
+        }
         let key = agent.name.to_lowercase();
         recipient_map.insert(key, agent.clone());
         agent
@@ -1039,11 +1107,217 @@ async fn push_recipient(
     // the entire message transaction.
     if !all_recipients.iter().any(|(id, _)| *id == agent_id) {
         all_recipients.push((agent_id, kind.to_string()));
+        if retired.ids.contains(&agent_id) {
+            retired.deferred.push(RecipientStatus {
+                name: agent.name.clone(),
+                deferred: true,
+            });
+        }
         resolved_list.push(agent.name);
     }
     Ok(())
 }
 
+/// Retired recipients seen while resolving a send. Their mail is queued
+/// (deferred) rather than delivered, or the send is refused when
+/// `fail_on_retired` is set.
+#[derive(Debug, Default)]
+struct RetiredRecipients {
+    fail_on_retired: bool,
+    ids: HashSet<i64>,
+    deferred: Vec<RecipientStatus>,
+}
+
+impl RetiredRecipients {
+    fn new(fail_on_retired: bool) -> Self {
+        Self {
+            fail_on_retired,
+            ..Self::default()
+        }
+    }
+
+    fn deferred_ids(&self) -> Vec<i64> {
+        let mut ids: Vec<i64> = self.ids.iter().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn is_deferred(&self, name: &str) -> bool {
+        self.deferred.iter().any(|status| status.name == name)
+    }
+}
+
+/// Recipients appended to a send by the project's auto-CC policy.
+#[derive(Debug, Default)]
+struct AutoCcOutcome {
+    added: Vec<String>,
+    added_ids: Vec<i64>,
+    skipped: Vec<String>,
+}
+
+/// Fire each to/cc recipient's `on_message_hook` (see
+/// [`mcp_agent_mail_core::hooks`]). BCC recipients are skipped, matching the
+/// notification signals. Hooks run in the background and never fail the send.
+fn dispatch_message_hooks<'a>(
+    config: &Config,
+    project: &mcp_agent_mail_db::ProjectRow,
+    sender_name: &str,
+    message: &mcp_agent_mail_db::MessageRow,
+    recipients: impl IntoIterator<Item = &'a String>,
+) {
+    let detail = json!({
+        "message": {
+            "id": message.id,
+            "from": sender_name,
+            "subject": &message.subject,
+            "importance": &message.importance,
+            "ack_required": message.ack_required != 0,
+            "thread_id": &message.thread_id,
+            "created": micros_to_iso(message.created_ts),
+        }
+    });
+    for name in recipients {
+        mcp_agent_mail_core::hooks::dispatch(
+            config,
+            mcp_agent_mail_core::hooks::HookEvent {
+                kind: mcp_agent_mail_core::hooks::HookEventKind::Message,
+                project_slug: project.slug.clone(),
+                project_human_key: project.human_key.clone(),
+                agent: Some(name.clone()),
+                detail: detail.clone(),
+            },
+        );
+    }
+}
+
+/// Append the project's `auto_cc_agents` to CC when `importance` meets
+/// `auto_cc_min_importance`.
+///
+/// Agents already addressed (to/cc/bcc) and the sender are left alone;
+/// configured names that are not registered in the project are reported as
+/// skipped instead of failing the send. Policy recipients bypass contact
+/// enforcement because the project itself opted them in.
+async fn apply_auto_cc_policy(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    project_id: i64,
+    sender: &mcp_agent_mail_db::AgentRow,
+    importance: &str,
+    all_recipients: &mut SmallVec<[(i64, String); 8]>,
+    resolved_cc: &mut SmallVec<[String; 4]>,
+) -> AutoCcOutcome {
+    let mut outcome = AutoCcOutcome::default();
+    let settings =
+        match mcp_agent_mail_db::queries::list_project_settings(ctx.cx(), pool, project_id).await {
+            Outcome::Ok(rows) => rows,
+            Outcome::Err(e) => {
+                tracing::warn!("auto-cc: list_project_settings failed (policy skipped): {e}");
+                return outcome;
+            }
+            Outcome::Cancelled(_) | Outcome::Panicked(_) => return outcome,
+        };
+    let Some(policy) = mcp_agent_mail_core::project_settings::AutoCcPolicy::from_settings(
+        settings
+            .iter()
+            .map(|row| (row.key.as_str(), row.value.as_str())),
+    ) else {
+        return outcome;
+    };
+    if !policy.applies_to(importance) {
+        return outcome;
+    }
+    for name in &policy.agents {
+        if name.eq_ignore_ascii_case(&sender.name) {
+            continue;
+        }
+        match mcp_agent_mail_db::queries::get_agent(ctx.cx(), pool, project_id, name).await {
+            Outcome::Ok(agent) => {
+                let agent_id = agent.id.unwrap_or(0);
+                if all_recipients.iter().any(|(id, _)| *id == agent_id) {
+                    continue;
+                }
+                if matches!(
+                    mcp_agent_mail_db::queries::is_agent_retired(ctx.cx(), pool, agent_id).await,
+                    Outcome::Ok(true)
+                ) {
+                    outcome.skipped.push(agent.name);
+                    continue;
+                }
+                all_recipients.push((agent_id, "cc".to_string()));
+                resolved_cc.push(agent.name.clone());
+                outcome.added.push(agent.name);
+                outcome.added_ids.push(agent_id);
+            }
+            Outcome::Err(DbError::NotFound { .. }) => outcome.skipped.push(name.clone()),
+            Outcome::Err(e) => {
+                tracing::warn!("auto-cc: resolving {name} failed (skipped): {e}");
+                outcome.skipped.push(name.clone());
+            }
+            Outcome::Cancelled(_) | Outcome::Panicked(_) => outcome.skipped.push(name.clone()),
+        }
+    }
+    outcome
+}
+
+/// Check `agent_secret` against the sender's stored secret hash.
+///
+/// Returns `true` when a secret was presented and matched. A missing secret
+/// is only an error when the project sets `require_sender_auth`; a secret
+/// that is presented but wrong (or the agent has none) always fails with
+/// `SENDER_AUTH_FAILED`.
+async fn verify_sender_secret(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    project_id: i64,
+    sender: &mcp_agent_mail_db::AgentRow,
+    agent_secret: Option<&str>,
+) -> McpResult<bool> {
+    let auth_failed = |reason: &str| {
+        legacy_tool_error(
+            "SENDER_AUTH_FAILED",
+            format!(
+                "Sender authentication failed for agent '{}': {reason}",
+                sender.name
+            ),
+            false,
+            json!({
+                "sender_name": sender.name,
+                "hint": "Pass the secret printed by `am agents register --generate-secret` \
+                         (or `am agents rotate-secret`) as agent_secret",
+            }),
+        )
+    };
+    let Some(secret) = agent_secret.filter(|secret| !secret.is_empty()) else {
+        let settings = db_outcome_to_mcp_result(
+            mcp_agent_mail_db::queries::list_project_settings(ctx.cx(), pool, project_id).await,
+        )?;
+        let required = mcp_agent_mail_core::project_settings::sender_auth_required(
+            settings
+                .iter()
+                .map(|row| (row.key.as_str(), row.value.as_str())),
+        );
+        if required {
+            return Err(auth_failed(
+                "this project requires agent_secret on every send",
+            ));
+        }
+        return Ok(false);
+    };
+    let stored = db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::get_agent_secret_hash(ctx.cx(), pool, sender.id.unwrap_or(0))
+            .await,
+    )?;
+    let Some(stored) = stored else {
+        return Err(auth_failed("the agent has no sender secret"));
+    };
+    let presented = mcp_agent_mail_db::queries::agent_secret_hash(secret);
+    if mcp_agent_mail_core::setup::constant_time_str_eq(&presented, &stored) {
+        Ok(true)
+    } else {
+        Err(auth_failed("agent_secret does not match"))
+    }
+}
+
 #[allow(dead_code, clippy::too_many_arguments, clippy::too_many_lines)]
 fn process_message_attachments(
     config: &Config,
@@ -1197,7 +1471,7 @@ fn process_message_attachments(
                         )
                     })?;
 
-                    let stored = mcp_agent_mail_storage::store_raw_attachment(
+                    let stored = mcp_agent_mail_storage::store_attachment_blob(
                         &archive,
                         &resolved,
                         config.max_attachment_bytes,
@@ -1331,6 +1605,33 @@ pub struct SendMessageResponse {
     ///
     /// If `sender_token` was provided but mismatched, the call is rejected with an error.
     pub verified_sender: bool,
+    /// Whether the sender presented its per-agent secret (`agent_secret`);
+    /// independent of `verified_sender`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sender_verified: bool,
+    /// When the message expires, if sent with `expires_in_seconds`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_ts: Option<String>,
+    /// CC recipients added by the project's auto-CC policy rather than the
+    /// sender (they also appear in the payload's `cc`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_cc: Vec<String>,
+    /// Auto-CC agents that were configured but not registered in the project.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_cc_skipped: Vec<String>,
+    /// Retired recipients whose copy was queued instead of delivered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deferred_recipients: Vec<RecipientStatus>,
+}
+
+/// Delivery state of one recipient that did not receive the message
+/// immediately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipientStatus {
+    pub name: String,
+    /// The recipient is retired; the message is queued and delivered if the
+    /// agent is revived.
+    pub deferred: bool,
 }
 
 /// Message payload in responses
@@ -1375,6 +1676,16 @@ pub struct InboxMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ack_ts: Option<String>,
     pub kind: String,
+    /// `"ack_receipt"` for automatic ack receipts; absent for regular mail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_kind: Option<String>,
+    /// Sent with a verified per-agent sender secret.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sender_verified: bool,
+    /// Numeric importance rank (`low` = 0 .. `urgent` = 3); present when the
+    /// fetch asked for `order` or `min_importance`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub importance_rank: Option<u8>,
     pub attachments: Vec<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body_md: Option<String>,
@@ -1387,6 +1698,14 @@ pub struct ReadStatusResponse {
     pub read: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub read_at: Option<String>,
+    /// True only for the call that moved the message from unread to read.
+    #[serde(default)]
+    pub changed: bool,
+    /// Prior state when the call was a no-op: `"read"` or `"acked"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub already: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acknowledged_at: Option<String>,
 }
 
 /// Acknowledge status response
@@ -1398,6 +1717,13 @@ pub struct AckStatusResponse {
     pub acknowledged_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub read_at: Option<String>,
+    /// True only for the call that recorded the acknowledgement; repeated or
+    /// racing calls see `false` with the original timestamps.
+    #[serde(default)]
+    pub changed: bool,
+    /// `"acked"` when the message was already acknowledged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub already: Option<String>,
 }
 
 /// Reply message response (includes both message fields and deliveries)
@@ -1422,6 +1748,13 @@ pub struct ReplyMessageResponse {
     pub count: usize,
     /// Whether the sender's identity was cryptographically verified via `sender_token`.
     pub verified_sender: bool,
+    /// Whether the sender presented its per-agent secret (`agent_secret`);
+    /// independent of `verified_sender`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sender_verified: bool,
+    /// Retired recipients whose copy was queued instead of delivered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deferred_recipients: Vec<RecipientStatus>,
 }
 
 /// Send a message to one or more recipients.
@@ -1442,6 +1775,14 @@ pub struct ReplyMessageResponse {
 /// - `topic`: Reserved for future topic tags; non-blank values are currently rejected
 /// - `auto_contact_if_blocked`: Auto-request contact if blocked (optional)
 /// - `sender_token`: Registration token for sender identity verification (optional)
+/// - `ack_receipt`: Send the sender an in-thread receipt when each recipient
+///   acknowledges (requires `ack_required`; default: false)
+/// - `agent_secret`: Per-agent sender secret; required when the project sets
+///   `require_sender_auth` (optional)
+/// - `expires_in_seconds`: Hide the message from unread inbox views once this
+///   many seconds have passed (optional; incompatible with `ack_required`)
+/// - `fail_on_retired`: Refuse the send with `RECIPIENT_RETIRED` instead of
+///   queuing mail for retired recipients (default: false)
 ///
 /// # Conformance
 /// Python-parity.
@@ -1451,7 +1792,7 @@ pub struct ReplyMessageResponse {
     clippy::too_many_lines
 )]
 #[tool(
-    description = "Send a Markdown message to one or more recipients and persist canonical and mailbox copies to Git.\n\nDiscovery\n---------\nTo discover available agent names for recipients, use: resource://agents/{project_key}\nAgent names are NOT the same as program names or user names.\n\nWhat this does\n--------------\n- Stores message (and recipients) in the database; updates sender's activity\n- Writes a canonical `.md` under `messages/YYYY/MM/`\n- Writes sender outbox and per-recipient inbox copies\n- Optionally converts referenced images to WebP and embeds small images inline\n- Supports explicit attachments via `attachment_paths` in addition to inline references\n\nParameters\n----------\nproject_key : str\n    Project identifier (same used with `ensure_project`/`register_agent`).\nsender_name : str\n    Must match an agent registered in the project.\nto : list[str]\n    Primary recipients (agent names). At least one of to/cc/bcc must be non-empty.\nsubject : str\n    Short subject line that will be visible in inbox/outbox and search results.\nbody_md : str\n    GitHub-Flavored Markdown body. Image references can be file paths or data URIs.\ncc, bcc : Optional[list[str]]\n    Additional recipients by name.\nattachment_paths : Optional[list[str]]\n    Extra file paths to include as attachments; will be converted to WebP and stored.\nconvert_images : Optional[bool]\n    Overrides server default for image conversion/inlining. If None, server settings apply.\n    Note: sender attachments_policy \"inline\"/\"file\" always forces conversion/inlining.\nimportance : str\n    One of {\"low\",\"normal\",\"high\",\"urgent\"} (free form tolerated; used by filters).\nack_required : bool\n    If true, recipients should call `acknowledge_message` after reading.\nthread_id : Optional[str]\n    If provided, message will be associated with an existing thread.\nbroadcast : bool\n    Reserved for schema compatibility only. `broadcast=true` is intentionally\n    rejected to prevent agent spam; address agents explicitly instead.\ntopic : Optional[str]\n    Reserved for future topic tags. Non-blank values are currently rejected until\n    topic persistence and filtering are implemented.\nsender_token : Optional[str]\n    Registration token returned by `register_agent`. If provided and valid,\n    the response includes `verified_sender: true`. If provided but mismatched,\n    the call is rejected. If omitted, the message sends but with `verified_sender: false`.\n\nReturns\n-------\ndict\n    {\n      \"deliveries\": [ { \"project\": str, \"payload\": { ... message payload ... } } ],\n      \"count\": int,\n      \"verified_sender\": bool\n    }\n\nEdge cases\n----------\n- If no recipients are given, the call fails.\n- Unknown recipient names fail fast; register them first.\n- Non-absolute attachment paths are resolved relative to the project archive root.\n- `broadcast=true` is intentionally rejected.\n\nDo / Don't\n----------\nDo:\n- Keep subjects concise and specific (aim for \u{2264} 80 characters).\n- Use `thread_id` (or `reply_message`) to keep related discussion in a single thread.\n- Address only relevant recipients; use CC/BCC sparingly and intentionally.\n- Prefer Markdown links; attach images only when they materially aid understanding. The server\n  auto-converts images to WebP and may inline small images depending on policy.\n\nDon't:\n- Send large, repeated binaries\u{2014}reuse prior attachments via `attachment_paths` when possible.\n- Change topics mid-thread\u{2014}start a new thread for a new subject.\n- Broadcast to \"all\" agents unnecessarily\u{2014}target just the agents who need to act.\n\nExamples\n--------\n1) Simple message:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"5\",\"method\":\"tools/call\",\"params\":{\"name\":\"send_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"sender_name\":\"GreenCastle\",\"to\":[\"BlueLake\"],\n  \"subject\":\"Plan for /api/users\",\"body_md\":\"See below.\"\n}}}\n```\n\n2) Inline image (auto-convert to WebP and inline if small):\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6a\",\"method\":\"tools/call\",\"params\":{\"name\":\"send_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"sender_name\":\"GreenCastle\",\"to\":[\"BlueLake\"],\n  \"subject\":\"Diagram\",\"body_md\":\"![diagram](docs/flow.png)\",\"convert_images\":true\n}}}\n```\n\n3) Explicit attachments:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6b\",\"method\":\"tools/call\",\"params\":{\"name\":\"send_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"sender_name\":\"GreenCastle\",\"to\":[\"BlueLake\"],\n  \"subject\":\"Screenshots\",\"body_md\":\"Please review.\",\"attachment_paths\":[\"shots/a.png\",\"shots/b.png\"]\n}}}\n```"
+    description = "Send a Markdown message to one or more recipients and persist canonical and mailbox copies to Git.\n\nDiscovery\n---------\nTo discover available agent names for recipients, use: resource://agents/{project_key}\nAgent names are NOT the same as program names or user names.\n\nWhat this does\n--------------\n- Stores message (and recipients) in the database; updates sender's activity\n- Writes a canonical `.md` under `messages/YYYY/MM/`\n- Writes sender outbox and per-recipient inbox copies\n- Optionally converts referenced images to WebP and embeds small images inline\n- Supports explicit attachments via `attachment_paths` in addition to inline references\n\nParameters\n----------\nproject_key : str\n    Project identifier (same used with `ensure_project`/`register_agent`).\nsender_name : str\n    Must match an agent registered in the project.\nto : list[str]\n    Primary recipients (agent names). At least one of to/cc/bcc must be non-empty.\nsubject : str\n    Short subject line that will be visible in inbox/outbox and search results.\nbody_md : str\n    GitHub-Flavored Markdown body. Image references can be file paths or data URIs.\ncc, bcc : Optional[list[str]]\n    Additional recipients by name.\nattachment_paths : Optional[list[str]]\n    Extra file paths to include as attachments; will be converted to WebP and stored.\nconvert_images : Optional[bool]\n    Overrides server default for image conversion/inlining. If None, server settings apply.\n    Note: sender attachments_policy \"inline\"/\"file\" always forces conversion/inlining.\nimportance : str\n    One of {\"low\",\"normal\",\"high\",\"urgent\"} (free form tolerated; used by filters).\nack_required : bool\n    If true, recipients should call `acknowledge_message` after reading.\nthread_id : Optional[str]\n    If provided, message will be associated with an existing thread.\nbroadcast : bool\n    Reserved for schema compatibility only. `broadcast=true` is intentionally\n    rejected to prevent agent spam; address agents explicitly instead.\ntopic : Optional[str]\n    Reserved for future topic tags. Non-blank values are currently rejected until\n    topic persistence and filtering are implemented.\nsender_token : Optional[str]\n    Registration token returned by `register_agent`. If provided and valid,\n    the response includes `verified_sender: true`. If provided but mismatched,\n    the call is rejected. If omitted, the message sends but with `verified_sender: false`.\n\nReturns\n-------\ndict\n    {\n      \"deliveries\": [ { \"project\": str, \"payload\": { ... message payload ... } } ],\n      \"count\": int,\n      \"verified_sender\": bool\n    }\n\nEdge cases\n----------\n- If no recipients are given, the call fails.\n- Unknown recipient names fail fast; register them first.\n- Non-absolute attachment paths are resolved relative to the project archive root.\n- `broadcast=true` is intentionally rejected.\n\nDo / Don't\n----------\nDo:\n- Keep subjects concise and specific (aim for \u{2264} 80 characters).\n- Use `thread_id` (or `reply_message`) to keep related discussion in a single thread.\n- Address only relevant recipients; use CC/BCC sparingly and intentionally.\n- Prefer Markdown links; attach images only when they materially aid understanding. The server\n  auto-converts images to WebP and may inline small images depending on policy.\n\nDon't:\n- Send large, repeated binaries\u{2014}reuse prior attachments via `attachment_paths` when possible.\n- Change topics mid-thread\u{2014}start a new thread for a new subject.\n- Broadcast to \"all\" agents unnecessarily\u{2014}target just the agents who need to act.\n\nExamples\n--------\n1) Simple message:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"5\",\"method\":\"tools/call\",\"params\":{\"name\":\"send_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"sender_name\":\"GreenCastle\",\"to\":[\"BlueLake\"],\n  \"subject\":\"Plan for /api/users\",\"body_md\":\"See below.\"\n}}}\n```\n\n2) Inline image (auto-convert to WebP and inline if small):\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6a\",\"method\":\"tools/call\",\"params\":{\"name\":\"send_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"sender_name\":\"GreenCastle\",\"to\":[\"BlueLake\"],\n  \"subject\":\"Diagram\",\"body_md\":\"![diagram](docs/flow.png)\",\"convert_images\":true\n}}}\n```\n\n3) Explicit attachments:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6b\",\"method\":\"tools/call\",\"params\":{\"name\":\"send_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"sender_name\":\"GreenCastle\",\"to\":[\"BlueLake\"],\n  \"subject\":\"Screenshots\",\"body_md\":\"Please review.\",\"attachment_paths\":[\"shots/a.png\",\"shots/b.png\"]\n}}}\n```\n\nAck receipts\n------------\nPass `ack_receipt=true` (requires `ack_required=true`) to be told when recipients acknowledge. Each\nrecipient's first acknowledgement sends you a receipt in the same thread (\"BlueLake acknowledged message\n#123 at <ts>\"). Receipts are never ack-required, are skipped if you are no longer registered or retired, and carry\n`message_kind: \"ack_receipt\"` in `fetch_inbox` results so they can be filtered out.\n\nSender authentication\n---------------------\nWhen the project setting `require_sender_auth` is true, pass `agent_secret` (printed once by\n`am agents register --generate-secret` or `am agents rotate-secret`). A missing or wrong secret fails\nwith `SENDER_AUTH_FAILED`. Messages sent with a verified secret carry `sender_verified: true` in\nthe response and in `fetch_inbox` results (`verified_sender` only reflects `sender_token`).\n\nExpiry\n------\nPass `expires_in_seconds` for ephemeral status mail (\"build started\"). Once it lapses, the message is\nleft out of `fetch_inbox` while still unread, and `am mail purge-expired` deletes it. The response\ncarries `expires_ts`. Cannot be combined with `ack_required=true`: ack-required messages never expire.\n\nRetired recipients\n------------------\nMail to an agent retired by `am agents apply --prune` is stored but queued: the response lists it under\n`deferred_recipients` as `{\"name\": ..., \"deferred\": true}`, and the agent sees it once revived by\n`macro_start_session` or `am agents revive`. Queued mail still unreleased after `DEFERRED_MAIL_TTL_DAYS`\n(default 14) is dropped. Pass `fail_on_retired=true` to refuse the send with `RECIPIENT_RETIRED` instead."
 )]
 pub async fn send_message(
     ctx: &McpContext,
@@ -1471,6 +1812,10 @@ pub async fn send_message(
     broadcast: Option<bool>,
     auto_contact_if_blocked: Option<bool>,
     sender_token: Option<String>,
+    ack_receipt: Option<bool>,
+    agent_secret: Option<String>,
+    expires_in_seconds: Option<i64>,
+    fail_on_retired: Option<bool>,
 ) -> McpResult<String> {
     // Normalize names
     let sender_name = normalize_agent_name_or_original(sender_name);
@@ -1516,6 +1861,42 @@ pub async fn send_message(
         .map(|t| t.trim().to_string())
         .filter(|t| !t.is_empty());
     reject_unsupported_topic_argument(topic.as_deref(), "send_message")?;
+    let ack_receipt = ack_receipt.unwrap_or(false);
+    if ack_receipt && !ack_required.unwrap_or(false) {
+        return Err(legacy_tool_error(
+            "INVALID_ARGUMENT",
+            "ack_receipt requires ack_required=true",
+            true,
+            json!({ "field": "ack_receipt" }),
+        ));
+    }
+    let expires_in_us = match expires_in_seconds {
+        None => None,
+        Some(_) if ack_required.unwrap_or(false) => {
+            return Err(legacy_tool_error(
+                "INVALID_ARGUMENT",
+                "expires_in_seconds cannot be combined with ack_required=true; \
+                 ack-required messages never expire",
+                true,
+                json!({ "field": "expires_in_seconds" }),
+            ));
+        }
+        Some(seconds) => Some(
+            seconds
+                .checked_mul(1_000_000)
+                .filter(|_| seconds >= 1)
+                .ok_or_else(|| {
+                    legacy_tool_error(
+                        "INVALID_ARGUMENT",
+                        format!(
+                            "expires_in_seconds must be a positive number of seconds, got {seconds}"
+                        ),
+                        true,
+                        json!({ "field": "expires_in_seconds", "provided": seconds }),
+                    )
+                })?,
+        ),
+    };
 
     let config = &Config::get();
 
@@ -1645,6 +2026,10 @@ effective_free_bytes={free}"
         None => false,
     };
 
+    // ── Sender authentication (per-agent secret) ──────────────────────
+    let sender_verified =
+        verify_sender_secret(ctx, &pool, project_id, &sender, agent_secret.as_deref()).await?;
+
     // Self-send detection: warn if sender is sending to themselves (Python parity)
     {
         let sender_lower = sender_name.trim().to_ascii_lowercase();
@@ -1693,6 +2078,7 @@ effective_free_bytes={free}"
     let mut recipient_map: HashMap<String, mcp_agent_mail_db::AgentRow> =
         HashMap::with_capacity(total_recip);
     let mut missing_local: Vec<String> = Vec::new();
+    let mut retired = RetiredRecipients::new(fail_on_retired.unwrap_or(false));
 
     for name in &to {
         if let Err(err) = push_recipient(
@@ -1708,6 +2094,7 @@ effective_free_bytes={free}"
             &mut recipient_map,
             &mut all_recipients,
             &mut resolved_to,
+            &mut retired,
         )
         .await
         {
@@ -1736,6 +2123,7 @@ effective_free_bytes={free}"
             &mut recipient_map,
             &mut all_recipients,
             &mut resolved_cc_recipients,
+            &mut retired,
         )
         .await
         {
@@ -1764,6 +2152,7 @@ effective_free_bytes={free}"
             &mut recipient_map,
             &mut all_recipients,
             &mut resolved_bcc_recipients,
+            &mut retired,
         )
         .await
         {
@@ -2000,6 +2389,20 @@ effective_free_bytes={free}"
             }
         }
 
+        if !blocked.is_empty() {
+            let approved = auto_approve_allowlisted_recipients(
+                ctx,
+                &pool,
+                project_id,
+                &sender,
+                &recipient_map,
+                blocked.iter().map(|(name, _)| name),
+                ttl_seconds,
+            )
+            .await;
+            blocked.retain(|(name, _)| !approved.contains(name));
+        }
+
         let mut attempted: Vec<String> = Vec::new();
         if !blocked.is_empty() {
             let effective_auto_contact =
@@ -2100,6 +2503,17 @@ effective_free_bytes={free}"
         }
     }
 
+    let auto_cc = apply_auto_cc_policy(
+        ctx,
+        &pool,
+        project_id,
+        &sender,
+        &importance_val,
+        &mut all_recipients,
+        &mut resolved_cc_recipients,
+    )
+    .await;
+
     let (final_body, all_attachment_meta, all_attachment_rel_paths) = process_message_attachments(
         config,
         &project.slug,
@@ -2125,8 +2539,9 @@ effective_free_bytes={free}"
         .iter()
         .map(|(id, kind)| (*id, kind.as_str()))
         .collect();
+    let deferred_ids = retired.deferred_ids();
     let message = db_outcome_to_mcp_result(
-        mcp_agent_mail_db::queries::create_message_with_recipients(
+        mcp_agent_mail_db::queries::create_message_with_recipients_and_sidecars(
             ctx.cx(),
             &pool,
             project_id,
@@ -2138,11 +2553,47 @@ effective_free_bytes={free}"
             ack_required.unwrap_or(false),
             &attachments_json,
             &recipient_refs,
+            mcp_agent_mail_db::queries::MessageSidecars {
+                sender_verified,
+                deferred_recipients: &deferred_ids,
+            },
         )
         .await,
     )?;
 
     let message_id = message.id.unwrap_or(0);
+    if let Outcome::Err(e) = mcp_agent_mail_db::queries::record_message_policy_recipients(
+        ctx.cx(),
+        &pool,
+        message_id,
+        &auto_cc.added_ids,
+        "auto_cc",
+    )
+    .await
+    {
+        tracing::warn!("auto-cc: recording policy recipients for message {message_id} failed: {e}");
+    }
+    if ack_receipt {
+        db_outcome_to_mcp_result(
+            mcp_agent_mail_db::queries::request_ack_receipt(ctx.cx(), &pool, message_id).await,
+        )?;
+    }
+    let expires_ts = match expires_in_us {
+        Some(ttl_us) => {
+            let expires_ts = message.created_ts.saturating_add(ttl_us);
+            db_outcome_to_mcp_result(
+                mcp_agent_mail_db::queries::set_message_expiry(
+                    ctx.cx(),
+                    &pool,
+                    message_id,
+                    expires_ts,
+                )
+                .await,
+            )?;
+            Some(micros_to_iso(expires_ts))
+        }
+        None => None,
+    };
     enqueue_message_semantic_index(project_id, message_id, &message.subject, &message.body_md);
     enqueue_message_lexical_index(&mcp_agent_mail_db::search_v3::IndexableMessage {
         id: message_id,
@@ -2168,7 +2619,11 @@ effective_free_bytes={free}"
         importance: Some(message.importance.clone()),
     };
     let mut notified = HashSet::new();
-    for name in resolved_to.iter().chain(resolved_cc_recipients.iter()) {
+    for name in resolved_to
+        .iter()
+        .chain(resolved_cc_recipients.iter())
+        .filter(|name| !retired.is_deferred(name))
+    {
         if notified.insert(name.clone()) {
             match mcp_agent_mail_storage::emit_notification_signal(
                 config,
@@ -2190,6 +2645,18 @@ effective_free_bytes={free}"
             }
         }
     }
+    dispatch_message_hooks(config, &project, &sender.name, &message, &notified);
+    crate::webhooks::enqueue(
+        config,
+        crate::webhooks::MESSAGE_SENT,
+        crate::webhooks::message_sent_fields(
+            &project.slug,
+            &sender.name,
+            resolved_to.iter().chain(resolved_cc_recipients.iter()),
+            &message,
+            config.webhook_include_body,
+        ),
+    );
 
     // Write message bundle to git archive (best-effort)
     {
@@ -2202,7 +2669,7 @@ effective_free_bytes={free}"
         all_recipient_names.sort_unstable();
         all_recipient_names.dedup();
 
-        let msg_json = serde_json::json!({
+        let mut msg_json = serde_json::json!({
             "id": message_id,
             "from": &sender.name,
             "to": &resolved_to,
@@ -2217,6 +2684,9 @@ effective_free_bytes={free}"
             "ack_required": message.ack_required != 0,
             "attachments": &all_attachment_meta,
         });
+        if !auto_cc.added.is_empty() {
+            msg_json["policy_cc"] = json!(&auto_cc.added);
+        }
         try_write_message_archive(
             config,
             &project.slug,
@@ -2259,6 +2729,11 @@ effective_free_bytes={free}"
         count: 1,
         attachments: attachment_paths_out,
         verified_sender,
+        sender_verified,
+        expires_ts,
+        policy_cc: auto_cc.added,
+        policy_cc_skipped: auto_cc.skipped,
+        deferred_recipients: retired.deferred,
     };
 
     tracing::debug!(
@@ -2285,6 +2760,8 @@ effective_free_bytes={free}"
 /// - `bcc`: BCC recipients
 /// - `subject_prefix`: Prefix for subject (default: "Re:")
 /// - `sender_token`: Registration token for sender identity verification (optional)
+/// - `agent_secret`: Per-agent sender secret; required when the project sets
+///   `require_sender_auth` (optional)
 ///
 /// # Conformance
 /// Python-parity.
@@ -2294,7 +2771,7 @@ effective_free_bytes={free}"
     clippy::too_many_lines
 )]
 #[tool(
-    description = "Reply to an existing message, preserving or establishing a thread.\n\nBehavior\n--------\n- Inherits original `importance` and `ack_required` flags unless overridden\n- `thread_id` is taken from the original message if present; otherwise, the original id is used\n- Subject is prefixed with `subject_prefix` if not already present\n- Defaults `to` to the original sender if not explicitly provided\n\nParameters\n----------\nproject_key : str\n    Project identifier.\nmessage_id : int\n    The id of the message you are replying to.\nsender_name : str\n    Your agent name (must be registered in the project).\nbody_md : str\n    Reply body in Markdown.\nto, cc, bcc : Optional[list[str]]\n    Recipients by agent name. If omitted, `to` defaults to original sender.\nsubject_prefix : str\n    Prefix to apply (default \"Re:\"). Case-insensitive idempotent.\nimportance : Optional[str]\n    Override importance level {\"low\",\"normal\",\"high\",\"urgent\"}. Inherits from original if omitted.\nack_required : Optional[bool]\n    Override acknowledgement requirement. Inherits from original if omitted.\nsender_token : Optional[str]\n    Registration token for identity verification.\n\nDo / Don't\n----------\nDo:\n- Keep the subject focused; avoid topic drift within a thread.\n- Reply to the original sender unless new stakeholders are strictly required.\n- Preserve importance/ack flags from the original unless there is a clear reason to change.\n- Use CC for FYI only; BCC sparingly and with intention.\n\nDon't:\n- Change `thread_id` when continuing the same discussion.\n- Escalate to many recipients; prefer targeted replies and start a new thread for new topics.\n- Attach large binaries in replies unless essential; reference prior attachments where possible.\n\nReturns\n-------\ndict\n    Message payload including `thread_id` and `reply_to`.\n\nExamples\n--------\nMinimal reply to original sender:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6\",\"method\":\"tools/call\",\"params\":{\"name\":\"reply_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"message_id\":1234,\"sender_name\":\"BlueLake\",\n  \"body_md\":\"Questions about the migration plan...\"\n}}}\n```\n\nReply with explicit recipients and CC:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6c\",\"method\":\"tools/call\",\"params\":{\"name\":\"reply_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"message_id\":1234,\"sender_name\":\"BlueLake\",\n  \"body_md\":\"Looping ops.\",\"to\":[\"GreenCastle\"],\"cc\":[\"RedCat\"],\"subject_prefix\":\"RE:\"\n}}}\n```"
+    description = "Reply to an existing message, preserving or establishing a thread.\n\nBehavior\n--------\n- Inherits original `importance` and `ack_required` flags unless overridden\n- `thread_id` is taken from the original message if present; otherwise, the original id is used\n- Subject is prefixed with `subject_prefix` if not already present\n- Defaults `to` to the original sender if not explicitly provided\n\nParameters\n----------\nproject_key : str\n    Project identifier.\nmessage_id : int\n    The id of the message you are replying to.\nsender_name : str\n    Your agent name (must be registered in the project).\nbody_md : str\n    Reply body in Markdown.\nto, cc, bcc : Optional[list[str]]\n    Recipients by agent name. If omitted, `to` defaults to original sender.\nsubject_prefix : str\n    Prefix to apply (default \"Re:\"). Case-insensitive idempotent.\nimportance : Optional[str]\n    Override importance level {\"low\",\"normal\",\"high\",\"urgent\"}. Inherits from original if omitted.\nack_required : Optional[bool]\n    Override acknowledgement requirement. Inherits from original if omitted.\nsender_token : Optional[str]\n    Registration token for identity verification.\n\nDo / Don't\n----------\nDo:\n- Keep the subject focused; avoid topic drift within a thread.\n- Reply to the original sender unless new stakeholders are strictly required.\n- Preserve importance/ack flags from the original unless there is a clear reason to change.\n- Use CC for FYI only; BCC sparingly and with intention.\n\nDon't:\n- Change `thread_id` when continuing the same discussion.\n- Escalate to many recipients; prefer targeted replies and start a new thread for new topics.\n- Attach large binaries in replies unless essential; reference prior attachments where possible.\n\nReturns\n-------\ndict\n    Message payload including `thread_id` and `reply_to`.\n\nExamples\n--------\nMinimal reply to original sender:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6\",\"method\":\"tools/call\",\"params\":{\"name\":\"reply_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"message_id\":1234,\"sender_name\":\"BlueLake\",\n  \"body_md\":\"Questions about the migration plan...\"\n}}}\n```\n\nReply with explicit recipients and CC:\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"6c\",\"method\":\"tools/call\",\"params\":{\"name\":\"reply_message\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"message_id\":1234,\"sender_name\":\"BlueLake\",\n  \"body_md\":\"Looping ops.\",\"to\":[\"GreenCastle\"],\"cc\":[\"RedCat\"],\"subject_prefix\":\"RE:\"\n}}}\n```\n\nSender authentication\n---------------------\nWhen the project setting `require_sender_auth` is true, pass `agent_secret`. A missing or wrong secret\nfails with `SENDER_AUTH_FAILED`."
 )]
 pub async fn reply_message(
     ctx: &McpContext,
@@ -2311,6 +2788,7 @@ pub async fn reply_message(
     attachment_paths: Option<Vec<String>>,
     convert_images: Option<bool>,
     sender_token: Option<String>,
+    agent_secret: Option<String>,
 ) -> McpResult<String> {
     // Normalize names
     let sender_name = normalize_agent_name_or_original(sender_name);
@@ -2471,6 +2949,10 @@ effective_free_bytes={free}"
         None => false,
     };
 
+    // ── Sender authentication (per-agent secret) ──────────────────────
+    let sender_verified =
+        verify_sender_secret(ctx, &pool, project_id, &sender, agent_secret.as_deref()).await?;
+
     // Resolve original sender name for default recipient
     let original_sender = db_outcome_to_mcp_result(
         mcp_agent_mail_db::queries::get_agent_by_id_fresh(ctx.cx(), &pool, original.sender_id)
@@ -2578,6 +3060,7 @@ effective_free_bytes={free}"
     let mut recipient_map: HashMap<String, mcp_agent_mail_db::AgentRow> =
         HashMap::with_capacity(total_recip);
     let mut missing_local: Vec<String> = Vec::new();
+    let mut retired = RetiredRecipients::new(false);
 
     for name in &to_names {
         if let Err(err) = push_recipient(
@@ -2593,6 +3076,7 @@ effective_free_bytes={free}"
             &mut recipient_map,
             &mut all_recipients,
             &mut resolved_to,
+            &mut retired,
         )
         .await
         {
@@ -2621,6 +3105,7 @@ effective_free_bytes={free}"
             &mut recipient_map,
             &mut all_recipients,
             &mut resolved_cc_recipients,
+            &mut retired,
         )
         .await
         {
@@ -2649,6 +3134,7 @@ effective_free_bytes={free}"
             &mut recipient_map,
             &mut all_recipients,
             &mut resolved_bcc_recipients,
+            &mut retired,
         )
         .await
         {
@@ -2843,6 +3329,20 @@ effective_free_bytes={free}"
             }
         }
 
+        if !blocked.is_empty() {
+            let approved = auto_approve_allowlisted_recipients(
+                ctx,
+                &pool,
+                project_id,
+                &sender,
+                &recipient_map,
+                &blocked,
+                ttl_seconds,
+            )
+            .await;
+            blocked.retain(|name| !approved.contains(name));
+        }
+
         let mut attempted: Vec<String> = Vec::new();
         if !blocked.is_empty() && config.messaging_auto_handshake_on_block {
             for name in &blocked {
@@ -2925,8 +3425,9 @@ effective_free_bytes={free}"
         .iter()
         .map(|(id, kind)| (*id, kind.as_str()))
         .collect();
+    let deferred_ids = retired.deferred_ids();
     let reply = db_outcome_to_mcp_result(
-        mcp_agent_mail_db::queries::create_message_with_recipients(
+        mcp_agent_mail_db::queries::create_message_with_recipients_and_sidecars(
             ctx.cx(),
             &pool,
             project_id,
@@ -2938,6 +3439,10 @@ effective_free_bytes={free}"
             ack_required.unwrap_or(original.ack_required != 0),
             &attachments_json,
             &recipient_refs,
+            mcp_agent_mail_db::queries::MessageSidecars {
+                sender_verified,
+                deferred_recipients: &deferred_ids,
+            },
         )
         .await,
     )?;
@@ -2965,7 +3470,11 @@ effective_free_bytes={free}"
         importance: Some(reply.importance.clone()),
     };
     let mut notified = HashSet::new();
-    for name in resolved_to.iter().chain(resolved_cc_recipients.iter()) {
+    for name in resolved_to
+        .iter()
+        .chain(resolved_cc_recipients.iter())
+        .filter(|name| !retired.is_deferred(name))
+    {
         if notified.insert(name.clone()) {
             match mcp_agent_mail_storage::emit_notification_signal(
                 config,
@@ -2987,6 +3496,18 @@ effective_free_bytes={free}"
             }
         }
     }
+    dispatch_message_hooks(config, &project, &sender.name, &reply, &notified);
+    crate::webhooks::enqueue(
+        config,
+        crate::webhooks::MESSAGE_SENT,
+        crate::webhooks::message_sent_fields(
+            &project.slug,
+            &sender.name,
+            resolved_to.iter().chain(resolved_cc_recipients.iter()),
+            &reply,
+            config.webhook_include_body,
+        ),
+    );
 
     // Write reply message bundle to git archive (best-effort)
     {
@@ -3065,6 +3586,8 @@ effective_free_bytes={free}"
         }],
         count: 1,
         verified_sender,
+        sender_verified,
+        deferred_recipients: retired.deferred,
     };
 
     tracing::debug!(
@@ -3094,16 +3617,30 @@ effective_free_bytes={free}"
 /// - `unread_only`: Only messages not yet marked read (default: false)
 /// - `ack_overdue_only`: Only unacknowledged ack-required messages older than the SLA (default: false)
 /// - `topic`: Reserved for future topic filtering; non-blank values are currently rejected
+/// - `after_watermark`: Only messages whose `id` is greater than this value,
+///   oldest first. Message ids are assigned monotonically in commit order, so
+///   feeding back the highest returned `id` is the reliable polling cursor;
+///   `since_ts` is approximate (ties and clock skew can hide messages).
+///   Cannot be combined with `since_ts` or `ack_overdue_only`.
+/// - `min_importance`: Only messages at or above `normal`, `high`, or
+///   `urgent`; `urgent_only` is equivalent to `high`
+/// - `order`: `time` (default, newest first), `importance` (highest rank
+///   first), or `hybrid` (`high`/`urgent` bucket first, newest first within
+///   each bucket). Applied to the fetched page.
+///
+/// When `min_importance` or `order` is given, each message carries
+/// `importance_rank` from [`inbox_order::inbox_importance_rank`].
 ///
 /// # Conformance
-/// Python-parity.
+/// Python-parity, plus the Rust-only `after_watermark`, `min_importance`,
+/// and `order` extensions.
 #[allow(
     clippy::items_after_statements,
     clippy::too_many_arguments,
     clippy::too_many_lines
 )]
 #[tool(
-    description = "Retrieve recent messages for an agent and mark returned messages read.\n\nFilters\n-------\n- `urgent_only`: only messages with importance in {high, urgent}\n- `unread_only`: only recipient rows whose read_ts is unset\n- `ack_overdue_only`: only ack-required rows with no ack_ts older than the 30-minute SLA\n- `since_ts`: ISO-8601 timestamp string; messages strictly newer than this are returned\n- `limit`: max number of messages (default 20)\n- `include_bodies`: include full Markdown bodies in the payloads\n- `topic`: reserved for future topic filtering; non-blank values are currently rejected\n\nUsage patterns\n--------------\n- Poll after each editing step in an agent loop to pick up coordination messages.\n- Use `since_ts` with the timestamp from your last poll for efficient incremental fetches.\n- Combine with `acknowledge_message` if `ack_required` is true.\n\nReturns\n-------\nlist[dict]\n    Each message includes: { id, subject, from, created_ts, read_ts?, ack_ts?, importance, ack_required, kind, [body_md] }\n\nExample\n-------\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"7\",\"method\":\"tools/call\",\"params\":{\"name\":\"fetch_inbox\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"agent_name\":\"BlueLake\",\"since_ts\":\"2025-10-23T00:00:00+00:00\"\n}}}\n```"
+    description = "Retrieve recent messages for an agent and mark returned messages read.\n\nFilters\n-------\n- `urgent_only`: only messages with importance in {high, urgent}\n- `unread_only`: only recipient rows whose read_ts is unset\n- `ack_overdue_only`: only ack-required rows with no ack_ts older than the 30-minute SLA\n- `since_ts`: ISO-8601 timestamp string; messages strictly newer than this are returned\n- `limit`: max number of messages (default 20)\n- `include_bodies`: include full Markdown bodies in the payloads\n- `topic`: reserved for future topic filtering; non-blank values are currently rejected\n\nUsage patterns\n--------------\n- Poll after each editing step in an agent loop to pick up coordination messages.\n- Use `since_ts` with the timestamp from your last poll for efficient incremental fetches.\n- Combine with `acknowledge_message` if `ack_required` is true.\n\nReturns\n-------\nlist[dict]\n    Each message includes: { id, subject, from, created_ts, read_ts?, ack_ts?, importance, ack_required, kind, [body_md] }\n\nExample\n-------\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"7\",\"method\":\"tools/call\",\"params\":{\"name\":\"fetch_inbox\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"agent_name\":\"BlueLake\",\"since_ts\":\"2025-10-23T00:00:00+00:00\"\n}}}\n```\n\nImportance\n----------\n- `min_importance`: `normal`, `high`, or `urgent`; only messages at or above that level (`urgent_only` equals `high`)\n- `order`: `time` (default, newest first), `importance` (highest first), or `hybrid` (high/urgent first, newest first within each group); applied to the fetched page\n- With either set, each message includes `importance_rank` (low=0, normal=1, high=2, urgent=3)"
 )]
 pub async fn fetch_inbox(
     ctx: &McpContext,
@@ -3116,6 +3653,9 @@ pub async fn fetch_inbox(
     unread_only: Option<bool>,
     ack_overdue_only: Option<bool>,
     topic: Option<String>,
+    after_watermark: Option<i64>,
+    min_importance: Option<String>,
+    order: Option<String>,
 ) -> McpResult<String> {
     let mut phase = TailLatencyPhaseRecorder::new("fetch_inbox");
     phase.mark("queue_wait");
@@ -3145,10 +3685,58 @@ pub async fn fetch_inbox(
         )
     })?;
     let include_body = include_bodies.unwrap_or(false);
-    let urgent = urgent_only.unwrap_or(false);
+    let min_rank = match min_importance.as_deref() {
+        None => None,
+        Some(raw) => Some(inbox_order::parse_min_importance(raw).ok_or_else(|| {
+            legacy_tool_error(
+                "INVALID_ARGUMENT",
+                format!("min_importance must be one of normal, high, urgent; got '{raw}'"),
+                true,
+                json!({ "provided": raw, "allowed": ["normal", "high", "urgent"] }),
+            )
+        })?),
+    };
+    let requested_order = match order.as_deref() {
+        None => None,
+        Some(raw) => Some(inbox_order::InboxOrder::parse(raw).ok_or_else(|| {
+            legacy_tool_error(
+                "INVALID_ARGUMENT",
+                format!("order must be one of time, importance, hybrid; got '{raw}'"),
+                true,
+                json!({ "provided": raw, "allowed": ["time", "importance", "hybrid"] }),
+            )
+        })?),
+    };
+    let importance_aware = min_rank.is_some() || requested_order.is_some();
+    let min_rank = if urgent_only.unwrap_or(false) {
+        min_rank.max(Some(inbox_order::HIGH_IMPORTANCE_RANK))
+    } else {
+        min_rank
+    };
+    // The inbox queries filter `high` and above in SQL; the `normal` and
+    // `urgent` floors are applied to the fetched rows below.
+    let urgent = min_rank.is_some_and(|rank| rank >= inbox_order::HIGH_IMPORTANCE_RANK);
     let unread = unread_only.unwrap_or(false);
     let ack_overdue = ack_overdue_only.unwrap_or(false);
     reject_unsupported_topic_argument(topic.as_deref(), "fetch_inbox")?;
+    if let Some(watermark) = after_watermark {
+        if watermark < 0 {
+            return Err(legacy_tool_error(
+                "INVALID_ARGUMENT",
+                format!("after_watermark must be >= 0, got {watermark}"),
+                true,
+                json!({ "provided": watermark }),
+            ));
+        }
+        if since_ts.is_some() || ack_overdue {
+            return Err(legacy_tool_error(
+                "INVALID_ARGUMENT",
+                "after_watermark cannot be combined with since_ts or ack_overdue_only",
+                true,
+                json!({ "after_watermark": watermark }),
+            ));
+        }
+    }
     phase.set_include_bodies(include_body);
     phase.mark("argument_validation");
 
@@ -3192,7 +3780,21 @@ pub async fn fetch_inbox(
         None
     };
 
-    let inbox_rows = db_outcome_to_mcp_result(match (include_body, ack_overdue, unread) {
+    let mut inbox_rows = db_outcome_to_mcp_result(match (include_body, ack_overdue, unread) {
+        _ if after_watermark.is_some() => {
+            mcp_agent_mail_db::queries::fetch_inbox_after_watermark(
+                ctx.cx(),
+                &read_pool,
+                project_id,
+                agent_id,
+                urgent,
+                unread,
+                after_watermark.unwrap_or(0),
+                msg_limit,
+                include_body,
+            )
+            .await
+        }
         (true, true, _) => {
             let threshold = mcp_agent_mail_db::now_micros() - FETCH_INBOX_ACK_OVERDUE_THRESHOLD_US;
             mcp_agent_mail_db::queries::fetch_inbox_ack_overdue(
@@ -3272,6 +3874,19 @@ pub async fn fetch_inbox(
     })?;
     phase.mark("sqlite_query");
 
+    if let Some(min_rank) = min_rank {
+        inbox_rows
+            .retain(|row| inbox_order::inbox_importance_rank(&row.message.importance) >= min_rank);
+    }
+    if let Some(order) = requested_order {
+        inbox_order::sort_inbox(&mut inbox_rows, order, |row| {
+            (
+                inbox_order::inbox_importance_rank(&row.message.importance),
+                row.message.created_ts,
+            )
+        });
+    }
+
     let mut messages: Vec<InboxMessage> = inbox_rows
         .into_iter()
         .map(|row| {
@@ -3302,6 +3917,10 @@ pub async fn fetch_inbox(
                 read_ts: row.read_ts.map(micros_to_iso),
                 ack_ts: row.ack_ts.map(micros_to_iso),
                 kind: row.kind,
+                message_kind: None,
+                sender_verified: false,
+                importance_rank: importance_aware
+                    .then(|| inbox_order::inbox_importance_rank(&row.message.importance)),
                 attachments,
                 body_md: if include_body {
                     Some(row.message.body_md)
@@ -3311,6 +3930,8 @@ pub async fn fetch_inbox(
             }
         })
         .collect();
+    tag_ack_receipts(ctx, &read_pool, &mut messages).await;
+    tag_verified_senders(ctx, &read_pool, &mut messages).await;
     phase.set_rows_returned(messages.len());
     phase.mark(if include_body {
         "body_hydration_and_row_materialization"
@@ -3319,7 +3940,7 @@ pub async fn fetch_inbox(
     });
 
     tracing::debug!(
-        "Fetched {} messages for {} in project {} (limit: {}, urgent: {}, unread: {}, ack_overdue: {}, since: {:?})",
+        "Fetched {} messages for {} in project {} (limit: {}, urgent: {}, unread: {}, ack_overdue: {}, since: {:?}, after_watermark: {:?})",
         messages.len(),
         agent_name,
         project_key,
@@ -3327,7 +3948,8 @@ pub async fn fetch_inbox(
         urgent,
         unread,
         ack_overdue,
-        since_ts
+        since_ts,
+        after_watermark
     );
 
     // Auto-mark fetched messages as read.
@@ -3406,6 +4028,68 @@ pub async fn fetch_inbox(
     Ok(response)
 }
 
+/// Set `message_kind` on inbox entries that are ack receipts (best-effort).
+async fn tag_ack_receipts(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    messages: &mut [InboxMessage],
+) {
+    let ids: Vec<i64> = messages.iter().map(|m| m.id).collect();
+    match mcp_agent_mail_db::queries::list_ack_receipt_message_ids(ctx.cx(), pool, &ids).await {
+        Outcome::Ok(receipts) => {
+            for message in messages {
+                if receipts.contains(&message.id) {
+                    message.message_kind =
+                        Some(mcp_agent_mail_db::queries::ACK_RECEIPT_MESSAGE_KIND.to_string());
+                }
+            }
+        }
+        Outcome::Err(e) => tracing::warn!("fetch_inbox: ack receipt lookup failed: {e}"),
+        Outcome::Cancelled(_) | Outcome::Panicked(_) => {}
+    }
+}
+
+/// Set `sender_verified` on inbox entries sent with a verified sender
+/// secret (best-effort).
+async fn tag_verified_senders(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    messages: &mut [InboxMessage],
+) {
+    let ids: Vec<i64> = messages.iter().map(|m| m.id).collect();
+    match mcp_agent_mail_db::queries::list_sender_verified_message_ids(ctx.cx(), pool, &ids).await {
+        Outcome::Ok(verified) => {
+            for message in messages {
+                message.sender_verified = verified.contains(&message.id);
+            }
+        }
+        Outcome::Err(e) => tracing::warn!("fetch_inbox: sender verification lookup failed: {e}"),
+        Outcome::Cancelled(_) | Outcome::Panicked(_) => {}
+    }
+}
+
+/// Send the ack receipt owed for `agent_id`'s acknowledgement, if any
+/// (best-effort: a failure never fails the acknowledgement itself).
+async fn send_ack_receipt_best_effort(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    agent_id: i64,
+    message_id: i64,
+) {
+    match mcp_agent_mail_db::queries::send_ack_receipt(ctx.cx(), pool, agent_id, message_id).await {
+        Outcome::Ok(Some(receipt)) => tracing::debug!(
+            message_id,
+            receipt_message_id = receipt.id.unwrap_or(0),
+            "ack receipt sent"
+        ),
+        Outcome::Ok(None) => {}
+        Outcome::Err(e) => {
+            tracing::warn!(message_id, agent_id, "sending ack receipt failed: {e}");
+        }
+        Outcome::Cancelled(_) | Outcome::Panicked(_) => {}
+    }
+}
+
 fn apply_auto_read_timestamp(
     messages: &mut [InboxMessage],
     updated_message_ids: &[i64],
@@ -3428,7 +4112,8 @@ fn apply_auto_read_timestamp(
 /// - `message_id`: Message to mark
 ///
 /// # Returns
-/// Read status with timestamp
+/// Read status with timestamp, plus `changed`/`already` describing whether
+/// this call performed the transition
 ///
 /// # Conformance
 /// Python-parity.
@@ -3462,14 +4147,17 @@ pub async fn mark_message_read(
     // all projects), so the DB query `WHERE agent_id = ? AND message_id = ?`
     // implicitly scopes to the correct project. An agent in project A cannot
     // match a message_recipients row for project B because the agent_ids differ.
-    let read_ts = db_outcome_to_mcp_result(
+    let read = db_outcome_to_mcp_result(
         mcp_agent_mail_db::queries::mark_message_read(ctx.cx(), &pool, agent_id, message_id).await,
     )?;
 
     let response = ReadStatusResponse {
         message_id,
         read: true,
-        read_at: Some(micros_to_iso(read_ts)),
+        read_at: Some(micros_to_iso(read.read_ts)),
+        changed: read.changed,
+        already: read.already().map(str::to_string),
+        acknowledged_at: read.ack_ts.map(micros_to_iso),
     };
 
     tracing::debug!(
@@ -3606,7 +4294,10 @@ async fn replay_single_ack_intent(
     )
     .await
     {
-        Outcome::Ok(_) => Ok(()),
+        Outcome::Ok(_) => {
+            send_ack_receipt_best_effort(ctx, pool, agent_id, intent.message_id).await;
+            Ok(())
+        }
         Outcome::Err(error) => {
             let retryable = db_error_supports_ack_intent(&error);
             Err((error.to_string(), retryable))
@@ -3672,7 +4363,8 @@ async fn replay_queued_ack_intents(
 /// - `message_id`: Message to acknowledge
 ///
 /// # Returns
-/// Acknowledgement status with timestamps
+/// Acknowledgement status with timestamps, plus `changed`/`already` so racing
+/// callers can tell which of them recorded the acknowledgement
 ///
 /// # Conformance
 /// Python-parity.
@@ -3746,7 +4438,7 @@ pub async fn acknowledge_message(
 
     // Authorization note: agent_id is globally unique (auto-increment), so
     // the DB query implicitly scopes to the correct project. See mark_message_read.
-    let (read_ts, ack_ts) = match mcp_agent_mail_db::queries::acknowledge_message(
+    let ack = match mcp_agent_mail_db::queries::acknowledge_message(
         ctx.cx(),
         &pool,
         agent_id,
@@ -3768,6 +4460,8 @@ pub async fn acknowledge_message(
         other => db_outcome_to_mcp_result(other)?,
     };
 
+    send_ack_receipt_best_effort(ctx, &pool, agent_id, message_id).await;
+
     // The DB is reachable: opportunistically replay any previously-queued ack
     // intents so degraded-mode acknowledgements land once the mailbox recovers.
     replay_queued_ack_intents(ctx, &pool, &config).await;
@@ -3775,10 +4469,25 @@ pub async fn acknowledge_message(
     let response = AckStatusResponse {
         message_id,
         acknowledged: true,
-        acknowledged_at: Some(micros_to_iso(ack_ts)),
-        read_at: Some(micros_to_iso(read_ts)),
+        acknowledged_at: Some(micros_to_iso(ack.ack_ts)),
+        read_at: Some(micros_to_iso(ack.read_ts)),
+        changed: ack.changed,
+        already: ack.already().map(str::to_string),
     };
 
+    if ack.changed {
+        crate::webhooks::enqueue(
+            &config,
+            crate::webhooks::MESSAGE_ACKED,
+            json!({
+                "project": &project.slug,
+                "agent": &agent.name,
+                "message_id": message_id,
+                "acknowledged_at": micros_to_iso(ack.ack_ts),
+            }),
+        );
+    }
+
     tracing::debug!(
         "Acknowledged message {} for {} in project {}",
         message_id,
@@ -3980,13 +4689,17 @@ mod tests {
                 "successfully replayed ack intent should not remain queued"
             );
 
-            let (read_ts, ack_ts) =
-                match queries::acknowledge_message(&cx, &pool, recipient_id, message_id).await {
-                    Outcome::Ok(value) => value,
-                    other => panic!("verify ack failed: {other:?}"),
-                };
-            assert!(ack_ts > 0, "message should be acknowledged after replay");
-            assert!(read_ts > 0, "message should be marked read after replay");
+            let ack = match queries::acknowledge_message(&cx, &pool, recipient_id, message_id).await
+            {
+                Outcome::Ok(value) => value,
+                other => panic!("verify ack failed: {other:?}"),
+            };
+            assert!(!ack.changed, "message should be acknowledged after replay");
+            assert_eq!(ack.already(), Some("acked"));
+            assert!(
+                ack.read_ts > 0,
+                "message should be marked read after replay"
+            );
         });
     }
 
@@ -4096,6 +4809,7 @@ mod tests {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .expect_err("reply should fail when original sender metadata is missing");
@@ -5074,11 +5788,19 @@ mod tests {
             count: 0,
             attachments: vec![],
             verified_sender: false,
+            sender_verified: false,
+            expires_ts: None,
+            policy_cc: vec![],
+            policy_cc_skipped: vec![],
+            deferred_recipients: vec![],
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
         assert_eq!(json["count"], 0);
         assert!(json["deliveries"].as_array().unwrap().is_empty());
+        assert!(json.get("expires_ts").is_none());
+        assert!(json.get("policy_cc").is_none());
+        assert!(json.get("policy_cc_skipped").is_none());
     }
 
     #[test]
@@ -5099,6 +5821,9 @@ mod tests {
             read_ts: None,
             ack_ts: None,
             kind: "to".into(),
+            message_kind: None,
+            sender_verified: false,
+            importance_rank: None,
             attachments: vec![],
             body_md: None,
         };
@@ -5124,6 +5849,9 @@ mod tests {
             read_ts: None,
             ack_ts: None,
             kind: "to".into(),
+            message_kind: None,
+            sender_verified: false,
+            importance_rank: None,
             attachments: vec![json!({"path": "img.webp", "type": "file"})],
             body_md: Some("Hello world".into()),
         };
@@ -5175,6 +5903,9 @@ mod tests {
             read_ts: None,
             ack_ts: None,
             kind: "to".into(),
+            message_kind: None,
+            sender_verified: false,
+            importance_rank: None,
             attachments: vec![],
             body_md: None,
         };
@@ -5206,6 +5937,9 @@ mod tests {
                 read_ts: None,
                 ack_ts: None,
                 kind: "to".into(),
+                message_kind: None,
+                sender_verified: false,
+                importance_rank: None,
                 attachments: vec![],
                 body_md: None,
             },
@@ -5225,6 +5959,9 @@ mod tests {
                 read_ts: Some(old_read_at.clone()),
                 ack_ts: None,
                 kind: "to".into(),
+                message_kind: None,
+                sender_verified: false,
+                importance_rank: None,
                 attachments: vec![],
                 body_md: None,
             },
@@ -5244,6 +5981,9 @@ mod tests {
                 read_ts: None,
                 ack_ts: None,
                 kind: "to".into(),
+                message_kind: None,
+                sender_verified: false,
+                importance_rank: None,
                 attachments: vec![],
                 body_md: None,
             },
@@ -5264,6 +6004,9 @@ mod tests {
             message_id: 42,
             read: false,
             read_at: None,
+            changed: false,
+            already: None,
+            acknowledged_at: None,
         };
         let json_str = serde_json::to_string(&r).unwrap();
         assert!(!json_str.contains("read_at"));
@@ -5279,6 +6022,8 @@ mod tests {
             acknowledged: true,
             acknowledged_at: Some("2026-02-06T01:00:00Z".into()),
             read_at: Some("2026-02-06T00:30:00Z".into()),
+            changed: true,
+            already: None,
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
@@ -5336,6 +6081,8 @@ mod tests {
             deliveries: vec![],
             count: 1,
             verified_sender: false,
+            sender_verified: false,
+            deferred_recipients: vec![],
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let deserialized: ReplyMessageResponse = serde_json::from_str(&json_str).unwrap();
@@ -6245,6 +6992,8 @@ mod tests {
             acknowledged: false,
             acknowledged_at: None,
             read_at: None,
+            changed: false,
+            already: None,
         };
         let json_str = serde_json::to_string(&r).unwrap();
         assert!(!json_str.contains("acknowledged_at"));
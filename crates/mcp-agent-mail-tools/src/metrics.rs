@@ -10,13 +10,14 @@
 
 #![forbid(unsafe_code)]
 
-use mcp_agent_mail_core::Log2Histogram;
+use mcp_agent_mail_core::{HistogramSnapshot, Log2Histogram};
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
 use std::sync::RwLock;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::TOOL_CLUSTER_MAP;
+use crate::extensions;
 
 const TOOL_COUNT: usize = TOOL_CLUSTER_MAP.len();
 
@@ -27,29 +28,59 @@ static TOOL_CALLS: LazyLock<[AtomicU64; TOOL_COUNT]> =
     LazyLock::new(|| std::array::from_fn(|_| AtomicU64::new(0)));
 static TOOL_ERRORS: LazyLock<[AtomicU64; TOOL_COUNT]> =
     LazyLock::new(|| std::array::from_fn(|_| AtomicU64::new(0)));
+static TOOL_RATE_LIMITED: LazyLock<[AtomicU64; TOOL_COUNT]> =
+    LazyLock::new(|| std::array::from_fn(|_| AtomicU64::new(0)));
 static TOOL_LATENCIES: LazyLock<[RwLock<Log2Histogram>; TOOL_COUNT]> =
     LazyLock::new(|| std::array::from_fn(|_| RwLock::new(Log2Histogram::new())));
 
 /// Convert tool name -> stable index into the pre-allocated counter arrays.
 ///
-/// The index corresponds to the tool's position in `TOOL_CLUSTER_MAP`.
+/// Built-in tools map to their position in `TOOL_CLUSTER_MAP`; registered
+/// extension tools follow after them.
 #[must_use]
 pub fn tool_index(tool_name: &str) -> Option<usize> {
     TOOL_CLUSTER_MAP
         .iter()
         .position(|(name, _cluster)| *name == tool_name)
+        .or_else(|| extensions::extension_tool_index(tool_name))
 }
 
 #[inline]
 pub fn record_call_idx(tool_index: usize) {
-    debug_assert!(tool_index < TOOL_COUNT);
-    TOOL_CALLS[tool_index].fetch_add(1, Ordering::Relaxed);
+    match TOOL_CALLS.get(tool_index) {
+        Some(counter) => {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        None => extensions::record_call(tool_index),
+    }
 }
 
 #[inline]
 pub fn record_error_idx(tool_index: usize) {
-    debug_assert!(tool_index < TOOL_COUNT);
-    TOOL_ERRORS[tool_index].fetch_add(1, Ordering::Relaxed);
+    match TOOL_ERRORS.get(tool_index) {
+        Some(counter) => {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        None => extensions::record_error(tool_index),
+    }
+}
+
+/// Count a call rejected by the per-tool rate limit before it reached the tool.
+#[inline]
+pub fn record_rate_limited_idx(tool_index: usize) {
+    match TOOL_RATE_LIMITED.get(tool_index) {
+        Some(counter) => {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        None => extensions::record_rate_limited(tool_index),
+    }
+}
+
+/// Count a rate-limited call by tool name (unknown names are ignored).
+pub fn record_rate_limited(tool_name: &str) {
+    if let Some(idx) = tool_index(tool_name) {
+        record_rate_limited_idx(idx);
+    }
 }
 
 /// Record a successful tool call.
@@ -79,9 +110,13 @@ pub fn record_error(tool_name: &str) {
 /// Record per-tool latency in microseconds (called from `InstrumentedTool`).
 #[inline]
 pub fn record_latency_idx(tool_index: usize, latency_us: u64) {
-    debug_assert!(tool_index < TOOL_COUNT);
-    if let Ok(h) = TOOL_LATENCIES[tool_index].read() {
-        h.record(latency_us);
+    match TOOL_LATENCIES.get(tool_index) {
+        Some(histogram) => {
+            if let Ok(h) = histogram.read() {
+                h.record(latency_us);
+            }
+        }
+        None => extensions::record_latency(tool_index, latency_us),
     }
 }
 
@@ -102,11 +137,15 @@ pub fn reset_tool_metrics() {
     for e in TOOL_ERRORS.iter() {
         e.store(0, Ordering::Relaxed);
     }
+    for r in TOOL_RATE_LIMITED.iter() {
+        r.store(0, Ordering::Relaxed);
+    }
     for h in TOOL_LATENCIES.iter() {
         if let Ok(guard) = h.write() {
             guard.reset();
         }
     }
+    extensions::reset_counters();
 }
 
 /// Reset only the per-tool latency histograms (rolling window support).
@@ -119,6 +158,7 @@ pub fn reset_tool_latencies() {
             guard.reset();
         }
     }
+    extensions::reset_latencies();
 }
 
 /// Static metadata for each tool (capabilities, complexity).
@@ -448,6 +488,9 @@ pub struct MetricsSnapshotEntry {
     pub name: String,
     pub calls: u64,
     pub errors: u64,
+    /// Calls rejected by the per-tool rate limit (`AGENT_MAIL_TOOL_RATE_LIMITS`).
+    #[serde(default)]
+    pub rate_limited: u64,
     pub cluster: String,
     pub capabilities: Vec<String>,
     pub complexity: String,
@@ -465,7 +508,10 @@ fn us_to_ms(us: u64) -> f64 {
 
 /// Build a `LatencySnapshot` from a tool's histogram, or `None` if no data.
 fn latency_snapshot_for(idx: usize) -> Option<LatencySnapshot> {
-    let hs = TOOL_LATENCIES[idx].read().ok()?.snapshot();
+    latency_snapshot_from(&TOOL_LATENCIES[idx].read().ok()?.snapshot())
+}
+
+fn latency_snapshot_from(hs: &HistogramSnapshot) -> Option<LatencySnapshot> {
     if hs.count == 0 {
         return None;
     }
@@ -483,7 +529,7 @@ fn latency_snapshot_for(idx: usize) -> Option<LatencySnapshot> {
 
 /// Produce a sorted metrics snapshot.
 ///
-/// Returns all tools that have been called (calls > 0), sorted alphabetically
+/// Returns all tools that have been called or rate limited, sorted alphabetically
 /// by name, enriched with cluster, capabilities, complexity, and per-tool
 /// latency histogram statistics (P50/P95/P99).
 #[must_use]
@@ -493,7 +539,8 @@ pub fn tool_metrics_snapshot() -> Vec<MetricsSnapshotEntry> {
         .enumerate()
         .filter_map(|(idx, (name, cluster))| {
             let calls = TOOL_CALLS[idx].load(Ordering::Relaxed);
-            if calls == 0 {
+            let rate_limited = TOOL_RATE_LIMITED[idx].load(Ordering::Relaxed);
+            if calls == 0 && rate_limited == 0 {
                 return None;
             }
 
@@ -503,6 +550,7 @@ pub fn tool_metrics_snapshot() -> Vec<MetricsSnapshotEntry> {
                 name: (*name).to_string(),
                 calls,
                 errors,
+                rate_limited,
                 cluster: (*cluster).to_string(),
                 capabilities: meta
                     .map(|m| m.capabilities.iter().map(|s| (*s).to_string()).collect())
@@ -512,6 +560,11 @@ pub fn tool_metrics_snapshot() -> Vec<MetricsSnapshotEntry> {
             })
         })
         .collect();
+    entries.extend(
+        extension_snapshot_entries()
+            .into_iter()
+            .filter(|entry| entry.calls > 0 || entry.rate_limited > 0),
+    );
 
     entries.sort_by(|a, b| a.name.cmp(&b.name));
     entries
@@ -531,6 +584,7 @@ pub fn tool_metrics_snapshot_full() -> Vec<MetricsSnapshotEntry> {
                 name: (*name).to_string(),
                 calls: TOOL_CALLS[idx].load(Ordering::Relaxed),
                 errors: TOOL_ERRORS[idx].load(Ordering::Relaxed),
+                rate_limited: TOOL_RATE_LIMITED[idx].load(Ordering::Relaxed),
                 cluster: (*cluster).to_string(),
                 capabilities: meta
                     .map(|m| m.capabilities.iter().map(|s| (*s).to_string()).collect())
@@ -540,11 +594,29 @@ pub fn tool_metrics_snapshot_full() -> Vec<MetricsSnapshotEntry> {
             }
         })
         .collect();
+    entries.extend(extension_snapshot_entries());
 
     entries.sort_by(|a, b| a.name.cmp(&b.name));
     entries
 }
 
+/// Snapshot entries for registered extension tools (including zero-call ones).
+fn extension_snapshot_entries() -> Vec<MetricsSnapshotEntry> {
+    extensions::counter_snapshots()
+        .into_iter()
+        .map(|counters| MetricsSnapshotEntry {
+            name: counters.spec.name.to_string(),
+            calls: counters.calls,
+            errors: counters.errors,
+            rate_limited: counters.rate_limited,
+            cluster: counters.spec.cluster.to_string(),
+            capabilities: counters.spec.capabilities,
+            complexity: counters.spec.complexity,
+            latency: latency_snapshot_from(&counters.latency),
+        })
+        .collect()
+}
+
 /// Return only tools flagged as slow (p95 > 500ms).
 ///
 /// Useful for alerting and diagnostic reports.
@@ -600,8 +672,12 @@ mod tests {
     #[test]
     fn snapshot_full_includes_all_tools() {
         let full = tool_metrics_snapshot_full();
-        // Should include all tools from TOOL_CLUSTER_MAP.
-        assert_eq!(full.len(), TOOL_CLUSTER_MAP.len());
+        // Should include all tools from TOOL_CLUSTER_MAP (plus any extension
+        // tools registered by other tests).
+        assert!(full.len() >= TOOL_CLUSTER_MAP.len());
+        for (name, _cluster) in TOOL_CLUSTER_MAP {
+            assert!(full.iter().any(|e| e.name == *name), "missing {name}");
+        }
 
         // Sorted alphabetically.
         for window in full.windows(2) {
@@ -801,6 +877,64 @@ mod tests {
         assert!(lc_full.latency.is_none());
     }
 
+    #[test]
+    fn extension_tools_share_index_space_and_snapshots() {
+        let _guard = METRICS_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let idx = extensions::register_extension_tool(extensions::ExtensionToolSpec {
+            name: "ext_metrics_probe",
+            cluster: "ext_tests",
+            summary: "metrics probe".to_string(),
+            input_schema: serde_json::json!({ "type": "object" }),
+            capabilities: vec!["tickets".to_string()],
+            complexity: "low".to_string(),
+        });
+        assert!(idx >= TOOL_COUNT);
+        assert_eq!(tool_index("ext_metrics_probe"), Some(idx));
+
+        record_call_idx(idx);
+        record_error_idx(idx);
+        record_latency_idx(idx, 1_500);
+
+        let snap = tool_metrics_snapshot();
+        let entry = snap
+            .iter()
+            .find(|e| e.name == "ext_metrics_probe")
+            .expect("extension tool in snapshot");
+        assert!(entry.calls >= 1);
+        assert!(entry.errors >= 1);
+        assert_eq!(entry.cluster, "ext_tests");
+        assert_eq!(entry.capabilities, vec!["tickets".to_string()]);
+        assert!(entry.latency.is_some());
+    }
+
+    #[test]
+    fn rate_limited_calls_are_counted_and_surface_in_snapshot() {
+        let _guard = METRICS_LOCK
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        reset_tool_metrics();
+
+        record_rate_limited("send_message");
+        record_rate_limited("send_message");
+        record_rate_limited("no_such_tool");
+
+        // Rejected calls never reach the tool, yet the entry is still reported.
+        let snap = tool_metrics_snapshot();
+        let entry = snap
+            .iter()
+            .find(|e| e.name == "send_message")
+            .expect("rate-limited tool in snapshot");
+        assert_eq!(entry.calls, 0);
+        assert_eq!(entry.rate_limited, 2);
+
+        reset_tool_metrics();
+        let full = tool_metrics_snapshot_full();
+        let entry = full.iter().find(|e| e.name == "send_message").unwrap();
+        assert_eq!(entry.rate_limited, 0);
+    }
+
     #[test]
     fn snapshot_full_accurate_counts_after_calls() {
         let _guard = METRICS_LOCK
@@ -1034,6 +1168,7 @@ mod tests {
             name: "health_check".to_string(),
             calls: 42,
             errors: 3,
+            rate_limited: 0,
             cluster: "infrastructure".to_string(),
             capabilities: vec!["infrastructure".to_string()],
             complexity: "low".to_string(),
@@ -1053,6 +1188,7 @@ mod tests {
             name: "test".to_string(),
             calls: 1,
             errors: 0,
+            rate_limited: 0,
             cluster: "test".to_string(),
             capabilities: Vec::new(),
             complexity: "low".to_string(),
@@ -820,6 +820,9 @@ pub async fn fetch_inbox_product(
                 read_ts: row.read_ts.map(micros_to_iso),
                 ack_ts: row.ack_ts.map(micros_to_iso),
                 kind: row.kind,
+                message_kind: None,
+                sender_verified: false,
+                importance_rank: None,
                 attachments: parse_attachment_metadata_json(&msg.attachments),
                 body_md: if with_bodies { Some(msg.body_md) } else { None },
             }
@@ -88,6 +88,13 @@ struct PendingReservationConflict {
 pub struct ReservationResponse {
     pub granted: Vec<GrantedReservation>,
     pub conflicts: Vec<ReservationConflict>,
+    /// Lease token for a `fenced` request. Returned only here; renew and
+    /// release of the granted reservations must present it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_token: Option<String>,
+    /// An `atomic` request hit a conflict, so nothing was granted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub atomic_rollback: bool,
 }
 
 /// Result of an authoritative, side-effect-free reservation conflict check.
@@ -1622,14 +1629,17 @@ pub async fn check_file_reservation_conflicts(
 /// - `ttl_seconds`: Time to live (min 60s, default: 3600)
 /// - `exclusive`: Exclusive intent (default: true)
 /// - `reason`: Explanation for reservation
+/// - `fenced`: Issue a lease token that renew/release must present
+/// - `atomic`: All-or-nothing: any conflict grants no path at all
 ///
 /// # Returns
-/// Granted reservations and any conflicts
+/// Granted reservations and any conflicts (plus `lease_token` when fenced,
+/// and `atomic_rollback: true` when an atomic request was refused)
 ///
 /// # Conformance
 /// Python-parity.
 #[tool(
-    description = "Request advisory file reservations (leases) on project-relative paths/globs.\n\nSemantics\n---------\n- Conflicts are reported if an overlapping active exclusive reservation exists held by another agent\n- Glob matching is symmetric (`fnmatchcase(a,b)` or `fnmatchcase(b,a)`), including exact matches\n- When granted, a JSON artifact is written under `file_reservations/<sha1(path)>.json` and the DB is updated\n- TTL must be >= 60 seconds (enforced by the server settings/policy)\n- Server-side enforcement (if enabled) only checks reservations that target mail archive paths\n  such as `agents/`, `messages/`, or `attachments/`; code repo enforcement is via the pre-commit guard\n\nDo / Don't\n----------\nDo:\n- Reserve files before starting edits to signal intent to other agents.\n- Use specific, minimal patterns (e.g., `app/api/*.py`) instead of broad globs.\n- Set a realistic TTL and renew with `renew_file_reservations` if you need more time.\n\nDon't:\n- Reserve the entire repository or very broad patterns (e.g., `**/*`) unless absolutely necessary.\n- Hold long-lived exclusive reservations when you are not actively editing.\n- Ignore conflicts; resolve them by coordinating with holders or waiting for expiry.\n\nParameters\n----------\nproject_key : str\nagent_name : str\npaths : list[str]\n    File paths or glob patterns relative to the project workspace (e.g., \"app/api/*.py\").\nttl_seconds : int\n    Time to live for the file_reservation; expired file_reservations are auto-released.\nexclusive : bool\n    If true, exclusive intent; otherwise shared/observe-only.\nreason : str\n    Optional explanation (helps humans reviewing Git artifacts).\n\nReturns\n-------\ndict\n    { granted: [{id, path_pattern, exclusive, reason, expires_ts}], conflicts: [{path, holders: [...]}] }\n\nExample\n-------\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"12\",\"method\":\"tools/call\",\"params\":{\"name\":\"file_reservation_paths\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"agent_name\":\"GreenCastle\",\"paths\":[\"app/api/*.py\"],\n  \"ttl_seconds\":7200,\"exclusive\":true,\"reason\":\"migrations\"\n}}}\n```"
+    description = "Request advisory file reservations (leases) on project-relative paths/globs.\n\nSemantics\n---------\n- Conflicts are reported if an overlapping active exclusive reservation exists held by another agent\n- Glob matching is symmetric (`fnmatchcase(a,b)` or `fnmatchcase(b,a)`), including exact matches\n- When granted, a JSON artifact is written under `file_reservations/<sha1(path)>.json` and the DB is updated\n- TTL must be >= 60 seconds (enforced by the server settings/policy)\n- Server-side enforcement (if enabled) only checks reservations that target mail archive paths\n  such as `agents/`, `messages/`, or `attachments/`; code repo enforcement is via the pre-commit guard\n\nDo / Don't\n----------\nDo:\n- Reserve files before starting edits to signal intent to other agents.\n- Use specific, minimal patterns (e.g., `app/api/*.py`) instead of broad globs.\n- Set a realistic TTL and renew with `renew_file_reservations` if you need more time.\n\nDon't:\n- Reserve the entire repository or very broad patterns (e.g., `**/*`) unless absolutely necessary.\n- Hold long-lived exclusive reservations when you are not actively editing.\n- Ignore conflicts; resolve them by coordinating with holders or waiting for expiry.\n\nParameters\n----------\nproject_key : str\nagent_name : str\npaths : list[str]\n    File paths or glob patterns relative to the project workspace (e.g., \"app/api/*.py\").\nttl_seconds : int\n    Time to live for the file_reservation; expired file_reservations are auto-released.\nexclusive : bool\n    If true, exclusive intent; otherwise shared/observe-only.\nreason : str\n    Optional explanation (helps humans reviewing Git artifacts).\n\nReturns\n-------\ndict\n    { granted: [{id, path_pattern, exclusive, reason, expires_ts}], conflicts: [{path, holders: [...]}] }\n\nExample\n-------\n```json\n{\"jsonrpc\":\"2.0\",\"id\":\"12\",\"method\":\"tools/call\",\"params\":{\"name\":\"file_reservation_paths\",\"arguments\":{\n  \"project_key\":\"/abs/path/backend\",\"agent_name\":\"GreenCastle\",\"paths\":[\"app/api/*.py\"],\n  \"ttl_seconds\":7200,\"exclusive\":true,\"reason\":\"migrations\"\n}}}\n```\n\nAtomic requests\n---------------\nPass `atomic=true` to grant every path or none of them. If any path conflicts, nothing is created and the\nresponse carries `granted: []`, the full `conflicts` list, and `atomic_rollback: true`."
 )]
 pub async fn file_reservation_paths(
     ctx: &McpContext,
@@ -1639,6 +1649,8 @@ pub async fn file_reservation_paths(
     ttl_seconds: Option<i64>,
     exclusive: Option<bool>,
     reason: Option<String>,
+    fenced: Option<bool>,
+    atomic: Option<bool>,
 ) -> McpResult<String> {
     let agent_name =
         mcp_agent_mail_core::models::normalize_agent_name(&agent_name).unwrap_or(agent_name);
@@ -1890,6 +1902,14 @@ pub async fn file_reservation_paths(
             .collect()
     };
 
+    // An atomic request grants nothing once any path conflicts. Otherwise the
+    // whole set goes to the DB layer, whose conflict check and inserts share a
+    // single transaction, so a race lost there rolls back every path too.
+    let atomic = atomic.unwrap_or(false);
+    if atomic && !conflicts.is_empty() {
+        paths_to_grant.clear();
+    }
+
     // Grant non-conflicting reservations.
     //
     // The DB layer performs its own conflict check inside an IMMEDIATE
@@ -2048,7 +2068,59 @@ pub async fn file_reservation_paths(
     }
 
     let conflicts_len = conflicts.len();
-    let response = ReservationResponse { granted, conflicts };
+    if !conflicts.is_empty() {
+        // Local `on_reservation_conflict_hook` and the outbound webhook for
+        // the requesting agent; both run in the background and never affect
+        // the response.
+        let config = Config::get();
+        mcp_agent_mail_core::hooks::dispatch(
+            &config,
+            mcp_agent_mail_core::hooks::HookEvent {
+                kind: mcp_agent_mail_core::hooks::HookEventKind::ReservationConflict,
+                project_slug: project.slug.clone(),
+                project_human_key: project.human_key.clone(),
+                agent: Some(agent.name.clone()),
+                detail: json!({ "conflicts": &conflicts }),
+            },
+        );
+        crate::webhooks::enqueue(
+            &config,
+            crate::webhooks::RESERVATION_CONFLICT,
+            json!({
+                "project": &project.slug,
+                "agent": &agent.name,
+                "conflicts": &conflicts,
+            }),
+        );
+    }
+    let lease_token = if fenced.unwrap_or(false) && !granted.is_empty() {
+        let token = mcp_agent_mail_core::setup::generate_token().map_err(|e| {
+            McpError::new(
+                McpErrorCode::InternalError,
+                format!("lease token generation failed: {e}"),
+            )
+        })?;
+        let ids: Vec<i64> = granted.iter().map(|g| g.id).collect();
+        db_outcome_to_mcp_result(
+            mcp_agent_mail_db::queries::fence_file_reservations(
+                ctx.cx(),
+                &pool,
+                &ids,
+                &mcp_agent_mail_db::queries::lease_token_hash(&token),
+            )
+            .await,
+        )?;
+        Some(token)
+    } else {
+        None
+    };
+    let atomic_rollback = atomic && granted.is_empty() && !conflicts.is_empty();
+    let response = ReservationResponse {
+        granted,
+        conflicts,
+        lease_token,
+        atomic_rollback,
+    };
 
     tracing::debug!(
         "Reserved {} paths for {} in project {} (ttl: {}s, exclusive: {}, conflicts: {})",
@@ -2064,6 +2136,50 @@ pub async fn file_reservation_paths(
         .map_err(|e| McpError::new(McpErrorCode::InternalError, format!("JSON error: {e}")))
 }
 
+/// Refuse to `action` fenced reservations unless `lease_token` matches the
+/// token issued with them. `target_ids` of `None` means every active
+/// reservation the agent holds.
+async fn ensure_lease_token(
+    ctx: &McpContext,
+    pool: &mcp_agent_mail_db::DbPool,
+    project_id: i64,
+    agent_id: i64,
+    target_ids: Option<&[i64]>,
+    lease_token: Option<&str>,
+    action: &str,
+) -> McpResult<()> {
+    let mut fences = db_outcome_to_mcp_result(
+        mcp_agent_mail_db::queries::list_agent_file_reservation_fences(
+            ctx.cx(),
+            pool,
+            project_id,
+            agent_id,
+        )
+        .await,
+    )?;
+    if let Some(ids) = target_ids {
+        fences.retain(|id, _| ids.contains(id));
+    }
+    let blocked = mcp_agent_mail_db::queries::fenced_reservations_blocking(&fences, lease_token);
+    if blocked.is_empty() {
+        return Ok(());
+    }
+    let reason = if lease_token.is_some() {
+        "the lease token does not match"
+    } else {
+        "no lease token was given"
+    };
+    Err(legacy_tool_error(
+        "RESERVATION_FENCED",
+        format!(
+            "Cannot {action} fenced file reservation(s) {blocked:?}: {reason}. Pass the \
+             lease_token returned when they were created."
+        ),
+        true,
+        json!({ "reservation_ids": blocked, "action": action }),
+    ))
+}
+
 /// Release active file reservations held by an agent.
 ///
 /// If both paths and `file_reservation_ids` are omitted, releases all active reservations.
@@ -2073,6 +2189,7 @@ pub async fn file_reservation_paths(
 /// - `agent_name`: Agent releasing reservations
 /// - `paths`: Restrict release to matching path patterns
 /// - `file_reservation_ids`: Restrict release to matching IDs
+/// - `lease_token`: Token required when any targeted reservation is fenced
 ///
 /// # Conformance
 /// Python-parity.
@@ -2086,6 +2203,7 @@ pub async fn release_file_reservations(
     agent_name: String,
     paths: Option<Vec<String>>,
     file_reservation_ids: Option<Vec<i64>>,
+    lease_token: Option<String>,
 ) -> McpResult<String> {
     let agent_name =
         mcp_agent_mail_core::models::normalize_agent_name(&agent_name).unwrap_or(agent_name);
@@ -2190,6 +2308,17 @@ pub async fn release_file_reservations(
         None
     };
 
+    ensure_lease_token(
+        ctx,
+        &pool,
+        project_id,
+        agent_id,
+        ids_to_release.as_deref(),
+        lease_token.as_deref(),
+        "release",
+    )
+    .await?;
+
     // Perform the DB release (returns the actual updated rows)
     let released_rows = match mcp_agent_mail_db::queries::release_reservations(
         ctx.cx(),
@@ -2264,6 +2393,7 @@ pub async fn release_file_reservations(
 /// - `extend_seconds`: Seconds to extend from max(now, expiry) (min 60s, default: 1800)
 /// - `paths`: Restrict to matching path patterns
 /// - `file_reservation_ids`: Restrict to matching IDs
+/// - `lease_token`: Token required when any targeted reservation is fenced
 ///
 /// # Conformance
 /// Python-parity.
@@ -2278,6 +2408,7 @@ pub async fn renew_file_reservations(
     extend_seconds: Option<i64>,
     paths: Option<Vec<String>>,
     file_reservation_ids: Option<Vec<i64>>,
+    lease_token: Option<String>,
 ) -> McpResult<String> {
     let agent_name =
         mcp_agent_mail_core::models::normalize_agent_name(&agent_name).unwrap_or(agent_name);
@@ -2320,6 +2451,16 @@ pub async fn renew_file_reservations(
         file_reservation_ids.as_deref(),
     );
     let ids_to_renew: Vec<i64> = previous_expires_by_id.keys().copied().collect();
+    ensure_lease_token(
+        ctx,
+        &pool,
+        project_id,
+        agent_id,
+        Some(&ids_to_renew),
+        lease_token.as_deref(),
+        "renew",
+    )
+    .await?;
 
     let renewed_rows = db_outcome_to_mcp_result(
         mcp_agent_mail_db::queries::renew_reservations(
@@ -3996,6 +4137,8 @@ mod tests {
                     Some(3600),
                     Some(true),
                     Some("f1 reconcile holder".to_string()),
+                    None,
+                    None,
                 )
                 .await
                 .expect("initial reservation");
@@ -4039,6 +4182,8 @@ mod tests {
                     Some(3600),
                     Some(false),
                     Some("f1 reconcile next access".to_string()),
+                    None,
+                    None,
                 )
                 .await
                 .expect("second reservation triggers reconcile-on-read");
@@ -4062,6 +4207,156 @@ mod tests {
         });
     }
 
+    #[test]
+    fn atomic_reserve_grants_nothing_when_any_path_conflicts() {
+        with_serialized_reservations(|| {
+            run_async(|cx| async move {
+                let pool = get_db_pool().expect("db pool");
+                let project_key = format!("/tmp/atomic-reserve-{}", unique_suffix());
+                let project = ensure_project(&cx, &pool, &project_key).await;
+                let project_id = project.id.unwrap_or(0);
+                register_agent(&cx, &pool, project_id, "GreenCastle").await;
+                let requester = register_agent(&cx, &pool, project_id, "BlueLake").await;
+                let requester_id = requester.id.unwrap_or(0);
+                let ctx = McpContext::new(cx.clone(), 1);
+
+                file_reservation_paths(
+                    &ctx,
+                    project_key.clone(),
+                    "GreenCastle".to_string(),
+                    vec!["src/d.rs".to_string()],
+                    Some(3600),
+                    Some(true),
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .expect("holder reserve");
+
+                let paths: Vec<String> = ["src/a.rs", "src/b.rs", "src/c.rs", "src/d.rs"]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                let response: Value = serde_json::from_str(
+                    &file_reservation_paths(
+                        &ctx,
+                        project_key.clone(),
+                        "BlueLake".to_string(),
+                        paths,
+                        Some(3600),
+                        Some(true),
+                        None,
+                        None,
+                        Some(true),
+                    )
+                    .await
+                    .expect("atomic reserve"),
+                )
+                .expect("reserve json");
+                assert_eq!(response["granted"], serde_json::json!([]));
+                assert_eq!(response["atomic_rollback"], true);
+                assert_eq!(response["conflicts"][0]["path"], "src/d.rs");
+
+                let active = match queries::get_active_reservations(&cx, &pool, project_id).await {
+                    Outcome::Ok(rows) => rows,
+                    other => panic!("get_active_reservations failed: {other:?}"),
+                };
+                assert!(
+                    active.iter().all(|row| row.agent_id != requester_id),
+                    "an atomic rollback must not leave a partial hold"
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn atomic_reserve_race_has_exactly_one_winner() {
+        const ROUNDS: usize = 8;
+        with_serialized_reservations(|| {
+            for round in 0..ROUNDS {
+                let project_key = format!("/tmp/atomic-race-{}-{round}", unique_suffix());
+                let (project_id, agent_ids) = run_async(|cx| {
+                    let project_key = project_key.clone();
+                    async move {
+                        let pool = get_db_pool().expect("db pool");
+                        let project = ensure_project(&cx, &pool, &project_key).await;
+                        let project_id = project.id.unwrap_or(0);
+                        let green = register_agent(&cx, &pool, project_id, "GreenCastle").await;
+                        let blue = register_agent(&cx, &pool, project_id, "BlueLake").await;
+                        (project_id, [green.id.unwrap_or(0), blue.id.unwrap_or(0)])
+                    }
+                });
+
+                // The sets overlap on `src/shared.rs`.
+                let requests = [
+                    ("GreenCastle", ["src/a.rs", "src/b.rs", "src/shared.rs"]),
+                    ("BlueLake", ["src/shared.rs", "src/y.rs", "src/z.rs"]),
+                ];
+                let barrier = std::sync::Barrier::new(requests.len());
+                let responses: Vec<Value> = std::thread::scope(|scope| {
+                    let handles: Vec<_> = requests
+                        .iter()
+                        .map(|(agent, paths)| {
+                            let barrier = &barrier;
+                            let project_key = project_key.clone();
+                            scope.spawn(move || {
+                                run_async(|cx| async move {
+                                    let ctx = McpContext::new(cx.clone(), 1);
+                                    barrier.wait();
+                                    let raw = file_reservation_paths(
+                                        &ctx,
+                                        project_key,
+                                        (*agent).to_string(),
+                                        paths.iter().map(ToString::to_string).collect(),
+                                        Some(3600),
+                                        Some(true),
+                                        None,
+                                        None,
+                                        Some(true),
+                                    )
+                                    .await
+                                    .expect("atomic reserve");
+                                    serde_json::from_str::<Value>(&raw).expect("reserve json")
+                                })
+                            })
+                        })
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| handle.join().expect("racer thread"))
+                        .collect()
+                });
+
+                let winners: Vec<usize> = responses
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, response)| response["granted"].as_array().map_or(0, Vec::len) == 3)
+                    .map(|(index, _)| index)
+                    .collect();
+                assert_eq!(winners.len(), 1, "round {round}: {responses:?}");
+                let loser = 1 - winners[0];
+                assert_eq!(responses[loser]["granted"], serde_json::json!([]));
+                assert_eq!(responses[loser]["atomic_rollback"], true);
+
+                let active = run_async(|cx| async move {
+                    let pool = get_db_pool().expect("db pool");
+                    match queries::get_active_reservations(&cx, &pool, project_id).await {
+                        Outcome::Ok(rows) => rows,
+                        other => panic!("get_active_reservations failed: {other:?}"),
+                    }
+                });
+                assert_eq!(active.len(), 3, "round {round}: only the winner's paths");
+                assert!(
+                    active
+                        .iter()
+                        .all(|row| row.agent_id == agent_ids[winners[0]]),
+                    "round {round}: the loser must hold nothing"
+                );
+            }
+        });
+    }
+
     // -----------------------------------------------------------------------
     // F3 (br-bvq1x.6.3): idempotent, parity-aware release_file_reservations
     // -----------------------------------------------------------------------
@@ -4088,6 +4383,8 @@ mod tests {
                     Some(3600),
                     Some(true),
                     None,
+                    None,
+                    None,
                 )
                 .await
                 .expect("reserve");
@@ -4099,6 +4396,7 @@ mod tests {
                         "GreenCastle".to_string(),
                         None,
                         None,
+                        None,
                     )
                     .await
                     .expect("first release"),
@@ -4116,6 +4414,7 @@ mod tests {
                         "GreenCastle".to_string(),
                         None,
                         None,
+                        None,
                     )
                     .await
                     .expect("second release must not error"),
@@ -4130,6 +4429,98 @@ mod tests {
         });
     }
 
+    #[test]
+    fn fenced_reservation_requires_lease_token_for_renew_and_release() {
+        with_serialized_reservations(|| {
+            run_async(|cx| async move {
+                let pool = get_db_pool().expect("db pool");
+                let project_key = format!("/tmp/fenced-lease-{}", unique_suffix());
+                let project = ensure_project(&cx, &pool, &project_key).await;
+                let project_id = project.id.unwrap_or(0);
+                register_agent(&cx, &pool, project_id, "GreenCastle").await;
+                let ctx = McpContext::new(cx.clone(), 1);
+
+                let granted: Value = serde_json::from_str(
+                    &file_reservation_paths(
+                        &ctx,
+                        project_key.clone(),
+                        "GreenCastle".to_string(),
+                        vec!["src/**".to_string()],
+                        Some(3600),
+                        Some(true),
+                        None,
+                        Some(true),
+                        None,
+                    )
+                    .await
+                    .expect("fenced reserve"),
+                )
+                .expect("reserve json");
+                let token = granted["lease_token"]
+                    .as_str()
+                    .expect("fenced reserve returns a lease token")
+                    .to_string();
+
+                for (lease_token, label) in
+                    [(None, "missing"), (Some("wrong".to_string()), "wrong")]
+                {
+                    let err = release_file_reservations(
+                        &ctx,
+                        project_key.clone(),
+                        "GreenCastle".to_string(),
+                        None,
+                        None,
+                        lease_token,
+                    )
+                    .await
+                    .expect_err("release without the lease token must be rejected");
+                    let data = err.data.expect("error payload");
+                    assert_eq!(data["error"]["type"], "RESERVATION_FENCED", "{label} token");
+                }
+
+                let err = renew_file_reservations(
+                    &ctx,
+                    project_key.clone(),
+                    "GreenCastle".to_string(),
+                    Some(600),
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .expect_err("renew without the lease token must be rejected");
+                let data = err.data.expect("error payload");
+                assert_eq!(data["error"]["type"], "RESERVATION_FENCED");
+
+                renew_file_reservations(
+                    &ctx,
+                    project_key.clone(),
+                    "GreenCastle".to_string(),
+                    Some(600),
+                    None,
+                    None,
+                    Some(token.clone()),
+                )
+                .await
+                .expect("renew with the lease token");
+                let released: Value = serde_json::from_str(
+                    &release_file_reservations(
+                        &ctx,
+                        project_key.clone(),
+                        "GreenCastle".to_string(),
+                        None,
+                        None,
+                        Some(token),
+                    )
+                    .await
+                    .expect("release with the lease token"),
+                )
+                .expect("release json");
+                assert_eq!(released["released"].as_i64(), Some(1));
+            });
+        });
+    }
+
     #[test]
     fn release_reconciles_missing_archive_artifact_for_other_active_holder() {
         // Acceptance: releasing reconciles both stores. A still-active holder's
@@ -4156,6 +4547,8 @@ mod tests {
                     Some(3600),
                     Some(true),
                     None,
+                    None,
+                    None,
                 )
                 .await
                 .expect("holder reserve");
@@ -4167,6 +4560,8 @@ mod tests {
                     Some(3600),
                     Some(false),
                     None,
+                    None,
+                    None,
                 )
                 .await
                 .expect("releaser reserve");
@@ -4198,6 +4593,7 @@ mod tests {
                     "BlueLake".to_string(),
                     None,
                     None,
+                    None,
                 )
                 .await
                 .expect("release triggers reconcile");
@@ -4243,6 +4639,8 @@ mod tests {
                     Some(3600),
                     Some(true),
                     None,
+                    None,
+                    None,
                 )
                 .await
                 .expect("holder reserve");
@@ -4292,6 +4690,8 @@ mod tests {
                     Some(3600),
                     Some(true),
                     None,
+                    None,
+                    None,
                 )
                 .await
                 .expect("reacquire");
@@ -4405,6 +4805,8 @@ mod tests {
                     expires_ts: "2026-02-06T04:00:00Z".into(),
                 }],
             }],
+            lease_token: None,
+            atomic_rollback: false,
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
@@ -4456,6 +4858,8 @@ mod tests {
                     expires_ts: "2026-02-06T04:00:00Z".into(),
                 }],
             }],
+            lease_token: None,
+            atomic_rollback: false,
         };
         let json_str = serde_json::to_string(&original).unwrap();
         let deserialized: ReservationResponse = serde_json::from_str(&json_str).unwrap();
@@ -4631,6 +5035,8 @@ mod tests {
         let r = ReservationResponse {
             granted: vec![],
             conflicts: vec![],
+            lease_token: None,
+            atomic_rollback: false,
         };
         let json: serde_json::Value =
             serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
@@ -5166,6 +5572,7 @@ mod tests {
                     agent.name.clone(),
                     None,
                     Some(vec![reservation_id]),
+                    None,
                 )
                 .await
                 .expect("release_file_reservations");
@@ -5402,6 +5809,7 @@ mod tests {
                     agent.name.clone(),
                     None,
                     Some(vec![reservation_id]),
+                    None,
                 )
                 .await
                 .expect("release_file_reservations");
@@ -1400,6 +1400,7 @@ fn build_tool_directory() -> ToolDirectory {
             ],
         },
     ];
+    append_extension_clusters(&mut clusters);
 
     if config.tool_filter.enabled {
         for cluster in &mut clusters {
@@ -1466,6 +1467,32 @@ fn build_tool_directory() -> ToolDirectory {
     }
 }
 
+/// Group registered extension tools into directory clusters, one per cluster
+/// name, after the built-in clusters.
+fn append_extension_clusters(clusters: &mut Vec<ToolCluster>) {
+    for spec in crate::extensions::extension_tools() {
+        let entry = ToolDirectoryEntry {
+            name: spec.name.to_string(),
+            summary: spec.summary.clone(),
+            use_when: spec.summary.clone(),
+            related: Vec::new(),
+            expected_frequency: "Defined by the embedding server.".to_string(),
+            required_capabilities: spec.capabilities.clone(),
+            usage_examples: Vec::new(),
+            capabilities: spec.capabilities,
+            complexity: spec.complexity,
+        };
+        match clusters.iter_mut().find(|c| c.name == spec.cluster) {
+            Some(cluster) => cluster.tools.push(entry),
+            None => clusters.push(ToolCluster {
+                name: spec.cluster.to_string(),
+                purpose: "Extension tools registered by the embedding server.".to_string(),
+                tools: vec![entry],
+            }),
+        }
+    }
+}
+
 /// Get tool directory with cluster/capability metadata.
 #[resource(
     uri = "resource://tooling/directory",
@@ -1610,6 +1637,18 @@ pub fn tooling_schemas(_ctx: &McpContext) -> McpResult<String> {
         },
     );
 
+    for spec in crate::extensions::extension_tools() {
+        let (required, optional) = spec.argument_names();
+        tools
+            .entry(spec.name.to_string())
+            .or_insert(ToolSchemaDetails {
+                required,
+                optional,
+                shapes: None,
+                aliases: None,
+            });
+    }
+
     if config.tool_filter.enabled {
         tools.retain(|name, _| tool_filter_allows(config, name));
     }
@@ -5961,6 +6000,10 @@ mod resource_shape_tests {
                         None,
                         None, // auto_contact_if_blocked
                         None, // sender_token
+                        None, // ack_receipt
+                        None, // agent_secret
+                        None, // expires_in_seconds
+                        None, // fail_on_retired
                     )
                     .await
                     .expect("send_message"),
@@ -5981,6 +6024,9 @@ mod resource_shape_tests {
                         None,
                         None,
                         None,
+                        None,
+                        None,
+                        None,
                     )
                     .await
                     .expect("fetch recipient inbox"),
@@ -6029,6 +6075,7 @@ mod resource_shape_tests {
                         None,
                         None,
                         None, // sender_token
+                        None, // agent_secret
                     )
                     .await
                     .expect("reply_message"),
@@ -6049,6 +6096,9 @@ mod resource_shape_tests {
                         None,
                         None,
                         None,
+                        None,
+                        None,
+                        None,
                     )
                     .await
                     .expect("fetch sender inbox"),
@@ -7170,6 +7220,33 @@ mod resource_shape_tests {
         }
     }
 
+    #[test]
+    fn tooling_directory_lists_registered_extension_tools() {
+        let _ = crate::extensions::register_extension_tool(crate::extensions::ExtensionToolSpec {
+            name: "ext_directory_probe",
+            cluster: "ext_directory_cluster",
+            summary: "Look up a ticket by id.".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": { "ticket_id": { "type": "string" } },
+                "required": ["ticket_id"]
+            }),
+            capabilities: vec!["tickets".to_string()],
+            complexity: "low".to_string(),
+        });
+
+        let directory = build_tool_directory();
+        let cluster = directory
+            .clusters
+            .iter()
+            .find(|cluster| cluster.name == "ext_directory_cluster")
+            .expect("extension cluster in directory");
+        assert_eq!(cluster.tools.len(), 1);
+        assert_eq!(cluster.tools[0].name, "ext_directory_probe");
+        assert_eq!(cluster.tools[0].summary, "Look up a ticket by id.");
+        assert_eq!(cluster.tools[0].capabilities, vec!["tickets".to_string()]);
+    }
+
     #[test]
     fn resources_use_archive_snapshot_when_live_db_is_stale() {
         with_serialized_resources(|| {
@@ -7424,7 +7501,7 @@ mod resource_shape_tests {
                 .expect("register_agent BlueDog");
 
                 // Call list_agents tool
-                let result = crate::list_agents(&ctx, project_key.clone(), None, None)
+                let result = crate::list_agents(&ctx, project_key.clone(), None, None, None)
                     .await
                     .expect("list_agents tool");
 
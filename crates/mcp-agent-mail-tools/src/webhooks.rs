@@ -0,0 +1,321 @@
+//! Outbound notification webhooks: POST a small JSON event to an operator URL.
+//!
+//! Configured with `AGENT_MAIL_WEBHOOK_URL` and, optionally,
+//! `AGENT_MAIL_WEBHOOK_EVENTS` (comma-separated subset of
+//! [`mcp_agent_mail_core::config::DEFAULT_WEBHOOK_EVENTS`]). Every event is an
+//! object with `event`, `timestamp`, and the event's fields:
+//!
+//! ```json
+//! { "event": "message.sent", "timestamp": "2026-01-01T00:00:00.000000Z",
+//!   "project": "backend", "sender": "BlueLake", "recipients": ["RedFox"],
+//!   "subject": "Build is green", "importance": "normal", "message_id": 42 }
+//! ```
+//!
+//! Message bodies are only included when `AGENT_MAIL_WEBHOOK_INCLUDE_BODY=1`,
+//! and BCC recipients are never listed.
+//!
+//! [`enqueue`] never blocks the mail operation: events go onto a bounded
+//! queue drained by a single background thread, which retries each POST up to
+//! [`MAX_ATTEMPTS`] times with exponential backoff. Events that cannot be
+//! delivered (queue full or retries exhausted) are dropped and counted in
+//! `webhooks_failed_total`; successful deliveries in `webhooks_delivered_total`.
+
+use std::sync::OnceLock;
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError, sync_channel};
+use std::time::Duration;
+
+use mcp_agent_mail_core::Config;
+use mcp_agent_mail_db::{micros_to_iso, now_micros};
+use serde_json::{Map, Value, json};
+
+/// Event posted after `send_message` / `reply_message` stores a message.
+pub const MESSAGE_SENT: &str = "message.sent";
+
+/// Event posted when an agent acknowledges a message for the first time.
+pub const MESSAGE_ACKED: &str = "message.acked";
+
+/// Event posted when a reservation request conflicts with existing holders.
+pub const RESERVATION_CONFLICT: &str = "reservation.conflict";
+
+/// Delivery attempts per event, including the first.
+pub const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled for each later retry.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Per-request timeout, so a stalled receiver cannot hold the queue.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Events waiting for delivery; further events are dropped while full.
+const QUEUE_CAPACITY: usize = 256;
+
+struct Delivery {
+    url: String,
+    event: String,
+    body: Vec<u8>,
+}
+
+static QUEUE: OnceLock<Option<SyncSender<Delivery>>> = OnceLock::new();
+
+/// Whether `event` should be posted under `config`.
+#[must_use]
+pub fn event_enabled(config: &Config, event: &str) -> bool {
+    config.webhook_url.is_some() && config.webhook_events.iter().any(|e| e == event)
+}
+
+/// Wrap `fields` in the event envelope (`event`, `timestamp`).
+#[must_use]
+pub fn build_event(event: &str, fields: Value) -> Value {
+    let mut object = Map::new();
+    object.insert("event".to_string(), Value::String(event.to_string()));
+    object.insert(
+        "timestamp".to_string(),
+        Value::String(micros_to_iso(now_micros())),
+    );
+    if let Value::Object(fields) = fields {
+        object.extend(fields);
+    }
+    Value::Object(object)
+}
+
+/// Fields of a `message.sent` event. `body_md` is only passed through when
+/// `include_body` is set.
+#[must_use]
+pub fn message_sent_fields<'a>(
+    project: &str,
+    sender: &str,
+    recipients: impl IntoIterator<Item = &'a String>,
+    message: &mcp_agent_mail_db::MessageRow,
+    include_body: bool,
+) -> Value {
+    let recipients: Vec<&String> = recipients.into_iter().collect();
+    let mut fields = json!({
+        "project": project,
+        "sender": sender,
+        "recipients": recipients,
+        "subject": &message.subject,
+        "importance": &message.importance,
+        "message_id": message.id,
+        "thread_id": &message.thread_id,
+        "created": micros_to_iso(message.created_ts),
+    });
+    if include_body {
+        fields["body_md"] = Value::String(message.body_md.clone());
+    }
+    fields
+}
+
+/// Queue `event` for delivery if it is enabled. Never blocks and never fails;
+/// delivery problems only show up in logs and metrics.
+pub fn enqueue(config: &Config, event: &str, fields: Value) {
+    let Some(url) = config.webhook_url.as_deref() else {
+        return;
+    };
+    if !event_enabled(config, event) {
+        return;
+    }
+    let body = match serde_json::to_vec(&build_event(event, fields)) {
+        Ok(body) => body,
+        Err(error) => {
+            tracing::warn!(event, %error, "failed to serialize webhook event");
+            return;
+        }
+    };
+    let Some(queue) = QUEUE.get_or_init(spawn_worker) else {
+        mcp_agent_mail_core::global_metrics()
+            .tools
+            .webhooks_failed_total
+            .inc();
+        return;
+    };
+    let delivery = Delivery {
+        url: url.to_string(),
+        event: event.to_string(),
+        body,
+    };
+    match queue.try_send(delivery) {
+        Ok(()) => {}
+        Err(TrySendError::Full(delivery) | TrySendError::Disconnected(delivery)) => {
+            mcp_agent_mail_core::global_metrics()
+                .tools
+                .webhooks_failed_total
+                .inc();
+            tracing::warn!(
+                event = %delivery.event,
+                "webhook queue unavailable; dropping event"
+            );
+        }
+    }
+}
+
+fn spawn_worker() -> Option<SyncSender<Delivery>> {
+    let (sender, receiver) = sync_channel(QUEUE_CAPACITY);
+    match std::thread::Builder::new()
+        .name("am-webhook".to_string())
+        .spawn(move || run_worker(&receiver))
+    {
+        Ok(_) => Some(sender),
+        Err(error) => {
+            tracing::warn!(%error, "failed to start webhook delivery thread");
+            None
+        }
+    }
+}
+
+fn run_worker(receiver: &Receiver<Delivery>) {
+    let runtime = match asupersync::runtime::RuntimeBuilder::current_thread().build() {
+        Ok(runtime) => runtime,
+        Err(error) => {
+            tracing::warn!(%error, "failed to build webhook delivery runtime");
+            return;
+        }
+    };
+    let client = asupersync::http::h1::HttpClient::new();
+    while let Ok(delivery) = receiver.recv() {
+        let result = deliver_with_retry(MAX_ATTEMPTS, INITIAL_BACKOFF, || {
+            post(&runtime, &client, &delivery.url, delivery.body.clone())
+        });
+        let metrics = &mcp_agent_mail_core::global_metrics().tools;
+        match result {
+            Ok(_) => metrics.webhooks_delivered_total.inc(),
+            Err(error) => {
+                metrics.webhooks_failed_total.inc();
+                tracing::warn!(
+                    event = %delivery.event,
+                    attempts = MAX_ATTEMPTS,
+                    %error,
+                    "webhook delivery failed; dropping event"
+                );
+            }
+        }
+    }
+}
+
+/// Call `send` until it reports a 2xx status, at most `attempts` times,
+/// sleeping `backoff`, `2 * backoff`, ... between tries. Returns the final
+/// status or the last error.
+pub fn deliver_with_retry(
+    attempts: u32,
+    backoff: Duration,
+    mut send: impl FnMut() -> Result<u16, String>,
+) -> Result<u16, String> {
+    let mut last_error = String::from("no delivery attempted");
+    let mut delay = backoff;
+    for attempt in 1..=attempts {
+        match send() {
+            Ok(status) if (200..300).contains(&status) => return Ok(status),
+            Ok(status) => last_error = format!("HTTP {status}"),
+            Err(error) => last_error = error,
+        }
+        if attempt < attempts {
+            std::thread::sleep(delay);
+            delay = delay.saturating_mul(2);
+        }
+    }
+    Err(last_error)
+}
+
+fn post(
+    runtime: &asupersync::runtime::Runtime,
+    client: &asupersync::http::h1::HttpClient,
+    url: &str,
+    body: Vec<u8>,
+) -> Result<u16, String> {
+    runtime.block_on(async {
+        let cx = asupersync::Cx::current()
+            .expect("Runtime::block_on installs an ambient Cx for the polled future");
+        let headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            (
+                "User-Agent".to_string(),
+                "mcp-agent-mail-webhook".to_string(),
+            ),
+        ];
+        let request = client.request(&cx, asupersync::http::h1::Method::Post, url, headers, body);
+        match asupersync::time::timeout(asupersync::time::wall_now(), REQUEST_TIMEOUT, request)
+            .await
+        {
+            Ok(Ok(response)) => Ok(response.status),
+            Ok(Err(error)) => Err(error.to_string()),
+            Err(_) => Err(format!("timed out after {}s", REQUEST_TIMEOUT.as_secs())),
+        }
+    })
+}
+
+/// POST one event to `url` synchronously, without retries. Used by
+/// `am tooling webhook-test` to check a receiver end to end.
+pub fn post_now(url: &str, event: &Value) -> Result<u16, String> {
+    let body = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+    let runtime = asupersync::runtime::RuntimeBuilder::current_thread()
+        .build()
+        .map_err(|e| format!("runtime error: {e}"))?;
+    let client = asupersync::http::h1::HttpClient::new();
+    post(&runtime, &client, url, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> mcp_agent_mail_db::MessageRow {
+        mcp_agent_mail_db::MessageRow {
+            id: Some(42),
+            subject: "Build is green".to_string(),
+            body_md: "secret details".to_string(),
+            importance: "high".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn events_require_url_and_subscription() {
+        let mut config = Config::default();
+        assert!(!event_enabled(&config, MESSAGE_SENT));
+        config.webhook_url = Some("http://127.0.0.1:9/hook".to_string());
+        assert!(event_enabled(&config, MESSAGE_SENT));
+        config.webhook_events = vec![MESSAGE_ACKED.to_string()];
+        assert!(!event_enabled(&config, MESSAGE_SENT));
+        assert!(event_enabled(&config, MESSAGE_ACKED));
+    }
+
+    #[test]
+    fn message_sent_event_omits_body_unless_requested() {
+        let recipients = vec!["RedFox".to_string()];
+        let event = build_event(
+            MESSAGE_SENT,
+            message_sent_fields("backend", "BlueLake", &recipients, &message(), false),
+        );
+        assert_eq!(event["event"], MESSAGE_SENT);
+        assert!(event["timestamp"].is_string());
+        assert_eq!(event["sender"], "BlueLake");
+        assert_eq!(event["recipients"], json!(["RedFox"]));
+        assert_eq!(event["message_id"], 42);
+        assert!(event.get("body_md").is_none());
+
+        let with_body = message_sent_fields("backend", "BlueLake", &recipients, &message(), true);
+        assert_eq!(with_body["body_md"], "secret details");
+    }
+
+    #[test]
+    fn retry_stops_on_success_and_gives_up_after_max_attempts() {
+        let mut calls = 0;
+        let result = deliver_with_retry(MAX_ATTEMPTS, Duration::ZERO, || {
+            calls += 1;
+            if calls == 1 {
+                Err("connection refused".to_string())
+            } else {
+                Ok(204)
+            }
+        });
+        assert_eq!(result, Ok(204));
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        let result = deliver_with_retry(MAX_ATTEMPTS, Duration::ZERO, || {
+            calls += 1;
+            Ok(503)
+        });
+        assert_eq!(result, Err("HTTP 503".to_string()));
+        assert_eq!(calls, MAX_ATTEMPTS);
+    }
+}
@@ -0,0 +1,171 @@
+//! Mail from `send_message` to retired agents (`am agents apply --prune`) is
+//! queued as deferred, or refused with `fail_on_retired`; reinstating them
+//! restores delivery.
+
+use asupersync::Cx;
+use asupersync::runtime::RuntimeBuilder;
+use fastmcp::prelude::McpContext;
+use mcp_agent_mail_core::{Config, config::with_process_env_overrides_for_test};
+use mcp_agent_mail_db::{DbPoolConfig, get_or_create_pool};
+use mcp_agent_mail_tools::{ensure_project, register_agent, send_message};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn unique_suffix() -> u64 {
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros();
+    let time_component = u64::try_from(micros).unwrap_or(u64::MAX);
+    time_component.wrapping_add(TEST_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+fn run_serial_async<F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(Cx) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let _lock = TEST_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let env_suffix = unique_suffix();
+    let db_path = format!("/tmp/agent-retirement-{env_suffix}.sqlite3");
+    let database_url = format!("sqlite://{db_path}");
+    let storage_root = format!("/tmp/agent-retirement-storage-{env_suffix}");
+    with_process_env_overrides_for_test(
+        &[
+            ("DATABASE_URL", database_url.as_str()),
+            ("STORAGE_ROOT", storage_root.as_str()),
+            ("CONTACT_ENFORCEMENT_ENABLED", "false"),
+        ],
+        || {
+            Config::reset_cached();
+            let cx = Cx::for_testing();
+            let rt = RuntimeBuilder::current_thread()
+                .build()
+                .expect("build runtime");
+            rt.block_on(f(cx))
+        },
+    )
+}
+
+fn error_type(err: &fastmcp::McpError) -> String {
+    err.data
+        .as_ref()
+        .and_then(Value::as_object)
+        .and_then(|root| root.get("error"))
+        .and_then(Value::as_object)
+        .and_then(|e| e.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or("<no type>")
+        .to_string()
+}
+
+async fn setup_project(ctx: &McpContext, project_key: &str) -> i64 {
+    let ensured = ensure_project(ctx, project_key.to_string(), None)
+        .await
+        .expect("ensure_project");
+    let project_id = serde_json::from_str::<Value>(&ensured)
+        .ok()
+        .and_then(|value| value.get("id").and_then(Value::as_i64))
+        .expect("project id");
+    for name in ["GreenCastle", "BlueLake"] {
+        register_agent(
+            ctx,
+            project_key.to_string(),
+            "codex-cli".to_string(),
+            "gpt-5".to_string(),
+            Some(name.to_string()),
+            Some("agent retirement test".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("register_agent");
+    }
+    project_id
+}
+
+async fn set_retired(cx: &Cx, project_id: i64, name: &str, retired: bool) {
+    let pool = get_or_create_pool(&DbPoolConfig::from_env()).expect("get pool");
+    let agent = mcp_agent_mail_db::queries::get_agent(cx, &pool, project_id, name)
+        .await
+        .into_result()
+        .expect("get agent");
+    mcp_agent_mail_db::queries::set_agent_retired(cx, &pool, agent.id.expect("agent id"), retired)
+        .await
+        .into_result()
+        .expect("set agent retired");
+}
+
+async fn send(
+    ctx: &McpContext,
+    project_key: &str,
+    fail_on_retired: bool,
+) -> Result<Value, fastmcp::McpError> {
+    let raw = send_message(
+        ctx,
+        project_key.to_string(),
+        "GreenCastle".to_string(),
+        vec!["BlueLake".to_string()],
+        "Retirement subject".to_string(),
+        "Retirement body".to_string(),
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        Some(fail_on_retired),
+    )
+    .await?;
+    Ok(serde_json::from_str(&raw).expect("parse send response"))
+}
+
+#[test]
+fn send_to_retired_agent_is_deferred_until_reinstated() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/agent-retirement-{}", unique_suffix());
+        let project_id = setup_project(&ctx, &project_key).await;
+
+        set_retired(&cx, project_id, "BlueLake", true).await;
+        let sent = send(&ctx, &project_key, false)
+            .await
+            .expect("mail to a retired recipient is queued");
+        assert_eq!(sent["count"], 1, "{sent}");
+        assert_eq!(
+            sent["deferred_recipients"],
+            serde_json::json!([{ "name": "BlueLake", "deferred": true }]),
+            "{sent}"
+        );
+
+        let err = send(&ctx, &project_key, true)
+            .await
+            .expect_err("fail_on_retired refuses the send");
+        assert_eq!(error_type(&err), "RECIPIENT_RETIRED");
+        assert!(err.message.contains("BlueLake"), "{}", err.message);
+
+        set_retired(&cx, project_id, "BlueLake", false).await;
+        let sent = send(&ctx, &project_key, true)
+            .await
+            .expect("reinstated recipient receives mail");
+        assert_eq!(sent["count"], 1, "{sent}");
+        assert!(sent.get("deferred_recipients").is_none(), "{sent}");
+    });
+}
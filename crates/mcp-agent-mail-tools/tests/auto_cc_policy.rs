@@ -0,0 +1,261 @@
+//! Project auto-CC policy applied by `send_message`.
+
+use asupersync::Cx;
+use asupersync::runtime::RuntimeBuilder;
+use fastmcp::prelude::McpContext;
+use mcp_agent_mail_core::{Config, config::with_process_env_overrides_for_test};
+use mcp_agent_mail_db::{DbPoolConfig, get_or_create_pool};
+use mcp_agent_mail_tools::{ensure_project, register_agent, send_message};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn unique_suffix() -> u64 {
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros();
+    let time_component = u64::try_from(micros).unwrap_or(u64::MAX);
+    time_component.wrapping_add(TEST_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+fn run_serial_async<F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(Cx) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let _lock = TEST_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let env_suffix = unique_suffix();
+    let db_path = format!("/tmp/auto-cc-policy-{env_suffix}.sqlite3");
+    let database_url = format!("sqlite://{db_path}");
+    let storage_root = format!("/tmp/auto-cc-policy-storage-{env_suffix}");
+    with_process_env_overrides_for_test(
+        &[
+            ("DATABASE_URL", database_url.as_str()),
+            ("STORAGE_ROOT", storage_root.as_str()),
+            ("CONTACT_ENFORCEMENT_ENABLED", "false"),
+        ],
+        || {
+            Config::reset_cached();
+            let cx = Cx::for_testing();
+            let rt = RuntimeBuilder::current_thread()
+                .build()
+                .expect("build runtime");
+            rt.block_on(f(cx))
+        },
+    )
+}
+
+async fn setup_project(ctx: &McpContext, project_key: &str, agents: &[&str]) -> i64 {
+    let ensured = ensure_project(ctx, project_key.to_string(), None)
+        .await
+        .expect("ensure_project");
+    let project_id = serde_json::from_str::<Value>(&ensured)
+        .ok()
+        .and_then(|value| value.get("id").and_then(Value::as_i64))
+        .expect("project id");
+    for name in agents {
+        register_agent(
+            ctx,
+            project_key.to_string(),
+            "codex-cli".to_string(),
+            "gpt-5".to_string(),
+            Some((*name).to_string()),
+            Some("auto-cc policy test".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("register_agent");
+    }
+    project_id
+}
+
+async fn set_setting(cx: &Cx, project_id: i64, key: &str, value: &str) {
+    let pool = get_or_create_pool(&DbPoolConfig::from_env()).expect("get pool");
+    mcp_agent_mail_db::queries::set_project_setting(cx, &pool, project_id, key, value)
+        .await
+        .into_result()
+        .expect("set project setting");
+}
+
+async fn send(
+    ctx: &McpContext,
+    project_key: &str,
+    to: &[&str],
+    importance: &str,
+    broadcast: Option<bool>,
+) -> Result<Value, fastmcp::McpError> {
+    let raw = send_message(
+        ctx,
+        project_key.to_string(),
+        "GreenCastle".to_string(),
+        to.iter().map(ToString::to_string).collect(),
+        "Auto-cc subject".to_string(),
+        "Auto-cc body".to_string(),
+        None,
+        None,
+        None,
+        Some(false),
+        Some(importance.to_string()),
+        Some(false),
+        None,
+        None,
+        broadcast,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    Ok(serde_json::from_str(&raw).expect("parse send response"))
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[test]
+fn urgent_send_appends_policy_cc_and_notes_missing_agents() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/auto-cc-urgent-{}", unique_suffix());
+        let project_id = setup_project(
+            &ctx,
+            &project_key,
+            &["GreenCastle", "BlueLake", "Conductor"],
+        )
+        .await;
+        set_setting(
+            &cx,
+            project_id,
+            "auto_cc_agents",
+            "Conductor,Overseer,GreenCastle",
+        )
+        .await;
+
+        let response = send(&ctx, &project_key, &["BlueLake"], "urgent", None)
+            .await
+            .expect("urgent send");
+        let payload = &response["deliveries"][0]["payload"];
+        assert_eq!(strings(&payload["to"]), vec!["BlueLake"]);
+        assert_eq!(strings(&payload["cc"]), vec!["Conductor"]);
+        assert_eq!(strings(&response["policy_cc"]), vec!["Conductor"]);
+        assert_eq!(strings(&response["policy_cc_skipped"]), vec!["Overseer"]);
+
+        let message_id = payload["id"].as_i64().expect("message id");
+        let pool = get_or_create_pool(&DbPoolConfig::from_env()).expect("get pool");
+        let recorded =
+            mcp_agent_mail_db::queries::list_message_policy_recipients(&cx, &pool, message_id)
+                .await
+                .into_result()
+                .expect("list policy recipients");
+        assert_eq!(
+            recorded,
+            vec![("Conductor".to_string(), "auto_cc".to_string())]
+        );
+    });
+}
+
+#[test]
+fn policy_respects_threshold_and_explicit_recipients() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/auto-cc-threshold-{}", unique_suffix());
+        let project_id = setup_project(
+            &ctx,
+            &project_key,
+            &["GreenCastle", "BlueLake", "Conductor"],
+        )
+        .await;
+        set_setting(&cx, project_id, "auto_cc_agents", "Conductor").await;
+
+        let normal = send(&ctx, &project_key, &["BlueLake"], "high", None)
+            .await
+            .expect("below-threshold send");
+        assert!(strings(&normal["deliveries"][0]["payload"]["cc"]).is_empty());
+        assert!(normal.get("policy_cc").is_none());
+
+        set_setting(&cx, project_id, "auto_cc_min_importance", "high").await;
+        let high = send(&ctx, &project_key, &["BlueLake"], "high", None)
+            .await
+            .expect("threshold send");
+        assert_eq!(strings(&high["policy_cc"]), vec!["Conductor"]);
+
+        let explicit = send(
+            &ctx,
+            &project_key,
+            &["BlueLake", "Conductor"],
+            "urgent",
+            None,
+        )
+        .await
+        .expect("explicit conductor send");
+        let payload = &explicit["deliveries"][0]["payload"];
+        assert_eq!(strings(&payload["to"]), vec!["BlueLake", "Conductor"]);
+        assert!(strings(&payload["cc"]).is_empty());
+        assert!(explicit.get("policy_cc").is_none());
+    });
+}
+
+#[test]
+fn policy_applies_only_in_its_own_project() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let suffix = unique_suffix();
+        let configured_key = format!("/tmp/auto-cc-configured-{suffix}");
+        let other_key = format!("/tmp/auto-cc-other-{suffix}");
+        let configured_id = setup_project(
+            &ctx,
+            &configured_key,
+            &["GreenCastle", "BlueLake", "Conductor"],
+        )
+        .await;
+        setup_project(&ctx, &other_key, &["GreenCastle", "BlueLake", "Conductor"]).await;
+        set_setting(&cx, configured_id, "auto_cc_agents", "Conductor").await;
+
+        let response = send(&ctx, &other_key, &["BlueLake"], "urgent", None)
+            .await
+            .expect("send in unconfigured project");
+        assert!(strings(&response["deliveries"][0]["payload"]["cc"]).is_empty());
+        assert!(response.get("policy_cc").is_none());
+    });
+}
+
+#[test]
+fn policy_does_not_enable_broadcast() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/auto-cc-broadcast-{}", unique_suffix());
+        let project_id = setup_project(
+            &ctx,
+            &project_key,
+            &["GreenCastle", "BlueLake", "Conductor"],
+        )
+        .await;
+        set_setting(&cx, project_id, "auto_cc_agents", "Conductor").await;
+
+        send(&ctx, &project_key, &["BlueLake"], "urgent", Some(true))
+            .await
+            .expect_err("broadcast stays rejected with auto-cc configured");
+    });
+}
@@ -0,0 +1,215 @@
+//! `auto` contact policy qualifiers: allowlisted senders are approved at
+//! send time, others still need a handshake, and the daily cap holds.
+
+use asupersync::Cx;
+use asupersync::runtime::RuntimeBuilder;
+use fastmcp::prelude::McpContext;
+use mcp_agent_mail_core::{Config, config::with_process_env_overrides_for_test};
+use mcp_agent_mail_tools::{
+    ensure_project, list_contacts, register_agent, send_message, set_contact_policy,
+};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn unique_suffix() -> u64 {
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros();
+    let time_component = u64::try_from(micros).unwrap_or(u64::MAX);
+    time_component.wrapping_add(TEST_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+fn run_serial_async<F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(Cx) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let _lock = TEST_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let env_suffix = unique_suffix();
+    let db_path = format!("/tmp/contact-auto-qualifiers-{env_suffix}.sqlite3");
+    let database_url = format!("sqlite://{db_path}");
+    let storage_root = format!("/tmp/contact-auto-qualifiers-storage-{env_suffix}");
+    with_process_env_overrides_for_test(
+        &[
+            ("DATABASE_URL", database_url.as_str()),
+            ("STORAGE_ROOT", storage_root.as_str()),
+            ("CONTACT_ENFORCEMENT_ENABLED", "true"),
+        ],
+        || {
+            Config::reset_cached();
+            let cx = Cx::for_testing();
+            let rt = RuntimeBuilder::current_thread()
+                .build()
+                .expect("build runtime");
+            rt.block_on(f(cx))
+        },
+    )
+}
+
+fn error_type(err: &fastmcp::McpError) -> String {
+    err.data
+        .as_ref()
+        .and_then(Value::as_object)
+        .and_then(|root| root.get("error"))
+        .and_then(Value::as_object)
+        .and_then(|e| e.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or("<no type>")
+        .to_string()
+}
+
+async fn setup_project(ctx: &McpContext, project_key: &str, agents: &[&str]) {
+    ensure_project(ctx, project_key.to_string(), None)
+        .await
+        .expect("ensure_project");
+    for name in agents {
+        register_agent(
+            ctx,
+            project_key.to_string(),
+            "codex-cli".to_string(),
+            "gpt-5".to_string(),
+            Some((*name).to_string()),
+            Some("auto qualifier test".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("register_agent");
+    }
+}
+
+async fn set_auto_policy(
+    ctx: &McpContext,
+    project_key: &str,
+    agent: &str,
+    auto_allow: &str,
+    auto_daily_cap: Option<i64>,
+) -> Result<Value, fastmcp::McpError> {
+    let raw = set_contact_policy(
+        ctx,
+        project_key.to_string(),
+        agent.to_string(),
+        "auto".to_string(),
+        Some(auto_allow.to_string()),
+        Some(3600),
+        auto_daily_cap,
+    )
+    .await?;
+    Ok(serde_json::from_str(&raw).expect("parse policy response"))
+}
+
+async fn send(
+    ctx: &McpContext,
+    project_key: &str,
+    sender: &str,
+    recipient: &str,
+) -> Result<Value, fastmcp::McpError> {
+    let raw = send_message(
+        ctx,
+        project_key.to_string(),
+        sender.to_string(),
+        vec![recipient.to_string()],
+        format!("{sender} says hello"),
+        "Auto qualifier body".to_string(),
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    Ok(serde_json::from_str(&raw).expect("parse send response"))
+}
+
+#[test]
+fn allowlisted_sender_is_auto_approved_and_others_are_not() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/contact-auto-allow-{}", unique_suffix());
+        setup_project(&ctx, &project_key, &["BlueLake", "GreenCastle", "RedStone"]).await;
+
+        let policy = set_auto_policy(&ctx, &project_key, "BlueLake", "Green*", None)
+            .await
+            .expect("set auto policy");
+        assert_eq!(policy["auto_allow"], serde_json::json!(["Green*"]));
+        assert_eq!(policy["auto_ttl_seconds"], 3600);
+
+        send(&ctx, &project_key, "GreenCastle", "BlueLake")
+            .await
+            .expect("allowlisted sender is delivered");
+        let err = send(&ctx, &project_key, "RedStone", "BlueLake")
+            .await
+            .expect_err("unlisted sender still needs approval");
+        assert_eq!(error_type(&err), "CONTACT_REQUIRED");
+
+        let raw = list_contacts(&ctx, project_key.clone(), "BlueLake".to_string())
+            .await
+            .expect("list_contacts");
+        let contacts: Vec<Value> = serde_json::from_str(&raw).expect("parse contacts");
+        let green = contacts
+            .iter()
+            .find(|c| c["to"] == "GreenCastle")
+            .expect("GreenCastle link");
+        assert_eq!(green["status"], "approved");
+        assert_eq!(green["approved_via"], "auto");
+        assert_eq!(green["approved_pattern"], "Green*");
+        assert!(contacts.iter().all(|c| c["to"] != "RedStone"));
+    });
+}
+
+#[test]
+fn daily_cap_limits_auto_approvals_per_requester() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/contact-auto-cap-{}", unique_suffix());
+        setup_project(&ctx, &project_key, &["BlueLake", "GoldHawk", "GreenCastle"]).await;
+        for target in ["BlueLake", "GoldHawk"] {
+            set_auto_policy(&ctx, &project_key, target, "GreenCastle", Some(1))
+                .await
+                .expect("set auto policy");
+        }
+
+        send(&ctx, &project_key, "GreenCastle", "BlueLake")
+            .await
+            .expect("first auto-approval is within the cap");
+        let err = send(&ctx, &project_key, "GreenCastle", "GoldHawk")
+            .await
+            .expect_err("second auto-approval exceeds the cap");
+        assert_eq!(error_type(&err), "CONTACT_REQUIRED");
+    });
+}
+
+#[test]
+fn bad_auto_allow_glob_is_rejected() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/contact-auto-bad-glob-{}", unique_suffix());
+        setup_project(&ctx, &project_key, &["BlueLake"]).await;
+
+        let err = set_auto_policy(&ctx, &project_key, "BlueLake", "Green*,[Red", None)
+            .await
+            .expect_err("unclosed character class is rejected");
+        assert_eq!(error_type(&err), "INVALID_ARGUMENT");
+        assert!(err.message.contains("[Red"), "{}", err.message);
+    });
+}
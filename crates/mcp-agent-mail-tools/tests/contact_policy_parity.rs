@@ -139,6 +139,10 @@ async fn send_basic_message(
         None,
         auto_contact_if_blocked,
         None,
+        None,
+        None,
+        None,
+        None,
     )
     .await
 }
@@ -188,6 +192,9 @@ fn test_request_contact_uses_canonical_agent_names_in_intro() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("fetch GreenCastle inbox");
@@ -225,6 +232,9 @@ fn test_contact_blocked_message() {
             project_key.clone(),
             "RedStone".to_string(),
             "block_all".to_string(),
+            None,
+            None,
+            None,
         )
         .await
         .expect("set block_all policy");
@@ -269,6 +279,9 @@ fn test_contact_blocked_message() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("fetch_inbox");
@@ -296,6 +309,9 @@ fn test_contact_required_message() {
             project_key.clone(),
             "BlueLake".to_string(),
             "contacts_only".to_string(),
+            None,
+            None,
+            None,
         )
         .await
         .expect("set contacts_only policy");
@@ -394,6 +410,9 @@ fn test_mixed_recipients_partial_block() {
             project_key.clone(),
             "BlueLake".to_string(),
             "open".to_string(),
+            None,
+            None,
+            None,
         )
         .await
         .expect("set open policy");
@@ -402,6 +421,9 @@ fn test_mixed_recipients_partial_block() {
             project_key.clone(),
             "RedStone".to_string(),
             "contacts_only".to_string(),
+            None,
+            None,
+            None,
         )
         .await
         .expect("set contacts_only policy");
@@ -441,6 +463,9 @@ fn test_mixed_recipients_partial_block() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("fetch BlueLake inbox");
@@ -466,6 +491,9 @@ fn test_contact_block_prevents_attachment_archive_artifacts() {
             project_key.clone(),
             "BlueLake".to_string(),
             "contacts_only".to_string(),
+            None,
+            None,
+            None,
         )
         .await
         .expect("set contacts_only policy");
@@ -492,6 +520,10 @@ fn test_contact_block_prevents_attachment_archive_artifacts() {
             None,
             Some(false),
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("contacts_only recipient should block send before attachment writes");
@@ -0,0 +1,192 @@
+//! `send_message(expires_in_seconds=...)`: the expiry is recorded at send
+//! time and `fetch_inbox` leaves lapsed unread messages out before `limit`.
+
+use asupersync::Cx;
+use asupersync::runtime::RuntimeBuilder;
+use fastmcp::prelude::McpContext;
+use mcp_agent_mail_core::{Config, config::with_process_env_overrides_for_test};
+use mcp_agent_mail_tools::{ensure_project, fetch_inbox, register_agent, send_message};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn unique_suffix() -> u64 {
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros();
+    let time_component = u64::try_from(micros).unwrap_or(u64::MAX);
+    time_component.wrapping_add(TEST_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+fn run_serial_async<F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(Cx) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let _lock = TEST_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let env_suffix = unique_suffix();
+    let db_path = format!("/tmp/message-expiry-{env_suffix}.sqlite3");
+    let database_url = format!("sqlite://{db_path}");
+    let storage_root = format!("/tmp/message-expiry-storage-{env_suffix}");
+    with_process_env_overrides_for_test(
+        &[
+            ("DATABASE_URL", database_url.as_str()),
+            ("STORAGE_ROOT", storage_root.as_str()),
+            ("CONTACT_ENFORCEMENT_ENABLED", "false"),
+        ],
+        || {
+            Config::reset_cached();
+            let cx = Cx::for_testing();
+            let rt = RuntimeBuilder::current_thread()
+                .build()
+                .expect("build runtime");
+            rt.block_on(f(cx))
+        },
+    )
+}
+
+fn error_type(err: &fastmcp::McpError) -> String {
+    err.data
+        .as_ref()
+        .and_then(Value::as_object)
+        .and_then(|root| root.get("error"))
+        .and_then(Value::as_object)
+        .and_then(|e| e.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or("<no type>")
+        .to_string()
+}
+
+async fn setup_project(ctx: &McpContext, project_key: &str) {
+    ensure_project(ctx, project_key.to_string(), None)
+        .await
+        .expect("ensure_project");
+    for name in ["GreenCastle", "BlueLake"] {
+        register_agent(
+            ctx,
+            project_key.to_string(),
+            "codex-cli".to_string(),
+            "gpt-5".to_string(),
+            Some(name.to_string()),
+            Some("message expiry test".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("register_agent");
+    }
+}
+
+async fn send(
+    ctx: &McpContext,
+    project_key: &str,
+    subject: &str,
+    ack_required: bool,
+    expires_in_seconds: Option<i64>,
+) -> Result<Value, fastmcp::McpError> {
+    let raw = send_message(
+        ctx,
+        project_key.to_string(),
+        "GreenCastle".to_string(),
+        vec!["BlueLake".to_string()],
+        subject.to_string(),
+        "Expiry body".to_string(),
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        Some(ack_required),
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+        expires_in_seconds,
+        None,
+    )
+    .await?;
+    Ok(serde_json::from_str(&raw).expect("parse send response"))
+}
+
+async fn inbox_subjects(ctx: &McpContext, project_key: &str, limit: i32) -> Vec<String> {
+    let raw = fetch_inbox(
+        ctx,
+        project_key.to_string(),
+        "BlueLake".to_string(),
+        None,
+        None,
+        Some(limit),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("fetch_inbox");
+    let messages: Vec<Value> = serde_json::from_str(&raw).expect("parse inbox");
+    messages
+        .iter()
+        .filter_map(|m| m["subject"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[test]
+fn expired_unread_message_is_left_out_before_limit() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/message-expiry-{}", unique_suffix());
+        setup_project(&ctx, &project_key).await;
+
+        send(&ctx, &project_key, "older status", false, None)
+            .await
+            .expect("plain send");
+        let sent = send(&ctx, &project_key, "build started", false, Some(1))
+            .await
+            .expect("send with expiry");
+        assert!(sent["expires_ts"].is_string(), "{sent}");
+        assert_eq!(
+            inbox_subjects(&ctx, &project_key, 1).await,
+            vec!["build started".to_string()]
+        );
+
+        std::thread::sleep(Duration::from_millis(1_200));
+        // A one-row page still finds the live message behind the expired one.
+        assert_eq!(
+            inbox_subjects(&ctx, &project_key, 1).await,
+            vec!["older status".to_string()]
+        );
+    });
+}
+
+#[test]
+fn expiry_is_rejected_for_ack_required_and_non_positive_ttls() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/message-expiry-bad-{}", unique_suffix());
+        setup_project(&ctx, &project_key).await;
+
+        let err = send(&ctx, &project_key, "please ack", true, Some(60))
+            .await
+            .expect_err("ack-required mail never expires");
+        assert_eq!(error_type(&err), "INVALID_ARGUMENT");
+        let err = send(&ctx, &project_key, "zero ttl", false, Some(0))
+            .await
+            .expect_err("ttl must be positive");
+        assert_eq!(error_type(&err), "INVALID_ARGUMENT");
+    });
+}
@@ -87,6 +87,9 @@ async fn setup_project_and_agent(ctx: &McpContext, project_key: &str, agent: &st
         project_key.to_string(),
         agent.to_string(),
         "open".to_string(),
+        None,
+        None,
+        None,
     )
     .await
     .expect("set_contact_policy");
@@ -192,6 +195,10 @@ fn test_send_message_empty_to_error() {
             None, // broadcast
             None, // auto_contact_if_blocked
             None, // sender_token
+            None, // ack_receipt
+            None, // agent_secret
+            None, // expires_in_seconds
+            None, // fail_on_retired
         )
         .await
         .expect_err("empty to should fail");
@@ -242,6 +249,10 @@ fn test_invalid_importance_error() {
             None,                              // broadcast
             None,                              // auto_contact_if_blocked
             None,                              // sender_token
+            None,                              // ack_receipt
+            None,                              // agent_secret
+            None,                              // expires_in_seconds
+            None,                              // fail_on_retired
         )
         .await
         .expect_err("invalid importance should fail");
@@ -288,6 +299,7 @@ fn test_reply_message_not_found() {
             None, // attachment_paths
             None, // convert_images
             None, // sender_token
+            None, // agent_secret
         )
         .await
         .expect_err("reply to nonexistent message should fail");
@@ -335,6 +347,10 @@ fn test_reply_message_subject_prefix() {
             None, // broadcast
             None, // auto_contact_if_blocked
             None, // sender_token
+            None, // ack_receipt
+            None, // agent_secret
+            None, // expires_in_seconds
+            None, // fail_on_retired
         )
         .await
         .expect("send_message should succeed");
@@ -360,6 +376,7 @@ fn test_reply_message_subject_prefix() {
             None, // attachment_paths
             None, // convert_images
             None, // sender_token
+            None, // agent_secret
         )
         .await
         .expect("reply should succeed");
@@ -392,6 +409,7 @@ fn test_reply_message_subject_prefix() {
             None, // attachment_paths
             None, // convert_images
             None, // sender_token
+            None, // agent_secret
         )
         .await
         .expect("second reply should succeed");
@@ -437,6 +455,10 @@ fn test_broadcast_with_explicit_to_error() {
             Some(true), // broadcast
             None,       // auto_contact_if_blocked
             None,       // sender_token
+            None,       // ack_receipt
+            None,       // agent_secret
+            None,       // expires_in_seconds
+            None,       // fail_on_retired
         )
         .await
         .expect_err("broadcast + explicit to should fail");
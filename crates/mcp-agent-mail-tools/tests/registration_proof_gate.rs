@@ -586,6 +586,10 @@ fn enabled_gate_blocks_send_message_and_request_contact_auto_register() {
                 None,
                 None,
                 None,
+                None,
+                None,
+                None,
+                None,
             )
             .await
             .expect_err("send_message to unknown recipient must fail closed");
@@ -656,6 +660,10 @@ fn enabled_gate_blocks_send_message_and_request_contact_auto_register() {
                 None,
                 Some(true), // auto_contact_if_blocked
                 None,
+                None,
+                None,
+                None,
+                None,
             )
             .await
             .expect("send_message between existing identities should still work");
@@ -708,6 +716,10 @@ fn disabled_gate_auto_registers_via_send_message_and_request_contact() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("send_message should auto-register recipient when gate disabled");
@@ -0,0 +1,226 @@
+//! Per-agent sender secrets enforced by `send_message` when a project sets
+//! `require_sender_auth`.
+
+use asupersync::Cx;
+use asupersync::runtime::RuntimeBuilder;
+use fastmcp::prelude::McpContext;
+use mcp_agent_mail_core::{Config, config::with_process_env_overrides_for_test};
+use mcp_agent_mail_db::{DbPoolConfig, get_or_create_pool};
+use mcp_agent_mail_tools::{ensure_project, fetch_inbox, register_agent, send_message};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+static TEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+const SECRET: &str = "s3cret-for-green-castle";
+
+fn unique_suffix() -> u64 {
+    let micros = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros();
+    let time_component = u64::try_from(micros).unwrap_or(u64::MAX);
+    time_component.wrapping_add(TEST_COUNTER.fetch_add(1, Ordering::Relaxed))
+}
+
+fn run_serial_async<F, Fut, T>(f: F) -> T
+where
+    F: FnOnce(Cx) -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    let _lock = TEST_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let env_suffix = unique_suffix();
+    let db_path = format!("/tmp/sender-auth-policy-{env_suffix}.sqlite3");
+    let database_url = format!("sqlite://{db_path}");
+    let storage_root = format!("/tmp/sender-auth-policy-storage-{env_suffix}");
+    with_process_env_overrides_for_test(
+        &[
+            ("DATABASE_URL", database_url.as_str()),
+            ("STORAGE_ROOT", storage_root.as_str()),
+            ("CONTACT_ENFORCEMENT_ENABLED", "false"),
+        ],
+        || {
+            Config::reset_cached();
+            let cx = Cx::for_testing();
+            let rt = RuntimeBuilder::current_thread()
+                .build()
+                .expect("build runtime");
+            rt.block_on(f(cx))
+        },
+    )
+}
+
+fn error_type(err: &fastmcp::McpError) -> String {
+    err.data
+        .as_ref()
+        .and_then(Value::as_object)
+        .and_then(|root| root.get("error"))
+        .and_then(Value::as_object)
+        .and_then(|e| e.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or("<no type>")
+        .to_string()
+}
+
+/// Create a project with GreenCastle (holding [`SECRET`]) and BlueLake.
+async fn setup_project(ctx: &McpContext, cx: &Cx, project_key: &str) -> i64 {
+    let ensured = ensure_project(ctx, project_key.to_string(), None)
+        .await
+        .expect("ensure_project");
+    let project_id = serde_json::from_str::<Value>(&ensured)
+        .ok()
+        .and_then(|value| value.get("id").and_then(Value::as_i64))
+        .expect("project id");
+    let mut sender_id = 0;
+    for name in ["GreenCastle", "BlueLake"] {
+        let raw = register_agent(
+            ctx,
+            project_key.to_string(),
+            "codex-cli".to_string(),
+            "gpt-5".to_string(),
+            Some(name.to_string()),
+            Some("sender auth test".to_string()),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .expect("register_agent");
+        if name == "GreenCastle" {
+            sender_id = serde_json::from_str::<Value>(&raw)
+                .ok()
+                .and_then(|value| value.get("id").and_then(Value::as_i64))
+                .expect("agent id");
+        }
+    }
+    let pool = get_or_create_pool(&DbPoolConfig::from_env()).expect("get pool");
+    let hash = mcp_agent_mail_db::queries::agent_secret_hash(SECRET);
+    mcp_agent_mail_db::queries::set_agent_secret_hash(cx, &pool, sender_id, &hash)
+        .await
+        .into_result()
+        .expect("store secret");
+    project_id
+}
+
+async fn require_sender_auth(cx: &Cx, project_id: i64) {
+    let pool = get_or_create_pool(&DbPoolConfig::from_env()).expect("get pool");
+    mcp_agent_mail_db::queries::set_project_setting(
+        cx,
+        &pool,
+        project_id,
+        "require_sender_auth",
+        "true",
+    )
+    .await
+    .into_result()
+    .expect("set project setting");
+}
+
+async fn send(
+    ctx: &McpContext,
+    project_key: &str,
+    subject: &str,
+    agent_secret: Option<&str>,
+) -> Result<Value, fastmcp::McpError> {
+    let raw = send_message(
+        ctx,
+        project_key.to_string(),
+        "GreenCastle".to_string(),
+        vec!["BlueLake".to_string()],
+        subject.to_string(),
+        "Sender auth body".to_string(),
+        None,
+        None,
+        None,
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        agent_secret.map(str::to_string),
+        None,
+        None,
+    )
+    .await?;
+    Ok(serde_json::from_str(&raw).expect("parse send response"))
+}
+
+async fn inbox(ctx: &McpContext, project_key: &str) -> Vec<Value> {
+    let raw = fetch_inbox(
+        ctx,
+        project_key.to_string(),
+        "BlueLake".to_string(),
+        None,
+        None,
+        Some(20),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("fetch_inbox");
+    serde_json::from_str(&raw).expect("parse inbox")
+}
+
+#[test]
+fn required_sender_auth_rejects_missing_or_wrong_secret() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/sender-auth-required-{}", unique_suffix());
+        let project_id = setup_project(&ctx, &cx, &project_key).await;
+        require_sender_auth(&cx, project_id).await;
+
+        let missing = send(&ctx, &project_key, "no secret", None)
+            .await
+            .expect_err("send without secret is rejected");
+        assert_eq!(error_type(&missing), "SENDER_AUTH_FAILED");
+        let wrong = send(&ctx, &project_key, "wrong secret", Some("guess"))
+            .await
+            .expect_err("send with wrong secret is rejected");
+        assert_eq!(error_type(&wrong), "SENDER_AUTH_FAILED");
+
+        let response = send(&ctx, &project_key, "signed", Some(SECRET))
+            .await
+            .expect("send with secret");
+        assert_eq!(response["sender_verified"], Value::Bool(true));
+
+        let messages = inbox(&ctx, &project_key).await;
+        let subjects: Vec<&str> = messages
+            .iter()
+            .filter_map(|m| m["subject"].as_str())
+            .collect();
+        assert_eq!(subjects, ["signed"]);
+        assert_eq!(messages[0]["sender_verified"], Value::Bool(true));
+    });
+}
+
+#[test]
+fn projects_without_the_flag_accept_unsigned_mail() {
+    run_serial_async(|cx| async move {
+        let ctx = McpContext::new(cx.clone(), 1);
+        let project_key = format!("/tmp/sender-auth-optional-{}", unique_suffix());
+        setup_project(&ctx, &cx, &project_key).await;
+
+        let response = send(&ctx, &project_key, "unsigned", None)
+            .await
+            .expect("unsigned send");
+        assert!(response.get("sender_verified").is_none());
+        let messages = inbox(&ctx, &project_key).await;
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].get("sender_verified").is_none());
+    });
+}
@@ -111,6 +111,7 @@ fn plausible_field_value(field: &str, u: &mut Unstructured<'_>) -> Value {
         | "ack_required"
         | "broadcast"
         | "auto_contact_if_blocked"
+        | "ack_receipt"
         | "explain" => Value::Bool(bool::arbitrary(u).unwrap_or_default()),
         _ => Value::String(bounded_string(u).unwrap_or_default()),
     }
@@ -169,6 +170,7 @@ fn send_message_arguments(bytes: &[u8]) -> Value {
             "broadcast",
             "auto_contact_if_blocked",
             "sender_token",
+            "ack_receipt",
         ],
     )
 }
@@ -89,6 +89,9 @@ async fn setup_project_and_agent(ctx: &McpContext, project_key: &str, agent: &st
         project_key.to_string(),
         agent.to_string(),
         "open".to_string(),
+        None,
+        None,
+        None,
     )
     .await
     .expect("set_contact_policy");
@@ -118,6 +121,9 @@ fn test_invalid_timestamp_message() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("invalid since_ts should fail");
@@ -199,6 +205,10 @@ fn test_invalid_thread_id_message() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("invalid thread_id should fail");
@@ -274,6 +284,10 @@ fn test_numeric_thread_id_reserved_message() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("numeric thread_id should fail");
@@ -414,6 +428,9 @@ fn test_invalid_limit_message() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("limit=0 should fail");
@@ -466,6 +483,9 @@ fn test_invalid_limit_negative() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect_err("limit=-5 should fail");
@@ -507,6 +527,9 @@ fn test_limit_capped_at_1000() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("limit > 1000 should succeed with capping");
@@ -520,6 +543,124 @@ fn test_limit_capped_at_1000() {
     });
 }
 
+#[test]
+fn test_after_watermark_rejects_negative_and_since_ts_combination() {
+    run_serial_async(|cx| async move {
+        let project_key = format!("/tmp/watermark-{}", unique_suffix());
+        let ctx = McpContext::new(cx.clone(), 1);
+        setup_project_and_agent(&ctx, &project_key, "BlueLake").await;
+
+        for (since_ts, watermark) in [(None, -1), (Some("2026-01-01T00:00:00Z".to_string()), 0)] {
+            let err = fetch_inbox(
+                &ctx,
+                project_key.clone(),
+                "BlueLake".to_string(),
+                None,
+                since_ts,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(watermark),
+                None,
+                None,
+            )
+            .await
+            .expect_err("invalid after_watermark usage should fail");
+            let payload = error_object(&err);
+            assert_eq!(
+                payload.get("type").and_then(Value::as_str),
+                Some("INVALID_ARGUMENT")
+            );
+        }
+
+        let ok = fetch_inbox(
+            &ctx,
+            project_key.clone(),
+            "BlueLake".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(0),
+            None,
+            None,
+        )
+        .await
+        .expect("after_watermark=0 on an empty inbox");
+        let parsed: Value = serde_json::from_str(&ok).expect("parse result");
+        assert_eq!(parsed, serde_json::json!([]));
+    });
+}
+
+#[test]
+fn test_fetch_inbox_rejects_unknown_min_importance_and_order() {
+    run_serial_async(|cx| async move {
+        let project_key = format!("/tmp/inbox_order-{}", unique_suffix());
+        let ctx = McpContext::new(cx.clone(), 1);
+        setup_project_and_agent(&ctx, &project_key, "BlueLake").await;
+
+        for (min_importance, order, field) in [
+            (Some("low".to_string()), None, "min_importance"),
+            (None, Some("priority".to_string()), "order"),
+        ] {
+            let err = fetch_inbox(
+                &ctx,
+                project_key.clone(),
+                "BlueLake".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                min_importance,
+                order,
+            )
+            .await
+            .expect_err("unknown importance arguments should fail");
+            let payload = error_object(&err);
+            assert_eq!(
+                payload.get("type").and_then(Value::as_str),
+                Some("INVALID_ARGUMENT")
+            );
+            assert!(
+                payload
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .is_some_and(|message| message.starts_with(field)),
+                "{field}: {payload:?}"
+            );
+        }
+
+        let ok = fetch_inbox(
+            &ctx,
+            project_key.clone(),
+            "BlueLake".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some("URGENT".to_string()),
+            Some("hybrid".to_string()),
+        )
+        .await
+        .expect("valid importance arguments on an empty inbox");
+        let parsed: Value = serde_json::from_str(&ok).expect("parse result");
+        assert_eq!(parsed, serde_json::json!([]));
+    });
+}
+
 // -----------------------------------------------------------------------
 // T8.5: Subject truncation at 200 chars
 // -----------------------------------------------------------------------
@@ -553,6 +694,10 @@ fn test_subject_truncation_at_200() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("long subject should succeed with truncation");
@@ -602,6 +747,10 @@ fn test_subject_exactly_200_not_truncated() {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .await
         .expect("200-char subject should succeed without truncation");
@@ -638,6 +787,8 @@ fn test_empty_paths_validation_message() {
             None,
             None,
             None,
+            None,
+            None,
         )
         .await
         .expect_err("empty paths should fail");
@@ -680,6 +831,8 @@ fn test_invalid_reservation_glob_validation_message() {
             None,
             None,
             None,
+            None,
+            None,
         )
         .await
         .expect_err("invalid glob pattern should fail");
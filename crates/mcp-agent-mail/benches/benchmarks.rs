@@ -2998,6 +2998,7 @@ fn run_share_export_once(
     let context = mcp_agent_mail_share::SnapshotContext {
         snapshot_path,
         scope,
+        message_filter: None,
         scrub_summary,
         fts_enabled: finalize.fts_enabled,
     };
@@ -50,7 +50,7 @@ e2e_save_artifact "case_01_help.txt" "$HELP_OUT"
 
 EXPECTED_CMDS=(
     serve-http serve-stdio lint typecheck share archive guard
-    file_reservations acks list-acks migrate list-projects
+    file_reservations acks migrate list-projects
     clear-and-reset-everything config amctl am-run projects
     mail products docs doctor
 )
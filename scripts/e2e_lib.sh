@@ -2783,6 +2783,10 @@ _e2e_resolve_server_binary() {
 #
 # Example:
 #   e2e_start_server_with_logs "/tmp/db.sqlite3" "/tmp/storage" "main" "HTTP_PORT=8765"
+#
+# Under `am e2e run --record/--replay` (AM_E2E_FIXTURE_MODE set), E2E_SERVER_URL
+# points at the runner's recording proxy, or in replay mode at its fixture stub
+# and no server is started at all.
 
 e2e_start_server_with_logs() {
     local db_path="$1"
@@ -2796,6 +2800,14 @@ e2e_start_server_with_logs() {
     _E2E_SERVER_AUTH_MODE="none"
     mkdir -p "$(dirname "$_E2E_SERVER_LOG")"
 
+    if [ "${AM_E2E_FIXTURE_MODE:-}" = "replay" ] && [ -n "${AM_E2E_FIXTURE_URL:-}" ]; then
+        _E2E_SERVER_PID=""
+        export E2E_SERVER_URL="${AM_E2E_FIXTURE_URL}/mcp/"
+        export E2E_SERVER_PID=""
+        e2e_log "Replaying fixtures (${label}): ${AM_E2E_FIXTURE_URL}"
+        return 0
+    fi
+
     # Determine server binary
     local bin
     bin="$(_e2e_resolve_server_binary "mcp-agent-mail")" || return $?
@@ -2867,6 +2879,10 @@ e2e_start_server_with_logs() {
 
     # Export server URL for tests
     export E2E_SERVER_URL="http://127.0.0.1:${port}/mcp/"
+    if [ "${AM_E2E_FIXTURE_MODE:-}" = "record" ] && [ -n "${AM_E2E_FIXTURE_URL:-}" ]; then
+        export E2E_SERVER_URL="${AM_E2E_FIXTURE_URL}/mcp/"
+        e2e_log "Recording HTTP exchanges via ${AM_E2E_FIXTURE_URL}"
+    fi
     export E2E_SERVER_PID="${_E2E_SERVER_PID}"
 
     e2e_log "Server started: pid=${_E2E_SERVER_PID}"
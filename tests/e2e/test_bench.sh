@@ -157,6 +157,38 @@ else
     e2e_fail "bench --list --json --filter mail_send* failed"
 fi
 
+# ---------------------------------------------------------------------------
+# Case 2c: DB-backed scenarios seed once and compare against their baseline
+# ---------------------------------------------------------------------------
+e2e_case_banner "bench_scenarios_small_json"
+SCENARIO_BASELINE="${WORKDIR}/scenario_baseline.json"
+if run_bench_case "bench_scenarios_small_json" --quick --json --filter "scenario_*" --scale small \
+    --save-baseline "${SCENARIO_BASELINE}"; then
+    out_file="${E2E_ARTIFACT_DIR}/bench_scenarios_small_json/stdout.json"
+    scale="$(jq -r '.scale' "${out_file}")"
+    seeded_messages="$(jq -r '.scenario_seed_report.dataset.messages' "${out_file}")"
+    has_inbox="$(jq -r '.summary.benchmarks.scenario_inbox_fetch_small != null' "${out_file}")"
+    has_conflicts="$(jq -r '.summary.benchmarks.scenario_conflict_check_small != null' "${out_file}")"
+    has_projects="$(jq -r '.summary.benchmarks.scenario_project_list_small != null' "${out_file}")"
+
+    e2e_assert_eq "scenario run reports its scale" "small" "${scale}"
+    e2e_assert_eq "small scale seeds 1000 messages" "1000" "${seeded_messages}"
+    e2e_assert_eq "inbox fetch scenario ran" "true" "${has_inbox}"
+    e2e_assert_eq "conflict check scenario ran" "true" "${has_conflicts}"
+    e2e_assert_eq "project list scenario ran" "true" "${has_projects}"
+
+    if run_bench_case "bench_scenarios_small_baseline" --quick --json --filter "scenario_*" --scale small \
+        --baseline "${SCENARIO_BASELINE}" || [ "$(cat "${E2E_ARTIFACT_DIR}/bench_scenarios_small_baseline/exit_code.txt")" = "3" ]; then
+        out_file="${E2E_ARTIFACT_DIR}/bench_scenarios_small_baseline/stdout.json"
+        baseline_p95="$(jq -r '.summary.benchmarks.scenario_inbox_fetch_small.baseline_p95_ms != null' "${out_file}")"
+        e2e_assert_eq "scenario benchmarks compare against saved baseline" "true" "${baseline_p95}"
+    else
+        e2e_fail "bench scenario baseline comparison failed"
+    fi
+else
+    e2e_fail "bench --quick --json --filter scenario_* failed"
+fi
+
 # ---------------------------------------------------------------------------
 # Case 3: full --json run
 # ---------------------------------------------------------------------------
@@ -1,5 +1,6 @@
 #!/usr/bin/env bash
 # test_peer_addr.sh - E2E test suite for peer_addr + localhost bypass behavior
+# @tags: replayable
 #
 # Verifies:
 # - Local (loopback) peer addr bypasses HTTP bearer auth when
@@ -9,6 +10,13 @@
 # Artifacts:
 # - Server logs: tests/artifacts/peer_addr/<timestamp>/logs/server_peer_addr.log
 # - Per-case case directories: <case_id>/{request,response,headers,status,timing}.*
+#
+# Record/replay reference suite: every request body is fixed and the suite
+# only talks to the server over HTTP, so it runs unchanged against fixtures.
+#   am e2e run peer_addr --record tests/e2e/fixtures   # boots the real server
+#   am e2e run peer_addr --replay tests/e2e/fixtures   # no server, no build
+# The Authorization headers sent in cases 3 and 4 are redacted in the fixture;
+# replay matches on method, path, and body, so the redaction does not matter.
 
 E2E_SUITE="peer_addr"
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"